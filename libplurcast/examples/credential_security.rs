@@ -16,6 +16,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         storage: StorageBackend::Encrypted,
         path: "/tmp/plurcast-test-encrypted".to_string(),
         master_password: Some("my-strong-password-12345".to_string()),
+        commands: Default::default(),
     };
 
     let encrypted_manager = CredentialManager::new(encrypted_config)?;
@@ -37,6 +38,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         storage: StorageBackend::Keyring,
         path: "/tmp/plurcast-test".to_string(), // Not used for keyring
         master_password: None,
+        commands: Default::default(),
     };
 
     match CredentialManager::new(keyring_config) {
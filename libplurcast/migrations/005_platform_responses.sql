@@ -0,0 +1,14 @@
+-- Raw platform responses
+-- Migration 005: Keep the full API response alongside each post record
+
+-- raw_response holds the JSON returned by the platform when publishing:
+-- - Mastodon: the created status (URL, visibility, server timestamps)
+-- - Nostr: the signed event plus which relays accepted or rejected it
+-- - SSB: the signed feed message
+-- NULL for failed attempts and for records written before this migration.
+-- When content encryption is enabled the value is encrypted like posts.content,
+-- since most responses echo the post text.
+ALTER TABLE post_records ADD COLUMN raw_response TEXT;
+
+-- Note: raw_response is kept for debugging and for features that need more
+-- than the platform post ID (editing, deleting, analytics)
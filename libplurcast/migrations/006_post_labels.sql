@@ -0,0 +1,14 @@
+-- User-defined labels on posts
+-- Migration 006: Free-form labels ("campaign:launch", "series:tips") via a join table
+
+-- One row per label per post; a post can carry any number of labels
+CREATE TABLE IF NOT EXISTS post_labels (
+    post_id TEXT NOT NULL,                 -- FK to posts.id
+    label TEXT NOT NULL,                   -- Label text, stored as given (trimmed)
+    created_at INTEGER NOT NULL,           -- Unix timestamp when the label was added
+    PRIMARY KEY (post_id, label),
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+-- Index for filtering history by label
+CREATE INDEX IF NOT EXISTS idx_post_labels_label ON post_labels(label);
@@ -0,0 +1,5 @@
+-- Keyset pagination for history
+-- Migration 007: Composite index so cursor pages seek on (created_at, id) instead of scanning
+
+-- Matches ORDER BY created_at DESC, id DESC used by history queries
+CREATE INDEX IF NOT EXISTS idx_posts_created_at_id ON posts(created_at, id);
@@ -0,0 +1,22 @@
+-- Per-attempt publish timing
+-- Migration 008: One row per publish attempt per platform, including retries
+
+CREATE TABLE IF NOT EXISTS post_attempts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    post_id TEXT NOT NULL,                 -- FK to posts.id
+    platform TEXT NOT NULL,                -- Platform name (nostr, mastodon, ssb)
+    attempt INTEGER NOT NULL,              -- 1-based attempt number within one send
+    started_at_ms INTEGER NOT NULL,        -- Unix timestamp (milliseconds) when the attempt began
+    finished_at_ms INTEGER NOT NULL,       -- Unix timestamp (milliseconds) when the platform answered
+    duration_ms INTEGER NOT NULL,          -- finished_at_ms - started_at_ms
+    success INTEGER NOT NULL,              -- 1 if the platform accepted the post
+    error_message TEXT,                    -- Error from a failed attempt
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+-- Index for loading the attempts of a post
+CREATE INDEX IF NOT EXISTS idx_post_attempts_post_id ON post_attempts(post_id);
+
+-- Index for per-platform latency reports
+CREATE INDEX IF NOT EXISTS idx_post_attempts_platform_started
+    ON post_attempts(platform, started_at_ms);
@@ -0,0 +1,16 @@
+-- Multi-device sync
+-- Migration 009: Track when each post last changed so changesets can be merged
+
+-- NULL means "never changed since creation"; readers use COALESCE(updated_at, created_at)
+ALTER TABLE posts ADD COLUMN updated_at INTEGER;
+
+-- Bump updated_at on any change that doesn't set it explicitly (sync merges set it)
+CREATE TRIGGER IF NOT EXISTS posts_touch_updated_at
+AFTER UPDATE ON posts
+FOR EACH ROW WHEN NEW.updated_at IS OLD.updated_at
+BEGIN
+    UPDATE posts SET updated_at = CAST(strftime('%s', 'now') AS INTEGER) WHERE id = NEW.id;
+END;
+
+-- Index for exporting changes since a point in time
+CREATE INDEX IF NOT EXISTS idx_posts_updated_at ON posts(updated_at);
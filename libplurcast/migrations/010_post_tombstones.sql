@@ -0,0 +1,14 @@
+-- Soft delete
+-- Migration 010: Tombstones for cancelled/cleared posts, kept until purged
+
+-- The post row stays with status 'deleted'; this records when and why
+CREATE TABLE IF NOT EXISTS post_tombstones (
+    post_id TEXT PRIMARY KEY,              -- FK to posts.id
+    deleted_at INTEGER NOT NULL,           -- Unix timestamp of the deletion
+    previous_status TEXT NOT NULL,         -- Status before deletion (scheduled, failed, ...)
+    reason TEXT,                           -- Why it was deleted (cancelled, cleared, ...)
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+-- Index for purging tombstones older than a cutoff
+CREATE INDEX IF NOT EXISTS idx_post_tombstones_deleted_at ON post_tombstones(deleted_at);
@@ -0,0 +1,15 @@
+-- Relay acknowledgements
+-- Migration 011: How each Nostr relay answered a published event
+
+CREATE TABLE IF NOT EXISTS relay_results (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    post_record_id INTEGER NOT NULL,      -- FK to post_records.id
+    relay_url TEXT NOT NULL,
+    accepted INTEGER NOT NULL,            -- 1 if the relay sent OK true
+    message TEXT,                         -- Rejection reason or connection error
+    latency_ms INTEGER,                   -- Time until the relay answered
+    FOREIGN KEY (post_record_id) REFERENCES post_records(id) ON DELETE CASCADE
+);
+
+-- Index for loading the relays of a post record
+CREATE INDEX IF NOT EXISTS idx_relay_results_post_record ON relay_results(post_record_id);
@@ -0,0 +1,14 @@
+-- Recurring posts
+-- Migration 012: Templates for posts scheduled by a cron expression
+
+-- Each series keeps one scheduled occurrence in posts (linked through
+-- metadata.series_id); plur-send schedules the next one after each send
+CREATE TABLE IF NOT EXISTS post_series (
+    id TEXT PRIMARY KEY,                   -- UUID v4
+    cron TEXT NOT NULL,                    -- 5-field cron expression, local time
+    content TEXT NOT NULL,                 -- Encrypted like posts.content
+    metadata TEXT,                         -- JSON copied onto each occurrence
+    labels TEXT,                           -- JSON array of labels for each occurrence
+    paused INTEGER NOT NULL DEFAULT 0,
+    created_at INTEGER NOT NULL            -- Unix timestamp
+);
@@ -0,0 +1,6 @@
+-- Queue priorities
+-- Migration 013: Let urgent scheduled posts go out first
+
+-- Higher priorities are sent first when several posts are due in the same
+-- plur-send poll; ties keep scheduled_at order. Changed with plur-queue bump.
+ALTER TABLE posts ADD COLUMN priority INTEGER NOT NULL DEFAULT 0;
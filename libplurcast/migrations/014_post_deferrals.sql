@@ -0,0 +1,15 @@
+-- Deferred posts
+-- Migration 014: Remember why plur-send pushed a scheduled post back
+
+-- plur-send defers due posts that hit a daily/weekly cap or fall in a
+-- blackout window by moving scheduled_at; this keeps the reason and the
+-- original time so plur-queue can show them. Cleared when the post is
+-- rescheduled by hand.
+CREATE TABLE IF NOT EXISTS post_deferrals (
+    post_id TEXT PRIMARY KEY,              -- FK to posts.id
+    deferred_at INTEGER NOT NULL,          -- Unix timestamp of the (latest) deferral
+    deferred_until INTEGER NOT NULL,       -- New scheduled_at
+    original_scheduled_at INTEGER,         -- scheduled_at before the first deferral
+    reason TEXT NOT NULL,                  -- e.g. "daily cap of 20 reached on mastodon"
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
@@ -0,0 +1,10 @@
+-- Queue pause
+-- Migration 015: Freeze scheduled posting without cancelling anything
+
+-- A single row exists while the queue is paused (plur-queue pause) and is
+-- removed on resume. plur-send checks it on every poll.
+CREATE TABLE IF NOT EXISTS queue_pause (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    paused_at INTEGER NOT NULL,            -- Unix timestamp of the pause
+    reason TEXT                            -- Optional note (e.g. "incident")
+);
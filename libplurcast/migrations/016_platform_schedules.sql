@@ -0,0 +1,15 @@
+-- Per-platform schedules
+-- Migration 016: Send one post to each platform at its own time
+
+-- plur-post --schedule-for mastodon=9:00 --schedule-for nostr=12:00 stores a
+-- row per platform. The post's scheduled_at is the earliest unsent row;
+-- plur-send sends the platforms that are due, then moves the post on to the
+-- next unsent row until every platform has gone out.
+CREATE TABLE IF NOT EXISTS platform_schedules (
+    post_id TEXT NOT NULL,                 -- FK to posts.id
+    platform TEXT NOT NULL,                -- e.g. "mastodon"
+    scheduled_at INTEGER NOT NULL,         -- Unix timestamp
+    sent_at INTEGER,                       -- When plur-send sent it (NULL = not yet)
+    PRIMARY KEY (post_id, platform),
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
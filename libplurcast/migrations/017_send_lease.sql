@@ -0,0 +1,12 @@
+-- Send lease
+-- Migration 017: Only one plur-send processes the queue at a time
+
+-- A single row names the plur-send instance that owns the queue. The holder
+-- renews it on every poll; another instance may take it over only once it
+-- has gone unrenewed for longer than the lease lifetime.
+CREATE TABLE IF NOT EXISTS send_lease (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    holder TEXT NOT NULL,                  -- Instance ID (e.g. "host:pid:uuid")
+    acquired_at INTEGER NOT NULL,          -- Unix timestamp the holder took it
+    renewed_at INTEGER NOT NULL            -- Unix timestamp of the last renewal
+);
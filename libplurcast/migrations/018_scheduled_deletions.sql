@@ -0,0 +1,22 @@
+-- Scheduled deletions
+-- Migration 018: Delete expiring posts on platforms without native expiry
+
+-- plur-post --expires 2h sends Nostr notes with a NIP-40 expiration tag.
+-- Other platforms have no such tag, so each successful record of an
+-- expiring post gets a row here; plur-send deletes the platform post once
+-- delete_at has passed and records the outcome.
+CREATE TABLE IF NOT EXISTS scheduled_deletions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    post_id TEXT NOT NULL,                 -- FK to posts.id
+    platform TEXT NOT NULL,                -- e.g. "mastodon"
+    platform_post_id TEXT NOT NULL,        -- ID of the post on that platform
+    account_name TEXT NOT NULL DEFAULT 'default',
+    delete_at INTEGER NOT NULL,            -- Unix timestamp
+    deleted_at INTEGER,                    -- When it was deleted (NULL = not yet)
+    attempts INTEGER NOT NULL DEFAULT 0,   -- Failed deletion attempts so far
+    error TEXT,                            -- Error of the last failed attempt
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_scheduled_deletions_due
+    ON scheduled_deletions(deleted_at, delete_at);
@@ -0,0 +1,14 @@
+-- Audit log
+-- Migration 019: Durable record of sensitive actions
+
+-- One row per sensitive action, such as `plur-creds show --reveal`
+-- printing a stored secret. Rows are only ever appended.
+CREATE TABLE IF NOT EXISTS audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    event TEXT NOT NULL,                   -- e.g. "credential_revealed"
+    subject TEXT NOT NULL,                 -- What it concerned, e.g. "mastodon/work"
+    actor TEXT NOT NULL,                   -- OS user who did it
+    created_at INTEGER NOT NULL            -- Unix timestamp
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_created_at ON audit_log(created_at);
@@ -0,0 +1,12 @@
+-- Imported items
+-- Migration 020: Remember which source items plur-import has brought in
+
+-- One row per imported item, so re-running an import skips it even after
+-- its post has been edited, published or pruned (hence no foreign key).
+CREATE TABLE IF NOT EXISTS imported_items (
+    source TEXT NOT NULL,                  -- e.g. "rss:https://example.com/feed.xml"
+    item_id TEXT NOT NULL,                 -- ID within the source, e.g. the entry GUID
+    post_id TEXT NOT NULL,                 -- Post created for the item
+    imported_at INTEGER NOT NULL,          -- Unix timestamp
+    PRIMARY KEY (source, item_id)
+);
@@ -0,0 +1,11 @@
+-- Import cursors
+-- Migration 021: Where each plur-import source left off
+
+-- One row per import source, e.g. "ssb:default" or "rss:<feed url>". The
+-- position is importer-specific (a feed sequence, a status ID, a timestamp)
+-- and only moves past items that were imported or skipped without error.
+CREATE TABLE IF NOT EXISTS import_cursors (
+    source TEXT PRIMARY KEY,
+    position TEXT NOT NULL,                -- Last item handled
+    updated_at INTEGER NOT NULL            -- Unix timestamp
+);
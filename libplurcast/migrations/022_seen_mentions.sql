@@ -0,0 +1,13 @@
+-- Seen mentions
+-- Migration 022: Mentions plur-notify has already reported
+
+-- One row per mention or reply, so each is reported once however often the
+-- platforms are polled. post_id is the Plurcast post it replies to, if any
+-- (no foreign key, so pruning posts keeps the mentions seen).
+CREATE TABLE IF NOT EXISTS seen_mentions (
+    platform TEXT NOT NULL,
+    platform_post_id TEXT NOT NULL,        -- ID of the mentioning post
+    post_id TEXT,                          -- Plurcast post it replies to
+    seen_at INTEGER NOT NULL,              -- Unix timestamp
+    PRIMARY KEY (platform, platform_post_id)
+);
@@ -0,0 +1,21 @@
+-- Engagement
+-- Migration 023: Replies, boosts, likes and zaps of published posts
+
+-- One row per published copy, holding the counts plur-stats --refresh last
+-- read from the platform; refreshing overwrites them.
+CREATE TABLE IF NOT EXISTS engagement (
+    platform TEXT NOT NULL,
+    platform_post_id TEXT NOT NULL,        -- ID of the post on that platform
+    post_id TEXT NOT NULL,                 -- FK to posts.id
+    replies INTEGER NOT NULL DEFAULT 0,
+    boosts INTEGER NOT NULL DEFAULT 0,     -- Boosts or reposts
+    likes INTEGER NOT NULL DEFAULT 0,      -- Favourites or reactions
+    zaps INTEGER NOT NULL DEFAULT 0,       -- Nostr zap receipts
+    zap_sats INTEGER NOT NULL DEFAULT 0,   -- Sats zapped
+    fetched_at INTEGER NOT NULL,           -- Unix timestamp
+    PRIMARY KEY (platform, platform_post_id),
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+-- Index for loading the engagement of a post
+CREATE INDEX IF NOT EXISTS idx_engagement_post ON engagement(post_id);
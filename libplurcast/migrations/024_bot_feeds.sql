@@ -0,0 +1,23 @@
+-- Bot feeds
+-- Migration 024: Feed entries plur-bot has already handled
+
+-- One row per feed, added on its first check; entries already in the feed
+-- then are recorded as seen without being posted.
+CREATE TABLE IF NOT EXISTS bot_feeds (
+    feed TEXT PRIMARY KEY,                 -- Name from [[bot.feeds]]
+    first_checked_at INTEGER NOT NULL      -- Unix timestamp
+);
+
+-- One row per entry handled, so each is posted once however often the feed
+-- is checked. post_id is the Plurcast post made from it, NULL if it was
+-- skipped (no foreign key, so pruning posts keeps the entries seen).
+CREATE TABLE IF NOT EXISTS bot_entries (
+    feed TEXT NOT NULL,
+    entry_id TEXT NOT NULL,                -- RSS guid or Atom id
+    post_id TEXT,
+    seen_at INTEGER NOT NULL,              -- Unix timestamp
+    PRIMARY KEY (feed, entry_id)
+);
+
+-- Index for the time of a feed's last post (min_interval)
+CREATE INDEX IF NOT EXISTS idx_bot_entries_feed_seen ON bot_entries(feed, seen_at);
@@ -0,0 +1,18 @@
+-- Post checks
+-- Migration 025: Whether published copies could still be found
+
+-- One row per published copy plur-verify has looked up, holding the outcome
+-- of the latest check; checking again overwrites it.
+CREATE TABLE IF NOT EXISTS post_checks (
+    platform TEXT NOT NULL,
+    platform_post_id TEXT NOT NULL,        -- ID of the post on that platform
+    post_id TEXT NOT NULL,                 -- FK to posts.id
+    present INTEGER NOT NULL,              -- 1 if the platform still had it
+    checked_at INTEGER NOT NULL,           -- Unix timestamp
+    missing_since INTEGER,                 -- First check it was missing in a row
+    PRIMARY KEY (platform, platform_post_id),
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+-- Index for loading the checks of a post
+CREATE INDEX IF NOT EXISTS idx_post_checks_post ON post_checks(post_id);
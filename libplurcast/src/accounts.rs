@@ -128,6 +128,17 @@ impl AccountManager {
             .unwrap_or_else(|| "default".to_string())
     }
 
+    /// Resolve which account a post goes out under
+    ///
+    /// Returns `requested` when given (e.g. from `--account`), otherwise the
+    /// active account for the platform.
+    pub fn resolve_account(&self, platform: &str, requested: Option<&str>) -> String {
+        match requested {
+            Some(account) => account.to_string(),
+            None => self.get_active_account(platform),
+        }
+    }
+
     /// Set active account for platform
     ///
     /// Validates that the account exists before setting it as active.
@@ -0,0 +1,303 @@
+//! Posts scheduled relative to when another post goes out
+//!
+//! `plur-post --schedule "after:<uuid>+30m"` schedules a follow-up for 30
+//! minutes after the anchor post is actually sent. The anchor and delay are
+//! kept in the follow-up's metadata. Until the anchor is sent, the
+//! follow-up's `scheduled_at` is an estimate (the anchor's scheduled time
+//! plus the delay) and plur-send holds it; once the anchor is sent, plur-send
+//! moves the follow-up to the real send time plus the delay.
+
+use chrono::Utc;
+
+use crate::error::{PlurcastError, Result};
+use crate::types::{Post, PostStatus};
+use crate::Database;
+
+/// Prefix marking an anchored schedule in `--schedule`
+pub const ANCHOR_PREFIX: &str = "after:";
+
+/// Metadata key holding a follow-up's anchor
+pub const ANCHOR_METADATA_KEY: &str = "after";
+
+/// The post a follow-up waits for, and how long after it to go out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Anchor {
+    pub post_id: String,
+    /// Seconds after the anchor is sent
+    pub delay: i64,
+}
+
+/// Where a follow-up's anchor stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnchorState {
+    /// Sent (to at least one platform) at this time
+    Sent(i64),
+    /// Not sent yet
+    Waiting,
+    /// Cancelled or no longer in the database
+    Gone,
+}
+
+impl Anchor {
+    /// Parse the part of `--schedule` after `after:`, e.g. `"<uuid>+30m"`
+    ///
+    /// The delay is optional; without one the follow-up goes out on the
+    /// next poll after the anchor.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if the post ID isn't a UUID or the delay can't
+    /// be parsed.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (post_id, delay) = match spec.split_once('+') {
+            Some((post_id, delay)) => (post_id.trim(), Some(delay.trim())),
+            None => (spec.trim(), None),
+        };
+
+        if uuid::Uuid::parse_str(post_id).is_err() {
+            return Err(PlurcastError::InvalidInput(format!(
+                "Invalid anchor '{}': expected after:<post-id>+<duration>, e.g. after:<uuid>+30m",
+                spec
+            )));
+        }
+
+        let delay = match delay {
+            Some(delay) => humantime::parse_duration(delay)
+                .map_err(|e| {
+                    PlurcastError::InvalidInput(format!("Invalid anchor delay '{}': {}", delay, e))
+                })?
+                .as_secs() as i64,
+            None => 0,
+        };
+
+        Ok(Self {
+            post_id: post_id.to_string(),
+            delay,
+        })
+    }
+}
+
+/// Anchor of a follow-up post, if it has one
+pub fn post_anchor(post: &Post) -> Option<Anchor> {
+    let meta = post
+        .metadata
+        .as_ref()
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())?;
+    let anchor = meta.get(ANCHOR_METADATA_KEY)?;
+
+    Some(Anchor {
+        post_id: anchor.get("post")?.as_str()?.to_string(),
+        delay: anchor.get("delay").and_then(|d| d.as_i64()).unwrap_or(0),
+    })
+}
+
+/// Post metadata with an anchor added
+pub fn with_anchor(metadata: Option<&str>, anchor: &Anchor) -> String {
+    let mut meta = metadata
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .filter(|m| m.is_object())
+        .unwrap_or_else(|| serde_json::json!({}));
+    meta[ANCHOR_METADATA_KEY] = serde_json::json!({
+        "post": anchor.post_id,
+        "delay": anchor.delay,
+    });
+    meta.to_string()
+}
+
+/// Where an anchor post stands
+///
+/// A post counts as sent from its first successful platform record, so a
+/// post that failed on one platform still releases its follow-ups.
+///
+/// # Errors
+///
+/// Returns an error if a database query fails.
+pub async fn anchor_state(db: &Database, anchor_id: &str) -> Result<AnchorState> {
+    let sent_at = db
+        .get_post_records(anchor_id)
+        .await?
+        .into_iter()
+        .filter(|record| record.success)
+        .filter_map(|record| record.posted_at)
+        .min();
+    if let Some(sent_at) = sent_at {
+        return Ok(AnchorState::Sent(sent_at));
+    }
+
+    match db.get_post(anchor_id).await? {
+        Some(post) if post.status != PostStatus::Deleted => Ok(AnchorState::Waiting),
+        _ => Ok(AnchorState::Gone),
+    }
+}
+
+/// First estimate of when a follow-up goes out
+///
+/// The anchor's send time plus the delay if it has been sent, otherwise its
+/// scheduled time (or now, if it has none) plus the delay.
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if the anchor doesn't exist or was cancelled, or
+/// an error if a database query fails.
+pub async fn estimate(db: &Database, anchor: &Anchor) -> Result<i64> {
+    match anchor_state(db, &anchor.post_id).await? {
+        AnchorState::Sent(sent_at) => Ok(sent_at + anchor.delay),
+        AnchorState::Gone => Err(PlurcastError::InvalidInput(format!(
+            "Anchor post not found: {}",
+            anchor.post_id
+        ))),
+        AnchorState::Waiting => {
+            let anchor_post = db.get_post(&anchor.post_id).await?;
+            let base = anchor_post
+                .and_then(|post| post.scheduled_at)
+                .unwrap_or_else(|| Utc::now().timestamp());
+            Ok(base.max(Utc::now().timestamp()) + anchor.delay)
+        }
+    }
+}
+
+/// Move follow-ups of a sent post to its send time plus their delays
+///
+/// Returns the follow-ups that were rescheduled.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn resolve_followers(db: &Database, anchor_id: &str, sent_at: i64) -> Result<Vec<Post>> {
+    let mut resolved = Vec::new();
+
+    for mut post in db.get_scheduled_posts().await? {
+        let anchor = match post_anchor(&post) {
+            Some(anchor) if anchor.post_id == anchor_id => anchor,
+            _ => continue,
+        };
+
+        let at = sent_at + anchor.delay;
+        if post.scheduled_at != Some(at) {
+            db.update_post_schedule(&post.id, Some(at)).await?;
+            post.scheduled_at = Some(at);
+            resolved.push(post);
+        }
+    }
+
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PostRecord;
+    use tempfile::TempDir;
+
+    const ANCHOR_ID: &str = "0b5e1f0c-6f1e-4c83-9d3e-8a2b7d4c1e9f";
+
+    #[test]
+    fn test_parse_anchor() {
+        let anchor = Anchor::parse(&format!("{}+30m", ANCHOR_ID)).unwrap();
+        assert_eq!(anchor.post_id, ANCHOR_ID);
+        assert_eq!(anchor.delay, 1800);
+
+        assert_eq!(Anchor::parse(ANCHOR_ID).unwrap().delay, 0);
+        assert!(Anchor::parse("not-a-uuid+30m").is_err());
+        assert!(Anchor::parse(&format!("{}+soon", ANCHOR_ID)).is_err());
+    }
+
+    #[test]
+    fn test_anchor_round_trips_through_metadata() {
+        let anchor = Anchor::parse(&format!("{}+1h", ANCHOR_ID)).unwrap();
+        let post = Post {
+            id: "follow-up".to_string(),
+            content: "Follow-up".to_string(),
+            created_at: 0,
+            scheduled_at: Some(0),
+            status: PostStatus::Scheduled,
+            metadata: Some(with_anchor(Some(r#"{"platforms":["nostr"]}"#), &anchor)),
+        };
+
+        assert_eq!(post_anchor(&post), Some(anchor));
+        assert!(post.metadata.unwrap().contains("\"platforms\""));
+    }
+
+    #[tokio::test]
+    async fn test_followers_wait_for_and_follow_the_anchor() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("anchor.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+        let now = Utc::now().timestamp();
+
+        let anchor_post = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Announcement".to_string(),
+            created_at: now,
+            scheduled_at: Some(now + 3600),
+            status: PostStatus::Scheduled,
+            metadata: None,
+        };
+        db.create_post(&anchor_post).await.unwrap();
+
+        let anchor = Anchor {
+            post_id: anchor_post.id.clone(),
+            delay: 1800,
+        };
+        let follow_up = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Follow-up".to_string(),
+            created_at: now,
+            scheduled_at: Some(estimate(&db, &anchor).await.unwrap()),
+            status: PostStatus::Scheduled,
+            metadata: Some(with_anchor(None, &anchor)),
+        };
+        assert_eq!(follow_up.scheduled_at, Some(now + 3600 + 1800));
+        db.create_post(&follow_up).await.unwrap();
+
+        assert_eq!(
+            anchor_state(&db, &anchor_post.id).await.unwrap(),
+            AnchorState::Waiting
+        );
+
+        // The anchor goes out late
+        let sent_at = now + 7200;
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: anchor_post.id.clone(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("note1".to_string()),
+            posted_at: Some(sent_at),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            anchor_state(&db, &anchor_post.id).await.unwrap(),
+            AnchorState::Sent(sent_at)
+        );
+
+        let resolved = resolve_followers(&db, &anchor_post.id, sent_at)
+            .await
+            .unwrap();
+        assert_eq!(resolved.len(), 1);
+        let stored = db.get_post(&follow_up.id).await.unwrap().unwrap();
+        assert_eq!(stored.scheduled_at, Some(sent_at + 1800));
+
+        // Resolving again changes nothing
+        assert!(resolve_followers(&db, &anchor_post.id, sent_at)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Unknown anchors are gone
+        let other = uuid::Uuid::new_v4().to_string();
+        assert_eq!(anchor_state(&db, &other).await.unwrap(), AnchorState::Gone);
+        assert!(estimate(
+            &db,
+            &Anchor {
+                post_id: other,
+                delay: 0
+            }
+        )
+        .await
+        .is_err());
+    }
+}
@@ -0,0 +1,303 @@
+//! Posting blackout windows
+//!
+//! Quiet hours configured under `[[scheduling.blackouts]]`. Each window is a
+//! local time range, a set of days, or both, optionally limited to some
+//! platforms. plur-send defers due posts that would go out during a
+//! blackout to the next time none of their platforms is blacked out.
+
+use crate::config::BlackoutWindow;
+use crate::error::{ConfigError, Result};
+use chrono::{DateTime, Datelike, Duration, Local, TimeZone, Timelike};
+
+/// How far ahead to look for an allowed time before giving up
+const SEARCH_LIMIT_MINUTES: i64 = 8 * 24 * 60; // 8 days
+
+/// A parsed blackout window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blackout {
+    /// Start and end as minutes after midnight; `None` for the whole day
+    hours: Option<(u32, u32)>,
+    /// Bitmask of days (bit 0 = Monday); 0 for every day
+    days: u8,
+    /// Platforms the window applies to; empty for all
+    platforms: Vec<String>,
+}
+
+impl Blackout {
+    /// Parse a configured window
+    ///
+    /// # Errors
+    ///
+    /// Returns a config error for malformed hours or day names, an empty
+    /// time range, or a window with neither hours nor days.
+    pub fn parse(window: &BlackoutWindow) -> Result<Self> {
+        let hours = window.hours.as_deref().map(parse_hours).transpose()?;
+
+        let mut days = 0u8;
+        for day in &window.days {
+            days |= parse_days(day)?;
+        }
+
+        if hours.is_none() && days == 0 {
+            return Err(invalid("blackout needs hours, days or both".to_string()));
+        }
+
+        Ok(Self {
+            hours,
+            days,
+            platforms: window.platforms.clone(),
+        })
+    }
+
+    /// Whether the window applies to a platform
+    pub fn applies_to(&self, platform: &str) -> bool {
+        self.platforms.is_empty() || self.platforms.iter().any(|p| p == platform)
+    }
+
+    /// Whether a moment falls inside the window
+    ///
+    /// Days are matched against the moment's own weekday, so an overnight
+    /// window on `["fri"]` covers Friday's early morning and late evening.
+    pub fn contains<Tz: TimeZone>(&self, at: &DateTime<Tz>) -> bool {
+        let day_bit = 1u8 << at.weekday().num_days_from_monday();
+        if self.days != 0 && self.days & day_bit == 0 {
+            return false;
+        }
+
+        match self.hours {
+            None => true,
+            Some((start, end)) => {
+                let minute = at.hour() * 60 + at.minute();
+                if start < end {
+                    minute >= start && minute < end
+                } else {
+                    minute >= start || minute < end
+                }
+            }
+        }
+    }
+}
+
+/// Parse every configured window
+///
+/// # Errors
+///
+/// Returns the first window's parse error.
+pub fn parse_all(windows: &[BlackoutWindow]) -> Result<Vec<Blackout>> {
+    windows.iter().map(Blackout::parse).collect()
+}
+
+/// Platforms blacked out at a moment, out of `platforms`
+pub fn blocked_platforms<Tz: TimeZone>(
+    blackouts: &[Blackout],
+    platforms: &[String],
+    at: &DateTime<Tz>,
+) -> Vec<String> {
+    platforms
+        .iter()
+        .filter(|platform| {
+            blackouts
+                .iter()
+                .any(|b| b.applies_to(platform) && b.contains(at))
+        })
+        .cloned()
+        .collect()
+}
+
+/// First moment at or after `after` when none of `platforms` is blacked out
+///
+/// Returns `None` if there is no such moment within 8 days, which means the
+/// windows cover the whole week.
+pub fn next_allowed<Tz: TimeZone>(
+    blackouts: &[Blackout],
+    platforms: &[String],
+    after: &DateTime<Tz>,
+) -> Option<DateTime<Tz>> {
+    if blocked_platforms(blackouts, platforms, after).is_empty() {
+        return Some(after.clone());
+    }
+
+    // Windows have minute resolution, so check each following minute
+    let start = after.clone() - Duration::seconds(i64::from(after.second()));
+    (1..=SEARCH_LIMIT_MINUTES)
+        .map(|minutes| start.clone() + Duration::minutes(minutes))
+        .find(|at| blocked_platforms(blackouts, platforms, at).is_empty())
+}
+
+/// [`next_allowed`] for a Unix timestamp, in local time
+pub fn next_allowed_time(blackouts: &[Blackout], platforms: &[String], after: i64) -> Option<i64> {
+    let after = Local.timestamp_opt(after, 0).single()?;
+    next_allowed(blackouts, platforms, &after).map(|at| at.timestamp())
+}
+
+fn invalid(message: String) -> crate::PlurcastError {
+    ConfigError::InvalidValue(message).into()
+}
+
+/// Parse "HH:MM-HH:MM" into minutes after midnight
+fn parse_hours(hours: &str) -> Result<(u32, u32)> {
+    let parse_time = |time: &str| -> Option<u32> {
+        let (h, m) = time.trim().split_once(':')?;
+        let (h, m) = (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?);
+        // 24:00 is allowed as the end of the day
+        if (h < 24 && m < 60) || (h == 24 && m == 0) {
+            Some(h * 60 + m)
+        } else {
+            None
+        }
+    };
+
+    let range = hours
+        .split_once('-')
+        .and_then(|(start, end)| Some((parse_time(start)?, parse_time(end)?)));
+    match range {
+        Some((start, end)) if start == end => Err(invalid(format!(
+            "blackout hours '{}' are an empty range",
+            hours
+        ))),
+        Some((start, end)) if start < 24 * 60 => Ok((start, end % (24 * 60))),
+        _ => Err(invalid(format!(
+            "blackout hours '{}' must look like 23:00-07:00",
+            hours
+        ))),
+    }
+}
+
+/// Parse a day name or group into a bitmask
+fn parse_days(day: &str) -> Result<u8> {
+    let mask = match day.trim().to_ascii_lowercase().as_str() {
+        "mon" | "monday" => 1 << 0,
+        "tue" | "tuesday" => 1 << 1,
+        "wed" | "wednesday" => 1 << 2,
+        "thu" | "thursday" => 1 << 3,
+        "fri" | "friday" => 1 << 4,
+        "sat" | "saturday" => 1 << 5,
+        "sun" | "sunday" => 1 << 6,
+        "weekdays" => 0b0011111,
+        "weekends" => 0b1100000,
+        _ => {
+            return Err(invalid(format!(
+                "unknown blackout day '{}' (use mon..sun, weekdays or weekends)",
+                day
+            )))
+        }
+    };
+    Ok(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn window(hours: Option<&str>, days: &[&str], platforms: &[&str]) -> Blackout {
+        Blackout::parse(&BlackoutWindow {
+            hours: hours.map(String::from),
+            days: days.iter().map(|d| d.to_string()).collect(),
+            platforms: platforms.iter().map(|p| p.to_string()).collect(),
+        })
+        .unwrap()
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn platforms(names: &[&str]) -> Vec<String> {
+        names.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_overnight_window() {
+        let quiet = window(Some("23:00-07:00"), &[], &[]);
+
+        // 2025-01-15 is a Wednesday
+        assert!(quiet.contains(&utc("2025-01-15T23:30:00Z")));
+        assert!(quiet.contains(&utc("2025-01-15T06:59:00Z")));
+        assert!(!quiet.contains(&utc("2025-01-15T07:00:00Z")));
+        assert!(!quiet.contains(&utc("2025-01-15T12:00:00Z")));
+    }
+
+    #[test]
+    fn test_weekend_window() {
+        let weekends = window(None, &["weekends"], &[]);
+
+        assert!(weekends.contains(&utc("2025-01-18T12:00:00Z"))); // Saturday
+        assert!(weekends.contains(&utc("2025-01-19T00:00:00Z"))); // Sunday
+        assert!(!weekends.contains(&utc("2025-01-17T23:59:00Z"))); // Friday
+    }
+
+    #[test]
+    fn test_next_allowed_skips_window() {
+        let blackouts = vec![window(Some("23:00-07:00"), &[], &[])];
+        let all = platforms(&["nostr"]);
+
+        let next = next_allowed(&blackouts, &all, &utc("2025-01-15T23:30:20Z")).unwrap();
+        assert_eq!(next, utc("2025-01-16T07:00:00Z"));
+
+        // Already allowed: unchanged
+        let now = utc("2025-01-15T12:00:20Z");
+        assert_eq!(next_allowed(&blackouts, &all, &now), Some(now));
+    }
+
+    #[test]
+    fn test_per_platform_windows() {
+        let blackouts = vec![
+            window(Some("18:00-20:00"), &[], &["mastodon"]),
+            window(None, &["weekends"], &["nostr"]),
+        ];
+        let friday_evening = utc("2025-01-17T19:00:00Z");
+
+        assert_eq!(
+            blocked_platforms(
+                &blackouts,
+                &platforms(&["nostr", "mastodon"]),
+                &friday_evening
+            ),
+            platforms(&["mastodon"])
+        );
+
+        // Both platforms: Friday 20:00 is free for both
+        let next = next_allowed(
+            &blackouts,
+            &platforms(&["nostr", "mastodon"]),
+            &friday_evening,
+        )
+        .unwrap();
+        assert_eq!(next, utc("2025-01-17T20:00:00Z"));
+
+        // Saturday: nostr waits for Monday
+        let next = next_allowed(
+            &blackouts,
+            &platforms(&["nostr"]),
+            &utc("2025-01-18T10:00:00Z"),
+        )
+        .unwrap();
+        assert_eq!(next, utc("2025-01-20T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_always_blacked_out() {
+        let blackouts = vec![window(None, &["weekdays", "weekends"], &[])];
+        let now = utc("2025-01-15T12:00:00Z");
+        assert_eq!(next_allowed(&blackouts, &platforms(&["nostr"]), &now), None);
+    }
+
+    #[test]
+    fn test_invalid_windows() {
+        let parse = |hours: Option<&str>, days: &[&str]| {
+            Blackout::parse(&BlackoutWindow {
+                hours: hours.map(String::from),
+                days: days.iter().map(|d| d.to_string()).collect(),
+                platforms: vec![],
+            })
+        };
+
+        assert!(parse(Some("23-07"), &[]).is_err());
+        assert!(parse(Some("25:00-07:00"), &[]).is_err());
+        assert!(parse(Some("07:00-07:00"), &[]).is_err());
+        assert!(parse(None, &["someday"]).is_err());
+        assert!(parse(None, &[]).is_err());
+        assert!(parse(Some("22:00-24:00"), &[]).is_ok());
+    }
+}
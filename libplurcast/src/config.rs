@@ -35,6 +35,14 @@ pub struct Config {
     /// Scheduling configuration (optional)
     #[serde(default)]
     pub scheduling: Option<SchedulingConfig>,
+
+    /// History retention policy (optional)
+    #[serde(default)]
+    pub retention: Option<RetentionConfig>,
+
+    /// Feeds plur-bot posts from (optional)
+    #[serde(default)]
+    pub bot: Option<BotConfig>,
 }
 
 /// Database configuration
@@ -43,6 +51,15 @@ pub struct DatabaseConfig {
     /// Path to the SQLite database file
     /// Supports ~ expansion and environment variable override via PLURCAST_DB_PATH
     pub path: String,
+
+    /// Encrypt post content at rest
+    ///
+    /// When enabled, post content is encrypted before it is written to the
+    /// database. The key is generated on first use and kept in the credential
+    /// store, not alongside the database. Existing plaintext rows remain
+    /// readable; new writes are encrypted.
+    #[serde(default)]
+    pub encrypt_content: bool,
 }
 
 /// Nostr platform configuration
@@ -65,6 +82,11 @@ pub struct NostrConfig {
     /// Recommended: 20-25 (takes 1-5 seconds), maximum: 64
     #[serde(default)]
     pub default_pow_difficulty: Option<u8>,
+
+    /// NIP-96 media server for image attachments (e.g. "https://nostr.build")
+    /// Without one, posts with attachments can't be sent to Nostr
+    #[serde(default)]
+    pub media_server: Option<String>,
 }
 
 impl NostrConfig {
@@ -139,6 +161,10 @@ pub struct DefaultsConfig {
     /// Default platforms to post to when not specified
     #[serde(default = "default_platforms")]
     pub platforms: Vec<String>,
+
+    /// Language posts are written in (ISO 639-1), unless plur-post --lang says otherwise
+    #[serde(default)]
+    pub language: Option<String>,
 }
 
 // Default value functions for serde
@@ -157,6 +183,26 @@ fn default_nostr_relays() -> Vec<String> {
     ]
 }
 
+impl Default for Config {
+    /// The default database and defaults, with every optional section unset
+    fn default() -> Self {
+        Self {
+            database: DatabaseConfig {
+                path: "~/.local/share/plurcast/posts.db".to_string(),
+                encrypt_content: false,
+            },
+            credentials: None,
+            nostr: None,
+            mastodon: None,
+            ssb: None,
+            defaults: DefaultsConfig::default(),
+            scheduling: None,
+            retention: None,
+            bot: None,
+        }
+    }
+}
+
 fn default_platforms() -> Vec<String> {
     vec!["nostr".to_string()]
 }
@@ -165,6 +211,7 @@ impl Default for DefaultsConfig {
     fn default() -> Self {
         Self {
             platforms: default_platforms(),
+            language: None,
         }
     }
 }
@@ -199,9 +246,70 @@ pub struct SchedulingConfig {
     #[serde(default)]
     pub max_retries_per_iteration: Option<u32>,
 
+    /// Default jitter (in seconds) for scheduled sends
+    /// Each post goes out somewhere within this many seconds either side of
+    /// its scheduled time; a post's own `--jitter` takes precedence
+    #[serde(default)]
+    pub jitter: Option<u64>,
+
+    /// What to do with posts that fell due while plur-send was down:
+    /// "immediate" (send them on startup, the default), "manual" (mark them
+    /// missed for rescheduling by hand) or a duration such as "6h" (send
+    /// only those less late than that, mark the rest missed)
+    #[serde(default)]
+    pub catch_up: Option<String>,
+
+    /// How many due posts plur-send sends at once (default 1); posts that
+    /// share a platform still go one at a time
+    #[serde(default)]
+    pub workers: Option<usize>,
+
     /// Platform-specific rate limits
     #[serde(default)]
     pub rate_limits: std::collections::HashMap<String, RateLimitConfig>,
+
+    /// Quiet hours during which due posts are deferred
+    #[serde(default)]
+    pub blackouts: Vec<BlackoutWindow>,
+
+    /// Platform-specific daily/weekly post caps
+    #[serde(default)]
+    pub caps: std::collections::HashMap<String, PostCapConfig>,
+}
+
+/// Daily and weekly post caps for a platform
+///
+/// Days and weeks are calendar periods in local time (weeks start on
+/// Monday). Posts over a cap are deferred to the start of the next period.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PostCapConfig {
+    /// Maximum number of posts per day
+    #[serde(default)]
+    pub posts_per_day: Option<u32>,
+
+    /// Maximum number of posts per week
+    #[serde(default)]
+    pub posts_per_week: Option<u32>,
+}
+
+/// A posting blackout window (`[[scheduling.blackouts]]`)
+///
+/// Set `hours`, `days` or both; see [`crate::blackout`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlackoutWindow {
+    /// Local time range like "23:00-07:00" (may wrap past midnight)
+    /// The whole day when unset
+    #[serde(default)]
+    pub hours: Option<String>,
+
+    /// Days the window applies to: "mon".."sun", "weekdays", "weekends"
+    /// Every day when empty
+    #[serde(default)]
+    pub days: Vec<String>,
+
+    /// Platforms the window applies to; all platforms when empty
+    #[serde(default)]
+    pub platforms: Vec<String>,
 }
 
 /// Rate limit configuration for a platform
@@ -211,6 +319,125 @@ pub struct RateLimitConfig {
     pub posts_per_hour: u32,
 }
 
+/// History retention policy
+///
+/// Controls pruning of old, successfully published posts from the local
+/// database. Drafts and scheduled posts are never pruned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    /// Prune successfully published posts older than this many days
+    #[serde(default = "default_retention_max_age_days")]
+    pub max_age_days: u32,
+
+    /// Keep posts that failed on any platform, regardless of age
+    #[serde(default = "default_retention_keep_failures")]
+    pub keep_failures: bool,
+
+    /// How often (in seconds) plur-send applies the policy
+    /// When unset, pruning only happens via `plur-history prune`
+    #[serde(default)]
+    pub prune_interval: Option<u64>,
+}
+
+fn default_retention_max_age_days() -> u32 {
+    180 // About six months
+}
+
+fn default_retention_keep_failures() -> bool {
+    true
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            max_age_days: default_retention_max_age_days(),
+            keep_failures: default_retention_keep_failures(),
+            prune_interval: None,
+        }
+    }
+}
+
+/// Feeds plur-bot bridges to the platforms
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BotConfig {
+    /// How often (in seconds) plur-bot checks the feeds
+    #[serde(default = "default_bot_poll_interval")]
+    pub poll_interval: u64,
+
+    /// Feeds to post new entries from
+    #[serde(default)]
+    pub feeds: Vec<FeedConfig>,
+}
+
+/// An RSS or Atom feed plur-bot posts from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedConfig {
+    /// Name the feed's seen entries are kept under; unique among the feeds
+    pub name: String,
+
+    /// `http(s)://` URL or local path of the feed
+    pub url: String,
+
+    /// Post text, with `{{title}}`, `{{link}}`, `{{summary}}` and
+    /// `{{published}}` filled from the entry; `{{title}}\n\n{{link}}` when
+    /// unset
+    #[serde(default)]
+    pub template: Option<String>,
+
+    /// Platforms to post to; `[defaults] platforms` when empty
+    #[serde(default)]
+    pub platforms: Vec<String>,
+
+    /// Account to post as (default: the active account)
+    #[serde(default)]
+    pub account: Option<String>,
+
+    /// Labels added to every post from the feed
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Most entries posted per check; the rest wait for later checks
+    #[serde(default = "default_feed_max_per_check")]
+    pub max_per_check: u32,
+
+    /// Least time (in seconds) between two posts from the feed
+    #[serde(default)]
+    pub min_interval: Option<u64>,
+}
+
+impl FeedConfig {
+    /// The feed's URL, or its path with shell variables expanded
+    pub fn location(&self) -> Result<String> {
+        if self.url.starts_with("http://") || self.url.starts_with("https://") {
+            return Ok(self.url.clone());
+        }
+        let expanded = shellexpand::full(&self.url).map_err(|e| {
+            ConfigError::InvalidValue(format!("Failed to expand feed path {}: {}", self.url, e))
+        })?;
+        Ok(expanded.into_owned())
+    }
+}
+
+fn default_bot_poll_interval() -> u64 {
+    900 // Check every 15 minutes
+}
+
+fn default_feed_max_per_check() -> u32 {
+    1
+}
+
+impl Default for BotConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: default_bot_poll_interval(),
+            feeds: Vec::new(),
+        }
+    }
+}
+
+/// Most due posts plur-send may send at once (`scheduling.workers`)
+pub const MAX_SEND_WORKERS: usize = 32;
+
 // Default values for scheduling configuration
 fn default_poll_interval() -> u64 {
     60 // Poll every 60 seconds
@@ -256,7 +483,12 @@ impl Default for SchedulingConfig {
             startup_delay: None,                 // No startup delay by default
             inter_retry_delay: Some(5),          // 5 seconds between retries
             max_retries_per_iteration: Some(10), // Max 10 retries per poll
+            jitter: None,
+            catch_up: None,
+            workers: None,
             rate_limits,
+            blackouts: Vec::new(),
+            caps: HashMap::new(),
         }
     }
 }
@@ -335,6 +567,15 @@ impl Config {
                     )
                     .into());
                 }
+                if let Some(server) = &nostr.media_server {
+                    if !server.starts_with("https://") && !server.starts_with("http://") {
+                        return Err(ConfigError::InvalidValue(format!(
+                            "Nostr media_server must be an http(s) URL, got '{}'",
+                            server
+                        ))
+                        .into());
+                    }
+                }
             }
         }
 
@@ -367,11 +608,75 @@ impl Config {
             // Note: pubs list can be empty (local-only mode)
         }
 
+        if let Some(language) = &self.defaults.language {
+            crate::language::parse(language).map_err(|_| {
+                ConfigError::InvalidValue(format!(
+                    "defaults.language must be a two-letter ISO 639-1 code, got '{}'",
+                    language
+                ))
+            })?;
+        }
+
         // Validate credential configuration if present
         if let Some(credentials) = &self.credentials {
             credentials.validate()?;
         }
 
+        // Validate blackout windows, catch-up policy and send workers
+        if let Some(scheduling) = &self.scheduling {
+            crate::blackout::parse_all(&scheduling.blackouts)?;
+            if let Some(catch_up) = &scheduling.catch_up {
+                crate::scheduling::CatchUp::parse(catch_up)?;
+            }
+            if let Some(workers) = scheduling.workers {
+                if !(1..=MAX_SEND_WORKERS).contains(&workers) {
+                    return Err(ConfigError::InvalidValue(format!(
+                        "scheduling.workers must be between 1 and {}, got {}",
+                        MAX_SEND_WORKERS, workers
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        // Validate bot feeds
+        if let Some(bot) = &self.bot {
+            let mut names = std::collections::HashSet::new();
+            for feed in &bot.feeds {
+                if feed.name.trim().is_empty() || feed.url.trim().is_empty() {
+                    return Err(ConfigError::MissingField(
+                        "Every bot.feeds entry needs a name and a url".to_string(),
+                    )
+                    .into());
+                }
+                if !names.insert(feed.name.as_str()) {
+                    return Err(ConfigError::InvalidValue(format!(
+                        "bot.feeds name '{}' is used more than once",
+                        feed.name
+                    ))
+                    .into());
+                }
+                if feed.max_per_check == 0 {
+                    return Err(ConfigError::InvalidValue(format!(
+                        "bot.feeds '{}': max_per_check must be at least 1",
+                        feed.name
+                    ))
+                    .into());
+                }
+                if let Some(platform) = feed
+                    .platforms
+                    .iter()
+                    .find(|p| !["nostr", "mastodon", "ssb"].contains(&p.as_str()))
+                {
+                    return Err(ConfigError::InvalidValue(format!(
+                        "bot.feeds '{}': unknown platform '{}' (expected nostr, mastodon or ssb)",
+                        feed.name, platform
+                    ))
+                    .into());
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -413,6 +718,8 @@ impl Config {
 # Path to the SQLite database file
 # Supports ~ expansion and environment variable override via PLURCAST_DB_PATH
 path = "~/.local/share/plurcast/posts.db"
+# Encrypt post content at rest (key is kept in the credential store)
+# encrypt_content = false
 
 # Credential storage configuration
 [credentials]
@@ -429,6 +736,11 @@ path = "~/.config/plurcast/credentials"
 # Note: Master password for encrypted storage can be set via PLURCAST_MASTER_PASSWORD environment variable
 # or will be prompted interactively when needed
 
+# Credentials printed by a command instead of stored (e.g. a password manager
+# or hardware wallet CLI), keyed platform.account.key
+# [credentials.commands]
+# "nostr.default.private_key" = "pass show nostr/key"
+
 # Nostr platform configuration
 [nostr]
 # Enable or disable Nostr posting
@@ -468,6 +780,29 @@ relays = [
 [defaults]
 # Default platforms to post to when not specified via --platform flag
 platforms = ["nostr"]
+# Language your posts are written in (ISO 639-1), unless --lang is given
+# language = "en"
+
+# History retention (disabled by default)
+# Uncomment to prune old, successfully published posts
+# Run `plur-history prune --dry-run` to preview what would be removed
+# [retention]
+# max_age_days = 180       # prune published posts older than this
+# keep_failures = true     # never prune posts that failed on any platform
+# prune_interval = 86400   # let plur-send apply the policy daily (seconds)
+
+# RSS/Atom feeds for plur-bot to post from (none by default)
+# [bot]
+# poll_interval = 900      # seconds between feed checks
+#
+# [[bot.feeds]]
+# name = "releases"
+# url = "https://github.com/plurcast/plurcast/releases.atom"
+# template = "New release: {{title}}\n\n{{link}}"
+# platforms = ["nostr", "mastodon"]
+# tags = ["bot:releases"]  # labels for plur-history --label
+# max_per_check = 1        # entries posted per check
+# min_interval = 3600      # seconds between posts from this feed
 "#.to_string()
     }
 
@@ -476,20 +811,16 @@ platforms = ["nostr"]
     /// This is used for creating new config files
     pub fn default_config() -> Self {
         Self {
-            database: DatabaseConfig {
-                path: "~/.local/share/plurcast/posts.db".to_string(),
-            },
             credentials: Some(CredentialConfig::default()),
             nostr: Some(NostrConfig {
                 enabled: true,
                 keys_file: "~/.config/plurcast/nostr.keys".to_string(),
                 relays: default_nostr_relays(),
                 default_pow_difficulty: None,
+                media_server: None,
             }),
-            mastodon: None,
-            ssb: None,
-            defaults: DefaultsConfig::default(),
             scheduling: Some(SchedulingConfig::default()),
+            ..Self::default()
         }
     }
 
@@ -623,6 +954,132 @@ platforms = ["nostr"]
         assert_eq!(config.defaults.platforms, vec!["nostr"]);
     }
 
+    #[test]
+    fn test_toml_parsing_blackouts() {
+        let toml_content = r#"
+[database]
+path = "~/.local/share/plurcast/posts.db"
+
+[scheduling]
+[[scheduling.blackouts]]
+hours = "23:00-07:00"
+
+[[scheduling.blackouts]]
+days = ["weekends"]
+platforms = ["mastodon"]
+"#;
+
+        let config: Config = toml::from_str(toml_content).unwrap();
+        let blackouts = &config.scheduling.as_ref().unwrap().blackouts;
+        assert_eq!(blackouts.len(), 2);
+        assert_eq!(blackouts[0].hours.as_deref(), Some("23:00-07:00"));
+        assert_eq!(blackouts[1].platforms, vec!["mastodon"]);
+        assert!(config.validate().is_ok());
+
+        let invalid: Config = toml::from_str(&toml_content.replace("23:00-07:00", "late")).unwrap();
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_toml_parsing_send_workers() {
+        let toml_content = r#"
+[database]
+path = "~/.local/share/plurcast/posts.db"
+
+[scheduling]
+workers = 4
+"#;
+
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert_eq!(config.scheduling.as_ref().unwrap().workers, Some(4));
+        assert!(config.validate().is_ok());
+
+        let invalid: Config = toml::from_str(&toml_content.replace("4", "0")).unwrap();
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_toml_parsing_bot_feeds() {
+        let toml_content = r#"
+[database]
+path = "~/.local/share/plurcast/posts.db"
+
+[bot]
+[[bot.feeds]]
+name = "releases"
+url = "https://example.com/releases.atom"
+platforms = ["mastodon"]
+min_interval = 3600
+
+[[bot.feeds]]
+name = "blog"
+url = "~/blog/feed.xml"
+template = "{{title}} {{link}}"
+max_per_check = 3
+"#;
+
+        let config: Config = toml::from_str(toml_content).unwrap();
+        let bot = config.bot.as_ref().unwrap();
+        assert_eq!(bot.poll_interval, 900);
+        assert_eq!(bot.feeds.len(), 2);
+        assert_eq!(bot.feeds[0].max_per_check, 1);
+        assert_eq!(bot.feeds[0].min_interval, Some(3600));
+        assert_eq!(bot.feeds[1].template.as_deref(), Some("{{title}} {{link}}"));
+        assert!(config.validate().is_ok());
+
+        for invalid in [
+            toml_content.replace("\"blog\"", "\"releases\""),
+            toml_content.replace("max_per_check = 3", "max_per_check = 0"),
+            toml_content.replace("[\"mastodon\"]", "[\"twitter\"]"),
+        ] {
+            let config: Config = toml::from_str(&invalid).unwrap();
+            assert!(config.validate().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_toml_parsing_nostr_media_server() {
+        let toml_content = r#"
+[database]
+path = "~/.local/share/plurcast/posts.db"
+
+[nostr]
+keys_file = "~/.config/plurcast/nostr.keys"
+media_server = "https://nostr.build"
+"#;
+
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert_eq!(
+            config.nostr.as_ref().unwrap().media_server.as_deref(),
+            Some("https://nostr.build")
+        );
+        assert!(config.validate().is_ok());
+
+        let invalid: Config =
+            toml::from_str(&toml_content.replace("https://nostr.build", "nostr.build")).unwrap();
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_toml_parsing_default_language() {
+        let toml_content = r#"
+[database]
+path = "/tmp/test.db"
+
+[defaults]
+platforms = ["mastodon"]
+language = "de"
+"#;
+
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert_eq!(config.defaults.language.as_deref(), Some("de"));
+        assert!(config.validate().is_ok());
+
+        let invalid: Config =
+            toml::from_str(&toml_content.replace("\"de\"", "\"german\"")).unwrap();
+        assert!(invalid.validate().is_err());
+    }
+
     #[test]
     fn test_toml_parsing_minimal_config() {
         let toml_content = r#"
@@ -22,6 +22,7 @@
 //!     storage: StorageBackend::Keyring,
 //!     path: "~/.config/plurcast/credentials".to_string(),
 //!     master_password: None,
+//!     commands: Default::default(),
 //! };
 //!
 //! let manager = CredentialManager::new(config)?;
@@ -300,6 +301,13 @@ pub trait CredentialStore: Send + Sync {
         self.exists_account(service, key, "default")
     }
 
+    /// Whether this backend only supplies credentials and cannot store them
+    ///
+    /// `CredentialManager` skips read-only backends when storing.
+    fn is_read_only(&self) -> bool {
+        false
+    }
+
     /// Get the name of this storage backend
     ///
     /// Used for logging and debugging to identify which backend
@@ -857,6 +865,7 @@ impl CredentialStore for EncryptedFileStore {
 }
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Storage backend type
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -883,6 +892,12 @@ pub struct CredentialConfig {
     /// Master password for encrypted storage (not serialized)
     #[serde(skip)]
     pub master_password: Option<String>,
+
+    /// Commands that print credentials, keyed `platform.account.key`
+    ///
+    /// See [`command::CommandStore`]. These take priority over stored secrets.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub commands: BTreeMap<String, String>,
 }
 
 fn default_credential_path() -> String {
@@ -895,6 +910,7 @@ impl Default for CredentialConfig {
             storage: StorageBackend::Keyring,
             path: default_credential_path(),
             master_password: None,
+            commands: BTreeMap::new(),
         }
     }
 }
@@ -945,6 +961,8 @@ impl CredentialConfig {
         // Validate path can be expanded
         let _ = shellexpand::tilde(&self.path);
 
+        CommandStore::validate(&self.commands)?;
+
         Ok(())
     }
 
@@ -1023,6 +1041,7 @@ impl Default for MigrationReport {
 ///     storage: StorageBackend::Keyring,
 ///     path: "~/.config/plurcast/credentials".to_string(),
 ///     master_password: None,
+///     commands: Default::default(),
 /// };
 ///
 /// let manager = CredentialManager::new(config)?;
@@ -1052,8 +1071,13 @@ impl CredentialManager {
     /// Create a new CredentialManager
     ///
     /// Builds a priority list of storage backends based on configuration:
-    /// 1. KeyringStore (if configured and available)
-    /// 2. EncryptedFileStore (if master password available)
+    /// 1. EnvStore (environment variables and secret files, read-only)
+    /// 2. CommandStore (if `commands` are configured, read-only)
+    /// 3. KeyringStore (if configured and available)
+    /// 4. EncryptedFileStore (if master password available)
+    ///
+    /// Without a keyring or master password, credentials can still be read
+    /// from the read-only backends; storing returns `NoStoreAvailable`.
     ///
     /// # Arguments
     ///
@@ -1061,7 +1085,7 @@ impl CredentialManager {
     ///
     /// # Errors
     ///
-    /// Returns an error if no storage backend is available.
+    /// Returns an error if the configured commands are invalid.
     pub fn new(config: CredentialConfig) -> Result<Self> {
         let mut stores: Vec<Box<dyn CredentialStore>> = vec![];
 
@@ -1136,10 +1160,16 @@ impl CredentialManager {
             }
         }
 
-        if stores.is_empty() {
-            return Err(CredentialError::NoStoreAvailable.into());
+        // Command-backed entries override anything stored
+        if !config.commands.is_empty() {
+            CommandStore::validate(&config.commands)?;
+            stores.insert(0, Box::new(CommandStore::new(config.commands.clone())));
         }
 
+        // Environment variables and secret files override everything, so
+        // containers and CI work without a keyring or master password
+        stores.insert(0, Box::new(EnvStore::new()));
+
         Ok(Self { stores, config })
     }
 
@@ -1155,7 +1185,7 @@ impl CredentialManager {
     ///
     /// Returns an error if the credential cannot be stored in any backend.
     pub fn store(&self, service: &str, key: &str, value: &str) -> Result<()> {
-        if let Some(store) = self.stores.first() {
+        if let Some(store) = self.writable_store() {
             store.store(service, key, value)?;
             tracing::debug!(
                 "Stored credential for {}.{} using {} backend",
@@ -1294,9 +1324,17 @@ impl CredentialManager {
     /// Get the name of the primary storage backend being used
     ///
     /// Returns the backend name (e.g., "keyring", "encrypted_file", "plain_file")
-    /// of the first (primary) storage backend.
+    /// of the first (primary) backend that stores credentials.
     pub fn primary_backend(&self) -> Option<&str> {
-        self.stores.first().map(|s| s.backend_name())
+        self.writable_store().map(|s| s.backend_name())
+    }
+
+    /// The first backend that can store credentials
+    fn writable_store(&self) -> Option<&dyn CredentialStore> {
+        self.stores
+            .iter()
+            .find(|s| !s.is_read_only())
+            .map(|s| s.as_ref())
     }
 
     /// Store a credential for a specific account using the first available backend
@@ -1318,7 +1356,7 @@ impl CredentialManager {
         account: &str,
         value: &str,
     ) -> Result<()> {
-        if let Some(store) = self.stores.first() {
+        if let Some(store) = self.writable_store() {
             store.store_account(service, key, account, value)?;
             tracing::debug!(
                 "Stored credential for {}.{}.{} using {} backend",
@@ -1458,6 +1496,58 @@ impl CredentialManager {
         Ok(accounts)
     }
 
+    /// Retrieve the credential an account holds for a scope
+    ///
+    /// A `Read` request prefers the read-only credential (`{key}_read`) and
+    /// falls back to the full one; a `Write` request only uses the full one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CredentialError::NotFound` if no usable credential is stored.
+    pub fn retrieve_scoped(
+        &self,
+        service: &str,
+        key: &str,
+        account: &str,
+        scope: CredentialScope,
+    ) -> Result<String> {
+        if scope == CredentialScope::Read {
+            let read_key = scope.key(key);
+            if self.exists_account(service, &read_key, account)? {
+                return self.retrieve_account(service, &read_key, account);
+            }
+        }
+        self.retrieve_account(service, key, account)
+    }
+
+    /// Check if an account has a credential usable for a scope
+    pub fn exists_scoped(
+        &self,
+        service: &str,
+        key: &str,
+        account: &str,
+        scope: CredentialScope,
+    ) -> Result<bool> {
+        Ok(self.exists_account(service, key, account)?
+            || (scope == CredentialScope::Read
+                && self.exists_account(service, &scope.key(key), account)?))
+    }
+
+    /// List accounts for a service/key held by one backend (e.g. "command")
+    ///
+    /// Returns an empty list if that backend isn't in use.
+    pub fn list_accounts_from(
+        &self,
+        backend: &str,
+        service: &str,
+        key: &str,
+    ) -> Result<Vec<String>> {
+        match self.stores.iter().find(|s| s.backend_name() == backend) {
+            Some(store) => store.list_accounts(service, key),
+            None => Ok(Vec::new()),
+        }
+    }
+
     /// Automatically migrate credentials from old namespace format to multi-account format
     ///
     /// This method detects credentials stored in the old single-account format
@@ -1633,7 +1723,7 @@ impl CredentialManager {
         let mut report = MigrationReport::new();
 
         // Ensure we have a storage backend
-        if self.stores.is_empty() {
+        if self.writable_store().is_none() {
             return Err(CredentialError::NoStoreAvailable.into());
         }
 
@@ -1898,8 +1988,7 @@ impl CredentialManager {
 
         // Ensure we have a non-plain-text primary store
         let primary_store = self
-            .stores
-            .first()
+            .writable_store()
             .ok_or(CredentialError::NoStoreAvailable)?;
 
         if primary_store.backend_name() == "plain_file" {
@@ -2082,5 +2171,14 @@ impl CredentialManager {
     }
 }
 
+pub mod bundle;
+pub mod command;
+pub mod env;
+pub mod scope;
+
+pub use command::CommandStore;
+pub use env::EnvStore;
+pub use scope::CredentialScope;
+
 #[cfg(test)]
 mod tests;
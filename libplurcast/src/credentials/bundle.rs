@@ -0,0 +1,287 @@
+//! Encrypted credential bundles for moving accounts between machines
+//!
+//! A bundle holds every stored account (and each platform's active account)
+//! as JSON, encrypted with an age passphrase. `plur-creds export --all`
+//! writes one and `plur-creds import` restores it into whatever storage
+//! backend the other machine uses.
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+use super::CredentialManager;
+use crate::accounts::AccountManager;
+use crate::error::{CredentialError, Result};
+
+/// Bundle format version
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Credential keys stored per platform, primary key first
+///
+/// The primary key decides whether an account exists; the rest are stored
+/// alongside it when present (e.g. by `plur-creds login mastodon`).
+pub const PLATFORM_KEYS: &[(&str, &str, &[&str])] = &[
+    ("nostr", "plurcast.nostr", &["private_key"]),
+    (
+        "mastodon",
+        "plurcast.mastodon",
+        &[
+            "access_token",
+            "access_token_read",
+            "instance",
+            "client_id",
+            "client_secret",
+        ],
+    ),
+    ("ssb", "plurcast.ssb", &["keypair"]),
+];
+
+/// One account's credentials
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleAccount {
+    pub platform: String,
+    pub account: String,
+    /// Credential key to value, e.g. `access_token`
+    pub credentials: BTreeMap<String, String>,
+}
+
+/// All stored accounts, as written to an export bundle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CredentialBundle {
+    pub version: u32,
+    /// RFC 3339 time the bundle was written
+    pub exported_at: String,
+    pub accounts: Vec<BundleAccount>,
+    /// Platform to active account name
+    #[serde(default)]
+    pub active: BTreeMap<String, String>,
+}
+
+/// What an import did
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ImportReport {
+    /// `platform/account` entries written
+    pub imported: Vec<String>,
+    /// `platform/account` entries left alone because they already exist
+    pub skipped: Vec<String>,
+}
+
+impl CredentialBundle {
+    /// Gather every account registered with `accounts` that has credentials
+    ///
+    /// Accounts are enumerated through the account registry because the OS
+    /// keyring cannot list its entries.
+    pub fn collect(manager: &CredentialManager, accounts: &AccountManager) -> Result<Self> {
+        let mut bundle = Self {
+            version: BUNDLE_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            accounts: Vec::new(),
+            active: BTreeMap::new(),
+        };
+
+        for (platform, service, keys) in PLATFORM_KEYS {
+            let mut found = false;
+            for account in accounts.list_accounts(platform) {
+                if !manager.exists_account(service, keys[0], &account)? {
+                    continue; // Stale registry entry
+                }
+
+                let mut credentials = BTreeMap::new();
+                for key in keys.iter() {
+                    if manager.exists_account(service, key, &account)? {
+                        let value = manager.retrieve_account(service, key, &account)?;
+                        credentials.insert(key.to_string(), value);
+                    }
+                }
+                bundle.accounts.push(BundleAccount {
+                    platform: platform.to_string(),
+                    account,
+                    credentials,
+                });
+                found = true;
+            }
+            if found {
+                bundle
+                    .active
+                    .insert(platform.to_string(), accounts.get_active_account(platform));
+            }
+        }
+
+        Ok(bundle)
+    }
+
+    /// Write the bundle's accounts into `manager` and register them
+    ///
+    /// Accounts that already have credentials are skipped unless
+    /// `overwrite` is set. Active accounts are restored only for platforms
+    /// that had nothing stored before the import.
+    pub fn restore(
+        &self,
+        manager: &CredentialManager,
+        accounts: &AccountManager,
+        overwrite: bool,
+    ) -> Result<ImportReport> {
+        let mut report = ImportReport::default();
+        let mut had_accounts = BTreeMap::new();
+
+        for entry in &self.accounts {
+            let (_, service, keys) = PLATFORM_KEYS
+                .iter()
+                .find(|(platform, _, _)| *platform == entry.platform)
+                .ok_or_else(|| {
+                    CredentialError::MigrationFailed(format!(
+                        "Unknown platform '{}' in bundle",
+                        entry.platform
+                    ))
+                })?;
+            AccountManager::validate_account_name(&entry.account)?;
+            if !entry.credentials.contains_key(keys[0]) {
+                return Err(CredentialError::MigrationFailed(format!(
+                    "{}/{} in bundle has no {}",
+                    entry.platform, entry.account, keys[0]
+                ))
+                .into());
+            }
+
+            had_accounts
+                .entry(entry.platform.as_str())
+                .or_insert_with(|| !accounts.list_accounts(&entry.platform).is_empty());
+
+            let label = format!("{}/{}", entry.platform, entry.account);
+            if !overwrite && manager.exists_account(service, keys[0], &entry.account)? {
+                report.skipped.push(label);
+                continue;
+            }
+
+            for (key, value) in &entry.credentials {
+                if keys.contains(&key.as_str()) {
+                    manager.store_account(service, key, &entry.account, value)?;
+                }
+            }
+            accounts.register_account(&entry.platform, &entry.account)?;
+            report.imported.push(label);
+        }
+
+        for (platform, account) in &self.active {
+            if had_accounts.get(platform.as_str()) == Some(&false)
+                && accounts.account_exists(platform, account)
+            {
+                accounts.set_active_account(platform, account)?;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Encrypt the bundle with an age passphrase
+    ///
+    /// # Errors
+    ///
+    /// Returns `CredentialError::WeakPassword` for passphrases shorter than
+    /// 8 characters.
+    pub fn seal(&self, passphrase: &str) -> Result<Vec<u8>> {
+        if passphrase.len() < 8 {
+            return Err(CredentialError::WeakPassword.into());
+        }
+
+        let json = serde_json::to_vec(self)
+            .map_err(|e| CredentialError::Encryption(format!("Failed to encode bundle: {}", e)))?;
+
+        let encryptor =
+            age::Encryptor::with_user_passphrase(age::secrecy::Secret::new(passphrase.to_string()));
+        let mut sealed = vec![];
+        let mut writer = encryptor
+            .wrap_output(&mut sealed)
+            .map_err(|e| CredentialError::Encryption(e.to_string()))?;
+        writer
+            .write_all(&json)
+            .map_err(|e| CredentialError::Encryption(e.to_string()))?;
+        writer
+            .finish()
+            .map_err(|e| CredentialError::Encryption(e.to_string()))?;
+
+        Ok(sealed)
+    }
+
+    /// Decrypt a bundle written by [`CredentialBundle::seal`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `CredentialError::DecryptionFailed` for a wrong passphrase,
+    /// and `CredentialError::Encryption` for data that is not a bundle.
+    pub fn open(sealed: &[u8], passphrase: &str) -> Result<Self> {
+        let decryptor = match age::Decryptor::new(sealed) {
+            Ok(age::Decryptor::Passphrase(d)) => d,
+            Ok(_) => {
+                return Err(CredentialError::Encryption(
+                    "Not a passphrase-encrypted bundle".to_string(),
+                )
+                .into())
+            }
+            Err(e) => return Err(CredentialError::Encryption(e.to_string()).into()),
+        };
+
+        let mut json = vec![];
+        let mut reader = decryptor
+            .decrypt(&age::secrecy::Secret::new(passphrase.to_string()), None)
+            .map_err(|_| CredentialError::DecryptionFailed)?;
+        reader
+            .read_to_end(&mut json)
+            .map_err(|e| CredentialError::Encryption(e.to_string()))?;
+
+        let bundle: Self = serde_json::from_slice(&json)
+            .map_err(|e| CredentialError::Encryption(format!("Invalid bundle: {}", e)))?;
+        if bundle.version > BUNDLE_VERSION {
+            return Err(CredentialError::Encryption(format!(
+                "Bundle version {} is newer than this plur-creds supports ({})",
+                bundle.version, BUNDLE_VERSION
+            ))
+            .into());
+        }
+        Ok(bundle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> CredentialBundle {
+        let mut credentials = BTreeMap::new();
+        credentials.insert("access_token".to_string(), "token".to_string());
+        credentials.insert("instance".to_string(), "mastodon.social".to_string());
+        CredentialBundle {
+            version: BUNDLE_VERSION,
+            exported_at: "2026-01-01T00:00:00+00:00".to_string(),
+            accounts: vec![BundleAccount {
+                platform: "mastodon".to_string(),
+                account: "work".to_string(),
+                credentials,
+            }],
+            active: BTreeMap::from([("mastodon".to_string(), "work".to_string())]),
+        }
+    }
+
+    #[test]
+    fn test_seal_open_round_trip() {
+        let bundle = sample();
+        let sealed = bundle.seal("long enough passphrase").unwrap();
+        assert!(!sealed.windows(5).any(|w| w == b"token"));
+        assert_eq!(
+            CredentialBundle::open(&sealed, "long enough passphrase").unwrap(),
+            bundle
+        );
+    }
+
+    #[test]
+    fn test_open_wrong_passphrase() {
+        let sealed = sample().seal("long enough passphrase").unwrap();
+        assert!(CredentialBundle::open(&sealed, "another passphrase").is_err());
+    }
+
+    #[test]
+    fn test_seal_rejects_short_passphrase() {
+        assert!(sample().seal("short").is_err());
+    }
+}
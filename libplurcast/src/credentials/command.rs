@@ -0,0 +1,248 @@
+//! Command-backed credentials
+//!
+//! Instead of holding a secret, a credential entry can name a command that
+//! prints it, run each time the credential is used:
+//!
+//! ```toml
+//! [credentials.commands]
+//! "nostr.default.private_key" = "pass show nostr/key"
+//! "mastodon.work.access_token" = "op read op://Private/mastodon/token"
+//! ```
+//!
+//! Entries are keyed `platform.account.key`. The command runs through the
+//! shell and its standard output, minus the trailing newline, is the
+//! credential. `PLURCAST_CREDENTIAL_SERVICE`, `PLURCAST_CREDENTIAL_ACCOUNT`
+//! and `PLURCAST_CREDENTIAL_KEY` are set so one script can serve several
+//! entries.
+
+use std::collections::BTreeMap;
+use std::process::Command;
+
+use super::CredentialStore;
+use crate::error::{CredentialError, Result};
+
+/// Read-only store that runs a configured command per credential
+pub struct CommandStore {
+    commands: BTreeMap<String, String>,
+}
+
+impl CommandStore {
+    /// Create a store from `[credentials.commands]` entries
+    pub fn new(commands: BTreeMap<String, String>) -> Self {
+        Self { commands }
+    }
+
+    /// Check that every entry is keyed `platform.account.key`
+    pub fn validate(commands: &BTreeMap<String, String>) -> Result<()> {
+        for (entry, command) in commands {
+            if entry.split('.').count() != 3 || entry.split('.').any(str::is_empty) {
+                return Err(CredentialError::Command(format!(
+                    "'{}' should be written as platform.account.key (e.g. nostr.default.private_key)",
+                    entry
+                ))
+                .into());
+            }
+            if command.trim().is_empty() {
+                return Err(
+                    CredentialError::Command(format!("No command given for '{}'", entry)).into(),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn entry(service: &str, key: &str, account: &str) -> String {
+        let platform = service.strip_prefix("plurcast.").unwrap_or(service);
+        format!("{}.{}.{}", platform, account, key)
+    }
+
+    fn run(&self, command: &str, service: &str, key: &str, account: &str) -> Result<String> {
+        let mut process = if cfg!(windows) {
+            let mut process = Command::new("cmd");
+            process.arg("/C").arg(command);
+            process
+        } else {
+            let mut process = Command::new("sh");
+            process.arg("-c").arg(command);
+            process
+        };
+
+        let output = process
+            .env("PLURCAST_CREDENTIAL_SERVICE", service)
+            .env("PLURCAST_CREDENTIAL_ACCOUNT", account)
+            .env("PLURCAST_CREDENTIAL_KEY", key)
+            .output()
+            .map_err(|e| CredentialError::Command(format!("Failed to run '{}': {}", command, e)))?;
+
+        // Never echo stdout in errors: it may hold part of the secret
+        if !output.status.success() {
+            return Err(CredentialError::Command(format!(
+                "'{}' exited with {}: {}",
+                command,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+            .into());
+        }
+
+        let value = String::from_utf8(output.stdout).map_err(|_| {
+            CredentialError::Command(format!("'{}' printed invalid UTF-8", command))
+        })?;
+        let value = value.trim_end_matches(['\r', '\n']);
+        if value.is_empty() {
+            return Err(CredentialError::Command(format!("'{}' printed nothing", command)).into());
+        }
+        Ok(value.to_string())
+    }
+}
+
+impl CredentialStore for CommandStore {
+    fn store_account(&self, service: &str, key: &str, account: &str, _value: &str) -> Result<()> {
+        Err(CredentialError::Command(format!(
+            "{} is provided by a command in [credentials.commands] and cannot be stored",
+            Self::entry(service, key, account)
+        ))
+        .into())
+    }
+
+    fn retrieve_account(&self, service: &str, key: &str, account: &str) -> Result<String> {
+        let entry = Self::entry(service, key, account);
+        match self.commands.get(&entry) {
+            Some(command) => {
+                tracing::debug!("Running credential command for {}", entry);
+                self.run(command, service, key, account)
+            }
+            None => Err(CredentialError::NotFound(entry).into()),
+        }
+    }
+
+    fn delete_account(&self, _service: &str, _key: &str, _account: &str) -> Result<()> {
+        // Nothing stored here; the config entry stays
+        Ok(())
+    }
+
+    fn exists_account(&self, service: &str, key: &str, account: &str) -> Result<bool> {
+        Ok(self
+            .commands
+            .contains_key(&Self::entry(service, key, account)))
+    }
+
+    fn list_accounts(&self, service: &str, key: &str) -> Result<Vec<String>> {
+        let platform = service.strip_prefix("plurcast.").unwrap_or(service);
+        Ok(self
+            .commands
+            .keys()
+            .filter_map(|entry| {
+                let mut parts = entry.split('.');
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(p), Some(account), Some(k)) if p == platform && k == key => {
+                        Some(account.to_string())
+                    }
+                    _ => None,
+                }
+            })
+            .collect())
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    fn backend_name(&self) -> &str {
+        "command"
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn store(entries: &[(&str, &str)]) -> CommandStore {
+        CommandStore::new(
+            entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_retrieve_runs_command() {
+        let store = store(&[
+            ("nostr.default.private_key", "echo secret-key"),
+            (
+                "mastodon.work.access_token",
+                "echo $PLURCAST_CREDENTIAL_ACCOUNT",
+            ),
+        ]);
+        assert_eq!(
+            store
+                .retrieve_account("plurcast.nostr", "private_key", "default")
+                .unwrap(),
+            "secret-key"
+        );
+        assert_eq!(
+            store
+                .retrieve_account("plurcast.mastodon", "access_token", "work")
+                .unwrap(),
+            "work"
+        );
+        assert!(store
+            .exists_account("plurcast.nostr", "private_key", "default")
+            .unwrap());
+        assert_eq!(
+            store
+                .list_accounts("plurcast.mastodon", "access_token")
+                .unwrap(),
+            vec!["work".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_missing_entry_is_not_found() {
+        let store = store(&[]);
+        let err = store
+            .retrieve_account("plurcast.nostr", "private_key", "default")
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::PlurcastError::Credential(CredentialError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_failing_or_empty_command() {
+        let store = store(&[
+            ("nostr.default.private_key", "echo oops >&2; exit 3"),
+            ("nostr.empty.private_key", "true"),
+        ]);
+        let err = store
+            .retrieve_account("plurcast.nostr", "private_key", "default")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("oops"));
+        assert!(store
+            .retrieve_account("plurcast.nostr", "private_key", "empty")
+            .is_err());
+    }
+
+    #[test]
+    fn test_store_is_read_only() {
+        let store = store(&[("nostr.default.private_key", "echo key")]);
+        assert!(store.is_read_only());
+        assert!(store
+            .store_account("plurcast.nostr", "private_key", "default", "x")
+            .is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let ok = BTreeMap::from([(
+            "nostr.default.private_key".to_string(),
+            "pass show nostr".to_string(),
+        )]);
+        assert!(CommandStore::validate(&ok).is_ok());
+        let bad = BTreeMap::from([("nostr".to_string(), "pass show nostr".to_string())]);
+        assert!(CommandStore::validate(&bad).is_err());
+    }
+}
@@ -0,0 +1,298 @@
+//! Credentials from environment variables and mounted secret files
+//!
+//! For containers and CI, where there is no keyring and no TTY to prompt
+//! for a master password. Each credential is looked up, in order, as:
+//!
+//! 1. an environment variable, e.g. `PLURCAST_NOSTR_KEY`
+//! 2. a file named by `<variable>_FILE`, e.g. `PLURCAST_NOSTR_KEY_FILE=/run/secrets/nostr`
+//! 3. a file in the secrets directory named after the variable in lower
+//!    case, e.g. `/run/secrets/plurcast_nostr_key` (`PLURCAST_SECRETS_DIR`
+//!    changes the directory)
+//!
+//! Variables are named `PLURCAST_<PLATFORM>[_<ACCOUNT>]_<KEY>`; the account
+//! part is left out for the default account. Private keys use `KEY`, access
+//! tokens `TOKEN` (`READ_TOKEN` for read-only ones), and other keys their
+//! own name, e.g. `PLURCAST_MASTODON_WORK_TOKEN` or
+//! `PLURCAST_MASTODON_INSTANCE`.
+
+use std::path::{Path, PathBuf};
+
+use super::CredentialStore;
+use crate::error::{CredentialError, Result};
+
+/// Default directory for mounted secrets (Docker, Kubernetes)
+pub const DEFAULT_SECRETS_DIR: &str = "/run/secrets";
+
+/// Environment variable overriding [`DEFAULT_SECRETS_DIR`]
+pub const SECRETS_DIR_ENV: &str = "PLURCAST_SECRETS_DIR";
+
+/// Read-only store resolving credentials from the environment
+pub struct EnvStore {
+    secrets_dir: PathBuf,
+}
+
+impl EnvStore {
+    /// Create a store using `PLURCAST_SECRETS_DIR` or `/run/secrets`
+    pub fn new() -> Self {
+        let secrets_dir = std::env::var(SECRETS_DIR_ENV)
+            .ok()
+            .filter(|dir| !dir.is_empty())
+            .unwrap_or_else(|| DEFAULT_SECRETS_DIR.to_string());
+        Self::with_secrets_dir(PathBuf::from(secrets_dir))
+    }
+
+    /// Create a store reading secret files from `secrets_dir`
+    pub fn with_secrets_dir(secrets_dir: PathBuf) -> Self {
+        Self { secrets_dir }
+    }
+
+    /// Name of the environment variable holding a credential
+    ///
+    /// ```
+    /// use libplurcast::credentials::EnvStore;
+    ///
+    /// assert_eq!(
+    ///     EnvStore::variable_name("plurcast.nostr", "private_key", "default"),
+    ///     "PLURCAST_NOSTR_KEY"
+    /// );
+    /// assert_eq!(
+    ///     EnvStore::variable_name("plurcast.mastodon", "access_token", "work-bot"),
+    ///     "PLURCAST_MASTODON_WORK_BOT_TOKEN"
+    /// );
+    /// ```
+    pub fn variable_name(service: &str, key: &str, account: &str) -> String {
+        let platform = service.strip_prefix("plurcast.").unwrap_or(service);
+        let mut name = format!("PLURCAST_{}", env_part(platform));
+        if account != "default" {
+            name.push('_');
+            name.push_str(&env_part(account));
+        }
+        name.push('_');
+        name.push_str(&key_part(key));
+        name
+    }
+
+    fn lookup(&self, service: &str, key: &str, account: &str) -> Result<Option<String>> {
+        let name = Self::variable_name(service, key, account);
+
+        if let Ok(value) = std::env::var(&name) {
+            if !value.is_empty() {
+                tracing::debug!("Using credential from environment variable {}", name);
+                return Ok(Some(value));
+            }
+        }
+
+        let file_var = format!("{}_FILE", name);
+        if let Ok(path) = std::env::var(&file_var) {
+            if !path.is_empty() {
+                tracing::debug!("Using credential from {} ({})", file_var, path);
+                return read_secret(Path::new(&path)).map(Some);
+            }
+        }
+
+        let path = self.secrets_dir.join(name.to_lowercase());
+        if path.is_file() {
+            tracing::debug!("Using credential from {}", path.display());
+            return read_secret(&path).map(Some);
+        }
+
+        Ok(None)
+    }
+}
+
+impl Default for EnvStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Upper-case a platform or account for a variable name
+fn env_part(text: &str) -> String {
+    text.to_uppercase().replace('-', "_")
+}
+
+/// Variable suffix for a key: short names for the common ones
+fn key_part(key: &str) -> String {
+    match key {
+        "private_key" => "KEY".to_string(),
+        "access_token" => "TOKEN".to_string(),
+        "access_token_read" => "READ_TOKEN".to_string(),
+        _ => env_part(key),
+    }
+}
+
+fn read_secret(path: &Path) -> Result<String> {
+    let value = std::fs::read_to_string(path).map_err(|e| {
+        CredentialError::Io(std::io::Error::new(
+            e.kind(),
+            format!("Failed to read secret file {}: {}", path.display(), e),
+        ))
+    })?;
+    let value = value.trim_end_matches(['\r', '\n']);
+    if value.is_empty() {
+        return Err(
+            CredentialError::NotFound(format!("secret file {} is empty", path.display())).into(),
+        );
+    }
+    Ok(value.to_string())
+}
+
+impl CredentialStore for EnvStore {
+    fn store_account(&self, service: &str, key: &str, account: &str, _value: &str) -> Result<()> {
+        Err(CredentialError::Io(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!(
+                "{} comes from the environment and cannot be stored",
+                Self::variable_name(service, key, account)
+            ),
+        ))
+        .into())
+    }
+
+    fn retrieve_account(&self, service: &str, key: &str, account: &str) -> Result<String> {
+        self.lookup(service, key, account)?.ok_or_else(|| {
+            CredentialError::NotFound(Self::variable_name(service, key, account)).into()
+        })
+    }
+
+    fn delete_account(&self, _service: &str, _key: &str, _account: &str) -> Result<()> {
+        // Nothing stored here; unset the variable or remove the file instead
+        Ok(())
+    }
+
+    fn exists_account(&self, service: &str, key: &str, account: &str) -> Result<bool> {
+        Ok(self.lookup(service, key, account)?.is_some())
+    }
+
+    fn list_accounts(&self, service: &str, key: &str) -> Result<Vec<String>> {
+        let platform = service.strip_prefix("plurcast.").unwrap_or(service);
+        let prefix = format!("PLURCAST_{}_", env_part(platform));
+        let suffix = format!("_{}", key_part(key));
+        let default = Self::variable_name(service, key, "default");
+
+        // Candidate names: variables, plus files in the secrets directory
+        let mut names: Vec<String> = std::env::vars().map(|(name, _)| name).collect();
+        if let Ok(entries) = std::fs::read_dir(&self.secrets_dir) {
+            names.extend(
+                entries
+                    .flatten()
+                    .map(|entry| entry.file_name().to_string_lossy().to_uppercase()),
+            );
+        }
+
+        let mut accounts = Vec::new();
+        for name in &names {
+            let name = name.strip_suffix("_FILE").unwrap_or(name);
+            let account = if name == default {
+                "default".to_string()
+            } else if let Some(middle) = name
+                .strip_prefix(&prefix)
+                .and_then(|rest| rest.strip_suffix(&suffix))
+            {
+                middle.to_lowercase()
+            } else {
+                continue;
+            };
+            if !account.is_empty()
+                && !accounts.contains(&account)
+                && self.exists_account(service, key, &account)?
+            {
+                accounts.push(account);
+            }
+        }
+        accounts.sort();
+        Ok(accounts)
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    fn backend_name(&self) -> &str {
+        "environment"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_variable_names() {
+        assert_eq!(
+            EnvStore::variable_name("plurcast.nostr", "private_key", "default"),
+            "PLURCAST_NOSTR_KEY"
+        );
+        assert_eq!(
+            EnvStore::variable_name("plurcast.mastodon", "instance", "work"),
+            "PLURCAST_MASTODON_WORK_INSTANCE"
+        );
+        assert_eq!(
+            EnvStore::variable_name("plurcast.ssb", "keypair", "default"),
+            "PLURCAST_SSB_KEYPAIR"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_env_then_file_then_secrets_dir() {
+        let dir = TempDir::new().unwrap();
+        let store = EnvStore::with_secrets_dir(dir.path().to_path_buf());
+
+        std::fs::write(dir.path().join("plurcast_nostr_envtest_key"), "from-dir\n").unwrap();
+        assert_eq!(
+            store
+                .retrieve_account("plurcast.nostr", "private_key", "envtest")
+                .unwrap(),
+            "from-dir"
+        );
+
+        let file = dir.path().join("other");
+        std::fs::write(&file, "from-file\n").unwrap();
+        std::env::set_var("PLURCAST_NOSTR_ENVTEST_KEY_FILE", &file);
+        assert_eq!(
+            store
+                .retrieve_account("plurcast.nostr", "private_key", "envtest")
+                .unwrap(),
+            "from-file"
+        );
+
+        std::env::set_var("PLURCAST_NOSTR_ENVTEST_KEY", "from-env");
+        assert_eq!(
+            store
+                .retrieve_account("plurcast.nostr", "private_key", "envtest")
+                .unwrap(),
+            "from-env"
+        );
+        assert!(store
+            .list_accounts("plurcast.nostr", "private_key")
+            .unwrap()
+            .contains(&"envtest".to_string()));
+
+        std::env::remove_var("PLURCAST_NOSTR_ENVTEST_KEY");
+        std::env::remove_var("PLURCAST_NOSTR_ENVTEST_KEY_FILE");
+    }
+
+    #[test]
+    #[serial]
+    fn test_missing_is_not_found_and_read_only() {
+        let dir = TempDir::new().unwrap();
+        let store = EnvStore::with_secrets_dir(dir.path().to_path_buf());
+
+        assert!(!store
+            .exists_account("plurcast.nostr", "private_key", "missing")
+            .unwrap());
+        assert!(matches!(
+            store.retrieve_account("plurcast.nostr", "private_key", "missing"),
+            Err(crate::error::PlurcastError::Credential(
+                CredentialError::NotFound(_)
+            ))
+        ));
+        assert!(store.is_read_only());
+        assert!(store
+            .store_account("plurcast.nostr", "private_key", "missing", "x")
+            .is_err());
+    }
+}
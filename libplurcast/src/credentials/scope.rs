@@ -0,0 +1,100 @@
+//! Credential scopes
+//!
+//! An account can hold a read-only credential alongside its full one, e.g.
+//! a Mastodon token granted only the `read` OAuth scope for timeline and
+//! notification features. Consumers ask for the narrowest scope they need
+//! with [`CredentialManager::retrieve_scoped`](super::CredentialManager::retrieve_scoped):
+//! a read request uses the read-only credential when one is stored and
+//! falls back to the full one, while a write request never gets a
+//! read-only credential.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{PlurcastError, Result};
+
+/// What a credential will be used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CredentialScope {
+    /// Reading timelines, notifications and account details
+    Read,
+    /// Posting and everything else (the stored credential as before)
+    #[default]
+    Write,
+}
+
+impl CredentialScope {
+    /// Key a credential of this scope is stored under
+    ///
+    /// Write credentials use the key unchanged, so existing accounts keep
+    /// working; read-only ones get a `_read` suffix (`access_token_read`).
+    pub fn key(&self, key: &str) -> String {
+        match self {
+            CredentialScope::Read => format!("{}_read", key),
+            CredentialScope::Write => key.to_string(),
+        }
+    }
+
+    /// OAuth scopes to request for a credential of this scope
+    pub fn oauth_scopes(&self) -> &'static [&'static str] {
+        match self {
+            CredentialScope::Read => &["read"],
+            CredentialScope::Write => &["read", "write"],
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CredentialScope::Read => "read",
+            CredentialScope::Write => "write",
+        }
+    }
+}
+
+impl fmt::Display for CredentialScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CredentialScope {
+    type Err = PlurcastError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "read" | "read-only" | "readonly" => Ok(CredentialScope::Read),
+            "write" | "full" => Ok(CredentialScope::Write),
+            other => Err(PlurcastError::InvalidInput(format!(
+                "Unknown credential scope '{}'. Use 'read' or 'write'",
+                other
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scoped_keys() {
+        assert_eq!(CredentialScope::Write.key("access_token"), "access_token");
+        assert_eq!(
+            CredentialScope::Read.key("access_token"),
+            "access_token_read"
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "read".parse::<CredentialScope>().unwrap(),
+            CredentialScope::Read
+        );
+        assert_eq!(
+            "Write".parse::<CredentialScope>().unwrap(),
+            CredentialScope::Write
+        );
+        assert!("admin".parse::<CredentialScope>().is_err());
+    }
+}
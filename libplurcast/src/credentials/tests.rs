@@ -10,6 +10,7 @@ fn test_config(temp_dir: &TempDir) -> CredentialConfig {
         storage: StorageBackend::Encrypted,
         path: temp_dir.path().to_string_lossy().to_string(),
         master_password: Some("test_password_123".to_string()),
+        commands: Default::default(),
     }
 }
 
@@ -1,14 +1,38 @@
 //! Database operations for Plurcast
 
-use sqlx::sqlite::SqlitePool;
+use sqlx::sqlite::{
+    Sqlite, SqliteArguments, SqliteConnectOptions, SqliteJournalMode, SqlitePool,
+    SqlitePoolOptions, SqliteSynchronous,
+};
+use sqlx::Arguments;
 use std::collections::HashMap;
 use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
+use crate::config::Config;
+use crate::credentials::CredentialManager;
+use crate::encryption::ContentCipher;
 use crate::error::Result;
 use crate::types::{
-    Attachment, AttachmentStatus, AttachmentUpload, ImageMimeType, Post, PostRecord, PostStatus,
+    Attachment, AttachmentStatus, AttachmentUpload, AuditEvent, EngagementRecord, ImageMimeType,
+    PlatformSchedule, Post, PostAttempt, PostCheck, PostDeferral, PostRecord, PostSeries,
+    PostStatus, QueuePause, RelayResult, ScheduledDeletion, SendLease, Tombstone,
 };
 
+/// How long a connection waits on a locked database before failing
+///
+/// plur-post, plur-send and plur-queue routinely have the database open at
+/// the same time; a short wait turns "database is locked" into a brief stall.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum pooled connections per process
+///
+/// With WAL, readers don't block the single writer, so a small pool is
+/// enough for concurrent service calls without hoarding file handles.
+const MAX_CONNECTIONS: u32 = 5;
+
 /// A post with all its platform records
 #[derive(Debug, Clone)]
 pub struct PostWithRecords {
@@ -16,9 +40,132 @@ pub struct PostWithRecords {
     pub records: Vec<PostRecord>,
 }
 
+/// Filters for [`Database::query_posts`]
+///
+/// All filters are optional and combine with AND. `limit: None` returns
+/// every matching post.
+#[derive(Debug, Clone, Default)]
+pub struct PostQuery {
+    /// Only posts with a record on this platform
+    pub platform: Option<String>,
+    /// Only posts created at or after this Unix timestamp
+    pub since: Option<i64>,
+    /// Only posts created at or before this Unix timestamp
+    pub until: Option<i64>,
+    /// Only posts whose content contains this text
+    pub search: Option<String>,
+    /// Only posts carrying this label
+    pub label: Option<String>,
+    /// Only posts made under this account: with a record under it, or
+    /// created for it and not sent yet
+    pub account: Option<String>,
+    /// Only posts with this status
+    pub status: Option<PostStatus>,
+    /// Only posts that sort after this `(created_at, id)` position
+    ///
+    /// Posts are ordered newest first, so this is the position of the last
+    /// post on the previous page (keyset pagination).
+    pub before: Option<(i64, String)>,
+    /// Include soft-deleted posts (hidden by default)
+    pub include_deleted: bool,
+    /// Maximum number of posts to return
+    pub limit: Option<usize>,
+}
+
+impl PostQuery {
+    /// The filters as a SQL condition and its arguments
+    ///
+    /// The condition is on `posts p LEFT JOIN post_records pr`, so queries
+    /// using it select `DISTINCT` posts. `limit` is left to the caller, as
+    /// is `search` unless `sql_search` is set (encrypted content can't be
+    /// matched in SQL).
+    pub(crate) fn where_clause(&self, sql_search: bool) -> Result<(String, SqliteArguments<'_>)> {
+        let mut where_clauses = vec!["1=1"];
+        let mut arguments = SqliteArguments::default();
+
+        if let Some(platform) = self.platform.as_deref() {
+            where_clauses.push("pr.platform = ?");
+            add_argument(&mut arguments, platform)?;
+        }
+        if let Some(since) = self.since {
+            where_clauses.push("p.created_at >= ?");
+            add_argument(&mut arguments, since)?;
+        }
+        if let Some(until) = self.until {
+            where_clauses.push("p.created_at <= ?");
+            add_argument(&mut arguments, until)?;
+        }
+        if let Some(search) = self.search.as_deref().filter(|_| sql_search) {
+            where_clauses.push("p.content LIKE ?");
+            add_argument(&mut arguments, format!("%{}%", search))?;
+        }
+        if let Some(label) = self.label.as_deref() {
+            where_clauses.push(
+                "EXISTS (SELECT 1 FROM post_labels pl WHERE pl.post_id = p.id AND pl.label = ?)",
+            );
+            add_argument(&mut arguments, label)?;
+        }
+        if let Some(account) = self.account.as_deref() {
+            where_clauses.push(
+                "(pr.account_name = ? OR CASE WHEN json_valid(p.metadata) THEN json_extract(p.metadata, '$.account') END = ?)",
+            );
+            add_argument(&mut arguments, account)?;
+            add_argument(&mut arguments, account)?;
+        }
+        if let Some(status) = &self.status {
+            where_clauses.push("p.status = ?");
+            add_argument(&mut arguments, status.as_str())?;
+        }
+        if let Some((created_at, id)) = &self.before {
+            where_clauses.push("(p.created_at < ? OR (p.created_at = ? AND p.id < ?))");
+            add_argument(&mut arguments, *created_at)?;
+            add_argument(&mut arguments, *created_at)?;
+            add_argument(&mut arguments, id.as_str())?;
+        }
+        if !self.include_deleted {
+            where_clauses.push("p.status != 'deleted'");
+        }
+
+        Ok((where_clauses.join(" AND "), arguments))
+    }
+}
+
+/// Add a bind argument to a query built with `query_with`
+pub(crate) fn add_argument<'q, T>(arguments: &mut SqliteArguments<'q>, value: T) -> Result<()>
+where
+    T: 'q + sqlx::Encode<'q, Sqlite> + sqlx::Type<Sqlite>,
+{
+    arguments
+        .add(value)
+        .map_err(|e| crate::error::DbError::SqlxError(sqlx::Error::Encode(e)))?;
+    Ok(())
+}
+
+/// Outcome of [`Database::maintenance`]
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MaintenanceReport {
+    /// Rows returned by `PRAGMA integrity_check` ("ok" when healthy)
+    pub integrity: Vec<String>,
+    /// Whether VACUUM and ANALYZE ran (skipped when integrity fails)
+    pub compacted: bool,
+    /// Database size in bytes before maintenance
+    pub size_before: u64,
+    /// Database size in bytes after maintenance
+    pub size_after: u64,
+}
+
+impl MaintenanceReport {
+    /// Whether the integrity check passed
+    pub fn is_healthy(&self) -> bool {
+        self.integrity.len() == 1 && self.integrity[0] == "ok"
+    }
+}
+
 #[derive(Clone)]
 pub struct Database {
     pool: SqlitePool,
+    /// Cipher for post content when `database.encrypt_content` is enabled
+    cipher: Option<Arc<ContentCipher>>,
 }
 
 impl Database {
@@ -42,7 +189,17 @@ impl Database {
         // Use mode=rwc to allow creating the database file if it doesn't exist
         let db_url = format!("sqlite://{}?mode=rwc", expanded_path.replace('\\', "/"));
 
-        let pool = SqlitePool::connect(&db_url)
+        // WAL lets readers and a writer work concurrently across processes;
+        // NORMAL synchronous is durable under WAL and avoids an fsync per commit
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .map_err(crate::error::DbError::SqlxError)?
+            .journal_mode(SqliteJournalMode::Wal)
+            .synchronous(SqliteSynchronous::Normal)
+            .busy_timeout(BUSY_TIMEOUT);
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(MAX_CONNECTIONS)
+            .connect_with(options)
             .await
             .map_err(crate::error::DbError::SqlxError)?;
 
@@ -52,7 +209,131 @@ impl Database {
             .await
             .map_err(crate::error::DbError::MigrationError)?;
 
-        Ok(Self { pool })
+        Ok(Self { pool, cipher: None })
+    }
+
+    /// Open an existing database read-only, without running migrations
+    ///
+    /// For inspecting a database as it is (e.g. its schema version) without
+    /// creating or upgrading it. Writes through this handle fail.
+    pub async fn open_read_only(db_path: &str) -> Result<Self> {
+        let expanded_path = shellexpand::tilde(db_path).to_string();
+        let db_url = format!("sqlite://{}?mode=ro", expanded_path.replace('\\', "/"));
+
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .map_err(crate::error::DbError::SqlxError)?
+            .read_only(true)
+            .busy_timeout(BUSY_TIMEOUT);
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(Self { pool, cipher: None })
+    }
+
+    /// Open an empty database in memory
+    ///
+    /// For working through a command without touching the real database,
+    /// e.g. a dry run before any database exists. It is migrated like a new
+    /// database, and everything in it is gone once it is dropped.
+    pub async fn in_memory() -> Result<Self> {
+        // Each connection to :memory: is a database of its own, so keep one
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect("sqlite::memory:")
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .map_err(crate::error::DbError::MigrationError)?;
+
+        Ok(Self { pool, cipher: None })
+    }
+
+    /// Open the database described by a configuration
+    ///
+    /// Resolves the database path (honouring `PLURCAST_DB_PATH`) and, when
+    /// `database.encrypt_content` is set, loads the content key from the
+    /// credential store, creating it on first use.
+    pub async fn from_config(config: &Config) -> Result<Self> {
+        let db_path = crate::config::resolve_db_path(Some(&config.database.path))?;
+        let db = Self::new(&db_path.to_string_lossy()).await?;
+
+        if !config.database.encrypt_content {
+            return Ok(db);
+        }
+
+        let manager = CredentialManager::new(config.credentials.clone().unwrap_or_default())?;
+        let cipher = ContentCipher::load_or_create(&manager)?;
+        Ok(db.with_cipher(cipher))
+    }
+
+    /// Open the database described by a configuration read-only
+    ///
+    /// Like [`Self::from_config`], but the database must exist and is not
+    /// migrated (see [`Self::open_read_only`]), and the content key is only
+    /// loaded, never created: without one, nothing was encrypted yet.
+    pub async fn open_read_only_from_config(config: &Config) -> Result<Self> {
+        let db_path = crate::config::resolve_db_path(Some(&config.database.path))?;
+        let db = Self::open_read_only(&db_path.to_string_lossy()).await?;
+
+        if !config.database.encrypt_content {
+            return Ok(db);
+        }
+
+        let manager = CredentialManager::new(config.credentials.clone().unwrap_or_default())?;
+        Ok(match ContentCipher::load(&manager)? {
+            Some(cipher) => db.with_cipher(cipher),
+            None => db,
+        })
+    }
+
+    /// Encrypt post content with the given cipher on write
+    pub fn with_cipher(mut self, cipher: ContentCipher) -> Self {
+        self.cipher = Some(Arc::new(cipher));
+        self
+    }
+
+    /// Whether post content is encrypted on write
+    pub fn is_encrypted(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    /// Prepare post content for storage
+    fn seal_content(&self, content: &str) -> Result<String> {
+        match &self.cipher {
+            Some(cipher) => cipher.encrypt(content),
+            None => Ok(content.to_string()),
+        }
+    }
+
+    /// Recover post content read from storage
+    ///
+    /// Needed by callers that read the `content` column with raw SQL. Without
+    /// a cipher, encrypted values are returned as stored.
+    pub fn open_content(&self, stored: String) -> Result<String> {
+        match &self.cipher {
+            Some(cipher) => cipher.decrypt(&stored),
+            None => Ok(stored),
+        }
+    }
+
+    /// Decrypt the content of each post in a list
+    fn open_posts(&self, posts: Vec<Post>) -> Result<Vec<Post>> {
+        posts
+            .into_iter()
+            .map(|mut post| {
+                post.content = self.open_content(post.content)?;
+                Ok(post)
+            })
+            .collect()
     }
 
     /// Create a new post
@@ -63,7 +344,10 @@ impl Database {
             PostStatus::Pending => "pending",
             PostStatus::Posted => "posted",
             PostStatus::Failed => "failed",
+            PostStatus::Deleted => "deleted",
+            PostStatus::Missed => "missed",
         };
+        let content = self.seal_content(&post.content)?;
 
         sqlx::query(
             r#"
@@ -72,7 +356,7 @@ impl Database {
             "#,
         )
         .bind(&post.id)
-        .bind(&post.content)
+        .bind(content)
         .bind(post.created_at)
         .bind(post.scheduled_at)
         .bind(status_str)
@@ -92,6 +376,8 @@ impl Database {
             PostStatus::Pending => "pending",
             PostStatus::Posted => "posted",
             PostStatus::Failed => "failed",
+            PostStatus::Deleted => "deleted",
+            PostStatus::Missed => "missed",
         };
 
         sqlx::query(
@@ -110,6 +396,8 @@ impl Database {
 
     /// Update post content
     pub async fn update_post_content(&self, post_id: &str, content: String) -> Result<()> {
+        let content = self.seal_content(&content)?;
+
         sqlx::query(
             r#"
             UPDATE posts SET content = ? WHERE id = ?
@@ -139,31 +427,53 @@ impl Database {
         .await
         .map_err(crate::error::DbError::SqlxError)?;
 
-        Ok(row.map(|r| Post {
-            id: r.get("id"),
-            content: r.get("content"),
-            created_at: r.get("created_at"),
-            scheduled_at: r.get("scheduled_at"),
-            status: match r.get::<String, _>("status").as_str() {
-                "draft" => PostStatus::Draft,
-                "scheduled" => PostStatus::Scheduled,
-                "pending" => PostStatus::Pending,
-                "posted" => PostStatus::Posted,
-                "failed" => PostStatus::Failed,
-                _ => PostStatus::Pending,
-            },
-            metadata: r.get("metadata"),
-        }))
+        row.map(|r| -> Result<Post> {
+            Ok(Post {
+                id: r.get("id"),
+                content: self.open_content(r.get("content"))?,
+                created_at: r.get("created_at"),
+                scheduled_at: r.get("scheduled_at"),
+                status: match r.get::<String, _>("status").as_str() {
+                    "draft" => PostStatus::Draft,
+                    "scheduled" => PostStatus::Scheduled,
+                    "pending" => PostStatus::Pending,
+                    "posted" => PostStatus::Posted,
+                    "failed" => PostStatus::Failed,
+                    "deleted" => PostStatus::Deleted,
+                    "missed" => PostStatus::Missed,
+                    _ => PostStatus::Pending,
+                },
+                metadata: r.get("metadata"),
+            })
+        })
+        .transpose()
     }
 
     /// Create a post record
     pub async fn create_post_record(&self, record: &PostRecord) -> Result<()> {
+        self.create_post_record_with_response(record, None).await?;
+        Ok(())
+    }
+
+    /// Create a post record together with the platform's raw response
+    ///
+    /// The response is stored as JSON (encrypted when content encryption is
+    /// enabled) and can be read back with [`get_raw_responses`](Self::get_raw_responses).
+    /// Returns the new record's ID.
+    pub async fn create_post_record_with_response(
+        &self,
+        record: &PostRecord,
+        raw_response: Option<&serde_json::Value>,
+    ) -> Result<i64> {
         let success = if record.success { 1 } else { 0 };
+        let raw_response = raw_response
+            .map(|raw| self.seal_content(&raw.to_string()))
+            .transpose()?;
 
-        sqlx::query(
+        let result = sqlx::query(
             r#"
-            INSERT INTO post_records (post_id, platform, platform_post_id, posted_at, success, error_message, account_name)
-            VALUES (?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO post_records (post_id, platform, platform_post_id, posted_at, success, error_message, account_name, raw_response)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&record.post_id)
@@ -173,11 +483,12 @@ impl Database {
         .bind(success)
         .bind(&record.error_message)
         .bind(&record.account_name)
+        .bind(raw_response)
         .execute(&self.pool)
         .await
         .map_err(crate::error::DbError::SqlxError)?;
 
-        Ok(())
+        Ok(result.last_insert_rowid())
     }
 
     /// Query posts with all platform records
@@ -189,25 +500,31 @@ impl Database {
         search: Option<&str>,
         limit: usize,
     ) -> Result<Vec<PostWithRecords>> {
-        use sqlx::Row;
+        self.query_posts(&PostQuery {
+            platform: platform.map(String::from),
+            since,
+            until,
+            search: search.map(String::from),
+            label: None,
+            account: None,
+            status: None,
+            before: None,
+            include_deleted: false,
+            limit: Some(limit),
+        })
+        .await
+    }
 
-        // Build the WHERE clause dynamically
-        let mut where_clauses = vec!["1=1"];
+    /// Query posts with all platform records using a [`PostQuery`]
+    pub async fn query_posts(&self, filter: &PostQuery) -> Result<Vec<PostWithRecords>> {
+        use sqlx::Row;
 
-        if platform.is_some() {
-            where_clauses.push("pr.platform = ?");
-        }
-        if since.is_some() {
-            where_clauses.push("p.created_at >= ?");
-        }
-        if until.is_some() {
-            where_clauses.push("p.created_at <= ?");
-        }
-        if search.is_some() {
-            where_clauses.push("p.content LIKE ?");
-        }
+        let search = filter.search.as_deref();
+        let limit = filter.limit.unwrap_or(usize::MAX);
 
-        let where_clause = where_clauses.join(" AND ");
+        // Encrypted content can't be matched in SQL; filter after decrypting
+        let sql_search = self.cipher.is_none();
+        let (where_clause, mut arguments) = filter.where_clause(sql_search)?;
 
         // First, get the post IDs that match the criteria
         let query_str = format!(
@@ -216,30 +533,24 @@ impl Database {
             FROM posts p
             LEFT JOIN post_records pr ON p.id = pr.post_id
             WHERE {}
-            ORDER BY p.created_at DESC
+            ORDER BY p.created_at DESC, p.id DESC
             LIMIT ?
             "#,
             where_clause
         );
 
-        let mut query = sqlx::query(&query_str);
-
-        // Bind parameters in the same order as WHERE clauses
-        if let Some(plat) = platform {
-            query = query.bind(plat);
-        }
-        if let Some(s) = since {
-            query = query.bind(s);
-        }
-        if let Some(u) = until {
-            query = query.bind(u);
-        }
-        if let Some(search_term) = search {
-            query = query.bind(format!("%{}%", search_term));
-        }
-        query = query.bind(limit as i64);
+        // LIMIT -1 is unbounded in SQLite; the limit is applied while filtering
+        let post_filter = search.filter(|_| !sql_search);
+        add_argument(
+            &mut arguments,
+            if post_filter.is_some() || filter.limit.is_none() {
+                -1
+            } else {
+                limit as i64
+            },
+        )?;
 
-        let rows = query
+        let rows = sqlx::query_with(&query_str, arguments)
             .fetch_all(&self.pool)
             .await
             .map_err(crate::error::DbError::SqlxError)?;
@@ -247,9 +558,18 @@ impl Database {
         let post_ids: Vec<String> = rows.iter().map(|r| r.get("id")).collect();
 
         // Now fetch full post data with records for these IDs
+        let needle = post_filter.map(|s| s.to_lowercase());
         let mut results = Vec::new();
         for post_id in post_ids {
+            if results.len() >= limit {
+                break;
+            }
             if let Some(post) = self.get_post(&post_id).await? {
+                if let Some(needle) = &needle {
+                    if !post.content.to_lowercase().contains(needle.as_str()) {
+                        continue;
+                    }
+                }
                 let records = self.get_post_records(&post_id).await?;
                 results.push(PostWithRecords { post, records });
             }
@@ -290,6 +610,103 @@ impl Database {
             .collect())
     }
 
+    /// Get post records written after the record with this ID, oldest first
+    ///
+    /// Record IDs only grow, so passing the last ID seen gives whatever has
+    /// been written since (used by `plur-history --follow`).
+    pub async fn get_post_records_after(
+        &self,
+        after_id: i64,
+        limit: usize,
+    ) -> Result<Vec<PostRecord>> {
+        use sqlx::Row;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, post_id, platform, platform_post_id, posted_at, success, error_message, account_name
+            FROM post_records
+            WHERE id > ?
+            ORDER BY id ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(after_id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(rows
+            .iter()
+            .map(|r| PostRecord {
+                id: r.get("id"),
+                post_id: r.get("post_id"),
+                platform: r.get("platform"),
+                platform_post_id: r.get("platform_post_id"),
+                posted_at: r.get("posted_at"),
+                success: r.get::<i32, _>("success") != 0,
+                error_message: r.get("error_message"),
+                account_name: r.get("account_name"),
+            })
+            .collect())
+    }
+
+    /// Get the ID of the newest post record, if there is one
+    pub async fn get_last_post_record_id(&self) -> Result<Option<i64>> {
+        let row: (Option<i64>,) = sqlx::query_as("SELECT MAX(id) FROM post_records")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(row.0)
+    }
+
+    /// Get the raw platform responses stored for a post
+    ///
+    /// Returns a map of platform name -> response JSON for successful records
+    /// that have one. If a post was published to a platform more than once
+    /// (e.g. after a retry), the most recent response wins.
+    pub async fn get_raw_responses(
+        &self,
+        post_id: &str,
+    ) -> Result<HashMap<String, serde_json::Value>> {
+        use sqlx::Row;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT platform, raw_response
+            FROM post_records
+            WHERE post_id = ? AND success = 1 AND raw_response IS NOT NULL
+            ORDER BY posted_at ASC, id ASC
+            "#,
+        )
+        .bind(post_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        let mut responses = HashMap::new();
+        for row in rows {
+            let platform: String = row.get("platform");
+            let raw = self.open_content(row.get("raw_response"))?;
+            match serde_json::from_str(&raw) {
+                Ok(value) => {
+                    responses.insert(platform, value);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Ignoring unreadable {} response for {}: {}",
+                        platform,
+                        post_id,
+                        e
+                    );
+                }
+            }
+        }
+
+        Ok(responses)
+    }
+
     /// Get platform-specific post IDs for a plurcast post UUID
     ///
     /// Returns a map of platform name -> platform_post_id for posts that
@@ -378,6 +795,37 @@ impl Database {
         Ok(row.map(|r| r.get("post_id")))
     }
 
+    /// Whether any post replies to this one as part of a thread
+    ///
+    /// Matches replies sent right away (`reply_to` holding one of this
+    /// post's platform IDs) and scheduled thread parts (`thread_parent_uuid`).
+    pub async fn has_thread_replies(&self, post_id: &str) -> Result<bool> {
+        let row = sqlx::query(
+            r#"
+            SELECT 1
+            FROM posts p
+            WHERE CASE WHEN json_valid(p.metadata) THEN (
+                json_extract(p.metadata, '$.thread_parent_uuid') = ?
+                OR EXISTS (
+                    SELECT 1
+                    FROM json_each(p.metadata, '$.reply_to') r
+                    JOIN post_records pr
+                      ON pr.platform = r.key AND pr.platform_post_id = r.value
+                    WHERE pr.post_id = ? AND pr.success = 1
+                )
+            ) ELSE 0 END
+            LIMIT 1
+            "#,
+        )
+        .bind(post_id)
+        .bind(post_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(row.is_some())
+    }
+
     /// Filter posts by platform
     pub async fn filter_by_platform(
         &self,
@@ -419,10 +867,19 @@ impl Database {
     /// - status = 'scheduled'
     /// - scheduled_at <= now (due or overdue)
     ///
+    /// Ordered by priority (highest first), then scheduled_at.
     /// Used by plur-send daemon to find posts that need to be sent.
     pub async fn get_scheduled_posts_due(&self) -> Result<Vec<Post>> {
-        let now = chrono::Utc::now().timestamp();
+        self.get_scheduled_posts_due_by(chrono::Utc::now().timestamp())
+            .await
+    }
 
+    /// Get scheduled posts due at or before `until`
+    ///
+    /// Like [`get_scheduled_posts_due`](Self::get_scheduled_posts_due) with a
+    /// different cutoff; plur-send looks ahead so jittered posts can go out
+    /// early.
+    pub async fn get_scheduled_posts_due_by(&self, until: i64) -> Result<Vec<Post>> {
         let rows = sqlx::query_as::<_, (String, String, i64, Option<i64>, String, Option<String>)>(
             r#"
             SELECT id, content, created_at, scheduled_at, status, metadata
@@ -430,10 +887,10 @@ impl Database {
             WHERE status = 'scheduled'
               AND scheduled_at IS NOT NULL
               AND scheduled_at <= ?
-            ORDER BY scheduled_at ASC
+            ORDER BY priority DESC, scheduled_at ASC
             "#,
         )
-        .bind(now)
+        .bind(until)
         .fetch_all(&self.pool)
         .await
         .map_err(crate::error::DbError::SqlxError)?;
@@ -448,6 +905,8 @@ impl Database {
                         "pending" => PostStatus::Pending,
                         "posted" => PostStatus::Posted,
                         "failed" => PostStatus::Failed,
+                        "deleted" => PostStatus::Deleted,
+                        "missed" => PostStatus::Missed,
                         _ => PostStatus::Pending,
                     };
 
@@ -463,7 +922,7 @@ impl Database {
             )
             .collect();
 
-        Ok(posts)
+        self.open_posts(posts)
     }
 
     /// Get all scheduled posts (for plur-queue list)
@@ -492,6 +951,54 @@ impl Database {
                         "pending" => PostStatus::Pending,
                         "posted" => PostStatus::Posted,
                         "failed" => PostStatus::Failed,
+                        "deleted" => PostStatus::Deleted,
+                        "missed" => PostStatus::Missed,
+                        _ => PostStatus::Pending,
+                    };
+
+                    Post {
+                        id,
+                        content,
+                        created_at,
+                        scheduled_at,
+                        status,
+                        metadata,
+                    }
+                },
+            )
+            .collect();
+
+        self.open_posts(posts)
+    }
+
+    /// Get posts held by the catch-up policy
+    ///
+    /// Returns posts with status 'missed', ordered by scheduled_at.
+    pub async fn get_missed_posts(&self) -> Result<Vec<Post>> {
+        let rows = sqlx::query_as::<_, (String, String, i64, Option<i64>, String, Option<String>)>(
+            r#"
+            SELECT id, content, created_at, scheduled_at, status, metadata
+            FROM posts
+            WHERE status = 'missed'
+            ORDER BY scheduled_at ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        let posts = rows
+            .into_iter()
+            .map(
+                |(id, content, created_at, scheduled_at, status, metadata)| {
+                    let status = match status.as_str() {
+                        "draft" => PostStatus::Draft,
+                        "scheduled" => PostStatus::Scheduled,
+                        "pending" => PostStatus::Pending,
+                        "posted" => PostStatus::Posted,
+                        "failed" => PostStatus::Failed,
+                        "deleted" => PostStatus::Deleted,
+                        "missed" => PostStatus::Missed,
                         _ => PostStatus::Pending,
                     };
 
@@ -507,7 +1014,7 @@ impl Database {
             )
             .collect();
 
-        Ok(posts)
+        self.open_posts(posts)
     }
 
     /// Get all failed posts that may need retry
@@ -536,6 +1043,8 @@ impl Database {
                         "pending" => PostStatus::Pending,
                         "posted" => PostStatus::Posted,
                         "failed" => PostStatus::Failed,
+                        "deleted" => PostStatus::Deleted,
+                        "missed" => PostStatus::Missed,
                         _ => PostStatus::Pending,
                     };
 
@@ -551,7 +1060,7 @@ impl Database {
             )
             .collect();
 
-        Ok(posts)
+        self.open_posts(posts)
     }
 
     /// Get the most recent scheduled_at timestamp from all scheduled posts
@@ -572,26 +1081,113 @@ impl Database {
         Ok(row.0)
     }
 
-    /// Update the scheduled_at time for a post
+    /// Get the publish time of every post successfully published since `since`
     ///
-    /// Used by plur-queue reschedule command.
-    pub async fn update_post_schedule(
-        &self,
-        post_id: &str,
-        scheduled_at: Option<i64>,
-    ) -> Result<()> {
-        sqlx::query(
+    /// Used by best-time scheduling. A post published to several platforms
+    /// counts once, at its first successful publish.
+    pub async fn get_publish_times(&self, since: i64) -> Result<Vec<i64>> {
+        let rows = sqlx::query_as::<_, (i64,)>(
             r#"
-            UPDATE posts SET scheduled_at = ? WHERE id = ?
+            SELECT MIN(posted_at) FROM post_records
+            WHERE success = 1 AND posted_at IS NOT NULL AND posted_at >= ?
+            GROUP BY post_id
+            ORDER BY 1
             "#,
         )
-        .bind(scheduled_at)
-        .bind(post_id)
-        .execute(&self.pool)
+        .bind(since)
+        .fetch_all(&self.pool)
         .await
         .map_err(crate::error::DbError::SqlxError)?;
 
-        Ok(())
+        Ok(rows.into_iter().map(|(posted_at,)| posted_at).collect())
+    }
+
+    /// Get the queue priority of a post
+    ///
+    /// Returns None if the post doesn't exist.
+    pub async fn get_post_priority(&self, post_id: &str) -> Result<Option<i64>> {
+        let priority = sqlx::query_scalar("SELECT priority FROM posts WHERE id = ?")
+            .bind(post_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(priority)
+    }
+
+    /// Get the queue priority of every scheduled post, keyed by post ID
+    pub async fn get_scheduled_priorities(&self) -> Result<HashMap<String, i64>> {
+        let rows = sqlx::query_as::<_, (String, i64)>(
+            "SELECT id, priority FROM posts WHERE status = 'scheduled'",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Set the queue priority of a post
+    ///
+    /// Used by plur-queue bump command. Returns false if the post doesn't exist.
+    pub async fn set_post_priority(&self, post_id: &str, priority: i64) -> Result<bool> {
+        let result = sqlx::query("UPDATE posts SET priority = ? WHERE id = ?")
+            .bind(priority)
+            .bind(post_id)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Update the scheduled_at time for a post
+    ///
+    /// Used by plur-queue reschedule command. A manual reschedule replaces
+    /// any deferral by plur-send, so the deferral record is dropped.
+    pub async fn update_post_schedule(
+        &self,
+        post_id: &str,
+        scheduled_at: Option<i64>,
+    ) -> Result<()> {
+        // Staggered posts keep their spacing: unsent platforms move by the
+        // same amount as the post
+        if let Some(scheduled_at) = scheduled_at {
+            sqlx::query(
+                r#"
+                UPDATE platform_schedules
+                SET scheduled_at = scheduled_at + (? - (SELECT scheduled_at FROM posts WHERE id = ?))
+                WHERE post_id = ? AND sent_at IS NULL
+                  AND (SELECT scheduled_at FROM posts WHERE id = ?) IS NOT NULL
+                "#,
+            )
+            .bind(scheduled_at)
+            .bind(post_id)
+            .bind(post_id)
+            .bind(post_id)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE posts SET scheduled_at = ? WHERE id = ?
+            "#,
+        )
+        .bind(scheduled_at)
+        .bind(post_id)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        sqlx::query("DELETE FROM post_deferrals WHERE post_id = ?")
+            .bind(post_id)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(())
     }
 
     /// Update post metadata
@@ -629,11 +1225,61 @@ impl Database {
         Ok(())
     }
 
-    /// Delete a scheduled post
+    /// Permanently delete a post and its records
     ///
-    /// Used by plur-queue cancel command.
+    /// plur-queue uses [`soft_delete_post`](Self::soft_delete_post) so
+    /// deletions stay auditable; this is for purging.
     pub async fn delete_post(&self, post_id: &str) -> Result<()> {
-        // Delete post records first (foreign key constraint)
+        // Drop the tombstone, deferral, platform schedules, deletions and checks, if any
+        sqlx::query("DELETE FROM post_tombstones WHERE post_id = ?")
+            .bind(post_id)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        sqlx::query("DELETE FROM post_deferrals WHERE post_id = ?")
+            .bind(post_id)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        sqlx::query("DELETE FROM platform_schedules WHERE post_id = ?")
+            .bind(post_id)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        sqlx::query("DELETE FROM scheduled_deletions WHERE post_id = ?")
+            .bind(post_id)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        sqlx::query("DELETE FROM engagement WHERE post_id = ?")
+            .bind(post_id)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        sqlx::query("DELETE FROM post_checks WHERE post_id = ?")
+            .bind(post_id)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        // Delete relay results and post records first (foreign key constraint)
+        sqlx::query(
+            r#"
+            DELETE FROM relay_results WHERE post_record_id IN (
+                SELECT id FROM post_records WHERE post_id = ?
+            )
+            "#,
+        )
+        .bind(post_id)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
         sqlx::query(
             r#"
             DELETE FROM post_records WHERE post_id = ?
@@ -1063,2299 +1709,5041 @@ impl Database {
             })
             .collect())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::error::{DbError, PlurcastError};
-    use crate::types::{Post, PostRecord, PostStatus};
-    use tempfile::TempDir;
+    // ========================================================================
+    // Label methods
+    // ========================================================================
 
-    /// Helper to create a test post
-    fn create_test_post() -> Post {
-        Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Test post content".to_string(),
-            created_at: chrono::Utc::now().timestamp(),
-            scheduled_at: None,
-            status: PostStatus::Pending,
-            metadata: None,
+    /// Attach labels to a post
+    ///
+    /// Labels the post already has are left alone.
+    pub async fn add_labels(&self, post_id: &str, labels: &[String]) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+
+        for label in labels {
+            sqlx::query(
+                r#"
+                INSERT OR IGNORE INTO post_labels (post_id, label, created_at)
+                VALUES (?, ?, ?)
+                "#,
+            )
+            .bind(post_id)
+            .bind(label)
+            .bind(now)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
         }
+
+        Ok(())
     }
 
-    #[tokio::test]
-    async fn test_database_initialization_with_invalid_path() {
-        // Test with a path that cannot be created (e.g., null byte in path on Unix)
-        // On Windows, we'll use an invalid character
-        #[cfg(unix)]
-        let invalid_path = "/tmp/test\0invalid.db";
+    /// Remove a label from a post
+    ///
+    /// Returns whether the post had the label.
+    pub async fn remove_label(&self, post_id: &str, label: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM post_labels WHERE post_id = ? AND label = ?")
+            .bind(post_id)
+            .bind(label)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
 
-        #[cfg(windows)]
-        let invalid_path = "C:\\invalid<>path\\test.db";
+        Ok(result.rows_affected() > 0)
+    }
 
-        let result = Database::new(invalid_path).await;
-        assert!(result.is_err(), "Expected error for invalid path");
+    /// Get the labels on a post, sorted alphabetically
+    pub async fn get_labels(&self, post_id: &str) -> Result<Vec<String>> {
+        let labels =
+            sqlx::query_scalar("SELECT label FROM post_labels WHERE post_id = ? ORDER BY label")
+                .bind(post_id)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
 
-        // Verify it's a database error
-        match result {
-            Err(PlurcastError::Database(_)) => {
-                // Success - got the expected error type
-            }
-            _ => panic!("Expected DbError for invalid path"),
-        }
+        Ok(labels)
     }
 
-    #[tokio::test]
-    async fn test_database_initialization_with_readonly_parent() {
-        // Note: This test is platform-specific and may behave differently on Windows vs Unix
-        // On Windows, readonly attribute on directories doesn't prevent file creation
-        // On Unix, we can make a directory truly read-only
+    /// List every label in use with the number of posts carrying it
+    pub async fn list_labels(&self) -> Result<Vec<(String, i64)>> {
+        let labels = sqlx::query_as(
+            r#"
+            SELECT label, COUNT(*) AS posts
+            FROM post_labels
+            GROUP BY label
+            ORDER BY label
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
 
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
+        Ok(labels)
+    }
 
-            // Create a temporary directory and make it read-only
-            let temp_dir = TempDir::new().unwrap();
-            let readonly_dir = temp_dir.path().join("readonly");
-            std::fs::create_dir(&readonly_dir).unwrap();
+    // ========================================================================
+    // Attempt methods
+    // ========================================================================
 
-            // Set directory to read-only (no write permission)
-            let mut perms = std::fs::metadata(&readonly_dir).unwrap().permissions();
-            perms.set_mode(0o444); // Read-only
-            std::fs::set_permissions(&readonly_dir, perms).unwrap();
+    /// Record the timing of one publish attempt
+    pub async fn create_post_attempt(&self, attempt: &PostAttempt) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO post_attempts (
+                post_id, platform, attempt, started_at_ms, finished_at_ms,
+                duration_ms, success, error_message
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&attempt.post_id)
+        .bind(&attempt.platform)
+        .bind(attempt.attempt as i64)
+        .bind(attempt.started_at_ms)
+        .bind(attempt.finished_at_ms)
+        .bind(attempt.duration_ms)
+        .bind(attempt.success)
+        .bind(&attempt.error_message)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
 
-            let db_path = readonly_dir.join("test.db");
-            let result = Database::new(db_path.to_str().unwrap()).await;
+        Ok(())
+    }
 
-            // Clean up permissions before asserting
-            let mut perms = std::fs::metadata(&readonly_dir).unwrap().permissions();
-            perms.set_mode(0o755);
-            std::fs::set_permissions(&readonly_dir, perms).unwrap();
+    /// Get every publish attempt for a post, oldest first
+    pub async fn get_post_attempts(&self, post_id: &str) -> Result<Vec<PostAttempt>> {
+        use sqlx::Row;
 
-            assert!(
-                result.is_err(),
-                "Expected error for read-only directory on Unix"
-            );
-        }
+        let rows = sqlx::query(
+            r#"
+            SELECT post_id, platform, attempt, started_at_ms, finished_at_ms,
+                   duration_ms, success, error_message
+            FROM post_attempts
+            WHERE post_id = ?
+            ORDER BY started_at_ms ASC, id ASC
+            "#,
+        )
+        .bind(post_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
 
-        #[cfg(windows)]
-        {
-            // On Windows, the readonly attribute on directories doesn't prevent file creation
-            // So we skip this test or test a different scenario
-            // We'll test that we can still create a database in a normal directory
-            let temp_dir = TempDir::new().unwrap();
-            let db_path = temp_dir.path().join("test.db");
-            let result = Database::new(db_path.to_str().unwrap()).await;
-            assert!(
-                result.is_ok(),
-                "Should be able to create database on Windows"
-            );
-        }
+        Ok(rows
+            .iter()
+            .map(|row| PostAttempt {
+                post_id: row.get("post_id"),
+                platform: row.get("platform"),
+                attempt: row.get::<i64, _>("attempt") as u32,
+                started_at_ms: row.get("started_at_ms"),
+                finished_at_ms: row.get("finished_at_ms"),
+                duration_ms: row.get("duration_ms"),
+                success: row.get("success"),
+                error_message: row.get("error_message"),
+            })
+            .collect())
     }
 
-    #[tokio::test]
-    async fn test_foreign_key_constraint_enforcement() {
-        // Use in-memory database for testing
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        // Try to create a post_record without a corresponding post
-        let record = PostRecord {
-            id: None,
-            post_id: "nonexistent-post-id".to_string(),
-            platform: "nostr".to_string(),
-            platform_post_id: Some("note1abc".to_string()),
-            posted_at: Some(chrono::Utc::now().timestamp()),
-            success: true,
-            error_message: None,
-            account_name: "default".to_string(),
-        };
+    // ========================================================================
+    // Relay result methods
+    // ========================================================================
 
-        // Enable foreign key constraints (SQLite has them off by default in some configurations)
-        sqlx::query("PRAGMA foreign_keys = ON")
-            .execute(&db.pool)
+    /// Record how each relay answered for a post record
+    pub async fn create_relay_results(
+        &self,
+        post_record_id: i64,
+        results: &[RelayResult],
+    ) -> Result<()> {
+        for result in results {
+            sqlx::query(
+                r#"
+                INSERT INTO relay_results (post_record_id, relay_url, accepted, message, latency_ms)
+                VALUES (?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(post_record_id)
+            .bind(&result.relay_url)
+            .bind(result.accepted)
+            .bind(&result.message)
+            .bind(result.latency_ms)
+            .execute(&self.pool)
             .await
-            .unwrap();
+            .map_err(crate::error::DbError::SqlxError)?;
+        }
 
-        let result = db.create_post_record(&record).await;
+        Ok(())
+    }
 
-        // This should fail due to foreign key constraint
-        assert!(result.is_err(), "Expected foreign key constraint violation");
+    /// Get the relay results of a post, keyed by post record ID
+    ///
+    /// Relays are ordered by URL within each record.
+    pub async fn get_relay_results(&self, post_id: &str) -> Result<HashMap<i64, Vec<RelayResult>>> {
+        use sqlx::Row;
 
-        match result {
-            Err(PlurcastError::Database(DbError::SqlxError(sqlx::Error::Database(db_err)))) => {
-                // Verify it's a foreign key constraint error
-                let message = db_err.message();
-                assert!(
-                    message.contains("FOREIGN KEY") || message.contains("foreign key"),
-                    "Expected foreign key error, got: {}",
-                    message
-                );
-            }
-            _ => panic!("Expected foreign key constraint error"),
+        let rows = sqlx::query(
+            r#"
+            SELECT rr.post_record_id, rr.relay_url, rr.accepted, rr.message, rr.latency_ms
+            FROM relay_results rr
+            JOIN post_records pr ON pr.id = rr.post_record_id
+            WHERE pr.post_id = ?
+            ORDER BY rr.post_record_id ASC, rr.relay_url ASC
+            "#,
+        )
+        .bind(post_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        let mut results: HashMap<i64, Vec<RelayResult>> = HashMap::new();
+        for row in rows {
+            results
+                .entry(row.get("post_record_id"))
+                .or_default()
+                .push(RelayResult {
+                    relay_url: row.get("relay_url"),
+                    accepted: row.get("accepted"),
+                    message: row.get("message"),
+                    latency_ms: row.get("latency_ms"),
+                });
         }
+
+        Ok(results)
     }
 
-    #[tokio::test]
-    async fn test_foreign_key_constraint_with_valid_post() {
+    // ========================================================================
+    // Series methods
+    // ========================================================================
+
+    /// Create a recurring post series
+    pub async fn create_series(&self, series: &PostSeries) -> Result<()> {
+        let content = self.seal_content(&series.content)?;
+        let labels = serde_json::json!(series.labels).to_string();
+
+        sqlx::query(
+            r#"
+            INSERT INTO post_series (id, cron, content, metadata, labels, paused, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&series.id)
+        .bind(&series.cron)
+        .bind(content)
+        .bind(&series.metadata)
+        .bind(labels)
+        .bind(series.paused)
+        .bind(series.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(())
+    }
+
+    /// Get a series by ID
+    pub async fn get_series(&self, series_id: &str) -> Result<Option<PostSeries>> {
+        Ok(self
+            .list_series()
+            .await?
+            .into_iter()
+            .find(|series| series.id == series_id))
+    }
+
+    /// List every series, oldest first
+    pub async fn list_series(&self) -> Result<Vec<PostSeries>> {
+        let rows = sqlx::query_as::<
+            _,
+            (
+                String,
+                String,
+                String,
+                Option<String>,
+                Option<String>,
+                bool,
+                i64,
+            ),
+        >(
+            r#"
+            SELECT id, cron, content, metadata, labels, paused, created_at
+            FROM post_series
+            ORDER BY created_at ASC, id ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        rows.into_iter()
+            .map(
+                |(id, cron, content, metadata, labels, paused, created_at)| {
+                    Ok(PostSeries {
+                        id,
+                        cron,
+                        content: self.open_content(content)?,
+                        metadata,
+                        labels: labels
+                            .and_then(|labels| serde_json::from_str(&labels).ok())
+                            .unwrap_or_default(),
+                        paused,
+                        created_at,
+                    })
+                },
+            )
+            .collect()
+    }
+
+    /// Pause or resume a series
+    ///
+    /// Returns `false` if the series doesn't exist.
+    pub async fn set_series_paused(&self, series_id: &str, paused: bool) -> Result<bool> {
+        let result = sqlx::query("UPDATE post_series SET paused = ? WHERE id = ?")
+            .bind(paused)
+            .bind(series_id)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete a series
+    ///
+    /// Occurrences already created are left alone. Returns `false` if the
+    /// series doesn't exist.
+    pub async fn delete_series(&self, series_id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM post_series WHERE id = ?")
+            .bind(series_id)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ========================================================================
+    // Sync methods
+    // ========================================================================
+
+    /// IDs and last-change times of posts changed at or after `since`
+    ///
+    /// With `since: None`, every post is returned. Ordered oldest change first.
+    pub async fn find_posts_changed_since(&self, since: Option<i64>) -> Result<Vec<(String, i64)>> {
+        let changed = sqlx::query_as(
+            r#"
+            SELECT id, COALESCE(updated_at, created_at) AS changed_at
+            FROM posts
+            WHERE COALESCE(updated_at, created_at) >= ?
+            ORDER BY changed_at ASC, id ASC
+            "#,
+        )
+        .bind(since.unwrap_or(i64::MIN))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(changed)
+    }
+
+    /// When a post last changed, or `None` if it doesn't exist
+    pub async fn get_post_updated_at(&self, post_id: &str) -> Result<Option<i64>> {
+        let changed_at =
+            sqlx::query_scalar("SELECT COALESCE(updated_at, created_at) FROM posts WHERE id = ?")
+                .bind(post_id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(changed_at)
+    }
+
+    /// Write a post received from another device, keeping its change time
+    ///
+    /// Inserts the post if it's new, otherwise overwrites every field. The
+    /// caller decides whether the incoming version should win.
+    pub async fn put_synced_post(&self, post: &Post, updated_at: i64) -> Result<()> {
+        let status_str = match post.status {
+            PostStatus::Draft => "draft",
+            PostStatus::Scheduled => "scheduled",
+            PostStatus::Pending => "pending",
+            PostStatus::Posted => "posted",
+            PostStatus::Failed => "failed",
+            PostStatus::Deleted => "deleted",
+            PostStatus::Missed => "missed",
+        };
+        let content = self.seal_content(&post.content)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO posts (id, content, created_at, scheduled_at, status, metadata, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                content = excluded.content,
+                created_at = excluded.created_at,
+                scheduled_at = excluded.scheduled_at,
+                status = excluded.status,
+                metadata = excluded.metadata,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(&post.id)
+        .bind(content)
+        .bind(post.created_at)
+        .bind(post.scheduled_at)
+        .bind(status_str)
+        .bind(&post.metadata)
+        .bind(updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(())
+    }
+
+    /// Add a post record unless an identical one already exists
+    ///
+    /// Record IDs are local, so records are matched on their contents.
+    /// Returns whether the record was added.
+    pub async fn merge_post_record(&self, record: &PostRecord) -> Result<bool> {
+        let exists: Option<i64> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM post_records
+            WHERE post_id = ? AND platform = ? AND account_name = ?
+              AND success = ?
+              AND platform_post_id IS ? AND posted_at IS ? AND error_message IS ?
+            LIMIT 1
+            "#,
+        )
+        .bind(&record.post_id)
+        .bind(&record.platform)
+        .bind(&record.account_name)
+        .bind(record.success)
+        .bind(&record.platform_post_id)
+        .bind(record.posted_at)
+        .bind(&record.error_message)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        if exists.is_some() {
+            return Ok(false);
+        }
+
+        self.create_post_record(record).await?;
+        Ok(true)
+    }
+
+    // ========================================================================
+    // Tombstone methods
+    // ========================================================================
+
+    /// Mark a post deleted and record a tombstone
+    ///
+    /// The post and its records stay in the database (hidden from history
+    /// by default) until purged. Returns `false` if the post doesn't exist
+    /// or is already deleted.
+    pub async fn soft_delete_post(&self, post_id: &str, reason: Option<&str>) -> Result<bool> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        let previous_status: Option<String> =
+            sqlx::query_scalar("SELECT status FROM posts WHERE id = ?")
+                .bind(post_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+
+        let previous_status = match previous_status {
+            Some(status) if status != "deleted" => status,
+            _ => return Ok(false),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO post_tombstones (post_id, deleted_at, previous_status, reason)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(post_id)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(&previous_status)
+        .bind(reason)
+        .execute(&mut *tx)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        sqlx::query("UPDATE posts SET status = 'deleted' WHERE id = ?")
+            .bind(post_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        tx.commit()
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(true)
+    }
+
+    /// Get the tombstone of a deleted post
+    pub async fn get_tombstone(&self, post_id: &str) -> Result<Option<Tombstone>> {
+        Ok(self
+            .list_tombstones(None)
+            .await?
+            .into_iter()
+            .find(|t| t.post_id == post_id))
+    }
+
+    /// List tombstones, optionally only those deleted before a cutoff
+    pub async fn list_tombstones(&self, before: Option<i64>) -> Result<Vec<Tombstone>> {
+        let rows = sqlx::query_as::<_, (String, i64, String, Option<String>)>(
+            r#"
+            SELECT post_id, deleted_at, previous_status, reason
+            FROM post_tombstones
+            WHERE deleted_at < ?
+            ORDER BY deleted_at ASC
+            "#,
+        )
+        .bind(before.unwrap_or(i64::MAX))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(post_id, deleted_at, previous_status, reason)| Tombstone {
+                post_id,
+                deleted_at,
+                previous_status: match previous_status.as_str() {
+                    "draft" => PostStatus::Draft,
+                    "scheduled" => PostStatus::Scheduled,
+                    "pending" => PostStatus::Pending,
+                    "posted" => PostStatus::Posted,
+                    "failed" => PostStatus::Failed,
+                    _ => PostStatus::Pending,
+                },
+                reason,
+            })
+            .collect())
+    }
+
+    /// Permanently remove soft-deleted posts
+    ///
+    /// With `before`, only posts deleted before that Unix timestamp are
+    /// purged. Returns the IDs of the purged posts.
+    pub async fn purge_deleted_posts(&self, before: Option<i64>) -> Result<Vec<String>> {
+        let post_ids: Vec<String> = self
+            .list_tombstones(before)
+            .await?
+            .into_iter()
+            .map(|t| t.post_id)
+            .collect();
+
+        if !post_ids.is_empty() {
+            self.prune_posts(&post_ids).await?;
+        }
+
+        Ok(post_ids)
+    }
+
+    // ========================================================================
+    // Deferral methods
+    // ========================================================================
+
+    /// Push a scheduled post back to `until`, recording why
+    ///
+    /// Used by plur-send for daily/weekly caps and blackout windows. The
+    /// original scheduled time is kept across repeated deferrals.
+    pub async fn defer_post(&self, post_id: &str, until: i64, reason: &str) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO post_deferrals
+                (post_id, deferred_at, deferred_until, original_scheduled_at, reason)
+            SELECT id, ?, ?, scheduled_at, ? FROM posts WHERE id = ?
+            ON CONFLICT(post_id) DO UPDATE SET
+                deferred_at = excluded.deferred_at,
+                deferred_until = excluded.deferred_until,
+                reason = excluded.reason
+            "#,
+        )
+        .bind(chrono::Utc::now().timestamp())
+        .bind(until)
+        .bind(reason)
+        .bind(post_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        sqlx::query("UPDATE posts SET scheduled_at = ? WHERE id = ?")
+            .bind(until)
+            .bind(post_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        tx.commit()
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(())
+    }
+
+    /// Get the deferral of a post, if it was deferred
+    pub async fn get_deferral(&self, post_id: &str) -> Result<Option<PostDeferral>> {
+        let row = sqlx::query_as::<_, (String, i64, i64, Option<i64>, String)>(
+            r#"
+            SELECT post_id, deferred_at, deferred_until, original_scheduled_at, reason
+            FROM post_deferrals
+            WHERE post_id = ?
+            "#,
+        )
+        .bind(post_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(row.map(
+            |(post_id, deferred_at, deferred_until, original_scheduled_at, reason)| PostDeferral {
+                post_id,
+                deferred_at,
+                deferred_until,
+                original_scheduled_at,
+                reason,
+            },
+        ))
+    }
+
+    /// List deferrals of posts that are still scheduled
+    pub async fn list_deferred_posts(&self) -> Result<Vec<PostDeferral>> {
+        let rows = sqlx::query_as::<_, (String, i64, i64, Option<i64>, String)>(
+            r#"
+            SELECT d.post_id, d.deferred_at, d.deferred_until, d.original_scheduled_at, d.reason
+            FROM post_deferrals d
+            JOIN posts p ON p.id = d.post_id
+            WHERE p.status = 'scheduled'
+            ORDER BY d.deferred_until ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(post_id, deferred_at, deferred_until, original_scheduled_at, reason)| {
+                    PostDeferral {
+                        post_id,
+                        deferred_at,
+                        deferred_until,
+                        original_scheduled_at,
+                        reason,
+                    }
+                },
+            )
+            .collect())
+    }
+
+    // ========================================================================
+    // Queue pause methods
+    // ========================================================================
+
+    /// Pause scheduled posting
+    ///
+    /// Returns false if the queue was already paused.
+    pub async fn pause_queue(&self, reason: Option<&str>) -> Result<bool> {
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO queue_pause (id, paused_at, reason) VALUES (1, ?, ?)",
+        )
+        .bind(chrono::Utc::now().timestamp())
+        .bind(reason)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Resume scheduled posting
+    ///
+    /// Returns false if the queue wasn't paused.
+    pub async fn resume_queue(&self) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM queue_pause")
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Get the current pause, if the queue is paused
+    pub async fn get_queue_pause(&self) -> Result<Option<QueuePause>> {
+        let row = sqlx::query_as::<_, (i64, Option<String>)>(
+            "SELECT paused_at, reason FROM queue_pause WHERE id = 1",
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(row.map(|(paused_at, reason)| QueuePause { paused_at, reason }))
+    }
+
+    // ========================================================================
+    // Platform schedule methods
+    // ========================================================================
+
+    /// Give a post its own time on each platform
+    ///
+    /// Replaces any existing platform schedules of the post.
+    pub async fn set_platform_schedules(
+        &self,
+        post_id: &str,
+        schedules: &[(String, i64)],
+    ) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        sqlx::query("DELETE FROM platform_schedules WHERE post_id = ?")
+            .bind(post_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        for (platform, scheduled_at) in schedules {
+            sqlx::query(
+                r#"
+                INSERT INTO platform_schedules (post_id, platform, scheduled_at)
+                VALUES (?, ?, ?)
+                "#,
+            )
+            .bind(post_id)
+            .bind(platform)
+            .bind(scheduled_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(())
+    }
+
+    /// Get a post's platform schedules, earliest first
+    ///
+    /// Empty unless the post was scheduled per platform.
+    pub async fn get_platform_schedules(&self, post_id: &str) -> Result<Vec<PlatformSchedule>> {
+        let rows = sqlx::query_as::<_, (String, String, i64, Option<i64>)>(
+            r#"
+            SELECT post_id, platform, scheduled_at, sent_at
+            FROM platform_schedules
+            WHERE post_id = ?
+            ORDER BY scheduled_at ASC, platform ASC
+            "#,
+        )
+        .bind(post_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(post_id, platform, scheduled_at, sent_at)| PlatformSchedule {
+                    post_id,
+                    platform,
+                    scheduled_at,
+                    sent_at,
+                },
+            )
+            .collect())
+    }
+
+    /// Mark platforms of a staggered post as sent
+    ///
+    /// If other platforms are still to go, the post goes back to scheduled
+    /// at the earliest of their times, which is returned.
+    pub async fn mark_platforms_sent(
+        &self,
+        post_id: &str,
+        platforms: &[String],
+        sent_at: i64,
+    ) -> Result<Option<i64>> {
+        for platform in platforms {
+            sqlx::query(
+                r#"
+                UPDATE platform_schedules SET sent_at = ?
+                WHERE post_id = ? AND platform = ? AND sent_at IS NULL
+                "#,
+            )
+            .bind(sent_at)
+            .bind(post_id)
+            .bind(platform)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+        }
+
+        let next: Option<i64> = sqlx::query_scalar(
+            "SELECT MIN(scheduled_at) FROM platform_schedules WHERE post_id = ? AND sent_at IS NULL",
+        )
+        .bind(post_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        if let Some(next) = next {
+            sqlx::query("UPDATE posts SET status = 'scheduled', scheduled_at = ? WHERE id = ?")
+                .bind(next)
+                .bind(post_id)
+                .execute(&self.pool)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+        }
+
+        Ok(next)
+    }
+
+    // ========================================================================
+    // Send lease methods
+    // ========================================================================
+
+    /// Take or renew the send lease for `holder`
+    ///
+    /// Succeeds if nobody holds the lease, `holder` already does, or the
+    /// current holder hasn't renewed it for more than `ttl` seconds. Returns
+    /// false if another instance holds it.
+    pub async fn acquire_send_lease(&self, holder: &str, now: i64, ttl: i64) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO send_lease (id, holder, acquired_at, renewed_at)
+            VALUES (1, ?1, ?2, ?2)
+            ON CONFLICT(id) DO UPDATE SET
+                acquired_at = CASE WHEN holder = excluded.holder
+                    THEN acquired_at ELSE excluded.acquired_at END,
+                holder = excluded.holder,
+                renewed_at = excluded.renewed_at
+            WHERE holder = excluded.holder OR renewed_at < ?2 - ?3
+            "#,
+        )
+        .bind(holder)
+        .bind(now)
+        .bind(ttl)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Give up the send lease if `holder` has it
+    pub async fn release_send_lease(&self, holder: &str) -> Result<()> {
+        sqlx::query("DELETE FROM send_lease WHERE holder = ?")
+            .bind(holder)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(())
+    }
+
+    /// Get the current send lease, if any instance has taken it
+    pub async fn get_send_lease(&self) -> Result<Option<SendLease>> {
+        let row = sqlx::query_as::<_, (String, i64, i64)>(
+            "SELECT holder, acquired_at, renewed_at FROM send_lease WHERE id = 1",
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(row.map(|(holder, acquired_at, renewed_at)| SendLease {
+            holder,
+            acquired_at,
+            renewed_at,
+        }))
+    }
+
+    // ========================================================================
+    // Scheduled deletion methods
+    // ========================================================================
+
+    /// Schedule a published post to be deleted from its platform
+    ///
+    /// Returns the ID of the new scheduled deletion.
+    pub async fn schedule_deletion(
+        &self,
+        post_id: &str,
+        platform: &str,
+        platform_post_id: &str,
+        account_name: &str,
+        delete_at: i64,
+    ) -> Result<i64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO scheduled_deletions
+                (post_id, platform, platform_post_id, account_name, delete_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(post_id)
+        .bind(platform)
+        .bind(platform_post_id)
+        .bind(account_name)
+        .bind(delete_at)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Get deletions that are due and not yet done, earliest first
+    ///
+    /// Deletions that have failed `max_attempts` times are left out.
+    pub async fn get_due_deletions(
+        &self,
+        now: i64,
+        max_attempts: i64,
+    ) -> Result<Vec<ScheduledDeletion>> {
+        use sqlx::Row;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, post_id, platform, platform_post_id, account_name,
+                   delete_at, deleted_at, attempts, error
+            FROM scheduled_deletions
+            WHERE deleted_at IS NULL AND delete_at <= ? AND attempts < ?
+            ORDER BY delete_at ASC, id ASC
+            "#,
+        )
+        .bind(now)
+        .bind(max_attempts)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(rows
+            .iter()
+            .map(|row| ScheduledDeletion {
+                id: row.get("id"),
+                post_id: row.get("post_id"),
+                platform: row.get("platform"),
+                platform_post_id: row.get("platform_post_id"),
+                account_name: row.get("account_name"),
+                delete_at: row.get("delete_at"),
+                deleted_at: row.get("deleted_at"),
+                attempts: row.get("attempts"),
+                error: row.get("error"),
+            })
+            .collect())
+    }
+
+    /// Get a post's scheduled deletions
+    pub async fn get_post_deletions(&self, post_id: &str) -> Result<Vec<ScheduledDeletion>> {
+        use sqlx::Row;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, post_id, platform, platform_post_id, account_name,
+                   delete_at, deleted_at, attempts, error
+            FROM scheduled_deletions
+            WHERE post_id = ?
+            ORDER BY delete_at ASC, platform ASC
+            "#,
+        )
+        .bind(post_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(rows
+            .iter()
+            .map(|row| ScheduledDeletion {
+                id: row.get("id"),
+                post_id: row.get("post_id"),
+                platform: row.get("platform"),
+                platform_post_id: row.get("platform_post_id"),
+                account_name: row.get("account_name"),
+                delete_at: row.get("delete_at"),
+                deleted_at: row.get("deleted_at"),
+                attempts: row.get("attempts"),
+                error: row.get("error"),
+            })
+            .collect())
+    }
+
+    /// Record that a scheduled deletion went through
+    pub async fn mark_deletion_done(&self, id: i64, deleted_at: i64) -> Result<()> {
+        sqlx::query("UPDATE scheduled_deletions SET deleted_at = ?, error = NULL WHERE id = ?")
+            .bind(deleted_at)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(())
+    }
+
+    /// Record a failed attempt at a scheduled deletion
+    pub async fn record_deletion_failure(&self, id: i64, error: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE scheduled_deletions SET attempts = attempts + 1, error = ? WHERE id = ?",
+        )
+        .bind(error)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // Audit log methods
+    // ========================================================================
+
+    /// Append an entry to the audit log
+    ///
+    /// Returns the ID of the new entry.
+    pub async fn record_audit_event(
+        &self,
+        event: &str,
+        subject: &str,
+        actor: &str,
+        created_at: i64,
+    ) -> Result<i64> {
+        let result = sqlx::query(
+            "INSERT INTO audit_log (event, subject, actor, created_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(event)
+        .bind(subject)
+        .bind(actor)
+        .bind(created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Get the most recent audit log entries, newest first
+    pub async fn get_audit_events(&self, limit: i64) -> Result<Vec<AuditEvent>> {
+        use sqlx::Row;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, event, subject, actor, created_at
+            FROM audit_log
+            ORDER BY created_at DESC, id DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(rows
+            .iter()
+            .map(|row| AuditEvent {
+                id: row.get("id"),
+                event: row.get("event"),
+                subject: row.get("subject"),
+                actor: row.get("actor"),
+                created_at: row.get("created_at"),
+            })
+            .collect())
+    }
+
+    // ========================================================================
+    // Import tracking methods
+    // ========================================================================
+
+    /// Remember that an item from an import source became a post
+    ///
+    /// `source` names the import source (e.g. `rss:<feed url>`) and
+    /// `item_id` the item within it (e.g. the entry GUID). Recording the
+    /// same item again updates its post.
+    pub async fn record_imported_item(
+        &self,
+        source: &str,
+        item_id: &str,
+        post_id: &str,
+        imported_at: i64,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO imported_items (source, item_id, post_id, imported_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(source, item_id) DO UPDATE SET
+                post_id = excluded.post_id,
+                imported_at = excluded.imported_at
+            "#,
+        )
+        .bind(source)
+        .bind(item_id)
+        .bind(post_id)
+        .bind(imported_at)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(())
+    }
+
+    /// Whether an item from an import source has been imported before
+    pub async fn is_item_imported(&self, source: &str, item_id: &str) -> Result<bool> {
+        let row = sqlx::query("SELECT 1 FROM imported_items WHERE source = ? AND item_id = ?")
+            .bind(source)
+            .bind(item_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(row.is_some())
+    }
+
+    /// Where an import source left off, if it has been imported before
+    pub async fn get_import_cursor(&self, source: &str) -> Result<Option<String>> {
+        use sqlx::Row;
+
+        let row = sqlx::query("SELECT position FROM import_cursors WHERE source = ?")
+            .bind(source)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(row.map(|r| r.get("position")))
+    }
+
+    /// Save where an import source left off, replacing the previous position
+    pub async fn set_import_cursor(
+        &self,
+        source: &str,
+        position: &str,
+        updated_at: i64,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO import_cursors (source, position, updated_at)
+            VALUES (?, ?, ?)
+            ON CONFLICT(source) DO UPDATE SET
+                position = excluded.position,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(source)
+        .bind(position)
+        .bind(updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // Mention tracking methods
+    // ========================================================================
+
+    /// Record a mention as reported
+    ///
+    /// Returns `true` the first time a mention is recorded and `false` if it
+    /// had been seen before, so callers can report each mention once.
+    /// `post_id` is the Plurcast post the mention replies to, if any.
+    pub async fn mark_mention_seen(
+        &self,
+        platform: &str,
+        platform_post_id: &str,
+        post_id: Option<&str>,
+        seen_at: i64,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO seen_mentions (platform, platform_post_id, post_id, seen_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(platform)
+        .bind(platform_post_id)
+        .bind(post_id)
+        .bind(seen_at)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ========================================================================
+    // Bot feed methods
+    // ========================================================================
+
+    /// Record the first check of a bot feed
+    ///
+    /// Returns `true` if the feed had not been checked before, so the
+    /// entries it holds now can be marked seen instead of posted.
+    pub async fn add_bot_feed(&self, feed: &str, checked_at: i64) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO bot_feeds (feed, first_checked_at)
+            VALUES (?, ?)
+            "#,
+        )
+        .bind(feed)
+        .bind(checked_at)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// When a bot feed was first checked, if it has been
+    pub async fn get_bot_feed_first_checked(&self, feed: &str) -> Result<Option<i64>> {
+        let first_checked_at =
+            sqlx::query_scalar("SELECT first_checked_at FROM bot_feeds WHERE feed = ?")
+                .bind(feed)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(first_checked_at)
+    }
+
+    /// Whether a feed entry has been handled
+    pub async fn is_bot_entry_seen(&self, feed: &str, entry_id: &str) -> Result<bool> {
+        let seen: Option<i64> =
+            sqlx::query_scalar("SELECT 1 FROM bot_entries WHERE feed = ? AND entry_id = ?")
+                .bind(feed)
+                .bind(entry_id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(seen.is_some())
+    }
+
+    /// Record a feed entry as handled
+    ///
+    /// `post_id` is the Plurcast post made from the entry, or `None` if it
+    /// was skipped. Returns `false` if the entry had been recorded before.
+    pub async fn mark_bot_entry_seen(
+        &self,
+        feed: &str,
+        entry_id: &str,
+        post_id: Option<&str>,
+        seen_at: i64,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO bot_entries (feed, entry_id, post_id, seen_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(feed)
+        .bind(entry_id)
+        .bind(post_id)
+        .bind(seen_at)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// When a post was last made from a feed, if ever
+    pub async fn last_bot_post_at(&self, feed: &str) -> Result<Option<i64>> {
+        let last_post_at = sqlx::query_scalar(
+            "SELECT MAX(seen_at) FROM bot_entries WHERE feed = ? AND post_id IS NOT NULL",
+        )
+        .bind(feed)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(last_post_at)
+    }
+
+    // ========================================================================
+    // Engagement methods
+    // ========================================================================
+
+    /// Save the engagement of a published copy, replacing what was read
+    /// before
+    pub async fn save_engagement(&self, record: &EngagementRecord) -> Result<()> {
+        let engagement = &record.engagement;
+        sqlx::query(
+            r#"
+            INSERT INTO engagement (
+                platform, platform_post_id, post_id, replies, boosts, likes, zaps, zap_sats,
+                fetched_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(platform, platform_post_id) DO UPDATE SET
+                post_id = excluded.post_id,
+                replies = excluded.replies,
+                boosts = excluded.boosts,
+                likes = excluded.likes,
+                zaps = excluded.zaps,
+                zap_sats = excluded.zap_sats,
+                fetched_at = excluded.fetched_at
+            "#,
+        )
+        .bind(&record.platform)
+        .bind(&record.platform_post_id)
+        .bind(&record.post_id)
+        .bind(engagement.replies as i64)
+        .bind(engagement.boosts as i64)
+        .bind(engagement.likes as i64)
+        .bind(engagement.zaps as i64)
+        .bind(engagement.zap_sats as i64)
+        .bind(record.fetched_at)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(())
+    }
+
+    /// Engagement read for the copies of a post, by platform
+    pub async fn get_engagement(&self, post_id: &str) -> Result<Vec<EngagementRecord>> {
+        use sqlx::Row;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT platform, platform_post_id, replies, boosts, likes, zaps, zap_sats, fetched_at
+            FROM engagement
+            WHERE post_id = ?
+            ORDER BY platform, platform_post_id
+            "#,
+        )
+        .bind(post_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(rows
+            .iter()
+            .map(|r| EngagementRecord {
+                post_id: post_id.to_string(),
+                platform: r.get("platform"),
+                platform_post_id: r.get("platform_post_id"),
+                engagement: crate::platforms::Engagement {
+                    replies: r.get::<i64, _>("replies") as u64,
+                    boosts: r.get::<i64, _>("boosts") as u64,
+                    likes: r.get::<i64, _>("likes") as u64,
+                    zaps: r.get::<i64, _>("zaps") as u64,
+                    zap_sats: r.get::<i64, _>("zap_sats") as u64,
+                },
+                fetched_at: r.get("fetched_at"),
+            })
+            .collect())
+    }
+
+    // ========================================================================
+    // Post check methods
+    // ========================================================================
+
+    /// Save the outcome of looking a published copy up, replacing the
+    /// previous check
+    pub async fn save_post_check(&self, check: &PostCheck) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO post_checks (
+                platform, platform_post_id, post_id, present, checked_at, missing_since
+            )
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT(platform, platform_post_id) DO UPDATE SET
+                post_id = excluded.post_id,
+                present = excluded.present,
+                checked_at = excluded.checked_at,
+                missing_since = excluded.missing_since
+            "#,
+        )
+        .bind(&check.platform)
+        .bind(&check.platform_post_id)
+        .bind(&check.post_id)
+        .bind(check.present)
+        .bind(check.checked_at)
+        .bind(check.missing_since)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(())
+    }
+
+    /// The latest checks of the copies of a post, by platform
+    pub async fn get_post_checks(&self, post_id: &str) -> Result<Vec<PostCheck>> {
+        use sqlx::Row;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT platform, platform_post_id, present, checked_at, missing_since
+            FROM post_checks
+            WHERE post_id = ?
+            ORDER BY platform, platform_post_id
+            "#,
+        )
+        .bind(post_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(rows
+            .iter()
+            .map(|r| PostCheck {
+                post_id: post_id.to_string(),
+                platform: r.get("platform"),
+                platform_post_id: r.get("platform_post_id"),
+                present: r.get("present"),
+                checked_at: r.get("checked_at"),
+                missing_since: r.get("missing_since"),
+            })
+            .collect())
+    }
+
+    /// Copies missing at their latest check, longest missing first
+    pub async fn get_missing_post_checks(&self) -> Result<Vec<PostCheck>> {
+        use sqlx::Row;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT post_id, platform, platform_post_id, present, checked_at, missing_since
+            FROM post_checks
+            WHERE present = 0
+            ORDER BY missing_since, platform, platform_post_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(rows
+            .iter()
+            .map(|r| PostCheck {
+                post_id: r.get("post_id"),
+                platform: r.get("platform"),
+                platform_post_id: r.get("platform_post_id"),
+                present: r.get("present"),
+                checked_at: r.get("checked_at"),
+                missing_since: r.get("missing_since"),
+            })
+            .collect())
+    }
+
+    // ========================================================================
+    // Retention methods
+    // ========================================================================
+
+    /// Find posts eligible for pruning under a retention policy
+    ///
+    /// Returns IDs of published posts created before `before`. When
+    /// `keep_failures` is set, posts with a failed record on any platform are
+    /// excluded; otherwise failed posts of the same age are included too.
+    /// Drafts, scheduled and pending posts are never returned.
+    pub async fn find_prunable_posts(
+        &self,
+        before: i64,
+        keep_failures: bool,
+    ) -> Result<Vec<String>> {
+        let query_str = if keep_failures {
+            r#"
+            SELECT p.id FROM posts p
+            WHERE p.status = 'posted'
+              AND p.created_at < ?
+              AND NOT EXISTS (
+                  SELECT 1 FROM post_records pr
+                  WHERE pr.post_id = p.id AND pr.success = 0
+              )
+            ORDER BY p.created_at ASC
+            "#
+        } else {
+            r#"
+            SELECT p.id FROM posts p
+            WHERE p.status IN ('posted', 'failed')
+              AND p.created_at < ?
+            ORDER BY p.created_at ASC
+            "#
+        };
+
+        let ids = sqlx::query_scalar::<_, String>(query_str)
+            .bind(before)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(ids)
+    }
+
+    /// Delete posts and everything attached to them
+    ///
+    /// Removes platform records, attachments and attachment uploads along with
+    /// each post, in a single transaction. Returns the number of posts deleted.
+    pub async fn prune_posts(&self, post_ids: &[String]) -> Result<usize> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        let mut deleted = 0;
+        for post_id in post_ids {
+            sqlx::query(
+                r#"
+                DELETE FROM attachment_uploads WHERE attachment_id IN (
+                    SELECT id FROM attachments WHERE post_id = ?
+                )
+                "#,
+            )
+            .bind(post_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+            sqlx::query("DELETE FROM attachments WHERE post_id = ?")
+                .bind(post_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+
+            sqlx::query("DELETE FROM post_labels WHERE post_id = ?")
+                .bind(post_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+
+            sqlx::query("DELETE FROM post_attempts WHERE post_id = ?")
+                .bind(post_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+
+            sqlx::query("DELETE FROM post_tombstones WHERE post_id = ?")
+                .bind(post_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+
+            sqlx::query("DELETE FROM post_deferrals WHERE post_id = ?")
+                .bind(post_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+
+            sqlx::query("DELETE FROM platform_schedules WHERE post_id = ?")
+                .bind(post_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+
+            sqlx::query("DELETE FROM scheduled_deletions WHERE post_id = ?")
+                .bind(post_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+
+            sqlx::query("DELETE FROM engagement WHERE post_id = ?")
+                .bind(post_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+
+            sqlx::query(
+                r#"
+                DELETE FROM relay_results WHERE post_record_id IN (
+                    SELECT id FROM post_records WHERE post_id = ?
+                )
+                "#,
+            )
+            .bind(post_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+            sqlx::query("DELETE FROM post_records WHERE post_id = ?")
+                .bind(post_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+
+            let result = sqlx::query("DELETE FROM posts WHERE id = ?")
+                .bind(post_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+            deleted += result.rows_affected() as usize;
+        }
+
+        tx.commit()
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(deleted)
+    }
+
+    // ========================================================================
+    // Backup methods
+    // ========================================================================
+
+    /// Latest schema version known to this build
+    ///
+    /// This is the highest migration version bundled with libplurcast.
+    pub fn latest_schema_version() -> i64 {
+        sqlx::migrate!("./migrations")
+            .migrations
+            .iter()
+            .map(|m| m.version)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Schema version of this database (highest applied migration)
+    pub async fn schema_version(&self) -> Result<i64> {
+        let version: Option<i64> =
+            sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success = 1")
+                .fetch_one(&self.pool)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(version.unwrap_or(0))
+    }
+
+    /// Write a consistent snapshot of the database to a new file
+    ///
+    /// Uses `VACUUM INTO`, which is safe while other processes are using the
+    /// database and includes changes still held in the WAL. The target file
+    /// must not exist.
+    pub async fn backup_to(&self, path: &Path) -> Result<()> {
+        sqlx::query("VACUUM INTO ?")
+            .bind(path.to_string_lossy().to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // Maintenance methods
+    // ========================================================================
+
+    /// Check integrity, then compact and re-analyze the database
+    ///
+    /// Runs `PRAGMA integrity_check`; if it passes, runs `VACUUM` to reclaim
+    /// free pages, `ANALYZE` to refresh query planner statistics, and
+    /// truncates the WAL. A database that fails the check is left untouched so
+    /// that VACUUM doesn't rewrite (and possibly lose) damaged pages.
+    pub async fn maintenance(&self) -> Result<MaintenanceReport> {
+        let size_before = self.size_bytes().await?;
+        let integrity = self.integrity_check().await?;
+
+        let mut report = MaintenanceReport {
+            integrity,
+            compacted: false,
+            size_before,
+            size_after: size_before,
+        };
+
+        if !report.is_healthy() {
+            return Ok(report);
+        }
+
+        for statement in ["VACUUM", "ANALYZE", "PRAGMA wal_checkpoint(TRUNCATE)"] {
+            sqlx::query(statement)
+                .execute(&self.pool)
+                .await
+                .map_err(crate::error::DbError::SqlxError)?;
+        }
+
+        report.compacted = true;
+        report.size_after = self.size_bytes().await?;
+        Ok(report)
+    }
+
+    /// Run `PRAGMA integrity_check` without changing anything
+    ///
+    /// Returns the rows it reports: a single "ok" when the database is
+    /// healthy, otherwise one row per problem found.
+    pub async fn integrity_check(&self) -> Result<Vec<String>> {
+        let integrity: Vec<String> = sqlx::query_scalar("PRAGMA integrity_check")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok(integrity)
+    }
+
+    /// Size of the main database in bytes (page count × page size)
+    async fn size_bytes(&self) -> Result<u64> {
+        let page_count: i64 = sqlx::query_scalar("PRAGMA page_count")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+        let page_size: i64 = sqlx::query_scalar("PRAGMA page_size")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(crate::error::DbError::SqlxError)?;
+
+        Ok((page_count * page_size) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{DbError, PlurcastError};
+    use crate::types::{Post, PostRecord, PostStatus};
+    use tempfile::TempDir;
+
+    /// Helper to create a test post
+    fn create_test_post() -> Post {
+        Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Test post content".to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            scheduled_at: None,
+            status: PostStatus::Pending,
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_database_initialization_with_invalid_path() {
+        // Test with a path that cannot be created (e.g., null byte in path on Unix)
+        // On Windows, we'll use an invalid character
+        #[cfg(unix)]
+        let invalid_path = "/tmp/test\0invalid.db";
+
+        #[cfg(windows)]
+        let invalid_path = "C:\\invalid<>path\\test.db";
+
+        let result = Database::new(invalid_path).await;
+        assert!(result.is_err(), "Expected error for invalid path");
+
+        // Verify it's a database error
+        match result {
+            Err(PlurcastError::Database(_)) => {
+                // Success - got the expected error type
+            }
+            _ => panic!("Expected DbError for invalid path"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_database_initialization_with_readonly_parent() {
+        // Note: This test is platform-specific and may behave differently on Windows vs Unix
+        // On Windows, readonly attribute on directories doesn't prevent file creation
+        // On Unix, we can make a directory truly read-only
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            // Create a temporary directory and make it read-only
+            let temp_dir = TempDir::new().unwrap();
+            let readonly_dir = temp_dir.path().join("readonly");
+            std::fs::create_dir(&readonly_dir).unwrap();
+
+            // Set directory to read-only (no write permission)
+            let mut perms = std::fs::metadata(&readonly_dir).unwrap().permissions();
+            perms.set_mode(0o444); // Read-only
+            std::fs::set_permissions(&readonly_dir, perms).unwrap();
+
+            let db_path = readonly_dir.join("test.db");
+            let result = Database::new(db_path.to_str().unwrap()).await;
+
+            // Clean up permissions before asserting
+            let mut perms = std::fs::metadata(&readonly_dir).unwrap().permissions();
+            perms.set_mode(0o755);
+            std::fs::set_permissions(&readonly_dir, perms).unwrap();
+
+            assert!(
+                result.is_err(),
+                "Expected error for read-only directory on Unix"
+            );
+        }
+
+        #[cfg(windows)]
+        {
+            // On Windows, the readonly attribute on directories doesn't prevent file creation
+            // So we skip this test or test a different scenario
+            // We'll test that we can still create a database in a normal directory
+            let temp_dir = TempDir::new().unwrap();
+            let db_path = temp_dir.path().join("test.db");
+            let result = Database::new(db_path.to_str().unwrap()).await;
+            assert!(
+                result.is_ok(),
+                "Should be able to create database on Windows"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_foreign_key_constraint_enforcement() {
+        // Use in-memory database for testing
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Try to create a post_record without a corresponding post
+        let record = PostRecord {
+            id: None,
+            post_id: "nonexistent-post-id".to_string(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("note1abc".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        };
+
+        // Enable foreign key constraints (SQLite has them off by default in some configurations)
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        let result = db.create_post_record(&record).await;
+
+        // This should fail due to foreign key constraint
+        assert!(result.is_err(), "Expected foreign key constraint violation");
+
+        match result {
+            Err(PlurcastError::Database(DbError::SqlxError(sqlx::Error::Database(db_err)))) => {
+                // Verify it's a foreign key constraint error
+                let message = db_err.message();
+                assert!(
+                    message.contains("FOREIGN KEY") || message.contains("foreign key"),
+                    "Expected foreign key error, got: {}",
+                    message
+                );
+            }
+            _ => panic!("Expected foreign key constraint error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_foreign_key_constraint_with_valid_post() {
+        // Use in-memory database for testing
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Enable foreign key constraints
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        // Create a valid post first
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
+
+        // Now create a post_record with valid foreign key
+        let record = PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("note1abc".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        };
+
+        let result = db.create_post_record(&record).await;
+        assert!(result.is_ok(), "Expected success with valid foreign key");
+    }
+
+    #[tokio::test]
+    async fn test_transaction_rollback_on_error() {
+        // Use in-memory database for testing
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Create a post
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
+
+        // Verify post exists
+        let retrieved = db.get_post(&post.id).await.unwrap();
+        assert!(retrieved.is_some());
+
+        // Now try to create a duplicate post (should fail due to PRIMARY KEY constraint)
+        let duplicate_post = Post {
+            id: post.id.clone(), // Same ID
+            content: "Different content".to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            scheduled_at: None,
+            status: PostStatus::Pending,
+            metadata: None,
+        };
+
+        let result = db.create_post(&duplicate_post).await;
+        assert!(result.is_err(), "Expected error for duplicate primary key");
+
+        // Verify original post is still there and unchanged
+        let retrieved = db.get_post(&post.id).await.unwrap().unwrap();
+        assert_eq!(retrieved.content, post.content);
+        assert_ne!(retrieved.content, duplicate_post.content);
+    }
+
+    #[tokio::test]
+    async fn test_constraint_violation_on_invalid_status() {
+        // Use in-memory database for testing
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Try to insert a post with invalid status directly via SQL
+        let post_id = uuid::Uuid::new_v4().to_string();
+        let _result = sqlx::query(
+            r#"
+            INSERT INTO posts (id, content, created_at, status)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(&post_id)
+        .bind("Test content")
+        .bind(chrono::Utc::now().timestamp())
+        .bind("invalid_status") // This should be 'pending', 'posted', or 'failed'
+        .execute(&db.pool)
+        .await;
+
+        // Note: SQLite doesn't enforce CHECK constraints on status by default in our schema
+        // But we can verify that our application logic handles status correctly
+        // This test documents the behavior
+
+        // If we want to enforce this, we'd need to add a CHECK constraint in the migration
+        // For now, we verify that our API only allows valid statuses
+        let post = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Test".to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            scheduled_at: None,
+            status: PostStatus::Pending, // Only valid statuses allowed by type system
+            metadata: None,
+        };
+
+        assert!(db.create_post(&post).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_not_null_constraint_on_content() {
+        // Use in-memory database for testing
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Try to insert a post with NULL content directly via SQL
+        let post_id = uuid::Uuid::new_v4().to_string();
+        let result = sqlx::query(
+            r#"
+            INSERT INTO posts (id, content, created_at, status)
+            VALUES (?, NULL, ?, ?)
+            "#,
+        )
+        .bind(&post_id)
+        .bind(chrono::Utc::now().timestamp())
+        .bind("pending")
+        .execute(&db.pool)
+        .await;
+
+        assert!(result.is_err(), "Expected NOT NULL constraint violation");
+
+        match result {
+            Err(sqlx::Error::Database(db_err)) => {
+                let message = db_err.message();
+                assert!(
+                    message.contains("NOT NULL") || message.contains("not null"),
+                    "Expected NOT NULL error, got: {}",
+                    message
+                );
+            }
+            _ => panic!("Expected NOT NULL constraint error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_database_operations_after_error() {
         // Use in-memory database for testing
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
+
+        // Create a post
+        let post1 = create_test_post();
+        db.create_post(&post1).await.unwrap();
+
+        // Try to create a duplicate (should fail)
+        let duplicate = Post {
+            id: post1.id.clone(),
+            content: "Duplicate".to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            scheduled_at: None,
+            status: PostStatus::Pending,
+            metadata: None,
+        };
+        let _ = db.create_post(&duplicate).await;
+
+        // Verify database is still functional after error
+        let post2 = create_test_post();
+        let result = db.create_post(&post2).await;
+        assert!(
+            result.is_ok(),
+            "Database should still be functional after error"
+        );
+
+        // Verify we can retrieve both posts
+        assert!(db.get_post(&post1.id).await.unwrap().is_some());
+        assert!(db.get_post(&post2.id).await.unwrap().is_some());
+    }
+
+    // Task 1.4: Expanded database CRUD tests
+
+    #[tokio::test]
+    async fn test_create_and_retrieve_post_happy_path() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Create a post
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
+
+        // Retrieve the post
+        let retrieved = db.get_post(&post.id).await.unwrap();
+        assert!(retrieved.is_some());
+
+        let retrieved = retrieved.unwrap();
+        assert_eq!(retrieved.id, post.id);
+        assert_eq!(retrieved.content, post.content);
+        assert_eq!(retrieved.created_at, post.created_at);
+        assert_eq!(retrieved.scheduled_at, post.scheduled_at);
+        assert!(matches!(retrieved.status, PostStatus::Pending));
+    }
+
+    #[tokio::test]
+    async fn test_update_post_status_pending_to_posted() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Create a post with Pending status
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
+
+        // Update status to Posted
+        db.update_post_status(&post.id, PostStatus::Posted)
+            .await
+            .unwrap();
+
+        // Verify status was updated
+        let retrieved = db.get_post(&post.id).await.unwrap().unwrap();
+        assert!(matches!(retrieved.status, PostStatus::Posted));
+    }
+
+    #[tokio::test]
+    async fn test_update_post_status_pending_to_failed() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Create a post with Pending status
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
+
+        // Update status to Failed
+        db.update_post_status(&post.id, PostStatus::Failed)
+            .await
+            .unwrap();
+
+        // Verify status was updated
+        let retrieved = db.get_post(&post.id).await.unwrap().unwrap();
+        assert!(matches!(retrieved.status, PostStatus::Failed));
+    }
+
+    #[tokio::test]
+    async fn test_get_nonexistent_post_returns_none() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Try to get a post that doesn't exist
+        let nonexistent_id = uuid::Uuid::new_v4().to_string();
+        let result = db.get_post(&nonexistent_id).await.unwrap();
+
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_create_post_record_with_success() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Create a post first
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
+
+        // Create a successful post record
+        let record = PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("note1abc123".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        };
+
+        let result = db.create_post_record(&record).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_post_record_with_failure() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Create a post first
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
+
+        // Create a failed post record
+        let record = PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: "mastodon".to_string(),
+            platform_post_id: None,
+            posted_at: None,
+            success: false,
+            error_message: Some("Network timeout".to_string()),
+            account_name: "default".to_string(),
+        };
+
+        let result = db.create_post_record(&record).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_post_operations() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Create multiple posts concurrently
+        let mut handles = vec![];
+
+        for i in 0..5 {
+            let post = Post {
+                id: uuid::Uuid::new_v4().to_string(),
+                content: format!("Concurrent post {}", i),
+                created_at: chrono::Utc::now().timestamp(),
+                scheduled_at: None,
+                status: PostStatus::Pending,
+                metadata: None,
+            };
+
+            // Clone the pool for each task
+            let pool_clone = db.pool.clone();
+            let post_clone = post.clone();
+
+            let handle = tokio::spawn(async move {
+                let db = Database { pool: pool_clone };
+                db.create_post(&post_clone).await
+            });
+
+            handles.push((handle, post.id));
+        }
+
+        // Wait for all operations to complete
+        for (handle, post_id) in handles {
+            let result = handle.await.unwrap();
+            assert!(result.is_ok(), "Concurrent post creation should succeed");
+
+            // Verify post was created
+            let retrieved = db.get_post(&post_id).await.unwrap();
+            assert!(retrieved.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_status_updates() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Create a post
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
+
+        // Update status concurrently (simulating multiple operations)
+        let mut handles = vec![];
+
+        for _ in 0..3 {
+            let pool_clone = db.pool.clone();
+            let post_id = post.id.clone();
+
+            let handle = tokio::spawn(async move {
+                let db = Database { pool: pool_clone };
+                db.update_post_status(&post_id, PostStatus::Posted).await
+            });
+
+            handles.push(handle);
+        }
+
+        // Wait for all operations to complete
+        for handle in handles {
+            let result = handle.await.unwrap();
+            assert!(result.is_ok(), "Concurrent status update should succeed");
+        }
+
+        // Verify final status
+        let retrieved = db.get_post(&post.id).await.unwrap().unwrap();
+        assert!(matches!(retrieved.status, PostStatus::Posted));
+    }
+
+    #[tokio::test]
+    async fn test_multiple_post_records_for_same_post() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Create a post
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
+
+        // Create multiple post records for different platforms
+        let platforms = vec!["nostr", "mastodon", "bluesky"];
+
+        for platform in platforms {
+            let record = PostRecord {
+                id: None,
+                post_id: post.id.clone(),
+                platform: platform.to_string(),
+                platform_post_id: Some(format!("{}_post_123", platform)),
+                posted_at: Some(chrono::Utc::now().timestamp()),
+                success: true,
+                error_message: None,
+                account_name: "default".to_string(),
+            };
+
+            let result = db.create_post_record(&record).await;
+            assert!(
+                result.is_ok(),
+                "Should be able to create multiple records for same post"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_post_with_scheduled_at() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        let scheduled_time = chrono::Utc::now().timestamp() + 3600; // 1 hour from now
+
+        let post = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Scheduled post".to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            scheduled_at: Some(scheduled_time),
+            status: PostStatus::Pending,
+            metadata: None,
+        };
+
+        db.create_post(&post).await.unwrap();
+
+        let retrieved = db.get_post(&post.id).await.unwrap().unwrap();
+        assert_eq!(retrieved.scheduled_at, Some(scheduled_time));
+    }
+
+    #[tokio::test]
+    async fn test_post_with_metadata() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        let metadata = r#"{"tags":["rust","nostr"],"reply_to":"note1abc"}"#;
+
+        let post = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Post with metadata".to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            scheduled_at: None,
+            status: PostStatus::Pending,
+            metadata: Some(metadata.to_string()),
+        };
+
+        db.create_post(&post).await.unwrap();
+
+        let retrieved = db.get_post(&post.id).await.unwrap().unwrap();
+        assert_eq!(retrieved.metadata, Some(metadata.to_string()));
+    }
+
+    // Task 10.3: Database tests for multi-platform queries
+
+    #[tokio::test]
+    async fn test_post_creation_with_multiple_platform_records() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Create a post
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
+
+        // Create records for multiple platforms
+        let platforms = vec![
+            ("nostr", "note1abc123", true, None),
+            ("mastodon", "12345", true, None),
+            ("bluesky", "", false, Some("Authentication failed")),
+        ];
+
+        for (platform, platform_post_id, success, error) in platforms {
+            let record = PostRecord {
+                id: None,
+                post_id: post.id.clone(),
+                platform: platform.to_string(),
+                platform_post_id: if platform_post_id.is_empty() {
+                    None
+                } else {
+                    Some(platform_post_id.to_string())
+                },
+                posted_at: if success {
+                    Some(chrono::Utc::now().timestamp())
+                } else {
+                    None
+                },
+                success,
+                error_message: error.map(|s| s.to_string()),
+                account_name: "default".to_string(),
+            };
+
+            db.create_post_record(&record).await.unwrap();
+        }
+
+        // Retrieve post with records
+        let records = db.get_post_records(&post.id).await.unwrap();
+        assert_eq!(records.len(), 3);
+
+        // Verify each platform record
+        let nostr_record = records.iter().find(|r| r.platform == "nostr").unwrap();
+        assert!(nostr_record.success);
+        assert_eq!(
+            nostr_record.platform_post_id,
+            Some("note1abc123".to_string())
+        );
+
+        let mastodon_record = records.iter().find(|r| r.platform == "mastodon").unwrap();
+        assert!(mastodon_record.success);
+        assert_eq!(mastodon_record.platform_post_id, Some("12345".to_string()));
+
+        let bluesky_record = records.iter().find(|r| r.platform == "bluesky").unwrap();
+        assert!(!bluesky_record.success);
+        assert_eq!(
+            bluesky_record.error_message,
+            Some("Authentication failed".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_posts_with_platform_filter() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Create multiple posts
+        let post1 = create_test_post();
+        let post2 = create_test_post();
+        let post3 = create_test_post();
+
+        db.create_post(&post1).await.unwrap();
+        db.create_post(&post2).await.unwrap();
+        db.create_post(&post3).await.unwrap();
+
+        // Add records for different platforms
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post1.id.clone(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("note1".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
+
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post2.id.clone(),
+            platform: "mastodon".to_string(),
+            platform_post_id: Some("12345".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
+
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post3.id.clone(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("note2".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
+
+        // Filter by nostr platform
+        let nostr_posts = db.filter_by_platform("nostr", 10).await.unwrap();
+        assert_eq!(nostr_posts.len(), 2);
+        assert!(nostr_posts
+            .iter()
+            .all(|p| p.records.iter().any(|r| r.platform == "nostr")));
+
+        // Filter by mastodon platform
+        let mastodon_posts = db.filter_by_platform("mastodon", 10).await.unwrap();
+        assert_eq!(mastodon_posts.len(), 1);
+        assert_eq!(mastodon_posts[0].post.id, post2.id);
+    }
+
+    #[tokio::test]
+    async fn test_query_posts_with_date_range_filter() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        let now = chrono::Utc::now().timestamp();
+        let one_hour_ago = now - 3600;
+        let two_hours_ago = now - 7200;
+
+        // Create posts with different timestamps
+        let post1 = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Old post".to_string(),
+            created_at: two_hours_ago,
+            scheduled_at: None,
+            status: PostStatus::Posted,
+            metadata: None,
+        };
 
-        // Enable foreign key constraints
-        sqlx::query("PRAGMA foreign_keys = ON")
-            .execute(&db.pool)
+        let post2 = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Recent post".to_string(),
+            created_at: one_hour_ago,
+            scheduled_at: None,
+            status: PostStatus::Posted,
+            metadata: None,
+        };
+
+        let post3 = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "New post".to_string(),
+            created_at: now,
+            scheduled_at: None,
+            status: PostStatus::Posted,
+            metadata: None,
+        };
+
+        db.create_post(&post1).await.unwrap();
+        db.create_post(&post2).await.unwrap();
+        db.create_post(&post3).await.unwrap();
+
+        // Query posts since one hour ago
+        let recent_posts = db
+            .filter_by_date_range(Some(one_hour_ago), None, 10)
             .await
             .unwrap();
+        assert_eq!(recent_posts.len(), 2);
+        assert!(recent_posts.iter().any(|p| p.post.id == post2.id));
+        assert!(recent_posts.iter().any(|p| p.post.id == post3.id));
 
-        // Create a valid post first
-        let post = create_test_post();
-        db.create_post(&post).await.unwrap();
+        // Query posts until one hour ago
+        let old_posts = db
+            .filter_by_date_range(None, Some(one_hour_ago), 10)
+            .await
+            .unwrap();
+        assert_eq!(old_posts.len(), 2);
+        assert!(old_posts.iter().any(|p| p.post.id == post1.id));
+        assert!(old_posts.iter().any(|p| p.post.id == post2.id));
 
-        // Now create a post_record with valid foreign key
-        let record = PostRecord {
-            id: None,
-            post_id: post.id.clone(),
-            platform: "nostr".to_string(),
-            platform_post_id: Some("note1abc".to_string()),
-            posted_at: Some(chrono::Utc::now().timestamp()),
-            success: true,
-            error_message: None,
-            account_name: "default".to_string(),
+        // Query posts in specific range
+        let range_posts = db
+            .filter_by_date_range(Some(two_hours_ago), Some(one_hour_ago), 10)
+            .await
+            .unwrap();
+        assert_eq!(range_posts.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_posts_by_content() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        // Create posts with different content
+        let post1 = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Learning Rust programming".to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            scheduled_at: None,
+            status: PostStatus::Posted,
+            metadata: None,
         };
 
-        let result = db.create_post_record(&record).await;
-        assert!(result.is_ok(), "Expected success with valid foreign key");
+        let post2 = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Exploring Nostr protocol".to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            scheduled_at: None,
+            status: PostStatus::Posted,
+            metadata: None,
+        };
+
+        let post3 = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Building with Rust and Nostr".to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            scheduled_at: None,
+            status: PostStatus::Posted,
+            metadata: None,
+        };
+
+        db.create_post(&post1).await.unwrap();
+        db.create_post(&post2).await.unwrap();
+        db.create_post(&post3).await.unwrap();
+
+        // Search for "Rust"
+        let rust_posts = db.search_content("Rust", 10).await.unwrap();
+        assert_eq!(rust_posts.len(), 2);
+        assert!(rust_posts.iter().any(|p| p.post.id == post1.id));
+        assert!(rust_posts.iter().any(|p| p.post.id == post3.id));
+
+        // Search for "Nostr"
+        let nostr_posts = db.search_content("Nostr", 10).await.unwrap();
+        assert_eq!(nostr_posts.len(), 2);
+        assert!(nostr_posts.iter().any(|p| p.post.id == post2.id));
+        assert!(nostr_posts.iter().any(|p| p.post.id == post3.id));
+
+        // Search for "protocol"
+        let protocol_posts = db.search_content("protocol", 10).await.unwrap();
+        assert_eq!(protocol_posts.len(), 1);
+        assert_eq!(protocol_posts[0].post.id, post2.id);
+
+        // Search for non-existent term
+        let empty_posts = db.search_content("blockchain", 10).await.unwrap();
+        assert_eq!(empty_posts.len(), 0);
     }
 
     #[tokio::test]
-    async fn test_transaction_rollback_on_error() {
-        // Use in-memory database for testing
+    async fn test_concurrent_writes_to_post_records() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
         // Create a post
         let post = create_test_post();
         db.create_post(&post).await.unwrap();
 
-        // Verify post exists
-        let retrieved = db.get_post(&post.id).await.unwrap();
-        assert!(retrieved.is_some());
+        // Concurrently create records for different platforms
+        let mut handles = vec![];
+        let platforms = vec!["nostr", "mastodon", "bluesky"];
 
-        // Now try to create a duplicate post (should fail due to PRIMARY KEY constraint)
-        let duplicate_post = Post {
-            id: post.id.clone(), // Same ID
-            content: "Different content".to_string(),
-            created_at: chrono::Utc::now().timestamp(),
+        for platform in platforms {
+            let pool_clone = db.pool.clone();
+            let post_id = post.id.clone();
+            let platform = platform.to_string();
+
+            let handle = tokio::spawn(async move {
+                let db = Database { pool: pool_clone };
+                let record = PostRecord {
+                    id: None,
+                    post_id,
+                    platform: platform.clone(),
+                    platform_post_id: Some(format!("{}_post_id", platform)),
+                    posted_at: Some(chrono::Utc::now().timestamp()),
+                    success: true,
+                    error_message: None,
+                    account_name: "default".to_string(),
+                };
+                db.create_post_record(&record).await
+            });
+
+            handles.push(handle);
+        }
+
+        // Wait for all operations to complete
+        for handle in handles {
+            let result = handle.await.unwrap();
+            assert!(result.is_ok(), "Concurrent record creation should succeed");
+        }
+
+        // Verify all records were created
+        let records = db.get_post_records(&post.id).await.unwrap();
+        assert_eq!(records.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_query_with_multiple_filters() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        let now = chrono::Utc::now().timestamp();
+        let one_hour_ago = now - 3600;
+
+        // Create posts
+        let post1 = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Rust programming on Nostr".to_string(),
+            created_at: one_hour_ago,
             scheduled_at: None,
-            status: PostStatus::Pending,
+            status: PostStatus::Posted,
             metadata: None,
         };
 
-        let result = db.create_post(&duplicate_post).await;
-        assert!(result.is_err(), "Expected error for duplicate primary key");
+        let post2 = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Rust programming on Mastodon".to_string(),
+            created_at: now,
+            scheduled_at: None,
+            status: PostStatus::Posted,
+            metadata: None,
+        };
 
-        // Verify original post is still there and unchanged
-        let retrieved = db.get_post(&post.id).await.unwrap().unwrap();
-        assert_eq!(retrieved.content, post.content);
-        assert_ne!(retrieved.content, duplicate_post.content);
+        db.create_post(&post1).await.unwrap();
+        db.create_post(&post2).await.unwrap();
+
+        // Add platform records
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post1.id.clone(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("note1".to_string()),
+            posted_at: Some(one_hour_ago),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
+
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post2.id.clone(),
+            platform: "mastodon".to_string(),
+            platform_post_id: Some("12345".to_string()),
+            posted_at: Some(now),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
+
+        // Query with platform and search filters
+        let results = db
+            .query_posts_with_records(Some("nostr"), None, None, Some("Rust"), 10)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].post.id, post1.id);
+
+        // Query with date range and search filters
+        let results = db
+            .query_posts_with_records(
+                None,
+                Some(now - 1800), // 30 minutes ago
+                None,
+                Some("programming"),
+                10,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].post.id, post2.id);
     }
 
     #[tokio::test]
-    async fn test_constraint_violation_on_invalid_status() {
-        // Use in-memory database for testing
+    async fn test_query_respects_limit() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        // Try to insert a post with invalid status directly via SQL
-        let post_id = uuid::Uuid::new_v4().to_string();
-        let _result = sqlx::query(
-            r#"
-            INSERT INTO posts (id, content, created_at, status)
-            VALUES (?, ?, ?, ?)
-            "#,
-        )
-        .bind(&post_id)
-        .bind("Test content")
-        .bind(chrono::Utc::now().timestamp())
-        .bind("invalid_status") // This should be 'pending', 'posted', or 'failed'
-        .execute(&db.pool)
-        .await;
+        let db = Database { pool, cipher: None };
 
-        // Note: SQLite doesn't enforce CHECK constraints on status by default in our schema
-        // But we can verify that our application logic handles status correctly
-        // This test documents the behavior
+        // Create 10 posts
+        for i in 0..10 {
+            let post = Post {
+                id: uuid::Uuid::new_v4().to_string(),
+                content: format!("Post {}", i),
+                created_at: chrono::Utc::now().timestamp() + i,
+                scheduled_at: None,
+                status: PostStatus::Posted,
+                metadata: None,
+            };
+            db.create_post(&post).await.unwrap();
+        }
 
-        // If we want to enforce this, we'd need to add a CHECK constraint in the migration
-        // For now, we verify that our API only allows valid statuses
-        let post = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Test".to_string(),
-            created_at: chrono::Utc::now().timestamp(),
-            scheduled_at: None,
-            status: PostStatus::Pending, // Only valid statuses allowed by type system
-            metadata: None,
-        };
+        // Query with limit of 5
+        let results = db
+            .query_posts_with_records(None, None, None, None, 5)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 5);
 
-        assert!(db.create_post(&post).await.is_ok());
+        // Query with limit of 3
+        let results = db
+            .query_posts_with_records(None, None, None, None, 3)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 3);
     }
 
     #[tokio::test]
-    async fn test_not_null_constraint_on_content() {
-        // Use in-memory database for testing
+    async fn test_get_post_records_empty() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        // Try to insert a post with NULL content directly via SQL
-        let post_id = uuid::Uuid::new_v4().to_string();
-        let result = sqlx::query(
-            r#"
-            INSERT INTO posts (id, content, created_at, status)
-            VALUES (?, NULL, ?, ?)
-            "#,
-        )
-        .bind(&post_id)
-        .bind(chrono::Utc::now().timestamp())
-        .bind("pending")
-        .execute(&db.pool)
-        .await;
+        let db = Database { pool, cipher: None };
 
-        assert!(result.is_err(), "Expected NOT NULL constraint violation");
+        // Create a post without any records
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
 
-        match result {
-            Err(sqlx::Error::Database(db_err)) => {
-                let message = db_err.message();
-                assert!(
-                    message.contains("NOT NULL") || message.contains("not null"),
-                    "Expected NOT NULL error, got: {}",
-                    message
-                );
-            }
-            _ => panic!("Expected NOT NULL constraint error"),
-        }
+        // Get records should return empty vector
+        let records = db.get_post_records(&post.id).await.unwrap();
+        assert_eq!(records.len(), 0);
     }
 
     #[tokio::test]
-    async fn test_database_operations_after_error() {
-        // Use in-memory database for testing
+    async fn test_get_post_records_after() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        // Create a post
-        let post1 = create_test_post();
-        db.create_post(&post1).await.unwrap();
+        assert_eq!(db.get_last_post_record_id().await.unwrap(), None);
 
-        // Try to create a duplicate (should fail)
-        let duplicate = Post {
-            id: post1.id.clone(),
-            content: "Duplicate".to_string(),
-            created_at: chrono::Utc::now().timestamp(),
-            scheduled_at: None,
-            status: PostStatus::Pending,
-            metadata: None,
-        };
-        let _ = db.create_post(&duplicate).await;
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
+        for platform in ["nostr", "mastodon", "ssb"] {
+            let record = PostRecord {
+                id: None,
+                post_id: post.id.clone(),
+                platform: platform.to_string(),
+                platform_post_id: Some(format!("{}-id", platform)),
+                posted_at: Some(chrono::Utc::now().timestamp()),
+                success: true,
+                error_message: None,
+                account_name: "default".to_string(),
+            };
+            db.create_post_record(&record).await.unwrap();
+        }
 
-        // Verify database is still functional after error
-        let post2 = create_test_post();
-        let result = db.create_post(&post2).await;
-        assert!(
-            result.is_ok(),
-            "Database should still be functional after error"
-        );
+        let all = db.get_post_records_after(0, 10).await.unwrap();
+        let platforms: Vec<&str> = all.iter().map(|r| r.platform.as_str()).collect();
+        assert_eq!(platforms, vec!["nostr", "mastodon", "ssb"]);
 
-        // Verify we can retrieve both posts
-        assert!(db.get_post(&post1.id).await.unwrap().is_some());
-        assert!(db.get_post(&post2.id).await.unwrap().is_some());
-    }
+        let first_id = all[0].id.unwrap();
+        let newer = db.get_post_records_after(first_id, 10).await.unwrap();
+        assert_eq!(newer.len(), 2);
+        assert_eq!(newer[0].platform, "mastodon");
 
-    // Task 1.4: Expanded database CRUD tests
+        assert_eq!(
+            db.get_post_records_after(first_id, 1).await.unwrap().len(),
+            1
+        );
+        assert_eq!(db.get_last_post_record_id().await.unwrap(), all[2].id);
+    }
 
     #[tokio::test]
-    async fn test_create_and_retrieve_post_happy_path() {
+    async fn test_post_with_records_ordering() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        // Create a post
         let post = create_test_post();
         db.create_post(&post).await.unwrap();
 
-        // Retrieve the post
-        let retrieved = db.get_post(&post.id).await.unwrap();
-        assert!(retrieved.is_some());
+        let now = chrono::Utc::now().timestamp();
 
-        let retrieved = retrieved.unwrap();
-        assert_eq!(retrieved.id, post.id);
-        assert_eq!(retrieved.content, post.content);
-        assert_eq!(retrieved.created_at, post.created_at);
-        assert_eq!(retrieved.scheduled_at, post.scheduled_at);
-        assert!(matches!(retrieved.status, PostStatus::Pending));
+        // Create records with different timestamps
+        for i in 0..3 {
+            let record = PostRecord {
+                id: None,
+                post_id: post.id.clone(),
+                platform: format!("platform{}", i),
+                platform_post_id: Some(format!("post{}", i)),
+                posted_at: Some(now + i),
+                success: true,
+                error_message: None,
+                account_name: "default".to_string(),
+            };
+            db.create_post_record(&record).await.unwrap();
+            // Small delay to ensure different timestamps
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        }
+
+        // Get records - should be ordered by posted_at DESC
+        let records = db.get_post_records(&post.id).await.unwrap();
+        assert_eq!(records.len(), 3);
+
+        // Verify descending order
+        for i in 0..records.len() - 1 {
+            assert!(records[i].posted_at >= records[i + 1].posted_at);
+        }
     }
 
+    // SCHEDULING TESTS (Phase 5.1 Task 2 & 3)
+
     #[tokio::test]
-    async fn test_update_post_status_pending_to_posted() {
+    async fn test_migration_creates_scheduling_indexes() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
 
-        // Create a post with Pending status
-        let post = create_test_post();
-        db.create_post(&post).await.unwrap();
-
-        // Update status to Posted
-        db.update_post_status(&post.id, PostStatus::Posted)
-            .await
-            .unwrap();
+        // Verify idx_posts_scheduled_at index exists
+        let result = sqlx::query(
+            r#"
+            SELECT name FROM sqlite_master
+            WHERE type='index' AND name='idx_posts_scheduled_at'
+            "#,
+        )
+        .fetch_optional(&pool)
+        .await
+        .unwrap();
+        assert!(result.is_some(), "idx_posts_scheduled_at index not created");
 
-        // Verify status was updated
-        let retrieved = db.get_post(&post.id).await.unwrap().unwrap();
-        assert!(matches!(retrieved.status, PostStatus::Posted));
+        // Verify idx_posts_status_scheduled index exists
+        let result = sqlx::query(
+            r#"
+            SELECT name FROM sqlite_master
+            WHERE type='index' AND name='idx_posts_status_scheduled'
+            "#,
+        )
+        .fetch_optional(&pool)
+        .await
+        .unwrap();
+        assert!(
+            result.is_some(),
+            "idx_posts_status_scheduled index not created"
+        );
     }
 
     #[tokio::test]
-    async fn test_update_post_status_pending_to_failed() {
+    async fn test_migration_creates_rate_limits_table() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
 
-        // Create a post with Pending status
-        let post = create_test_post();
-        db.create_post(&post).await.unwrap();
+        // Verify rate_limits table exists
+        let result = sqlx::query(
+            r#"
+            SELECT name FROM sqlite_master
+            WHERE type='table' AND name='rate_limits'
+            "#,
+        )
+        .fetch_optional(&pool)
+        .await
+        .unwrap();
+        assert!(result.is_some(), "rate_limits table not created");
 
-        // Update status to Failed
-        db.update_post_status(&post.id, PostStatus::Failed)
-            .await
-            .unwrap();
+        // Verify table structure by inserting and querying
+        sqlx::query(
+            r#"
+            INSERT INTO rate_limits (platform, window_start, post_count)
+            VALUES ('nostr', 1234567890, 5)
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
 
-        // Verify status was updated
-        let retrieved = db.get_post(&post.id).await.unwrap().unwrap();
-        assert!(matches!(retrieved.status, PostStatus::Failed));
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT post_count FROM rate_limits
+            WHERE platform = 'nostr' AND window_start = 1234567890
+            "#,
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(count.0, 5);
     }
 
     #[tokio::test]
-    async fn test_get_nonexistent_post_returns_none() {
+    async fn test_get_scheduled_posts_due_returns_only_due_posts() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        // Try to get a post that doesn't exist
-        let nonexistent_id = uuid::Uuid::new_v4().to_string();
-        let result = db.get_post(&nonexistent_id).await.unwrap();
+        let now = chrono::Utc::now().timestamp();
 
-        assert!(result.is_none());
+        // Create post scheduled in the past (due)
+        let past_post = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Past post".to_string(),
+            created_at: now - 3600,
+            scheduled_at: Some(now - 1800),
+            status: PostStatus::Scheduled,
+            metadata: None,
+        };
+        db.create_post(&past_post).await.unwrap();
+
+        // Create post scheduled for future (not due)
+        let future_post = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Future post".to_string(),
+            created_at: now,
+            scheduled_at: Some(now + 3600),
+            status: PostStatus::Scheduled,
+            metadata: None,
+        };
+        db.create_post(&future_post).await.unwrap();
+
+        // Create posted post (not scheduled)
+        let posted_post = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Posted post".to_string(),
+            created_at: now - 7200,
+            scheduled_at: Some(now - 3600),
+            status: PostStatus::Posted,
+            metadata: None,
+        };
+        db.create_post(&posted_post).await.unwrap();
+
+        let due_posts = db.get_scheduled_posts_due().await.unwrap();
+
+        assert_eq!(due_posts.len(), 1);
+        assert_eq!(due_posts[0].id, past_post.id);
+        assert_eq!(due_posts[0].content, "Past post");
     }
 
     #[tokio::test]
-    async fn test_create_post_record_with_success() {
+    async fn test_get_scheduled_posts_due_empty_when_none_due() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        // Create a post first
-        let post = create_test_post();
-        db.create_post(&post).await.unwrap();
+        let now = chrono::Utc::now().timestamp();
 
-        // Create a successful post record
-        let record = PostRecord {
-            id: None,
-            post_id: post.id.clone(),
-            platform: "nostr".to_string(),
-            platform_post_id: Some("note1abc123".to_string()),
-            posted_at: Some(chrono::Utc::now().timestamp()),
-            success: true,
-            error_message: None,
-            account_name: "default".to_string(),
+        // Create only future posts
+        let future_post = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Future post".to_string(),
+            created_at: now,
+            scheduled_at: Some(now + 3600),
+            status: PostStatus::Scheduled,
+            metadata: None,
         };
+        db.create_post(&future_post).await.unwrap();
 
-        let result = db.create_post_record(&record).await;
-        assert!(result.is_ok());
+        let due_posts = db.get_scheduled_posts_due().await.unwrap();
+        assert_eq!(due_posts.len(), 0);
     }
 
     #[tokio::test]
-    async fn test_create_post_record_with_failure() {
+    async fn test_get_scheduled_posts_returns_all_scheduled() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        // Create a post first
-        let post = create_test_post();
-        db.create_post(&post).await.unwrap();
+        let now = chrono::Utc::now().timestamp();
 
-        // Create a failed post record
-        let record = PostRecord {
-            id: None,
-            post_id: post.id.clone(),
-            platform: "mastodon".to_string(),
-            platform_post_id: None,
-            posted_at: None,
-            success: false,
-            error_message: Some("Network timeout".to_string()),
-            account_name: "default".to_string(),
+        // Create scheduled posts with different times
+        for i in 0..3 {
+            let post = Post {
+                id: uuid::Uuid::new_v4().to_string(),
+                content: format!("Scheduled post {}", i),
+                created_at: now,
+                scheduled_at: Some(now + (i * 3600)),
+                status: PostStatus::Scheduled,
+                metadata: None,
+            };
+            db.create_post(&post).await.unwrap();
+        }
+
+        // Create non-scheduled post
+        let posted = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Posted".to_string(),
+            created_at: now,
+            scheduled_at: None,
+            status: PostStatus::Posted,
+            metadata: None,
         };
+        db.create_post(&posted).await.unwrap();
 
-        let result = db.create_post_record(&record).await;
-        assert!(result.is_ok());
+        let scheduled = db.get_scheduled_posts().await.unwrap();
+
+        assert_eq!(scheduled.len(), 3);
+        // Verify ordering (ASC by scheduled_at)
+        for i in 0..scheduled.len() - 1 {
+            assert!(scheduled[i].scheduled_at <= scheduled[i + 1].scheduled_at);
+        }
     }
 
     #[tokio::test]
-    async fn test_concurrent_post_operations() {
+    async fn test_get_last_scheduled_timestamp_returns_max() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        // Create multiple posts concurrently
-        let mut handles = vec![];
+        let now = chrono::Utc::now().timestamp();
 
-        for i in 0..5 {
+        // Create scheduled posts
+        let times = vec![now + 1000, now + 5000, now + 3000];
+        for time in &times {
             let post = Post {
                 id: uuid::Uuid::new_v4().to_string(),
-                content: format!("Concurrent post {}", i),
-                created_at: chrono::Utc::now().timestamp(),
-                scheduled_at: None,
-                status: PostStatus::Pending,
+                content: "Scheduled".to_string(),
+                created_at: now,
+                scheduled_at: Some(*time),
+                status: PostStatus::Scheduled,
                 metadata: None,
             };
-
-            // Clone the pool for each task
-            let pool_clone = db.pool.clone();
-            let post_clone = post.clone();
-
-            let handle = tokio::spawn(async move {
-                let db = Database { pool: pool_clone };
-                db.create_post(&post_clone).await
-            });
-
-            handles.push((handle, post.id));
+            db.create_post(&post).await.unwrap();
         }
 
-        // Wait for all operations to complete
-        for (handle, post_id) in handles {
-            let result = handle.await.unwrap();
-            assert!(result.is_ok(), "Concurrent post creation should succeed");
+        let max_time = db.get_last_scheduled_timestamp().await.unwrap();
 
-            // Verify post was created
-            let retrieved = db.get_post(&post_id).await.unwrap();
-            assert!(retrieved.is_some());
-        }
+        assert_eq!(max_time, Some(now + 5000));
     }
 
     #[tokio::test]
-    async fn test_concurrent_status_updates() {
+    async fn test_get_last_scheduled_timestamp_none_when_empty() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        // Create a post
-        let post = create_test_post();
-        db.create_post(&post).await.unwrap();
+        let max_time = db.get_last_scheduled_timestamp().await.unwrap();
+        assert_eq!(max_time, None);
+    }
 
-        // Update status concurrently (simulating multiple operations)
-        let mut handles = vec![];
+    #[tokio::test]
+    async fn test_get_last_scheduled_timestamp_ignores_posted() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
 
-        for _ in 0..3 {
-            let pool_clone = db.pool.clone();
-            let post_id = post.id.clone();
+        let now = chrono::Utc::now().timestamp();
 
-            let handle = tokio::spawn(async move {
-                let db = Database { pool: pool_clone };
-                db.update_post_status(&post_id, PostStatus::Posted).await
-            });
+        // Create posted post with scheduled_at
+        let posted = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Posted".to_string(),
+            created_at: now,
+            scheduled_at: Some(now + 10000),
+            status: PostStatus::Posted,
+            metadata: None,
+        };
+        db.create_post(&posted).await.unwrap();
 
-            handles.push(handle);
-        }
+        // Create scheduled post with earlier time
+        let scheduled = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Scheduled".to_string(),
+            created_at: now,
+            scheduled_at: Some(now + 1000),
+            status: PostStatus::Scheduled,
+            metadata: None,
+        };
+        db.create_post(&scheduled).await.unwrap();
 
-        // Wait for all operations to complete
-        for handle in handles {
-            let result = handle.await.unwrap();
-            assert!(result.is_ok(), "Concurrent status update should succeed");
-        }
+        let max_time = db.get_last_scheduled_timestamp().await.unwrap();
 
-        // Verify final status
-        let retrieved = db.get_post(&post.id).await.unwrap().unwrap();
-        assert!(matches!(retrieved.status, PostStatus::Posted));
+        // Should return scheduled post time, not posted post time
+        assert_eq!(max_time, Some(now + 1000));
     }
 
     #[tokio::test]
-    async fn test_multiple_post_records_for_same_post() {
+    async fn test_get_publish_times_counts_posts_once() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
+
+        let now = chrono::Utc::now().timestamp();
 
-        // Create a post
         let post = create_test_post();
         db.create_post(&post).await.unwrap();
 
-        // Create multiple post records for different platforms
-        let platforms = vec!["nostr", "mastodon", "bluesky"];
-
-        for platform in platforms {
+        // Published to two platforms, plus one failed attempt
+        for (platform, posted_at, success) in [
+            ("nostr", now - 100, true),
+            ("mastodon", now - 50, true),
+            ("bluesky", now - 200, false),
+        ] {
             let record = PostRecord {
                 id: None,
                 post_id: post.id.clone(),
                 platform: platform.to_string(),
-                platform_post_id: Some(format!("{}_post_123", platform)),
-                posted_at: Some(chrono::Utc::now().timestamp()),
-                success: true,
+                platform_post_id: None,
+                posted_at: Some(posted_at),
+                success,
                 error_message: None,
                 account_name: "default".to_string(),
             };
+            db.create_post_record(&record).await.unwrap();
+        }
 
-            let result = db.create_post_record(&record).await;
-            assert!(
-                result.is_ok(),
-                "Should be able to create multiple records for same post"
-            );
+        let times = db.get_publish_times(now - 1000).await.unwrap();
+        assert_eq!(times, vec![now - 100]);
+
+        // Records before the cutoff are ignored
+        let times = db.get_publish_times(now - 60).await.unwrap();
+        assert_eq!(times, vec![now - 50]);
+    }
+
+    #[tokio::test]
+    async fn test_due_posts_ordered_by_priority() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        let now = chrono::Utc::now().timestamp();
+
+        let mut ids = Vec::new();
+        for offset in [300, 200, 100] {
+            let post = Post {
+                id: uuid::Uuid::new_v4().to_string(),
+                content: format!("Due {}s ago", offset),
+                created_at: now - offset,
+                scheduled_at: Some(now - offset),
+                status: PostStatus::Scheduled,
+                metadata: None,
+            };
+            db.create_post(&post).await.unwrap();
+            ids.push(post.id);
         }
+
+        // The newest post is urgent
+        assert!(db.set_post_priority(&ids[2], 5).await.unwrap());
+        assert_eq!(db.get_post_priority(&ids[2]).await.unwrap(), Some(5));
+        assert_eq!(db.get_post_priority(&ids[0]).await.unwrap(), Some(0));
+        assert!(!db.set_post_priority("missing", 1).await.unwrap());
+
+        let due: Vec<String> = db
+            .get_scheduled_posts_due()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| p.id)
+            .collect();
+        assert_eq!(due, vec![ids[2].clone(), ids[0].clone(), ids[1].clone()]);
+
+        let priorities = db.get_scheduled_priorities().await.unwrap();
+        assert_eq!(priorities.get(&ids[2]), Some(&5));
+        assert_eq!(priorities.get(&ids[1]), Some(&0));
     }
 
     #[tokio::test]
-    async fn test_post_with_scheduled_at() {
+    async fn test_update_post_schedule_changes_time() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        let scheduled_time = chrono::Utc::now().timestamp() + 3600; // 1 hour from now
+        let now = chrono::Utc::now().timestamp();
 
         let post = Post {
             id: uuid::Uuid::new_v4().to_string(),
-            content: "Scheduled post".to_string(),
-            created_at: chrono::Utc::now().timestamp(),
-            scheduled_at: Some(scheduled_time),
-            status: PostStatus::Pending,
+            content: "Test".to_string(),
+            created_at: now,
+            scheduled_at: Some(now + 1000),
+            status: PostStatus::Scheduled,
             metadata: None,
         };
-
         db.create_post(&post).await.unwrap();
 
-        let retrieved = db.get_post(&post.id).await.unwrap().unwrap();
-        assert_eq!(retrieved.scheduled_at, Some(scheduled_time));
+        // Update schedule time
+        let new_time = now + 5000;
+        db.update_post_schedule(&post.id, Some(new_time))
+            .await
+            .unwrap();
+
+        let updated = db.get_post(&post.id).await.unwrap().unwrap();
+        assert_eq!(updated.scheduled_at, Some(new_time));
     }
 
     #[tokio::test]
-    async fn test_post_with_metadata() {
+    async fn test_update_post_schedule_can_clear_time() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        let metadata = r#"{"tags":["rust","nostr"],"reply_to":"note1abc"}"#;
+        let now = chrono::Utc::now().timestamp();
 
         let post = Post {
             id: uuid::Uuid::new_v4().to_string(),
-            content: "Post with metadata".to_string(),
-            created_at: chrono::Utc::now().timestamp(),
-            scheduled_at: None,
-            status: PostStatus::Pending,
-            metadata: Some(metadata.to_string()),
+            content: "Test".to_string(),
+            created_at: now,
+            scheduled_at: Some(now + 1000),
+            status: PostStatus::Scheduled,
+            metadata: None,
         };
-
         db.create_post(&post).await.unwrap();
 
-        let retrieved = db.get_post(&post.id).await.unwrap().unwrap();
-        assert_eq!(retrieved.metadata, Some(metadata.to_string()));
-    }
+        // Clear schedule time
+        db.update_post_schedule(&post.id, None).await.unwrap();
 
-    // Task 10.3: Database tests for multi-platform queries
+        let updated = db.get_post(&post.id).await.unwrap().unwrap();
+        assert_eq!(updated.scheduled_at, None);
+    }
 
     #[tokio::test]
-    async fn test_post_creation_with_multiple_platform_records() {
+    async fn test_delete_post_removes_post() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        // Create a post
         let post = create_test_post();
         db.create_post(&post).await.unwrap();
 
-        // Create records for multiple platforms
-        let platforms = vec![
-            ("nostr", "note1abc123", true, None),
-            ("mastodon", "12345", true, None),
-            ("bluesky", "", false, Some("Authentication failed")),
-        ];
+        db.delete_post(&post.id).await.unwrap();
 
-        for (platform, platform_post_id, success, error) in platforms {
-            let record = PostRecord {
-                id: None,
-                post_id: post.id.clone(),
-                platform: platform.to_string(),
-                platform_post_id: if platform_post_id.is_empty() {
-                    None
-                } else {
-                    Some(platform_post_id.to_string())
-                },
-                posted_at: if success {
-                    Some(chrono::Utc::now().timestamp())
-                } else {
-                    None
-                },
-                success,
-                error_message: error.map(|s| s.to_string()),
-                account_name: "default".to_string(),
-            };
+        let retrieved = db.get_post(&post.id).await.unwrap();
+        assert!(retrieved.is_none());
+    }
 
-            db.create_post_record(&record).await.unwrap();
-        }
+    #[tokio::test]
+    async fn test_delete_post_removes_records() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
+
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
+
+        // Add a record
+        let record = PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("note1abc".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        };
+        db.create_post_record(&record).await.unwrap();
+
+        db.delete_post(&post.id).await.unwrap();
 
-        // Retrieve post with records
         let records = db.get_post_records(&post.id).await.unwrap();
-        assert_eq!(records.len(), 3);
+        assert_eq!(records.len(), 0);
+    }
 
-        // Verify each platform record
-        let nostr_record = records.iter().find(|r| r.platform == "nostr").unwrap();
-        assert!(nostr_record.success);
-        assert_eq!(
-            nostr_record.platform_post_id,
-            Some("note1abc123".to_string())
-        );
+    #[tokio::test]
+    async fn test_soft_delete_keeps_post_and_tombstone() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
 
-        let mastodon_record = records.iter().find(|r| r.platform == "mastodon").unwrap();
-        assert!(mastodon_record.success);
-        assert_eq!(mastodon_record.platform_post_id, Some("12345".to_string()));
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
 
-        let bluesky_record = records.iter().find(|r| r.platform == "bluesky").unwrap();
-        assert!(!bluesky_record.success);
-        assert_eq!(
-            bluesky_record.error_message,
-            Some("Authentication failed".to_string())
-        );
+        assert!(db
+            .soft_delete_post(&post.id, Some("cancelled"))
+            .await
+            .unwrap());
+        // Deleting again is a no-op
+        assert!(!db
+            .soft_delete_post(&post.id, Some("cancelled"))
+            .await
+            .unwrap());
+
+        let retrieved = db.get_post(&post.id).await.unwrap().unwrap();
+        assert_eq!(retrieved.status, PostStatus::Deleted);
+
+        let tombstone = db.get_tombstone(&post.id).await.unwrap().unwrap();
+        assert_eq!(tombstone.previous_status, post.status);
+        assert_eq!(tombstone.reason.as_deref(), Some("cancelled"));
+
+        // Hidden from queries unless asked for
+        let visible = db.query_posts(&PostQuery::default()).await.unwrap();
+        assert!(visible.is_empty());
+        let all = db
+            .query_posts(&PostQuery {
+                include_deleted: true,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 1);
     }
 
     #[tokio::test]
-    async fn test_query_posts_with_platform_filter() {
+    async fn test_purge_deleted_posts() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
+
+        let kept = create_test_post();
+        db.create_post(&kept).await.unwrap();
+        let deleted = create_test_post();
+        db.create_post(&deleted).await.unwrap();
+        db.soft_delete_post(&deleted.id, None).await.unwrap();
+
+        // Nothing was deleted before the cutoff
+        let purged = db.purge_deleted_posts(Some(0)).await.unwrap();
+        assert!(purged.is_empty());
+
+        let purged = db.purge_deleted_posts(None).await.unwrap();
+        assert_eq!(purged, vec![deleted.id.clone()]);
+        assert!(db.get_post(&deleted.id).await.unwrap().is_none());
+        assert!(db.get_tombstone(&deleted.id).await.unwrap().is_none());
+        assert!(db.get_post(&kept.id).await.unwrap().is_some());
+    }
 
-        // Create multiple posts
-        let post1 = create_test_post();
-        let post2 = create_test_post();
-        let post3 = create_test_post();
+    #[tokio::test]
+    async fn test_get_rate_limit_count_zero_when_empty() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
 
-        db.create_post(&post1).await.unwrap();
-        db.create_post(&post2).await.unwrap();
-        db.create_post(&post3).await.unwrap();
+        let count = db.get_rate_limit_count("nostr", 1234567890).await.unwrap();
+        assert_eq!(count, 0);
+    }
 
-        // Add records for different platforms
-        db.create_post_record(&PostRecord {
-            id: None,
-            post_id: post1.id.clone(),
-            platform: "nostr".to_string(),
-            platform_post_id: Some("note1".to_string()),
-            posted_at: Some(chrono::Utc::now().timestamp()),
-            success: true,
-            error_message: None,
-            account_name: "default".to_string(),
-        })
-        .await
-        .unwrap();
+    #[tokio::test]
+    async fn test_increment_rate_limit_creates_record() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
 
-        db.create_post_record(&PostRecord {
-            id: None,
-            post_id: post2.id.clone(),
-            platform: "mastodon".to_string(),
-            platform_post_id: Some("12345".to_string()),
-            posted_at: Some(chrono::Utc::now().timestamp()),
-            success: true,
-            error_message: None,
-            account_name: "default".to_string(),
-        })
-        .await
-        .unwrap();
+        let window = 1234567890;
+        db.increment_rate_limit("nostr", window).await.unwrap();
 
-        db.create_post_record(&PostRecord {
-            id: None,
-            post_id: post3.id.clone(),
-            platform: "nostr".to_string(),
-            platform_post_id: Some("note2".to_string()),
-            posted_at: Some(chrono::Utc::now().timestamp()),
-            success: true,
-            error_message: None,
-            account_name: "default".to_string(),
-        })
-        .await
-        .unwrap();
+        let count = db.get_rate_limit_count("nostr", window).await.unwrap();
+        assert_eq!(count, 1);
+    }
 
-        // Filter by nostr platform
-        let nostr_posts = db.filter_by_platform("nostr", 10).await.unwrap();
-        assert_eq!(nostr_posts.len(), 2);
-        assert!(nostr_posts
-            .iter()
-            .all(|p| p.records.iter().any(|r| r.platform == "nostr")));
+    #[tokio::test]
+    async fn test_increment_rate_limit_increments_existing() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
 
-        // Filter by mastodon platform
-        let mastodon_posts = db.filter_by_platform("mastodon", 10).await.unwrap();
-        assert_eq!(mastodon_posts.len(), 1);
-        assert_eq!(mastodon_posts[0].post.id, post2.id);
+        let window = 1234567890;
+
+        // Increment multiple times
+        for _ in 0..5 {
+            db.increment_rate_limit("nostr", window).await.unwrap();
+        }
+
+        let count = db.get_rate_limit_count("nostr", window).await.unwrap();
+        assert_eq!(count, 5);
     }
 
     #[tokio::test]
-    async fn test_query_posts_with_date_range_filter() {
+    async fn test_increment_rate_limit_separate_platforms() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        let now = chrono::Utc::now().timestamp();
-        let one_hour_ago = now - 3600;
-        let two_hours_ago = now - 7200;
+        let window = 1234567890;
 
-        // Create posts with different timestamps
-        let post1 = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Old post".to_string(),
-            created_at: two_hours_ago,
-            scheduled_at: None,
-            status: PostStatus::Posted,
-            metadata: None,
-        };
+        db.increment_rate_limit("nostr", window).await.unwrap();
+        db.increment_rate_limit("nostr", window).await.unwrap();
+        db.increment_rate_limit("bluesky", window).await.unwrap();
 
-        let post2 = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Recent post".to_string(),
-            created_at: one_hour_ago,
-            scheduled_at: None,
-            status: PostStatus::Posted,
-            metadata: None,
-        };
+        let nostr_count = db.get_rate_limit_count("nostr", window).await.unwrap();
+        let bluesky_count = db.get_rate_limit_count("bluesky", window).await.unwrap();
 
-        let post3 = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "New post".to_string(),
-            created_at: now,
-            scheduled_at: None,
-            status: PostStatus::Posted,
-            metadata: None,
-        };
+        assert_eq!(nostr_count, 2);
+        assert_eq!(bluesky_count, 1);
+    }
 
-        db.create_post(&post1).await.unwrap();
-        db.create_post(&post2).await.unwrap();
-        db.create_post(&post3).await.unwrap();
+    #[tokio::test]
+    async fn test_cleanup_rate_limits_removes_old() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
 
-        // Query posts since one hour ago
-        let recent_posts = db
-            .filter_by_date_range(Some(one_hour_ago), None, 10)
-            .await
-            .unwrap();
-        assert_eq!(recent_posts.len(), 2);
-        assert!(recent_posts.iter().any(|p| p.post.id == post2.id));
-        assert!(recent_posts.iter().any(|p| p.post.id == post3.id));
+        let old_window = 1000000;
+        let recent_window = 2000000;
 
-        // Query posts until one hour ago
-        let old_posts = db
-            .filter_by_date_range(None, Some(one_hour_ago), 10)
+        db.increment_rate_limit("nostr", old_window).await.unwrap();
+        db.increment_rate_limit("nostr", recent_window)
             .await
             .unwrap();
-        assert_eq!(old_posts.len(), 2);
-        assert!(old_posts.iter().any(|p| p.post.id == post1.id));
-        assert!(old_posts.iter().any(|p| p.post.id == post2.id));
 
-        // Query posts in specific range
-        let range_posts = db
-            .filter_by_date_range(Some(two_hours_ago), Some(one_hour_ago), 10)
+        // Cleanup everything before 1500000
+        db.cleanup_rate_limits(1500000).await.unwrap();
+
+        let old_count = db.get_rate_limit_count("nostr", old_window).await.unwrap();
+        let recent_count = db
+            .get_rate_limit_count("nostr", recent_window)
             .await
             .unwrap();
-        assert_eq!(range_posts.len(), 2);
-    }
-
-    #[tokio::test]
-    async fn test_search_posts_by_content() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
 
-        // Create posts with different content
-        let post1 = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Learning Rust programming".to_string(),
-            created_at: chrono::Utc::now().timestamp(),
-            scheduled_at: None,
-            status: PostStatus::Posted,
-            metadata: None,
-        };
+        assert_eq!(old_count, 0);
+        assert_eq!(recent_count, 1);
+    }
 
-        let post2 = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Exploring Nostr protocol".to_string(),
-            created_at: chrono::Utc::now().timestamp(),
-            scheduled_at: None,
-            status: PostStatus::Posted,
-            metadata: None,
-        };
+    // ========================================================================
+    // Attachment CRUD Tests
+    // ========================================================================
 
-        let post3 = Post {
+    fn create_test_attachment(post_id: &str) -> Attachment {
+        Attachment {
             id: uuid::Uuid::new_v4().to_string(),
-            content: "Building with Rust and Nostr".to_string(),
+            post_id: post_id.to_string(),
+            file_path: "/path/to/image.jpg".to_string(),
+            mime_type: ImageMimeType::Jpeg,
+            file_size: 1024,
+            file_hash: format!("hash_{}", uuid::Uuid::new_v4()),
+            alt_text: Some("Test image".to_string()),
             created_at: chrono::Utc::now().timestamp(),
-            scheduled_at: None,
-            status: PostStatus::Posted,
-            metadata: None,
-        };
+        }
+    }
 
-        db.create_post(&post1).await.unwrap();
-        db.create_post(&post2).await.unwrap();
-        db.create_post(&post3).await.unwrap();
+    #[tokio::test]
+    async fn test_create_and_retrieve_attachment() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
 
-        // Search for "Rust"
-        let rust_posts = db.search_content("Rust", 10).await.unwrap();
-        assert_eq!(rust_posts.len(), 2);
-        assert!(rust_posts.iter().any(|p| p.post.id == post1.id));
-        assert!(rust_posts.iter().any(|p| p.post.id == post3.id));
+        // Create a post first (foreign key)
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
 
-        // Search for "Nostr"
-        let nostr_posts = db.search_content("Nostr", 10).await.unwrap();
-        assert_eq!(nostr_posts.len(), 2);
-        assert!(nostr_posts.iter().any(|p| p.post.id == post2.id));
-        assert!(nostr_posts.iter().any(|p| p.post.id == post3.id));
+        // Create attachment
+        let attachment = create_test_attachment(&post.id);
+        db.create_attachment(&attachment).await.unwrap();
 
-        // Search for "protocol"
-        let protocol_posts = db.search_content("protocol", 10).await.unwrap();
-        assert_eq!(protocol_posts.len(), 1);
-        assert_eq!(protocol_posts[0].post.id, post2.id);
+        // Retrieve attachment
+        let retrieved = db.get_attachment(&attachment.id).await.unwrap();
+        assert!(retrieved.is_some());
 
-        // Search for non-existent term
-        let empty_posts = db.search_content("blockchain", 10).await.unwrap();
-        assert_eq!(empty_posts.len(), 0);
+        let retrieved = retrieved.unwrap();
+        assert_eq!(retrieved.id, attachment.id);
+        assert_eq!(retrieved.post_id, post.id);
+        assert_eq!(retrieved.file_path, "/path/to/image.jpg");
+        assert_eq!(retrieved.mime_type, ImageMimeType::Jpeg);
+        assert_eq!(retrieved.file_size, 1024);
+        assert_eq!(retrieved.alt_text, Some("Test image".to_string()));
     }
 
     #[tokio::test]
-    async fn test_concurrent_writes_to_post_records() {
+    async fn test_get_attachments_for_post() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
         // Create a post
         let post = create_test_post();
         db.create_post(&post).await.unwrap();
 
-        // Concurrently create records for different platforms
-        let mut handles = vec![];
-        let platforms = vec!["nostr", "mastodon", "bluesky"];
-
-        for platform in platforms {
-            let pool_clone = db.pool.clone();
-            let post_id = post.id.clone();
-            let platform = platform.to_string();
-
-            let handle = tokio::spawn(async move {
-                let db = Database { pool: pool_clone };
-                let record = PostRecord {
-                    id: None,
-                    post_id,
-                    platform: platform.clone(),
-                    platform_post_id: Some(format!("{}_post_id", platform)),
-                    posted_at: Some(chrono::Utc::now().timestamp()),
-                    success: true,
-                    error_message: None,
-                    account_name: "default".to_string(),
-                };
-                db.create_post_record(&record).await
-            });
-
-            handles.push(handle);
+        // Create multiple attachments
+        for i in 0..3 {
+            let mut attachment = create_test_attachment(&post.id);
+            attachment.file_path = format!("/path/to/image{}.jpg", i);
+            attachment.created_at = chrono::Utc::now().timestamp() + i;
+            db.create_attachment(&attachment).await.unwrap();
         }
 
-        // Wait for all operations to complete
-        for handle in handles {
-            let result = handle.await.unwrap();
-            assert!(result.is_ok(), "Concurrent record creation should succeed");
-        }
+        // Retrieve all attachments
+        let attachments = db.get_attachments_for_post(&post.id).await.unwrap();
+        assert_eq!(attachments.len(), 3);
 
-        // Verify all records were created
-        let records = db.get_post_records(&post.id).await.unwrap();
-        assert_eq!(records.len(), 3);
+        // Verify ordering (oldest first)
+        assert!(attachments[0].created_at <= attachments[1].created_at);
+        assert!(attachments[1].created_at <= attachments[2].created_at);
     }
 
     #[tokio::test]
-    async fn test_query_with_multiple_filters() {
+    async fn test_delete_attachment() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        let now = chrono::Utc::now().timestamp();
-        let one_hour_ago = now - 3600;
-
-        // Create posts
-        let post1 = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Rust programming on Nostr".to_string(),
-            created_at: one_hour_ago,
-            scheduled_at: None,
-            status: PostStatus::Posted,
-            metadata: None,
-        };
-
-        let post2 = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Rust programming on Mastodon".to_string(),
-            created_at: now,
-            scheduled_at: None,
-            status: PostStatus::Posted,
-            metadata: None,
-        };
+        let db = Database { pool, cipher: None };
 
-        db.create_post(&post1).await.unwrap();
-        db.create_post(&post2).await.unwrap();
+        // Create a post and attachment
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
 
-        // Add platform records
-        db.create_post_record(&PostRecord {
-            id: None,
-            post_id: post1.id.clone(),
-            platform: "nostr".to_string(),
-            platform_post_id: Some("note1".to_string()),
-            posted_at: Some(one_hour_ago),
-            success: true,
-            error_message: None,
-            account_name: "default".to_string(),
-        })
-        .await
-        .unwrap();
+        let attachment = create_test_attachment(&post.id);
+        db.create_attachment(&attachment).await.unwrap();
 
-        db.create_post_record(&PostRecord {
-            id: None,
-            post_id: post2.id.clone(),
-            platform: "mastodon".to_string(),
-            platform_post_id: Some("12345".to_string()),
-            posted_at: Some(now),
-            success: true,
-            error_message: None,
-            account_name: "default".to_string(),
-        })
-        .await
-        .unwrap();
+        // Delete attachment
+        db.delete_attachment(&attachment.id).await.unwrap();
 
-        // Query with platform and search filters
-        let results = db
-            .query_posts_with_records(Some("nostr"), None, None, Some("Rust"), 10)
-            .await
-            .unwrap();
+        // Verify deleted
+        let retrieved = db.get_attachment(&attachment.id).await.unwrap();
+        assert!(retrieved.is_none());
+    }
 
-        assert_eq!(results.len(), 1);
-        assert_eq!(results[0].post.id, post1.id);
+    #[tokio::test]
+    async fn test_delete_attachments_for_post() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
 
-        // Query with date range and search filters
-        let results = db
-            .query_posts_with_records(
-                None,
-                Some(now - 1800), // 30 minutes ago
-                None,
-                Some("programming"),
-                10,
-            )
-            .await
-            .unwrap();
+        // Create a post with multiple attachments
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
 
-        assert_eq!(results.len(), 1);
-        assert_eq!(results[0].post.id, post2.id);
+        for _ in 0..3 {
+            let attachment = create_test_attachment(&post.id);
+            db.create_attachment(&attachment).await.unwrap();
+        }
+
+        // Delete all attachments for post
+        db.delete_attachments_for_post(&post.id).await.unwrap();
+
+        // Verify all deleted
+        let attachments = db.get_attachments_for_post(&post.id).await.unwrap();
+        assert_eq!(attachments.len(), 0);
     }
 
     #[tokio::test]
-    async fn test_query_respects_limit() {
+    async fn test_find_attachment_by_hash() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        // Create 10 posts
-        for i in 0..10 {
-            let post = Post {
-                id: uuid::Uuid::new_v4().to_string(),
-                content: format!("Post {}", i),
-                created_at: chrono::Utc::now().timestamp() + i,
-                scheduled_at: None,
-                status: PostStatus::Posted,
-                metadata: None,
-            };
-            db.create_post(&post).await.unwrap();
-        }
+        // Create a post and attachment
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
 
-        // Query with limit of 5
-        let results = db
-            .query_posts_with_records(None, None, None, None, 5)
+        let mut attachment = create_test_attachment(&post.id);
+        attachment.file_hash = "unique_hash_12345".to_string();
+        db.create_attachment(&attachment).await.unwrap();
+
+        // Find by hash
+        let found = db
+            .find_attachment_by_hash("unique_hash_12345")
             .await
             .unwrap();
-        assert_eq!(results.len(), 5);
+        assert!(found.is_some());
+        assert_eq!(found.unwrap().id, attachment.id);
 
-        // Query with limit of 3
-        let results = db
-            .query_posts_with_records(None, None, None, None, 3)
+        // Not found
+        let not_found = db
+            .find_attachment_by_hash("nonexistent_hash")
             .await
             .unwrap();
-        assert_eq!(results.len(), 3);
+        assert!(not_found.is_none());
     }
 
     #[tokio::test]
-    async fn test_get_post_records_empty() {
+    async fn test_attachment_cascade_delete() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        // Create a post without any records
+        // Enable foreign key constraints
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        // Create a post and attachment
         let post = create_test_post();
         db.create_post(&post).await.unwrap();
 
-        // Get records should return empty vector
-        let records = db.get_post_records(&post.id).await.unwrap();
-        assert_eq!(records.len(), 0);
+        let attachment = create_test_attachment(&post.id);
+        db.create_attachment(&attachment).await.unwrap();
+
+        // Delete post (should cascade to attachments)
+        db.delete_post(&post.id).await.unwrap();
+
+        // Verify attachment is deleted
+        let retrieved = db.get_attachment(&attachment.id).await.unwrap();
+        assert!(retrieved.is_none());
     }
 
+    // ========================================================================
+    // Attachment Upload CRUD Tests
+    // ========================================================================
+
     #[tokio::test]
-    async fn test_post_with_records_ordering() {
+    async fn test_create_and_retrieve_attachment_upload() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
+        // Enable foreign key constraints
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        // Create a post and attachment
         let post = create_test_post();
         db.create_post(&post).await.unwrap();
 
-        let now = chrono::Utc::now().timestamp();
+        let attachment = create_test_attachment(&post.id);
+        db.create_attachment(&attachment).await.unwrap();
 
-        // Create records with different timestamps
-        for i in 0..3 {
-            let record = PostRecord {
-                id: None,
-                post_id: post.id.clone(),
-                platform: format!("platform{}", i),
-                platform_post_id: Some(format!("post{}", i)),
-                posted_at: Some(now + i),
-                success: true,
-                error_message: None,
-                account_name: "default".to_string(),
-            };
-            db.create_post_record(&record).await.unwrap();
-            // Small delay to ensure different timestamps
-            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
-        }
+        // Create upload record
+        let upload = AttachmentUpload::new_pending(attachment.id.clone(), "mastodon".to_string());
+        db.create_attachment_upload(&upload).await.unwrap();
 
-        // Get records - should be ordered by posted_at DESC
-        let records = db.get_post_records(&post.id).await.unwrap();
-        assert_eq!(records.len(), 3);
+        // Retrieve upload
+        let retrieved = db
+            .get_attachment_upload(&attachment.id, "mastodon")
+            .await
+            .unwrap();
+        assert!(retrieved.is_some());
 
-        // Verify descending order
-        for i in 0..records.len() - 1 {
-            assert!(records[i].posted_at >= records[i + 1].posted_at);
-        }
+        let retrieved = retrieved.unwrap();
+        assert_eq!(retrieved.attachment_id, attachment.id);
+        assert_eq!(retrieved.platform, "mastodon");
+        assert_eq!(retrieved.status, AttachmentStatus::Pending);
     }
 
-    // SCHEDULING TESTS (Phase 5.1 Task 2 & 3)
-
     #[tokio::test]
-    async fn test_migration_creates_scheduling_indexes() {
+    async fn test_update_attachment_upload_success() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
 
-        // Verify idx_posts_scheduled_at index exists
-        let result = sqlx::query(
-            r#"
-            SELECT name FROM sqlite_master
-            WHERE type='index' AND name='idx_posts_scheduled_at'
-            "#,
-        )
-        .fetch_optional(&pool)
-        .await
-        .unwrap();
-        assert!(result.is_some(), "idx_posts_scheduled_at index not created");
+        // Create a post and attachment
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
 
-        // Verify idx_posts_status_scheduled index exists
-        let result = sqlx::query(
-            r#"
-            SELECT name FROM sqlite_master
-            WHERE type='index' AND name='idx_posts_status_scheduled'
-            "#,
+        let attachment = create_test_attachment(&post.id);
+        db.create_attachment(&attachment).await.unwrap();
+
+        // Create and update upload record
+        let upload = AttachmentUpload::new_pending(attachment.id.clone(), "mastodon".to_string());
+        db.create_attachment_upload(&upload).await.unwrap();
+
+        db.update_attachment_upload(
+            &attachment.id,
+            "mastodon",
+            Some("media_123"),
+            Some("https://example.com/media.jpg"),
+            AttachmentStatus::Uploaded,
+            None,
         )
-        .fetch_optional(&pool)
         .await
         .unwrap();
-        assert!(
-            result.is_some(),
-            "idx_posts_status_scheduled index not created"
+
+        // Verify update
+        let retrieved = db
+            .get_attachment_upload(&attachment.id, "mastodon")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(retrieved.status, AttachmentStatus::Uploaded);
+        assert_eq!(
+            retrieved.platform_attachment_id,
+            Some("media_123".to_string())
+        );
+        assert_eq!(
+            retrieved.remote_url,
+            Some("https://example.com/media.jpg".to_string())
         );
+        assert!(retrieved.uploaded_at.is_some());
     }
 
     #[tokio::test]
-    async fn test_migration_creates_rate_limits_table() {
+    async fn test_update_attachment_upload_failure() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
 
-        // Verify rate_limits table exists
-        let result = sqlx::query(
-            r#"
-            SELECT name FROM sqlite_master
-            WHERE type='table' AND name='rate_limits'
-            "#,
-        )
-        .fetch_optional(&pool)
-        .await
-        .unwrap();
-        assert!(result.is_some(), "rate_limits table not created");
+        // Create a post and attachment
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
 
-        // Verify table structure by inserting and querying
-        sqlx::query(
-            r#"
-            INSERT INTO rate_limits (platform, window_start, post_count)
-            VALUES ('nostr', 1234567890, 5)
-            "#,
-        )
-        .execute(&pool)
-        .await
-        .unwrap();
+        let attachment = create_test_attachment(&post.id);
+        db.create_attachment(&attachment).await.unwrap();
 
-        let count: (i64,) = sqlx::query_as(
-            r#"
-            SELECT post_count FROM rate_limits
-            WHERE platform = 'nostr' AND window_start = 1234567890
-            "#,
+        // Create and update upload record
+        let upload = AttachmentUpload::new_pending(attachment.id.clone(), "nostr".to_string());
+        db.create_attachment_upload(&upload).await.unwrap();
+
+        db.update_attachment_upload(
+            &attachment.id,
+            "nostr",
+            None,
+            None,
+            AttachmentStatus::Failed,
+            Some("Network timeout"),
         )
-        .fetch_one(&pool)
         .await
         .unwrap();
 
-        assert_eq!(count.0, 5);
+        // Verify update
+        let retrieved = db
+            .get_attachment_upload(&attachment.id, "nostr")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(retrieved.status, AttachmentStatus::Failed);
+        assert_eq!(retrieved.error_message, Some("Network timeout".to_string()));
     }
 
     #[tokio::test]
-    async fn test_get_scheduled_posts_due_returns_only_due_posts() {
+    async fn test_get_attachment_uploads() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        let now = chrono::Utc::now().timestamp();
-
-        // Create post scheduled in the past (due)
-        let past_post = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Past post".to_string(),
-            created_at: now - 3600,
-            scheduled_at: Some(now - 1800),
-            status: PostStatus::Scheduled,
-            metadata: None,
-        };
-        db.create_post(&past_post).await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
 
-        // Create post scheduled for future (not due)
-        let future_post = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Future post".to_string(),
-            created_at: now,
-            scheduled_at: Some(now + 3600),
-            status: PostStatus::Scheduled,
-            metadata: None,
-        };
-        db.create_post(&future_post).await.unwrap();
+        // Create a post and attachment
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
 
-        // Create posted post (not scheduled)
-        let posted_post = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Posted post".to_string(),
-            created_at: now - 7200,
-            scheduled_at: Some(now - 3600),
-            status: PostStatus::Posted,
-            metadata: None,
-        };
-        db.create_post(&posted_post).await.unwrap();
+        let attachment = create_test_attachment(&post.id);
+        db.create_attachment(&attachment).await.unwrap();
 
-        let due_posts = db.get_scheduled_posts_due().await.unwrap();
+        // Create upload records for multiple platforms
+        for platform in ["nostr", "mastodon"] {
+            let upload = AttachmentUpload::new_pending(attachment.id.clone(), platform.to_string());
+            db.create_attachment_upload(&upload).await.unwrap();
+        }
 
-        assert_eq!(due_posts.len(), 1);
-        assert_eq!(due_posts[0].id, past_post.id);
-        assert_eq!(due_posts[0].content, "Past post");
+        // Get all uploads
+        let uploads = db.get_attachment_uploads(&attachment.id).await.unwrap();
+        assert_eq!(uploads.len(), 2);
     }
 
     #[tokio::test]
-    async fn test_get_scheduled_posts_due_empty_when_none_due() {
+    async fn test_get_pending_uploads() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        let now = chrono::Utc::now().timestamp();
+        // Create a post and attachments
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
 
-        // Create only future posts
-        let future_post = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Future post".to_string(),
-            created_at: now,
-            scheduled_at: Some(now + 3600),
-            status: PostStatus::Scheduled,
-            metadata: None,
-        };
-        db.create_post(&future_post).await.unwrap();
+        let attachment1 = create_test_attachment(&post.id);
+        let attachment2 = create_test_attachment(&post.id);
+        db.create_attachment(&attachment1).await.unwrap();
+        db.create_attachment(&attachment2).await.unwrap();
 
-        let due_posts = db.get_scheduled_posts_due().await.unwrap();
-        assert_eq!(due_posts.len(), 0);
+        // Create pending upload for attachment1
+        let upload1 = AttachmentUpload::new_pending(attachment1.id.clone(), "mastodon".to_string());
+        db.create_attachment_upload(&upload1).await.unwrap();
+
+        // Create completed upload for attachment2
+        let mut upload2 =
+            AttachmentUpload::new_pending(attachment2.id.clone(), "mastodon".to_string());
+        upload2.status = AttachmentStatus::Uploaded;
+        db.create_attachment_upload(&upload2).await.unwrap();
+        db.update_attachment_upload(
+            &attachment2.id,
+            "mastodon",
+            Some("media_id"),
+            None,
+            AttachmentStatus::Uploaded,
+            None,
+        )
+        .await
+        .unwrap();
+
+        // Get pending uploads
+        let pending = db.get_pending_uploads("mastodon").await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].attachment_id, attachment1.id);
     }
 
     #[tokio::test]
-    async fn test_get_scheduled_posts_returns_all_scheduled() {
+    async fn test_attachment_upload_cascade_delete() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        let now = chrono::Utc::now().timestamp();
+        // Enable foreign key constraints
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&db.pool)
+            .await
+            .unwrap();
 
-        // Create scheduled posts with different times
-        for i in 0..3 {
-            let post = Post {
-                id: uuid::Uuid::new_v4().to_string(),
-                content: format!("Scheduled post {}", i),
-                created_at: now,
-                scheduled_at: Some(now + (i * 3600)),
-                status: PostStatus::Scheduled,
-                metadata: None,
-            };
-            db.create_post(&post).await.unwrap();
-        }
+        // Create a post, attachment, and upload
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
 
-        // Create non-scheduled post
-        let posted = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Posted".to_string(),
-            created_at: now,
-            scheduled_at: None,
-            status: PostStatus::Posted,
-            metadata: None,
-        };
-        db.create_post(&posted).await.unwrap();
+        let attachment = create_test_attachment(&post.id);
+        db.create_attachment(&attachment).await.unwrap();
 
-        let scheduled = db.get_scheduled_posts().await.unwrap();
+        let upload = AttachmentUpload::new_pending(attachment.id.clone(), "nostr".to_string());
+        db.create_attachment_upload(&upload).await.unwrap();
 
-        assert_eq!(scheduled.len(), 3);
-        // Verify ordering (ASC by scheduled_at)
-        for i in 0..scheduled.len() - 1 {
-            assert!(scheduled[i].scheduled_at <= scheduled[i + 1].scheduled_at);
-        }
+        // Delete attachment (should cascade to uploads)
+        db.delete_attachment(&attachment.id).await.unwrap();
+
+        // Verify upload is deleted
+        let retrieved = db
+            .get_attachment_upload(&attachment.id, "nostr")
+            .await
+            .unwrap();
+        assert!(retrieved.is_none());
     }
 
     #[tokio::test]
-    async fn test_get_last_scheduled_timestamp_returns_max() {
+    async fn test_attachment_upload_unique_constraint() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        let now = chrono::Utc::now().timestamp();
+        // Create a post and attachment
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
 
-        // Create scheduled posts
-        let times = vec![now + 1000, now + 5000, now + 3000];
-        for time in &times {
-            let post = Post {
-                id: uuid::Uuid::new_v4().to_string(),
-                content: "Scheduled".to_string(),
-                created_at: now,
-                scheduled_at: Some(*time),
-                status: PostStatus::Scheduled,
-                metadata: None,
-            };
-            db.create_post(&post).await.unwrap();
-        }
+        let attachment = create_test_attachment(&post.id);
+        db.create_attachment(&attachment).await.unwrap();
 
-        let max_time = db.get_last_scheduled_timestamp().await.unwrap();
+        // Create first upload
+        let upload = AttachmentUpload::new_pending(attachment.id.clone(), "mastodon".to_string());
+        db.create_attachment_upload(&upload).await.unwrap();
 
-        assert_eq!(max_time, Some(now + 5000));
+        // Try to create duplicate (same attachment_id + platform)
+        let duplicate =
+            AttachmentUpload::new_pending(attachment.id.clone(), "mastodon".to_string());
+        let result = db.create_attachment_upload(&duplicate).await;
+
+        // Should fail due to UNIQUE constraint
+        assert!(result.is_err());
     }
 
     #[tokio::test]
-    async fn test_get_last_scheduled_timestamp_none_when_empty() {
+    async fn test_migration_creates_attachment_tables() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
 
-        let max_time = db.get_last_scheduled_timestamp().await.unwrap();
-        assert_eq!(max_time, None);
+        // Verify attachments table exists
+        let result = sqlx::query(
+            r#"
+            SELECT name FROM sqlite_master
+            WHERE type='table' AND name='attachments'
+            "#,
+        )
+        .fetch_optional(&pool)
+        .await
+        .unwrap();
+        assert!(result.is_some(), "attachments table not created");
+
+        // Verify attachment_uploads table exists
+        let result = sqlx::query(
+            r#"
+            SELECT name FROM sqlite_master
+            WHERE type='table' AND name='attachment_uploads'
+            "#,
+        )
+        .fetch_optional(&pool)
+        .await
+        .unwrap();
+        assert!(result.is_some(), "attachment_uploads table not created");
+
+        // Verify indexes exist
+        let result = sqlx::query(
+            r#"
+            SELECT name FROM sqlite_master
+            WHERE type='index' AND name='idx_attachments_post_id'
+            "#,
+        )
+        .fetch_optional(&pool)
+        .await
+        .unwrap();
+        assert!(
+            result.is_some(),
+            "idx_attachments_post_id index not created"
+        );
     }
 
+    // Tests for get_platform_post_ids (cross-platform reply-to lookup)
+
     #[tokio::test]
-    async fn test_get_last_scheduled_timestamp_ignores_posted() {
+    async fn test_get_platform_post_ids_success() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        let now = chrono::Utc::now().timestamp();
+        // Create a post with successful records on multiple platforms
+        let post = create_test_post();
+        db.create_post(&post).await.unwrap();
 
-        // Create posted post with scheduled_at
-        let posted = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Posted".to_string(),
-            created_at: now,
-            scheduled_at: Some(now + 10000),
-            status: PostStatus::Posted,
-            metadata: None,
-        };
-        db.create_post(&posted).await.unwrap();
+        // Add successful Nostr record
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("note1abc123".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
 
-        // Create scheduled post with earlier time
-        let scheduled = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Scheduled".to_string(),
-            created_at: now,
-            scheduled_at: Some(now + 1000),
-            status: PostStatus::Scheduled,
-            metadata: None,
-        };
-        db.create_post(&scheduled).await.unwrap();
+        // Add successful Mastodon record
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: "mastodon".to_string(),
+            platform_post_id: Some("12345678".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
 
-        let max_time = db.get_last_scheduled_timestamp().await.unwrap();
+        // Look up platform IDs
+        let result = db.get_platform_post_ids(&post.id).await.unwrap();
 
-        // Should return scheduled post time, not posted post time
-        assert_eq!(max_time, Some(now + 1000));
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.get("nostr"), Some(&"note1abc123".to_string()));
+        assert_eq!(result.get("mastodon"), Some(&"12345678".to_string()));
     }
 
     #[tokio::test]
-    async fn test_update_post_schedule_changes_time() {
+    async fn test_get_platform_post_ids_partial_success() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        let now = chrono::Utc::now().timestamp();
+        let db = Database { pool, cipher: None };
 
-        let post = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Test".to_string(),
-            created_at: now,
-            scheduled_at: Some(now + 1000),
-            status: PostStatus::Scheduled,
-            metadata: None,
-        };
+        // Create a post with one success and one failure
+        let post = create_test_post();
         db.create_post(&post).await.unwrap();
 
-        // Update schedule time
-        let new_time = now + 5000;
-        db.update_post_schedule(&post.id, Some(new_time))
-            .await
-            .unwrap();
+        // Add successful Nostr record
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("note1abc123".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
 
-        let updated = db.get_post(&post.id).await.unwrap().unwrap();
-        assert_eq!(updated.scheduled_at, Some(new_time));
+        // Add failed Mastodon record
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: "mastodon".to_string(),
+            platform_post_id: None,
+            posted_at: None,
+            success: false,
+            error_message: Some("Authentication failed".to_string()),
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
+
+        // Look up platform IDs - should only return successful ones
+        let result = db.get_platform_post_ids(&post.id).await.unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.get("nostr"), Some(&"note1abc123".to_string()));
+        assert!(result.get("mastodon").is_none());
     }
 
     #[tokio::test]
-    async fn test_update_post_schedule_can_clear_time() {
+    async fn test_get_platform_post_ids_not_found() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        let now = chrono::Utc::now().timestamp();
-
-        let post = Post {
-            id: uuid::Uuid::new_v4().to_string(),
-            content: "Test".to_string(),
-            created_at: now,
-            scheduled_at: Some(now + 1000),
-            status: PostStatus::Scheduled,
-            metadata: None,
-        };
-        db.create_post(&post).await.unwrap();
+        let db = Database { pool, cipher: None };
 
-        // Clear schedule time
-        db.update_post_schedule(&post.id, None).await.unwrap();
+        // Query for non-existent post
+        let result = db.get_platform_post_ids("nonexistent-uuid").await.unwrap();
 
-        let updated = db.get_post(&post.id).await.unwrap().unwrap();
-        assert_eq!(updated.scheduled_at, None);
+        // Should return empty map
+        assert!(result.is_empty());
     }
 
     #[tokio::test]
-    async fn test_delete_post_removes_post() {
+    async fn test_get_platform_post_ids_null_platform_post_id() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
+        // Create a post with a successful record but null platform_post_id
         let post = create_test_post();
         db.create_post(&post).await.unwrap();
 
-        db.delete_post(&post.id).await.unwrap();
+        // Add record with success=true but platform_post_id=null (edge case)
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: "nostr".to_string(),
+            platform_post_id: None, // Edge case: success but no ID
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
 
-        let retrieved = db.get_post(&post.id).await.unwrap();
-        assert!(retrieved.is_none());
+        // Should not include records with null platform_post_id
+        let result = db.get_platform_post_ids(&post.id).await.unwrap();
+        assert!(result.is_empty());
     }
 
+    // Tests for get_post_id_by_platform_post_id (reverse lookup for cross-platform reply-to)
+
     #[tokio::test]
-    async fn test_delete_post_removes_records() {
+    async fn test_get_post_id_by_platform_post_id_found() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        let post = create_test_post();
+        // Create a post with successful record
+        let post = Post::new("Test content".to_string());
         db.create_post(&post).await.unwrap();
 
-        // Add a record
-        let record = PostRecord {
+        db.create_post_record(&PostRecord {
             id: None,
             post_id: post.id.clone(),
             platform: "nostr".to_string(),
-            platform_post_id: Some("note1abc".to_string()),
+            platform_post_id: Some("note1abc123".to_string()),
             posted_at: Some(chrono::Utc::now().timestamp()),
             success: true,
             error_message: None,
             account_name: "default".to_string(),
-        };
-        db.create_post_record(&record).await.unwrap();
-
-        db.delete_post(&post.id).await.unwrap();
-
-        let records = db.get_post_records(&post.id).await.unwrap();
-        assert_eq!(records.len(), 0);
-    }
+        })
+        .await
+        .unwrap();
 
-    #[tokio::test]
-    async fn test_get_rate_limit_count_zero_when_empty() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        // Look up by platform_post_id
+        let result = db
+            .get_post_id_by_platform_post_id("nostr", "note1abc123")
+            .await
+            .unwrap();
 
-        let count = db.get_rate_limit_count("nostr", 1234567890).await.unwrap();
-        assert_eq!(count, 0);
+        assert_eq!(result, Some(post.id));
     }
 
     #[tokio::test]
-    async fn test_increment_rate_limit_creates_record() {
+    async fn test_get_post_id_by_platform_post_id_not_found() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        let window = 1234567890;
-        db.increment_rate_limit("nostr", window).await.unwrap();
+        // Query for non-existent platform_post_id
+        let result = db
+            .get_post_id_by_platform_post_id("nostr", "note1nonexistent")
+            .await
+            .unwrap();
 
-        let count = db.get_rate_limit_count("nostr", window).await.unwrap();
-        assert_eq!(count, 1);
+        assert_eq!(result, None);
     }
 
     #[tokio::test]
-    async fn test_increment_rate_limit_increments_existing() {
+    async fn test_get_post_id_by_platform_post_id_failed_record() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        let window = 1234567890;
+        // Create a post with FAILED record (success = false)
+        let post = Post::new("Test content".to_string());
+        db.create_post(&post).await.unwrap();
 
-        // Increment multiple times
-        for _ in 0..5 {
-            db.increment_rate_limit("nostr", window).await.unwrap();
-        }
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("note1failed".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: false, // Failed record
+            error_message: Some("Network error".to_string()),
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
 
-        let count = db.get_rate_limit_count("nostr", window).await.unwrap();
-        assert_eq!(count, 5);
+        // Should NOT return failed records
+        let result = db
+            .get_post_id_by_platform_post_id("nostr", "note1failed")
+            .await
+            .unwrap();
+
+        assert_eq!(result, None);
     }
 
     #[tokio::test]
-    async fn test_increment_rate_limit_separate_platforms() {
+    async fn test_get_post_id_by_platform_post_id_wrong_platform() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        let window = 1234567890;
+        // Create a post with successful Nostr record
+        let post = Post::new("Test content".to_string());
+        db.create_post(&post).await.unwrap();
 
-        db.increment_rate_limit("nostr", window).await.unwrap();
-        db.increment_rate_limit("nostr", window).await.unwrap();
-        db.increment_rate_limit("bluesky", window).await.unwrap();
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("note1abc123".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
 
-        let nostr_count = db.get_rate_limit_count("nostr", window).await.unwrap();
-        let bluesky_count = db.get_rate_limit_count("bluesky", window).await.unwrap();
+        // Look up with correct ID but wrong platform - should not find it
+        let result = db
+            .get_post_id_by_platform_post_id("mastodon", "note1abc123")
+            .await
+            .unwrap();
 
-        assert_eq!(nostr_count, 2);
-        assert_eq!(bluesky_count, 1);
+        assert_eq!(result, None);
     }
 
     #[tokio::test]
-    async fn test_cleanup_rate_limits_removes_old() {
+    async fn test_get_post_id_by_platform_post_id_cross_platform_lookup() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
 
-        let old_window = 1000000;
-        let recent_window = 2000000;
+        // Create a post with successful records on multiple platforms
+        let post = Post::new("Cross-platform post".to_string());
+        db.create_post(&post).await.unwrap();
+
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("note1cross123".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
+
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: "mastodon".to_string(),
+            platform_post_id: Some("123456789".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
 
-        db.increment_rate_limit("nostr", old_window).await.unwrap();
-        db.increment_rate_limit("nostr", recent_window)
+        // Look up by Nostr ID
+        let uuid_from_nostr = db
+            .get_post_id_by_platform_post_id("nostr", "note1cross123")
             .await
             .unwrap();
+        assert_eq!(uuid_from_nostr, Some(post.id.clone()));
 
-        // Cleanup everything before 1500000
-        db.cleanup_rate_limits(1500000).await.unwrap();
-
-        let old_count = db.get_rate_limit_count("nostr", old_window).await.unwrap();
-        let recent_count = db
-            .get_rate_limit_count("nostr", recent_window)
+        // Look up by Mastodon ID - should return the same UUID
+        let uuid_from_mastodon = db
+            .get_post_id_by_platform_post_id("mastodon", "123456789")
             .await
             .unwrap();
+        assert_eq!(uuid_from_mastodon, Some(post.id.clone()));
 
-        assert_eq!(old_count, 0);
-        assert_eq!(recent_count, 1);
+        // Now use the UUID to get all platform IDs (the full cross-platform workflow)
+        let all_ids = db.get_platform_post_ids(&post.id).await.unwrap();
+        assert_eq!(all_ids.len(), 2);
+        assert_eq!(all_ids.get("nostr"), Some(&"note1cross123".to_string()));
+        assert_eq!(all_ids.get("mastodon"), Some(&"123456789".to_string()));
     }
 
     // ========================================================================
-    // Attachment CRUD Tests
+    // Raw response tests
     // ========================================================================
 
-    fn create_test_attachment(post_id: &str) -> Attachment {
-        Attachment {
-            id: uuid::Uuid::new_v4().to_string(),
+    fn success_record(post_id: &str, platform: &str, platform_post_id: &str) -> PostRecord {
+        PostRecord {
+            id: None,
             post_id: post_id.to_string(),
-            file_path: "/path/to/image.jpg".to_string(),
-            mime_type: ImageMimeType::Jpeg,
-            file_size: 1024,
-            file_hash: format!("hash_{}", uuid::Uuid::new_v4()),
-            alt_text: Some("Test image".to_string()),
-            created_at: chrono::Utc::now().timestamp(),
+            platform: platform.to_string(),
+            platform_post_id: Some(platform_post_id.to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
         }
     }
 
     #[tokio::test]
-    async fn test_create_and_retrieve_attachment() {
+    async fn test_raw_responses_roundtrip() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        // Create a post first (foreign key)
+        let db = Database { pool, cipher: None };
         let post = create_test_post();
         db.create_post(&post).await.unwrap();
 
-        // Create attachment
-        let attachment = create_test_attachment(&post.id);
-        db.create_attachment(&attachment).await.unwrap();
+        let status = serde_json::json!({
+            "id": "109876",
+            "url": "https://mastodon.social/@alice/109876"
+        });
+        db.create_post_record_with_response(
+            &success_record(&post.id, "mastodon", "109876"),
+            Some(&status),
+        )
+        .await
+        .unwrap();
+        // Records without a response are not included
+        db.create_post_record(&success_record(&post.id, "nostr", "note1abc"))
+            .await
+            .unwrap();
 
-        // Retrieve attachment
-        let retrieved = db.get_attachment(&attachment.id).await.unwrap();
-        assert!(retrieved.is_some());
+        let responses = db.get_raw_responses(&post.id).await.unwrap();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses.get("mastodon"), Some(&status));
 
-        let retrieved = retrieved.unwrap();
-        assert_eq!(retrieved.id, attachment.id);
-        assert_eq!(retrieved.post_id, post.id);
-        assert_eq!(retrieved.file_path, "/path/to/image.jpg");
-        assert_eq!(retrieved.mime_type, ImageMimeType::Jpeg);
-        assert_eq!(retrieved.file_size, 1024);
-        assert_eq!(retrieved.alt_text, Some("Test image".to_string()));
+        // The record itself is unchanged
+        let records = db.get_post_records(&post.id).await.unwrap();
+        assert_eq!(records.len(), 2);
     }
 
     #[tokio::test]
-    async fn test_get_attachments_for_post() {
+    async fn test_raw_responses_ignore_failed_records() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        // Create a post
+        let db = Database { pool, cipher: None };
         let post = create_test_post();
         db.create_post(&post).await.unwrap();
 
-        // Create multiple attachments
-        for i in 0..3 {
-            let mut attachment = create_test_attachment(&post.id);
-            attachment.file_path = format!("/path/to/image{}.jpg", i);
-            attachment.created_at = chrono::Utc::now().timestamp() + i;
-            db.create_attachment(&attachment).await.unwrap();
-        }
-
-        // Retrieve all attachments
-        let attachments = db.get_attachments_for_post(&post.id).await.unwrap();
-        assert_eq!(attachments.len(), 3);
+        let mut record = success_record(&post.id, "nostr", "note1abc");
+        record.success = false;
+        record.platform_post_id = None;
+        db.create_post_record_with_response(&record, Some(&serde_json::json!({"error": true})))
+            .await
+            .unwrap();
 
-        // Verify ordering (oldest first)
-        assert!(attachments[0].created_at <= attachments[1].created_at);
-        assert!(attachments[1].created_at <= attachments[2].created_at);
+        assert!(db.get_raw_responses(&post.id).await.unwrap().is_empty());
     }
 
-    #[tokio::test]
-    async fn test_delete_attachment() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        // Create a post and attachment
-        let post = create_test_post();
-        db.create_post(&post).await.unwrap();
-
-        let attachment = create_test_attachment(&post.id);
-        db.create_attachment(&attachment).await.unwrap();
-
-        // Delete attachment
-        db.delete_attachment(&attachment.id).await.unwrap();
-
-        // Verify deleted
-        let retrieved = db.get_attachment(&attachment.id).await.unwrap();
-        assert!(retrieved.is_none());
-    }
+    // ========================================================================
+    // Content encryption tests
+    // ========================================================================
 
-    #[tokio::test]
-    async fn test_delete_attachments_for_post() {
+    async fn encrypted_test_db() -> Database {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        // Create a post with multiple attachments
-        let post = create_test_post();
-        db.create_post(&post).await.unwrap();
-
-        for _ in 0..3 {
-            let attachment = create_test_attachment(&post.id);
-            db.create_attachment(&attachment).await.unwrap();
-        }
-
-        // Delete all attachments for post
-        db.delete_attachments_for_post(&post.id).await.unwrap();
-
-        // Verify all deleted
-        let attachments = db.get_attachments_for_post(&post.id).await.unwrap();
-        assert_eq!(attachments.len(), 0);
+        Database { pool, cipher: None }.with_cipher(ContentCipher::generate())
     }
 
     #[tokio::test]
-    async fn test_find_attachment_by_hash() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        // Create a post and attachment
+    async fn test_encrypted_content_is_not_stored_in_plaintext() {
+        let db = encrypted_test_db().await;
         let post = create_test_post();
         db.create_post(&post).await.unwrap();
 
-        let mut attachment = create_test_attachment(&post.id);
-        attachment.file_hash = "unique_hash_12345".to_string();
-        db.create_attachment(&attachment).await.unwrap();
-
-        // Find by hash
-        let found = db
-            .find_attachment_by_hash("unique_hash_12345")
+        let stored: String = sqlx::query_scalar("SELECT content FROM posts WHERE id = ?")
+            .bind(&post.id)
+            .fetch_one(db.pool())
             .await
             .unwrap();
-        assert!(found.is_some());
-        assert_eq!(found.unwrap().id, attachment.id);
+        assert!(ContentCipher::is_encrypted(&stored));
+        assert!(!stored.contains("Test post content"));
 
-        // Not found
-        let not_found = db
-            .find_attachment_by_hash("nonexistent_hash")
-            .await
-            .unwrap();
-        assert!(not_found.is_none());
+        let retrieved = db.get_post(&post.id).await.unwrap().unwrap();
+        assert_eq!(retrieved.content, "Test post content");
     }
 
     #[tokio::test]
-    async fn test_attachment_cascade_delete() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+    async fn test_encrypted_update_and_scheduled_listing() {
+        let db = encrypted_test_db().await;
+        let mut post = create_test_post();
+        post.status = PostStatus::Scheduled;
+        post.scheduled_at = Some(chrono::Utc::now().timestamp() - 60);
+        db.create_post(&post).await.unwrap();
 
-        // Enable foreign key constraints
-        sqlx::query("PRAGMA foreign_keys = ON")
-            .execute(&db.pool)
+        db.update_post_content(&post.id, "Edited content".to_string())
             .await
             .unwrap();
 
-        // Create a post and attachment
-        let post = create_test_post();
-        db.create_post(&post).await.unwrap();
-
-        let attachment = create_test_attachment(&post.id);
-        db.create_attachment(&attachment).await.unwrap();
-
-        // Delete post (should cascade to attachments)
-        db.delete_post(&post.id).await.unwrap();
+        let scheduled = db.get_scheduled_posts().await.unwrap();
+        assert_eq!(scheduled.len(), 1);
+        assert_eq!(scheduled[0].content, "Edited content");
 
-        // Verify attachment is deleted
-        let retrieved = db.get_attachment(&attachment.id).await.unwrap();
-        assert!(retrieved.is_none());
+        let due = db.get_scheduled_posts_due().await.unwrap();
+        assert_eq!(due[0].content, "Edited content");
     }
 
-    // ========================================================================
-    // Attachment Upload CRUD Tests
-    // ========================================================================
-
     #[tokio::test]
-    async fn test_create_and_retrieve_attachment_upload() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        // Enable foreign key constraints
-        sqlx::query("PRAGMA foreign_keys = ON")
-            .execute(&db.pool)
-            .await
-            .unwrap();
-
-        // Create a post and attachment
-        let post = create_test_post();
-        db.create_post(&post).await.unwrap();
-
-        let attachment = create_test_attachment(&post.id);
-        db.create_attachment(&attachment).await.unwrap();
+    async fn test_encrypted_search_filters_after_decryption() {
+        let db = encrypted_test_db().await;
 
-        // Create upload record
-        let upload = AttachmentUpload::new_pending(attachment.id.clone(), "mastodon".to_string());
-        db.create_attachment_upload(&upload).await.unwrap();
+        for content in ["Learning Rust", "Exploring Nostr", "rust and nostr"] {
+            let mut post = create_test_post();
+            post.content = content.to_string();
+            db.create_post(&post).await.unwrap();
+        }
 
-        // Retrieve upload
-        let retrieved = db
-            .get_attachment_upload(&attachment.id, "mastodon")
-            .await
-            .unwrap();
-        assert!(retrieved.is_some());
+        let results = db.search_content("rust", 10).await.unwrap();
+        assert_eq!(results.len(), 2);
 
-        let retrieved = retrieved.unwrap();
-        assert_eq!(retrieved.attachment_id, attachment.id);
-        assert_eq!(retrieved.platform, "mastodon");
-        assert_eq!(retrieved.status, AttachmentStatus::Pending);
+        let limited = db.search_content("rust", 1).await.unwrap();
+        assert_eq!(limited.len(), 1);
     }
 
     #[tokio::test]
-    async fn test_update_attachment_upload_success() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        // Create a post and attachment
+    async fn test_encrypted_raw_responses_are_not_stored_in_plaintext() {
+        let db = encrypted_test_db().await;
         let post = create_test_post();
         db.create_post(&post).await.unwrap();
 
-        let attachment = create_test_attachment(&post.id);
-        db.create_attachment(&attachment).await.unwrap();
-
-        // Create and update upload record
-        let upload = AttachmentUpload::new_pending(attachment.id.clone(), "mastodon".to_string());
-        db.create_attachment_upload(&upload).await.unwrap();
-
-        db.update_attachment_upload(
-            &attachment.id,
-            "mastodon",
-            Some("media_123"),
-            Some("https://example.com/media.jpg"),
-            AttachmentStatus::Uploaded,
-            None,
+        let event = serde_json::json!({"event": {"content": "Test post content"}});
+        db.create_post_record_with_response(
+            &success_record(&post.id, "nostr", "note1abc"),
+            Some(&event),
         )
         .await
         .unwrap();
 
-        // Verify update
-        let retrieved = db
-            .get_attachment_upload(&attachment.id, "mastodon")
-            .await
-            .unwrap()
-            .unwrap();
+        let stored: String =
+            sqlx::query_scalar("SELECT raw_response FROM post_records WHERE post_id = ?")
+                .bind(&post.id)
+                .fetch_one(db.pool())
+                .await
+                .unwrap();
+        assert!(ContentCipher::is_encrypted(&stored));
 
-        assert_eq!(retrieved.status, AttachmentStatus::Uploaded);
-        assert_eq!(
-            retrieved.platform_attachment_id,
-            Some("media_123".to_string())
-        );
-        assert_eq!(
-            retrieved.remote_url,
-            Some("https://example.com/media.jpg".to_string())
-        );
-        assert!(retrieved.uploaded_at.is_some());
+        let responses = db.get_raw_responses(&post.id).await.unwrap();
+        assert_eq!(responses.get("nostr"), Some(&event));
     }
 
     #[tokio::test]
-    async fn test_update_attachment_upload_failure() {
+    async fn test_encrypted_database_reads_legacy_plaintext_rows() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        // Create a post and attachment
+        let plain = Database { pool, cipher: None };
         let post = create_test_post();
-        db.create_post(&post).await.unwrap();
+        plain.create_post(&post).await.unwrap();
 
-        let attachment = create_test_attachment(&post.id);
-        db.create_attachment(&attachment).await.unwrap();
+        let encrypted = plain.with_cipher(ContentCipher::generate());
+        let retrieved = encrypted.get_post(&post.id).await.unwrap().unwrap();
+        assert_eq!(retrieved.content, "Test post content");
+    }
 
-        // Create and update upload record
-        let upload = AttachmentUpload::new_pending(attachment.id.clone(), "nostr".to_string());
-        db.create_attachment_upload(&upload).await.unwrap();
+    // ========================================================================
+    // Concurrency tests
+    // ========================================================================
 
-        db.update_attachment_upload(
-            &attachment.id,
-            "nostr",
-            None,
-            None,
-            AttachmentStatus::Failed,
-            Some("Network timeout"),
-        )
-        .await
-        .unwrap();
+    #[tokio::test]
+    async fn test_database_uses_wal_and_busy_timeout() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("wal.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+        let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(journal_mode.to_lowercase(), "wal");
 
-        // Verify update
-        let retrieved = db
-            .get_attachment_upload(&attachment.id, "nostr")
+        let busy_timeout: i64 = sqlx::query_scalar("PRAGMA busy_timeout")
+            .fetch_one(db.pool())
             .await
-            .unwrap()
             .unwrap();
+        assert_eq!(busy_timeout, BUSY_TIMEOUT.as_millis() as i64);
+    }
 
-        assert_eq!(retrieved.status, AttachmentStatus::Failed);
-        assert_eq!(retrieved.error_message, Some("Network timeout".to_string()));
+    #[tokio::test]
+    async fn test_concurrent_writers_on_same_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("shared.db");
+        let path = db_path.to_str().unwrap().to_string();
+
+        // Two handles simulate separate tools opening the same database
+        let db_a = Database::new(&path).await.unwrap();
+        let db_b = Database::new(&path).await.unwrap();
+
+        let mut handles = Vec::new();
+        for db in [db_a.clone(), db_b.clone()] {
+            handles.push(tokio::spawn(async move {
+                for _ in 0..20 {
+                    db.create_post(&create_test_post()).await.unwrap();
+                }
+            }));
+        }
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM posts")
+            .fetch_one(db_a.pool())
+            .await
+            .unwrap();
+        assert_eq!(count, 40);
     }
 
+    // ========================================================================
+    // Attempt tests
+    // ========================================================================
+
     #[tokio::test]
-    async fn test_get_attachment_uploads() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+    async fn test_post_attempts_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("attempts.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
 
-        // Create a post and attachment
         let post = create_test_post();
         db.create_post(&post).await.unwrap();
 
-        let attachment = create_test_attachment(&post.id);
-        db.create_attachment(&attachment).await.unwrap();
-
-        // Create upload records for multiple platforms
-        for platform in ["nostr", "mastodon"] {
-            let upload = AttachmentUpload::new_pending(attachment.id.clone(), platform.to_string());
-            db.create_attachment_upload(&upload).await.unwrap();
+        for (attempt, success) in [(1, false), (2, true)] {
+            let started_at_ms = 1_700_000_000_000 + attempt as i64 * 5_000;
+            db.create_post_attempt(&PostAttempt {
+                post_id: post.id.clone(),
+                platform: "nostr".to_string(),
+                attempt,
+                started_at_ms,
+                finished_at_ms: started_at_ms + 1_200,
+                duration_ms: 1_200,
+                success,
+                error_message: (!success).then(|| "relay timeout".to_string()),
+            })
+            .await
+            .unwrap();
         }
 
-        // Get all uploads
-        let uploads = db.get_attachment_uploads(&attachment.id).await.unwrap();
-        assert_eq!(uploads.len(), 2);
+        let attempts = db.get_post_attempts(&post.id).await.unwrap();
+        assert_eq!(attempts.len(), 2);
+        assert_eq!(attempts[0].attempt, 1);
+        assert!(!attempts[0].success);
+        assert_eq!(attempts[0].error_message.as_deref(), Some("relay timeout"));
+        assert_eq!(attempts[1].attempt, 2);
+        assert!(attempts[1].success);
+        assert_eq!(attempts[1].duration_ms, 1_200);
+
+        db.prune_posts(&[post.id.clone()]).await.unwrap();
+        assert!(db.get_post_attempts(&post.id).await.unwrap().is_empty());
     }
 
+    // ========================================================================
+    // Relay result tests
+    // ========================================================================
+
     #[tokio::test]
-    async fn test_get_pending_uploads() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+    async fn test_relay_results_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("relays.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
 
-        // Create a post and attachments
         let post = create_test_post();
         db.create_post(&post).await.unwrap();
 
-        let attachment1 = create_test_attachment(&post.id);
-        let attachment2 = create_test_attachment(&post.id);
-        db.create_attachment(&attachment1).await.unwrap();
-        db.create_attachment(&attachment2).await.unwrap();
+        let record = PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("note1abc".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        };
+        let record_id = db
+            .create_post_record_with_response(&record, None)
+            .await
+            .unwrap();
 
-        // Create pending upload for attachment1
-        let upload1 = AttachmentUpload::new_pending(attachment1.id.clone(), "mastodon".to_string());
-        db.create_attachment_upload(&upload1).await.unwrap();
+        let relays = vec![
+            RelayResult {
+                relay_url: "wss://relay.damus.io".to_string(),
+                accepted: true,
+                message: None,
+                latency_ms: Some(140),
+            },
+            RelayResult {
+                relay_url: "wss://nos.lol".to_string(),
+                accepted: false,
+                message: Some("blocked: pow too low".to_string()),
+                latency_ms: Some(95),
+            },
+        ];
+        db.create_relay_results(record_id, &relays).await.unwrap();
 
-        // Create completed upload for attachment2
-        let mut upload2 =
-            AttachmentUpload::new_pending(attachment2.id.clone(), "mastodon".to_string());
-        upload2.status = AttachmentStatus::Uploaded;
-        db.create_attachment_upload(&upload2).await.unwrap();
-        db.update_attachment_upload(
-            &attachment2.id,
-            "mastodon",
-            Some("media_id"),
-            None,
-            AttachmentStatus::Uploaded,
-            None,
-        )
-        .await
-        .unwrap();
+        let stored = db.get_relay_results(&post.id).await.unwrap();
+        let stored = &stored[&record_id];
+        assert_eq!(stored.len(), 2);
+        // Ordered by relay URL
+        assert_eq!(stored[0], relays[1]);
+        assert_eq!(stored[1], relays[0]);
 
-        // Get pending uploads
-        let pending = db.get_pending_uploads("mastodon").await.unwrap();
-        assert_eq!(pending.len(), 1);
-        assert_eq!(pending[0].attachment_id, attachment1.id);
+        let records = db.get_post_records(&post.id).await.unwrap();
+        assert_eq!(records[0].id, Some(record_id));
+
+        db.prune_posts(&[post.id.clone()]).await.unwrap();
+        assert!(db.get_relay_results(&post.id).await.unwrap().is_empty());
     }
 
-    #[tokio::test]
-    async fn test_attachment_upload_cascade_delete() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+    // ========================================================================
+    // Deferral tests
+    // ========================================================================
 
-        // Enable foreign key constraints
-        sqlx::query("PRAGMA foreign_keys = ON")
-            .execute(&db.pool)
-            .await
-            .unwrap();
+    #[tokio::test]
+    async fn test_defer_post_keeps_original_time() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("deferrals.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
 
-        // Create a post, attachment, and upload
-        let post = create_test_post();
+        let now = chrono::Utc::now().timestamp();
+        let post = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Deferred".to_string(),
+            created_at: now,
+            scheduled_at: Some(now - 60),
+            status: PostStatus::Scheduled,
+            metadata: None,
+        };
         db.create_post(&post).await.unwrap();
 
-        let attachment = create_test_attachment(&post.id);
-        db.create_attachment(&attachment).await.unwrap();
-
-        let upload = AttachmentUpload::new_pending(attachment.id.clone(), "nostr".to_string());
-        db.create_attachment_upload(&upload).await.unwrap();
+        db.defer_post(&post.id, now + 3600, "daily cap")
+            .await
+            .unwrap();
+        db.defer_post(&post.id, now + 7200, "weekly cap")
+            .await
+            .unwrap();
 
-        // Delete attachment (should cascade to uploads)
-        db.delete_attachment(&attachment.id).await.unwrap();
+        let deferral = db.get_deferral(&post.id).await.unwrap().unwrap();
+        assert_eq!(deferral.deferred_until, now + 7200);
+        assert_eq!(deferral.original_scheduled_at, Some(now - 60));
+        assert_eq!(deferral.reason, "weekly cap");
+        assert_eq!(
+            db.get_post(&post.id).await.unwrap().unwrap().scheduled_at,
+            Some(now + 7200)
+        );
+        assert_eq!(db.list_deferred_posts().await.unwrap().len(), 1);
 
-        // Verify upload is deleted
-        let retrieved = db
-            .get_attachment_upload(&attachment.id, "nostr")
+        // Rescheduling by hand clears the deferral
+        db.update_post_schedule(&post.id, Some(now + 600))
             .await
             .unwrap();
-        assert!(retrieved.is_none());
+        assert!(db.get_deferral(&post.id).await.unwrap().is_none());
+        assert!(db.list_deferred_posts().await.unwrap().is_empty());
     }
 
+    // ========================================================================
+    // Queue pause tests
+    // ========================================================================
+
     #[tokio::test]
-    async fn test_attachment_upload_unique_constraint() {
+    async fn test_pause_and_resume_queue() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        // Create a post and attachment
-        let post = create_test_post();
-        db.create_post(&post).await.unwrap();
-
-        let attachment = create_test_attachment(&post.id);
-        db.create_attachment(&attachment).await.unwrap();
+        let db = Database { pool, cipher: None };
 
-        // Create first upload
-        let upload = AttachmentUpload::new_pending(attachment.id.clone(), "mastodon".to_string());
-        db.create_attachment_upload(&upload).await.unwrap();
+        assert!(db.get_queue_pause().await.unwrap().is_none());
 
-        // Try to create duplicate (same attachment_id + platform)
-        let duplicate =
-            AttachmentUpload::new_pending(attachment.id.clone(), "mastodon".to_string());
-        let result = db.create_attachment_upload(&duplicate).await;
+        assert!(db.pause_queue(Some("incident")).await.unwrap());
+        assert!(!db.pause_queue(None).await.unwrap());
+        let pause = db.get_queue_pause().await.unwrap().unwrap();
+        assert_eq!(pause.reason.as_deref(), Some("incident"));
 
-        // Should fail due to UNIQUE constraint
-        assert!(result.is_err());
+        assert!(db.resume_queue().await.unwrap());
+        assert!(!db.resume_queue().await.unwrap());
+        assert!(db.get_queue_pause().await.unwrap().is_none());
     }
 
+    // ========================================================================
+    // Platform schedule tests
+    // ========================================================================
+
     #[tokio::test]
-    async fn test_migration_creates_attachment_tables() {
+    async fn test_platform_schedules_move_post_along() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Database { pool, cipher: None };
 
-        // Verify attachments table exists
-        let result = sqlx::query(
-            r#"
-            SELECT name FROM sqlite_master
-            WHERE type='table' AND name='attachments'
-            "#,
+        let mut post = create_test_post();
+        post.status = PostStatus::Scheduled;
+        post.scheduled_at = Some(1000);
+        db.create_post(&post).await.unwrap();
+        db.set_platform_schedules(
+            &post.id,
+            &[("mastodon".to_string(), 1000), ("nostr".to_string(), 4600)],
         )
-        .fetch_optional(&pool)
         .await
         .unwrap();
-        assert!(result.is_some(), "attachments table not created");
 
-        // Verify attachment_uploads table exists
-        let result = sqlx::query(
-            r#"
-            SELECT name FROM sqlite_master
-            WHERE type='table' AND name='attachment_uploads'
-            "#,
-        )
-        .fetch_optional(&pool)
-        .await
-        .unwrap();
-        assert!(result.is_some(), "attachment_uploads table not created");
+        // Rescheduling keeps the spacing between platforms
+        db.update_post_schedule(&post.id, Some(2000)).await.unwrap();
+        let schedules = db.get_platform_schedules(&post.id).await.unwrap();
+        assert_eq!(schedules[0].platform, "mastodon");
+        assert_eq!(schedules[0].scheduled_at, 2000);
+        assert_eq!(schedules[1].scheduled_at, 5600);
 
-        // Verify indexes exist
-        let result = sqlx::query(
-            r#"
-            SELECT name FROM sqlite_master
-            WHERE type='index' AND name='idx_attachments_post_id'
-            "#,
-        )
-        .fetch_optional(&pool)
-        .await
-        .unwrap();
-        assert!(
-            result.is_some(),
-            "idx_attachments_post_id index not created"
-        );
+        // Sending the first platform moves the post on to the second
+        db.update_post_status(&post.id, PostStatus::Posted)
+            .await
+            .unwrap();
+        let next = db
+            .mark_platforms_sent(&post.id, &["mastodon".to_string()], 2000)
+            .await
+            .unwrap();
+        assert_eq!(next, Some(5600));
+        let stored = db.get_post(&post.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, PostStatus::Scheduled);
+        assert_eq!(stored.scheduled_at, Some(5600));
+
+        // Once every platform has gone out the post is left alone
+        db.update_post_status(&post.id, PostStatus::Posted)
+            .await
+            .unwrap();
+        let next = db
+            .mark_platforms_sent(&post.id, &["nostr".to_string()], 5600)
+            .await
+            .unwrap();
+        assert!(next.is_none());
+        let stored = db.get_post(&post.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, PostStatus::Posted);
+
+        db.delete_post(&post.id).await.unwrap();
+        assert!(db
+            .get_platform_schedules(&post.id)
+            .await
+            .unwrap()
+            .is_empty());
     }
 
-    // Tests for get_platform_post_ids (cross-platform reply-to lookup)
+    // ========================================================================
+    // Send lease tests
+    // ========================================================================
 
     #[tokio::test]
-    async fn test_get_platform_post_ids_success() {
+    async fn test_send_lease_has_one_holder() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+        let db = Database { pool, cipher: None };
+
+        assert!(db.get_send_lease().await.unwrap().is_none());
+
+        assert!(db.acquire_send_lease("a", 1000, 300).await.unwrap());
+        assert!(!db.acquire_send_lease("b", 1100, 300).await.unwrap());
+
+        // Renewing keeps the original acquisition time
+        assert!(db.acquire_send_lease("a", 1200, 300).await.unwrap());
+        let lease = db.get_send_lease().await.unwrap().unwrap();
+        assert_eq!(lease.holder, "a");
+        assert_eq!(lease.acquired_at, 1000);
+        assert_eq!(lease.renewed_at, 1200);
+
+        // An expired lease can be taken over
+        assert!(!db.acquire_send_lease("b", 1500, 300).await.unwrap());
+        assert!(db.acquire_send_lease("b", 1501, 300).await.unwrap());
+        let lease = db.get_send_lease().await.unwrap().unwrap();
+        assert_eq!(lease.holder, "b");
+        assert_eq!(lease.acquired_at, 1501);
+
+        // Only the holder can release it
+        db.release_send_lease("a").await.unwrap();
+        assert!(db.get_send_lease().await.unwrap().is_some());
+        db.release_send_lease("b").await.unwrap();
+        assert!(db.get_send_lease().await.unwrap().is_none());
+    }
+
+    // ========================================================================
+    // Scheduled deletion tests
+    // ========================================================================
+
+    #[tokio::test]
+    async fn test_scheduled_deletions_come_due_until_done() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("deletions.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
 
-        // Create a post with successful records on multiple platforms
         let post = create_test_post();
         db.create_post(&post).await.unwrap();
+        let early = db
+            .schedule_deletion(&post.id, "mastodon", "111", "default", 1000)
+            .await
+            .unwrap();
+        let late = db
+            .schedule_deletion(&post.id, "mastodon", "222", "work", 2000)
+            .await
+            .unwrap();
 
-        // Add successful Nostr record
-        db.create_post_record(&PostRecord {
-            id: None,
-            post_id: post.id.clone(),
-            platform: "nostr".to_string(),
-            platform_post_id: Some("note1abc123".to_string()),
-            posted_at: Some(chrono::Utc::now().timestamp()),
-            success: true,
-            error_message: None,
-            account_name: "default".to_string(),
-        })
-        .await
-        .unwrap();
+        assert!(db.get_due_deletions(999, 3).await.unwrap().is_empty());
+        let due = db.get_due_deletions(1500, 3).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, early);
+        assert_eq!(due[0].platform_post_id, "111");
+
+        // Failures are retried until they reach the attempt limit
+        db.record_deletion_failure(early, "timeout").await.unwrap();
+        let due = db.get_due_deletions(2500, 3).await.unwrap();
+        assert_eq!(due.len(), 2);
+        assert_eq!(due[0].attempts, 1);
+        assert_eq!(due[0].error.as_deref(), Some("timeout"));
+        assert_eq!(db.get_due_deletions(2500, 1).await.unwrap().len(), 1);
+
+        db.mark_deletion_done(early, 2500).await.unwrap();
+        let due = db.get_due_deletions(2500, 3).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, late);
+        assert_eq!(due[0].account_name, "work");
+
+        let deletions = db.get_post_deletions(&post.id).await.unwrap();
+        assert_eq!(deletions[0].deleted_at, Some(2500));
+        assert_eq!(deletions[0].error, None);
 
-        // Add successful Mastodon record
-        db.create_post_record(&PostRecord {
-            id: None,
-            post_id: post.id.clone(),
-            platform: "mastodon".to_string(),
-            platform_post_id: Some("12345678".to_string()),
-            posted_at: Some(chrono::Utc::now().timestamp()),
-            success: true,
-            error_message: None,
-            account_name: "default".to_string(),
-        })
-        .await
-        .unwrap();
+        db.delete_post(&post.id).await.unwrap();
+        assert!(db.get_post_deletions(&post.id).await.unwrap().is_empty());
+    }
 
-        // Look up platform IDs
-        let result = db.get_platform_post_ids(&post.id).await.unwrap();
+    // ========================================================================
+    // Bot feed tests
+    // ========================================================================
 
-        assert_eq!(result.len(), 2);
-        assert_eq!(result.get("nostr"), Some(&"note1abc123".to_string()));
-        assert_eq!(result.get("mastodon"), Some(&"12345678".to_string()));
+    #[tokio::test]
+    async fn test_bot_feed_state() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("bot.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+        assert_eq!(
+            db.get_bot_feed_first_checked("releases").await.unwrap(),
+            None
+        );
+        assert!(db.add_bot_feed("releases", 1000).await.unwrap());
+        assert!(!db.add_bot_feed("releases", 2000).await.unwrap());
+        assert_eq!(
+            db.get_bot_feed_first_checked("releases").await.unwrap(),
+            Some(1000)
+        );
+
+        assert!(!db.is_bot_entry_seen("releases", "v1.0").await.unwrap());
+        assert!(db
+            .mark_bot_entry_seen("releases", "v1.0", None, 1000)
+            .await
+            .unwrap());
+        assert!(db.is_bot_entry_seen("releases", "v1.0").await.unwrap());
+        assert!(!db.is_bot_entry_seen("blog", "v1.0").await.unwrap());
+        assert_eq!(db.last_bot_post_at("releases").await.unwrap(), None);
+
+        assert!(db
+            .mark_bot_entry_seen("releases", "v1.1", Some("post-1"), 3000)
+            .await
+            .unwrap());
+        assert!(!db
+            .mark_bot_entry_seen("releases", "v1.1", Some("post-2"), 4000)
+            .await
+            .unwrap());
+        assert_eq!(db.last_bot_post_at("releases").await.unwrap(), Some(3000));
     }
 
+    // ========================================================================
+    // Audit log tests
+    // ========================================================================
+
     #[tokio::test]
-    async fn test_get_platform_post_ids_partial_success() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+    async fn test_audit_events_newest_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("audit.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
 
-        // Create a post with one success and one failure
-        let post = create_test_post();
-        db.create_post(&post).await.unwrap();
+        db.record_audit_event("credential_revealed", "nostr/default", "alice", 1000)
+            .await
+            .unwrap();
+        db.record_audit_event("credential_revealed", "mastodon/work", "alice", 2000)
+            .await
+            .unwrap();
 
-        // Add successful Nostr record
-        db.create_post_record(&PostRecord {
-            id: None,
-            post_id: post.id.clone(),
-            platform: "nostr".to_string(),
-            platform_post_id: Some("note1abc123".to_string()),
-            posted_at: Some(chrono::Utc::now().timestamp()),
-            success: true,
-            error_message: None,
-            account_name: "default".to_string(),
-        })
-        .await
-        .unwrap();
+        let events = db.get_audit_events(10).await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].subject, "mastodon/work");
+        assert_eq!(events[0].created_at, 2000);
+        assert_eq!(events[1].actor, "alice");
 
-        // Add failed Mastodon record
-        db.create_post_record(&PostRecord {
-            id: None,
-            post_id: post.id.clone(),
-            platform: "mastodon".to_string(),
-            platform_post_id: None,
-            posted_at: None,
-            success: false,
-            error_message: Some("Authentication failed".to_string()),
-            account_name: "default".to_string(),
-        })
-        .await
-        .unwrap();
+        assert_eq!(db.get_audit_events(1).await.unwrap().len(), 1);
+    }
 
-        // Look up platform IDs - should only return successful ones
-        let result = db.get_platform_post_ids(&post.id).await.unwrap();
+    // ========================================================================
+    // Mention tracking tests
+    // ========================================================================
 
-        assert_eq!(result.len(), 1);
-        assert_eq!(result.get("nostr"), Some(&"note1abc123".to_string()));
-        assert!(result.get("mastodon").is_none());
+    #[tokio::test]
+    async fn test_mark_mention_seen_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("mentions.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+        assert!(db
+            .mark_mention_seen("mastodon", "110", Some("post-1"), 1000)
+            .await
+            .unwrap());
+        assert!(!db
+            .mark_mention_seen("mastodon", "110", Some("post-1"), 2000)
+            .await
+            .unwrap());
+        // Same ID on another platform is another mention
+        assert!(db
+            .mark_mention_seen("nostr", "110", None, 2000)
+            .await
+            .unwrap());
     }
 
     #[tokio::test]
-    async fn test_get_platform_post_ids_not_found() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+    async fn test_engagement_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("engagement.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
 
-        // Query for non-existent post
-        let result = db.get_platform_post_ids("nonexistent-uuid").await.unwrap();
+        let post = Post::new("Popular".to_string());
+        db.create_post(&post).await.unwrap();
+        let mut record = EngagementRecord {
+            post_id: post.id.clone(),
+            platform: "mastodon".to_string(),
+            platform_post_id: "110".to_string(),
+            engagement: crate::platforms::Engagement {
+                replies: 1,
+                boosts: 2,
+                likes: 3,
+                ..Default::default()
+            },
+            fetched_at: 1000,
+        };
+        db.save_engagement(&record).await.unwrap();
 
-        // Should return empty map
-        assert!(result.is_empty());
+        // Refreshing replaces the counts
+        record.engagement.likes = 5;
+        record.fetched_at = 2000;
+        db.save_engagement(&record).await.unwrap();
+        assert_eq!(db.get_engagement(&post.id).await.unwrap(), vec![record]);
+
+        db.delete_post(&post.id).await.unwrap();
+        assert!(db.get_engagement(&post.id).await.unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn test_get_platform_post_ids_null_platform_post_id() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+    async fn test_post_check_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("checks.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
 
-        // Create a post with a successful record but null platform_post_id
-        let post = create_test_post();
+        let post = Post::new("Still there?".to_string());
         db.create_post(&post).await.unwrap();
-
-        // Add record with success=true but platform_post_id=null (edge case)
-        db.create_post_record(&PostRecord {
-            id: None,
+        let mut check = PostCheck {
             post_id: post.id.clone(),
             platform: "nostr".to_string(),
-            platform_post_id: None, // Edge case: success but no ID
-            posted_at: Some(chrono::Utc::now().timestamp()),
-            success: true,
-            error_message: None,
-            account_name: "default".to_string(),
-        })
-        .await
-        .unwrap();
+            platform_post_id: "note1abc".to_string(),
+            present: true,
+            checked_at: 1000,
+            missing_since: None,
+        };
+        db.save_post_check(&check).await.unwrap();
 
-        // Should not include records with null platform_post_id
-        let result = db.get_platform_post_ids(&post.id).await.unwrap();
-        assert!(result.is_empty());
+        // Checking again replaces the outcome
+        check.present = false;
+        check.checked_at = 2000;
+        check.missing_since = Some(2000);
+        db.save_post_check(&check).await.unwrap();
+        assert_eq!(
+            db.get_post_checks(&post.id).await.unwrap(),
+            vec![check.clone()]
+        );
+        assert_eq!(db.get_missing_post_checks().await.unwrap(), vec![check]);
+
+        db.delete_post(&post.id).await.unwrap();
+        assert!(db.get_post_checks(&post.id).await.unwrap().is_empty());
+        assert!(db.get_missing_post_checks().await.unwrap().is_empty());
     }
 
-    // Tests for get_post_id_by_platform_post_id (reverse lookup for cross-platform reply-to)
+    // ========================================================================
+    // Import tracking tests
+    // ========================================================================
 
     #[tokio::test]
-    async fn test_get_post_id_by_platform_post_id_found() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        // Create a post with successful record
-        let post = Post::new("Test content".to_string());
-        db.create_post(&post).await.unwrap();
+    async fn test_imported_items_by_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("imports.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
 
-        db.create_post_record(&PostRecord {
-            id: None,
-            post_id: post.id.clone(),
-            platform: "nostr".to_string(),
-            platform_post_id: Some("note1abc123".to_string()),
-            posted_at: Some(chrono::Utc::now().timestamp()),
-            success: true,
-            error_message: None,
-            account_name: "default".to_string(),
-        })
-        .await
-        .unwrap();
+        let feed = "rss:https://example.com/feed.xml";
+        assert!(!db.is_item_imported(feed, "guid-1").await.unwrap());
 
-        // Look up by platform_post_id
-        let result = db
-            .get_post_id_by_platform_post_id("nostr", "note1abc123")
+        db.record_imported_item(feed, "guid-1", "post-1", 1000)
+            .await
+            .unwrap();
+        // Recording again is not an error
+        db.record_imported_item(feed, "guid-1", "post-2", 2000)
             .await
             .unwrap();
 
-        assert_eq!(result, Some(post.id));
+        assert!(db.is_item_imported(feed, "guid-1").await.unwrap());
+        assert!(!db.is_item_imported(feed, "guid-2").await.unwrap());
+        assert!(!db
+            .is_item_imported("rss:https://other.example/feed.xml", "guid-1")
+            .await
+            .unwrap());
     }
 
     #[tokio::test]
-    async fn test_get_post_id_by_platform_post_id_not_found() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+    async fn test_import_cursor_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("cursors.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
 
-        // Query for non-existent platform_post_id
-        let result = db
-            .get_post_id_by_platform_post_id("nostr", "note1nonexistent")
+        assert_eq!(db.get_import_cursor("ssb:default").await.unwrap(), None);
+
+        db.set_import_cursor("ssb:default", "41", 1000)
+            .await
+            .unwrap();
+        db.set_import_cursor("ssb:default", "42", 2000)
+            .await
+            .unwrap();
+        db.set_import_cursor("mastodon:default", "110", 2000)
             .await
             .unwrap();
 
-        assert_eq!(result, None);
+        assert_eq!(
+            db.get_import_cursor("ssb:default")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("42")
+        );
+        assert_eq!(
+            db.get_import_cursor("mastodon:default")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("110")
+        );
     }
 
+    // ========================================================================
+    // Backup tests
+    // ========================================================================
+
     #[tokio::test]
-    async fn test_get_post_id_by_platform_post_id_failed_record() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+    async fn test_schema_version_matches_latest() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("schema.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+        let version = db.schema_version().await.unwrap();
+        assert!(version > 0);
+        assert_eq!(version, Database::latest_schema_version());
+    }
 
-        // Create a post with FAILED record (success = false)
-        let post = Post::new("Test content".to_string());
+    #[tokio::test]
+    async fn test_backup_to_creates_readable_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("source.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+        let post = create_test_post();
         db.create_post(&post).await.unwrap();
 
-        db.create_post_record(&PostRecord {
-            id: None,
-            post_id: post.id.clone(),
-            platform: "nostr".to_string(),
-            platform_post_id: Some("note1failed".to_string()),
-            posted_at: Some(chrono::Utc::now().timestamp()),
-            success: false, // Failed record
-            error_message: Some("Network error".to_string()),
-            account_name: "default".to_string(),
-        })
-        .await
-        .unwrap();
+        let snapshot_path = temp_dir.path().join("snapshot.db");
+        db.backup_to(&snapshot_path).await.unwrap();
+        assert!(snapshot_path.exists());
 
-        // Should NOT return failed records
-        let result = db
-            .get_post_id_by_platform_post_id("nostr", "note1failed")
+        let snapshot = Database::new(snapshot_path.to_str().unwrap())
             .await
             .unwrap();
-
-        assert_eq!(result, None);
+        let retrieved = snapshot.get_post(&post.id).await.unwrap().unwrap();
+        assert_eq!(retrieved.content, post.content);
     }
 
     #[tokio::test]
-    async fn test_get_post_id_by_platform_post_id_wrong_platform() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
-
-        // Create a post with successful Nostr record
-        let post = Post::new("Test content".to_string());
-        db.create_post(&post).await.unwrap();
+    async fn test_open_read_only_leaves_database_as_is() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("inspect.db");
 
-        db.create_post_record(&PostRecord {
-            id: None,
-            post_id: post.id.clone(),
-            platform: "nostr".to_string(),
-            platform_post_id: Some("note1abc123".to_string()),
-            posted_at: Some(chrono::Utc::now().timestamp()),
-            success: true,
-            error_message: None,
-            account_name: "default".to_string(),
-        })
-        .await
-        .unwrap();
+        // Nothing is created for a database that isn't there
+        assert!(Database::open_read_only(db_path.to_str().unwrap())
+            .await
+            .is_err());
+        assert!(!db_path.exists());
 
-        // Look up with correct ID but wrong platform - should not find it
-        let result = db
-            .get_post_id_by_platform_post_id("mastodon", "note1abc123")
+        Database::new(db_path.to_str().unwrap()).await.unwrap();
+        let db = Database::open_read_only(db_path.to_str().unwrap())
             .await
             .unwrap();
-
-        assert_eq!(result, None);
+        assert_eq!(
+            db.schema_version().await.unwrap(),
+            Database::latest_schema_version()
+        );
+        assert_eq!(db.integrity_check().await.unwrap(), vec!["ok"]);
+        assert!(db.create_post(&create_test_post()).await.is_err());
     }
 
     #[tokio::test]
-    async fn test_get_post_id_by_platform_post_id_cross_platform_lookup() {
-        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-        let db = Database { pool };
+    async fn test_in_memory_database_is_migrated() {
+        let db = Database::in_memory().await.unwrap();
+        assert_eq!(
+            db.schema_version().await.unwrap(),
+            Database::latest_schema_version()
+        );
 
-        // Create a post with successful records on multiple platforms
-        let post = Post::new("Cross-platform post".to_string());
+        let post = create_test_post();
         db.create_post(&post).await.unwrap();
+        assert!(db.get_post(&post.id).await.unwrap().is_some());
+    }
 
-        db.create_post_record(&PostRecord {
-            id: None,
-            post_id: post.id.clone(),
-            platform: "nostr".to_string(),
-            platform_post_id: Some("note1cross123".to_string()),
-            posted_at: Some(chrono::Utc::now().timestamp()),
-            success: true,
-            error_message: None,
-            account_name: "default".to_string(),
-        })
-        .await
-        .unwrap();
+    // ========================================================================
+    // Maintenance tests
+    // ========================================================================
 
-        db.create_post_record(&PostRecord {
-            id: None,
-            post_id: post.id.clone(),
-            platform: "mastodon".to_string(),
-            platform_post_id: Some("123456789".to_string()),
-            posted_at: Some(chrono::Utc::now().timestamp()),
-            success: true,
-            error_message: None,
-            account_name: "default".to_string(),
-        })
-        .await
-        .unwrap();
+    #[tokio::test]
+    async fn test_maintenance_reclaims_space() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("maintenance.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+        let mut ids = Vec::new();
+        for _ in 0..200 {
+            let mut post = create_test_post();
+            post.content = "x".repeat(2000);
+            db.create_post(&post).await.unwrap();
+            ids.push(post.id);
+        }
+        for id in &ids {
+            db.delete_post(id).await.unwrap();
+        }
 
-        // Look up by Nostr ID
-        let uuid_from_nostr = db
-            .get_post_id_by_platform_post_id("nostr", "note1cross123")
-            .await
-            .unwrap();
-        assert_eq!(uuid_from_nostr, Some(post.id.clone()));
+        let report = db.maintenance().await.unwrap();
 
-        // Look up by Mastodon ID - should return the same UUID
-        let uuid_from_mastodon = db
-            .get_post_id_by_platform_post_id("mastodon", "123456789")
-            .await
-            .unwrap();
-        assert_eq!(uuid_from_mastodon, Some(post.id.clone()));
+        assert!(report.is_healthy());
+        assert!(report.compacted);
+        assert!(report.size_after < report.size_before);
+    }
 
-        // Now use the UUID to get all platform IDs (the full cross-platform workflow)
-        let all_ids = db.get_platform_post_ids(&post.id).await.unwrap();
-        assert_eq!(all_ids.len(), 2);
-        assert_eq!(all_ids.get("nostr"), Some(&"note1cross123".to_string()));
-        assert_eq!(all_ids.get("mastodon"), Some(&"123456789".to_string()));
+    #[tokio::test]
+    async fn test_maintenance_on_empty_database() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("empty.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+        let report = db.maintenance().await.unwrap();
+
+        assert_eq!(report.integrity, vec!["ok".to_string()]);
+        assert!(report.size_after <= report.size_before);
     }
 }
@@ -0,0 +1,59 @@
+//! Editing text in the user's editor
+//!
+//! Used by `plur-post --edit` to compose posts and by `plur-queue edit` to
+//! change scheduled ones. The editor is `$VISUAL`, then `$EDITOR`, then
+//! `vi`, and may include arguments (e.g. `code --wait`).
+
+use crate::error::{PlurcastError, Result};
+
+/// Open `initial` in the user's editor and return the saved text
+///
+/// The text is written to `file_name` in the temp directory, which is
+/// removed afterwards; the name's extension lets editors pick a mode.
+/// Trailing newlines are trimmed from the result.
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if the editor can't be started or exits with an
+/// error, or the temp file can't be written or read back.
+pub fn edit(initial: &str, file_name: &str) -> Result<String> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // Allow editors with arguments, e.g. "code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| PlurcastError::InvalidInput("$EDITOR is empty".to_string()))?;
+
+    let path = std::env::temp_dir().join(file_name);
+    std::fs::write(&path, initial)
+        .map_err(|e| PlurcastError::InvalidInput(format!("Failed to write temp file: {}", e)))?;
+
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(&path)
+        .status();
+    let edited = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            return Err(PlurcastError::InvalidInput(format!(
+                "Editor exited with {}",
+                status
+            )))
+        }
+        Err(e) => {
+            return Err(PlurcastError::InvalidInput(format!(
+                "Failed to run editor '{}': {}",
+                program, e
+            )))
+        }
+    }
+
+    let edited = edited
+        .map_err(|e| PlurcastError::InvalidInput(format!("Failed to read temp file: {}", e)))?;
+    Ok(edited.trim_end_matches('\n').to_string())
+}
@@ -0,0 +1,224 @@
+//! Application-level encryption of post content at rest
+//!
+//! When `database.encrypt_content` is enabled, post content is sealed with an
+//! age X25519 key before it is written to SQLite. The key lives in the
+//! credential store (OS keyring or encrypted files), never next to the
+//! database, so a copied `posts.db` does not reveal drafts or queued posts.
+//!
+//! Encrypted values carry the `enc:age:` prefix. Values without the prefix are
+//! returned unchanged, so encryption can be switched on for an existing
+//! database: old rows stay readable and every new write is encrypted.
+
+use std::io::{Read, Write};
+
+use age::secrecy::ExposeSecret;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+
+use crate::credentials::CredentialManager;
+use crate::error::{CredentialError, DbError, PlurcastError, Result};
+
+/// Prefix marking a column value as age-encrypted
+pub const ENCRYPTED_PREFIX: &str = "enc:age:";
+
+/// Credential service under which the database key is stored
+pub const KEY_SERVICE: &str = "plurcast.database";
+
+/// Credential key name for the database key
+pub const KEY_NAME: &str = "encryption_key";
+
+/// Credential account for the database key (one key per installation)
+const KEY_ACCOUNT: &str = "default";
+
+/// Symmetric-use age identity for sealing post content
+///
+/// X25519 recipients are used instead of passphrases because passphrase
+/// encryption runs scrypt on every call, which is far too slow per row.
+pub struct ContentCipher {
+    identity: age::x25519::Identity,
+}
+
+impl ContentCipher {
+    /// Generate a new random key
+    pub fn generate() -> Self {
+        Self {
+            identity: age::x25519::Identity::generate(),
+        }
+    }
+
+    /// Load a key from its `AGE-SECRET-KEY-1...` string form
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError::Encryption` if the string is not a valid age identity.
+    pub fn from_secret(secret: &str) -> Result<Self> {
+        let identity = secret
+            .trim()
+            .parse::<age::x25519::Identity>()
+            .map_err(|e| DbError::Encryption(format!("Invalid database key: {}", e)))?;
+        Ok(Self { identity })
+    }
+
+    /// Load the database key from the credential store, creating it on first use
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the credential store cannot be read or written, or
+    /// if the stored key is malformed.
+    pub fn load_or_create(manager: &CredentialManager) -> Result<Self> {
+        match Self::load(manager)? {
+            Some(cipher) => Ok(cipher),
+            None => {
+                let cipher = Self::generate();
+                manager.store_account(
+                    KEY_SERVICE,
+                    KEY_NAME,
+                    KEY_ACCOUNT,
+                    cipher.identity.to_string().expose_secret(),
+                )?;
+                tracing::info!("Generated new database encryption key");
+                Ok(cipher)
+            }
+        }
+    }
+
+    /// Load the database key from the credential store, if there is one
+    ///
+    /// Returns `None` when no key was created yet, so nothing is encrypted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the credential store cannot be read, or if the
+    /// stored key is malformed.
+    pub fn load(manager: &CredentialManager) -> Result<Option<Self>> {
+        match manager.retrieve_account(KEY_SERVICE, KEY_NAME, KEY_ACCOUNT) {
+            Ok(secret) => Self::from_secret(&secret).map(Some),
+            Err(PlurcastError::Credential(CredentialError::NotFound(_))) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Check whether a stored value is encrypted
+    pub fn is_encrypted(value: &str) -> bool {
+        value.starts_with(ENCRYPTED_PREFIX)
+    }
+
+    /// Encrypt a value for storage
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError::Encryption` if encryption fails.
+    pub fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let recipient: Box<dyn age::Recipient + Send> = Box::new(self.identity.to_public());
+        let encryptor = age::Encryptor::with_recipients(vec![recipient])
+            .ok_or_else(|| DbError::Encryption("No recipient for encryption".to_string()))?;
+
+        let mut sealed = vec![];
+        let mut writer = encryptor
+            .wrap_output(&mut sealed)
+            .map_err(|e| DbError::Encryption(e.to_string()))?;
+        writer
+            .write_all(plaintext.as_bytes())
+            .map_err(|e| DbError::Encryption(e.to_string()))?;
+        writer
+            .finish()
+            .map_err(|e| DbError::Encryption(e.to_string()))?;
+
+        Ok(format!("{}{}", ENCRYPTED_PREFIX, BASE64.encode(sealed)))
+    }
+
+    /// Decrypt a stored value
+    ///
+    /// Values without the `enc:age:` prefix are returned unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError::Encryption` if the value is corrupt or was sealed
+    /// with a different key.
+    pub fn decrypt(&self, value: &str) -> Result<String> {
+        let encoded = match value.strip_prefix(ENCRYPTED_PREFIX) {
+            Some(encoded) => encoded,
+            None => return Ok(value.to_string()),
+        };
+
+        let sealed = BASE64
+            .decode(encoded)
+            .map_err(|e| DbError::Encryption(format!("Invalid encrypted value: {}", e)))?;
+
+        let decryptor = match age::Decryptor::new(&sealed[..]) {
+            Ok(age::Decryptor::Recipients(d)) => d,
+            Ok(_) => {
+                return Err(DbError::Encryption(
+                    "Invalid encryption format (expected recipient key)".to_string(),
+                )
+                .into())
+            }
+            Err(e) => return Err(DbError::Encryption(e.to_string()).into()),
+        };
+
+        let mut reader = decryptor
+            .decrypt(std::iter::once(&self.identity as &dyn age::Identity))
+            .map_err(|_| {
+                DbError::Encryption(
+                    "Failed to decrypt content: database key does not match".to_string(),
+                )
+            })?;
+
+        let mut plaintext = String::new();
+        reader
+            .read_to_string(&mut plaintext)
+            .map_err(|e| DbError::Encryption(e.to_string()))?;
+
+        Ok(plaintext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let cipher = ContentCipher::generate();
+
+        let sealed = cipher.encrypt("unpublished draft").unwrap();
+        assert!(ContentCipher::is_encrypted(&sealed));
+        assert!(!sealed.contains("unpublished draft"));
+
+        assert_eq!(cipher.decrypt(&sealed).unwrap(), "unpublished draft");
+    }
+
+    #[test]
+    fn test_decrypt_passes_plaintext_through() {
+        let cipher = ContentCipher::generate();
+        assert_eq!(cipher.decrypt("legacy row").unwrap(), "legacy row");
+    }
+
+    #[test]
+    fn test_decrypt_with_wrong_key_fails() {
+        let cipher = ContentCipher::generate();
+        let other = ContentCipher::generate();
+
+        let sealed = cipher.encrypt("secret").unwrap();
+        let result = other.decrypt(&sealed);
+
+        assert!(matches!(
+            result,
+            Err(PlurcastError::Database(DbError::Encryption(_)))
+        ));
+    }
+
+    #[test]
+    fn test_from_secret_roundtrip() {
+        let cipher = ContentCipher::generate();
+        let secret = cipher.identity.to_string().expose_secret().clone();
+
+        let restored = ContentCipher::from_secret(&secret).unwrap();
+        let sealed = cipher.encrypt("hello").unwrap();
+        assert_eq!(restored.decrypt(&sealed).unwrap(), "hello");
+    }
+
+    #[test]
+    fn test_from_secret_rejects_garbage() {
+        assert!(ContentCipher::from_secret("not-a-key").is_err());
+    }
+}
@@ -50,6 +50,9 @@ pub enum ConfigError {
 
     #[error("Missing required field: {0}")]
     MissingField(String),
+
+    #[error("Invalid configuration value: {0}")]
+    InvalidValue(String),
 }
 
 #[derive(Error, Debug)]
@@ -62,6 +65,12 @@ pub enum DbError {
 
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
+
+    #[error("Content encryption error: {0}")]
+    Encryption(String),
+
+    #[error("Database integrity check failed: {0}")]
+    IntegrityCheckFailed(String),
 }
 
 #[derive(Error, Debug, Clone)]
@@ -119,6 +128,9 @@ pub enum CredentialError {
 
     #[error("Encryption error: {0}")]
     Encryption(String),
+
+    #[error("Credential command failed: {0}")]
+    Command(String),
 }
 
 impl From<keyring::Error> for CredentialError {
@@ -721,6 +733,7 @@ mod tests {
             CredentialError::MigrationFailed("test".to_string()),
             CredentialError::Keyring("test".to_string()),
             CredentialError::Encryption("test".to_string()),
+            CredentialError::Command("test".to_string()),
         ];
 
         for error in errors {
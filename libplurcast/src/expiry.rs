@@ -0,0 +1,216 @@
+//! Posts that expire (`plur-post --expires 2h`)
+//!
+//! The lifetime is kept in the post's metadata under `"expires_in"` (in
+//! seconds) and counts from when the post is actually sent, so scheduled
+//! posts get their full lifetime. Nostr notes carry a NIP-40 `expiration`
+//! tag and relays drop them on their own. Other platforms have no native
+//! expiry: each successful post there gets a scheduled deletion, which
+//! plur-send carries out once it falls due. SSB feeds are append-only, so
+//! SSB posts can't expire at all.
+
+use crate::config::Config;
+use crate::db::Database;
+use crate::error::{PlurcastError, Result};
+use crate::platforms::Platform;
+use crate::types::ScheduledDeletion;
+
+/// Metadata key holding a post's lifetime in seconds
+pub const EXPIRES_METADATA_KEY: &str = "expires_in";
+
+/// Failed attempts after which a scheduled deletion is given up
+pub const MAX_DELETE_ATTEMPTS: i64 = 5;
+
+/// Parse an `--expires` lifetime such as `"2h"` into seconds
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if the duration can't be parsed or is zero.
+pub fn parse(spec: &str) -> Result<i64> {
+    let seconds = crate::scheduling::parse_duration(spec.trim())?.num_seconds();
+    if seconds <= 0 {
+        return Err(PlurcastError::InvalidInput(
+            "Expiry must be longer than zero, e.g. \"2h\"".to_string(),
+        ));
+    }
+    Ok(seconds)
+}
+
+/// The lifetime recorded in a post's metadata, if any
+pub fn from_metadata(metadata: Option<&str>) -> Option<i64> {
+    metadata
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .and_then(|m| m.get(EXPIRES_METADATA_KEY).and_then(|e| e.as_i64()))
+}
+
+/// Whether the platform expires posts itself (Nostr, via NIP-40)
+pub fn has_native_expiry(platform: &str) -> bool {
+    platform == "nostr"
+}
+
+/// Whether posts on the platform can expire at all
+pub fn supports_expiry(platform: &str) -> bool {
+    platform != "ssb"
+}
+
+/// Delete every platform post whose scheduled deletion is due
+///
+/// Platform clients are created per platform and account. A failed
+/// deletion is recorded and retried on later runs until it has failed
+/// [`MAX_DELETE_ATTEMPTS`] times. Returns how many posts were deleted.
+///
+/// # Errors
+///
+/// Returns an error only if the due deletions can't be read; problems with
+/// single deletions are recorded on them instead.
+pub async fn delete_due(db: &Database, config: &Config, now: i64) -> Result<usize> {
+    let due = db.get_due_deletions(now, MAX_DELETE_ATTEMPTS).await?;
+    let mut deleted = 0;
+
+    for deletion in &due {
+        let platform = [deletion.platform.clone()];
+        let platforms = match crate::poster::create_platforms(
+            config,
+            Some(&platform),
+            Some(&deletion.account_name),
+        )
+        .await
+        {
+            Ok(platforms) => platforms,
+            Err(e) => {
+                db.record_deletion_failure(deletion.id, &e.to_string())
+                    .await?;
+                continue;
+            }
+        };
+
+        match platforms.iter().find(|p| p.name() == deletion.platform) {
+            Some(platform) => {
+                if delete_one(db, platform.as_ref(), deletion, now).await? {
+                    deleted += 1;
+                }
+            }
+            None => {
+                db.record_deletion_failure(
+                    deletion.id,
+                    &format!("{} is not configured", deletion.platform),
+                )
+                .await?;
+            }
+        }
+    }
+
+    Ok(deleted)
+}
+
+/// Delete one platform post and record the outcome
+///
+/// Returns whether the post was deleted.
+///
+/// # Errors
+///
+/// Returns an error if the outcome can't be recorded.
+pub async fn delete_one(
+    db: &Database,
+    platform: &dyn Platform,
+    deletion: &ScheduledDeletion,
+    now: i64,
+) -> Result<bool> {
+    match platform.delete(&deletion.platform_post_id).await {
+        Ok(()) => {
+            tracing::info!(
+                "Deleted expired post {} from {} ({})",
+                deletion.post_id,
+                deletion.platform,
+                deletion.platform_post_id
+            );
+            db.mark_deletion_done(deletion.id, now).await?;
+            Ok(true)
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Failed to delete expired post {} from {}: {}",
+                deletion.post_id,
+                deletion.platform,
+                e
+            );
+            db.record_deletion_failure(deletion.id, &e.to_string())
+                .await?;
+            Ok(false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::mock::{MockConfig, MockPlatform};
+    use crate::types::{Post, PostStatus};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_expiry() {
+        assert_eq!(parse("2h").unwrap(), 7200);
+        assert_eq!(parse(" 30m ").unwrap(), 1800);
+        assert!(parse("0s").is_err());
+        assert!(parse("soon").is_err());
+    }
+
+    #[test]
+    fn test_from_metadata() {
+        assert_eq!(from_metadata(Some(r#"{"expires_in":7200}"#)), Some(7200));
+        assert_eq!(from_metadata(Some(r#"{"platforms":["nostr"]}"#)), None);
+        assert_eq!(from_metadata(None), None);
+    }
+
+    #[tokio::test]
+    async fn test_delete_one_records_outcome() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("expiry.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+        let post = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Gone soon".to_string(),
+            created_at: 1000,
+            scheduled_at: None,
+            status: PostStatus::Posted,
+            metadata: Some(r#"{"expires_in":60}"#.to_string()),
+        };
+        db.create_post(&post).await.unwrap();
+        db.schedule_deletion(&post.id, "mock", "status-1", "default", 1060)
+            .await
+            .unwrap();
+        let deletion = db
+            .get_due_deletions(1060, MAX_DELETE_ATTEMPTS)
+            .await
+            .unwrap()[0]
+            .clone();
+
+        let failing = MockPlatform::new(MockConfig {
+            name: "mock".to_string(),
+            delete_succeeds: false,
+            ..Default::default()
+        });
+        assert!(!delete_one(&db, &failing, &deletion, 1060).await.unwrap());
+        let deletion = db
+            .get_due_deletions(1120, MAX_DELETE_ATTEMPTS)
+            .await
+            .unwrap()[0]
+            .clone();
+        assert_eq!(deletion.attempts, 1);
+        assert!(deletion
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("Mock delete failed"));
+
+        let platform = MockPlatform::success("mock");
+        assert!(delete_one(&db, &platform, &deletion, 1120).await.unwrap());
+        assert_eq!(platform.deleted_posts(), vec!["status-1".to_string()]);
+        assert!(db
+            .get_due_deletions(1180, MAX_DELETE_ATTEMPTS)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
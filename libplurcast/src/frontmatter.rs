@@ -0,0 +1,293 @@
+//! Frontmatter for post files
+//!
+//! A post file may start with a metadata block, either YAML between `---`
+//! lines or TOML between `+++` lines:
+//!
+//! ```text
+//! ---
+//! platforms: [nostr, mastodon]
+//! schedule: tomorrow
+//! tags:
+//!   - campaign:launch
+//! cw: Spoilers
+//! account: work
+//! ---
+//! The post body.
+//! ```
+//!
+//! It is read by `plur-post --from-file` and `--template`, and by
+//! plur-watch for the files dropped in its directory. Only the flat keys
+//! that set post options are read (`platforms`, `schedule`, `tags`, `cw`,
+//! `account`); anything else, such as a static site generator's `title` or
+//! `date`, is ignored. The YAML side is the subset those keys need:
+//! `key: value` pairs, quoted or bare strings, and lists as `[a, b]` or
+//! `- item` lines.
+
+use crate::{PlurcastError, Result};
+
+/// Post options read from a file's frontmatter
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Frontmatter {
+    pub platforms: Vec<String>,
+    pub schedule: Option<String>,
+    pub tags: Vec<String>,
+    pub cw: Option<String>,
+    pub account: Option<String>,
+}
+
+/// A frontmatter value before it's matched to a key
+#[derive(Debug, PartialEq, Eq)]
+enum Value {
+    Str(String),
+    List(Vec<String>),
+}
+
+/// Split a file into its frontmatter and body
+///
+/// Files without a frontmatter block get default options and their whole
+/// text as the body.
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if a frontmatter block isn't closed or can't be
+/// parsed, or a known key has the wrong type.
+pub fn parse(text: &str) -> Result<(Frontmatter, String)> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let first_line = text.lines().next().unwrap_or("").trim_end();
+
+    let delimiter = match first_line {
+        "---" => "---",
+        "+++" => "+++",
+        _ => return Ok((Frontmatter::default(), text.to_string())),
+    };
+
+    let mut block = Vec::new();
+    let mut body = None;
+    let mut offset = text.find('\n').map(|i| i + 1).unwrap_or(text.len());
+    while offset < text.len() {
+        let end = text[offset..]
+            .find('\n')
+            .map(|i| offset + i + 1)
+            .unwrap_or(text.len());
+        let line = &text[offset..end];
+        if line.trim_end() == delimiter {
+            body = Some(text[end..].to_string());
+            break;
+        }
+        block.push(line.trim_end_matches(['\r', '\n']));
+        offset = end;
+    }
+
+    let body = body.ok_or_else(|| {
+        PlurcastError::InvalidInput(format!(
+            "Frontmatter is not closed: expected a '{}' line",
+            delimiter
+        ))
+    })?;
+
+    let values = if delimiter == "+++" {
+        parse_toml(&block.join("\n"))?
+    } else {
+        parse_yaml(&block)?
+    };
+
+    Ok((frontmatter(values)?, body))
+}
+
+/// Match parsed values to the post option keys
+fn frontmatter(values: Vec<(String, Value)>) -> Result<Frontmatter> {
+    let mut fm = Frontmatter::default();
+
+    for (key, value) in values {
+        match key.as_str() {
+            "platforms" | "platform" => fm.platforms = list(value),
+            "tags" | "tag" => fm.tags = list(value),
+            "schedule" => fm.schedule = Some(string(&key, value)?),
+            "cw" => fm.cw = Some(string(&key, value)?),
+            "account" => fm.account = Some(string(&key, value)?),
+            _ => {}
+        }
+    }
+
+    fm.schedule = fm.schedule.filter(|s| !s.is_empty());
+    fm.cw = fm.cw.filter(|s| !s.is_empty());
+    fm.account = fm.account.filter(|s| !s.is_empty());
+
+    Ok(fm)
+}
+
+/// A list value; a single string is a one-item list
+fn list(value: Value) -> Vec<String> {
+    match value {
+        Value::List(items) => items,
+        Value::Str(s) if s.is_empty() => Vec::new(),
+        Value::Str(s) => vec![s],
+    }
+}
+
+fn string(key: &str, value: Value) -> Result<String> {
+    match value {
+        Value::Str(s) => Ok(s),
+        Value::List(_) => Err(PlurcastError::InvalidInput(format!(
+            "Frontmatter '{}' must be a single value, not a list",
+            key
+        ))),
+    }
+}
+
+fn parse_toml(block: &str) -> Result<Vec<(String, Value)>> {
+    let table: toml::Table = block
+        .parse()
+        .map_err(|e| PlurcastError::InvalidInput(format!("Invalid TOML frontmatter: {}", e)))?;
+
+    let mut values = Vec::new();
+    for (key, value) in table {
+        let value = match value {
+            toml::Value::String(s) => Value::Str(s),
+            toml::Value::Array(items) => Value::List(
+                items
+                    .into_iter()
+                    .map(|item| match item {
+                        toml::Value::String(s) => s,
+                        other => other.to_string(),
+                    })
+                    .collect(),
+            ),
+            toml::Value::Table(_) => continue,
+            other => Value::Str(other.to_string()),
+        };
+        values.push((key, value));
+    }
+    Ok(values)
+}
+
+fn parse_yaml(lines: &[&str]) -> Result<Vec<(String, Value)>> {
+    let mut values: Vec<(String, Value)> = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        // "- item" continues the list of the key above it
+        let item = if trimmed == "-" {
+            Some("")
+        } else {
+            trimmed.strip_prefix("- ")
+        };
+        if let Some(item) = item {
+            match values.last_mut() {
+                Some((_, Value::List(items))) => {
+                    items.push(scalar(item));
+                    continue;
+                }
+                Some((_, value)) if *value == Value::Str(String::new()) => {
+                    *value = Value::List(vec![scalar(item)]);
+                    continue;
+                }
+                _ => {
+                    return Err(PlurcastError::InvalidInput(format!(
+                        "Invalid YAML frontmatter on line {}: list item without a key",
+                        i + 2
+                    )))
+                }
+            }
+        }
+
+        // Nested values belong to keys that aren't read
+        if line.starts_with([' ', '\t']) {
+            continue;
+        }
+
+        let (key, raw) = match line.split_once(':') {
+            Some((key, raw)) => (key.trim(), raw.trim()),
+            None => {
+                return Err(PlurcastError::InvalidInput(format!(
+                    "Invalid YAML frontmatter on line {}: expected 'key: value'",
+                    i + 2
+                )))
+            }
+        };
+
+        let value = match raw.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+            Some(inner) => Value::List(
+                inner
+                    .split(',')
+                    .map(scalar)
+                    .filter(|item| !item.is_empty())
+                    .collect(),
+            ),
+            None => Value::Str(scalar(raw)),
+        };
+        values.push((key.to_string(), value));
+    }
+
+    Ok(values)
+}
+
+/// A YAML scalar: quotes removed, trailing comments dropped from bare values
+fn scalar(raw: &str) -> String {
+    let raw = raw.trim();
+    for quote in ['"', '\''] {
+        if raw.len() >= 2 && raw.starts_with(quote) && raw.ends_with(quote) {
+            return raw[1..raw.len() - 1].to_string();
+        }
+    }
+    match raw.find(" #") {
+        Some(i) => raw[..i].trim_end().to_string(),
+        None => raw.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_frontmatter() {
+        let (fm, body) = parse("Just a post\n").unwrap();
+        assert_eq!(fm, Frontmatter::default());
+        assert_eq!(body, "Just a post\n");
+    }
+
+    #[test]
+    fn test_yaml_frontmatter() {
+        let text = "---\ntitle: Ignored\nplatforms: [nostr, mastodon]\nschedule: \"2h\"\ntags:\n  - campaign:launch\n  - 'series:weekly'\ncw: Spoilers # for the finale\naccount: work\n---\nThe body.\n";
+        let (fm, body) = parse(text).unwrap();
+
+        assert_eq!(fm.platforms, vec!["nostr", "mastodon"]);
+        assert_eq!(fm.schedule.as_deref(), Some("2h"));
+        assert_eq!(fm.tags, vec!["campaign:launch", "series:weekly"]);
+        assert_eq!(fm.cw.as_deref(), Some("Spoilers"));
+        assert_eq!(fm.account.as_deref(), Some("work"));
+        assert_eq!(body, "The body.\n");
+    }
+
+    #[test]
+    fn test_toml_frontmatter() {
+        let text = "+++\nplatforms = [\"nostr\"]\nschedule = \"tomorrow\"\ntags = [\"launch\"]\ncw = \"Politics\"\n+++\nBody\n";
+        let (fm, body) = parse(text).unwrap();
+
+        assert_eq!(fm.platforms, vec!["nostr"]);
+        assert_eq!(fm.schedule.as_deref(), Some("tomorrow"));
+        assert_eq!(fm.tags, vec!["launch"]);
+        assert_eq!(fm.cw.as_deref(), Some("Politics"));
+        assert_eq!(fm.account, None);
+        assert_eq!(body, "Body\n");
+    }
+
+    #[test]
+    fn test_single_platform_string() {
+        let (fm, _) = parse("---\nplatforms: nostr\n---\nBody").unwrap();
+        assert_eq!(fm.platforms, vec!["nostr"]);
+    }
+
+    #[test]
+    fn test_invalid_frontmatter() {
+        assert!(parse("---\nplatforms: [nostr]\nBody").is_err());
+        assert!(parse("---\nnot a pair\n---\nBody").is_err());
+        assert!(parse("---\nschedule: [2h, 3h]\n---\nBody").is_err());
+        assert!(parse("+++\nplatforms = [\n+++\nBody").is_err());
+    }
+}
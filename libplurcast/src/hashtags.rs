@@ -0,0 +1,173 @@
+//! Hashtags added with `plur-post --tag`
+//!
+//! Requested hashtags are kept in the post's metadata under `"hashtags"`
+//! (without the `#`) and applied when the post is sent, the way each
+//! platform expects: Mastodon and SSB get `#tag` appended to the text,
+//! skipping tags the text already contains; Nostr gets a NIP-24 `t` tag per
+//! hashtag and the text is left alone.
+
+use crate::error::{PlurcastError, Result};
+
+/// Metadata key holding a post's requested hashtags
+pub const HASHTAGS_METADATA_KEY: &str = "hashtags";
+
+/// Check a hashtag and strip its leading `#`
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if the tag is empty, contains anything but
+/// letters, digits and `_`, or is only digits (which platforms don't
+/// treat as a hashtag).
+pub fn parse(tag: &str) -> Result<String> {
+    let name = tag.trim().trim_start_matches('#');
+    if name.is_empty() {
+        return Err(PlurcastError::InvalidInput(
+            "Hashtag cannot be empty".to_string(),
+        ));
+    }
+    if !name.chars().all(is_hashtag_char) {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Invalid hashtag '{}': use only letters, digits and '_'",
+            tag.trim()
+        )));
+    }
+    if name.chars().all(|c| c.is_ascii_digit()) {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Invalid hashtag '{}': a hashtag needs at least one letter",
+            tag.trim()
+        )));
+    }
+    Ok(name.to_string())
+}
+
+/// Remove repeats (ignoring case), keeping the first spelling of each tag
+pub fn dedup(tags: &[String]) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        if !unique
+            .iter()
+            .any(|t| t.to_lowercase() == tag.to_lowercase())
+        {
+            unique.push(tag.clone());
+        }
+    }
+    unique
+}
+
+/// Hashtags already written in the content, lowercased
+pub fn in_content(content: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut previous = ' ';
+    let mut chars = content.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if c == '#' && !is_hashtag_char(previous) {
+            let start = i + 1;
+            let mut end = start;
+            while let Some(&(j, next)) = chars.peek() {
+                if !is_hashtag_char(next) {
+                    break;
+                }
+                end = j + next.len_utf8();
+                chars.next();
+            }
+            if end > start {
+                found.push(content[start..end].to_lowercase());
+            }
+            previous = content[..end].chars().last().unwrap_or(c);
+            continue;
+        }
+        previous = c;
+    }
+
+    found
+}
+
+/// The content with any requested hashtags it lacks appended
+///
+/// Missing tags go on a new paragraph as `#tag #other`; content that
+/// already has all of them is returned unchanged.
+pub fn append(content: &str, tags: &[String]) -> String {
+    let existing = in_content(content);
+    let missing: Vec<String> = dedup(tags)
+        .into_iter()
+        .filter(|tag| !existing.contains(&tag.to_lowercase()))
+        .map(|tag| format!("#{}", tag))
+        .collect();
+
+    if missing.is_empty() {
+        content.to_string()
+    } else {
+        format!("{}\n\n{}", content.trim_end(), missing.join(" "))
+    }
+}
+
+/// The hashtags recorded in a post's metadata
+pub fn from_metadata(metadata: Option<&str>) -> Vec<String> {
+    metadata
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .and_then(|m| {
+            m.get(HASHTAGS_METADATA_KEY)?.as_array().map(|tags| {
+                tags.iter()
+                    .filter_map(|tag| tag.as_str().map(String::from))
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+fn is_hashtag_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("#rust").unwrap(), "rust");
+        assert_eq!(parse("Plurcast_Dev").unwrap(), "Plurcast_Dev");
+        assert_eq!(parse("straße").unwrap(), "straße");
+        assert!(parse("#").is_err());
+        assert!(parse("two words").is_err());
+        assert!(parse("c++").is_err());
+        assert!(parse("2024").is_err());
+    }
+
+    #[test]
+    fn test_in_content() {
+        assert_eq!(
+            in_content("Loving #Rust and #nostr_dev! Not an issue#42 though. #"),
+            tags(&["rust", "nostr_dev"])
+        );
+        assert!(in_content("No tags here").is_empty());
+    }
+
+    #[test]
+    fn test_append_skips_tags_already_in_content() {
+        assert_eq!(
+            append(
+                "Shipping #Rust today",
+                &tags(&["rust", "release", "Release"])
+            ),
+            "Shipping #Rust today\n\n#release"
+        );
+        assert_eq!(append("All #tagged", &tags(&["Tagged"])), "All #tagged");
+        assert_eq!(append("No tags", &[]), "No tags");
+    }
+
+    #[test]
+    fn test_from_metadata() {
+        assert_eq!(
+            from_metadata(Some(r#"{"hashtags":["rust","release"]}"#)),
+            tags(&["rust", "release"])
+        );
+        assert!(from_metadata(Some(r#"{"platforms":["nostr"]}"#)).is_empty());
+        assert!(from_metadata(None).is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+//! Post language tags
+//!
+//! `plur-post --lang de` (or `[defaults] language`) records the language a
+//! post is written in, as an ISO 639-1 code, in the post's metadata under
+//! `"language"`. Mastodon sends it as the status language; Nostr adds a
+//! NIP-32 `ISO-639-1` label. SSB has no language field.
+
+use crate::error::{PlurcastError, Result};
+
+/// Metadata key holding a post's language
+pub const LANGUAGE_METADATA_KEY: &str = "language";
+
+/// NIP-32 label namespace for ISO 639-1 language codes
+pub const NOSTR_LANGUAGE_NAMESPACE: &str = "ISO-639-1";
+
+/// ISO 639-1 two-letter language codes
+const ISO_639_1: [&str; 184] = [
+    "aa", "ab", "ae", "af", "ak", "am", "an", "ar", "as", "av", "ay", "az", "ba", "be", "bg", "bh",
+    "bi", "bm", "bn", "bo", "br", "bs", "ca", "ce", "ch", "co", "cr", "cs", "cu", "cv", "cy", "da",
+    "de", "dv", "dz", "ee", "el", "en", "eo", "es", "et", "eu", "fa", "ff", "fi", "fj", "fo", "fr",
+    "fy", "ga", "gd", "gl", "gn", "gu", "gv", "ha", "he", "hi", "ho", "hr", "ht", "hu", "hy", "hz",
+    "ia", "id", "ie", "ig", "ii", "ik", "io", "is", "it", "iu", "ja", "jv", "ka", "kg", "ki", "kj",
+    "kk", "kl", "km", "kn", "ko", "kr", "ks", "ku", "kv", "kw", "ky", "la", "lb", "lg", "li", "ln",
+    "lo", "lt", "lu", "lv", "mg", "mh", "mi", "mk", "ml", "mn", "mr", "ms", "mt", "my", "na", "nb",
+    "nd", "ne", "ng", "nl", "nn", "no", "nr", "nv", "ny", "oc", "oj", "om", "or", "os", "pa", "pi",
+    "pl", "ps", "pt", "qu", "rm", "rn", "ro", "ru", "rw", "sa", "sc", "sd", "se", "sg", "si", "sk",
+    "sl", "sm", "sn", "so", "sq", "sr", "ss", "st", "su", "sv", "sw", "ta", "te", "tg", "th", "ti",
+    "tk", "tl", "tn", "to", "tr", "ts", "tt", "tw", "ty", "ug", "uk", "ur", "uz", "ve", "vi", "vo",
+    "wa", "wo", "xh", "yi", "yo", "za", "zh", "zu",
+];
+
+/// Check and normalize a language code (e.g. `"DE"` becomes `"de"`)
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if the code isn't an ISO 639-1 language code.
+pub fn parse(code: &str) -> Result<String> {
+    let code = code.trim().to_ascii_lowercase();
+    if ISO_639_1.binary_search(&code.as_str()).is_ok() {
+        Ok(code)
+    } else {
+        Err(PlurcastError::InvalidInput(format!(
+            "Invalid language '{}': expected a two-letter ISO 639-1 code such as 'en' or 'de'",
+            code
+        )))
+    }
+}
+
+/// The language recorded in a post's metadata, if any
+pub fn from_metadata(metadata: Option<&str>) -> Option<String> {
+    metadata
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .and_then(|m| {
+            m.get(LANGUAGE_METADATA_KEY)
+                .and_then(|lang| lang.as_str())
+                .map(String::from)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_are_sorted_for_binary_search() {
+        assert!(ISO_639_1.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_parse_language() {
+        assert_eq!(parse("de").unwrap(), "de");
+        assert_eq!(parse(" EN ").unwrap(), "en");
+        assert!(parse("xx").is_err());
+        assert!(parse("deu").is_err());
+        assert!(parse("").is_err());
+    }
+
+    #[test]
+    fn test_from_metadata() {
+        assert_eq!(
+            from_metadata(Some(r#"{"language":"de"}"#)).as_deref(),
+            Some("de")
+        );
+        assert_eq!(from_metadata(Some(r#"{"platforms":["nostr"]}"#)), None);
+        assert_eq!(from_metadata(None), None);
+    }
+}
@@ -4,25 +4,42 @@
 //! social media platforms following Unix philosophy principles.
 
 pub mod accounts;
+pub mod anchor;
+pub mod blackout;
 pub mod config;
 pub mod credentials;
 pub mod db;
+pub mod editor;
+pub mod encryption;
 pub mod error;
+pub mod expiry;
+pub mod frontmatter;
+pub mod hashtags;
+pub mod language;
 pub mod logging;
+pub mod media;
 pub mod platforms;
+pub mod poll;
 pub mod poster;
 pub mod rate_limiter;
+pub mod recurrence;
+pub mod retention;
 pub mod scheduling;
 pub mod service;
+pub mod sync;
+pub mod templates;
+pub mod thread;
 pub mod types;
 
 // Re-export commonly used types
 pub use accounts::{AccountManager, AccountState, PlatformAccounts};
 pub use config::Config;
 pub use credentials::{CredentialConfig, CredentialManager, StorageBackend};
-pub use db::{Database, PostWithRecords};
+pub use db::{Database, MaintenanceReport, PostQuery, PostWithRecords};
 pub use error::{PlurcastError, Result};
 pub use rate_limiter::RateLimiter;
 pub use types::{
-    Attachment, AttachmentStatus, AttachmentUpload, ImageMimeType, Post, PostRecord, PostStatus,
+    Attachment, AttachmentStatus, AttachmentUpload, AuditEvent, EngagementRecord, ImageMimeType,
+    PlatformSchedule, Post, PostAttempt, PostCheck, PostDeferral, PostRecord, PostSeries,
+    PostStatus, QueuePause, RelayResult, ScheduledDeletion, SendLease, Tombstone, Visibility,
 };
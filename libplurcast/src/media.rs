@@ -0,0 +1,414 @@
+//! Media attachment lifecycle
+//!
+//! Attachments are recorded against a post when it is created or scheduled,
+//! and uploaded to each platform only when the post is actually sent. Upload
+//! results are kept in `attachment_uploads`, so a retry reuses media that
+//! already reached a platform instead of uploading it again.
+
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::error::{PlatformError, PlurcastError, Result};
+use crate::platforms::Platform;
+use crate::types::{Attachment, AttachmentStatus, AttachmentUpload, ImageMimeType};
+use crate::Database;
+
+/// A file to attach to a new post, with optional alt text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaFile {
+    pub path: PathBuf,
+    pub alt_text: Option<String>,
+}
+
+/// Record a file as an attachment of a post
+///
+/// The file stays where it is; only its absolute path, size, SHA-256 hash
+/// and MIME type (from the extension) are stored.
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if the file can't be read or isn't a supported
+/// image type.
+pub async fn attach_file(
+    db: &Database,
+    post_id: &str,
+    path: &Path,
+    alt_text: Option<String>,
+) -> Result<Attachment> {
+    let attachment = load_file(post_id, path, alt_text).await?;
+    db.create_attachment(&attachment).await?;
+
+    Ok(attachment)
+}
+
+/// Read a file into an attachment record without storing it
+///
+/// Lets a caller check every file, and every platform's limits, before
+/// anything is written to the database.
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if the file can't be read or isn't a supported
+/// image type.
+pub async fn load_file(post_id: &str, path: &Path, alt_text: Option<String>) -> Result<Attachment> {
+    let path = std::fs::canonicalize(path).map_err(|e| {
+        PlurcastError::InvalidInput(format!("Cannot attach '{}': {}", path.display(), e))
+    })?;
+
+    let mime_type = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(ImageMimeType::from_extension)
+        .ok_or_else(|| {
+            PlurcastError::InvalidInput(format!(
+                "Cannot attach '{}': unsupported file type (expected jpg, png, gif or webp)",
+                path.display()
+            ))
+        })?;
+
+    let bytes = tokio::fs::read(&path).await.map_err(|e| {
+        PlurcastError::InvalidInput(format!("Cannot attach '{}': {}", path.display(), e))
+    })?;
+
+    Ok(Attachment::new(
+        post_id.to_string(),
+        path.to_string_lossy().to_string(),
+        mime_type,
+        bytes.len() as u64,
+        format!("{:x}", Sha256::digest(&bytes)),
+        alt_text,
+    ))
+}
+
+/// Why a platform can't take these attachments, if it can't
+///
+/// Checks attachment support, the number of files, and each file's size
+/// and type against the platform's limits.
+pub fn attachment_problem(platform: &dyn Platform, attachments: &[Attachment]) -> Option<String> {
+    if attachments.is_empty() {
+        return None;
+    }
+
+    let name = platform.name();
+
+    if !platform.supports_attachments() {
+        return Some(match name {
+            "nostr" => "nostr needs a NIP-96 media server for attachments \
+                (set media_server in the [nostr] config section)"
+                .to_string(),
+            _ => format!("{} does not support attachments", name),
+        });
+    }
+
+    if attachments.len() > platform.max_attachments() {
+        return Some(format!(
+            "{} allows at most {} attachments per post (post has {})",
+            name,
+            platform.max_attachments(),
+            attachments.len()
+        ));
+    }
+
+    let supported = platform.supported_mime_types();
+    for attachment in attachments {
+        if attachment.file_size > platform.max_attachment_size() {
+            return Some(format!(
+                "{} accepts files up to {} bytes; {} is {} bytes",
+                name,
+                platform.max_attachment_size(),
+                attachment.file_path,
+                attachment.file_size
+            ));
+        }
+        if !supported.contains(&attachment.mime_type) {
+            return Some(format!(
+                "{} does not accept {} files ({})",
+                name,
+                attachment.mime_type.as_str(),
+                attachment.file_path
+            ));
+        }
+    }
+
+    None
+}
+
+/// Upload a post's attachments to a platform
+///
+/// Returns the upload records in attachment order, ready to pass to
+/// [`Platform::post_with_media`]. Attachments already uploaded to this
+/// platform are not uploaded again.
+///
+/// # Errors
+///
+/// Returns a validation error if the platform can't take the attachments
+/// (see [`attachment_problem`]), or the platform error if an upload fails;
+/// a failed upload is recorded so the next attempt retries just that file.
+pub async fn upload_attachments(
+    db: &Database,
+    platform: &dyn Platform,
+    post_id: &str,
+) -> Result<Vec<AttachmentUpload>> {
+    let attachments = db.get_attachments_for_post(post_id).await?;
+    if attachments.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let platform_name = platform.name().to_string();
+
+    if let Some(problem) = attachment_problem(platform, &attachments) {
+        return Err(PlatformError::Validation(problem).into());
+    }
+
+    let mut uploads = Vec::with_capacity(attachments.len());
+    for attachment in &attachments {
+        let mut upload = match db
+            .get_attachment_upload(&attachment.id, &platform_name)
+            .await?
+        {
+            Some(upload) if upload.status == AttachmentStatus::Uploaded => {
+                debug!(
+                    "Reusing {} upload of {}",
+                    platform_name, attachment.file_path
+                );
+                uploads.push(upload);
+                continue;
+            }
+            Some(upload) => upload,
+            None => {
+                let upload =
+                    AttachmentUpload::new_pending(attachment.id.clone(), platform_name.clone());
+                db.create_attachment_upload(&upload).await?;
+                upload
+            }
+        };
+
+        debug!("Uploading {} to {}", attachment.file_path, platform_name);
+        match platform.upload_attachment(attachment).await {
+            Ok((platform_attachment_id, remote_url)) => {
+                upload.mark_uploaded(platform_attachment_id, remote_url);
+                db.update_attachment_upload(
+                    &attachment.id,
+                    &platform_name,
+                    upload.platform_attachment_id.as_deref(),
+                    upload.remote_url.as_deref(),
+                    AttachmentStatus::Uploaded,
+                    None,
+                )
+                .await?;
+                uploads.push(upload);
+            }
+            Err(e) => {
+                db.update_attachment_upload(
+                    &attachment.id,
+                    &platform_name,
+                    None,
+                    None,
+                    AttachmentStatus::Failed,
+                    Some(&e.to_string()),
+                )
+                .await?;
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(uploads)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::mock::MockPlatform;
+    use crate::types::{Post, PostStatus};
+    use tempfile::TempDir;
+
+    async fn setup() -> (TempDir, Database, Post) {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+        let post = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Post with media".to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            scheduled_at: None,
+            status: PostStatus::Scheduled,
+            metadata: None,
+        };
+        db.create_post(&post).await.unwrap();
+
+        (temp_dir, db, post)
+    }
+
+    fn write_image(temp_dir: &TempDir, name: &str) -> std::path::PathBuf {
+        let path = temp_dir.path().join(name);
+        std::fs::write(&path, b"\x89PNG\r\n\x1a\nfake image data").unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn test_attach_file_records_hash_and_type() {
+        let (temp_dir, db, post) = setup().await;
+        let path = write_image(&temp_dir, "photo.png");
+
+        let attachment = attach_file(&db, &post.id, &path, Some("A photo".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(attachment.mime_type, ImageMimeType::Png);
+        assert_eq!(attachment.file_hash.len(), 64);
+        assert_eq!(attachment.alt_text.as_deref(), Some("A photo"));
+
+        let stored = db.get_attachments_for_post(&post.id).await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].file_hash, attachment.file_hash);
+    }
+
+    #[tokio::test]
+    async fn test_attach_file_rejects_unsupported_type() {
+        let (temp_dir, db, post) = setup().await;
+        let path = temp_dir.path().join("notes.txt");
+        std::fs::write(&path, b"not an image").unwrap();
+
+        let result = attach_file(&db, &post.id, &path, None).await;
+        assert!(matches!(result, Err(PlurcastError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_upload_without_attachments_is_empty() {
+        let (_temp_dir, db, post) = setup().await;
+        let platform = MockPlatform::success("mock");
+
+        let uploads = upload_attachments(&db, &platform, &post.id).await.unwrap();
+        assert!(uploads.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_upload_fails_for_platform_without_attachment_support() {
+        let (temp_dir, db, post) = setup().await;
+        let path = write_image(&temp_dir, "photo.png");
+        attach_file(&db, &post.id, &path, None).await.unwrap();
+        let platform = MockPlatform::success("mock");
+
+        // Sending the text alone would silently drop the media
+        let err = upload_attachments(&db, &platform, &post.id)
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("mock does not support attachments"));
+    }
+
+    #[tokio::test]
+    async fn test_attachment_problem_checks_platform_limits() {
+        let (temp_dir, _db, post) = setup().await;
+        let mut attachments = Vec::new();
+        for name in ["a.png", "b.png", "c.png", "d.png", "e.png"] {
+            let path = write_image(&temp_dir, name);
+            attachments.push(load_file(&post.id, &path, None).await.unwrap());
+        }
+        let platform = MockPlatform::with_attachments("mock");
+
+        assert_eq!(attachment_problem(&platform, &attachments[..4]), None);
+        assert_eq!(attachment_problem(&platform, &[]), None);
+
+        let problem = attachment_problem(&platform, &attachments).unwrap();
+        assert!(problem.contains("at most 4 attachments"));
+
+        attachments[0].file_size = 41 * 1024 * 1024;
+        let problem = attachment_problem(&platform, &attachments[..1]).unwrap();
+        assert!(problem.contains("accepts files up to"));
+    }
+
+    #[tokio::test]
+    async fn test_attachment_problem_explains_nostr_media_server() {
+        let (temp_dir, _db, post) = setup().await;
+        let path = write_image(&temp_dir, "photo.png");
+        let attachment = load_file(&post.id, &path, None).await.unwrap();
+        let platform = crate::platforms::nostr::NostrPlatform::new(&crate::config::NostrConfig {
+            enabled: true,
+            keys_file: String::new(),
+            relays: vec![],
+            default_pow_difficulty: None,
+            media_server: None,
+        });
+
+        let problem = attachment_problem(&platform, &[attachment]).unwrap();
+        assert!(problem.contains("media_server"));
+    }
+
+    #[tokio::test]
+    async fn test_load_file_does_not_store_attachment() {
+        let (temp_dir, db, post) = setup().await;
+        let path = write_image(&temp_dir, "photo.png");
+
+        let attachment = load_file(&post.id, &path, None).await.unwrap();
+        assert_eq!(attachment.post_id, post.id);
+        assert!(db
+            .get_attachments_for_post(&post.id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_upload_records_ids_and_reuses_them() {
+        let (temp_dir, db, post) = setup().await;
+        let first = attach_file(&db, &post.id, &write_image(&temp_dir, "a.png"), None)
+            .await
+            .unwrap();
+        attach_file(&db, &post.id, &write_image(&temp_dir, "b.gif"), None)
+            .await
+            .unwrap();
+        let platform = MockPlatform::with_attachments("mock");
+
+        let uploads = upload_attachments(&db, &platform, &post.id).await.unwrap();
+        assert_eq!(uploads.len(), 2);
+        assert!(uploads
+            .iter()
+            .all(|u| u.status == AttachmentStatus::Uploaded));
+        assert_eq!(platform.uploaded_files().len(), 2);
+
+        let stored = db
+            .get_attachment_upload(&first.id, "mock")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.status, AttachmentStatus::Uploaded);
+        assert_eq!(
+            stored.platform_attachment_id,
+            uploads[0].platform_attachment_id
+        );
+
+        // A second send (e.g. a retry) doesn't upload again
+        let again = upload_attachments(&db, &platform, &post.id).await.unwrap();
+        assert_eq!(again.len(), 2);
+        assert_eq!(platform.uploaded_files().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_upload_is_recorded() {
+        let (temp_dir, db, post) = setup().await;
+        let attachment = attach_file(&db, &post.id, &write_image(&temp_dir, "a.png"), None)
+            .await
+            .unwrap();
+        let platform = MockPlatform::new(crate::platforms::mock::MockConfig {
+            name: "mock".to_string(),
+            supports_attachments: true,
+            upload_succeeds: false,
+            ..Default::default()
+        });
+
+        assert!(upload_attachments(&db, &platform, &post.id).await.is_err());
+
+        let stored = db
+            .get_attachment_upload(&attachment.id, "mock")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.status, AttachmentStatus::Failed);
+        assert!(stored.error_message.is_some());
+    }
+}
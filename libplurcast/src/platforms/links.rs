@@ -0,0 +1,108 @@
+//! Web links for published posts
+//!
+//! Turns a platform post ID into a URL that opens the post in a browser:
+//!
+//! - **Nostr**: `https://njump.me/<note1…|hex>`
+//! - **Mastodon**: the status `url` from the stored platform response, or
+//!   `https://<instance>/web/statuses/<id>` when there is none
+//! - **SSB**: no web view, so no link
+//!
+//! # Example
+//!
+//! ```
+//! use libplurcast::platforms::links::post_url;
+//!
+//! assert_eq!(
+//!     post_url("nostr", "note1abc", None, None).as_deref(),
+//!     Some("https://njump.me/note1abc")
+//! );
+//! assert_eq!(
+//!     post_url("mastodon", "123", None, Some("mastodon.social")).as_deref(),
+//!     Some("https://mastodon.social/web/statuses/123")
+//! );
+//! ```
+
+/// Nostr web gateway used for note links
+pub const NJUMP_URL: &str = "https://njump.me";
+
+/// Build a web URL for a published post, if the platform has one
+///
+/// `raw_response` is the platform response stored with the post record
+/// (see [`crate::db::Database::get_raw_responses`]); for Mastodon its `url`
+/// is preferred since it points at the account's own instance. `instance`
+/// is the configured Mastodon instance, used when no response was stored.
+#[must_use]
+pub fn post_url(
+    platform: &str,
+    platform_post_id: &str,
+    raw_response: Option<&serde_json::Value>,
+    instance: Option<&str>,
+) -> Option<String> {
+    if platform_post_id.is_empty() {
+        return None;
+    }
+
+    match platform {
+        "nostr" => Some(format!("{}/{}", NJUMP_URL, platform_post_id)),
+        "mastodon" => {
+            let stored = raw_response
+                .and_then(|raw| raw.get("url"))
+                .and_then(|url| url.as_str())
+                .filter(|url| !url.is_empty());
+            match stored {
+                Some(url) => Some(url.to_string()),
+                None => instance.filter(|i| !i.is_empty()).map(|instance| {
+                    format!(
+                        "{}/web/statuses/{}",
+                        instance_base_url(instance),
+                        platform_post_id
+                    )
+                }),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// `mastodon.social` or `https://mastodon.social/` as `https://mastodon.social`
+fn instance_base_url(instance: &str) -> String {
+    let instance = instance.trim_end_matches('/');
+    if instance.starts_with("http://") || instance.starts_with("https://") {
+        instance.to_string()
+    } else {
+        format!("https://{}", instance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nostr_links_use_njump() {
+        assert_eq!(
+            post_url("nostr", "note1xyz", None, None).as_deref(),
+            Some("https://njump.me/note1xyz")
+        );
+        assert_eq!(post_url("nostr", "", None, None), None);
+    }
+
+    #[test]
+    fn test_mastodon_links_prefer_stored_url() {
+        let raw = serde_json::json!({"id": "123", "url": "https://fosstodon.org/@me/123"});
+        assert_eq!(
+            post_url("mastodon", "123", Some(&raw), Some("mastodon.social")).as_deref(),
+            Some("https://fosstodon.org/@me/123")
+        );
+        assert_eq!(
+            post_url("mastodon", "123", None, Some("https://mastodon.social/")).as_deref(),
+            Some("https://mastodon.social/web/statuses/123")
+        );
+        assert_eq!(post_url("mastodon", "123", None, None), None);
+    }
+
+    #[test]
+    fn test_ssb_has_no_link() {
+        assert_eq!(post_url("ssb", "%abc=.sha256", None, None), None);
+    }
+}
@@ -5,14 +5,19 @@
 //! GoToSocial, and Akkoma instances.
 
 use async_trait::async_trait;
-use megalodon::entities::UploadMedia;
-use megalodon::megalodon::{PostStatusInputOptions, UploadMediaInputOptions};
+use megalodon::entities::notification::NotificationType;
+use megalodon::entities::{Status, StatusVisibility, UploadMedia};
+use megalodon::megalodon::{
+    GetHomeTimelineInputOptions, GetNotificationsInputOptions, PollOptions, PostStatusInputOptions,
+    UploadMediaInputOptions,
+};
 use megalodon::{Megalodon, SNS};
 
 use crate::config::MastodonConfig;
+use crate::credentials::CredentialScope;
 use crate::error::{PlatformError, Result};
-use crate::platforms::Platform;
-use crate::types::{Attachment, ImageMimeType};
+use crate::platforms::{Engagement, Platform, PublishedPost, TimelineEntry, TimelineKind};
+use crate::types::{Attachment, AttachmentUpload, ImageMimeType, Visibility};
 
 /// Mastodon platform client
 ///
@@ -128,15 +133,7 @@ impl MastodonClient {
             );
         }
 
-        // Ensure instance URL has https:// prefix
-        let instance_url =
-            if config.instance.starts_with("http://") || config.instance.starts_with("https://") {
-                config.instance.clone()
-            } else {
-                format!("https://{}", config.instance)
-            };
-
-        Self::new(instance_url, token)
+        Self::new(instance_url(&config.instance), token)
     }
 
     /// Fetch instance information including character limit
@@ -167,24 +164,17 @@ impl MastodonClient {
 
         Ok(())
     }
-}
-
-#[async_trait]
-impl Platform for MastodonClient {
-    async fn authenticate(&mut self) -> Result<()> {
-        // Verify credentials by calling the verify_credentials endpoint
-        self.client
-            .verify_account_credentials()
-            .await
-            .map_err(|e| map_megalodon_error(e, "authenticate"))?;
-
-        Ok(())
-    }
-
-    async fn post(&self, post: &crate::Post) -> Result<String> {
-        // Validate content before posting
-        self.validate_content(&post.content)?;
 
+    /// Publish a status, optionally with already-uploaded media
+    ///
+    /// Shared by every posting path. The full status returned by the server
+    /// (URL, visibility, server-side timestamps) is kept as the raw response.
+    async fn publish_status(
+        &self,
+        post: &crate::Post,
+        media_ids: Option<Vec<String>>,
+        operation: &str,
+    ) -> Result<PublishedPost> {
         // Extract reply_to from metadata for threading support
         // reply_to is now a per-platform map: { "nostr": "note1...", "mastodon": "12345" }
         let reply_to_id: Option<String> = post
@@ -198,17 +188,55 @@ impl Platform for MastodonClient {
                     .map(String::from)
             });
 
-        // Build options with reply_to if present
-        let options = if reply_to_id.is_some() {
+        // Content warning becomes the status's spoiler text
+        let content_warning: Option<String> = post
+            .metadata
+            .as_ref()
+            .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+            .and_then(|m| {
+                m.get("content_warning")
+                    .and_then(|cw| cw.as_str())
+                    .map(String::from)
+            });
+
+        // Only non-public visibility is recorded; public is the server default
+        let visibility = match Visibility::from_metadata(post.metadata.as_deref()) {
+            Visibility::Public => None,
+            Visibility::Unlisted => Some(StatusVisibility::Unlisted),
+            Visibility::Private => Some(StatusVisibility::Private),
+            Visibility::Direct => Some(StatusVisibility::Direct),
+        };
+
+        let language = crate::language::from_metadata(post.metadata.as_deref());
+
+        let poll = crate::poll::from_metadata(post.metadata.as_deref()).map(|poll| PollOptions {
+            options: poll.options,
+            expires_in: Some(poll.duration as u64),
+            multiple: None,
+            hide_totals: None,
+        });
+
+        // Requested hashtags the text doesn't already have go at the end
+        let hashtags = crate::hashtags::from_metadata(post.metadata.as_deref());
+        let content = crate::hashtags::append(&post.content, &hashtags);
+
+        // Build options only when there is something to set
+        let options = if reply_to_id.is_some()
+            || media_ids.is_some()
+            || content_warning.is_some()
+            || visibility.is_some()
+            || language.is_some()
+            || poll.is_some()
+        {
             Some(PostStatusInputOptions {
                 in_reply_to_id: reply_to_id,
-                media_ids: None,
-                poll: None,
+                media_ids,
+                poll,
                 sensitive: None,
-                spoiler_text: None,
-                visibility: None,
+                spoiler_text: content_warning,
+                visibility,
                 scheduled_at: None,
-                language: None,
+                language,
                 quote_id: None,
             })
         } else {
@@ -218,18 +246,52 @@ impl Platform for MastodonClient {
         // Post the status
         let response = self
             .client
-            .post_status(post.content.to_string(), options.as_ref())
+            .post_status(content, options.as_ref())
             .await
-            .map_err(|e| map_megalodon_error(e, "post status"))?;
+            .map_err(|e| map_megalodon_error(e, operation))?;
 
-        // Extract the status ID from the response
+        // Extract the status ID from the response and keep the full status
         // PostStatusOutput is an enum, we need to match on it
-        let post_id = match response.json {
-            megalodon::megalodon::PostStatusOutput::Status(status) => status.id,
-            megalodon::megalodon::PostStatusOutput::ScheduledStatus(scheduled) => scheduled.id,
+        let (post_id, raw_response) = match response.json {
+            megalodon::megalodon::PostStatusOutput::Status(status) => {
+                (status.id.clone(), serde_json::to_value(&status).ok())
+            }
+            megalodon::megalodon::PostStatusOutput::ScheduledStatus(scheduled) => {
+                (scheduled.id.clone(), serde_json::to_value(&scheduled).ok())
+            }
         };
 
-        Ok(post_id)
+        let published = PublishedPost::new(post_id);
+        Ok(match raw_response {
+            Some(raw) => published.with_response(raw),
+            None => published,
+        })
+    }
+}
+
+#[async_trait]
+impl Platform for MastodonClient {
+    async fn authenticate(&mut self) -> Result<()> {
+        // Verify credentials by calling the verify_credentials endpoint
+        self.client
+            .verify_account_credentials()
+            .await
+            .map_err(|e| map_megalodon_error(e, "authenticate"))?;
+
+        Ok(())
+    }
+
+    async fn post(&self, post: &crate::Post) -> Result<String> {
+        self.post_detailed(post)
+            .await
+            .map(|published| published.post_id)
+    }
+
+    async fn post_detailed(&self, post: &crate::Post) -> Result<PublishedPost> {
+        // Validate content before posting
+        self.validate_content(&post.content)?;
+
+        self.publish_status(post, None, "post status").await
     }
 
     fn validate_content(&self, content: &str) -> Result<()> {
@@ -332,49 +394,296 @@ impl Platform for MastodonClient {
             media_ids.push(media_id);
         }
 
-        // Extract reply_to from metadata for threading support
-        // reply_to is now a per-platform map: { "nostr": "note1...", "mastodon": "12345" }
-        let reply_to_id: Option<String> = post
-            .metadata
-            .as_ref()
-            .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
-            .and_then(|m| {
-                m.get("reply_to")
-                    .and_then(|r| r.get("mastodon")) // Get Mastodon-specific ID
-                    .and_then(|r| r.as_str())
-                    .map(String::from)
-            });
+        self.publish_status(post, Some(media_ids), "post status with attachments")
+            .await
+            .map(|published| published.post_id)
+    }
 
-        // Build post options with media IDs and reply_to
-        let options = PostStatusInputOptions {
-            media_ids: Some(media_ids),
-            poll: None,
-            in_reply_to_id: reply_to_id,
-            sensitive: None,
-            spoiler_text: None,
-            visibility: None,
-            scheduled_at: None,
-            language: None,
-            quote_id: None,
+    async fn post_with_media(
+        &self,
+        post: &crate::Post,
+        media: &[AttachmentUpload],
+    ) -> Result<PublishedPost> {
+        // Validate content before posting
+        self.validate_content(&post.content)?;
+
+        let media_ids: Vec<String> = media
+            .iter()
+            .filter_map(|upload| upload.platform_attachment_id.clone())
+            .collect();
+        let media_ids = if media_ids.is_empty() {
+            None
+        } else {
+            Some(media_ids)
         };
 
-        // Post the status with attachments
-        let response = self
-            .client
-            .post_status(post.content.to_string(), Some(&options))
+        self.publish_status(post, media_ids, "post status with attachments")
             .await
-            .map_err(|e| map_megalodon_error(e, "post status with attachments"))?;
+    }
+
+    async fn delete(&self, platform_post_id: &str) -> Result<()> {
+        self.client
+            .delete_status(platform_post_id.to_string())
+            .await
+            .map_err(|e| map_megalodon_error(e, "delete status"))?;
+
+        Ok(())
+    }
 
-        // Extract the status ID from the response
-        let post_id = match response.json {
-            megalodon::megalodon::PostStatusOutput::Status(status) => status.id,
-            megalodon::megalodon::PostStatusOutput::ScheduledStatus(scheduled) => scheduled.id,
+    /// Read the home timeline or mention notifications
+    ///
+    /// Mastodon returns at most 40 statuses per request, so larger limits
+    /// are capped. Mentions are read from notifications, which also hold
+    /// boosts and favourites, so fewer than `limit` may come back.
+    async fn timeline(&self, kind: TimelineKind, limit: usize) -> Result<Vec<TimelineEntry>> {
+        let limit = Some(limit.min(MAX_TIMELINE_LIMIT) as u32);
+
+        let statuses: Vec<Status> = match kind {
+            TimelineKind::Home => {
+                let options = GetHomeTimelineInputOptions {
+                    limit,
+                    ..Default::default()
+                };
+                self.client
+                    .get_home_timeline(Some(&options))
+                    .await
+                    .map_err(|e| map_megalodon_error(e, "fetch home timeline"))?
+                    .json
+            }
+            TimelineKind::Mentions => {
+                let options = GetNotificationsInputOptions {
+                    limit,
+                    ..Default::default()
+                };
+                self.client
+                    .get_notifications(Some(&options))
+                    .await
+                    .map_err(|e| map_megalodon_error(e, "fetch notifications"))?
+                    .json
+                    .into_iter()
+                    .filter(|n| matches!(n.r#type, NotificationType::Mention))
+                    .filter_map(|n| n.status)
+                    .collect()
+            }
         };
 
-        Ok(post_id)
+        Ok(statuses
+            .iter()
+            .map(|status| status_entry(status, kind))
+            .collect())
+    }
+
+    async fn fetch_post(&self, platform_post_id: &str) -> Result<TimelineEntry> {
+        let status = self
+            .client
+            .get_status(platform_post_id.to_string())
+            .await
+            .map_err(|e| map_megalodon_error(e, "fetch status"))?
+            .json;
+
+        Ok(status_entry(&status, TimelineKind::Home))
+    }
+
+    /// Look a status up; the instance answering 404 or 410 means it's gone
+    async fn exists(&self, platform_post_id: &str) -> Result<bool> {
+        match self.client.get_status(platform_post_id.to_string()).await {
+            Ok(_) => Ok(true),
+            Err(e) => match extract_http_status(&e.to_string()) {
+                Some(404) | Some(410) => Ok(false),
+                _ => Err(map_megalodon_error(e, "fetch status").into()),
+            },
+        }
+    }
+
+    /// Read the reply, boost and favourite counts of a status
+    ///
+    /// The counts are the instance's own: interactions from servers it
+    /// hasn't heard of are missing.
+    async fn engagement(&self, platform_post_id: &str) -> Result<Engagement> {
+        let status = self
+            .client
+            .get_status(platform_post_id.to_string())
+            .await
+            .map_err(|e| map_megalodon_error(e, "fetch status"))?
+            .json;
+
+        Ok(Engagement {
+            replies: status.replies_count as u64,
+            boosts: status.reblogs_count as u64,
+            likes: status.favourites_count as u64,
+            ..Default::default()
+        })
+    }
+}
+
+/// Most statuses Mastodon returns for one timeline request
+const MAX_TIMELINE_LIMIT: usize = 40;
+
+/// A status as a timeline entry
+///
+/// A boost shows the boosted status, so replying to the entry replies to
+/// the original post.
+fn status_entry(status: &Status, kind: TimelineKind) -> TimelineEntry {
+    let status = status.reblog.as_deref().unwrap_or(status);
+    TimelineEntry {
+        platform: "mastodon".to_string(),
+        kind,
+        id: status.id.clone(),
+        author: status.account.acct.clone(),
+        author_name: Some(status.account.display_name.clone()).filter(|name| !name.is_empty()),
+        content: html_to_text(&status.content),
+        content_warning: Some(status.spoiler_text.clone()).filter(|cw| !cw.is_empty()),
+        created_at: status.created_at.timestamp(),
+        url: status.url.clone(),
+        reply_to: status.in_reply_to_id.clone(),
+    }
+}
+
+/// Redirect URI for pasting the authorization code by hand
+pub const OOB_REDIRECT_URI: &str = "urn:ietf:wg:oauth:2.0:oob";
+
+/// Plurcast registered as an app on a Mastodon instance
+///
+/// The first half of `plur-creds login mastodon`: the user approves the app
+/// at `authorize_url`, and the code they get back is exchanged for an access
+/// token with [`exchange_code`].
+#[derive(Debug, Clone)]
+pub struct AppRegistration {
+    pub instance_url: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_uri: String,
+    pub authorize_url: String,
+}
+
+/// The instance URL with `https://` added if it has no scheme
+pub fn instance_url(instance: &str) -> String {
+    let instance = instance.trim().trim_end_matches('/');
+    if instance.starts_with("http://") || instance.starts_with("https://") {
+        instance.to_string()
+    } else {
+        format!("https://{}", instance)
     }
 }
 
+/// Convert status HTML to plain text
+///
+/// Paragraphs become blank-line separated, `<br>` a line break, other tags
+/// (links, mentions, hashtags) are dropped keeping their text.
+pub fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let end = match rest[start..].find('>') {
+            Some(end) => start + end,
+            None => {
+                rest = &rest[start..];
+                break;
+            }
+        };
+        let tag = rest[start + 1..end]
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+        let closing = rest[start + 1..].starts_with('/');
+        match tag.as_str() {
+            "br" => text.push('\n'),
+            "p" if closing => text.push_str("\n\n"),
+            _ => {}
+        }
+        rest = &rest[end + 1..];
+    }
+    text.push_str(rest);
+
+    unescape_html(text.trim_end())
+}
+
+fn unescape_html(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Register Plurcast as an OAuth app on an instance
+///
+/// # Arguments
+///
+/// * `instance` - Instance host or URL (e.g., "mastodon.social")
+/// * `redirect_uri` - Where the instance sends the code: a localhost URL,
+///   or [`OOB_REDIRECT_URI`] to show it to the user
+/// * `scope` - `Read` asks only for the `read` OAuth scope
+///
+/// # Errors
+///
+/// Returns an error if the instance can't be reached or rejects the app.
+pub async fn register_app(
+    instance: &str,
+    redirect_uri: &str,
+    scope: CredentialScope,
+) -> Result<AppRegistration> {
+    let instance_url = instance_url(instance);
+    let client =
+        megalodon::generator(SNS::Mastodon, instance_url.clone(), None, None).map_err(|e| {
+            PlatformError::Authentication(format!("Failed to create Mastodon client: {:?}", e))
+        })?;
+
+    let options = megalodon::megalodon::AppInputOptions {
+        scopes: Some(scope.oauth_scopes().iter().map(|s| s.to_string()).collect()),
+        redirect_uris: Some(redirect_uri.to_string()),
+        website: Some("https://github.com/plurcast/plurcast".to_string()),
+    };
+    let app = client
+        .register_app("Plurcast".to_string(), &options)
+        .await
+        .map_err(|e| map_megalodon_error(e, "register app"))?;
+
+    let authorize_url = app.url.ok_or_else(|| {
+        PlatformError::Authentication(
+            "Mastodon instance did not return an authorization URL".to_string(),
+        )
+    })?;
+
+    Ok(AppRegistration {
+        instance_url,
+        client_id: app.client_id,
+        client_secret: app.client_secret,
+        redirect_uri: redirect_uri.to_string(),
+        authorize_url,
+    })
+}
+
+/// Exchange an authorization code for an access token
+///
+/// # Errors
+///
+/// Returns an error if the code is wrong, expired or already used.
+pub async fn exchange_code(app: &AppRegistration, code: &str) -> Result<String> {
+    let client = megalodon::generator(SNS::Mastodon, app.instance_url.clone(), None, None)
+        .map_err(|e| {
+            PlatformError::Authentication(format!("Failed to create Mastodon client: {:?}", e))
+        })?;
+
+    let token = client
+        .fetch_access_token(
+            app.client_id.clone(),
+            app.client_secret.clone(),
+            code.trim().to_string(),
+            app.redirect_uri.clone(),
+        )
+        .await
+        .map_err(|e| map_megalodon_error(e, "fetch access token"))?;
+
+    Ok(token.access_token)
+}
+
 /// Map megalodon errors to PlatformError
 ///
 /// Converts megalodon-specific errors into our unified PlatformError type
@@ -564,6 +873,17 @@ fn extract_http_status(error_str: &str) -> Option<u16> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_html_to_text() {
+        assert_eq!(
+            html_to_text(
+                "<p>Hi <span class=\"h-card\"><a href=\"https://x\">@<span>bob</span></a></span></p>"
+            ),
+            "Hi @bob"
+        );
+        assert_eq!(html_to_text("a &lt;b&gt; &amp;amp;"), "a <b> &amp;");
+    }
+
     #[test]
     fn test_mastodon_client_creation() {
         let client = MastodonClient::new(
@@ -649,6 +969,19 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_instance_url_adds_scheme() {
+        assert_eq!(instance_url("mastodon.social"), "https://mastodon.social");
+        assert_eq!(
+            instance_url("https://fosstodon.org/"),
+            "https://fosstodon.org"
+        );
+        assert_eq!(
+            instance_url("http://localhost:3000"),
+            "http://localhost:3000"
+        );
+    }
+
     #[test]
     fn test_extract_http_status_with_http_prefix() {
         assert_eq!(extract_http_status("HTTP 401 Unauthorized"), Some(401));
@@ -6,12 +6,14 @@
 //! actual platform credentials or network access.
 
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::time::sleep;
 
 use crate::error::{PlatformError, Result};
-use crate::platforms::Platform;
+use crate::platforms::{Engagement, Platform, PublishedPost, TimelineEntry, TimelineKind};
+use crate::types::{Attachment, AttachmentUpload};
 
 /// Configuration for mock platform behavior
 #[derive(Debug, Clone)]
@@ -48,6 +50,33 @@ pub struct MockConfig {
 
     /// Posts that have been made (for verification)
     pub posted_content: Arc<Mutex<Vec<String>>>,
+
+    /// Whether the platform accepts attachments
+    pub supports_attachments: bool,
+
+    /// Whether attachment uploads should succeed
+    pub upload_succeeds: bool,
+
+    /// File paths that have been uploaded (for verification)
+    pub uploaded_files: Arc<Mutex<Vec<String>>>,
+
+    /// Media IDs sent with each post (for verification)
+    pub posted_media: Arc<Mutex<Vec<Vec<String>>>>,
+
+    /// Whether deleting posts should succeed
+    pub delete_succeeds: bool,
+
+    /// Post IDs that have been deleted (for verification)
+    pub deleted_posts: Arc<Mutex<Vec<String>>>,
+
+    /// Entries served by `timeline()`, for both kinds, and `fetch_post()`
+    pub timeline: Vec<TimelineEntry>,
+
+    /// Whether reading timelines and posts should succeed
+    pub timeline_succeeds: bool,
+
+    /// Engagement served by `engagement()`, by platform post ID
+    pub engagement: HashMap<String, Engagement>,
 }
 
 impl Default for MockConfig {
@@ -64,6 +93,15 @@ impl Default for MockConfig {
             auth_call_count: Arc::new(Mutex::new(0)),
             post_call_count: Arc::new(Mutex::new(0)),
             posted_content: Arc::new(Mutex::new(Vec::new())),
+            supports_attachments: false,
+            upload_succeeds: true,
+            uploaded_files: Arc::new(Mutex::new(Vec::new())),
+            posted_media: Arc::new(Mutex::new(Vec::new())),
+            delete_succeeds: true,
+            deleted_posts: Arc::new(Mutex::new(Vec::new())),
+            timeline: Vec::new(),
+            timeline_succeeds: true,
+            engagement: HashMap::new(),
         }
     }
 }
@@ -138,6 +176,15 @@ impl MockPlatform {
         })
     }
 
+    /// Create a mock platform that accepts attachments
+    pub fn with_attachments(name: &str) -> Self {
+        Self::new(MockConfig {
+            name: name.to_string(),
+            supports_attachments: true,
+            ..Default::default()
+        })
+    }
+
     /// Create a simple mock platform with just a name (for compatibility)
     pub fn new_simple(name: &str) -> Self {
         let mut platform = Self::success(name);
@@ -168,6 +215,21 @@ impl MockPlatform {
     pub fn posted_content(&self) -> Vec<String> {
         self.config.posted_content.lock().unwrap().clone()
     }
+
+    /// Get the paths of all uploaded attachments
+    pub fn uploaded_files(&self) -> Vec<String> {
+        self.config.uploaded_files.lock().unwrap().clone()
+    }
+
+    /// Get the media IDs sent with each post
+    pub fn posted_media(&self) -> Vec<Vec<String>> {
+        self.config.posted_media.lock().unwrap().clone()
+    }
+
+    /// Get the IDs of all deleted posts
+    pub fn deleted_posts(&self) -> Vec<String> {
+        self.config.deleted_posts.lock().unwrap().clone()
+    }
 }
 
 #[async_trait]
@@ -259,6 +321,116 @@ impl Platform for MockPlatform {
     fn is_configured(&self) -> bool {
         self.config.is_configured
     }
+
+    fn supports_attachments(&self) -> bool {
+        self.config.supports_attachments
+    }
+
+    async fn upload_attachment(&self, attachment: &Attachment) -> Result<(String, Option<String>)> {
+        if !self.config.supports_attachments {
+            return Err(PlatformError::NotImplemented(format!(
+                "{} does not support attachments",
+                self.config.name
+            ))
+            .into());
+        }
+
+        if !self.config.upload_succeeds {
+            return Err(PlatformError::Network("Mock upload failed".to_string()).into());
+        }
+
+        let mut uploaded = self.config.uploaded_files.lock().unwrap();
+        uploaded.push(attachment.file_path.clone());
+        Ok((
+            format!("{}:media-{}", self.config.name, uploaded.len()),
+            None,
+        ))
+    }
+
+    async fn post_with_media(
+        &self,
+        post: &crate::Post,
+        media: &[AttachmentUpload],
+    ) -> Result<PublishedPost> {
+        let published = self.post_detailed(post).await?;
+
+        self.config.posted_media.lock().unwrap().push(
+            media
+                .iter()
+                .filter_map(|upload| upload.platform_attachment_id.clone())
+                .collect(),
+        );
+
+        Ok(published)
+    }
+
+    async fn delete(&self, platform_post_id: &str) -> Result<()> {
+        if !self.config.delete_succeeds {
+            return Err(PlatformError::Network("Mock delete failed".to_string()).into());
+        }
+
+        self.config
+            .deleted_posts
+            .lock()
+            .unwrap()
+            .push(platform_post_id.to_string());
+        Ok(())
+    }
+
+    async fn timeline(&self, kind: TimelineKind, limit: usize) -> Result<Vec<TimelineEntry>> {
+        if !self.config.timeline_succeeds {
+            return Err(PlatformError::Network("Mock timeline failed".to_string()).into());
+        }
+
+        Ok(self
+            .config
+            .timeline
+            .iter()
+            .filter(|entry| entry.kind == kind)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn fetch_post(&self, platform_post_id: &str) -> Result<TimelineEntry> {
+        if !self.config.timeline_succeeds {
+            return Err(PlatformError::Network("Mock fetch failed".to_string()).into());
+        }
+
+        self.config
+            .timeline
+            .iter()
+            .find(|entry| entry.id == platform_post_id)
+            .cloned()
+            .ok_or_else(|| {
+                PlatformError::Network(format!("Mock post {} not found", platform_post_id)).into()
+            })
+    }
+
+    async fn exists(&self, platform_post_id: &str) -> Result<bool> {
+        if !self.config.timeline_succeeds {
+            return Err(PlatformError::Network("Mock lookup failed".to_string()).into());
+        }
+
+        // Posts this mock deleted are gone
+        Ok(!self
+            .config
+            .deleted_posts
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|id| id == platform_post_id))
+    }
+
+    async fn engagement(&self, platform_post_id: &str) -> Result<Engagement> {
+        self.config
+            .engagement
+            .get(platform_post_id)
+            .copied()
+            .ok_or_else(|| {
+                PlatformError::Network(format!("Mock post {} not found", platform_post_id)).into()
+            })
+    }
 }
 
 #[cfg(test)]
@@ -38,12 +38,15 @@
 //! ```
 
 use async_trait::async_trait;
+use serde::Serialize;
 
 use crate::error::{PlatformError, Result};
-use crate::types::{Attachment, ImageMimeType};
+use crate::types::{Attachment, AttachmentUpload, ImageMimeType, RelayResult};
 
 pub mod id_detection;
+pub mod links;
 pub mod mastodon;
+pub mod nip96; // Media uploads for Nostr
 pub mod nostr;
 pub mod nostr_pow; // Parallel PoW mining for Nostr (NIP-13)
 pub mod ssb;
@@ -51,6 +54,121 @@ pub mod ssb;
 // Mock platform is available for all builds (not just tests) to support integration tests
 pub mod mock;
 
+/// A successfully published post
+///
+/// Carries the platform post ID together with whatever the platform returned
+/// when publishing (status JSON, signed event and relay acknowledgements,
+/// feed message), so it can be stored alongside the post record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublishedPost {
+    /// Platform-specific post ID
+    pub post_id: String,
+    /// Raw platform response, if the platform provides one
+    pub raw_response: Option<serde_json::Value>,
+    /// Per-relay acknowledgements (Nostr only)
+    pub relay_results: Vec<RelayResult>,
+}
+
+impl PublishedPost {
+    /// Create a published post without a raw response
+    pub fn new(post_id: impl Into<String>) -> Self {
+        Self {
+            post_id: post_id.into(),
+            raw_response: None,
+            relay_results: Vec::new(),
+        }
+    }
+
+    /// Attach the raw platform response
+    pub fn with_response(mut self, raw_response: serde_json::Value) -> Self {
+        self.raw_response = Some(raw_response);
+        self
+    }
+
+    /// Attach how each relay answered
+    pub fn with_relay_results(mut self, relay_results: Vec<RelayResult>) -> Self {
+        self.relay_results = relay_results;
+        self
+    }
+}
+
+/// Which timeline to read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimelineKind {
+    /// Posts from the accounts the user follows
+    Home,
+    /// Posts that mention the user, replies included
+    Mentions,
+}
+
+impl TimelineKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimelineKind::Home => "home",
+            TimelineKind::Mentions => "mentions",
+        }
+    }
+}
+
+/// A post read from a timeline
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimelineEntry {
+    /// Platform name (e.g., "nostr", "mastodon")
+    pub platform: String,
+    /// Timeline the entry was read from
+    pub kind: TimelineKind,
+    /// Platform post ID, in the form used for published posts
+    pub id: String,
+    /// Author handle (`user@instance` or `npub1...`)
+    pub author: String,
+    /// Author display name, when the platform provides one
+    pub author_name: Option<String>,
+    /// Plain-text content
+    pub content: String,
+    pub content_warning: Option<String>,
+    /// When the post was created (Unix timestamp)
+    pub created_at: i64,
+    /// Web URL of the post
+    pub url: Option<String>,
+    /// Platform ID of the post this one replies to
+    pub reply_to: Option<String>,
+}
+
+/// How people reacted to a published post
+///
+/// Counts are what the platform reports when asked: Mastodon keeps them
+/// per status, Nostr has them counted from what the relays return.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Engagement {
+    pub replies: u64,
+    /// Boosts (Mastodon) or reposts (Nostr)
+    pub boosts: u64,
+    /// Favourites (Mastodon) or reactions (Nostr)
+    pub likes: u64,
+    /// Zap receipts (Nostr)
+    pub zaps: u64,
+    /// Sats zapped, from the amounts the zap requests name
+    pub zap_sats: u64,
+}
+
+impl Engagement {
+    /// Replies, boosts, likes and zaps together
+    pub fn total(&self) -> u64 {
+        self.replies + self.boosts + self.likes + self.zaps
+    }
+}
+
+impl std::ops::AddAssign for Engagement {
+    fn add_assign(&mut self, other: Self) {
+        self.replies += other.replies;
+        self.boosts += other.boosts;
+        self.likes += other.likes;
+        self.zaps += other.zaps;
+        self.zap_sats += other.zap_sats;
+    }
+}
+
 /// Platform trait for unified social media platform interactions
 ///
 /// This trait defines the common interface that all platform implementations must provide.
@@ -92,6 +210,20 @@ pub trait Platform: Send + Sync {
     /// - Network issues occur (`PlatformError::Network`)
     async fn post(&self, post: &crate::Post) -> Result<String>;
 
+    /// Post content and return the platform's raw response
+    ///
+    /// Like [`post`](Platform::post), but also returns the full response from
+    /// the platform so it can be persisted with the post record. Platforms
+    /// that have nothing more than an ID to report can rely on the default,
+    /// which calls `post()` and returns no raw response.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`post`](Platform::post).
+    async fn post_detailed(&self, post: &crate::Post) -> Result<PublishedPost> {
+        Ok(PublishedPost::new(self.post(post).await?))
+    }
+
     /// Validate content before posting
     ///
     /// Checks if the content meets platform-specific requirements such as character limits,
@@ -221,7 +353,8 @@ pub trait Platform: Send + Sync {
     ///
     /// A tuple of (platform_attachment_id, optional_url).
     /// - For Mastodon: (media_id, None)
-    /// - For Nostr: (nip96_hash, Some(url))
+    /// - For Nostr: (NIP-92 imeta fields as a JSON array, Some(url))
+    /// - For SSB: (blob mention as a JSON object, None)
     ///
     /// # Errors
     ///
@@ -275,4 +408,124 @@ pub trait Platform: Send + Sync {
                 .into(),
         )
     }
+
+    /// Post content with media that has already been uploaded
+    ///
+    /// Used when posting from the database, where attachments are uploaded
+    /// ahead of time by [`crate::media::upload_attachments`] and each upload
+    /// record carries the platform's media ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the platform doesn't support attachments or the
+    /// post fails to publish.
+    async fn post_with_media(
+        &self,
+        post: &crate::Post,
+        media: &[AttachmentUpload],
+    ) -> Result<PublishedPost> {
+        if media.is_empty() {
+            return self.post_detailed(post).await;
+        }
+
+        // Platforms that support attachments must override this method
+        Err(
+            PlatformError::NotImplemented(format!("{} does not support attachments", self.name()))
+                .into(),
+        )
+    }
+
+    /// Delete a published post
+    ///
+    /// Used by `plur-delete` and to expire posts (`plur-post --expires`) on
+    /// platforms that have no native expiry. Platforms that can delete posts
+    /// must override this.
+    ///
+    /// # Arguments
+    ///
+    /// * `platform_post_id` - The ID returned when the post was published
+    ///
+    /// # Errors
+    ///
+    /// Returns `PlatformError::NotImplemented` if the platform can't delete
+    /// posts, or an error if the deletion fails.
+    async fn delete(&self, _platform_post_id: &str) -> Result<()> {
+        Err(PlatformError::NotImplemented(format!(
+            "{} does not support deleting posts",
+            self.name()
+        ))
+        .into())
+    }
+
+    /// Read recent posts from a timeline
+    ///
+    /// Returns up to `limit` entries, newest first. Used by `plur-timeline`;
+    /// platforms that can read timelines must override this.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PlatformError::NotImplemented` if the platform has no
+    /// timelines, or an error if fetching fails.
+    async fn timeline(&self, kind: TimelineKind, _limit: usize) -> Result<Vec<TimelineEntry>> {
+        Err(PlatformError::NotImplemented(format!(
+            "{} does not support reading the {} timeline",
+            self.name(),
+            kind.as_str()
+        ))
+        .into())
+    }
+
+    /// Read a single post by its ID
+    ///
+    /// Used by `plur-reply` to show what is being replied to. The entry's
+    /// `kind` is [`TimelineKind::Home`]. Platforms that can read timelines
+    /// should override this too.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PlatformError::NotImplemented` if the platform can't read
+    /// posts, or an error if the post can't be fetched.
+    async fn fetch_post(&self, _platform_post_id: &str) -> Result<TimelineEntry> {
+        Err(PlatformError::NotImplemented(format!(
+            "{} does not support reading posts",
+            self.name()
+        ))
+        .into())
+    }
+
+    /// Check whether a published post can still be found
+    ///
+    /// Used by `plur-verify`. Returns `Ok(false)` when the platform answers
+    /// that the post is gone (deleted, or on no relay any more); network
+    /// trouble is an error, not a missing post. Platforms that can read
+    /// posts should override this.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PlatformError::NotImplemented` if the platform can't look
+    /// posts up, or an error if the platform couldn't be asked.
+    async fn exists(&self, _platform_post_id: &str) -> Result<bool> {
+        Err(PlatformError::NotImplemented(format!(
+            "{} does not support looking up posts",
+            self.name()
+        ))
+        .into())
+    }
+
+    /// Read how people reacted to a published post
+    ///
+    /// Used by `plur-stats --refresh`; platforms that can count replies,
+    /// boosts and likes must override this.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PlatformError::NotImplemented` if the platform has no
+    /// engagement to read, or an error if fetching fails.
+    async fn engagement(&self, _platform_post_id: &str) -> Result<Engagement> {
+        Err(PlatformError::NotImplemented(format!(
+            "{} does not support reading engagement",
+            self.name()
+        ))
+        .into())
+    }
 }
@@ -0,0 +1,368 @@
+//! NIP-96 media uploads for Nostr
+//!
+//! Nostr events can't carry files, so images are uploaded to an HTTP media
+//! server and the note links to them. The server publishes its upload
+//! endpoint at `/.well-known/nostr/nip96.json`; uploads are authorized with
+//! a signed NIP-98 event in the `Authorization` header.
+//!
+//! The uploaded file is described to clients with a NIP-92 `imeta` tag on
+//! the note, built from [`imeta_fields`].
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use nostr_sdk::{EventBuilder, Keys, Kind, Tag, TagKind};
+use serde_json::Value as JsonValue;
+use std::time::Duration;
+
+use crate::error::{PlatformError, Result};
+use crate::types::Attachment;
+
+/// How long to wait on the media server for any one request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How many times to check on an upload the server is still processing
+const PROCESSING_CHECKS: u32 = 10;
+
+/// A file stored on a NIP-96 server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nip96Upload {
+    /// Where clients download the file
+    pub url: String,
+    /// SHA-256 of the file as served (may differ from the original)
+    pub hash: Option<String>,
+    /// Dimensions as `<width>x<height>`, if the server reported them
+    pub dimensions: Option<String>,
+}
+
+/// Upload an attachment to a NIP-96 server
+///
+/// # Errors
+///
+/// Returns `Network` if the server can't be reached and `Posting` if it
+/// refuses the upload or doesn't speak NIP-96.
+pub async fn upload(server: &str, keys: &Keys, attachment: &Attachment) -> Result<Nip96Upload> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| PlatformError::Network(format!("Failed to create HTTP client: {}", e)))?;
+
+    let api_url = discover_api_url(&client, server).await?;
+
+    let bytes = tokio::fs::read(&attachment.file_path).await.map_err(|e| {
+        PlatformError::Posting(format!("Failed to read {}: {}", attachment.file_path, e))
+    })?;
+    let file_name = std::path::Path::new(&attachment.file_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "upload".to_string());
+    let mime_type = attachment.mime_type.as_str();
+
+    let file_part = reqwest::multipart::Part::bytes(bytes)
+        .file_name(file_name)
+        .mime_str(mime_type)
+        .map_err(|e| PlatformError::Posting(format!("Invalid media type: {}", e)))?;
+    let mut form = reqwest::multipart::Form::new()
+        .part("file", file_part)
+        .text("size", attachment.file_size.to_string())
+        .text("content_type", mime_type.to_string());
+    if let Some(alt) = &attachment.alt_text {
+        form = form.text("alt", alt.clone());
+    }
+
+    let authorization = authorization(keys, &api_url, "POST", Some(&attachment.file_hash))?;
+    let response = client
+        .post(&api_url)
+        .header("Authorization", authorization)
+        .multipart(form)
+        .send()
+        .await
+        .map_err(|e| {
+            PlatformError::Network(format!("NIP-96 upload to {} failed: {}", server, e))
+        })?;
+
+    let status = response.status();
+    let body: JsonValue = response.json().await.map_err(|e| {
+        PlatformError::Posting(format!(
+            "NIP-96 server {} sent an unreadable response (HTTP {}): {}",
+            server, status, e
+        ))
+    })?;
+
+    if let Some(upload) = parse_response(&body) {
+        return Ok(upload);
+    }
+
+    // Large files may be processed after the request returns
+    if is_processing(&body) {
+        if let Some(processing_url) = body.get("processing_url").and_then(|url| url.as_str()) {
+            return wait_for_processing(&client, keys, server, processing_url).await;
+        }
+    }
+
+    Err(PlatformError::Posting(format!(
+        "NIP-96 server {} refused the upload of {} (HTTP {}): {}",
+        server,
+        attachment.file_path,
+        status,
+        body.get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("no reason given")
+    ))
+    .into())
+}
+
+/// NIP-92 `imeta` fields for an uploaded attachment
+///
+/// Each field is `"<key> <value>"`, as the tag expects.
+pub fn imeta_fields(upload: &Nip96Upload, attachment: &Attachment) -> Vec<String> {
+    let mut fields = vec![
+        format!("url {}", upload.url),
+        format!("m {}", attachment.mime_type.as_str()),
+    ];
+    if let Some(hash) = &upload.hash {
+        fields.push(format!("x {}", hash));
+    }
+    if let Some(dimensions) = &upload.dimensions {
+        fields.push(format!("dim {}", dimensions));
+    }
+    if let Some(alt) = &attachment.alt_text {
+        fields.push(format!("alt {}", alt));
+    }
+    fields
+}
+
+/// Find the upload endpoint from the server's NIP-96 document
+async fn discover_api_url(client: &reqwest::Client, server: &str) -> Result<String> {
+    let mut base = server.trim_end_matches('/').to_string();
+
+    // A server may delegate uploads to another; follow that once
+    for _ in 0..2 {
+        let well_known = format!("{}/.well-known/nostr/nip96.json", base);
+        let document: JsonValue = client
+            .get(&well_known)
+            .send()
+            .await
+            .map_err(|e| PlatformError::Network(format!("Failed to reach {}: {}", well_known, e)))?
+            .json()
+            .await
+            .map_err(|e| {
+                PlatformError::Posting(format!(
+                    "{} is not a NIP-96 media server (no valid {}): {}",
+                    server, well_known, e
+                ))
+            })?;
+
+        if let Some(api_url) = document
+            .get("api_url")
+            .and_then(|url| url.as_str())
+            .filter(|url| !url.is_empty())
+        {
+            return Ok(api_url.to_string());
+        }
+
+        match document
+            .get("delegated_to_url")
+            .and_then(|url| url.as_str())
+            .filter(|url| !url.is_empty())
+        {
+            Some(delegated) => base = delegated.trim_end_matches('/').to_string(),
+            None => break,
+        }
+    }
+
+    Err(PlatformError::Posting(format!(
+        "{} does not advertise a NIP-96 upload endpoint (api_url)",
+        server
+    ))
+    .into())
+}
+
+/// Poll a processing upload until the server reports the file's URL
+async fn wait_for_processing(
+    client: &reqwest::Client,
+    keys: &Keys,
+    server: &str,
+    processing_url: &str,
+) -> Result<Nip96Upload> {
+    for _ in 0..PROCESSING_CHECKS {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        let authorization = authorization(keys, processing_url, "GET", None)?;
+        let body: JsonValue = client
+            .get(processing_url)
+            .header("Authorization", authorization)
+            .send()
+            .await
+            .map_err(|e| PlatformError::Network(format!("Failed to reach {}: {}", server, e)))?
+            .json()
+            .await
+            .map_err(|e| {
+                PlatformError::Posting(format!(
+                    "NIP-96 server {} sent an unreadable processing status: {}",
+                    server, e
+                ))
+            })?;
+
+        if let Some(upload) = parse_response(&body) {
+            return Ok(upload);
+        }
+        if !is_processing(&body) {
+            break;
+        }
+    }
+
+    Err(PlatformError::Network(format!(
+        "NIP-96 server {} is still processing the upload",
+        server
+    ))
+    .into())
+}
+
+/// A NIP-98 `Authorization` header value for one request
+fn authorization(keys: &Keys, url: &str, method: &str, payload: Option<&str>) -> Result<String> {
+    let mut tags = vec![
+        Tag::custom(TagKind::custom("u"), vec![url.to_string()]),
+        Tag::custom(TagKind::custom("method"), vec![method.to_string()]),
+    ];
+    if let Some(payload) = payload {
+        tags.push(Tag::custom(
+            TagKind::custom("payload"),
+            vec![payload.to_string()],
+        ));
+    }
+
+    let event = EventBuilder::new(Kind::HttpAuth, "", tags)
+        .to_event(keys)
+        .map_err(|e| {
+            PlatformError::Authentication(format!("Failed to sign NIP-98 upload auth: {}", e))
+        })?;
+    let json = serde_json::to_string(&event).map_err(|e| {
+        PlatformError::Authentication(format!("Failed to encode NIP-98 upload auth: {}", e))
+    })?;
+
+    Ok(format!("Nostr {}", BASE64.encode(json)))
+}
+
+fn is_processing(body: &JsonValue) -> bool {
+    body.get("status").and_then(|s| s.as_str()) == Some("processing")
+}
+
+/// The uploaded file from a successful response's `nip94_event` tags
+fn parse_response(body: &JsonValue) -> Option<Nip96Upload> {
+    if body.get("status").and_then(|s| s.as_str()) != Some("success") {
+        return None;
+    }
+
+    let tags = body.get("nip94_event")?.get("tags")?.as_array()?;
+    let tag = |name: &str| {
+        tags.iter().find_map(|tag| {
+            let tag = tag.as_array()?;
+            if tag.first()?.as_str()? == name {
+                tag.get(1)?.as_str().map(String::from)
+            } else {
+                None
+            }
+        })
+    };
+
+    Some(Nip96Upload {
+        url: tag("url")?,
+        hash: tag("x").or_else(|| tag("ox")),
+        dimensions: tag("dim"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ImageMimeType;
+
+    #[test]
+    fn test_parse_success_response() {
+        let body = serde_json::json!({
+            "status": "success",
+            "message": "Upload successful.",
+            "nip94_event": {
+                "tags": [
+                    ["url", "https://media.example.com/abc.png"],
+                    ["ox", "original-hash"],
+                    ["x", "served-hash"],
+                    ["m", "image/png"],
+                    ["dim", "800x600"]
+                ],
+                "content": ""
+            }
+        });
+
+        let upload = parse_response(&body).unwrap();
+        assert_eq!(upload.url, "https://media.example.com/abc.png");
+        assert_eq!(upload.hash.as_deref(), Some("served-hash"));
+        assert_eq!(upload.dimensions.as_deref(), Some("800x600"));
+    }
+
+    #[test]
+    fn test_parse_error_and_processing_responses() {
+        let error = serde_json::json!({"status": "error", "message": "File too large"});
+        assert_eq!(parse_response(&error), None);
+        assert!(!is_processing(&error));
+
+        let processing = serde_json::json!({
+            "status": "processing",
+            "processing_url": "https://media.example.com/status/1"
+        });
+        assert_eq!(parse_response(&processing), None);
+        assert!(is_processing(&processing));
+    }
+
+    #[test]
+    fn test_imeta_fields() {
+        let attachment = Attachment::new(
+            "post".to_string(),
+            "/tmp/photo.png".to_string(),
+            ImageMimeType::Png,
+            1024,
+            "ab".repeat(32),
+            Some("A sunset".to_string()),
+        );
+        let upload = Nip96Upload {
+            url: "https://media.example.com/abc.png".to_string(),
+            hash: Some("cd".repeat(32)),
+            dimensions: None,
+        };
+
+        assert_eq!(
+            imeta_fields(&upload, &attachment),
+            vec![
+                "url https://media.example.com/abc.png".to_string(),
+                "m image/png".to_string(),
+                format!("x {}", "cd".repeat(32)),
+                "alt A sunset".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_authorization_is_signed_http_auth_event() {
+        let keys = Keys::generate();
+        let header = authorization(
+            &keys,
+            "https://media.example.com/upload",
+            "POST",
+            Some("abc"),
+        )
+        .unwrap();
+
+        let json = BASE64
+            .decode(header.strip_prefix("Nostr ").unwrap())
+            .unwrap();
+        let event: JsonValue = serde_json::from_slice(&json).unwrap();
+        assert_eq!(event["kind"], 27235);
+        assert!(event["tags"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!(["method", "POST"])));
+        assert!(event["tags"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!(["payload", "abc"])));
+    }
+}
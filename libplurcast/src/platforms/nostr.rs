@@ -1,13 +1,19 @@
 //! Nostr platform implementation
 
 use async_trait::async_trait;
-use nostr_sdk::{Client, EventId, Keys, Tag, TagKind, ToBech32};
+use nostr_sdk::{
+    Client, Event, EventBuilder, EventId, EventSource, Filter, Keys, Kind, PublicKey, Tag, TagKind,
+    Timestamp, ToBech32,
+};
 use secrecy::{DebugSecret, ExposeSecret, Secret, SecretString};
 use zeroize::Zeroize;
 
 use crate::config::NostrConfig;
 use crate::error::{PlatformError, Result};
-use crate::platforms::Platform;
+use crate::platforms::{
+    links, nip96, Engagement, Platform, PublishedPost, TimelineEntry, TimelineKind,
+};
+use crate::types::{Attachment, AttachmentUpload, RelayResult};
 
 /// Wrapper around nostr_sdk::Keys that implements Zeroize
 ///
@@ -65,10 +71,33 @@ impl NostrKeys {
 pub const SHARED_TEST_KEY: &str =
     "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
 
+/// Most events asked of the relays per request when fetching notes
+///
+/// Relays cap how many events they return for one filter, so
+/// [`NostrPlatform::fetch_own_notes`] pages back through time in steps of
+/// this size.
+const FETCH_PAGE_SIZE: usize = 500;
+
+/// How long to wait for relays when reading a timeline
+const TIMELINE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// One of the account's own notes, as fetched from its relays
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchedNote {
+    /// Note ID in bech32 (`note1...`), as stored for published posts
+    pub note_id: String,
+    /// When the note was created (Unix timestamp)
+    pub created_at: i64,
+    pub content: String,
+    /// The signed event as JSON
+    pub event: serde_json::Value,
+}
+
 pub struct NostrPlatform {
     client: Option<Client>,
     keys: Option<Secret<NostrKeys>>, // Protected with Secret for automatic memory zeroing
     relays: Vec<String>,
+    media_server: Option<String>,
     authenticated: bool,
 }
 
@@ -79,6 +108,7 @@ impl std::fmt::Debug for NostrPlatform {
             .field("client", &self.client.is_some())
             .field("keys", &self.keys) // Secret<T> has safe Debug impl that redacts content
             .field("relays", &self.relays)
+            .field("media_server", &self.media_server)
             .field("authenticated", &self.authenticated)
             .finish()
     }
@@ -90,6 +120,7 @@ impl NostrPlatform {
             client: None,
             keys: None,
             relays: config.relays.clone(),
+            media_server: config.media_server.clone(),
             authenticated: false,
         }
     }
@@ -205,62 +236,13 @@ impl NostrPlatform {
 
         self.load_keys_from_string(&content)
     }
-}
-
-// Drop implementation for secure memory zeroing
-impl Drop for NostrPlatform {
-    fn drop(&mut self) {
-        if self.keys.is_some() {
-            tracing::debug!("Zeroing Nostr private key from memory");
-            // Keys automatically zeroed by Secret<T> wrapper
-            self.keys = None;
-        }
-    }
-}
-
-#[async_trait]
-impl Platform for NostrPlatform {
-    async fn authenticate(&mut self) -> Result<()> {
-        if self.keys.is_none() {
-            return Err(PlatformError::Authentication(
-                "Nostr authentication failed (authenticate): Keys not loaded. \
-                Suggestion: Load keys using load_keys() before calling authenticate()."
-                    .to_string(),
-            )
-            .into());
-        }
 
-        let client = self.client.as_ref().ok_or_else(|| {
-            PlatformError::Authentication(
-                "Nostr authentication failed (authenticate): Client not initialized. \
-                Suggestion: Load keys using load_keys() before calling authenticate()."
-                    .to_string(),
-            )
-        })?;
-
-        // Add relays
-        tracing::debug!("Adding {} Nostr relays", self.relays.len());
-        for relay in &self.relays {
-            tracing::debug!("  Adding relay: {}", relay);
-            client.add_relay(relay).await.map_err(|e| {
-                PlatformError::Network(format!(
-                    "Nostr network error (add relay): Failed to add relay '{}': {}. \
-                    Suggestion: Check that the relay URL is valid and accessible.",
-                    relay, e
-                ))
-            })?;
-        }
-
-        // Connect to relays
-        tracing::debug!("Connecting to Nostr relays...");
-        client.connect().await;
-
-        self.authenticated = true;
-        tracing::debug!("Nostr authentication complete");
-        Ok(())
-    }
-
-    async fn post(&self, post: &crate::Post) -> Result<String> {
+    /// Sign and publish a note, linking any media already uploaded
+    async fn publish_note(
+        &self,
+        post: &crate::Post,
+        media: &[AttachmentUpload],
+    ) -> Result<PublishedPost> {
         if !self.authenticated {
             return Err(PlatformError::Authentication(
                 "Nostr posting failed (post): Not authenticated. \
@@ -333,7 +315,7 @@ impl Platform for NostrPlatform {
         // Build tags for NIP-10 threading
         // NIP-10 format: ["e", "<event-id>", "<relay>", "reply"]
         // The "reply" marker tells clients this is a direct reply for threading
-        let tags: Vec<Tag> = if let Some(parent_id) = reply_to {
+        let mut tags: Vec<Tag> = if let Some(parent_id) = reply_to {
             tracing::debug!("Adding NIP-10 reply tag for parent event: {}", parent_id);
             vec![Tag::custom(
                 TagKind::custom("e"),
@@ -343,8 +325,112 @@ impl Platform for NostrPlatform {
             vec![]
         };
 
-        // Create and publish event (with or without POW)
-        let event_id = if let Some(difficulty) = pow_difficulty {
+        // NIP-36 content warning: ["content-warning", "<reason>"]
+        let content_warning: Option<String> = post
+            .metadata
+            .as_ref()
+            .and_then(|metadata_str| serde_json::from_str::<serde_json::Value>(metadata_str).ok())
+            .and_then(|metadata| {
+                metadata
+                    .get("content_warning")
+                    .and_then(|cw| cw.as_str())
+                    .map(String::from)
+            });
+        if let Some(reason) = content_warning {
+            tags.push(Tag::custom(
+                TagKind::custom("content-warning"),
+                vec![reason],
+            ));
+        }
+
+        // NIP-32 language label: ["L", "ISO-639-1"], ["l", "<code>", "ISO-639-1"]
+        if let Some(language) = crate::language::from_metadata(post.metadata.as_deref()) {
+            tags.push(Tag::custom(
+                TagKind::custom("L"),
+                vec![crate::language::NOSTR_LANGUAGE_NAMESPACE.to_string()],
+            ));
+            tags.push(Tag::custom(
+                TagKind::custom("l"),
+                vec![
+                    language,
+                    crate::language::NOSTR_LANGUAGE_NAMESPACE.to_string(),
+                ],
+            ));
+        }
+
+        // NIP-24 hashtags: ["t", "<tag>"], lowercase
+        for hashtag in crate::hashtags::from_metadata(post.metadata.as_deref()) {
+            tags.push(Tag::hashtag(hashtag.to_lowercase()));
+        }
+
+        // NIP-40 expiration: ["expiration", "<unix timestamp>"], counted from now
+        if let Some(expires_in) = crate::expiry::from_metadata(post.metadata.as_deref()) {
+            let expires_at = chrono::Utc::now().timestamp() + expires_in;
+            tags.push(Tag::custom(
+                TagKind::custom("expiration"),
+                vec![expires_at.to_string()],
+            ));
+        }
+
+        // NIP-92: media URLs go in the content, described by imeta tags
+        let mut urls = Vec::new();
+        for upload in media {
+            let url = match &upload.remote_url {
+                Some(url) => url,
+                None => continue,
+            };
+            urls.push(url.as_str());
+
+            let fields: Vec<String> = upload
+                .platform_attachment_id
+                .as_deref()
+                .and_then(|id| serde_json::from_str(id).ok())
+                .unwrap_or_else(|| vec![format!("url {}", url)]);
+            tags.push(Tag::custom(TagKind::custom("imeta"), fields));
+        }
+        let content = if urls.is_empty() {
+            post.content.clone()
+        } else {
+            format!("{}\n\n{}", post.content, urls.join("\n"))
+        };
+
+        // NIP-88 polls: a kind 1068 event with the text as the question,
+        // ["option", "<id>", "<label>"] per choice and ["endsAt", "<unix timestamp>"]
+        let poll = crate::poll::from_metadata(post.metadata.as_deref());
+        if let Some(poll) = &poll {
+            for (i, option) in poll.options.iter().enumerate() {
+                tags.push(Tag::custom(
+                    TagKind::custom("option"),
+                    vec![i.to_string(), option.clone()],
+                ));
+            }
+            tags.push(Tag::custom(
+                TagKind::custom("polltype"),
+                vec!["singlechoice".to_string()],
+            ));
+            let ends_at = chrono::Utc::now().timestamp() + poll.duration;
+            tags.push(Tag::custom(
+                TagKind::custom("endsAt"),
+                vec![ends_at.to_string()],
+            ));
+        }
+
+        // Create and sign the event (with or without POW)
+        let event = if poll.is_some() {
+            // Proof of work is only mined for text notes
+            if pow_difficulty.is_some() {
+                tracing::warn!("Skipping POW for Nostr poll event");
+            }
+            EventBuilder::new(Kind::Custom(crate::poll::NOSTR_POLL_KIND), &content, tags)
+                .to_event(keys.expose_secret().as_keys())
+                .map_err(|e| {
+                    PlatformError::Posting(format!(
+                        "Nostr posting failed (sign): Failed to sign poll: {}. \
+                        Suggestion: Ensure your keys are valid.",
+                        e
+                    ))
+                })?
+        } else if let Some(difficulty) = pow_difficulty {
             // Use parallel POW mining (multi-threaded)
             if require_21e8 {
                 tracing::info!(
@@ -359,7 +445,7 @@ impl Platform for NostrPlatform {
             }
 
             let event = crate::platforms::nostr_pow::mine_event_parallel(
-                &post.content,
+                &content,
                 keys.expose_secret().as_keys(),
                 difficulty,
                 require_21e8,
@@ -375,34 +461,263 @@ impl Platform for NostrPlatform {
             })?;
 
             tracing::info!("POW mining complete, publishing event...");
-            client.send_event(event).await.map_err(|e| {
-                PlatformError::Posting(format!(
-                    "Nostr posting failed (publish POW event): Failed to publish note: {}. \
-                    Suggestion: Check relay connectivity and ensure your keys are valid. \
-                    The system will automatically retry transient failures.",
-                    e
-                ))
-            })?
+            event
         } else {
-            // Standard posting without POW (includes NIP-10 tags if replying)
-            client
-                .publish_text_note(&post.content, tags)
-                .await
+            // Standard note without POW (includes NIP-10 tags if replying)
+            EventBuilder::text_note(&content, tags)
+                .to_event(keys.expose_secret().as_keys())
                 .map_err(|e| {
                     PlatformError::Posting(format!(
-                        "Nostr posting failed (publish): Failed to publish note: {}. \
-                    Suggestion: Check relay connectivity and ensure your keys are valid. \
-                    The system will automatically retry transient failures.",
+                        "Nostr posting failed (sign): Failed to sign note: {}. \
+                        Suggestion: Ensure your keys are valid.",
                         e
                     ))
                 })?
         };
 
+        // Keep the signed event so it can be stored with the post record
+        let event_json = serde_json::to_value(&event).ok();
+
+        // Publish to each relay separately so every acknowledgement is timed
+        let sends = self.relays.iter().map(|relay| {
+            let event = event.clone();
+            async move {
+                let started = std::time::Instant::now();
+                let output = client.send_event_to([relay.as_str()], event).await;
+                let latency_ms = Some(started.elapsed().as_millis() as i64);
+
+                let (event_id, accepted, message) = match output {
+                    Ok(output) if !output.success.is_empty() => (Some(*output.id()), true, None),
+                    Ok(output) => (None, false, output.failed.into_values().next().flatten()),
+                    Err(e) => (None, false, Some(e.to_string())),
+                };
+
+                (
+                    event_id,
+                    RelayResult {
+                        relay_url: relay.clone(),
+                        accepted,
+                        message,
+                        latency_ms,
+                    },
+                )
+            }
+        });
+        let (event_ids, relay_results): (Vec<Option<EventId>>, Vec<RelayResult>) =
+            futures::future::join_all(sends).await.into_iter().unzip();
+
+        let event_id = match event_ids.into_iter().flatten().next() {
+            Some(event_id) => event_id,
+            None => {
+                let reasons: Vec<String> = relay_results
+                    .iter()
+                    .map(|r| {
+                        format!(
+                            "{}: {}",
+                            r.relay_url,
+                            r.message.as_deref().unwrap_or("rejected")
+                        )
+                    })
+                    .collect();
+                return Err(PlatformError::Posting(format!(
+                    "Nostr posting failed (publish): No relay accepted the note ({}). \
+                    Suggestion: Check relay connectivity and ensure your keys are valid. \
+                    The system will automatically retry transient failures.",
+                    reasons.join("; ")
+                ))
+                .into());
+            }
+        };
+
         // Return note ID in bech32 format
-        Ok(event_id
-            .id()
-            .to_bech32()
-            .unwrap_or_else(|_| event_id.id().to_hex()))
+        let note_id = event_id.to_bech32().unwrap_or_else(|_| event_id.to_hex());
+
+        // Record which relays accepted the event and why the others refused it
+        let accepted: Vec<String> = relay_results
+            .iter()
+            .filter(|r| r.accepted)
+            .map(|r| r.relay_url.clone())
+            .collect();
+        let rejected: serde_json::Map<String, serde_json::Value> = relay_results
+            .iter()
+            .filter(|r| !r.accepted)
+            .map(|r| (r.relay_url.clone(), serde_json::json!(r.message)))
+            .collect();
+
+        Ok(PublishedPost::new(note_id)
+            .with_response(serde_json::json!({
+                "event": event_json,
+                "relays": {
+                    "accepted": accepted,
+                    "rejected": rejected,
+                },
+            }))
+            .with_relay_results(relay_results))
+    }
+
+    /// Fetch the account's own text notes (kind 1) from the configured relays
+    ///
+    /// Asks every relay for notes authored by the loaded key, newest first,
+    /// paging back until no relay has older ones, and returns them oldest
+    /// first with duplicates across relays removed. `since` limits the
+    /// fetch to notes created at or after that Unix timestamp; `timeout`
+    /// bounds each request. Requires [`authenticate`](Platform::authenticate).
+    pub async fn fetch_own_notes(
+        &self,
+        since: Option<i64>,
+        timeout: std::time::Duration,
+    ) -> Result<Vec<FetchedNote>> {
+        if !self.authenticated {
+            return Err(PlatformError::Authentication(
+                "Nostr fetch failed (fetch notes): Not authenticated. \
+                Suggestion: Call authenticate() before fetching notes."
+                    .to_string(),
+            )
+            .into());
+        }
+        let (client, keys) = match (self.client.as_ref(), self.keys.as_ref()) {
+            (Some(client), Some(keys)) => (client, keys),
+            _ => {
+                return Err(PlatformError::Authentication(
+                    "Nostr fetch failed (fetch notes): Keys not loaded. \
+                    Suggestion: Load keys using load_keys() before fetching notes."
+                        .to_string(),
+                )
+                .into())
+            }
+        };
+        let author = keys.expose_secret().as_keys().public_key();
+
+        let mut notes: std::collections::HashMap<EventId, FetchedNote> =
+            std::collections::HashMap::new();
+        let mut until: Option<Timestamp> = None;
+
+        loop {
+            let mut filter = Filter::new()
+                .author(author)
+                .kind(Kind::TextNote)
+                .limit(FETCH_PAGE_SIZE);
+            if let Some(since) = since {
+                filter = filter.since(Timestamp::from(since.max(0) as u64));
+            }
+            if let Some(until) = until {
+                filter = filter.until(until);
+            }
+
+            let events = client
+                .get_events_of(vec![filter], EventSource::relays(Some(timeout)))
+                .await
+                .map_err(|e| {
+                    PlatformError::Network(format!(
+                        "Nostr network error (fetch notes): {}. \
+                        Suggestion: Check relay connectivity.",
+                        e
+                    ))
+                })?;
+
+            let before = notes.len();
+            let mut oldest = until;
+            for event in events {
+                if event.pubkey != author || event.kind != Kind::TextNote {
+                    continue;
+                }
+                oldest = Some(match oldest {
+                    Some(oldest) if oldest <= event.created_at => oldest,
+                    _ => event.created_at,
+                });
+                notes.entry(event.id).or_insert_with(|| FetchedNote {
+                    note_id: event.id.to_bech32().unwrap_or_else(|_| event.id.to_hex()),
+                    created_at: event.created_at.as_u64() as i64,
+                    content: event.content.clone(),
+                    event: serde_json::to_value(&event).unwrap_or_default(),
+                });
+            }
+
+            tracing::debug!(
+                "Fetched {} new note(s) from relays ({} total)",
+                notes.len() - before,
+                notes.len()
+            );
+
+            // Page back from the oldest note seen; notes sharing its second
+            // come back again and are skipped, so stop when nothing is new
+            if notes.len() == before || oldest == until {
+                break;
+            }
+            until = oldest;
+        }
+
+        let mut notes: Vec<FetchedNote> = notes.into_values().collect();
+        notes.sort_by(|a, b| {
+            a.created_at
+                .cmp(&b.created_at)
+                .then_with(|| a.note_id.cmp(&b.note_id))
+        });
+        Ok(notes)
+    }
+}
+
+// Drop implementation for secure memory zeroing
+impl Drop for NostrPlatform {
+    fn drop(&mut self) {
+        if self.keys.is_some() {
+            tracing::debug!("Zeroing Nostr private key from memory");
+            // Keys automatically zeroed by Secret<T> wrapper
+            self.keys = None;
+        }
+    }
+}
+
+#[async_trait]
+impl Platform for NostrPlatform {
+    async fn authenticate(&mut self) -> Result<()> {
+        if self.keys.is_none() {
+            return Err(PlatformError::Authentication(
+                "Nostr authentication failed (authenticate): Keys not loaded. \
+                Suggestion: Load keys using load_keys() before calling authenticate()."
+                    .to_string(),
+            )
+            .into());
+        }
+
+        let client = self.client.as_ref().ok_or_else(|| {
+            PlatformError::Authentication(
+                "Nostr authentication failed (authenticate): Client not initialized. \
+                Suggestion: Load keys using load_keys() before calling authenticate()."
+                    .to_string(),
+            )
+        })?;
+
+        // Add relays
+        tracing::debug!("Adding {} Nostr relays", self.relays.len());
+        for relay in &self.relays {
+            tracing::debug!("  Adding relay: {}", relay);
+            client.add_relay(relay).await.map_err(|e| {
+                PlatformError::Network(format!(
+                    "Nostr network error (add relay): Failed to add relay '{}': {}. \
+                    Suggestion: Check that the relay URL is valid and accessible.",
+                    relay, e
+                ))
+            })?;
+        }
+
+        // Connect to relays
+        tracing::debug!("Connecting to Nostr relays...");
+        client.connect().await;
+
+        self.authenticated = true;
+        tracing::debug!("Nostr authentication complete");
+        Ok(())
+    }
+
+    async fn post(&self, post: &crate::Post) -> Result<String> {
+        self.post_detailed(post)
+            .await
+            .map(|published| published.post_id)
+    }
+
+    async fn post_detailed(&self, post: &crate::Post) -> Result<PublishedPost> {
+        self.publish_note(post, &[]).await
     }
 
     fn validate_content(&self, content: &str) -> Result<()> {
@@ -440,6 +755,533 @@ impl Platform for NostrPlatform {
         // Platform is configured if keys have been loaded
         self.keys.is_some()
     }
+
+    fn supports_attachments(&self) -> bool {
+        // Nostr itself can't carry files; they go to a NIP-96 server
+        self.media_server.is_some()
+    }
+
+    async fn upload_attachment(&self, attachment: &Attachment) -> Result<(String, Option<String>)> {
+        let server = self.media_server.as_deref().ok_or_else(|| {
+            PlatformError::NotImplemented(
+                "Nostr attachments need a NIP-96 media server (set media_server in [nostr])"
+                    .to_string(),
+            )
+        })?;
+        let keys = self.keys.as_ref().ok_or_else(|| {
+            PlatformError::Authentication(
+                "Nostr upload failed: Keys not loaded. \
+                Suggestion: Load keys using load_keys() before uploading."
+                    .to_string(),
+            )
+        })?;
+
+        let upload = nip96::upload(server, keys.expose_secret().as_keys(), attachment).await?;
+        let fields = serde_json::to_string(&nip96::imeta_fields(&upload, attachment))
+            .map_err(|e| PlatformError::Posting(format!("Failed to record upload: {}", e)))?;
+
+        Ok((fields, Some(upload.url)))
+    }
+
+    async fn post_with_media(
+        &self,
+        post: &crate::Post,
+        media: &[AttachmentUpload],
+    ) -> Result<PublishedPost> {
+        self.publish_note(post, media).await
+    }
+
+    /// Ask relays to delete a note (NIP-09)
+    ///
+    /// Publishes a deletion request (kind 5) for the note. Relays that honour
+    /// it stop serving the note, but nothing forces them to, and copies
+    /// already fetched by clients stay where they are.
+    async fn delete(&self, platform_post_id: &str) -> Result<()> {
+        if !self.authenticated {
+            return Err(PlatformError::Authentication(
+                "Nostr deletion failed (delete): Not authenticated. \
+                Suggestion: Call authenticate() before attempting to delete."
+                    .to_string(),
+            )
+            .into());
+        }
+
+        let (client, keys) = match (self.client.as_ref(), self.keys.as_ref()) {
+            (Some(client), Some(keys)) => (client, keys),
+            _ => {
+                return Err(PlatformError::Authentication(
+                    "Nostr deletion failed (delete): Keys not loaded. \
+                    Suggestion: Load keys using load_keys() before attempting to delete."
+                        .to_string(),
+                )
+                .into())
+            }
+        };
+
+        let event_id = EventId::parse(platform_post_id).map_err(|e| {
+            PlatformError::Validation(format!(
+                "Nostr deletion failed (parse): '{}' is not a note ID: {}",
+                platform_post_id, e
+            ))
+        })?;
+
+        let event = EventBuilder::delete([event_id])
+            .to_event(keys.expose_secret().as_keys())
+            .map_err(|e| {
+                PlatformError::Posting(format!(
+                    "Nostr deletion failed (sign): Failed to sign deletion request: {}",
+                    e
+                ))
+            })?;
+
+        let output = client.send_event(event).await.map_err(|e| {
+            PlatformError::Network(format!(
+                "Nostr deletion failed (publish): {}. \
+                Suggestion: Check relay connectivity.",
+                e
+            ))
+        })?;
+        if output.success.is_empty() {
+            return Err(PlatformError::Posting(
+                "Nostr deletion failed (publish): No relay accepted the deletion request"
+                    .to_string(),
+            )
+            .into());
+        }
+
+        tracing::debug!(
+            "Deletion request for {} accepted by {} relay(s)",
+            platform_post_id,
+            output.success.len()
+        );
+        Ok(())
+    }
+
+    /// Read notes from followed accounts, or notes mentioning the account
+    ///
+    /// The home timeline follows the account's NIP-02 contact list: the
+    /// newest one names the followed keys, whose notes are then asked of the
+    /// relays. Mentions are notes tagging the account's key, which replies
+    /// to its notes do as well.
+    async fn timeline(&self, kind: TimelineKind, limit: usize) -> Result<Vec<TimelineEntry>> {
+        if !self.authenticated {
+            return Err(PlatformError::Authentication(
+                "Nostr fetch failed (timeline): Not authenticated. \
+                Suggestion: Call authenticate() before reading timelines."
+                    .to_string(),
+            )
+            .into());
+        }
+        let (client, keys) = match (self.client.as_ref(), self.keys.as_ref()) {
+            (Some(client), Some(keys)) => (client, keys),
+            _ => {
+                return Err(PlatformError::Authentication(
+                    "Nostr fetch failed (timeline): Keys not loaded. \
+                    Suggestion: Load keys using load_keys() before reading timelines."
+                        .to_string(),
+                )
+                .into())
+            }
+        };
+        let me = keys.expose_secret().as_keys().public_key();
+
+        let filter = match kind {
+            TimelineKind::Home => {
+                let contact_lists = client
+                    .get_events_of(
+                        vec![Filter::new().author(me).kind(Kind::ContactList).limit(1)],
+                        EventSource::relays(Some(TIMELINE_TIMEOUT)),
+                    )
+                    .await
+                    .map_err(|e| {
+                        PlatformError::Network(format!(
+                            "Nostr network error (timeline): {}. \
+                            Suggestion: Check relay connectivity.",
+                            e
+                        ))
+                    })?;
+                let follows = contact_lists
+                    .iter()
+                    .filter(|event| event.pubkey == me)
+                    .max_by_key(|event| event.created_at)
+                    .map(|event| followed_keys(event.tags.iter()))
+                    .unwrap_or_default();
+                if follows.is_empty() {
+                    tracing::debug!("No contact list on the relays, so the home timeline is empty");
+                    return Ok(Vec::new());
+                }
+                Filter::new()
+                    .authors(follows)
+                    .kind(Kind::TextNote)
+                    .limit(limit)
+            }
+            TimelineKind::Mentions => Filter::new().pubkey(me).kind(Kind::TextNote).limit(limit),
+        };
+
+        let events = client
+            .get_events_of(vec![filter], EventSource::relays(Some(TIMELINE_TIMEOUT)))
+            .await
+            .map_err(|e| {
+                PlatformError::Network(format!(
+                    "Nostr network error (timeline): {}. \
+                    Suggestion: Check relay connectivity.",
+                    e
+                ))
+            })?;
+
+        let mut seen = std::collections::HashSet::new();
+        let mut entries: Vec<TimelineEntry> = events
+            .iter()
+            .filter(|event| event.kind == Kind::TextNote)
+            .filter(|event| kind == TimelineKind::Home || event.pubkey != me)
+            .filter(|event| seen.insert(event.id))
+            .map(|event| note_entry(event, kind))
+            .collect();
+        entries.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        entries.truncate(limit);
+
+        Ok(entries)
+    }
+
+    /// Ask the relays for one note, by `note1...` or hex ID
+    async fn fetch_post(&self, platform_post_id: &str) -> Result<TimelineEntry> {
+        let client = match self.client.as_ref() {
+            Some(client) if self.authenticated => client,
+            _ => {
+                return Err(PlatformError::Authentication(
+                    "Nostr fetch failed (note): Not authenticated. \
+                    Suggestion: Call authenticate() before reading notes."
+                        .to_string(),
+                )
+                .into())
+            }
+        };
+
+        let event_id = EventId::parse(platform_post_id).map_err(|e| {
+            PlatformError::Validation(format!(
+                "Nostr fetch failed (parse): '{}' is not a note ID: {}",
+                platform_post_id, e
+            ))
+        })?;
+
+        let events = client
+            .get_events_of(
+                vec![Filter::new().id(event_id)],
+                EventSource::relays(Some(TIMELINE_TIMEOUT)),
+            )
+            .await
+            .map_err(|e| {
+                PlatformError::Network(format!(
+                    "Nostr network error (note): {}. \
+                    Suggestion: Check relay connectivity.",
+                    e
+                ))
+            })?;
+
+        events
+            .iter()
+            .find(|event| event.id == event_id)
+            .map(|event| note_entry(event, TimelineKind::Home))
+            .ok_or_else(|| {
+                PlatformError::Network(format!(
+                    "Nostr fetch failed (note): No relay has note {}",
+                    platform_post_id
+                ))
+                .into()
+            })
+    }
+
+    /// Ask the relays whether a note is still there
+    ///
+    /// A note none of the answering relays returns, or one its author asked
+    /// to delete (NIP-09), is gone. An empty answer can't be told apart from
+    /// relays that didn't answer in time, so the query also asks for any one
+    /// event: if nothing at all comes back, no relay answered and that's an
+    /// error, not a missing note.
+    async fn exists(&self, platform_post_id: &str) -> Result<bool> {
+        let client = match self.client.as_ref() {
+            Some(client) if self.authenticated => client,
+            _ => {
+                return Err(PlatformError::Authentication(
+                    "Nostr fetch failed (note): Not authenticated. \
+                    Suggestion: Call authenticate() before looking up notes."
+                        .to_string(),
+                )
+                .into())
+            }
+        };
+
+        let event_id = EventId::parse(platform_post_id).map_err(|e| {
+            PlatformError::Validation(format!(
+                "Nostr fetch failed (parse): '{}' is not a note ID: {}",
+                platform_post_id, e
+            ))
+        })?;
+
+        let filters = vec![
+            Filter::new().id(event_id),
+            Filter::new().kind(Kind::EventDeletion).event(event_id),
+            // Any event shows a relay answered
+            Filter::new().limit(1),
+        ];
+        let events = client
+            .get_events_of(filters, EventSource::relays(Some(TIMELINE_TIMEOUT)))
+            .await
+            .map_err(|e| {
+                PlatformError::Network(format!(
+                    "Nostr network error (note): {}. \
+                    Suggestion: Check relay connectivity.",
+                    e
+                ))
+            })?;
+        if events.is_empty() {
+            return Err(PlatformError::Network(format!(
+                "Nostr network error (note): No relay answered the lookup of {}. \
+                Suggestion: Check relay connectivity.",
+                platform_post_id
+            ))
+            .into());
+        }
+
+        Ok(note_present(&event_id, &events))
+    }
+
+    /// Count replies, reposts, reactions and zaps of a note
+    ///
+    /// Asks the relays for events referencing the note. Relays only know
+    /// what was sent to them and may cap how much they return, so the
+    /// counts are a lower bound.
+    async fn engagement(&self, platform_post_id: &str) -> Result<Engagement> {
+        let client = match self.client.as_ref() {
+            Some(client) if self.authenticated => client,
+            _ => {
+                return Err(PlatformError::Authentication(
+                    "Nostr fetch failed (engagement): Not authenticated. \
+                    Suggestion: Call authenticate() before reading engagement."
+                        .to_string(),
+                )
+                .into())
+            }
+        };
+
+        let event_id = EventId::parse(platform_post_id).map_err(|e| {
+            PlatformError::Validation(format!(
+                "Nostr fetch failed (parse): '{}' is not a note ID: {}",
+                platform_post_id, e
+            ))
+        })?;
+
+        let filter = Filter::new().event(event_id).kinds([
+            Kind::TextNote,
+            Kind::Repost,
+            Kind::Reaction,
+            Kind::ZapReceipt,
+        ]);
+        let events = client
+            .get_events_of(vec![filter], EventSource::relays(Some(TIMELINE_TIMEOUT)))
+            .await
+            .map_err(|e| {
+                PlatformError::Network(format!(
+                    "Nostr network error (engagement): {}. \
+                    Suggestion: Check relay connectivity.",
+                    e
+                ))
+            })?;
+
+        Ok(count_engagement(&event_id, &events))
+    }
+}
+
+/// Whether a note is among `events` and no deletion request from its
+/// author is
+fn note_present(note: &EventId, events: &[Event]) -> bool {
+    let author = match events.iter().find(|event| event.id == *note) {
+        Some(event) => event.pubkey,
+        None => return false,
+    };
+
+    !events.iter().any(|event| {
+        event.kind == Kind::EventDeletion
+            && event.pubkey == author
+            && event
+                .tags
+                .iter()
+                .map(Tag::as_slice)
+                .any(|parts| parts.len() >= 2 && parts[0] == "e" && parts[1] == note.to_hex())
+    })
+}
+
+/// Engagement of a note, from the events that reference it
+///
+/// Only notes replying to this one count as replies (not those quoting it
+/// or replying further down its thread), and `-` reactions (NIP-25
+/// dislikes) aren't likes.
+fn count_engagement(note: &EventId, events: &[Event]) -> Engagement {
+    let mut seen = std::collections::HashSet::new();
+    let mut engagement = Engagement::default();
+
+    for event in events.iter().filter(|event| seen.insert(event.id)) {
+        match event.kind {
+            Kind::TextNote => {
+                if reply_target(event.tags.iter()).as_ref() == Some(note) {
+                    engagement.replies += 1;
+                }
+            }
+            Kind::Repost => engagement.boosts += 1,
+            Kind::Reaction => {
+                if event.content != "-" {
+                    engagement.likes += 1;
+                }
+            }
+            Kind::ZapReceipt => {
+                engagement.zaps += 1;
+                engagement.zap_sats += zap_sats(event);
+            }
+            _ => {}
+        }
+    }
+
+    engagement
+}
+
+/// Sats a zap receipt pays, per the `amount` (millisats) of the zap
+/// request in its `description` tag (NIP-57); 0 if it names none
+fn zap_sats(receipt: &Event) -> u64 {
+    let request = receipt
+        .tags
+        .iter()
+        .map(Tag::as_slice)
+        .find(|parts| parts.len() >= 2 && parts[0] == "description")
+        .and_then(|parts| serde_json::from_str::<serde_json::Value>(&parts[1]).ok());
+
+    request
+        .as_ref()
+        .and_then(|request| request["tags"].as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|tag| tag.as_array())
+        .find(|tag| tag.first().and_then(|name| name.as_str()) == Some("amount"))
+        .and_then(|tag| tag.get(1)?.as_str()?.parse::<u64>().ok())
+        .map(|millisats| millisats / 1000)
+        .unwrap_or(0)
+}
+
+/// A text note as a timeline entry
+fn note_entry(event: &Event, kind: TimelineKind) -> TimelineEntry {
+    let id = event.id.to_bech32().unwrap_or_else(|_| event.id.to_hex());
+    TimelineEntry {
+        platform: "nostr".to_string(),
+        kind,
+        url: links::post_url("nostr", &id, None, None),
+        id,
+        author: event
+            .pubkey
+            .to_bech32()
+            .unwrap_or_else(|_| event.pubkey.to_hex()),
+        author_name: None,
+        content: event.content.clone(),
+        content_warning: event
+            .tags
+            .iter()
+            .map(Tag::as_slice)
+            .find(|parts| parts.first().is_some_and(|name| name == "content-warning"))
+            .and_then(|parts| parts.get(1).cloned())
+            .filter(|reason| !reason.is_empty()),
+        created_at: event.created_at.as_u64() as i64,
+        reply_to: reply_target(event.tags.iter())
+            .map(|id| id.to_bech32().unwrap_or_else(|_| id.to_hex())),
+    }
+}
+
+/// Keys named by `p` tags, as in a NIP-02 contact list
+fn followed_keys<'a>(tags: impl IntoIterator<Item = &'a Tag>) -> Vec<PublicKey> {
+    tags.into_iter()
+        .map(Tag::as_slice)
+        .filter(|parts| parts.len() >= 2 && parts[0] == "p")
+        .filter_map(|parts| PublicKey::from_hex(&parts[1]).ok())
+        .collect()
+}
+
+/// The note a note replies to, per NIP-10
+///
+/// Prefers the `e` tag marked `reply`, then the one marked `root` (a direct
+/// reply to the start of a thread), then the last unmarked `e` tag, as in
+/// the older positional form. Tags marked `mention` only quote a note.
+fn reply_target<'a>(tags: impl IntoIterator<Item = &'a Tag>) -> Option<EventId> {
+    let mut reply = None;
+    let mut root = None;
+    let mut positional = None;
+
+    for parts in tags.into_iter().map(Tag::as_slice) {
+        if parts.len() < 2 || parts[0] != "e" {
+            continue;
+        }
+        let id = match EventId::parse(&parts[1]) {
+            Ok(id) => id,
+            Err(_) => continue,
+        };
+        match parts.get(3).map(String::as_str) {
+            Some("reply") => reply = Some(id),
+            Some("root") => root = Some(id),
+            Some("mention") => {}
+            _ => positional = Some(id),
+        }
+    }
+
+    reply.or(root).or(positional)
+}
+
+/// Prefix of NIP-49 encrypted secret keys
+pub const NCRYPTSEC_PREFIX: &str = "ncryptsec1";
+
+/// scrypt cost (log2 of N) for keys Plurcast encrypts; 16 is NIP-49's baseline
+const NIP49_LOG_N: u8 = 16;
+
+/// Decrypt a NIP-49 `ncryptsec` key, returning it as an nsec
+///
+/// # Errors
+///
+/// Returns an error if the key is malformed or the passphrase is wrong.
+pub fn decrypt_ncryptsec(ncryptsec: &str, passphrase: &str) -> Result<String> {
+    use nostr_sdk::nips::nip49::EncryptedSecretKey;
+    use nostr_sdk::FromBech32;
+
+    let encrypted = EncryptedSecretKey::from_bech32(ncryptsec.trim()).map_err(|e| {
+        PlatformError::Authentication(format!("Invalid NIP-49 encrypted key: {}", e))
+    })?;
+    let secret_key = encrypted.to_secret_key(passphrase).map_err(|_| {
+        PlatformError::Authentication(
+            "Could not decrypt the NIP-49 key: wrong passphrase?".to_string(),
+        )
+    })?;
+
+    secret_key.to_bech32().map_err(|e| {
+        PlatformError::Authentication(format!("Failed to encode the decrypted key: {}", e)).into()
+    })
+}
+
+/// Encrypt a hex or nsec secret key as a NIP-49 `ncryptsec`
+///
+/// # Errors
+///
+/// Returns an error if the key isn't a valid secret key.
+pub fn encrypt_ncryptsec(secret_key: &str, passphrase: &str) -> Result<String> {
+    use nostr_sdk::nips::nip49::{EncryptedSecretKey, KeySecurity};
+
+    let keys = Keys::parse(secret_key.trim())
+        .map_err(|e| PlatformError::Authentication(format!("Invalid Nostr secret key: {}", e)))?;
+    // The key has been stored elsewhere before, so its history is unknown
+    let encrypted = EncryptedSecretKey::new(
+        keys.secret_key(),
+        passphrase,
+        NIP49_LOG_N,
+        KeySecurity::Unknown,
+    )
+    .map_err(|e| PlatformError::Authentication(format!("Failed to encrypt key: {}", e)))?;
+
+    encrypted.to_bech32().map_err(|e| {
+        PlatformError::Authentication(format!("Failed to encode the encrypted key: {}", e)).into()
+    })
 }
 
 #[cfg(test)]
@@ -457,6 +1299,7 @@ mod tests {
                 "wss://nos.lol".to_string(),
             ],
             default_pow_difficulty: None,
+            media_server: None,
         }
     }
 
@@ -479,6 +1322,7 @@ mod tests {
             keys_file: keys_file.to_str().unwrap().to_string(),
             relays: vec![],
             default_pow_difficulty: None,
+            media_server: None,
         };
 
         let mut platform = NostrPlatform::new(&config);
@@ -511,6 +1355,7 @@ mod tests {
             keys_file: keys_file.to_str().unwrap().to_string(),
             relays: vec![],
             default_pow_difficulty: None,
+            media_server: None,
         };
 
         let mut platform = NostrPlatform::new(&config);
@@ -534,6 +1379,7 @@ mod tests {
             keys_file: keys_file.to_str().unwrap().to_string(),
             relays: vec![],
             default_pow_difficulty: None,
+            media_server: None,
         };
 
         let mut platform = NostrPlatform::new(&config);
@@ -563,6 +1409,7 @@ mod tests {
             keys_file: keys_file.to_str().unwrap().to_string(),
             relays: vec![],
             default_pow_difficulty: None,
+            media_server: None,
         };
 
         let mut platform = NostrPlatform::new(&config);
@@ -588,6 +1435,7 @@ mod tests {
             keys_file: "/nonexistent/path/keys".to_string(),
             relays: vec![],
             default_pow_difficulty: None,
+            media_server: None,
         };
 
         let mut platform = NostrPlatform::new(&config);
@@ -682,6 +1530,194 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_delete_without_authentication() {
+        let config = create_test_config();
+        let platform = NostrPlatform::new(&config);
+
+        let result = platform
+            .delete("note1xvwqmxy5t2dhujkme857rfdhul424wkpthzqfwfkxcdlzgkyu2fsra5prs")
+            .await;
+
+        match result {
+            Err(crate::PlurcastError::Platform(PlatformError::Authentication(msg))) => {
+                assert!(msg.contains("Not authenticated"));
+            }
+            _ => panic!("Expected authentication error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeline_without_authentication() {
+        let config = create_test_config();
+        let platform = NostrPlatform::new(&config);
+
+        match platform.timeline(TimelineKind::Mentions, 20).await {
+            Err(crate::PlurcastError::Platform(PlatformError::Authentication(msg))) => {
+                assert!(msg.contains("Not authenticated"));
+            }
+            _ => panic!("Expected authentication error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_post_without_authentication() {
+        let config = create_test_config();
+        let platform = NostrPlatform::new(&config);
+
+        match platform
+            .fetch_post("331c0d98945a9b7e4adbc9e9e1a5b7e7eaaabac15dc404b936361bf122c4e293")
+            .await
+        {
+            Err(crate::PlurcastError::Platform(PlatformError::Authentication(msg))) => {
+                assert!(msg.contains("Not authenticated"));
+            }
+            _ => panic!("Expected authentication error"),
+        }
+    }
+
+    fn e_tag(id: &EventId, marker: &str) -> Tag {
+        Tag::custom(
+            TagKind::custom("e"),
+            vec![id.to_hex(), String::new(), marker.to_string()],
+        )
+    }
+
+    #[test]
+    fn test_reply_target_follows_nip10_markers() {
+        let root =
+            EventId::parse("d2a3c4e0f7c2b1e8a3f9c6d5b4a7e8f9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5")
+                .unwrap();
+        let parent =
+            EventId::parse("331c0d98945a9b7e4adbc9e9e1a5b7e7eaaabac15dc404b936361bf122c4e293")
+                .unwrap();
+
+        let marked = [e_tag(&root, "root"), e_tag(&parent, "reply")];
+        assert_eq!(reply_target(marked.iter()), Some(parent));
+
+        let direct = [e_tag(&root, "root"), e_tag(&parent, "mention")];
+        assert_eq!(reply_target(direct.iter()), Some(root));
+
+        let positional = [
+            Tag::custom(TagKind::custom("e"), vec![root.to_hex()]),
+            Tag::custom(TagKind::custom("e"), vec![parent.to_hex()]),
+        ];
+        assert_eq!(reply_target(positional.iter()), Some(parent));
+
+        assert_eq!(reply_target([].iter()), None);
+    }
+
+    #[test]
+    fn test_note_entry() {
+        let keys = Keys::generate();
+        let followed = Keys::generate().public_key();
+        let parent =
+            EventId::parse("331c0d98945a9b7e4adbc9e9e1a5b7e7eaaabac15dc404b936361bf122c4e293")
+                .unwrap();
+        let event = EventBuilder::text_note(
+            "Nice post",
+            [
+                e_tag(&parent, "reply"),
+                Tag::custom(TagKind::custom("p"), vec![followed.to_hex()]),
+                Tag::custom(
+                    TagKind::custom("content-warning"),
+                    vec!["spoilers".to_string()],
+                ),
+            ],
+        )
+        .to_event(&keys)
+        .unwrap();
+
+        let entry = note_entry(&event, TimelineKind::Mentions);
+        assert!(entry.id.starts_with("note1"));
+        assert_eq!(entry.url, Some(format!("https://njump.me/{}", entry.id)));
+        assert_eq!(entry.author, keys.public_key().to_bech32().unwrap());
+        assert_eq!(entry.content, "Nice post");
+        assert_eq!(entry.content_warning.as_deref(), Some("spoilers"));
+        assert_eq!(entry.reply_to, Some(parent.to_bech32().unwrap()));
+
+        assert_eq!(followed_keys(event.tags.iter()), vec![followed]);
+    }
+
+    #[test]
+    fn test_note_present() {
+        let keys = Keys::generate();
+        let note = EventBuilder::text_note("Still here", vec![])
+            .to_event(&keys)
+            .unwrap();
+        let deletion = |keys: &Keys| {
+            EventBuilder::new(Kind::EventDeletion, "", vec![e_tag(&note.id, "")])
+                .to_event(keys)
+                .unwrap()
+        };
+
+        assert!(note_present(&note.id, &[note.clone()]));
+        assert!(!note_present(&note.id, &[]));
+        // Only the author can delete a note
+        assert!(note_present(
+            &note.id,
+            &[note.clone(), deletion(&Keys::generate())]
+        ));
+        assert!(!note_present(&note.id, &[note.clone(), deletion(&keys)]));
+    }
+
+    #[test]
+    fn test_count_engagement() {
+        let keys = Keys::generate();
+        let note =
+            EventId::parse("331c0d98945a9b7e4adbc9e9e1a5b7e7eaaabac15dc404b936361bf122c4e293")
+                .unwrap();
+        let other =
+            EventId::parse("d2a3c4e0f7c2b1e8a3f9c6d5b4a7e8f9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5")
+                .unwrap();
+        let event = |kind: Kind, content: &str, tags: Vec<Tag>| {
+            EventBuilder::new(kind, content, tags)
+                .to_event(&keys)
+                .unwrap()
+        };
+        let zap_request = r#"{"kind":9734,"content":"","tags":[["p","abc"],["amount","21000"]]}"#;
+
+        let reply = event(Kind::TextNote, "Agreed", vec![e_tag(&note, "reply")]);
+        let events = vec![
+            reply.clone(),
+            reply,
+            // Replies deeper in the thread and quotes aren't replies to the note
+            event(
+                Kind::TextNote,
+                "Me too",
+                vec![e_tag(&note, "root"), e_tag(&other, "reply")],
+            ),
+            event(Kind::TextNote, "Look", vec![e_tag(&note, "mention")]),
+            event(Kind::Repost, "", vec![e_tag(&note, "")]),
+            event(Kind::Reaction, "+", vec![e_tag(&note, "")]),
+            event(Kind::Reaction, "🤙", vec![e_tag(&note, "")]),
+            event(Kind::Reaction, "-", vec![e_tag(&note, "")]),
+            event(
+                Kind::ZapReceipt,
+                "",
+                vec![
+                    e_tag(&note, ""),
+                    Tag::custom(
+                        TagKind::custom("description"),
+                        vec![zap_request.to_string()],
+                    ),
+                ],
+            ),
+            event(Kind::ZapReceipt, "", vec![e_tag(&note, "")]),
+        ];
+
+        assert_eq!(
+            count_engagement(&note, &events),
+            Engagement {
+                replies: 1,
+                boosts: 1,
+                likes: 2,
+                zaps: 2,
+                zap_sats: 21,
+            }
+        );
+    }
+
     #[tokio::test]
     async fn test_authenticate_without_keys() {
         let config = create_test_config();
@@ -716,6 +1752,7 @@ mod tests {
             keys_file: keys_file.to_str().unwrap().to_string(),
             relays: vec![], // Empty relays to avoid actual network connections
             default_pow_difficulty: None,
+            media_server: None,
         };
 
         let mut platform = NostrPlatform::new(&config);
@@ -765,6 +1802,7 @@ mod tests {
             keys_file: keys_file.to_str().unwrap().to_string(),
             relays: vec![],
             default_pow_difficulty: None,
+            media_server: None,
         };
 
         let mut platform = NostrPlatform::new(&config);
@@ -774,6 +1812,22 @@ mod tests {
         assert!(result.is_ok(), "Should handle whitespace in keys file");
     }
 
+    #[test]
+    fn test_ncryptsec_round_trip() {
+        let keys = Keys::generate();
+        let nsec = keys.secret_key().to_bech32().unwrap();
+
+        let ncryptsec = encrypt_ncryptsec(&nsec, "correct horse").unwrap();
+        assert!(ncryptsec.starts_with(NCRYPTSEC_PREFIX));
+
+        assert_eq!(
+            decrypt_ncryptsec(&ncryptsec, "correct horse").unwrap(),
+            nsec
+        );
+        assert!(decrypt_ncryptsec(&ncryptsec, "wrong horse").is_err());
+        assert!(decrypt_ncryptsec("ncryptsec1garbage", "correct horse").is_err());
+    }
+
     #[test]
     fn test_multiple_relays_configuration() {
         let config = NostrConfig {
@@ -785,6 +1839,7 @@ mod tests {
                 "wss://relay3.example.com".to_string(),
             ],
             default_pow_difficulty: None,
+            media_server: None,
         };
 
         let platform = NostrPlatform::new(&config);
@@ -813,6 +1868,15 @@ mod tests {
         assert!(!platform.is_configured());
     }
 
+    #[test]
+    fn test_attachments_need_media_server() {
+        let mut config = create_test_config();
+        assert!(!NostrPlatform::new(&config).supports_attachments());
+
+        config.media_server = Some("https://nostr.build".to_string());
+        assert!(NostrPlatform::new(&config).supports_attachments());
+    }
+
     #[test]
     fn test_is_configured_with_keys() {
         let temp_dir = TempDir::new().unwrap();
@@ -828,6 +1892,7 @@ mod tests {
             keys_file: keys_file.to_str().unwrap().to_string(),
             relays: vec![],
             default_pow_difficulty: None,
+            media_server: None,
         };
 
         let mut platform = NostrPlatform::new(&config);
@@ -850,6 +1915,7 @@ mod tests {
             keys_file: "/nonexistent/path/keys".to_string(),
             relays: vec![],
             default_pow_difficulty: None,
+            media_server: None,
         };
 
         let mut platform = NostrPlatform::new(&config);
@@ -61,6 +61,16 @@ impl SSBMessage {
         }
     }
 
+    /// Attach blob mentions to a post, before it is signed
+    ///
+    /// Each mention is an object with at least a `link` to the blob.
+    pub fn with_mentions(mut self, mentions: Vec<JsonValue>) -> Self {
+        if !mentions.is_empty() {
+            self.content["mentions"] = JsonValue::Array(mentions);
+        }
+        self
+    }
+
     /// Get the canonical JSON representation for signing
     fn to_signable_json(&self) -> Result<String> {
         let signable = serde_json::json!({
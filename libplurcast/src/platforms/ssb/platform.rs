@@ -3,12 +3,15 @@
 //! This module contains the SSBPlatform struct and Platform trait implementation.
 
 use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use sha2::{Digest, Sha256};
 use std::path::PathBuf;
 
 use crate::config::SSBConfig;
 use crate::credentials::CredentialManager;
 use crate::error::{PlatformError, Result};
-use crate::platforms::Platform;
+use crate::platforms::{Platform, PublishedPost};
+use crate::types::{Attachment, AttachmentUpload};
 
 use super::keypair::SSBKeypair;
 use super::message::SSBMessage;
@@ -389,22 +392,34 @@ impl SSBPlatform {
         };
         self.post(&post).await
     }
-}
 
-#[async_trait]
-impl Platform for SSBPlatform {
-    async fn authenticate(&mut self) -> Result<()> {
-        if self.initialized {
-            return Ok(());
+    /// Copy a file into the local blob store, returning its blob ID
+    ///
+    /// Blobs live under `<feed_path>/blobs/sha256/` in the ssb-blobs layout,
+    /// so a pub replicating the feed can serve them alongside it.
+    fn store_blob(&self, bytes: &[u8]) -> Result<String> {
+        let digest = Sha256::digest(bytes);
+        let hex = format!("{:x}", digest);
+        let blob_dir = self.feed_path.join("blobs").join("sha256").join(&hex[..2]);
+        let blob_path = blob_dir.join(&hex[2..]);
+
+        if !blob_path.exists() {
+            std::fs::create_dir_all(&blob_dir).map_err(|e| {
+                PlatformError::Posting(format!("Failed to create blob directory: {}", e))
+            })?;
+            std::fs::write(&blob_path, bytes)
+                .map_err(|e| PlatformError::Posting(format!("Failed to write blob: {}", e)))?;
         }
 
-        Err(PlatformError::Authentication(
-            "SSB platform not initialized - call initialize_with_credentials first".to_string(),
-        )
-        .into())
+        Ok(format!("&{}.sha256", BASE64.encode(digest)))
     }
 
-    async fn post(&self, post: &crate::Post) -> Result<String> {
+    /// Sign and append a post, mentioning any blobs already stored
+    async fn publish(
+        &self,
+        post: &crate::Post,
+        media: &[AttachmentUpload],
+    ) -> Result<PublishedPost> {
         if !self.initialized {
             return Err(
                 PlatformError::Authentication("SSB platform not initialized".to_string()).into(),
@@ -416,11 +431,31 @@ impl Platform for SSBPlatform {
             .as_ref()
             .ok_or_else(|| PlatformError::Authentication("SSB keypair not loaded".to_string()))?;
 
-        tracing::debug!(
-            "Validating content for SSB (length: {} bytes)",
-            post.content.len()
-        );
-        self.validate_content(&post.content)?;
+        // Blobs are linked from the text as markdown images and listed in mentions
+        let mentions: Vec<serde_json::Value> = media
+            .iter()
+            .filter_map(|upload| upload.platform_attachment_id.as_deref())
+            .filter_map(|mention| serde_json::from_str(mention).ok())
+            .collect();
+        let images: Vec<String> = mentions
+            .iter()
+            .filter_map(|mention| {
+                let link = mention.get("link")?.as_str()?;
+                let name = mention.get("name").and_then(|n| n.as_str()).unwrap_or("");
+                Some(format!("![{}]({})", name, link))
+            })
+            .collect();
+        // SSB clients link #hashtags written in the text
+        let hashtags = crate::hashtags::from_metadata(post.metadata.as_deref());
+        let content = crate::hashtags::append(&post.content, &hashtags);
+        let text = if images.is_empty() {
+            content
+        } else {
+            format!("{}\n\n{}", content, images.join("\n"))
+        };
+
+        tracing::debug!("Validating content for SSB (length: {} bytes)", text.len());
+        self.validate_content(&text)?;
 
         tracing::debug!("Querying feed state from: {}", self.feed_path.display());
         let (sequence, previous) = self.query_feed_state().await?;
@@ -429,10 +464,11 @@ impl Platform for SSBPlatform {
             "Creating SSB message: sequence={}, previous={:?}, content_length={}",
             sequence,
             previous.as_ref().map(|s| &s[..20.min(s.len())]),
-            post.content.len()
+            text.len()
         );
 
-        let mut message = SSBMessage::new_post(&keypair.id, sequence, previous, &post.content);
+        let mut message =
+            SSBMessage::new_post(&keypair.id, sequence, previous, &text).with_mentions(mentions);
 
         tracing::debug!("Signing message with keypair for feed: {}", keypair.id);
         message.sign(keypair)?;
@@ -465,7 +501,35 @@ impl Platform for SSBPlatform {
             tracing::debug!("Replication will occur in background process");
         }
 
-        Ok(message_id)
+        let published = PublishedPost::new(message_id);
+        Ok(match serde_json::to_value(&message) {
+            Ok(raw) => published.with_response(raw),
+            Err(_) => published,
+        })
+    }
+}
+
+#[async_trait]
+impl Platform for SSBPlatform {
+    async fn authenticate(&mut self) -> Result<()> {
+        if self.initialized {
+            return Ok(());
+        }
+
+        Err(PlatformError::Authentication(
+            "SSB platform not initialized - call initialize_with_credentials first".to_string(),
+        )
+        .into())
+    }
+
+    async fn post(&self, post: &crate::Post) -> Result<String> {
+        self.post_detailed(post)
+            .await
+            .map(|published| published.post_id)
+    }
+
+    async fn post_detailed(&self, post: &crate::Post) -> Result<PublishedPost> {
+        self.publish(post, &[]).await
     }
 
     fn validate_content(&self, content: &str) -> Result<()> {
@@ -497,4 +561,43 @@ impl Platform for SSBPlatform {
     fn is_configured(&self) -> bool {
         self.config.enabled
     }
+
+    fn supports_attachments(&self) -> bool {
+        true
+    }
+
+    fn max_attachment_size(&self) -> u64 {
+        // Default blob size limit of ssb-server and go-ssb
+        5 * 1024 * 1024
+    }
+
+    async fn upload_attachment(&self, attachment: &Attachment) -> Result<(String, Option<String>)> {
+        let bytes = tokio::fs::read(&attachment.file_path).await.map_err(|e| {
+            PlatformError::Posting(format!("Failed to read {}: {}", attachment.file_path, e))
+        })?;
+        let blob_id = self.store_blob(&bytes)?;
+
+        let name = attachment.alt_text.clone().unwrap_or_else(|| {
+            std::path::Path::new(&attachment.file_path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+        let mention = serde_json::json!({
+            "link": blob_id,
+            "name": name,
+            "type": attachment.mime_type.as_str(),
+            "size": bytes.len(),
+        });
+
+        Ok((mention.to_string(), None))
+    }
+
+    async fn post_with_media(
+        &self,
+        post: &crate::Post,
+        media: &[AttachmentUpload],
+    ) -> Result<PublishedPost> {
+        self.publish(post, media).await
+    }
 }
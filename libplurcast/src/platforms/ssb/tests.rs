@@ -86,6 +86,56 @@ fn test_is_configured() {
     assert!(!platform_disabled.is_configured());
 }
 
+#[tokio::test]
+async fn test_upload_attachment_stores_blob() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = SSBConfig {
+        enabled: true,
+        feed_path: temp_dir.path().join("feed").to_string_lossy().to_string(),
+        pubs: vec![],
+    };
+    let platform = SSBPlatform::new(&config);
+    assert!(platform.supports_attachments());
+
+    let path = temp_dir.path().join("photo.png");
+    std::fs::write(&path, b"fake image data").unwrap();
+    let attachment = crate::types::Attachment::new(
+        "post".to_string(),
+        path.to_string_lossy().to_string(),
+        crate::types::ImageMimeType::Png,
+        15,
+        String::new(),
+        Some("A photo".to_string()),
+    );
+
+    let (mention, url) = platform.upload_attachment(&attachment).await.unwrap();
+    assert!(url.is_none());
+
+    let mention: serde_json::Value = serde_json::from_str(&mention).unwrap();
+    let link = mention["link"].as_str().unwrap();
+    assert!(link.starts_with('&') && link.ends_with(".sha256"));
+    assert_eq!(mention["name"], "A photo");
+    assert_eq!(mention["type"], "image/png");
+
+    let blobs = temp_dir.path().join("feed").join("blobs").join("sha256");
+    let stored: Vec<_> = walk(&blobs);
+    assert_eq!(stored.len(), 1);
+    assert_eq!(std::fs::read(&stored[0]).unwrap(), b"fake image data");
+}
+
+fn walk(dir: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            files.extend(walk(&path));
+        } else {
+            files.push(path);
+        }
+    }
+    files
+}
+
 // ============================================================================
 // Keypair Tests
 // ============================================================================
@@ -165,6 +215,18 @@ fn test_message_creation() {
     );
 }
 
+#[test]
+fn test_message_with_mentions() {
+    let keypair = SSBKeypair::generate();
+    let mention = serde_json::json!({"link": "&abc=.sha256", "name": "photo.png"});
+
+    let message = SSBMessage::new_post(&keypair.id, 1, None, "Look").with_mentions(vec![mention]);
+    assert_eq!(message.content["mentions"][0]["link"], "&abc=.sha256");
+
+    let plain = SSBMessage::new_post(&keypair.id, 1, None, "Look").with_mentions(vec![]);
+    assert!(plain.content.get("mentions").is_none());
+}
+
 #[test]
 fn test_message_signing() {
     let keypair = SSBKeypair::generate();
@@ -209,6 +271,7 @@ fn test_store_and_retrieve_keypair() {
         storage: StorageBackend::Encrypted,
         path: temp_dir.path().to_string_lossy().to_string(),
         master_password: Some("test-password-12345".to_string()),
+        commands: Default::default(),
     };
 
     let credentials = CredentialManager::new(config).unwrap();
@@ -231,6 +294,7 @@ fn test_has_keypair() {
         storage: StorageBackend::Encrypted,
         path: temp_dir.path().to_string_lossy().to_string(),
         master_password: Some("test-password-12345".to_string()),
+        commands: Default::default(),
     };
 
     let credentials = CredentialManager::new(config).unwrap();
@@ -0,0 +1,170 @@
+//! Polls attached with `plur-post --poll "A|B|C"`
+//!
+//! The choices and how long voting stays open are kept in the post's
+//! metadata under `"poll"`. Mastodon sends them as the status poll; Nostr
+//! publishes the post as a NIP-88 poll event (kind 1068) with an `option`
+//! tag per choice and an `endsAt` tag. SSB has no polls.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PlurcastError, Result};
+
+/// Metadata key holding a post's poll
+pub const POLL_METADATA_KEY: &str = "poll";
+
+/// NIP-88 poll event kind
+pub const NOSTR_POLL_KIND: u16 = 1068;
+
+/// Poll duration used when `--poll-duration` isn't given
+pub const DEFAULT_POLL_DURATION: &str = "1d";
+
+/// Most choices a Mastodon poll takes (the server default)
+pub const MASTODON_MAX_OPTIONS: usize = 4;
+
+/// Longest Mastodon poll choice, in characters (the server default)
+pub const MASTODON_MAX_OPTION_LENGTH: usize = 50;
+
+/// Shortest and longest Mastodon poll duration in seconds (5 minutes, 30 days)
+pub const MASTODON_DURATION_RANGE: (i64, i64) = (5 * 60, 30 * 24 * 3600);
+
+/// A poll: its choices and how long it stays open
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Poll {
+    pub options: Vec<String>,
+    /// Seconds the poll stays open after the post is sent
+    pub duration: i64,
+}
+
+/// Parse `--poll` choices (separated by `|`) and a `--poll-duration`
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if there are fewer than two choices, a choice is
+/// empty or repeated, or the duration can't be parsed or is zero.
+pub fn parse(choices: &str, duration: &str) -> Result<Poll> {
+    let options: Vec<String> = choices
+        .split('|')
+        .map(|option| option.trim().to_string())
+        .collect();
+
+    if options.len() < 2 {
+        return Err(PlurcastError::InvalidInput(
+            "A poll needs at least two choices, separated by '|' (e.g. \"Yes|No\")".to_string(),
+        ));
+    }
+    if options.iter().any(String::is_empty) {
+        return Err(PlurcastError::InvalidInput(
+            "Poll choices cannot be empty".to_string(),
+        ));
+    }
+    for (i, option) in options.iter().enumerate() {
+        if options[..i].contains(option) {
+            return Err(PlurcastError::InvalidInput(format!(
+                "Poll choice '{}' is given more than once",
+                option
+            )));
+        }
+    }
+
+    let duration = crate::scheduling::parse_duration(duration.trim())?.num_seconds();
+    if duration <= 0 {
+        return Err(PlurcastError::InvalidInput(
+            "Poll duration must be longer than zero, e.g. \"1d\"".to_string(),
+        ));
+    }
+
+    Ok(Poll { options, duration })
+}
+
+/// The poll recorded in a post's metadata, if any
+pub fn from_metadata(metadata: Option<&str>) -> Option<Poll> {
+    metadata
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .and_then(|m| serde_json::from_value(m.get(POLL_METADATA_KEY)?.clone()).ok())
+}
+
+/// Why a platform can't post the poll, if it can't
+///
+/// SSB has no polls; Mastodon holds polls to its server defaults for the
+/// number and length of choices and the duration.
+pub fn platform_error(platform: &str, poll: &Poll) -> Option<String> {
+    match platform {
+        "nostr" => None,
+        "mastodon" => {
+            let (min, max) = MASTODON_DURATION_RANGE;
+            if poll.options.len() > MASTODON_MAX_OPTIONS {
+                Some(format!(
+                    "Mastodon polls take at most {} choices (got {})",
+                    MASTODON_MAX_OPTIONS,
+                    poll.options.len()
+                ))
+            } else if let Some(option) = poll
+                .options
+                .iter()
+                .find(|option| option.chars().count() > MASTODON_MAX_OPTION_LENGTH)
+            {
+                Some(format!(
+                    "Mastodon poll choices are limited to {} characters: '{}'",
+                    MASTODON_MAX_OPTION_LENGTH, option
+                ))
+            } else if poll.duration < min || poll.duration > max {
+                Some("Mastodon polls must run between 5 minutes and 30 days".to_string())
+            } else {
+                None
+            }
+        }
+        "ssb" => Some("SSB does not support polls".to_string()),
+        other => Some(format!("{} does not support polls", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_poll() {
+        let poll = parse(" Tea | Coffee |Water", "1d").unwrap();
+        assert_eq!(poll.options, vec!["Tea", "Coffee", "Water"]);
+        assert_eq!(poll.duration, 86400);
+
+        assert!(parse("Only one", "1d").is_err());
+        assert!(parse("Yes||No", "1d").is_err());
+        assert!(parse("Yes|No|Yes", "1d").is_err());
+        assert!(parse("Yes|No", "whenever").is_err());
+    }
+
+    #[test]
+    fn test_from_metadata() {
+        let metadata = r#"{"poll":{"options":["Yes","No"],"duration":3600}}"#;
+        assert_eq!(
+            from_metadata(Some(metadata)),
+            Some(Poll {
+                options: vec!["Yes".to_string(), "No".to_string()],
+                duration: 3600,
+            })
+        );
+        assert_eq!(from_metadata(Some(r#"{"platforms":["nostr"]}"#)), None);
+        assert_eq!(from_metadata(None), None);
+    }
+
+    #[test]
+    fn test_platform_error() {
+        let poll = parse("A|B|C|D|E", "1d").unwrap();
+        assert_eq!(platform_error("nostr", &poll), None);
+        assert!(platform_error("mastodon", &poll)
+            .unwrap()
+            .contains("at most 4 choices"));
+        assert_eq!(
+            platform_error("ssb", &poll).as_deref(),
+            Some("SSB does not support polls")
+        );
+
+        let poll = parse("Yes|No", "1m").unwrap();
+        assert!(platform_error("mastodon", &poll)
+            .unwrap()
+            .contains("between 5 minutes and 30 days"));
+        let poll = parse("Yes|No", "2d").unwrap();
+        assert_eq!(platform_error("mastodon", &poll), None);
+    }
+}
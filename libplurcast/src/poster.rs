@@ -8,11 +8,12 @@ use tokio::time::sleep;
 use tracing::{info, warn};
 
 use crate::config::Config;
-use crate::credentials::CredentialManager;
+use crate::credentials::{CredentialManager, CredentialScope};
 use crate::db::Database;
 use crate::error::{PlatformError, Result};
-use crate::platforms::{mastodon::MastodonClient, nostr::NostrPlatform, Platform};
-use crate::types::{Post, PostRecord, PostStatus};
+use crate::media::upload_attachments;
+use crate::platforms::{mastodon::MastodonClient, nostr::NostrPlatform, Platform, PublishedPost};
+use crate::types::{AttachmentUpload, Post, PostRecord, PostStatus};
 
 /// Result of posting to a single platform
 #[derive(Debug, Clone)]
@@ -25,6 +26,8 @@ pub struct PostResult {
     pub platform_post_id: Option<String>,
     /// Error message (if failed)
     pub error: Option<String>,
+    /// Raw platform response (if successful and the platform provides one)
+    pub raw_response: Option<serde_json::Value>,
 }
 
 /// Check if an error is transient and should be retried
@@ -53,28 +56,33 @@ fn is_transient_error(error: &crate::error::PlurcastError) -> bool {
 ///
 /// * `platform` - Reference to the platform to post to
 /// * `post` - The Post object containing content and metadata
+/// * `media` - Attachments already uploaded to this platform (may be empty)
 ///
 /// # Returns
 ///
-/// Returns a tuple of (platform_name, post_id) on success, or an error on failure.
+/// Returns a tuple of (platform_name, published_post) on success, or an error on failure.
 ///
 /// # Errors
 ///
 /// Returns the final error if all retry attempts are exhausted or if a permanent error occurs.
-async fn post_with_retry(platform: &dyn Platform, post: &Post) -> Result<(String, String)> {
+async fn post_with_retry(
+    platform: &dyn Platform,
+    post: &Post,
+    media: &[AttachmentUpload],
+) -> Result<(String, PublishedPost)> {
     let max_attempts = 3;
     let platform_name = platform.name().to_string();
 
     for attempt in 1..=max_attempts {
-        match platform.post(post).await {
-            Ok(post_id) => {
+        match platform.post_with_media(post, media).await {
+            Ok(published) => {
                 if attempt > 1 {
                     info!(
                         "Successfully posted to {} on attempt {}",
                         platform_name, attempt
                     );
                 }
-                return Ok((platform_name, post_id));
+                return Ok((platform_name, published));
             }
             Err(e) => {
                 if is_transient_error(&e) && attempt < max_attempts {
@@ -282,18 +290,25 @@ impl MultiPlatformPoster {
             .iter()
             .map(|platform| {
                 let post = post.clone();
+                let db = &self.db;
                 async move {
                     let platform_name = platform.name().to_string();
                     info!("Posting to platform: {}", platform_name);
 
-                    match post_with_retry(*platform, &post).await {
-                        Ok((name, post_id)) => {
-                            info!("Successfully posted to {}: {}", name, post_id);
+                    let result = match upload_attachments(db, *platform, &post.id).await {
+                        Ok(media) => post_with_retry(*platform, &post, &media).await,
+                        Err(e) => Err(e),
+                    };
+
+                    match result {
+                        Ok((name, published)) => {
+                            info!("Successfully posted to {}: {}", name, published.post_id);
                             PostResult {
                                 platform: name,
                                 success: true,
-                                platform_post_id: Some(post_id),
+                                platform_post_id: Some(published.post_id),
                                 error: None,
+                                raw_response: published.raw_response,
                             }
                         }
                         Err(e) => {
@@ -303,6 +318,7 @@ impl MultiPlatformPoster {
                                 success: false,
                                 platform_post_id: None,
                                 error: Some(e.to_string()),
+                                raw_response: None,
                             }
                         }
                     }
@@ -334,7 +350,11 @@ impl MultiPlatformPoster {
                 account_name: "default".to_string(),
             };
 
-            if let Err(e) = self.db.create_post_record(&record).await {
+            if let Err(e) = self
+                .db
+                .create_post_record_with_response(&record, result.raw_response.as_ref())
+                .await
+            {
                 warn!(
                     "Failed to record result for platform {}: {}",
                     result.platform, e
@@ -400,6 +420,20 @@ pub async fn create_platforms(
     config: &Config,
     filter_platforms: Option<&[String]>,
     account: Option<&str>,
+) -> Result<Vec<Box<dyn Platform>>> {
+    create_platforms_scoped(config, filter_platforms, account, CredentialScope::Write).await
+}
+
+/// Create platform instances using credentials of the given scope
+///
+/// Like [`create_platforms`], but consumers that only read (timelines,
+/// notifications) pass [`CredentialScope::Read`] so a read-only token is
+/// used when the account has one.
+pub async fn create_platforms_scoped(
+    config: &Config,
+    filter_platforms: Option<&[String]>,
+    account: Option<&str>,
+    scope: CredentialScope,
 ) -> Result<Vec<Box<dyn Platform>>> {
     let mut platforms: Vec<Box<dyn Platform>> = Vec::new();
 
@@ -422,8 +456,8 @@ pub async fn create_platforms(
             info!("Creating Nostr platform client");
 
             // Determine which account to use
-            let active_account = account_manager.get_active_account("nostr");
-            let account_to_use = account.unwrap_or(active_account.as_str());
+            let resolved_account = account_manager.resolve_account("nostr", account);
+            let account_to_use = resolved_account.as_str();
 
             tracing::debug!("Using account '{}' for Nostr", account_to_use);
 
@@ -512,16 +546,20 @@ pub async fn create_platforms(
             info!("Creating Mastodon platform client");
 
             // Determine which account to use
-            let active_account = account_manager.get_active_account("mastodon");
-            let account_to_use = account.unwrap_or(active_account.as_str());
+            let resolved_account = account_manager.resolve_account("mastodon", account);
+            let account_to_use = resolved_account.as_str();
 
             tracing::debug!("Using account '{}' for Mastodon", account_to_use);
 
             // Try to get credentials from CredentialManager first, then fall back to file
             let token = if let Some(ref cred_mgr) = credential_manager {
                 // Try to retrieve from credential manager with account
-                match cred_mgr.retrieve_account("plurcast.mastodon", "access_token", account_to_use)
-                {
+                match cred_mgr.retrieve_scoped(
+                    "plurcast.mastodon",
+                    "access_token",
+                    account_to_use,
+                    scope,
+                ) {
                     Ok(token) => {
                         tracing::debug!(
                             "Retrieved Mastodon credentials from secure storage for account '{}'",
@@ -530,6 +568,20 @@ pub async fn create_platforms(
                         token
                     }
                     Err(_) => {
+                        if scope == CredentialScope::Write
+                            && cred_mgr.exists_account(
+                                "plurcast.mastodon",
+                                &CredentialScope::Read.key("access_token"),
+                                account_to_use,
+                            )?
+                        {
+                            return Err(PlatformError::Authentication(format!(
+                                "Mastodon account '{}' only has a read-only token. Run 'plur-creds login mastodon --account {}' to grant write access.",
+                                account_to_use, account_to_use
+                            ))
+                            .into());
+                        }
+
                         // Fall back to file reading for backward compatibility
                         tracing::debug!("Mastodon credentials not found in secure storage for account '{}', falling back to file", account_to_use);
                         let token_path = mastodon_config.expand_token_file_path()?;
@@ -576,14 +628,16 @@ pub async fn create_platforms(
                     .to_string()
             };
 
-            // Ensure instance URL has https:// prefix
-            let instance_url = if mastodon_config.instance.starts_with("http://")
-                || mastodon_config.instance.starts_with("https://")
-            {
-                mastodon_config.instance.clone()
-            } else {
-                format!("https://{}", mastodon_config.instance)
-            };
+            // Accounts logged in with `plur-creds login` remember their instance
+            let instance = credential_manager
+                .as_ref()
+                .and_then(|cred_mgr| {
+                    cred_mgr
+                        .retrieve_account("plurcast.mastodon", "instance", account_to_use)
+                        .ok()
+                })
+                .unwrap_or_else(|| mastodon_config.instance.clone());
+            let instance_url = crate::platforms::mastodon::instance_url(&instance);
 
             // Create MastodonClient
             let mut mastodon_client = MastodonClient::new(instance_url, token)?;
@@ -606,8 +660,8 @@ pub async fn create_platforms(
             tracing::debug!("SSB pub servers: {}", ssb_config.pubs.len());
 
             // Determine which account to use
-            let active_account = account_manager.get_active_account("ssb");
-            let account_to_use = account.unwrap_or(active_account.as_str());
+            let resolved_account = account_manager.resolve_account("ssb", account);
+            let account_to_use = resolved_account.as_str();
 
             tracing::info!("Using account '{}' for SSB", account_to_use);
 
@@ -650,6 +704,7 @@ mod tests {
         let config = Config {
             database: DatabaseConfig {
                 path: ":memory:".to_string(),
+                encrypt_content: false,
             },
             credentials: None,
             nostr: None,
@@ -657,6 +712,8 @@ mod tests {
             ssb: None,
             defaults: DefaultsConfig::default(),
             scheduling: None,
+            retention: None,
+            bot: None,
         };
 
         let platforms = create_platforms(&config, None, None).await.unwrap();
@@ -668,6 +725,7 @@ mod tests {
         let config = Config {
             database: DatabaseConfig {
                 path: ":memory:".to_string(),
+                encrypt_content: false,
             },
             credentials: None,
             nostr: Some(NostrConfig {
@@ -675,11 +733,14 @@ mod tests {
                 keys_file: "/nonexistent/nostr.keys".to_string(),
                 relays: vec!["wss://relay.damus.io".to_string()],
                 default_pow_difficulty: None,
+                media_server: None,
             }),
             mastodon: None,
             ssb: None,
             defaults: DefaultsConfig::default(),
             scheduling: None,
+            retention: None,
+            bot: None,
         };
 
         let result = create_platforms(&config, None, None).await;
@@ -698,6 +759,7 @@ mod tests {
         let config = Config {
             database: DatabaseConfig {
                 path: ":memory:".to_string(),
+                encrypt_content: false,
             },
             credentials: None,
             nostr: None,
@@ -709,6 +771,8 @@ mod tests {
             ssb: None,
             defaults: DefaultsConfig::default(),
             scheduling: None,
+            retention: None,
+            bot: None,
         };
 
         let result = create_platforms(&config, None, None).await;
@@ -731,6 +795,7 @@ mod tests {
         let config = Config {
             database: DatabaseConfig {
                 path: ":memory:".to_string(),
+                encrypt_content: false,
             },
             credentials: None,
             nostr: Some(NostrConfig {
@@ -738,11 +803,14 @@ mod tests {
                 keys_file: keys_file.to_str().unwrap().to_string(),
                 relays: vec!["wss://relay.damus.io".to_string()],
                 default_pow_difficulty: None,
+                media_server: None,
             }),
             mastodon: None,
             ssb: None,
             defaults: DefaultsConfig::default(),
             scheduling: None,
+            retention: None,
+            bot: None,
         };
 
         let platforms = create_platforms(&config, None, None).await.unwrap();
@@ -904,31 +972,31 @@ mod tests {
     async fn test_post_with_retry_success_first_attempt() {
         let platform = MockPlatform::new_failing_then_success("test", 1);
         let post = Post::new("Test content".to_string());
-        let result = post_with_retry(&platform, &post).await;
+        let result = post_with_retry(&platform, &post, &[]).await;
 
         assert!(result.is_ok());
-        let (platform_name, post_id) = result.unwrap();
+        let (platform_name, published) = result.unwrap();
         assert_eq!(platform_name, "test");
-        assert_eq!(post_id, "test:mock_post_id");
+        assert_eq!(published.post_id, "test:mock_post_id");
     }
 
     #[tokio::test]
     async fn test_post_with_retry_success_after_retries() {
         let platform = MockPlatform::new_failing_then_success("test", 2);
         let post = Post::new("Test content".to_string());
-        let result = post_with_retry(&platform, &post).await;
+        let result = post_with_retry(&platform, &post, &[]).await;
 
         assert!(result.is_ok());
-        let (platform_name, post_id) = result.unwrap();
+        let (platform_name, published) = result.unwrap();
         assert_eq!(platform_name, "test");
-        assert_eq!(post_id, "test:mock_post_id");
+        assert_eq!(published.post_id, "test:mock_post_id");
     }
 
     #[tokio::test]
     async fn test_post_with_retry_permanent_failure() {
         let platform = MockPlatform::new_permanent_failure("test");
         let post = Post::new("Test content".to_string());
-        let result = post_with_retry(&platform, &post).await;
+        let result = post_with_retry(&platform, &post, &[]).await;
 
         assert!(result.is_err());
         match result {
@@ -947,7 +1015,7 @@ mod tests {
     async fn test_post_with_retry_exhausted_retries() {
         let platform = MockPlatform::new_failing_then_success("test", 10); // More than max attempts
         let post = Post::new("Test content".to_string());
-        let result = post_with_retry(&platform, &post).await;
+        let result = post_with_retry(&platform, &post, &[]).await;
 
         assert!(result.is_err());
 
@@ -1,21 +1,110 @@
 //! Rate limiting for scheduled posts
 //!
 //! Prevents over-posting to platforms by tracking posts per hour window.
+//! Daily and weekly caps are checked against the same hourly windows.
 
+use crate::config::PostCapConfig;
 use crate::error::Result;
 use crate::Database;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone};
 use std::collections::HashMap;
 
 /// Rate limiter for platform posting
 pub struct RateLimiter {
     /// Platform-specific limits (posts per hour)
     limits: HashMap<String, u32>,
+    /// Platform-specific daily/weekly caps
+    caps: HashMap<String, PostCapConfig>,
+}
+
+/// A daily or weekly cap that has been reached
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapHit {
+    /// "daily" or "weekly"
+    pub period: &'static str,
+    /// The configured cap
+    pub limit: u32,
+    /// When the period ends and posting is allowed again (Unix timestamp)
+    pub resets_at: i64,
 }
 
 impl RateLimiter {
     /// Create a new rate limiter with the given limits
     pub fn new(limits: HashMap<String, u32>) -> Self {
-        Self { limits }
+        Self {
+            limits,
+            caps: HashMap::new(),
+        }
+    }
+
+    /// Add daily/weekly caps
+    pub fn with_caps(mut self, caps: HashMap<String, PostCapConfig>) -> Self {
+        self.caps = caps;
+        self
+    }
+
+    /// Check a platform's daily and weekly caps (local time)
+    ///
+    /// Returns the cap that has been reached, if any; when both have, the
+    /// one that resets later.
+    pub async fn check_caps(
+        &self,
+        db: &Database,
+        platform: &str,
+        now: i64,
+    ) -> Result<Option<CapHit>> {
+        match Local.timestamp_opt(now, 0).single() {
+            Some(now) => self.check_caps_at(db, platform, &now).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn check_caps_at<Tz: TimeZone>(
+        &self,
+        db: &Database,
+        platform: &str,
+        now: &DateTime<Tz>,
+    ) -> Result<Option<CapHit>> {
+        let caps = match self.caps.get(platform) {
+            Some(caps) => caps,
+            None => return Ok(None),
+        };
+
+        let today = now.date_naive();
+        let week_start = today - Duration::days(i64::from(today.weekday().num_days_from_monday()));
+        let periods = [
+            (
+                "weekly",
+                caps.posts_per_week,
+                week_start,
+                week_start + Duration::days(7),
+            ),
+            (
+                "daily",
+                caps.posts_per_day,
+                today,
+                today + Duration::days(1),
+            ),
+        ];
+
+        for (period, limit, start, end) in periods {
+            let limit = match limit {
+                Some(limit) => limit,
+                None => continue,
+            };
+
+            let start = start_of_day(&now.timezone(), start, now.timestamp());
+            let count = get_count_since(db, platform, get_window_start(start)).await?;
+            if count >= limit {
+                return Ok(Some(CapHit {
+                    period,
+                    limit,
+                    resets_at: start_of_day(&now.timezone(), end, now.timestamp()),
+                }));
+            }
+        }
+
+        Ok(None)
     }
 
     /// Check if posting is allowed and record the post
@@ -63,6 +152,16 @@ impl RateLimiter {
     }
 }
 
+/// Midnight at the start of a local date
+///
+/// Falls back to `fallback` if midnight doesn't exist (a DST gap).
+fn start_of_day<Tz: TimeZone>(tz: &Tz, date: NaiveDate, fallback: i64) -> i64 {
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|midnight| tz.from_local_datetime(&midnight).earliest())
+        .map(|dt| dt.timestamp())
+        .unwrap_or(fallback)
+}
+
 /// Get the window start timestamp (floor to hour)
 fn get_window_start(timestamp: i64) -> i64 {
     (timestamp / 3600) * 3600
@@ -87,6 +186,25 @@ async fn get_window_count(db: &Database, platform: &str, window_start: i64) -> R
     Ok(row.and_then(|r| r.0).unwrap_or(0) as u32)
 }
 
+/// Get the post count over all windows starting at or after `since`
+async fn get_count_since(db: &Database, platform: &str, since: i64) -> Result<u32> {
+    use crate::error::DbError;
+
+    let row = sqlx::query_as::<_, (Option<i64>,)>(
+        r#"
+        SELECT SUM(post_count) FROM rate_limits
+        WHERE platform = ? AND window_start >= ?
+        "#,
+    )
+    .bind(platform)
+    .bind(since)
+    .fetch_one(db.pool())
+    .await
+    .map_err(DbError::SqlxError)?;
+
+    Ok(row.0.unwrap_or(0) as u32)
+}
+
 /// Increment the post count for a window
 async fn increment_window_count(db: &Database, platform: &str, window_start: i64) -> Result<()> {
     use crate::error::DbError;
@@ -302,4 +420,101 @@ mod tests {
         let allowed = limiter.check_and_record(&db, "nostr", now).await.unwrap();
         assert!(allowed, "Should allow posting when no limit configured");
     }
+
+    fn capped_limiter(per_day: Option<u32>, per_week: Option<u32>) -> RateLimiter {
+        let mut caps = HashMap::new();
+        caps.insert(
+            "mastodon".to_string(),
+            PostCapConfig {
+                posts_per_day: per_day,
+                posts_per_week: per_week,
+            },
+        );
+        RateLimiter::new(HashMap::new()).with_caps(caps)
+    }
+
+    #[tokio::test]
+    async fn test_daily_cap() {
+        let (_temp, db) = setup_test_db().await;
+        let limiter = capped_limiter(Some(2), None);
+        // Wednesday 2025-01-15 10:00 UTC
+        let now = chrono::Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap();
+
+        // Yesterday's posts don't count
+        limiter
+            .record(&db, "mastodon", now.timestamp() - 86400)
+            .await
+            .unwrap();
+        for _ in 0..2 {
+            assert_eq!(
+                limiter.check_caps_at(&db, "mastodon", &now).await.unwrap(),
+                None
+            );
+            limiter
+                .record(&db, "mastodon", now.timestamp())
+                .await
+                .unwrap();
+        }
+
+        let hit = limiter
+            .check_caps_at(&db, "mastodon", &now)
+            .await
+            .unwrap()
+            .expect("daily cap should be reached");
+        assert_eq!(hit.period, "daily");
+        assert_eq!(hit.limit, 2);
+        assert_eq!(
+            hit.resets_at,
+            chrono::Utc
+                .with_ymd_and_hms(2025, 1, 16, 0, 0, 0)
+                .unwrap()
+                .timestamp()
+        );
+
+        // Other platforms are unaffected
+        assert_eq!(
+            limiter.check_caps_at(&db, "nostr", &now).await.unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_weekly_cap() {
+        let (_temp, db) = setup_test_db().await;
+        let limiter = capped_limiter(Some(5), Some(3));
+        let monday = chrono::Utc.with_ymd_and_hms(2025, 1, 13, 9, 0, 0).unwrap();
+
+        // One post a day, Monday to Wednesday
+        for day in 0..3 {
+            limiter
+                .record(&db, "mastodon", monday.timestamp() + day * 86400)
+                .await
+                .unwrap();
+        }
+
+        let thursday = monday + Duration::days(3);
+        let hit = limiter
+            .check_caps_at(&db, "mastodon", &thursday)
+            .await
+            .unwrap()
+            .expect("weekly cap should be reached");
+        assert_eq!(hit.period, "weekly");
+        assert_eq!(
+            hit.resets_at,
+            chrono::Utc
+                .with_ymd_and_hms(2025, 1, 20, 0, 0, 0)
+                .unwrap()
+                .timestamp()
+        );
+
+        // Next week starts fresh
+        let next_monday = monday + Duration::days(7);
+        assert_eq!(
+            limiter
+                .check_caps_at(&db, "mastodon", &next_monday)
+                .await
+                .unwrap(),
+            None
+        );
+    }
 }
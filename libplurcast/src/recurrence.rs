@@ -0,0 +1,511 @@
+//! Recurring posts driven by cron expressions
+//!
+//! `plur-post --schedule "cron:0 9 * * MON"` schedules the first occurrence
+//! and records a [`PostSeries`] holding the content, metadata and labels.
+//! Each occurrence is an ordinary scheduled post whose metadata carries the
+//! `series_id`; after plur-send sends one, [`schedule_next`] creates the
+//! next. A series therefore has at most one scheduled occurrence at a time.
+//!
+//! Expressions use the standard five fields (minute, hour, day of month,
+//! month, day of week) and are evaluated in local time. Fields accept `*`,
+//! numbers, ranges (`1-5`), lists (`1,15`), steps (`*/15`, `9-17/2`) and
+//! English month/day names (`JAN`, `MON`). As in cron, when both day of
+//! month and day of week are restricted, a day matching either one fires.
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+
+use crate::error::{PlurcastError, Result};
+use crate::types::{Post, PostSeries, PostStatus};
+use crate::Database;
+
+/// Prefix marking a cron expression in `--schedule`
+pub const CRON_PREFIX: &str = "cron:";
+
+/// How far ahead to look for the next occurrence before giving up
+/// (covers leap days; rules out expressions like "0 0 30 2 *")
+const MAX_SEARCH_DAYS: i64 = 366 * 5;
+
+const MONTH_NAMES: &[&str] = &[
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const DAY_NAMES: &[&str] = &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// A parsed five-field cron expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    /// Parse a cron expression such as `"0 9 * * MON-FRI"`
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if the expression doesn't have five fields or a
+    /// field is out of range.
+    pub fn parse(expression: &str) -> Result<Self> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(PlurcastError::InvalidInput(format!(
+                "Invalid cron expression '{}': expected 5 fields (minute hour day-of-month month day-of-week)",
+                expression
+            )));
+        }
+
+        let field_error = |name: &str, e: String| {
+            PlurcastError::InvalidInput(format!(
+                "Invalid cron expression '{}': {} field {}",
+                expression, name, e
+            ))
+        };
+
+        let minutes = parse_field(fields[0], 0, 59, &[]).map_err(|e| field_error("minute", e))?;
+        let hours = parse_field(fields[1], 0, 23, &[]).map_err(|e| field_error("hour", e))?;
+        let days_of_month =
+            parse_field(fields[2], 1, 31, &[]).map_err(|e| field_error("day-of-month", e))?;
+        let months =
+            parse_field(fields[3], 1, 12, MONTH_NAMES).map_err(|e| field_error("month", e))?;
+        let mut days_of_week =
+            parse_field(fields[4], 0, 7, DAY_NAMES).map_err(|e| field_error("day-of-week", e))?;
+
+        // 7 is Sunday too
+        if days_of_week & (1u64 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1u64 << 7);
+        }
+
+        Ok(Self {
+            expression: fields.join(" "),
+            minutes,
+            hours,
+            days_of_month,
+            months,
+            days_of_week,
+            day_of_month_restricted: fields[2] != "*",
+            day_of_week_restricted: fields[4] != "*",
+        })
+    }
+
+    /// The normalized expression
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// First occurrence strictly after `after`, in the same time zone
+    ///
+    /// Local times skipped by a DST change are skipped; ambiguous ones use
+    /// the earlier instant. Returns `None` if nothing matches within five
+    /// years.
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = after.timezone();
+        let start = after.naive_local() + Duration::minutes(1);
+        let start_date = start.date();
+
+        for day in 0..MAX_SEARCH_DAYS {
+            let date = start_date + Duration::days(day);
+            if !self.matches_date(date) {
+                continue;
+            }
+
+            let (first_hour, first_minute) = if day == 0 {
+                (start.hour(), start.minute())
+            } else {
+                (0, 0)
+            };
+
+            for hour in first_hour..24 {
+                if self.hours & (1u64 << hour) == 0 {
+                    continue;
+                }
+                let minute_from = if hour == first_hour { first_minute } else { 0 };
+                for minute in minute_from..60 {
+                    if self.minutes & (1u64 << minute) == 0 {
+                        continue;
+                    }
+                    let time = NaiveTime::from_hms_opt(hour, minute, 0)?;
+                    if let Some(next) = tz.from_local_datetime(&date.and_time(time)).earliest() {
+                        return Some(next);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if self.months & (1u64 << date.month()) == 0 {
+            return false;
+        }
+
+        let day_of_month = self.days_of_month & (1u64 << date.day()) != 0;
+        let day_of_week = self.days_of_week & (1u64 << date.weekday().num_days_from_sunday()) != 0;
+
+        if self.day_of_month_restricted && self.day_of_week_restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+}
+
+impl std::fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+/// Parse one cron field into a bitmask of allowed values
+fn parse_field(
+    field: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+) -> std::result::Result<u64, String> {
+    let mut mask = 0u64;
+
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| format!("has an invalid step '{}'", step))?;
+                if step == 0 {
+                    return Err("has a step of 0".to_string());
+                }
+                (range, step)
+            }
+            None => (item, 1),
+        };
+
+        let (low, high) = if range == "*" {
+            (min, max)
+        } else if let Some((low, high)) = range.split_once('-') {
+            (
+                parse_value(low, min, max, names)?,
+                parse_value(high, min, max, names)?,
+            )
+        } else {
+            let value = parse_value(range, min, max, names)?;
+            // "5/15" means every 15 starting at 5
+            (value, if step > 1 { max } else { value })
+        };
+
+        if low > high {
+            return Err(format!("has a backwards range '{}'", range));
+        }
+
+        let mut value = low;
+        while value <= high {
+            mask |= 1u64 << value;
+            value += step;
+        }
+    }
+
+    Ok(mask)
+}
+
+/// Parse a single number or name within a field
+fn parse_value(
+    value: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+) -> std::result::Result<u32, String> {
+    let upper = value.to_ascii_uppercase();
+    let parsed = match names.iter().position(|name| *name == upper) {
+        // Month names start at 1, day names at 0
+        Some(index) => index as u32 + min,
+        None => value
+            .parse()
+            .map_err(|_| format!("has an invalid value '{}'", value))?,
+    };
+
+    if parsed < min || parsed > max {
+        return Err(format!(
+            "value {} is out of range ({}-{})",
+            parsed, min, max
+        ));
+    }
+
+    Ok(parsed)
+}
+
+/// Next occurrence of a cron expression after a Unix timestamp, in local time
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if the expression is invalid or never fires.
+pub fn next_occurrence(expression: &str, after: i64) -> Result<i64> {
+    let schedule = CronSchedule::parse(expression)?;
+    let after = DateTime::from_timestamp(after, 0)
+        .unwrap_or_else(Utc::now)
+        .with_timezone(&Local);
+
+    schedule
+        .next_after(&after)
+        .map(|next| next.timestamp())
+        .ok_or_else(|| {
+            PlurcastError::InvalidInput(format!(
+                "Cron expression '{}' has no upcoming occurrence",
+                expression
+            ))
+        })
+}
+
+/// Series ID of a post, if it is an occurrence of a series
+pub fn series_id(post: &Post) -> Option<String> {
+    post.metadata
+        .as_ref()
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .and_then(|v| {
+            v.get("series_id")
+                .and_then(|id| id.as_str())
+                .map(String::from)
+        })
+}
+
+/// Turn a scheduled post into the first occurrence of a new series
+///
+/// The post's content, metadata and labels become the series template.
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if the expression is invalid or the post doesn't
+/// exist, or an error if a database write fails.
+pub async fn start_series(db: &Database, post_id: &str, expression: &str) -> Result<PostSeries> {
+    let schedule = CronSchedule::parse(expression)?;
+    let post = db
+        .get_post(post_id)
+        .await?
+        .ok_or_else(|| PlurcastError::InvalidInput(format!("Post not found: {}", post_id)))?;
+
+    let series = PostSeries {
+        id: uuid::Uuid::new_v4().to_string(),
+        cron: schedule.expression().to_string(),
+        content: post.content.clone(),
+        metadata: post.metadata.clone(),
+        labels: db.get_labels(post_id).await?,
+        paused: false,
+        created_at: chrono::Utc::now().timestamp(),
+    };
+    db.create_series(&series).await?;
+
+    db.update_post_metadata(post_id, &occurrence_metadata(&series))
+        .await?;
+
+    Ok(series)
+}
+
+/// Schedule the next occurrence of a series
+///
+/// Does nothing (and returns `None`) if the series is paused or gone, or
+/// already has a scheduled occurrence. The next occurrence is the first one
+/// after both `after` and the current time, so a daemon that was down
+/// doesn't fire a burst of missed occurrences.
+///
+/// # Errors
+///
+/// Returns an error if the series' expression has no upcoming occurrence or
+/// a database operation fails.
+pub async fn schedule_next(db: &Database, series_id: &str, after: i64) -> Result<Option<Post>> {
+    let series = match db.get_series(series_id).await? {
+        Some(series) if !series.paused => series,
+        _ => return Ok(None),
+    };
+
+    if pending_occurrence(db, series_id).await?.is_some() {
+        return Ok(None);
+    }
+
+    let scheduled_at = next_occurrence(&series.cron, after.max(Utc::now().timestamp()))?;
+
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content: series.content.clone(),
+        created_at: Utc::now().timestamp(),
+        scheduled_at: Some(scheduled_at),
+        status: PostStatus::Scheduled,
+        metadata: Some(occurrence_metadata(&series)),
+    };
+    db.create_post(&post).await?;
+    db.add_labels(&post.id, &series.labels).await?;
+
+    Ok(Some(post))
+}
+
+/// The scheduled occurrence of a series, if there is one
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub async fn pending_occurrence(db: &Database, series_id: &str) -> Result<Option<Post>> {
+    Ok(db
+        .get_scheduled_posts()
+        .await?
+        .into_iter()
+        .find(|post| self::series_id(post).as_deref() == Some(series_id)))
+}
+
+/// Series metadata with the series ID added
+fn occurrence_metadata(series: &PostSeries) -> String {
+    let mut meta = series
+        .metadata
+        .as_ref()
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .filter(|m| m.is_object())
+        .unwrap_or_else(|| serde_json::json!({}));
+    meta["series_id"] = serde_json::json!(series.id);
+    meta.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Weekday;
+    use tempfile::TempDir;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_weekly_expression() {
+        let schedule = CronSchedule::parse("0 9 * * MON").unwrap();
+
+        // Wednesday 2025-01-15 -> Monday 2025-01-20 09:00
+        let next = schedule.next_after(&utc("2025-01-15T12:00:00Z")).unwrap();
+        assert_eq!(next, utc("2025-01-20T09:00:00Z"));
+        assert_eq!(next.weekday(), Weekday::Mon);
+
+        // Exactly at an occurrence -> the following week
+        let after = schedule.next_after(&next).unwrap();
+        assert_eq!(after, utc("2025-01-27T09:00:00Z"));
+    }
+
+    #[test]
+    fn test_steps_ranges_and_lists() {
+        let schedule = CronSchedule::parse("*/15 9-17 * * 1-5").unwrap();
+        let next = schedule.next_after(&utc("2025-01-17T17:50:00Z")).unwrap();
+        // Friday after 17:45 -> Monday 09:00
+        assert_eq!(next, utc("2025-01-20T09:00:00Z"));
+
+        let schedule = CronSchedule::parse("30 8,20 1 JAN,jul *").unwrap();
+        let next = schedule.next_after(&utc("2025-01-01T09:00:00Z")).unwrap();
+        assert_eq!(next, utc("2025-01-01T20:30:00Z"));
+        let next = schedule.next_after(&next).unwrap();
+        assert_eq!(next, utc("2025-07-01T08:30:00Z"));
+    }
+
+    #[test]
+    fn test_day_of_month_or_day_of_week() {
+        // The 1st of the month or any Sunday
+        let schedule = CronSchedule::parse("0 12 1 * 0").unwrap();
+        let next = schedule.next_after(&utc("2025-01-02T00:00:00Z")).unwrap();
+        assert_eq!(next, utc("2025-01-05T12:00:00Z"));
+
+        // 7 is Sunday as well
+        let sunday = CronSchedule::parse("0 12 * * 7").unwrap();
+        assert_eq!(sunday.next_after(&utc("2025-01-02T00:00:00Z")), Some(next));
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for expression in [
+            "0 9 * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * FUNDAY",
+            "*/0 * * * *",
+            "5-1 * * * *",
+        ] {
+            assert!(
+                matches!(
+                    CronSchedule::parse(expression),
+                    Err(PlurcastError::InvalidInput(_))
+                ),
+                "{} should be rejected",
+                expression
+            );
+        }
+    }
+
+    #[test]
+    fn test_impossible_date_has_no_occurrence() {
+        let schedule = CronSchedule::parse("0 0 30 2 *").unwrap();
+        assert!(schedule.next_after(&utc("2025-01-01T00:00:00Z")).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_series_schedules_one_occurrence_at_a_time() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("series.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+        let first = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Weekly update".to_string(),
+            created_at: Utc::now().timestamp(),
+            scheduled_at: Some(next_occurrence("0 9 * * MON", Utc::now().timestamp()).unwrap()),
+            status: PostStatus::Scheduled,
+            metadata: Some(r#"{"platforms":["nostr"]}"#.to_string()),
+        };
+        db.create_post(&first).await.unwrap();
+        db.add_labels(&first.id, &["series:weekly".to_string()])
+            .await
+            .unwrap();
+
+        let series = start_series(&db, &first.id, "0 9 * * MON").await.unwrap();
+        let stored = db.get_post(&first.id).await.unwrap().unwrap();
+        assert_eq!(series_id(&stored), Some(series.id.clone()));
+
+        // The first occurrence is still pending, so nothing new is scheduled
+        assert!(schedule_next(&db, &series.id, first.scheduled_at.unwrap())
+            .await
+            .unwrap()
+            .is_none());
+
+        // Once it has been sent, the next one follows a week later
+        db.update_post_status(&first.id, PostStatus::Posted)
+            .await
+            .unwrap();
+        let next = schedule_next(&db, &series.id, first.scheduled_at.unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.content, "Weekly update");
+        // A week later, give or take a DST change
+        let gap = next.scheduled_at.unwrap() - first.scheduled_at.unwrap();
+        assert!((gap - 7 * 24 * 3600).abs() <= 3600);
+        assert_eq!(
+            db.get_labels(&next.id).await.unwrap(),
+            vec!["series:weekly".to_string()]
+        );
+        assert_eq!(
+            pending_occurrence(&db, &series.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .id,
+            next.id
+        );
+
+        // Paused series don't schedule anything
+        db.update_post_status(&next.id, PostStatus::Posted)
+            .await
+            .unwrap();
+        db.set_series_paused(&series.id, true).await.unwrap();
+        assert!(schedule_next(&db, &series.id, next.scheduled_at.unwrap())
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
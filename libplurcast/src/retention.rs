@@ -0,0 +1,163 @@
+//! History retention and pruning
+//!
+//! Applies a [`RetentionConfig`] to the local database, removing old posts
+//! that were published successfully. Used by `plur-history prune` and,
+//! when `prune_interval` is configured, periodically by plur-send.
+
+use crate::config::RetentionConfig;
+use crate::error::Result;
+use crate::Database;
+use serde::Serialize;
+
+/// Outcome of applying a retention policy
+#[derive(Debug, Clone, Serialize)]
+pub struct PruneReport {
+    /// Posts created before this Unix timestamp were considered
+    pub cutoff: i64,
+    /// Whether this was a dry run (nothing deleted)
+    pub dry_run: bool,
+    /// IDs of posts that were (or would be) pruned
+    pub post_ids: Vec<String>,
+    /// Number of posts actually deleted (0 on dry run)
+    pub deleted: usize,
+}
+
+/// Compute the cutoff timestamp for a policy
+pub fn cutoff(policy: &RetentionConfig, now: i64) -> i64 {
+    now - i64::from(policy.max_age_days) * 86400
+}
+
+/// Apply a retention policy
+///
+/// With `dry_run` set, reports which posts would be removed without
+/// touching the database.
+///
+/// # Errors
+///
+/// Returns an error if the database query or deletion fails.
+pub async fn prune(db: &Database, policy: &RetentionConfig, dry_run: bool) -> Result<PruneReport> {
+    let cutoff = cutoff(policy, chrono::Utc::now().timestamp());
+    let post_ids = db.find_prunable_posts(cutoff, policy.keep_failures).await?;
+
+    let deleted = if dry_run || post_ids.is_empty() {
+        0
+    } else {
+        db.prune_posts(&post_ids).await?
+    };
+
+    Ok(PruneReport {
+        cutoff,
+        dry_run,
+        post_ids,
+        deleted,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Post, PostRecord, PostStatus};
+    use tempfile::TempDir;
+
+    async fn create_post(
+        db: &Database,
+        age_days: i64,
+        status: PostStatus,
+        success: bool,
+    ) -> String {
+        let created_at = chrono::Utc::now().timestamp() - age_days * 86400;
+        let post = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Retention test".to_string(),
+            created_at,
+            scheduled_at: None,
+            status,
+            metadata: None,
+        };
+        db.create_post(&post).await.unwrap();
+
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: "nostr".to_string(),
+            platform_post_id: success.then(|| "note1abc".to_string()),
+            posted_at: Some(created_at),
+            success,
+            error_message: (!success).then(|| "relay error".to_string()),
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
+
+        post.id
+    }
+
+    async fn setup() -> (TempDir, Database) {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+        (temp_dir, db)
+    }
+
+    #[tokio::test]
+    async fn test_prune_removes_old_successful_posts_only() {
+        let (_temp_dir, db) = setup().await;
+        let old = create_post(&db, 200, PostStatus::Posted, true).await;
+        let recent = create_post(&db, 10, PostStatus::Posted, true).await;
+        let old_failed = create_post(&db, 200, PostStatus::Failed, false).await;
+
+        let report = prune(&db, &RetentionConfig::default(), false)
+            .await
+            .unwrap();
+
+        assert_eq!(report.post_ids, vec![old.clone()]);
+        assert_eq!(report.deleted, 1);
+        assert!(db.get_post(&old).await.unwrap().is_none());
+        assert!(db.get_post_records(&old).await.unwrap().is_empty());
+        assert!(db.get_post(&recent).await.unwrap().is_some());
+        assert!(db.get_post(&old_failed).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_prune_dry_run_deletes_nothing() {
+        let (_temp_dir, db) = setup().await;
+        let old = create_post(&db, 200, PostStatus::Posted, true).await;
+
+        let report = prune(&db, &RetentionConfig::default(), true).await.unwrap();
+
+        assert!(report.dry_run);
+        assert_eq!(report.post_ids, vec![old.clone()]);
+        assert_eq!(report.deleted, 0);
+        assert!(db.get_post(&old).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_prune_can_include_failures() {
+        let (_temp_dir, db) = setup().await;
+        let old_failed = create_post(&db, 200, PostStatus::Failed, false).await;
+        let policy = RetentionConfig {
+            keep_failures: false,
+            ..RetentionConfig::default()
+        };
+
+        let report = prune(&db, &policy, false).await.unwrap();
+
+        assert_eq!(report.deleted, 1);
+        assert!(db.get_post(&old_failed).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prune_never_touches_scheduled_posts() {
+        let (_temp_dir, db) = setup().await;
+        let scheduled = create_post(&db, 400, PostStatus::Scheduled, true).await;
+        let policy = RetentionConfig {
+            keep_failures: false,
+            ..RetentionConfig::default()
+        };
+
+        let report = prune(&db, &policy, false).await.unwrap();
+
+        assert!(report.post_ids.is_empty());
+        assert!(db.get_post(&scheduled).await.unwrap().is_some());
+    }
+}
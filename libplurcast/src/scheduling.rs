@@ -2,13 +2,32 @@
 //!
 //! This module provides parsing of human-readable time formats for scheduling posts.
 
-use crate::{PlurcastError, Result};
-use chrono::{DateTime, Duration, Utc};
+use crate::error::ConfigError;
+use crate::{PlurcastError, Post, Result};
+use chrono::{DateTime, Duration, Local, TimeZone, Timelike, Utc};
 use rand::Rng;
 
 const MIN_RANDOM_SECONDS: i64 = 30;
 const MAX_RANDOM_SECONDS: i64 = 30 * 24 * 3600; // 30 days
 
+/// Schedule string that picks a time from posting history
+pub const BEST_SCHEDULE: &str = "best";
+
+/// How far back posting history is considered for best-time scheduling
+pub const BEST_TIME_HISTORY_DAYS: i64 = 90;
+
+/// Hour of day (local time) used when there is no posting history
+const BEST_TIME_FALLBACK_HOUR: u32 = 9;
+
+/// Minimum lead time so a best-time post is never due immediately
+const BEST_TIME_MIN_LEAD_SECONDS: i64 = 5 * 60;
+
+/// Post metadata key holding a per-post send jitter (seconds)
+pub const JITTER_METADATA_KEY: &str = "jitter";
+
+/// Largest jitter allowed in either direction
+pub const MAX_JITTER_SECONDS: i64 = 6 * 3600; // 6 hours
+
 /// Parse a schedule string into a DateTime
 ///
 /// Supports multiple formats:
@@ -49,7 +68,7 @@ pub fn parse_schedule(input: &str, last_scheduled: Option<i64>) -> Result<DateTi
 }
 
 /// Parse a duration string into a chrono::Duration
-fn parse_duration(input: &str) -> Result<Duration> {
+pub(crate) fn parse_duration(input: &str) -> Result<Duration> {
     // Try humantime for simple formats like "1h", "30m"
     if let Ok(std_duration) = humantime::parse_duration(input) {
         let seconds = std_duration.as_secs() as i64;
@@ -174,6 +193,155 @@ fn generate_random_duration(min: Duration, max: Duration) -> Duration {
     Duration::try_seconds(random_secs).unwrap_or(min)
 }
 
+/// Pick the next good time to post, based on when posts were published before
+///
+/// `history` holds the publish timestamps of earlier posts. The hour of day
+/// (local time) with the most posts wins, ties going to the earlier hour, and
+/// the post is scheduled at the start of the next occurrence of that hour.
+/// Without history the fallback is 09:00 local time.
+pub fn best_time(history: &[i64], now: DateTime<Utc>) -> DateTime<Utc> {
+    best_time_in(history, &now.with_timezone(&Local)).with_timezone(&Utc)
+}
+
+/// Best-time selection in an arbitrary timezone
+fn best_time_in<Tz: TimeZone>(history: &[i64], now: &DateTime<Tz>) -> DateTime<Tz> {
+    let tz = now.timezone();
+    let mut counts = [0usize; 24];
+    for ts in history {
+        if let Some(dt) = DateTime::from_timestamp(*ts, 0) {
+            counts[dt.with_timezone(&tz).hour() as usize] += 1;
+        }
+    }
+
+    // First hour with the highest count; all zero means no usable history
+    let max_count = counts.iter().copied().max().unwrap_or(0);
+    let best_hour = if max_count == 0 {
+        BEST_TIME_FALLBACK_HOUR
+    } else {
+        counts.iter().position(|&c| c == max_count).unwrap_or(0) as u32
+    };
+
+    let earliest = now.clone() + Duration::seconds(BEST_TIME_MIN_LEAD_SECONDS);
+    let today = now.date_naive();
+    for day in 0..3 {
+        let candidate = (today + Duration::days(day))
+            .and_hms_opt(best_hour, 0, 0)
+            .and_then(|naive| tz.from_local_datetime(&naive).earliest());
+        // A candidate can be missing when the hour falls in a DST gap
+        if let Some(candidate) = candidate {
+            if candidate >= earliest {
+                return candidate;
+            }
+        }
+    }
+
+    earliest
+}
+
+/// Parse a jitter duration such as "10m"
+///
+/// # Errors
+///
+/// Returns an error if the duration can't be parsed or exceeds
+/// [`MAX_JITTER_SECONDS`].
+pub fn parse_jitter(input: &str) -> Result<i64> {
+    let seconds = parse_duration(input)?.num_seconds();
+    if seconds > MAX_JITTER_SECONDS {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Jitter must be at most {} hours",
+            MAX_JITTER_SECONDS / 3600
+        )));
+    }
+    Ok(seconds)
+}
+
+/// Per-post jitter from metadata, if one was set
+pub fn post_jitter(post: &Post) -> Option<i64> {
+    post.metadata
+        .as_ref()
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .and_then(|v| v.get(JITTER_METADATA_KEY).and_then(|j| j.as_i64()))
+}
+
+/// Post metadata with a per-post jitter added
+pub fn with_jitter(metadata: Option<&str>, jitter: i64) -> String {
+    let mut meta = metadata
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .filter(|m| m.is_object())
+        .unwrap_or_else(|| serde_json::json!({}));
+    meta[JITTER_METADATA_KEY] = serde_json::json!(jitter);
+    meta.to_string()
+}
+
+/// Offset in `-jitter..=jitter` seconds for a post
+///
+/// Derived from the post ID (FNV-1a) rather than drawn at random, so every
+/// poll of plur-send agrees on when a post goes out.
+pub fn jitter_offset(post_id: &str, jitter: i64) -> i64 {
+    let jitter = jitter.clamp(0, MAX_JITTER_SECONDS);
+    if jitter == 0 {
+        return 0;
+    }
+
+    let hash = post_id.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    });
+    (hash % (2 * jitter as u64 + 1)) as i64 - jitter
+}
+
+/// When plur-send should send a scheduled post
+///
+/// The scheduled time moved by the post's jitter, or by `default_jitter`
+/// (from `[scheduling] jitter`) when the post doesn't set its own.
+pub fn send_time(post: &Post, default_jitter: i64) -> Option<i64> {
+    let jitter = post_jitter(post).unwrap_or(default_jitter);
+    post.scheduled_at
+        .map(|at| at + jitter_offset(&post.id, jitter))
+}
+
+/// What plur-send does with posts that fell due while it wasn't running
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatchUp {
+    /// Send them as soon as plur-send starts
+    Immediate,
+    /// Send posts less than this many seconds late; hold the rest
+    Within(i64),
+    /// Hold every missed post until it's rescheduled
+    Manual,
+}
+
+impl CatchUp {
+    /// Parse `[scheduling] catch_up`: "immediate", "manual", or a duration
+    /// such as "6h" for the longest a post may be late and still go out
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error for anything else.
+    pub fn parse(input: &str) -> Result<Self> {
+        match input.trim() {
+            "immediate" => Ok(Self::Immediate),
+            "manual" => Ok(Self::Manual),
+            other => match parse_duration(other) {
+                Ok(duration) => Ok(Self::Within(duration.num_seconds())),
+                Err(_) => Err(ConfigError::InvalidValue(format!(
+                    "Invalid catch_up '{}': expected \"immediate\", \"manual\" or a duration such as \"6h\"",
+                    input
+                ))
+                .into()),
+            },
+        }
+    }
+
+    /// Whether a post `late` seconds overdue should still be sent
+    pub fn sends(&self, late: i64) -> bool {
+        match self {
+            Self::Immediate => true,
+            Self::Within(limit) => late < *limit,
+            Self::Manual => false,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -502,4 +670,125 @@ mod tests {
             );
         }
     }
+
+    // BEST TIME TESTS
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_best_time_fallback_without_history() {
+        let now = utc(2025, 3, 10, 7, 30);
+        assert_eq!(best_time_in(&[], &now), utc(2025, 3, 10, 9, 0));
+
+        // Past today's fallback hour: tomorrow
+        let now = utc(2025, 3, 10, 12, 0);
+        assert_eq!(best_time_in(&[], &now), utc(2025, 3, 11, 9, 0));
+    }
+
+    #[test]
+    fn test_best_time_uses_most_common_hour() {
+        let history = [
+            utc(2025, 3, 1, 18, 5).timestamp(),
+            utc(2025, 3, 2, 18, 40).timestamp(),
+            utc(2025, 3, 3, 8, 0).timestamp(),
+        ];
+        let now = utc(2025, 3, 10, 10, 0);
+        assert_eq!(best_time_in(&history, &now), utc(2025, 3, 10, 18, 0));
+    }
+
+    #[test]
+    fn test_best_time_ties_prefer_earlier_hour() {
+        let history = [
+            utc(2025, 3, 1, 20, 0).timestamp(),
+            utc(2025, 3, 2, 7, 0).timestamp(),
+        ];
+        let now = utc(2025, 3, 10, 10, 0);
+        assert_eq!(best_time_in(&history, &now), utc(2025, 3, 11, 7, 0));
+    }
+
+    #[test]
+    fn test_best_time_respects_minimum_lead() {
+        let history = [utc(2025, 3, 1, 18, 0).timestamp()];
+        let now = utc(2025, 3, 10, 17, 58);
+        assert_eq!(
+            best_time_in(&history, &now),
+            utc(2025, 3, 10, 18, 0) + Duration::days(1)
+        );
+    }
+
+    // JITTER TESTS
+
+    fn scheduled_post(id: &str, metadata: Option<String>) -> Post {
+        Post {
+            id: id.to_string(),
+            content: "Test".to_string(),
+            created_at: 0,
+            scheduled_at: Some(1_000_000),
+            status: crate::PostStatus::Scheduled,
+            metadata,
+        }
+    }
+
+    #[test]
+    fn test_jitter_offset_is_stable_and_bounded() {
+        for id in ["a", "post-1", "0b5e1f0c-6f1e-4c83-9d3e-8a2b7d4c1e9f"] {
+            let offset = jitter_offset(id, 600);
+            assert!((-600..=600).contains(&offset));
+            assert_eq!(offset, jitter_offset(id, 600));
+        }
+        assert_eq!(jitter_offset("a", 0), 0);
+    }
+
+    #[test]
+    fn test_jitter_offsets_vary_between_posts() {
+        let offsets: std::collections::HashSet<i64> = (0..20)
+            .map(|i| jitter_offset(&format!("post-{}", i), 600))
+            .collect();
+        assert!(offsets.len() > 1);
+    }
+
+    #[test]
+    fn test_send_time_prefers_post_jitter() {
+        let post = scheduled_post("post-1", Some(with_jitter(None, 0)));
+        assert_eq!(send_time(&post, 600), Some(1_000_000));
+
+        let post = scheduled_post("post-1", None);
+        assert_eq!(
+            send_time(&post, 600),
+            Some(1_000_000 + jitter_offset("post-1", 600))
+        );
+    }
+
+    #[test]
+    fn test_with_jitter_keeps_metadata() {
+        let meta = with_jitter(Some(r#"{"nostr_pow":20}"#), 300);
+        let post = scheduled_post("post-1", Some(meta.clone()));
+        assert_eq!(post_jitter(&post), Some(300));
+        assert!(meta.contains("nostr_pow"));
+    }
+
+    #[test]
+    fn test_parse_jitter() {
+        assert_eq!(parse_jitter("10m").unwrap(), 600);
+        assert!(parse_jitter("7h").is_err());
+        assert!(parse_jitter("soon").is_err());
+    }
+
+    #[test]
+    fn test_parse_catch_up() {
+        assert_eq!(CatchUp::parse("immediate").unwrap(), CatchUp::Immediate);
+        assert_eq!(CatchUp::parse("manual").unwrap(), CatchUp::Manual);
+        assert_eq!(CatchUp::parse("6h").unwrap(), CatchUp::Within(6 * 3600));
+        assert!(CatchUp::parse("sometimes").is_err());
+    }
+
+    #[test]
+    fn test_catch_up_sends() {
+        assert!(CatchUp::Immediate.sends(86400));
+        assert!(CatchUp::Within(3600).sends(1800));
+        assert!(!CatchUp::Within(3600).sends(7200));
+        assert!(!CatchUp::Manual.sends(1));
+    }
 }
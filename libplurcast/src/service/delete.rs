@@ -0,0 +1,424 @@
+//! Delete service for removing posts from the platforms they went to
+//!
+//! A post is named by its Plurcast UUID or by its ID on one platform. Like
+//! `plur-post --reply-to`, a platform ID found in history stands for the
+//! whole post, so its copies on every platform are deleted; an ID that isn't
+//! in history is deleted on its own platform only. Once every copy is gone
+//! the post is marked deleted in history.
+
+use std::sync::Arc;
+
+use crate::error::{PlatformError, PlurcastError};
+use crate::platforms::id_detection::detect_platform_from_id;
+use crate::platforms::Platform;
+use crate::poster::create_platforms;
+use crate::{Config, Database, Result};
+
+/// Reason recorded on the tombstone of posts deleted here
+pub const DELETE_REASON: &str = "deleted";
+
+/// Delete service
+///
+/// Resolves post IDs and deletes the posts on their platforms, recording
+/// the outcome in the database.
+#[derive(Clone)]
+pub struct DeleteService {
+    db: Arc<Database>,
+    config: Arc<Config>,
+}
+
+/// A post resolved for deletion
+#[derive(Debug, Clone)]
+pub struct DeleteTarget {
+    /// The ID as given
+    pub id: String,
+    /// Plurcast post ID, when the post is in history
+    pub post_id: Option<String>,
+    /// Copies of the post on each platform
+    pub copies: Vec<PlatformCopy>,
+}
+
+/// Where a post was published on one platform
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlatformCopy {
+    pub platform: String,
+    pub platform_post_id: String,
+    /// Account the post was sent from
+    pub account: String,
+}
+
+/// What happened to one copy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeleteOutcome {
+    /// The platform deleted the post (or, on Nostr, accepted the request)
+    Deleted,
+    /// The platform can't delete posts (SSB feeds are append-only)
+    Unsupported(String),
+    /// The deletion failed; trying again may work
+    Failed(String),
+}
+
+/// Result of deleting one copy
+#[derive(Debug, Clone)]
+pub struct CopyResult {
+    pub copy: PlatformCopy,
+    pub outcome: DeleteOutcome,
+}
+
+/// Result of deleting a post
+#[derive(Debug, Clone)]
+pub struct DeleteReport {
+    pub target: DeleteTarget,
+    pub results: Vec<CopyResult>,
+    /// Whether the post was marked deleted in history
+    pub marked_deleted: bool,
+}
+
+impl DeleteReport {
+    /// Whether no deletion failed
+    pub fn success(&self) -> bool {
+        !self
+            .results
+            .iter()
+            .any(|r| matches!(r.outcome, DeleteOutcome::Failed(_)))
+    }
+}
+
+/// Whether posts on the platform can be deleted at all
+pub fn supports_deletion(platform: &str) -> bool {
+    platform != "ssb"
+}
+
+impl DeleteService {
+    /// Create a new delete service
+    pub fn new(db: Arc<Database>, config: Arc<Config>) -> Self {
+        Self { db, config }
+    }
+
+    /// Resolve a Plurcast UUID or platform post ID
+    ///
+    /// `account` is the account an ID not found in history is deleted
+    /// from (default: the platform's active account).
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if a UUID isn't in history or the ID matches
+    /// no platform's format.
+    pub async fn resolve(&self, id: &str, account: Option<&str>) -> Result<DeleteTarget> {
+        let id = id.trim();
+
+        if uuid::Uuid::parse_str(id).is_ok() {
+            if self.db.get_post(id).await?.is_none() {
+                return Err(PlurcastError::InvalidInput(format!(
+                    "Post not found: {}",
+                    id
+                )));
+            }
+            return self.target_for_post(id, id).await;
+        }
+
+        let platform = match detect_platform_from_id(id).as_platform_name() {
+            Some(platform) => platform,
+            None => {
+                return Err(PlurcastError::InvalidInput(format!(
+                    "Could not detect platform for ID '{}'. \
+                     Expected formats: note1... (Nostr), numeric ID (Mastodon), \
+                     %...=.sha256 (SSB), or a plurcast UUID.",
+                    id
+                )))
+            }
+        };
+
+        for form in id_forms(platform, id) {
+            if let Some(post_id) = self
+                .db
+                .get_post_id_by_platform_post_id(platform, &form)
+                .await?
+            {
+                tracing::debug!("{} ID {} belongs to post {}", platform, id, post_id);
+                return self.target_for_post(id, &post_id).await;
+            }
+        }
+
+        tracing::debug!("{} ID {} is not in history", platform, id);
+        let account = crate::accounts::AccountManager::new()?.resolve_account(platform, account);
+        Ok(DeleteTarget {
+            id: id.to_string(),
+            post_id: None,
+            copies: vec![PlatformCopy {
+                platform: platform.to_string(),
+                platform_post_id: id.to_string(),
+                account,
+            }],
+        })
+    }
+
+    /// The published copies of a post in history
+    async fn target_for_post(&self, id: &str, post_id: &str) -> Result<DeleteTarget> {
+        // Records come newest first; keep the latest copy per platform
+        let mut copies: Vec<PlatformCopy> = Vec::new();
+        for record in self.db.get_post_records(post_id).await? {
+            let platform_post_id = match record.platform_post_id {
+                Some(platform_post_id) if record.success => platform_post_id,
+                _ => continue,
+            };
+            if copies.iter().any(|c| c.platform == record.platform) {
+                continue;
+            }
+            copies.push(PlatformCopy {
+                platform: record.platform,
+                platform_post_id,
+                account: record.account_name,
+            });
+        }
+
+        Ok(DeleteTarget {
+            id: id.to_string(),
+            post_id: Some(post_id.to_string()),
+            copies,
+        })
+    }
+
+    /// Delete a resolved post on its platforms
+    ///
+    /// With `platforms`, only the copies there are deleted. The post is
+    /// marked deleted in history once no copy is left to delete: every copy
+    /// was deleted or is on a platform that can't delete posts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the outcome can't be recorded; failures on a
+    /// platform are reported in the [`DeleteReport`] instead.
+    pub async fn delete(
+        &self,
+        target: &DeleteTarget,
+        platforms: Option<&[String]>,
+    ) -> Result<DeleteReport> {
+        let mut results = Vec::new();
+        for copy in &target.copies {
+            if platforms.is_some_and(|platforms| !platforms.contains(&copy.platform)) {
+                continue;
+            }
+            let outcome = if supports_deletion(&copy.platform) {
+                self.delete_copy(copy).await
+            } else {
+                DeleteOutcome::Unsupported(format!("{} posts can't be deleted", copy.platform))
+            };
+            results.push(CopyResult {
+                copy: copy.clone(),
+                outcome,
+            });
+        }
+
+        self.finish(target, results).await
+    }
+
+    /// Delete one copy with a client for its platform and account
+    async fn delete_copy(&self, copy: &PlatformCopy) -> DeleteOutcome {
+        let platform = [copy.platform.clone()];
+        match create_platforms(&self.config, Some(&platform), Some(&copy.account)).await {
+            Ok(clients) => match clients.iter().find(|p| p.name() == copy.platform) {
+                Some(client) => delete_with(client.as_ref(), copy).await,
+                None => DeleteOutcome::Failed(format!("{} is not configured", copy.platform)),
+            },
+            Err(e) => DeleteOutcome::Failed(e.to_string()),
+        }
+    }
+
+    /// Record the outcome of deleting a post's copies
+    async fn finish(
+        &self,
+        target: &DeleteTarget,
+        results: Vec<CopyResult>,
+    ) -> Result<DeleteReport> {
+        let every_copy = results.len() == target.copies.len();
+        let none_failed = !results
+            .iter()
+            .any(|r| matches!(r.outcome, DeleteOutcome::Failed(_)));
+
+        let marked_deleted = match target.post_id {
+            Some(ref post_id) if every_copy && none_failed => {
+                self.db
+                    .soft_delete_post(post_id, Some(DELETE_REASON))
+                    .await?
+            }
+            _ => false,
+        };
+
+        Ok(DeleteReport {
+            target: target.clone(),
+            results,
+            marked_deleted,
+        })
+    }
+}
+
+/// Delete a copy with a platform client
+pub async fn delete_with(platform: &dyn Platform, copy: &PlatformCopy) -> DeleteOutcome {
+    match platform.delete(&copy.platform_post_id).await {
+        Ok(()) => {
+            tracing::info!(
+                "Deleted {} from {} ({})",
+                copy.platform_post_id,
+                copy.platform,
+                copy.account
+            );
+            DeleteOutcome::Deleted
+        }
+        Err(PlurcastError::Platform(PlatformError::NotImplemented(message))) => {
+            DeleteOutcome::Unsupported(message)
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Failed to delete {} from {}: {}",
+                copy.platform_post_id,
+                copy.platform,
+                e
+            );
+            DeleteOutcome::Failed(e.to_string())
+        }
+    }
+}
+
+/// Forms a platform ID may be stored under
+///
+/// Nostr notes are recorded as `note1...`, but may be given in hex.
+pub(crate) fn id_forms(platform: &str, id: &str) -> Vec<String> {
+    let mut forms = vec![id.to_string()];
+    if platform == "nostr" {
+        use nostr_sdk::ToBech32;
+        if let Some(bech32) = nostr_sdk::EventId::parse(id)
+            .ok()
+            .and_then(|event_id| event_id.to_bech32().ok())
+        {
+            if bech32 != id {
+                forms.push(bech32);
+            }
+        }
+    }
+    forms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::mock::{MockConfig, MockPlatform};
+    use crate::service::test_support::{create_sent_post, setup_db};
+    use crate::types::PostStatus;
+    use tempfile::TempDir;
+
+    async fn setup() -> (DeleteService, Arc<Database>, TempDir) {
+        let (db, config, temp_dir) = setup_db().await;
+        let service = DeleteService::new(Arc::clone(&db), config);
+        (service, db, temp_dir)
+    }
+
+    #[tokio::test]
+    async fn test_resolve_finds_every_copy() {
+        let (service, db, _temp_dir) = setup().await;
+        let post_id = create_sent_post(&db, &[("mastodon", "110"), ("ssb", "%abc=.sha256")]).await;
+
+        let by_uuid = service.resolve(&post_id, None).await.unwrap();
+        assert_eq!(by_uuid.post_id.as_deref(), Some(post_id.as_str()));
+        assert_eq!(by_uuid.copies.len(), 2);
+
+        // A platform ID stands for the whole post
+        let by_platform_id = service.resolve("110", None).await.unwrap();
+        assert_eq!(by_platform_id.post_id.as_deref(), Some(post_id.as_str()));
+        assert_eq!(by_platform_id.copies.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_unknown_ids() {
+        let (service, _db, _temp_dir) = setup().await;
+
+        let elsewhere = service.resolve("999", Some("work")).await.unwrap();
+        assert_eq!(elsewhere.post_id, None);
+        assert_eq!(
+            elsewhere.copies,
+            vec![PlatformCopy {
+                platform: "mastodon".to_string(),
+                platform_post_id: "999".to_string(),
+                account: "work".to_string(),
+            }]
+        );
+
+        assert!(matches!(
+            service.resolve("not-an-id", None).await,
+            Err(PlurcastError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            service
+                .resolve("00000000-0000-4000-8000-000000000000", None)
+                .await,
+            Err(PlurcastError::InvalidInput(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_delete_marks_post_deleted_when_nothing_is_left() {
+        let (service, db, _temp_dir) = setup().await;
+        let post_id = create_sent_post(&db, &[("ssb", "%abc=.sha256")]).await;
+
+        let target = service.resolve(&post_id, None).await.unwrap();
+        let report = service.delete(&target, None).await.unwrap();
+
+        assert!(report.success());
+        assert!(matches!(
+            report.results[0].outcome,
+            DeleteOutcome::Unsupported(_)
+        ));
+        assert!(report.marked_deleted);
+        let post = db.get_post(&post_id).await.unwrap().unwrap();
+        assert_eq!(post.status, PostStatus::Deleted);
+    }
+
+    #[tokio::test]
+    async fn test_delete_keeps_post_when_copies_remain() {
+        let (service, db, _temp_dir) = setup().await;
+        let post_id = create_sent_post(&db, &[("mastodon", "110"), ("ssb", "%abc=.sha256")]).await;
+
+        let target = service.resolve(&post_id, None).await.unwrap();
+        let report = service
+            .delete(&target, Some(&["ssb".to_string()]))
+            .await
+            .unwrap();
+
+        assert_eq!(report.results.len(), 1);
+        assert!(!report.marked_deleted);
+        let post = db.get_post(&post_id).await.unwrap().unwrap();
+        assert_eq!(post.status, PostStatus::Posted);
+    }
+
+    #[tokio::test]
+    async fn test_delete_with_reports_outcome() {
+        let copy = PlatformCopy {
+            platform: "mock".to_string(),
+            platform_post_id: "status-1".to_string(),
+            account: "default".to_string(),
+        };
+
+        let platform = MockPlatform::success("mock");
+        assert_eq!(delete_with(&platform, &copy).await, DeleteOutcome::Deleted);
+        assert_eq!(platform.deleted_posts(), vec!["status-1".to_string()]);
+
+        let failing = MockPlatform::new(MockConfig {
+            name: "mock".to_string(),
+            delete_succeeds: false,
+            ..Default::default()
+        });
+        assert!(matches!(
+            delete_with(&failing, &copy).await,
+            DeleteOutcome::Failed(_)
+        ));
+    }
+
+    #[test]
+    fn test_id_forms_include_nostr_bech32() {
+        let hex = "4a5d5f14bfbcbd646dc231648e80ee21e65e0779509bece2aebcc54dcd85b2a1";
+        let forms = id_forms("nostr", hex);
+        assert_eq!(forms.len(), 2);
+        assert!(forms[1].starts_with("note1"));
+        assert_eq!(id_forms("mastodon", "110"), vec!["110".to_string()]);
+    }
+}
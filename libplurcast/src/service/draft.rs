@@ -8,7 +8,8 @@ use std::sync::Arc;
 use chrono::{DateTime, Utc};
 
 use super::posting::{PostRequest, PostResponse, PostingService};
-use crate::{Database, Post, PostStatus, Result};
+use crate::media::MediaFile;
+use crate::{Database, Post, PostStatus, Result, Visibility};
 
 /// Draft service
 ///
@@ -197,6 +198,24 @@ impl DraftService {
             reply_to: HashMap::new(), // No threading for draft publishing (could be added later)
             thread_parent_uuid: None, // No scheduled threading for draft publishing
             thread_sequence: None, // Not part of a thread
+            labels: self.db.get_labels(id).await?, // Keep the draft's labels
+            content_warning: None, // Drafts carry no content warning
+            visibility: Visibility::Public, // Drafts are published publicly
+            language: None,  // Drafts carry no language
+            hashtags: vec![], // Drafts carry no hashtags
+            expires_in: None, // Drafts never expire
+            poll: None,      // Drafts carry no poll
+            fail_fast: false, // Post to every platform
+            media: self
+                .db
+                .get_attachments_for_post(id)
+                .await?
+                .into_iter()
+                .map(|attachment| MediaFile {
+                    path: attachment.file_path.into(),
+                    alt_text: attachment.alt_text,
+                })
+                .collect(), // Keep the draft's attachments
         };
 
         // Post via posting service
@@ -226,13 +245,19 @@ mod tests {
         let config = Config {
             database: crate::config::DatabaseConfig {
                 path: db_path.to_str().unwrap().to_string(),
+                encrypt_content: false,
             },
             nostr: None,
             mastodon: None,
             ssb: None,
-            defaults: crate::config::DefaultsConfig { platforms: vec![] },
+            defaults: crate::config::DefaultsConfig {
+                platforms: vec![],
+                language: None,
+            },
             credentials: None,
             scheduling: None,
+            retention: None,
+            bot: None,
         };
 
         let event_bus = EventBus::new(100);
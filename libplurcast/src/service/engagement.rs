@@ -0,0 +1,385 @@
+//! Engagement service for post analytics
+//!
+//! Reads how people reacted to published posts (replies, boosts, likes and
+//! zaps) from the platforms and keeps the latest counts per published copy
+//! in the database. Reports add the copies of each post up, and the posts
+//! up, over the same filters `plur-history` takes.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::credentials::CredentialScope;
+use crate::db::PostWithRecords;
+use crate::error::{PlatformError, PlurcastError};
+use crate::platforms::{Engagement, Platform};
+use crate::poster::create_platforms_scoped;
+use crate::service::history::HistoryQuery;
+use crate::{Config, Database, EngagementRecord, PostStatus, Result};
+
+/// Engagement service
+///
+/// Refreshes engagement counts from the platforms and reports on them.
+#[derive(Clone)]
+pub struct EngagementService {
+    db: Arc<Database>,
+    config: Arc<Config>,
+}
+
+/// Engagement of one post across its platforms
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PostEngagement {
+    pub post_id: String,
+    pub content: String,
+    /// When the post was created (Unix timestamp)
+    pub created_at: i64,
+    /// Counts per platform the post was read on
+    pub platforms: BTreeMap<String, Engagement>,
+    /// Counts of every platform together
+    pub total: Engagement,
+    /// When the counts were last read, if ever
+    pub fetched_at: Option<i64>,
+}
+
+/// Engagement of the posts matching a query
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EngagementReport {
+    /// Every matching post, newest first
+    pub posts: Vec<PostEngagement>,
+    /// Counts of all posts together
+    pub total: Engagement,
+    /// Counts of all posts, per platform
+    pub platforms: BTreeMap<String, Engagement>,
+}
+
+/// A published copy whose engagement couldn't be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefreshFailure {
+    pub platform: String,
+    pub platform_post_id: String,
+    pub error: String,
+}
+
+/// Outcome of refreshing engagement
+#[derive(Debug, Clone, Default)]
+pub struct RefreshReport {
+    /// Copies whose counts were read and saved
+    pub updated: usize,
+    /// Copies that couldn't be read
+    pub failures: Vec<RefreshFailure>,
+}
+
+/// A published copy of a post, to read engagement for or look up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PublishedCopy {
+    pub(crate) post_id: String,
+    pub(crate) platform: String,
+    pub(crate) platform_post_id: String,
+    pub(crate) account: String,
+}
+
+impl EngagementService {
+    /// Create a new engagement service
+    pub fn new(db: Arc<Database>, config: Arc<Config>) -> Self {
+        Self { db, config }
+    }
+
+    /// Read the engagement of the posted posts matching `query` from their
+    /// platforms and save it
+    ///
+    /// Copies are read as the account they were posted from, with
+    /// read-scoped credentials. Platforms without engagement (SSB) and
+    /// platforms that aren't enabled are skipped; errors are collected per
+    /// copy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database can't be read or written, or a
+    /// platform client can't be created.
+    pub async fn refresh(&self, query: &HistoryQuery) -> Result<RefreshReport> {
+        let posts = self.posted(query).await?;
+
+        let mut by_account: BTreeMap<String, Vec<PublishedCopy>> = BTreeMap::new();
+        for copy in posts
+            .iter()
+            .flat_map(|post| copies(post, query))
+            .filter(|copy| supports_engagement(&copy.platform))
+        {
+            by_account
+                .entry(copy.account.clone())
+                .or_default()
+                .push(copy);
+        }
+
+        let mut report = RefreshReport::default();
+        for (account, copies) in by_account {
+            let mut platforms: Vec<String> = copies.iter().map(|c| c.platform.clone()).collect();
+            platforms.sort();
+            platforms.dedup();
+
+            let clients = create_platforms_scoped(
+                &self.config,
+                Some(platforms.as_slice()),
+                Some(&account),
+                CredentialScope::Read,
+            )
+            .await?;
+            let part = refresh_with(&self.db, &clients, &copies).await?;
+            report.updated += part.updated;
+            report.failures.extend(part.failures);
+        }
+
+        Ok(report)
+    }
+
+    /// Add up the saved engagement of the posted posts matching `query`
+    ///
+    /// Posts whose engagement was never read are listed with zero counts
+    /// and no `fetched_at`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database can't be read.
+    pub async fn report(&self, query: &HistoryQuery) -> Result<EngagementReport> {
+        let posts = self.posted(query).await?;
+
+        let mut report = EngagementReport::default();
+        for post in &posts {
+            let mut entry = PostEngagement {
+                post_id: post.post.id.clone(),
+                content: post.post.content.clone(),
+                created_at: post.post.created_at,
+                platforms: BTreeMap::new(),
+                total: Engagement::default(),
+                fetched_at: None,
+            };
+
+            for record in self.db.get_engagement(&post.post.id).await? {
+                if query
+                    .platform
+                    .as_ref()
+                    .is_some_and(|platform| *platform != record.platform)
+                {
+                    continue;
+                }
+                *entry.platforms.entry(record.platform.clone()).or_default() += record.engagement;
+                entry.total += record.engagement;
+                entry.fetched_at = entry.fetched_at.max(Some(record.fetched_at));
+            }
+
+            report.total += entry.total;
+            for (platform, engagement) in &entry.platforms {
+                *report.platforms.entry(platform.clone()).or_default() += *engagement;
+            }
+            report.posts.push(entry);
+        }
+
+        Ok(report)
+    }
+
+    /// Posted posts matching `query`, whatever status it asks for
+    async fn posted(&self, query: &HistoryQuery) -> Result<Vec<PostWithRecords>> {
+        let query = HistoryQuery {
+            status: Some(PostStatus::Posted),
+            ..query.clone()
+        };
+        self.db.query_posts(&query.to_post_query()?).await
+    }
+}
+
+/// Whether engagement can be read on a platform
+///
+/// SSB has no counts to ask for: likes and replies are messages in other
+/// people's feeds.
+pub fn supports_engagement(platform: &str) -> bool {
+    matches!(platform, "nostr" | "mastodon")
+}
+
+/// The latest published copy of a post on each platform `query` allows
+pub(crate) fn copies(post: &PostWithRecords, query: &HistoryQuery) -> Vec<PublishedCopy> {
+    let mut latest: HashMap<&str, &crate::PostRecord> = HashMap::new();
+    for record in &post.records {
+        if !record.success || record.platform_post_id.is_none() {
+            continue;
+        }
+        if query
+            .platform
+            .as_ref()
+            .is_some_and(|platform| *platform != record.platform)
+        {
+            continue;
+        }
+        let newer = latest
+            .get(record.platform.as_str())
+            .is_none_or(|current| record.posted_at > current.posted_at);
+        if newer {
+            latest.insert(&record.platform, record);
+        }
+    }
+
+    let mut copies: Vec<PublishedCopy> = latest
+        .into_values()
+        .filter_map(|record| {
+            Some(PublishedCopy {
+                post_id: post.post.id.clone(),
+                platform: record.platform.clone(),
+                platform_post_id: record.platform_post_id.clone()?,
+                account: record.account_name.clone(),
+            })
+        })
+        .collect();
+    copies.sort_by(|a, b| a.platform.cmp(&b.platform));
+    copies
+}
+
+/// Read the engagement of each copy from its platform client and save it
+async fn refresh_with(
+    db: &Database,
+    platforms: &[Box<dyn Platform>],
+    copies: &[PublishedCopy],
+) -> Result<RefreshReport> {
+    let now = chrono::Utc::now().timestamp();
+    let mut report = RefreshReport::default();
+
+    for copy in copies {
+        let client = match platforms.iter().find(|p| p.name() == copy.platform) {
+            Some(client) => client,
+            None => {
+                tracing::debug!(
+                    "{} is not enabled, skipping {}",
+                    copy.platform,
+                    copy.post_id
+                );
+                continue;
+            }
+        };
+
+        match client.engagement(&copy.platform_post_id).await {
+            Ok(engagement) => {
+                db.save_engagement(&EngagementRecord {
+                    post_id: copy.post_id.clone(),
+                    platform: copy.platform.clone(),
+                    platform_post_id: copy.platform_post_id.clone(),
+                    engagement,
+                    fetched_at: now,
+                })
+                .await?;
+                report.updated += 1;
+            }
+            Err(PlurcastError::Platform(PlatformError::NotImplemented(message))) => {
+                tracing::debug!("Skipping {}: {}", copy.platform, message);
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to read engagement of {} {}: {}",
+                    copy.platform,
+                    copy.platform_post_id,
+                    e
+                );
+                report.failures.push(RefreshFailure {
+                    platform: copy.platform.clone(),
+                    platform_post_id: copy.platform_post_id.clone(),
+                    error: e.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::mock::{MockConfig, MockPlatform};
+    use crate::service::test_support::{create_sent_post_at, setup_db};
+    use crate::types::Post;
+    use tempfile::TempDir;
+
+    async fn setup() -> (EngagementService, Arc<Database>, TempDir) {
+        let (db, config, temp_dir) = setup_db().await;
+        let service = EngagementService::new(Arc::clone(&db), config);
+        (service, db, temp_dir)
+    }
+
+    fn engagement(replies: u64, boosts: u64, likes: u64) -> Engagement {
+        Engagement {
+            replies,
+            boosts,
+            likes,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refresh_and_report() {
+        let (service, db, _temp_dir) = setup().await;
+        let old = create_sent_post_at(&db, 1_000, &[("mastodon", "110")]).await;
+        let new =
+            create_sent_post_at(&db, 2_000, &[("mastodon", "120"), ("nostr", "note1a")]).await;
+
+        let mastodon: Box<dyn Platform> = Box::new(MockPlatform::new(MockConfig {
+            name: "mastodon".to_string(),
+            engagement: HashMap::from([
+                ("110".to_string(), engagement(1, 0, 2)),
+                ("120".to_string(), engagement(2, 3, 4)),
+            ]),
+            ..Default::default()
+        }));
+        // The nostr note isn't on the relays
+        let nostr: Box<dyn Platform> = Box::new(MockPlatform::new(MockConfig {
+            name: "nostr".to_string(),
+            ..Default::default()
+        }));
+
+        let posts = service.posted(&HistoryQuery::default()).await.unwrap();
+        let copies: Vec<PublishedCopy> = posts
+            .iter()
+            .flat_map(|post| copies(post, &HistoryQuery::default()))
+            .collect();
+        assert_eq!(copies.len(), 3);
+
+        let refreshed = refresh_with(&db, &[mastodon, nostr], &copies)
+            .await
+            .unwrap();
+        assert_eq!(refreshed.updated, 2);
+        assert_eq!(refreshed.failures.len(), 1);
+        assert_eq!(refreshed.failures[0].platform_post_id, "note1a");
+
+        let report = service.report(&HistoryQuery::default()).await.unwrap();
+        assert_eq!(report.posts.len(), 2);
+        assert_eq!(report.posts[0].post_id, new);
+        assert_eq!(report.posts[0].total, engagement(2, 3, 4));
+        assert!(report.posts[0].fetched_at.is_some());
+        assert_eq!(report.posts[1].post_id, old);
+        assert_eq!(report.total, engagement(3, 3, 6));
+        assert_eq!(report.platforms["mastodon"], engagement(3, 3, 6));
+        assert!(!report.platforms.contains_key("nostr"));
+
+        // Time windows narrow the posts counted
+        let recent = service
+            .report(&HistoryQuery {
+                since: chrono::DateTime::from_timestamp(1_500, 0),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(recent.posts.len(), 1);
+        assert_eq!(recent.total, engagement(2, 3, 4));
+    }
+
+    #[tokio::test]
+    async fn test_posts_never_read_have_no_counts() {
+        let (service, db, _temp_dir) = setup().await;
+        create_sent_post_at(&db, 1_000, &[("ssb", "%abc=.sha256")]).await;
+        db.create_post(&Post::new("Draft".to_string()))
+            .await
+            .unwrap();
+
+        let report = service.report(&HistoryQuery::default()).await.unwrap();
+        assert_eq!(report.posts.len(), 1);
+        assert_eq!(report.posts[0].fetched_at, None);
+        assert_eq!(report.total, Engagement::default());
+    }
+}
@@ -183,6 +183,12 @@ pub struct PlatformResult {
     pub post_id: Option<String>,
     /// Error message (if failed)
     pub error: Option<String>,
+    /// Raw platform response (if successful and the platform provides one)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_response: Option<serde_json::Value>,
+    /// How each Nostr relay answered (empty for other platforms)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relay_results: Vec<crate::types::RelayResult>,
 }
 
 #[cfg(test)]
@@ -277,12 +283,16 @@ mod tests {
                     success: true,
                     post_id: Some("note1abc".to_string()),
                     error: None,
+                    raw_response: None,
+                    relay_results: vec![],
                 },
                 PlatformResult {
                     platform: "mastodon".to_string(),
                     success: false,
                     post_id: None,
                     error: Some("Rate limited".to_string()),
+                    raw_response: None,
+                    relay_results: vec![],
                 },
             ],
         };
@@ -2,10 +2,14 @@
 //!
 //! This module provides flexible querying and analysis of post history.
 
-use crate::db::PostWithRecords;
-use crate::{Database, PostStatus, Result};
-use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use crate::db::{add_argument, PostQuery, PostWithRecords};
+use crate::error::DbError;
+use crate::{Database, Post, PostStatus, RelayResult, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Datelike, Utc};
+use serde::Serialize;
+use sqlx::sqlite::SqliteArguments;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 /// History service
@@ -26,17 +30,102 @@ pub struct HistoryQuery {
     pub search: Option<String>,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
+    /// Only posts carrying this label
+    pub label: Option<String>,
+    /// Only posts sent from this account, or queued to be sent from it
+    pub account: Option<String>,
+    /// Continue after this cursor (from [`HistoryPage::next_cursor`])
+    pub cursor: Option<String>,
 }
 
-/// Statistics about post history
+impl HistoryQuery {
+    /// The database filter for this query
+    ///
+    /// Unlike [`HistoryService::list_posts`], which returns 20 posts when no
+    /// limit is set, `limit: None` stays unbounded here. Other tools (e.g.
+    /// `plur-export`) filter posts the same way plur-history does through
+    /// this.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` for a malformed cursor.
+    pub fn to_post_query(&self) -> Result<PostQuery> {
+        let before = match self.cursor.as_deref() {
+            Some(cursor) => Some(decode_cursor(cursor)?),
+            None => None,
+        };
+
+        Ok(PostQuery {
+            platform: self.platform.clone(),
+            since: self.since.map(|dt| dt.timestamp()),
+            until: self.until.map(|dt| dt.timestamp()),
+            search: self.search.clone(),
+            label: self.label.clone(),
+            account: self.account.clone(),
+            status: self.status.clone(),
+            before,
+            include_deleted: matches!(self.status, Some(PostStatus::Deleted)),
+            limit: self.limit,
+        })
+    }
+}
+
+/// Parse a `--since`/`--until` date: a Unix timestamp, `YYYY-MM-DD` (midnight
+/// UTC) or RFC 3339
+///
+/// # Errors
+///
+/// Returns `InvalidInput` when the date is in none of these formats.
+pub fn parse_date(date_str: &str) -> Result<DateTime<Utc>> {
+    let invalid = || {
+        crate::error::PlurcastError::InvalidInput(format!(
+            "Invalid date format: {}. Use Unix timestamp or ISO 8601 (YYYY-MM-DD or YYYY-MM-DDTHH:MM:SSZ)",
+            date_str
+        ))
+    };
+
+    if let Ok(timestamp) = date_str.parse::<i64>() {
+        return DateTime::from_timestamp(timestamp, 0).ok_or_else(invalid);
+    }
+
+    DateTime::parse_from_rfc3339(date_str)
+        .map(|dt| dt.with_timezone(&Utc))
+        .or_else(|_| {
+            chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+                .map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc())
+        })
+        .map_err(|_| invalid())
+}
+
+/// One page of history from [`HistoryService::list_page`]
 #[derive(Debug, Clone)]
+pub struct HistoryPage {
+    pub posts: Vec<PostWithRecords>,
+    /// Cursor for the following page; `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Statistics about post history
+///
+/// Days, weeks and hours are in UTC.
+#[derive(Debug, Clone, Serialize)]
 pub struct HistoryStats {
     pub total_posts: usize,
-    pub platform_stats: HashMap<String, PlatformStats>,
+    pub platform_stats: BTreeMap<String, PlatformStats>,
+    /// Posts per local day ("2025-10-01"), oldest first
+    pub per_day: Vec<PeriodCount>,
+    /// Posts per ISO 8601 week ("2025-W40", starting on Monday), oldest first
+    pub per_week: Vec<PeriodCount>,
+    /// Posts per local hour of the day, busiest first
+    pub busiest_hours: Vec<HourCount>,
+    /// Threads: posts with replies of your own
+    pub threads: usize,
+    /// Average number of posts per thread, the first one included
+    pub average_thread_length: f64,
 }
 
 /// Statistics for a single platform
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PlatformStats {
     pub total: usize,
     pub successful: usize,
@@ -44,6 +133,32 @@ pub struct PlatformStats {
     pub success_rate: f64,
 }
 
+/// Number of posts in a day or week
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PeriodCount {
+    pub period: String,
+    pub posts: usize,
+}
+
+/// Number of posts in an hour of the day (0-23, local time)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HourCount {
+    pub hour: u32,
+    pub posts: usize,
+}
+
+/// Where a post sits in a thread of your own posts
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ThreadPosition {
+    /// ID of the thread's first post
+    pub thread_id: String,
+    /// 1 for the first post, 2 for the reply to it, and so on
+    pub part: usize,
+}
+
+/// Most replies followed back to the start of a thread
+const MAX_THREAD_DEPTH: usize = 1000;
+
 impl HistoryService {
     /// Create a new history service
     pub fn new(db: Arc<Database>) -> Self {
@@ -56,16 +171,12 @@ impl HistoryService {
     ///
     /// Returns an error if the database query fails.
     pub async fn list_posts(&self, query: HistoryQuery) -> Result<Vec<PostWithRecords>> {
-        let platform = query.platform.as_deref();
-        let since = query.since.map(|dt| dt.timestamp());
-        let until = query.until.map(|dt| dt.timestamp());
-        let search = query.search.as_deref();
-        let limit = query.limit.unwrap_or(20);
-
-        let mut results = self
-            .db
-            .query_posts_with_records(platform, since, until, search, limit)
-            .await?;
+        let filter = PostQuery {
+            limit: Some(query.limit.unwrap_or(20)),
+            ..query.to_post_query()?
+        };
+
+        let mut results = self.db.query_posts(&filter).await?;
 
         // Apply offset if specified
         if let Some(offset) = query.offset {
@@ -76,14 +187,43 @@ impl HistoryService {
             }
         }
 
-        // Filter by status if specified
-        if let Some(status) = query.status {
-            results.retain(|pwr| matches_status(&pwr.post.status, &status));
-        }
-
         Ok(results)
     }
 
+    /// List one page of posts, newest first
+    ///
+    /// Pages are keyed on the last post seen rather than an offset, so paging
+    /// through a large history stays cheap and doesn't skip or repeat posts
+    /// when new ones arrive. Pass the returned `next_cursor` as
+    /// [`HistoryQuery::cursor`] to get the following page.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` for a malformed cursor, or an error if the
+    /// database query fails.
+    pub async fn list_page(&self, query: HistoryQuery) -> Result<HistoryPage> {
+        let page_size = query.limit.unwrap_or(20);
+
+        // Fetch one extra post to learn whether another page exists
+        let mut posts = self
+            .list_posts(HistoryQuery {
+                limit: Some(page_size + 1),
+                ..query
+            })
+            .await?;
+
+        let next_cursor = if posts.len() > page_size {
+            posts.truncate(page_size);
+            posts
+                .last()
+                .map(|pwr| encode_cursor(pwr.post.created_at, &pwr.post.id))
+        } else {
+            None
+        };
+
+        Ok(HistoryPage { posts, next_cursor })
+    }
+
     /// Get a single post by ID
     ///
     /// # Errors
@@ -101,50 +241,263 @@ impl HistoryService {
         }
     }
 
-    /// Get statistics for posts matching the query
+    /// Get how each Nostr relay answered for a post
+    ///
+    /// Keyed by post record ID, so results from retries or reposts stay
+    /// with the record they belong to.
     ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
-    pub async fn get_stats(&self, query: HistoryQuery) -> Result<HistoryStats> {
-        let posts = self.list_posts(query).await?;
+    pub async fn get_relay_results(&self, post_id: &str) -> Result<HashMap<i64, Vec<RelayResult>>> {
+        self.db.get_relay_results(post_id).await
+    }
 
-        let mut platform_stats: HashMap<String, PlatformStats> = HashMap::new();
-
-        for post_with_records in &posts {
-            for record in &post_with_records.records {
-                let stats =
-                    platform_stats
-                        .entry(record.platform.clone())
-                        .or_insert(PlatformStats {
-                            total: 0,
-                            successful: 0,
-                            failed: 0,
-                            success_rate: 0.0,
-                        });
-
-                stats.total += 1;
-                if record.success {
-                    stats.successful += 1;
-                } else {
-                    stats.failed += 1;
-                }
+    /// Find the thread a post belongs to, if any
+    ///
+    /// A post is in a thread if it replies to another post sent from
+    /// Plurcast (right away via `reply_to`, or as a scheduled thread part via
+    /// `thread_parent_uuid`), or if such a post replies to it. The thread is
+    /// named after its first post, found by following the replies back.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn get_thread_position(&self, post: &Post) -> Result<Option<ThreadPosition>> {
+        let mut thread_id = post.id.clone();
+        let mut current = Some(post.clone());
+        let mut seen = vec![post.id.clone()];
+
+        while let Some(parent_id) = match &current {
+            Some(p) => self.thread_parent(p).await?,
+            None => None,
+        } {
+            if seen.contains(&parent_id) || seen.len() > MAX_THREAD_DEPTH {
+                break;
             }
+            // A parent that's gone still names the thread
+            current = self.db.get_post(&parent_id).await?;
+            thread_id = parent_id.clone();
+            seen.push(parent_id);
+        }
+
+        if seen.len() > 1 {
+            return Ok(Some(ThreadPosition {
+                thread_id,
+                part: seen.len(),
+            }));
         }
+        if self.db.has_thread_replies(&post.id).await? {
+            return Ok(Some(ThreadPosition {
+                thread_id: post.id.clone(),
+                part: 1,
+            }));
+        }
+        Ok(None)
+    }
+
+    /// The post this one replies to, if it was sent from Plurcast
+    async fn thread_parent(&self, post: &Post) -> Result<Option<String>> {
+        let metadata = match post
+            .metadata
+            .as_deref()
+            .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        {
+            Some(metadata) => metadata,
+            None => return Ok(None),
+        };
 
-        // Calculate success rates
-        for stats in platform_stats.values_mut() {
-            if stats.total > 0 {
-                stats.success_rate = (stats.successful as f64 / stats.total as f64) * 100.0;
+        if let Some(parent) = metadata.get("thread_parent_uuid").and_then(|p| p.as_str()) {
+            return Ok(Some(parent.to_string()));
+        }
+        if let Some(reply_to) = metadata.get("reply_to").and_then(|r| r.as_object()) {
+            for (platform, id) in reply_to {
+                if let Some(id) = id.as_str() {
+                    if let Some(parent) = self
+                        .db
+                        .get_post_id_by_platform_post_id(platform, id)
+                        .await?
+                    {
+                        return Ok(Some(parent));
+                    }
+                }
             }
         }
+        Ok(None)
+    }
+
+    /// Get statistics for posts matching the query
+    ///
+    /// Counts are computed in SQL over the posts [`Self::list_posts`] would
+    /// return for the query, except that `limit: None` counts every post
+    /// rather than 20. Success rates count every platform result of those
+    /// posts.
+    ///
+    /// A thread is a post followed by replies to it sent from Plurcast,
+    /// whether they were posted right away (`reply_to`) or scheduled
+    /// (`thread_parent_uuid`).
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` for a malformed cursor, or for `search` on an
+    /// encrypted database (content can't be matched in SQL there), or an
+    /// error if the database query fails.
+    pub async fn get_stats(&self, query: HistoryQuery) -> Result<HistoryStats> {
+        if query.search.is_some() && self.db.is_encrypted() {
+            return Err(crate::error::PlurcastError::InvalidInput(
+                "Statistics can't search encrypted content; leave out the search".to_string(),
+            ));
+        }
+        let filter = query.to_post_query()?;
+        let (scope, arguments) = stats_scope(&filter, query.offset)?;
+        let pool = self.db.pool();
+
+        let (total_posts,) = sqlx::query_as_with::<_, (i64,), _>(
+            &format!("{} SELECT COUNT(*) FROM scope", scope),
+            arguments.clone(),
+        )
+        .fetch_one(pool)
+        .await
+        .map_err(DbError::SqlxError)?;
+
+        let platform_rows = sqlx::query_as_with::<_, (String, i64, i64), _>(
+            &format!(
+                r#"{}
+                SELECT pr.platform, COUNT(*), COALESCE(SUM(pr.success), 0)
+                FROM post_records pr
+                JOIN scope s ON s.id = pr.post_id
+                GROUP BY pr.platform
+                "#,
+                scope
+            ),
+            arguments.clone(),
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(DbError::SqlxError)?;
+
+        let platform_stats = platform_rows
+            .into_iter()
+            .map(|(platform, total, successful)| {
+                let stats = PlatformStats {
+                    total: total as usize,
+                    successful: successful as usize,
+                    failed: (total - successful) as usize,
+                    success_rate: if total > 0 {
+                        (successful as f64 / total as f64) * 100.0
+                    } else {
+                        0.0
+                    },
+                };
+                (platform, stats)
+            })
+            .collect();
+
+        let per_day = self.count_days(&scope, arguments.clone()).await?;
+        let per_week = iso_weeks(&per_day);
+
+        let busiest_hours = sqlx::query_as_with::<_, (i64, i64), _>(
+            &format!(
+                r#"{}
+                SELECT CAST(strftime('%H', created_at, 'unixepoch', 'localtime') AS INTEGER)
+                           AS hour,
+                       COUNT(*) AS posts
+                FROM scope
+                GROUP BY hour
+                ORDER BY posts DESC, hour ASC
+                "#,
+                scope
+            ),
+            arguments.clone(),
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(DbError::SqlxError)?
+        .into_iter()
+        .map(|(hour, posts)| HourCount {
+            hour: hour as u32,
+            posts: posts as usize,
+        })
+        .collect();
+
+        // Each reply's parent, then the parents that aren't replies themselves
+        let (replies, threads) = sqlx::query_as_with::<_, (i64, i64), _>(
+            &format!(
+                r#"{},
+                links AS (
+                    SELECT s.id,
+                        CASE WHEN json_valid(s.metadata) THEN COALESCE(
+                            json_extract(s.metadata, '$.thread_parent_uuid'),
+                            (SELECT pr.post_id
+                             FROM json_each(s.metadata, '$.reply_to') r
+                             JOIN post_records pr
+                               ON pr.platform = r.key AND pr.platform_post_id = r.value
+                             LIMIT 1)
+                        ) END AS parent
+                    FROM scope s
+                ),
+                replies AS (SELECT id, parent FROM links WHERE parent IS NOT NULL)
+                SELECT
+                    (SELECT COUNT(*) FROM replies),
+                    (SELECT COUNT(DISTINCT parent) FROM replies
+                     WHERE parent NOT IN (SELECT id FROM replies))
+                "#,
+                scope
+            ),
+            arguments,
+        )
+        .fetch_one(pool)
+        .await
+        .map_err(DbError::SqlxError)?;
+
+        let average_thread_length = if threads > 0 {
+            (threads + replies) as f64 / threads as f64
+        } else {
+            0.0
+        };
 
         Ok(HistoryStats {
-            total_posts: posts.len(),
+            total_posts: total_posts as usize,
             platform_stats,
+            per_day,
+            per_week,
+            busiest_hours,
+            threads: threads as usize,
+            average_thread_length,
         })
     }
 
+    /// Count the posts on each (local) day, oldest first
+    async fn count_days(
+        &self,
+        scope: &str,
+        arguments: SqliteArguments<'_>,
+    ) -> Result<Vec<PeriodCount>> {
+        let rows = sqlx::query_as_with::<_, (String, i64), _>(
+            &format!(
+                r#"{}
+                SELECT date(created_at, 'unixepoch', 'localtime') AS period, COUNT(*)
+                FROM scope
+                GROUP BY period
+                ORDER BY period ASC
+                "#,
+                scope
+            ),
+            arguments,
+        )
+        .fetch_all(self.db.pool())
+        .await
+        .map_err(DbError::SqlxError)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(period, posts)| PeriodCount {
+                period,
+                posts: posts as usize,
+            })
+            .collect())
+    }
+
     /// Count posts matching the query
     ///
     /// # Errors
@@ -180,22 +533,84 @@ impl HistoryService {
     }
 }
 
-/// Helper function to match post status
-fn matches_status(post_status: &PostStatus, filter_status: &PostStatus) -> bool {
-    matches!(
-        (post_status, filter_status),
-        (PostStatus::Draft, PostStatus::Draft)
-            | (PostStatus::Scheduled, PostStatus::Scheduled)
-            | (PostStatus::Pending, PostStatus::Pending)
-            | (PostStatus::Posted, PostStatus::Posted)
-            | (PostStatus::Failed, PostStatus::Failed)
-    )
+/// Encode a page position as an opaque cursor
+fn encode_cursor(created_at: i64, post_id: &str) -> String {
+    URL_SAFE_NO_PAD.encode(format!("{}:{}", created_at, post_id))
+}
+
+/// Decode a cursor produced by [`encode_cursor`]
+fn decode_cursor(cursor: &str) -> Result<(i64, String)> {
+    let invalid =
+        || crate::error::PlurcastError::InvalidInput(format!("Invalid history cursor: {}", cursor));
+
+    let decoded = URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or_else(invalid)?;
+
+    match decoded.split_once(':') {
+        Some((created_at, post_id)) if !post_id.is_empty() => {
+            let created_at = created_at.parse::<i64>().map_err(|_| invalid())?;
+            Ok((created_at, post_id.to_string()))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Posts counted by [`HistoryService::get_stats`]: those
+/// [`HistoryService::list_posts`] would return, newest first
+///
+/// Returns a `WITH scope AS (...)` clause selecting them, filtered by
+/// [`PostQuery::where_clause`], and its arguments.
+fn stats_scope(filter: &PostQuery, offset: Option<usize>) -> Result<(String, SqliteArguments<'_>)> {
+    let (where_clause, mut arguments) = filter.where_clause(true)?;
+    let scope = format!(
+        r#"
+    WITH scope AS (
+        SELECT DISTINCT p.id, p.created_at, p.metadata
+        FROM posts p
+        LEFT JOIN post_records pr ON p.id = pr.post_id
+        WHERE {}
+        ORDER BY p.created_at DESC, p.id DESC
+        LIMIT ? OFFSET ?
+    )"#,
+        where_clause
+    );
+    // LIMIT -1 is unbounded in SQLite
+    add_argument(
+        &mut arguments,
+        filter.limit.map_or(-1, |limit| limit as i64),
+    )?;
+    add_argument(&mut arguments, offset.unwrap_or(0) as i64)?;
+    Ok((scope, arguments))
+}
+
+/// Group per-day counts into ISO 8601 weeks ("2025-W40"), oldest first
+///
+/// Weeks start on Monday and belong to the year their Thursday is in, so
+/// 2024-12-30 is in 2025-W01.
+fn iso_weeks(per_day: &[PeriodCount]) -> Vec<PeriodCount> {
+    let mut weeks: BTreeMap<String, usize> = BTreeMap::new();
+    for day in per_day {
+        if let Ok(date) = chrono::NaiveDate::parse_from_str(&day.period, "%Y-%m-%d") {
+            let week = date.iso_week();
+            *weeks
+                .entry(format!("{:04}-W{:02}", week.year(), week.week()))
+                .or_default() += day.posts;
+        }
+    }
+    weeks
+        .into_iter()
+        .map(|(period, posts)| PeriodCount { period, posts })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{Post, PostRecord};
+    use chrono::{Local, Timelike};
     use tempfile::TempDir;
 
     async fn setup_test_db() -> (Database, TempDir) {
@@ -308,6 +723,218 @@ mod tests {
         assert_eq!(results.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_list_posts_with_label_filter() {
+        let (db, _temp_dir) = setup_test_db().await;
+        let service = HistoryService::new(Arc::new(db.clone()));
+
+        let launch = create_test_post(&db, "Launch day", PostStatus::Posted).await;
+        let other = create_test_post(&db, "Unrelated", PostStatus::Posted).await;
+        db.add_labels(
+            &launch,
+            &["campaign:launch".to_string(), "series:tips".to_string()],
+        )
+        .await
+        .unwrap();
+        db.add_labels(&other, &["series:tips".to_string()])
+            .await
+            .unwrap();
+
+        let query = HistoryQuery {
+            label: Some("campaign:launch".to_string()),
+            ..Default::default()
+        };
+        let results = service.list_posts(query).await.unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].post.id, launch);
+        assert_eq!(
+            db.get_labels(&launch).await.unwrap(),
+            vec!["campaign:launch".to_string(), "series:tips".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_posts_with_status_filter() {
+        let (db, _temp_dir) = setup_test_db().await;
+        let service = HistoryService::new(Arc::new(db.clone()));
+
+        let failed = create_test_post(&db, "Didn't go out", PostStatus::Failed).await;
+        create_test_record(&db, &failed, "nostr", false).await;
+        // More recent posts don't push the failed one out of the limit
+        for i in 0..3 {
+            let post_id = create_test_post(&db, &format!("Post {}", i), PostStatus::Posted).await;
+            create_test_record(&db, &post_id, "nostr", true).await;
+        }
+        let deleted = create_test_post(&db, "Cancelled", PostStatus::Deleted).await;
+
+        let results = service
+            .list_posts(HistoryQuery {
+                status: Some(PostStatus::Failed),
+                limit: Some(1),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].post.id, failed);
+
+        let results = service
+            .list_posts(HistoryQuery {
+                status: Some(PostStatus::Deleted),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].post.id, deleted);
+    }
+
+    #[tokio::test]
+    async fn test_list_posts_with_account_filter() {
+        let (db, _temp_dir) = setup_test_db().await;
+        let service = HistoryService::new(Arc::new(db.clone()));
+
+        let personal = create_test_post(&db, "Personal", PostStatus::Posted).await;
+        create_test_record(&db, &personal, "nostr", true).await;
+        let work = create_test_post(&db, "Work", PostStatus::Posted).await;
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: work.clone(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("nostr:note456".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "work".to_string(),
+        })
+        .await
+        .unwrap();
+
+        let query = HistoryQuery {
+            account: Some("work".to_string()),
+            ..Default::default()
+        };
+        let results = service.list_posts(query).await.unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].post.id, work);
+        assert_eq!(results[0].records[0].account_name, "work");
+
+        // Posts not sent yet match on the account they were created for
+        let queued = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Work, later".to_string(),
+            created_at: chrono::Utc::now().timestamp() + 1,
+            scheduled_at: Some(chrono::Utc::now().timestamp() + 3600),
+            status: PostStatus::Scheduled,
+            metadata: Some(r#"{"platforms":["nostr"],"account":"work"}"#.to_string()),
+        };
+        db.create_post(&queued).await.unwrap();
+
+        let query = HistoryQuery {
+            account: Some("work".to_string()),
+            ..Default::default()
+        };
+        let results = service.list_posts(query).await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].post.id, queued.id);
+    }
+
+    #[tokio::test]
+    async fn test_list_page_walks_history_with_cursor() {
+        let (db, _temp_dir) = setup_test_db().await;
+        let service = HistoryService::new(Arc::new(db.clone()));
+
+        // Posts created within the same second tie on created_at, so the
+        // cursor has to break ties on the ID
+        let mut ids = Vec::new();
+        for i in 0..5 {
+            let post_id = create_test_post(&db, &format!("Post {}", i), PostStatus::Posted).await;
+            create_test_record(&db, &post_id, "nostr", true).await;
+            ids.push(post_id);
+        }
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = service
+                .list_page(HistoryQuery {
+                    limit: Some(2),
+                    cursor: cursor.clone(),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            assert!(page.posts.len() <= 2);
+            seen.extend(page.posts.into_iter().map(|pwr| pwr.post.id));
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        ids.sort();
+        let mut sorted_seen = seen.clone();
+        sorted_seen.sort();
+        assert_eq!(seen.len(), 5);
+        assert_eq!(sorted_seen, ids);
+    }
+
+    #[tokio::test]
+    async fn test_list_page_rejects_invalid_cursor() {
+        let (db, _temp_dir) = setup_test_db().await;
+        let service = HistoryService::new(Arc::new(db));
+
+        let result = service
+            .list_page(HistoryQuery {
+                cursor: Some("not a cursor".to_string()),
+                ..Default::default()
+            })
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(crate::error::PlurcastError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_date_formats() {
+        assert_eq!(parse_date("1700000000").unwrap().timestamp(), 1_700_000_000);
+        assert_eq!(parse_date("2024-03-01").unwrap().timestamp(), 1_709_251_200);
+        assert_eq!(
+            parse_date("2024-03-01T12:00:00+02:00").unwrap().timestamp(),
+            1_709_287_200
+        );
+        assert!(matches!(
+            parse_date("last tuesday"),
+            Err(crate::error::PlurcastError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_to_post_query_keeps_limit_unbounded() {
+        let query = HistoryQuery {
+            status: Some(PostStatus::Deleted),
+            ..Default::default()
+        };
+        let filter = query.to_post_query().unwrap();
+        assert_eq!(filter.limit, None);
+        assert!(filter.include_deleted);
+    }
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let cursor = encode_cursor(1_700_000_000, "abc-123");
+        assert_eq!(
+            decode_cursor(&cursor).unwrap(),
+            (1_700_000_000, "abc-123".to_string())
+        );
+    }
+
     #[tokio::test]
     async fn test_get_post_existing() {
         let (db, _temp_dir) = setup_test_db().await;
@@ -367,6 +994,240 @@ mod tests {
         assert_eq!(mastodon_stats.success_rate, 50.0);
     }
 
+    #[tokio::test]
+    async fn test_get_stats_periods_hours_and_threads() {
+        let (db, _temp_dir) = setup_test_db().await;
+        let service = HistoryService::new(Arc::new(db.clone()));
+
+        let create = |id: &str, created_at: i64, status: PostStatus, metadata: Option<&str>| Post {
+            id: id.to_string(),
+            content: format!("Post {}", id),
+            created_at,
+            scheduled_at: None,
+            status,
+            metadata: metadata.map(String::from),
+        };
+
+        // A thread of three: a reply sent right away, then a scheduled one
+        db.create_post(&create("a", 1759310100, PostStatus::Posted, None))
+            .await
+            .unwrap();
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: "a".to_string(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("note1a".to_string()),
+            posted_at: Some(1759310100),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
+        db.create_post(&create(
+            "b",
+            1759311900,
+            PostStatus::Posted,
+            Some(r#"{"reply_to":{"nostr":"note1a"}}"#),
+        ))
+        .await
+        .unwrap();
+        db.create_post(&create(
+            "c",
+            1759395900,
+            PostStatus::Posted,
+            Some(r#"{"thread_parent_uuid":"b","thread_sequence":2}"#),
+        ))
+        .await
+        .unwrap();
+        db.create_post(&create("d", 1759932000, PostStatus::Posted, None))
+            .await
+            .unwrap();
+        // Drafts are counted like in the listing; deleted posts aren't
+        db.create_post(&create("e", 1759482000, PostStatus::Draft, None))
+            .await
+            .unwrap();
+        db.create_post(&create("f", 1759482000, PostStatus::Deleted, None))
+            .await
+            .unwrap();
+
+        let stats = service.get_stats(HistoryQuery::default()).await.unwrap();
+
+        // Days and hours are bucketed in local time
+        let local = |ts: i64| {
+            DateTime::from_timestamp(ts, 0)
+                .unwrap()
+                .with_timezone(&Local)
+        };
+        let counted = [1759310100, 1759311900, 1759395900, 1759482000, 1759932000];
+        let mut days: BTreeMap<String, usize> = BTreeMap::new();
+        let mut hours: BTreeMap<u32, usize> = BTreeMap::new();
+        for ts in counted {
+            *days
+                .entry(local(ts).format("%Y-%m-%d").to_string())
+                .or_default() += 1;
+            *hours.entry(local(ts).hour()).or_default() += 1;
+        }
+        let busiest = hours
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+            .unwrap();
+
+        assert_eq!(stats.total_posts, 5);
+        assert_eq!(
+            stats.per_day,
+            days.into_iter()
+                .map(|(period, posts)| PeriodCount { period, posts })
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            stats.per_week,
+            vec![
+                PeriodCount {
+                    period: "2025-W40".to_string(),
+                    posts: 4
+                },
+                PeriodCount {
+                    period: "2025-W41".to_string(),
+                    posts: 1
+                },
+            ]
+        );
+        assert_eq!(
+            stats.busiest_hours[0],
+            HourCount {
+                hour: *busiest.0,
+                posts: *busiest.1
+            }
+        );
+        assert_eq!(stats.threads, 1);
+        assert_eq!(stats.average_thread_length, 3.0);
+
+        // Filters narrow the counted posts
+        let since = chrono::DateTime::from_timestamp(1759400000, 0);
+        let stats = service
+            .get_stats(HistoryQuery {
+                since,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(stats.total_posts, 2);
+        assert_eq!(stats.threads, 0);
+        assert_eq!(stats.average_thread_length, 0.0);
+
+        let stats = service
+            .get_stats(HistoryQuery {
+                status: Some(PostStatus::Posted),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(stats.total_posts, 4);
+
+        // Search, limit and offset pick posts as in the listing
+        let stats = service
+            .get_stats(HistoryQuery {
+                search: Some("post b".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(stats.total_posts, 1);
+
+        let stats = service
+            .get_stats(HistoryQuery {
+                limit: Some(2),
+                offset: Some(1),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(stats.total_posts, 2);
+        let days: Vec<&str> = stats.per_day.iter().map(|d| d.period.as_str()).collect();
+        let expected: Vec<String> = [1759395900, 1759482000]
+            .into_iter()
+            .map(|ts| local(ts).format("%Y-%m-%d").to_string())
+            .collect();
+        assert_eq!(days, expected);
+    }
+
+    #[test]
+    fn test_iso_weeks_cross_years() {
+        let day = |period: &str, posts| PeriodCount {
+            period: period.to_string(),
+            posts,
+        };
+        let weeks = iso_weeks(&[
+            day("2020-12-31", 1),
+            day("2021-01-03", 2),
+            day("2024-12-29", 1),
+            day("2024-12-30", 1),
+            day("2025-01-05", 3),
+        ]);
+        assert_eq!(
+            weeks,
+            vec![day("2020-W53", 3), day("2024-W52", 1), day("2025-W01", 4)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_thread_position() {
+        let (db, _temp_dir) = setup_test_db().await;
+        let service = HistoryService::new(Arc::new(db.clone()));
+
+        let first = create_test_post(&db, "1/3", PostStatus::Posted).await;
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: first.clone(),
+            platform: "mastodon".to_string(),
+            platform_post_id: Some("109001".to_string()),
+            posted_at: Some(chrono::Utc::now().timestamp()),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
+
+        let post = |content: &str, metadata: String| Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: content.to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            scheduled_at: None,
+            status: PostStatus::Posted,
+            metadata: Some(metadata),
+        };
+        let second = post("2/3", r#"{"reply_to":{"mastodon":"109001"}}"#.to_string());
+        db.create_post(&second).await.unwrap();
+        let third = post(
+            "3/3",
+            format!(r#"{{"thread_parent_uuid":"{}"}}"#, second.id),
+        );
+        db.create_post(&third).await.unwrap();
+        let alone = create_test_post(&db, "Standalone", PostStatus::Posted).await;
+
+        let position = |post_id: String| {
+            let service = &service;
+            let db = &db;
+            async move {
+                let post = db.get_post(&post_id).await.unwrap().unwrap();
+                service.get_thread_position(&post).await.unwrap()
+            }
+        };
+
+        let expected = |part| {
+            Some(ThreadPosition {
+                thread_id: first.clone(),
+                part,
+            })
+        };
+        assert_eq!(position(first.clone()).await, expected(1));
+        assert_eq!(position(second.id.clone()).await, expected(2));
+        assert_eq!(position(third.id.clone()).await, expected(3));
+        assert_eq!(position(alone).await, None);
+    }
+
     #[tokio::test]
     async fn test_count_posts() {
         let (db, _temp_dir) = setup_test_db().await;
@@ -11,7 +11,13 @@
 //! - `PostingService`: Multi-platform posting with retry logic
 //! - `HistoryService`: Query and analyze post history
 //! - `DraftService`: Manage draft posts
+//! - `DeleteService`: Delete posts on the platforms they were sent to
+//! - `EngagementService`: Read and report replies, boosts, likes and zaps
+//! - `ReplyService`: Resolve and read the posts replies answer
+//! - `ThreadService`: Post and schedule hand-written threads as a whole
+//! - `TimelineService`: Read home timelines and mentions
 //! - `ValidationService`: Real-time content validation
+//! - `VerifyService`: Check published posts can still be found
 //! - `EventBus`: Progress event distribution
 //!
 //! # Example
@@ -20,6 +26,7 @@
 //! use std::collections::HashMap;
 //! use libplurcast::service::PlurcastService;
 //! use libplurcast::service::posting::PostRequest;
+//! use libplurcast::Visibility;
 //!
 //! # async fn example() -> libplurcast::Result<()> {
 //! let service = PlurcastService::new().await?;
@@ -33,6 +40,15 @@
 //!     nostr_pow: None,
 //!     nostr_21e8: false,
 //!     reply_to: HashMap::new(),
+//!     labels: vec![],
+//!     content_warning: None,
+//!     visibility: Visibility::Public,
+//!     language: None,
+//!     hashtags: vec![],
+//!     expires_in: None,
+//!     poll: None,
+//!     fail_fast: false,
+//!     media: vec![],
 //! };
 //!
 //! let response = service.posting().post(request).await?;
@@ -41,20 +57,35 @@
 //! # }
 //! ```
 
+pub mod delete;
 pub mod draft;
+pub mod engagement;
 pub mod events;
 pub mod history;
 pub mod posting;
+pub mod reply;
+pub mod thread;
+pub mod timeline;
 pub mod validation;
+pub mod verify;
+
+#[cfg(test)]
+mod test_support;
 
 // Re-export commonly used types
 pub use events::PlatformResult;
 
+use self::delete::DeleteService;
 use self::draft::DraftService;
+use self::engagement::EngagementService;
 use self::events::EventBus;
 use self::history::HistoryService;
 use self::posting::PostingService;
+use self::reply::ReplyService;
+use self::thread::ThreadService;
+use self::timeline::TimelineService;
 use self::validation::ValidationService;
+use self::verify::VerifyService;
 use crate::{Config, Database, Result};
 use std::sync::Arc;
 
@@ -94,7 +125,13 @@ pub struct PlurcastService {
     posting: PostingService,
     history: HistoryService,
     draft: DraftService,
+    delete: DeleteService,
+    engagement: EngagementService,
+    reply: ReplyService,
+    thread: ThreadService,
+    timeline: TimelineService,
     validation: ValidationService,
+    verify: VerifyService,
     event_bus: EventBus,
 }
 
@@ -126,15 +163,16 @@ impl PlurcastService {
     /// - Database cannot be initialized
     /// - Database migrations fail
     pub async fn from_config(config: Config) -> Result<Self> {
-        // Initialize shared resources
-        let db_path = crate::config::resolve_db_path(Some(&config.database.path))?;
-        let db_path_str = db_path.to_str().ok_or_else(|| {
-            crate::error::PlurcastError::Config(crate::error::ConfigError::MissingField(
-                "Invalid database path".to_string(),
-            ))
-        })?;
-        let db = Database::new(db_path_str).await?;
+        let db = Database::from_config(&config).await?;
+        Ok(Self::with_database(db, config))
+    }
 
+    /// Create a service on a database that is already open
+    ///
+    /// For callers that open the database themselves, e.g. read-only with
+    /// [`Database::open_read_only`].
+    pub fn with_database(db: Database, config: Config) -> Self {
+        // Initialize shared resources
         let db = Arc::new(db);
         let config = Arc::new(config);
         let event_bus = EventBus::new(100);
@@ -144,15 +182,32 @@ impl PlurcastService {
         let history = HistoryService::new(Arc::clone(&db));
         let validation = ValidationService::new(Arc::clone(&config));
         let draft = DraftService::new(Arc::clone(&db), posting.clone());
+        let delete = DeleteService::new(Arc::clone(&db), Arc::clone(&config));
+        let engagement = EngagementService::new(Arc::clone(&db), Arc::clone(&config));
+        let reply = ReplyService::new(Arc::clone(&db), Arc::clone(&config));
+        let timeline = TimelineService::new(Arc::clone(&db), Arc::clone(&config));
+        let verify = VerifyService::new(Arc::clone(&db), Arc::clone(&config));
+        let thread = ThreadService::new(
+            Arc::clone(&db),
+            posting.clone(),
+            validation.clone(),
+            delete.clone(),
+        );
 
-        Ok(Self {
+        Self {
             db,
             posting,
             history,
             draft,
+            delete,
+            engagement,
+            reply,
+            thread,
+            timeline,
             validation,
+            verify,
             event_bus,
-        })
+        }
     }
 
     /// Access the database directly
@@ -185,6 +240,46 @@ impl PlurcastService {
         &self.draft
     }
 
+    /// Access the delete service
+    ///
+    /// The delete service resolves post IDs and deletes posts on every
+    /// platform they were sent to.
+    pub fn delete(&self) -> &DeleteService {
+        &self.delete
+    }
+
+    /// Access the engagement service
+    ///
+    /// The engagement service reads replies, boosts, likes and zaps of
+    /// published posts and reports on them.
+    pub fn engagement(&self) -> &EngagementService {
+        &self.engagement
+    }
+
+    /// Access the reply service
+    ///
+    /// The reply service resolves the post a reply answers on every
+    /// platform and reads it for context.
+    pub fn reply(&self) -> &ReplyService {
+        &self.reply
+    }
+
+    /// Access the thread service
+    ///
+    /// The thread service posts or schedules a thread's parts as a whole,
+    /// each replying to the one before it.
+    pub fn thread(&self) -> &ThreadService {
+        &self.thread
+    }
+
+    /// Access the timeline service
+    ///
+    /// The timeline service reads home timelines and mentions from the
+    /// platforms.
+    pub fn timeline(&self) -> &TimelineService {
+        &self.timeline
+    }
+
     /// Access the validation service
     ///
     /// The validation service provides real-time content validation against
@@ -193,6 +288,14 @@ impl PlurcastService {
         &self.validation
     }
 
+    /// Access the verify service
+    ///
+    /// The verify service looks published posts up on their platforms and
+    /// records which can no longer be found.
+    pub fn verify(&self) -> &VerifyService {
+        &self.verify
+    }
+
     /// Subscribe to service events
     ///
     /// Returns a receiver that will receive progress events from service
@@ -9,6 +9,7 @@
 //!
 //! ```no_run
 //! use libplurcast::service::{PlurcastService, posting::PostRequest};
+//! use libplurcast::Visibility;
 //! use std::collections::HashMap;
 //!
 //! # async fn example() -> libplurcast::Result<()> {
@@ -23,6 +24,15 @@
 //!     nostr_pow: None,
 //!     nostr_21e8: false,
 //!     reply_to: HashMap::new(),
+//!     labels: vec![],
+//!     content_warning: None,
+//!     visibility: Visibility::Public,
+//!     language: None,
+//!     hashtags: vec![],
+//!     expires_in: None,
+//!     poll: None,
+//!     fail_fast: false,
+//!     media: vec![],
 //! };
 //!
 //! let response = service.posting().post(request).await?;
@@ -58,14 +68,16 @@
 use futures::future::join_all;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::time::sleep;
 use tracing::{info, warn};
 
 use super::events::{Event, EventBus, PlatformResult};
 use crate::error::PlatformError;
-use crate::platforms::Platform;
+use crate::media::{attachment_problem, load_file, upload_attachments, MediaFile};
+use crate::platforms::{Platform, PublishedPost};
 use crate::poster::create_platforms;
+use crate::types::{AttachmentUpload, PostAttempt, Visibility};
 use crate::{Config, Database, Post, PostRecord, PostStatus, Result};
 
 /// Posting service
@@ -93,11 +105,18 @@ pub struct PostingService {
 /// * `reply_to` - Per-platform parent post IDs for threading
 /// * `thread_parent_uuid` - For scheduled threads: UUID of the parent post in the thread chain
 /// * `thread_sequence` - For scheduled threads: position in the thread (0 = root)
+/// * `labels` - User-defined labels stored with the post for filtering history
+/// * `content_warning` - Content warning shown before the post on platforms that support one
+/// * `visibility` - Who can see the post (non-public only on Mastodon)
+/// * `language` - ISO 639-1 code of the language the post is written in
+/// * `hashtags` - Hashtags (without `#`) added per platform when the post is sent
+/// * `media` - Image files to attach, each with optional alt text
 ///
 /// # Example
 ///
 /// ```
 /// use libplurcast::service::posting::PostRequest;
+/// use libplurcast::Visibility;
 /// use std::collections::HashMap;
 ///
 /// let request = PostRequest {
@@ -111,6 +130,15 @@ pub struct PostingService {
 ///     reply_to: HashMap::new(), // Empty for new post, or per-platform IDs for replies
 ///     thread_parent_uuid: None, // For scheduled threads: parent's UUID
 ///     thread_sequence: None,    // For scheduled threads: position (0, 1, 2, ...)
+///     labels: vec![],
+///     content_warning: None,
+///     visibility: Visibility::Public,
+///     language: None,
+///     hashtags: vec![],
+///     expires_in: None,
+///     poll: None,
+///     fail_fast: false,
+///     media: vec![],
 /// };
 /// ```
 #[derive(Debug, Clone)]
@@ -133,6 +161,26 @@ pub struct PostRequest {
     pub thread_parent_uuid: Option<String>,
     /// For scheduled threads: position in the thread (0 = root, 1 = first reply, etc.)
     pub thread_sequence: Option<u32>,
+    /// User-defined labels to attach to the post (e.g. "campaign:launch")
+    pub labels: Vec<String>,
+    /// Content warning shown before the post (Mastodon spoiler text, Nostr NIP-36)
+    pub content_warning: Option<String>,
+    /// Who can see the post; anything but public is Mastodon-only
+    pub visibility: Visibility,
+    /// ISO 639-1 language code, tagged on platforms that support it
+    pub language: Option<String>,
+    /// Hashtags without the `#`: appended to the text on Mastodon and SSB
+    /// (unless already there), `t` tags on Nostr
+    pub hashtags: Vec<String>,
+    /// Seconds after sending that the post expires: a NIP-40 expiration on
+    /// Nostr, a scheduled deletion elsewhere
+    pub expires_in: Option<i64>,
+    /// Poll choices and duration (Mastodon poll, Nostr NIP-88 poll event)
+    pub poll: Option<crate::poll::Poll>,
+    /// Post to one platform at a time and stop at the first failure
+    pub fail_fast: bool,
+    /// Image files to attach, uploaded to each platform when the post is sent
+    pub media: Vec<MediaFile>,
 }
 
 /// Response from posting operation
@@ -147,6 +195,7 @@ pub struct PostRequest {
 ///
 /// ```no_run
 /// # use libplurcast::service::{PlurcastService, posting::PostRequest};
+/// # use libplurcast::Visibility;
 /// # async fn example() -> libplurcast::Result<()> {
 /// # use std::collections::HashMap;
 /// # let service = PlurcastService::new().await?;
@@ -159,6 +208,15 @@ pub struct PostRequest {
 /// #     nostr_pow: None,
 /// #     nostr_21e8: false,
 /// #     reply_to: HashMap::new(),
+/// #     labels: vec![],
+/// #     content_warning: None,
+/// #     visibility: Visibility::Public,
+/// #     language: None,
+/// #     hashtags: vec![],
+/// #     expires_in: None,
+/// #     poll: None,
+/// #     fail_fast: false,
+/// #     media: vec![],
 /// # };
 /// let response = service.posting().post(request).await?;
 ///
@@ -199,6 +257,8 @@ impl PostingService {
     /// Returns an error if the operation fails critically. Individual platform
     /// failures are captured in the response.
     pub async fn post(&self, request: PostRequest) -> Result<PostResponse> {
+        let labels = normalize_labels(&request.labels)?;
+
         // Determine status based on request
         let (status, scheduled_at) = if request.draft {
             (PostStatus::Pending, None)
@@ -230,6 +290,36 @@ impl PostingService {
                 meta["thread_sequence"] = serde_json::json!(sequence);
             }
 
+            // Drafts and scheduled posts are sent later under the same account
+            if let Some(ref account) = request.account {
+                meta["account"] = serde_json::json!(account);
+            }
+
+            if let Some(ref cw) = request.content_warning {
+                meta["content_warning"] = serde_json::json!(cw);
+            }
+
+            if request.visibility != Visibility::Public {
+                meta["visibility"] = serde_json::json!(request.visibility);
+            }
+
+            if let Some(ref language) = request.language {
+                meta[crate::language::LANGUAGE_METADATA_KEY] = serde_json::json!(language);
+            }
+
+            if !request.hashtags.is_empty() {
+                meta[crate::hashtags::HASHTAGS_METADATA_KEY] =
+                    serde_json::json!(crate::hashtags::dedup(&request.hashtags));
+            }
+
+            if let Some(expires_in) = request.expires_in {
+                meta[crate::expiry::EXPIRES_METADATA_KEY] = serde_json::json!(expires_in);
+            }
+
+            if let Some(ref poll) = request.poll {
+                meta[crate::poll::POLL_METADATA_KEY] = serde_json::json!(poll);
+            }
+
             // Add Nostr-specific options
             let has_nostr_options = request.nostr_pow.is_some()
                 || request.nostr_21e8
@@ -280,9 +370,17 @@ impl PostingService {
 
         let post_id = post.id.clone();
 
+        // Read every file before anything is saved, so a bad path leaves no post behind
+        let mut attachments = Vec::with_capacity(request.media.len());
+        for file in &request.media {
+            attachments.push(load_file(&post_id, &file.path, file.alt_text.clone()).await?);
+        }
+
         // Handle draft mode
         if request.draft {
             self.db.create_post(&post).await?;
+            self.db.add_labels(&post_id, &labels).await?;
+            self.save_attachments(&attachments).await?;
             return Ok(PostResponse {
                 post_id,
                 results: vec![],
@@ -293,6 +391,8 @@ impl PostingService {
         // Handle scheduled mode
         if request.scheduled_at.is_some() {
             self.db.create_post(&post).await?;
+            self.db.add_labels(&post_id, &labels).await?;
+            self.save_attachments(&attachments).await?;
             return Ok(PostResponse {
                 post_id,
                 results: vec![],
@@ -311,15 +411,33 @@ impl PostingService {
         let platforms =
             create_platforms(&self.config, Some(&request.platforms), account_ref).await?;
 
+        // Refuse up front rather than post to some platforms and not others
+        let problems: Vec<String> = platforms
+            .iter()
+            .filter_map(|platform| attachment_problem(platform.as_ref(), &attachments))
+            .collect();
+        if !problems.is_empty() {
+            return Err(crate::error::PlurcastError::InvalidInput(format!(
+                "Cannot attach media: {}",
+                problems.join("; ")
+            )));
+        }
+
         // Save post to database
         self.db.create_post(&post).await?;
+        self.db.add_labels(&post_id, &labels).await?;
+        self.save_attachments(&attachments).await?;
 
-        // Post to platforms concurrently
+        // Post to platforms concurrently, or one at a time when failing fast
         let platform_refs: Vec<&dyn Platform> = platforms.iter().map(|p| p.as_ref()).collect();
-        let results = self.post_to_platforms(&post, &platform_refs).await;
+        let results = if request.fail_fast {
+            self.post_to_platforms_in_turn(&post, &platform_refs).await
+        } else {
+            self.post_to_platforms(&post, &platform_refs).await
+        };
 
         // Record results
-        self.record_results(&post, &results).await;
+        self.record_results(&post, &results, account_ref).await;
 
         // Determine overall success
         let overall_success = !results.is_empty() && results.iter().any(|r| r.success);
@@ -344,6 +462,14 @@ impl PostingService {
         })
     }
 
+    /// Store a new post's attachments
+    async fn save_attachments(&self, attachments: &[crate::types::Attachment]) -> Result<()> {
+        for attachment in attachments {
+            self.db.create_attachment(attachment).await?;
+        }
+        Ok(())
+    }
+
     /// Create a draft without posting
     ///
     /// # Errors
@@ -395,7 +521,7 @@ impl PostingService {
         let results = self.post_to_platforms(&post, &platform_refs).await;
 
         // Record results
-        self.record_results(&post, &results).await;
+        self.record_results(&post, &results, account_ref).await;
 
         let overall_success = !results.is_empty() && results.iter().any(|r| r.success);
 
@@ -475,6 +601,14 @@ impl PostingService {
     ) -> Result<PostResponse> {
         let post_id = post.id.clone();
 
+        // Without an explicit account, use the one the post was created under
+        let account = account.or_else(|| {
+            post.metadata
+                .as_deref()
+                .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+                .and_then(|m| m.get("account")?.as_str().map(String::from))
+        });
+
         // If reply_to_override provided, merge into post metadata for threading
         let post = if let Some(ref reply_to) = reply_to_override {
             if !reply_to.is_empty() {
@@ -516,7 +650,7 @@ impl PostingService {
         let results = self.post_to_platforms(&post, &platform_refs).await;
 
         // Record results (this will update status to Posted or Failed)
-        self.record_results(&post, &results).await;
+        self.record_results(&post, &results, account_ref).await;
 
         let overall_success = !results.is_empty() && results.iter().any(|r| r.success);
 
@@ -546,55 +680,94 @@ impl PostingService {
         post: &Post,
         platforms: &[&dyn Platform],
     ) -> Vec<PlatformResult> {
-        // Create futures for each platform
-        let futures: Vec<_> = platforms
-            .iter()
-            .map(|platform| {
-                let post = post.clone();
-                let event_bus = self.event_bus.clone();
-                let platform_name = platform.name().to_string();
-
-                async move {
-                    info!("Posting to platform: {}", platform_name);
-
-                    // Emit progress event
-                    event_bus.emit(Event::PostingProgress {
-                        post_id: post.id.clone(),
-                        platform: platform_name.clone(),
-                        status: "starting".to_string(),
-                    });
+        join_all(
+            platforms
+                .iter()
+                .map(|platform| self.post_to_platform(post, *platform)),
+        )
+        .await
+    }
 
-                    match post_with_retry(*platform, &post).await {
-                        Ok((name, platform_post_id)) => {
-                            info!("Successfully posted to {}: {}", name, platform_post_id);
-                            PlatformResult {
-                                platform: name,
-                                success: true,
-                                post_id: Some(platform_post_id),
-                                error: None,
-                            }
-                        }
-                        Err(e) => {
-                            warn!("Failed to post to {}: {}", platform_name, e);
-                            PlatformResult {
-                                platform: platform_name,
-                                success: false,
-                                post_id: None,
-                                error: Some(e.to_string()),
-                            }
-                        }
-                    }
-                }
-            })
-            .collect();
+    /// Post to platforms one at a time, stopping at the first failure
+    ///
+    /// Platforms after a failed one are not sent to; each gets a failed
+    /// result saying so, so callers still see every requested platform.
+    async fn post_to_platforms_in_turn(
+        &self,
+        post: &Post,
+        platforms: &[&dyn Platform],
+    ) -> Vec<PlatformResult> {
+        let mut results: Vec<PlatformResult> = Vec::with_capacity(platforms.len());
+        for platform in platforms {
+            if let Some(failed) = results.iter().find(|r| !r.success) {
+                results.push(PlatformResult {
+                    platform: platform.name().to_string(),
+                    success: false,
+                    post_id: None,
+                    error: Some(format!(
+                        "Not sent: stopped after posting to {} failed",
+                        failed.platform
+                    )),
+                    raw_response: None,
+                    relay_results: Vec::new(),
+                });
+                continue;
+            }
+            results.push(self.post_to_platform(post, *platform).await);
+        }
+        results
+    }
 
-        // Execute all futures concurrently
-        join_all(futures).await
+    /// Upload a post's attachments to one platform and publish it there
+    async fn post_to_platform(&self, post: &Post, platform: &dyn Platform) -> PlatformResult {
+        let platform_name = platform.name().to_string();
+        info!("Posting to platform: {}", platform_name);
+
+        // Emit progress event
+        self.event_bus.emit(Event::PostingProgress {
+            post_id: post.id.clone(),
+            platform: platform_name.clone(),
+            status: "starting".to_string(),
+        });
+
+        // Upload attachments first; uploads that already succeeded
+        // on an earlier attempt are reused
+        let result = match upload_attachments(&self.db, platform, &post.id).await {
+            Ok(media) => post_with_retry(&self.db, platform, post, &media).await,
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok((name, published)) => {
+                info!("Successfully posted to {}: {}", name, published.post_id);
+                PlatformResult {
+                    platform: name,
+                    success: true,
+                    post_id: Some(published.post_id),
+                    error: None,
+                    raw_response: published.raw_response,
+                    relay_results: published.relay_results,
+                }
+            }
+            Err(e) => {
+                warn!("Failed to post to {}: {}", platform_name, e);
+                PlatformResult {
+                    platform: platform_name,
+                    success: false,
+                    post_id: None,
+                    error: Some(e.to_string()),
+                    raw_response: None,
+                    relay_results: Vec::new(),
+                }
+            }
+        }
     }
 
     /// Record posting results in the database
-    async fn record_results(&self, post: &Post, results: &[PlatformResult]) {
+    async fn record_results(&self, post: &Post, results: &[PlatformResult], account: Option<&str>) {
         let now = chrono::Utc::now().timestamp();
+        let account_manager = crate::accounts::AccountManager::new().ok();
+        let expires_in = crate::expiry::from_metadata(post.metadata.as_deref());
 
         // Record each platform result
         for result in results {
@@ -606,14 +779,54 @@ impl PostingService {
                 posted_at: if result.success { Some(now) } else { None },
                 success: result.success,
                 error_message: result.error.clone(),
-                account_name: "default".to_string(),
+                account_name: match &account_manager {
+                    Some(manager) => manager.resolve_account(&result.platform, account),
+                    None => account.unwrap_or("default").to_string(),
+                },
             };
 
-            if let Err(e) = self.db.create_post_record(&record).await {
-                warn!(
-                    "Failed to record result for platform {}: {}",
-                    result.platform, e
-                );
+            match self
+                .db
+                .create_post_record_with_response(&record, result.raw_response.as_ref())
+                .await
+            {
+                Ok(record_id) => {
+                    if let Err(e) = self
+                        .db
+                        .create_relay_results(record_id, &result.relay_results)
+                        .await
+                    {
+                        warn!("Failed to record relay results for {}: {}", post.id, e);
+                    }
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to record result for platform {}: {}",
+                        result.platform, e
+                    );
+                }
+            }
+
+            // Platforms without native expiry delete expiring posts later
+            if let (Some(expires_in), Some(platform_post_id)) = (expires_in, &result.post_id) {
+                if result.success && !crate::expiry::has_native_expiry(&result.platform) {
+                    if let Err(e) = self
+                        .db
+                        .schedule_deletion(
+                            &post.id,
+                            &result.platform,
+                            platform_post_id,
+                            &record.account_name,
+                            now + expires_in,
+                        )
+                        .await
+                    {
+                        warn!(
+                            "Failed to schedule deletion of {} on {}: {}",
+                            post.id, result.platform, e
+                        );
+                    }
+                }
             }
         }
 
@@ -636,20 +849,49 @@ impl PostingService {
 }
 
 /// Post to a platform with retry logic and exponential backoff
-async fn post_with_retry(platform: &dyn Platform, post: &crate::Post) -> Result<(String, String)> {
+///
+/// Each attempt's timing is recorded in `post_attempts`.
+async fn post_with_retry(
+    db: &Database,
+    platform: &dyn Platform,
+    post: &crate::Post,
+    media: &[AttachmentUpload],
+) -> Result<(String, PublishedPost)> {
     let max_attempts = 3;
     let platform_name = platform.name().to_string();
 
     for attempt in 1..=max_attempts {
-        match platform.post(post).await {
-            Ok(post_id) => {
+        let started_at_ms = chrono::Utc::now().timestamp_millis();
+        let started = Instant::now();
+        let result = platform.post_with_media(post, media).await;
+        let duration_ms = started.elapsed().as_millis() as i64;
+
+        let timing = PostAttempt {
+            post_id: post.id.clone(),
+            platform: platform_name.clone(),
+            attempt,
+            started_at_ms,
+            finished_at_ms: started_at_ms + duration_ms,
+            duration_ms,
+            success: result.is_ok(),
+            error_message: result.as_ref().err().map(|e| e.to_string()),
+        };
+        if let Err(e) = db.create_post_attempt(&timing).await {
+            warn!(
+                "Failed to record {} attempt {} timing: {}",
+                platform_name, attempt, e
+            );
+        }
+
+        match result {
+            Ok(published) => {
                 if attempt > 1 {
                     info!(
                         "Successfully posted to {} on attempt {}",
                         platform_name, attempt
                     );
                 }
-                return Ok((platform_name, post_id));
+                return Ok((platform_name, published));
             }
             Err(e) => {
                 if is_transient_error(&e) && attempt < max_attempts {
@@ -673,6 +915,35 @@ async fn post_with_retry(platform: &dyn Platform, post: &crate::Post) -> Result<
     .into())
 }
 
+/// Trim and validate user-supplied labels
+///
+/// Labels are free-form but must be non-empty and contain no whitespace or
+/// commas, so they round-trip through CLI flags and CSV output. Duplicates
+/// are dropped.
+fn normalize_labels(labels: &[String]) -> Result<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::with_capacity(labels.len());
+
+    for label in labels {
+        let label = label.trim();
+        if label.is_empty() {
+            return Err(crate::error::PlurcastError::InvalidInput(
+                "Labels cannot be empty".to_string(),
+            ));
+        }
+        if label.chars().any(|c| c.is_whitespace() || c == ',') {
+            return Err(crate::error::PlurcastError::InvalidInput(format!(
+                "Invalid label '{}': labels cannot contain spaces or commas",
+                label
+            )));
+        }
+        if !normalized.iter().any(|l| l == label) {
+            normalized.push(label.to_string());
+        }
+    }
+
+    Ok(normalized)
+}
+
 /// Check if an error is transient and should be retried
 fn is_transient_error(error: &crate::error::PlurcastError) -> bool {
     match error {
@@ -701,13 +972,19 @@ mod tests {
         let config = Config {
             database: crate::config::DatabaseConfig {
                 path: db_path.to_str().unwrap().to_string(),
+                encrypt_content: false,
             },
             nostr: None,
             mastodon: None,
             ssb: None,
-            defaults: crate::config::DefaultsConfig { platforms: vec![] },
+            defaults: crate::config::DefaultsConfig {
+                platforms: vec![],
+                language: None,
+            },
             credentials: None,
             scheduling: None,
+            retention: None,
+            bot: None,
         };
 
         let event_bus = EventBus::new(100);
@@ -748,6 +1025,15 @@ mod tests {
             reply_to: HashMap::new(),
             thread_parent_uuid: None,
             thread_sequence: None,
+            labels: vec![],
+            content_warning: None,
+            visibility: Visibility::Public,
+            language: None,
+            hashtags: vec![],
+            expires_in: None,
+            poll: None,
+            fail_fast: false,
+            media: vec![],
         };
 
         let response = service.post(request).await.unwrap();
@@ -760,6 +1046,398 @@ mod tests {
         assert!(post.is_some());
     }
 
+    #[tokio::test]
+    async fn test_draft_remembers_account() {
+        let (service, _temp_dir) = setup_test_service().await;
+
+        let request = PostRequest {
+            content: "Work draft".to_string(),
+            platforms: vec!["nostr".to_string()],
+            draft: true,
+            account: Some("work".to_string()),
+            scheduled_at: None,
+            nostr_pow: None,
+            nostr_21e8: false,
+            reply_to: HashMap::new(),
+            thread_parent_uuid: None,
+            thread_sequence: None,
+            labels: vec![],
+            content_warning: None,
+            visibility: Visibility::Public,
+            language: None,
+            hashtags: vec![],
+            expires_in: None,
+            poll: None,
+            fail_fast: false,
+            media: vec![],
+        };
+
+        let response = service.post(request).await.unwrap();
+
+        let post = service
+            .db
+            .get_post(&response.post_id)
+            .await
+            .unwrap()
+            .unwrap();
+        let metadata: serde_json::Value =
+            serde_json::from_str(post.metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata["account"], "work");
+    }
+
+    #[tokio::test]
+    async fn test_post_stores_labels() {
+        let (service, _temp_dir) = setup_test_service().await;
+
+        let request = PostRequest {
+            content: "Labelled draft".to_string(),
+            platforms: vec!["nostr".to_string()],
+            draft: true,
+            account: None,
+            scheduled_at: None,
+            nostr_pow: None,
+            nostr_21e8: false,
+            reply_to: HashMap::new(),
+            thread_parent_uuid: None,
+            thread_sequence: None,
+            labels: vec![
+                " campaign:launch ".to_string(),
+                "campaign:launch".to_string(),
+            ],
+            content_warning: None,
+            visibility: Visibility::Public,
+            language: None,
+            hashtags: vec![],
+            expires_in: None,
+            poll: None,
+            fail_fast: false,
+            media: vec![],
+        };
+
+        let response = service.post(request).await.unwrap();
+
+        let labels = service.db.get_labels(&response.post_id).await.unwrap();
+        assert_eq!(labels, vec!["campaign:launch".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_post_stores_content_warning() {
+        let (service, _temp_dir) = setup_test_service().await;
+
+        let request = PostRequest {
+            content: "Finale thoughts".to_string(),
+            platforms: vec!["nostr".to_string(), "mastodon".to_string()],
+            draft: true,
+            account: None,
+            scheduled_at: None,
+            nostr_pow: None,
+            nostr_21e8: false,
+            reply_to: HashMap::new(),
+            thread_parent_uuid: None,
+            thread_sequence: None,
+            labels: vec![],
+            content_warning: Some("Spoilers".to_string()),
+            visibility: Visibility::Public,
+            language: None,
+            hashtags: vec![],
+            expires_in: None,
+            poll: None,
+            fail_fast: false,
+            media: vec![],
+        };
+
+        let response = service.post(request).await.unwrap();
+
+        let post = service
+            .db
+            .get_post(&response.post_id)
+            .await
+            .unwrap()
+            .unwrap();
+        let metadata: serde_json::Value =
+            serde_json::from_str(post.metadata.as_ref().unwrap()).unwrap();
+        assert_eq!(metadata["content_warning"], "Spoilers");
+    }
+
+    #[tokio::test]
+    async fn test_post_stores_non_public_visibility() {
+        let (service, _temp_dir) = setup_test_service().await;
+
+        let request = PostRequest {
+            content: "Just for followers".to_string(),
+            platforms: vec!["mastodon".to_string()],
+            draft: true,
+            account: None,
+            scheduled_at: None,
+            nostr_pow: None,
+            nostr_21e8: false,
+            reply_to: HashMap::new(),
+            thread_parent_uuid: None,
+            thread_sequence: None,
+            labels: vec![],
+            content_warning: None,
+            visibility: Visibility::Private,
+            language: None,
+            hashtags: vec![],
+            expires_in: None,
+            poll: None,
+            fail_fast: false,
+            media: vec![],
+        };
+
+        let response = service.post(request).await.unwrap();
+
+        let post = service
+            .db
+            .get_post(&response.post_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            Visibility::from_metadata(post.metadata.as_deref()),
+            Visibility::Private
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expiring_post_schedules_deletion_without_native_expiry() {
+        let (service, _temp_dir) = setup_test_service().await;
+
+        let now = chrono::Utc::now().timestamp();
+        let post = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "Flash sale".to_string(),
+            created_at: now,
+            scheduled_at: None,
+            status: PostStatus::Pending,
+            metadata: Some(r#"{"platforms":["nostr","mastodon"],"expires_in":7200}"#.to_string()),
+        };
+        service.db.create_post(&post).await.unwrap();
+
+        let result = |platform: &str, post_id: &str| PlatformResult {
+            platform: platform.to_string(),
+            success: true,
+            post_id: Some(post_id.to_string()),
+            error: None,
+            raw_response: None,
+            relay_results: Vec::new(),
+        };
+        service
+            .record_results(
+                &post,
+                &[result("nostr", "note1abc"), result("mastodon", "109")],
+                None,
+            )
+            .await;
+
+        // Nostr expires the note itself (NIP-40)
+        let deletions = service.db.get_post_deletions(&post.id).await.unwrap();
+        assert_eq!(deletions.len(), 1);
+        assert_eq!(deletions[0].platform, "mastodon");
+        assert_eq!(deletions[0].platform_post_id, "109");
+        assert!(deletions[0].delete_at >= now + 7200);
+        assert_eq!(deletions[0].deleted_at, None);
+    }
+
+    #[tokio::test]
+    async fn test_post_stores_language() {
+        let (service, _temp_dir) = setup_test_service().await;
+
+        let request = PostRequest {
+            content: "Guten Morgen".to_string(),
+            platforms: vec!["nostr".to_string(), "mastodon".to_string()],
+            draft: true,
+            account: None,
+            scheduled_at: None,
+            nostr_pow: None,
+            nostr_21e8: false,
+            reply_to: HashMap::new(),
+            thread_parent_uuid: None,
+            thread_sequence: None,
+            labels: vec![],
+            content_warning: None,
+            visibility: Visibility::Public,
+            language: Some("de".to_string()),
+            hashtags: vec![],
+            expires_in: None,
+            poll: None,
+            fail_fast: false,
+            media: vec![],
+        };
+
+        let response = service.post(request).await.unwrap();
+
+        let post = service
+            .db
+            .get_post(&response.post_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            crate::language::from_metadata(post.metadata.as_deref()).as_deref(),
+            Some("de")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_post_stores_deduplicated_hashtags() {
+        let (service, _temp_dir) = setup_test_service().await;
+
+        let request = PostRequest {
+            content: "Release day".to_string(),
+            platforms: vec!["nostr".to_string(), "mastodon".to_string()],
+            draft: true,
+            account: None,
+            scheduled_at: None,
+            nostr_pow: None,
+            nostr_21e8: false,
+            reply_to: HashMap::new(),
+            thread_parent_uuid: None,
+            thread_sequence: None,
+            labels: vec![],
+            content_warning: None,
+            visibility: Visibility::Public,
+            language: None,
+            hashtags: vec![
+                "rust".to_string(),
+                "Rust".to_string(),
+                "release".to_string(),
+            ],
+            expires_in: None,
+            poll: None,
+            fail_fast: false,
+            media: vec![],
+        };
+
+        let response = service.post(request).await.unwrap();
+
+        let post = service
+            .db
+            .get_post(&response.post_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(post.content, "Release day");
+        assert_eq!(
+            crate::hashtags::from_metadata(post.metadata.as_deref()),
+            vec!["rust".to_string(), "release".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_post_stores_media_with_scheduled_post() {
+        let (service, temp_dir) = setup_test_service().await;
+        let image = temp_dir.path().join("photo.png");
+        std::fs::write(&image, b"\x89PNG fake image").unwrap();
+
+        let mut request = PostRequest {
+            content: "Look at this".to_string(),
+            platforms: vec!["nostr".to_string()],
+            draft: false,
+            account: None,
+            scheduled_at: Some(chrono::Utc::now().timestamp() + 3600),
+            nostr_pow: None,
+            nostr_21e8: false,
+            reply_to: HashMap::new(),
+            thread_parent_uuid: None,
+            thread_sequence: None,
+            labels: vec![],
+            content_warning: None,
+            visibility: Visibility::Public,
+            language: None,
+            hashtags: vec![],
+            expires_in: None,
+            poll: None,
+            fail_fast: false,
+            media: vec![MediaFile {
+                path: image,
+                alt_text: Some("A photo".to_string()),
+            }],
+        };
+
+        let response = service.post(request.clone()).await.unwrap();
+        let attachments = service
+            .db
+            .get_attachments_for_post(&response.post_id)
+            .await
+            .unwrap();
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].alt_text.as_deref(), Some("A photo"));
+
+        // A missing file fails before any post is saved
+        request.media[0].path = temp_dir.path().join("missing.png");
+        assert!(service.post(request).await.is_err());
+        assert_eq!(service.db.get_scheduled_posts().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_post_with_retry_records_attempt_timing() {
+        let (service, _temp_dir) = setup_test_service().await;
+        let post = Post::new("Timed post".to_string());
+        service.db.create_post(&post).await.unwrap();
+
+        let platform =
+            crate::platforms::mock::MockPlatform::with_delay("mock", Duration::from_millis(20));
+        post_with_retry(&service.db, &platform, &post, &[])
+            .await
+            .unwrap();
+
+        let failing = crate::platforms::mock::MockPlatform::post_failure("broken", "rejected");
+        assert!(post_with_retry(&service.db, &failing, &post, &[])
+            .await
+            .is_err());
+
+        let attempts = service.db.get_post_attempts(&post.id).await.unwrap();
+        assert_eq!(attempts.len(), 2);
+        assert_eq!(attempts[0].platform, "mock");
+        assert!(attempts[0].success);
+        assert!(attempts[0].duration_ms >= 20);
+        assert_eq!(
+            attempts[0].finished_at_ms - attempts[0].started_at_ms,
+            attempts[0].duration_ms
+        );
+        assert_eq!(attempts[1].platform, "broken");
+        assert!(!attempts[1].success);
+        assert!(attempts[1].error_message.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_post_in_turn_stops_at_first_failure() {
+        let (service, _temp_dir) = setup_test_service().await;
+        let post = Post::new("Fail fast".to_string());
+        service.db.create_post(&post).await.unwrap();
+
+        let first = crate::platforms::mock::MockPlatform::new_simple("first");
+        let failing = crate::platforms::mock::MockPlatform::post_failure("broken", "rejected");
+        let last = crate::platforms::mock::MockPlatform::new_simple("last");
+
+        let results = service
+            .post_to_platforms_in_turn(&post, &[&first, &failing, &last])
+            .await;
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].success);
+        assert!(!results[1].success);
+        assert!(!results[2].success);
+        assert_eq!(
+            results[2].error.as_deref(),
+            Some("Not sent: stopped after posting to broken failed")
+        );
+        assert_eq!(first.post_call_count(), 1);
+        assert_eq!(last.post_call_count(), 0);
+    }
+
+    #[test]
+    fn test_normalize_labels_rejects_invalid() {
+        assert!(normalize_labels(&["".to_string()]).is_err());
+        assert!(normalize_labels(&["two words".to_string()]).is_err());
+        assert!(normalize_labels(&["a,b".to_string()]).is_err());
+        assert_eq!(
+            normalize_labels(&["series:tips".to_string()]).unwrap(),
+            vec!["series:tips".to_string()]
+        );
+    }
+
     #[tokio::test]
     async fn test_retry_post_nonexistent() {
         let (service, _temp_dir) = setup_test_service().await;
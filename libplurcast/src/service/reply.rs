@@ -0,0 +1,231 @@
+//! Reply service for answering posts
+//!
+//! A post is named by its Plurcast UUID or by its ID on one platform. As
+//! with `plur-post --reply-to`, a platform ID found in history stands for
+//! the whole post, so the reply goes to every platform the post is on; an
+//! ID that isn't in history is answered on its own platform only. The post
+//! being answered can be read first, from history or from its platform, to
+//! show what the reply is about.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::credentials::CredentialScope;
+use crate::error::PlurcastError;
+use crate::platforms::id_detection::detect_platform_from_id;
+use crate::platforms::TimelineEntry;
+use crate::poster::create_platforms_scoped;
+use crate::service::delete::id_forms;
+use crate::{Config, Database, Post, Result};
+
+/// Reply service
+///
+/// Resolves the post being replied to and reads it for context.
+#[derive(Clone)]
+pub struct ReplyService {
+    db: Arc<Database>,
+    config: Arc<Config>,
+}
+
+/// A post resolved for replying
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplyTarget {
+    /// The ID as given
+    pub id: String,
+    /// Plurcast post, if the post is in history
+    pub post_id: Option<String>,
+    /// Post ID on each platform the post can be replied to on
+    pub reply_to: HashMap<String, String>,
+}
+
+/// What a reply answers
+#[derive(Debug, Clone)]
+pub enum ReplyContext {
+    /// One of your posts, from history
+    Own(Post),
+    /// Someone's post, read from its platform
+    Remote(TimelineEntry),
+}
+
+impl ReplyTarget {
+    /// The platforms the post can be replied to on, sorted
+    pub fn platforms(&self) -> Vec<String> {
+        let mut platforms: Vec<String> = self.reply_to.keys().cloned().collect();
+        platforms.sort();
+        platforms
+    }
+}
+
+impl ReplyService {
+    /// Create a new reply service
+    pub fn new(db: Arc<Database>, config: Arc<Config>) -> Self {
+        Self { db, config }
+    }
+
+    /// Resolve a Plurcast UUID or platform post ID
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if a UUID isn't in history or wasn't posted
+    /// anywhere, or the ID matches no platform's format.
+    pub async fn resolve(&self, id: &str) -> Result<ReplyTarget> {
+        let id = id.trim();
+
+        if uuid::Uuid::parse_str(id).is_ok() {
+            let reply_to = self.db.get_platform_post_ids(id).await?;
+            if reply_to.is_empty() {
+                return Err(PlurcastError::InvalidInput(format!(
+                    "Post ID not found in database or never posted: {}",
+                    id
+                )));
+            }
+            return Ok(ReplyTarget {
+                id: id.to_string(),
+                post_id: Some(id.to_string()),
+                reply_to,
+            });
+        }
+
+        let platform = match detect_platform_from_id(id).as_platform_name() {
+            Some(platform) => platform,
+            None => {
+                return Err(PlurcastError::InvalidInput(format!(
+                    "Could not detect platform for ID '{}'. \
+                     Expected formats: note1... (Nostr), numeric ID (Mastodon), \
+                     %...=.sha256 (SSB), or a plurcast UUID.",
+                    id
+                )))
+            }
+        };
+
+        for form in id_forms(platform, id) {
+            if let Some(post_id) = self
+                .db
+                .get_post_id_by_platform_post_id(platform, &form)
+                .await?
+            {
+                let reply_to = self.db.get_platform_post_ids(&post_id).await?;
+                if !reply_to.is_empty() {
+                    tracing::debug!("{} ID {} belongs to post {}", platform, id, post_id);
+                    return Ok(ReplyTarget {
+                        id: id.to_string(),
+                        post_id: Some(post_id),
+                        reply_to,
+                    });
+                }
+            }
+        }
+
+        tracing::debug!("{} ID {} is not in history", platform, id);
+        Ok(ReplyTarget {
+            id: id.to_string(),
+            post_id: None,
+            reply_to: HashMap::from([(platform.to_string(), id.to_string())]),
+        })
+    }
+
+    /// Read the post a reply answers
+    ///
+    /// Posts in history are read from the database; others are fetched
+    /// from their platform as `account` (default: the active account).
+    /// Returns `None` if the post can't be read, which shouldn't stop the
+    /// reply.
+    pub async fn context(
+        &self,
+        target: &ReplyTarget,
+        account: Option<&str>,
+    ) -> Result<Option<ReplyContext>> {
+        if let Some(ref post_id) = target.post_id {
+            return Ok(self.db.get_post(post_id).await?.map(ReplyContext::Own));
+        }
+
+        for (platform, platform_post_id) in &target.reply_to {
+            let clients = match create_platforms_scoped(
+                &self.config,
+                Some(std::slice::from_ref(platform)),
+                account,
+                CredentialScope::Read,
+            )
+            .await
+            {
+                Ok(clients) => clients,
+                Err(e) => {
+                    tracing::debug!("Can't read {} posts: {}", platform, e);
+                    continue;
+                }
+            };
+            for client in &clients {
+                match client.fetch_post(platform_post_id).await {
+                    Ok(entry) => return Ok(Some(ReplyContext::Remote(entry))),
+                    Err(e) => tracing::debug!(
+                        "Failed to read {} post {}: {}",
+                        platform,
+                        platform_post_id,
+                        e
+                    ),
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::test_support::{create_sent_post, setup_db};
+    use tempfile::TempDir;
+
+    async fn setup() -> (ReplyService, Arc<Database>, TempDir) {
+        let (db, config, temp_dir) = setup_db().await;
+        let service = ReplyService::new(Arc::clone(&db), config);
+        (service, db, temp_dir)
+    }
+
+    #[tokio::test]
+    async fn test_resolve_finds_every_platform() {
+        let (service, db, _temp_dir) = setup().await;
+        let post_id = create_sent_post(&db, &[("mastodon", "110"), ("ssb", "%abc=.sha256")]).await;
+
+        for id in [post_id.as_str(), "110", "%abc=.sha256"] {
+            let target = service.resolve(id).await.unwrap();
+            assert_eq!(target.post_id.as_deref(), Some(post_id.as_str()));
+            assert_eq!(target.platforms(), vec!["mastodon", "ssb"]);
+            assert_eq!(target.reply_to["mastodon"], "110");
+        }
+
+        match service
+            .context(&service.resolve("110").await.unwrap(), None)
+            .await
+        {
+            Ok(Some(ReplyContext::Own(post))) => assert_eq!(post.id, post_id),
+            other => panic!("Expected the post from history, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_ids_not_in_history() {
+        let (service, _db, _temp_dir) = setup().await;
+
+        let target = service.resolve("109876543210").await.unwrap();
+        assert_eq!(target.post_id, None);
+        assert_eq!(target.platforms(), vec!["mastodon"]);
+        assert_eq!(target.reply_to["mastodon"], "109876543210");
+
+        // Not readable without a configured platform, but still repliable
+        assert!(service.context(&target, None).await.unwrap().is_none());
+
+        match service
+            .resolve("550e8400-e29b-41d4-a716-446655440000")
+            .await
+        {
+            Err(PlurcastError::InvalidInput(msg)) => assert!(msg.contains("not found")),
+            other => panic!("Expected InvalidInput, got {:?}", other),
+        }
+        assert!(matches!(
+            service.resolve("not-a-post-id").await,
+            Err(PlurcastError::InvalidInput(_))
+        ));
+    }
+}
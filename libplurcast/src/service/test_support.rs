@@ -0,0 +1,64 @@
+//! Shared setup for the service unit tests
+
+use std::sync::Arc;
+
+use tempfile::TempDir;
+
+use crate::config::{DatabaseConfig, DefaultsConfig};
+use crate::types::{Post, PostRecord, PostStatus};
+use crate::{Config, Database};
+
+/// A fresh database in a temporary directory and a config pointing at it
+///
+/// No platforms are configured. Keep the `TempDir` alive for the test.
+pub(crate) async fn setup_db() -> (Arc<Database>, Arc<Config>, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let db = Arc::new(Database::new(db_path.to_str().unwrap()).await.unwrap());
+    let config = Config {
+        database: DatabaseConfig {
+            path: db_path.to_str().unwrap().to_string(),
+            encrypt_content: false,
+        },
+        defaults: DefaultsConfig {
+            platforms: vec![],
+            language: None,
+        },
+        ..Config::default()
+    };
+    (db, Arc::new(config), temp_dir)
+}
+
+/// Store a posted post with one successful record per `(platform, id)` copy
+pub(crate) async fn create_sent_post(db: &Database, copies: &[(&str, &str)]) -> String {
+    create_sent_post_at(db, chrono::Utc::now().timestamp(), copies).await
+}
+
+/// Like [`create_sent_post`], posted at `created_at`
+pub(crate) async fn create_sent_post_at(
+    db: &Database,
+    created_at: i64,
+    copies: &[(&str, &str)],
+) -> String {
+    let post = Post {
+        created_at,
+        status: PostStatus::Posted,
+        ..Post::new(format!("Posted at {}", created_at))
+    };
+    db.create_post(&post).await.unwrap();
+    for (platform, platform_post_id) in copies {
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: platform.to_string(),
+            platform_post_id: Some(platform_post_id.to_string()),
+            posted_at: Some(created_at),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
+    }
+    post.id
+}
@@ -0,0 +1,391 @@
+//! Thread service for posting hand-written threads as a whole
+//!
+//! Every part is validated before anything is sent. A scheduled thread is
+//! queued completely or not at all. When a part fails on a platform now,
+//! the rest of the thread isn't sent there and, unless the partial thread
+//! is kept, the parts already on that platform are deleted again, so no
+//! platform is left with half a thread.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::delete::{DeleteOutcome, DeleteService};
+use super::posting::{PostRequest, PostResponse, PostingService};
+use super::validation::{ValidationRequest, ValidationService};
+use crate::error::PlurcastError;
+use crate::thread::SCHEDULE_GAP_SECS;
+use crate::types::Visibility;
+use crate::{Database, Result};
+
+/// Thread service
+///
+/// Posts or schedules the parts of a thread, each replying to the one
+/// before it.
+#[derive(Clone)]
+pub struct ThreadService {
+    db: Arc<Database>,
+    posting: PostingService,
+    validation: ValidationService,
+    delete: DeleteService,
+}
+
+/// Request to post a thread
+#[derive(Debug, Clone)]
+pub struct ThreadRequest {
+    /// Text of each part, in order
+    pub parts: Vec<String>,
+    pub platforms: Vec<String>,
+    /// Account to post from (default: each platform's active account)
+    pub account: Option<String>,
+    /// Unix timestamp of the first part; later parts follow a minute apart
+    pub scheduled_at: Option<i64>,
+    pub labels: Vec<String>,
+    /// Content warning shown before every part
+    pub content_warning: Option<String>,
+    pub language: Option<String>,
+    /// Leave parts already sent on a platform where a later part failed
+    pub keep_partial: bool,
+}
+
+/// Response from posting a thread
+#[derive(Debug, Clone)]
+pub struct ThreadResponse {
+    /// Response for each part that was posted or scheduled
+    pub parts: Vec<PostResponse>,
+    /// Platforms the thread couldn't be completed on
+    pub failures: Vec<ThreadFailure>,
+}
+
+/// A platform the thread stopped on
+#[derive(Debug, Clone)]
+pub struct ThreadFailure {
+    pub platform: String,
+    /// Part that failed, from 1
+    pub part: usize,
+    pub error: String,
+    /// Whether the earlier parts on the platform were deleted again
+    pub rolled_back: bool,
+}
+
+impl ThreadResponse {
+    /// Whether the whole thread reached every platform
+    pub fn success(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+impl ThreadService {
+    /// Create a new thread service
+    pub fn new(
+        db: Arc<Database>,
+        posting: PostingService,
+        validation: ValidationService,
+        delete: DeleteService,
+    ) -> Self {
+        Self {
+            db,
+            posting,
+            validation,
+            delete,
+        }
+    }
+
+    /// Post or schedule a thread
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if the thread is empty or a part isn't valid
+    /// on every platform; nothing is sent then. Failures on a platform are
+    /// reported in the [`ThreadResponse`] instead.
+    pub async fn post(&self, request: ThreadRequest) -> Result<ThreadResponse> {
+        self.validate(&request)?;
+
+        if let Some(scheduled_at) = request.scheduled_at {
+            self.schedule(&request, scheduled_at).await
+        } else {
+            self.send(&request).await
+        }
+    }
+
+    /// Check every part against every platform's requirements
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` naming each part that isn't valid and why.
+    pub fn validate(&self, request: &ThreadRequest) -> Result<()> {
+        if request.parts.is_empty() {
+            return Err(PlurcastError::InvalidInput(
+                "Thread has no parts".to_string(),
+            ));
+        }
+
+        let mut problems = Vec::new();
+        for (i, part) in request.parts.iter().enumerate() {
+            let response = self.validation.validate(ValidationRequest {
+                content: part.clone(),
+                platforms: request.platforms.clone(),
+                auto_thread: false,
+                visibility: Visibility::Public,
+            });
+            for result in response.results {
+                for error in result.errors {
+                    problems.push(format!("part {} on {}: {}", i + 1, result.platform, error));
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(PlurcastError::InvalidInput(format!(
+                "Thread is not valid: {}",
+                problems.join("; ")
+            )))
+        }
+    }
+
+    /// Queue every part, each pointing at the one before it
+    ///
+    /// plur-send resolves `thread_parent_uuid` to the parent's platform IDs
+    /// when the parts are due. If a part can't be queued, those already
+    /// queued are removed again.
+    async fn schedule(&self, request: &ThreadRequest, scheduled_at: i64) -> Result<ThreadResponse> {
+        let mut parts: Vec<PostResponse> = Vec::new();
+        for (i, content) in request.parts.iter().enumerate() {
+            let post = PostRequest {
+                scheduled_at: Some(scheduled_at + i as i64 * SCHEDULE_GAP_SECS),
+                thread_parent_uuid: parts.last().map(|p| p.post_id.clone()),
+                thread_sequence: (request.parts.len() > 1).then_some(i as u32),
+                ..self.part_request(request, content, HashMap::new())
+            };
+
+            match self.posting.post(post).await {
+                Ok(response) => parts.push(response),
+                Err(e) => {
+                    for queued in &parts {
+                        if let Err(cleanup) = self.db.delete_post(&queued.post_id).await {
+                            tracing::warn!(
+                                "Failed to remove queued thread part {}: {}",
+                                queued.post_id,
+                                cleanup
+                            );
+                        }
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(ThreadResponse {
+            parts,
+            failures: Vec::new(),
+        })
+    }
+
+    /// Send every part now, each replying to the part before it
+    async fn send(&self, request: &ThreadRequest) -> Result<ThreadResponse> {
+        let mut platforms = request.platforms.clone();
+        let mut reply_to: HashMap<String, String> = HashMap::new();
+        let mut parts: Vec<PostResponse> = Vec::new();
+        let mut failures = Vec::new();
+
+        for (i, content) in request.parts.iter().enumerate() {
+            if platforms.is_empty() {
+                break;
+            }
+            let post = PostRequest {
+                platforms: platforms.clone(),
+                ..self.part_request(request, content, reply_to.clone())
+            };
+            let response = self.posting.post(post).await?;
+
+            reply_to.clear();
+            for result in &response.results {
+                match (result.success, &result.post_id) {
+                    (true, Some(post_id)) => {
+                        reply_to.insert(result.platform.clone(), post_id.clone());
+                    }
+                    _ => {
+                        platforms.retain(|p| p != &result.platform);
+                        failures.push(ThreadFailure {
+                            platform: result.platform.clone(),
+                            part: i + 1,
+                            error: result
+                                .error
+                                .clone()
+                                .unwrap_or_else(|| "no post ID returned".to_string()),
+                            rolled_back: false,
+                        });
+                    }
+                }
+            }
+            parts.push(response);
+        }
+
+        if !request.keep_partial {
+            for failure in &mut failures {
+                failure.rolled_back = self.roll_back(&parts, failure).await;
+            }
+        }
+
+        Ok(ThreadResponse { parts, failures })
+    }
+
+    /// Delete the parts sent to a platform before the one that failed there
+    ///
+    /// Returns whether nothing is left of the thread on the platform.
+    async fn roll_back(&self, parts: &[PostResponse], failure: &ThreadFailure) -> bool {
+        let platform = [failure.platform.clone()];
+        let mut rolled_back = true;
+        for sent in parts[..failure.part - 1].iter().rev() {
+            let report = match self.delete.resolve(&sent.post_id, None).await {
+                Ok(target) => self.delete.delete(&target, Some(&platform)).await,
+                Err(e) => Err(e),
+            };
+            match report {
+                Ok(report) => {
+                    for result in report.results {
+                        if result.outcome != DeleteOutcome::Deleted {
+                            tracing::warn!(
+                                "Could not delete thread part {} on {}: {:?}",
+                                sent.post_id,
+                                failure.platform,
+                                result.outcome
+                            );
+                            rolled_back = false;
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Could not delete thread part {} on {}: {}",
+                        sent.post_id,
+                        failure.platform,
+                        e
+                    );
+                    rolled_back = false;
+                }
+            }
+        }
+        rolled_back
+    }
+
+    /// The post request for one part
+    fn part_request(
+        &self,
+        request: &ThreadRequest,
+        content: &str,
+        reply_to: HashMap<String, String>,
+    ) -> PostRequest {
+        PostRequest {
+            content: content.to_string(),
+            platforms: request.platforms.clone(),
+            draft: false,
+            account: request.account.clone(),
+            scheduled_at: None,
+            nostr_pow: None,
+            nostr_21e8: false,
+            reply_to,
+            thread_parent_uuid: None,
+            thread_sequence: None,
+            labels: request.labels.clone(),
+            content_warning: request.content_warning.clone(),
+            visibility: Visibility::Public,
+            language: request.language.clone(),
+            hashtags: vec![],
+            expires_in: None,
+            poll: None,
+            fail_fast: false,
+            media: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::events::EventBus;
+    use crate::service::test_support::setup_db;
+    use crate::PostStatus;
+    use tempfile::TempDir;
+
+    async fn setup() -> (ThreadService, Arc<Database>, TempDir) {
+        let (db, config, temp_dir) = setup_db().await;
+        let posting = PostingService::new(Arc::clone(&db), Arc::clone(&config), EventBus::new(10));
+        let service = ThreadService::new(
+            Arc::clone(&db),
+            posting,
+            ValidationService::new(Arc::clone(&config)),
+            DeleteService::new(Arc::clone(&db), config),
+        );
+        (service, db, temp_dir)
+    }
+
+    fn request(parts: &[&str], scheduled_at: Option<i64>) -> ThreadRequest {
+        ThreadRequest {
+            parts: parts.iter().map(|p| p.to_string()).collect(),
+            platforms: vec!["nostr".to_string()],
+            account: None,
+            scheduled_at,
+            labels: vec![],
+            content_warning: None,
+            language: None,
+            keep_partial: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_schedule_chains_parts() {
+        let (service, db, _temp_dir) = setup().await;
+        let start = chrono::Utc::now().timestamp() + 3600;
+
+        let response = service
+            .post(request(&["One", "Two", "Three"], Some(start)))
+            .await
+            .unwrap();
+        assert!(response.success());
+        assert_eq!(response.parts.len(), 3);
+
+        for (i, part) in response.parts.iter().enumerate() {
+            let post = db.get_post(&part.post_id).await.unwrap().unwrap();
+            assert_eq!(post.status, PostStatus::Scheduled);
+            assert_eq!(
+                post.scheduled_at,
+                Some(start + i as i64 * SCHEDULE_GAP_SECS)
+            );
+
+            let metadata: serde_json::Value =
+                serde_json::from_str(post.metadata.as_deref().unwrap()).unwrap();
+            assert_eq!(metadata["thread_sequence"], i as u64);
+            let parent = i
+                .checked_sub(1)
+                .map(|p| serde_json::json!(response.parts[p].post_id));
+            assert_eq!(
+                metadata.get("thread_parent_uuid").cloned(),
+                parent,
+                "part {}",
+                i + 1
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_part_sends_nothing() {
+        let (service, db, _temp_dir) = setup().await;
+        let start = chrono::Utc::now().timestamp() + 3600;
+
+        let result = service
+            .post(request(&["One", "   ", "Three"], Some(start)))
+            .await;
+        match result {
+            Err(PlurcastError::InvalidInput(msg)) => assert!(msg.contains("part 2"), "{}", msg),
+            other => panic!("Expected InvalidInput, got {:?}", other),
+        }
+        assert!(db.get_scheduled_posts().await.unwrap().is_empty());
+
+        assert!(matches!(
+            service.post(request(&[], None)).await,
+            Err(PlurcastError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,339 @@
+//! Timeline service for reading feeds
+//!
+//! Reads the home timeline or mentions from every enabled platform that has
+//! them and merges the entries newest first. Clients are created with
+//! read-scoped credentials, so an account with a read-only Mastodon token
+//! can read without being able to post. A platform that fails to answer
+//! doesn't hide the others: its error is reported next to the entries.
+//!
+//! Mentions can also be followed over time: [`TimelineService::new_mentions`]
+//! keeps the ones not reported before and links replies to the Plurcast
+//! posts they answer, for `plur-notify`.
+
+use std::sync::Arc;
+
+use futures::future::join_all;
+use serde::Serialize;
+
+use crate::credentials::CredentialScope;
+use crate::error::{PlatformError, PlurcastError};
+use crate::platforms::{Platform, TimelineEntry, TimelineKind};
+use crate::poster::create_platforms_scoped;
+use crate::{Config, Database, Result};
+
+/// Entries read per platform when no limit is given
+pub const DEFAULT_LIMIT: usize = 20;
+
+/// Timeline service
+///
+/// Creates read-only platform clients and reads their timelines.
+#[derive(Clone)]
+pub struct TimelineService {
+    db: Arc<Database>,
+    config: Arc<Config>,
+}
+
+/// What to read
+#[derive(Debug, Clone)]
+pub struct TimelineRequest {
+    pub kind: TimelineKind,
+    /// Platforms to read (default: every enabled platform)
+    pub platforms: Option<Vec<String>>,
+    /// Account to read as (default: each platform's active account)
+    pub account: Option<String>,
+    /// Most entries to return, across all platforms
+    pub limit: usize,
+}
+
+impl Default for TimelineRequest {
+    fn default() -> Self {
+        Self {
+            kind: TimelineKind::Home,
+            platforms: None,
+            account: None,
+            limit: DEFAULT_LIMIT,
+        }
+    }
+}
+
+/// A mention not reported before
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Mention {
+    #[serde(flatten)]
+    pub entry: TimelineEntry,
+    /// Plurcast post the mention replies to, when it answers one of yours
+    pub post_id: Option<String>,
+}
+
+/// A platform that couldn't be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelineFailure {
+    pub platform: String,
+    pub error: String,
+}
+
+/// Entries read from the platforms
+#[derive(Debug, Clone, Default)]
+pub struct Timeline {
+    /// Entries from every platform, newest first
+    pub entries: Vec<TimelineEntry>,
+    /// Platforms that were read
+    pub platforms: Vec<String>,
+    /// Platforms whose timeline couldn't be read
+    pub failures: Vec<TimelineFailure>,
+}
+
+impl TimelineService {
+    /// Create a new timeline service
+    pub fn new(db: Arc<Database>, config: Arc<Config>) -> Self {
+        Self { db, config }
+    }
+
+    /// Read a timeline from the requested platforms
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a platform client can't be created (missing
+    /// credentials, failed authentication); errors while reading are
+    /// reported in [`Timeline::failures`] instead.
+    pub async fn fetch(&self, request: &TimelineRequest) -> Result<Timeline> {
+        let platforms = create_platforms_scoped(
+            &self.config,
+            request.platforms.as_deref(),
+            request.account.as_deref(),
+            CredentialScope::Read,
+        )
+        .await?;
+
+        Ok(read_timelines(&platforms, request.kind, request.limit).await)
+    }
+
+    /// Keep the mentions not reported before, oldest first
+    ///
+    /// Each mention is recorded as seen, so the next call leaves it out.
+    /// Replies to posts in history get the post's Plurcast ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database can't be read or written.
+    pub async fn new_mentions(&self, entries: &[TimelineEntry]) -> Result<Vec<Mention>> {
+        let now = chrono::Utc::now().timestamp();
+        let mut mentions = Vec::new();
+
+        // Entries come newest first; report in the order they were written
+        for entry in entries.iter().rev() {
+            let post_id = match entry.reply_to {
+                Some(ref reply_to) => {
+                    self.db
+                        .get_post_id_by_platform_post_id(&entry.platform, reply_to)
+                        .await?
+                }
+                None => None,
+            };
+            if self
+                .db
+                .mark_mention_seen(&entry.platform, &entry.id, post_id.as_deref(), now)
+                .await?
+            {
+                mentions.push(Mention {
+                    entry: entry.clone(),
+                    post_id,
+                });
+            }
+        }
+
+        Ok(mentions)
+    }
+}
+
+/// Read a timeline from each platform client and merge the entries
+///
+/// Platforms are read concurrently. Those without timelines (SSB) are
+/// skipped; errors are collected per platform.
+pub async fn read_timelines(
+    platforms: &[Box<dyn Platform>],
+    kind: TimelineKind,
+    limit: usize,
+) -> Timeline {
+    let results =
+        join_all(platforms.iter().map(|platform| async move {
+            (platform.name(), platform.timeline(kind, limit).await)
+        }))
+        .await;
+
+    let mut timeline = Timeline::default();
+    for (platform, result) in results {
+        match result {
+            Ok(entries) => {
+                tracing::debug!(
+                    "Read {} {} entries from {}",
+                    entries.len(),
+                    kind.as_str(),
+                    platform
+                );
+                timeline.platforms.push(platform.to_string());
+                timeline.entries.extend(entries);
+            }
+            Err(PlurcastError::Platform(PlatformError::NotImplemented(message))) => {
+                tracing::debug!("Skipping {}: {}", platform, message);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to read the {} timeline: {}", platform, e);
+                timeline.failures.push(TimelineFailure {
+                    platform: platform.to_string(),
+                    error: e.to_string(),
+                });
+            }
+        }
+    }
+
+    timeline
+        .entries
+        .sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    timeline.entries.truncate(limit);
+    timeline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::mock::{MockConfig, MockPlatform};
+    use crate::service::test_support::{create_sent_post, setup_db};
+
+    fn entry(platform: &str, id: &str, kind: TimelineKind, created_at: i64) -> TimelineEntry {
+        TimelineEntry {
+            platform: platform.to_string(),
+            kind,
+            id: id.to_string(),
+            author: "alice".to_string(),
+            author_name: None,
+            content: format!("Post {}", id),
+            content_warning: None,
+            created_at,
+            url: None,
+            reply_to: None,
+        }
+    }
+
+    fn mock(name: &str, timeline: Vec<TimelineEntry>) -> Box<dyn Platform> {
+        Box::new(MockPlatform::new(MockConfig {
+            name: name.to_string(),
+            timeline,
+            ..Default::default()
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_read_timelines_merges_newest_first() {
+        let platforms = vec![
+            mock(
+                "nostr",
+                vec![
+                    entry("nostr", "n2", TimelineKind::Home, 300),
+                    entry("nostr", "n1", TimelineKind::Home, 100),
+                    entry("nostr", "m1", TimelineKind::Mentions, 400),
+                ],
+            ),
+            mock(
+                "mastodon",
+                vec![entry("mastodon", "110", TimelineKind::Home, 200)],
+            ),
+        ];
+
+        let timeline = read_timelines(&platforms, TimelineKind::Home, 20).await;
+        let ids: Vec<&str> = timeline.entries.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["n2", "110", "n1"]);
+        assert_eq!(timeline.platforms, vec!["nostr", "mastodon"]);
+        assert!(timeline.failures.is_empty());
+
+        let limited = read_timelines(&platforms, TimelineKind::Home, 2).await;
+        assert_eq!(limited.entries.len(), 2);
+
+        let mentions = read_timelines(&platforms, TimelineKind::Mentions, 20).await;
+        assert_eq!(mentions.entries.len(), 1);
+        assert_eq!(mentions.entries[0].id, "m1");
+    }
+
+    #[tokio::test]
+    async fn test_read_timelines_reports_failures() {
+        let failing: Box<dyn Platform> = Box::new(MockPlatform::new(MockConfig {
+            name: "mastodon".to_string(),
+            timeline_succeeds: false,
+            ..Default::default()
+        }));
+        let platforms = vec![
+            failing,
+            mock("nostr", vec![entry("nostr", "n1", TimelineKind::Home, 100)]),
+        ];
+
+        let timeline = read_timelines(&platforms, TimelineKind::Home, 20).await;
+        assert_eq!(timeline.entries.len(), 1);
+        assert_eq!(timeline.platforms, vec!["nostr"]);
+        assert_eq!(timeline.failures.len(), 1);
+        assert_eq!(timeline.failures[0].platform, "mastodon");
+        assert!(timeline.failures[0].error.contains("Mock timeline failed"));
+    }
+
+    #[tokio::test]
+    async fn test_new_mentions_reported_once() {
+        let (db, config, _temp_dir) = setup_db().await;
+        let service = TimelineService::new(Arc::clone(&db), config);
+
+        let post_id = create_sent_post(&db, &[("mastodon", "109")]).await;
+
+        let mut reply = entry("mastodon", "111", TimelineKind::Mentions, 200);
+        reply.reply_to = Some("109".to_string());
+        let mention = entry("mastodon", "110", TimelineKind::Mentions, 100);
+
+        let first = service
+            .new_mentions(&[reply.clone(), mention.clone()])
+            .await
+            .unwrap();
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].entry.id, "110");
+        assert_eq!(first[0].post_id, None);
+        assert_eq!(first[1].entry.id, "111");
+        assert_eq!(first[1].post_id.as_deref(), Some(post_id.as_str()));
+
+        let later = entry("mastodon", "112", TimelineKind::Mentions, 300);
+        let second = service
+            .new_mentions(&[later, reply, mention])
+            .await
+            .unwrap();
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].entry.id, "112");
+    }
+
+    #[tokio::test]
+    async fn test_read_timelines_skips_platforms_without_timelines() {
+        struct NoTimeline;
+
+        #[async_trait::async_trait]
+        impl Platform for NoTimeline {
+            async fn authenticate(&mut self) -> Result<()> {
+                Ok(())
+            }
+            async fn post(&self, _post: &crate::Post) -> Result<String> {
+                Ok("%id=.sha256".to_string())
+            }
+            fn validate_content(&self, _content: &str) -> Result<()> {
+                Ok(())
+            }
+            fn name(&self) -> &str {
+                "ssb"
+            }
+            fn character_limit(&self) -> Option<usize> {
+                None
+            }
+            fn is_configured(&self) -> bool {
+                true
+            }
+        }
+
+        let platforms: Vec<Box<dyn Platform>> = vec![Box::new(NoTimeline)];
+        let timeline = read_timelines(&platforms, TimelineKind::Home, 20).await;
+        assert!(timeline.entries.is_empty());
+        assert!(timeline.platforms.is_empty());
+        assert!(timeline.failures.is_empty());
+    }
+}
@@ -3,6 +3,7 @@
 //! Provides real-time validation of content against platform requirements,
 //! including character limits, content size, and empty content checks.
 
+use crate::types::Visibility;
 use crate::Config;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -21,12 +22,13 @@ const MASTODON_DEFAULT_CHAR_LIMIT: usize = 500;
 /// - Empty or whitespace-only content
 /// - Content size (MAX_CONTENT_LENGTH = 100KB)
 /// - Platform-specific character limits
+/// - Whether each platform supports the requested visibility
 ///
 /// # Example
 ///
 /// ```no_run
 /// use libplurcast::service::validation::{ValidationService, ValidationRequest};
-/// use libplurcast::Config;
+/// use libplurcast::{Config, Visibility};
 /// use std::sync::Arc;
 ///
 /// # fn example() -> libplurcast::Result<()> {
@@ -37,6 +39,7 @@ const MASTODON_DEFAULT_CHAR_LIMIT: usize = 500;
 ///     content: "Hello decentralized world!".to_string(),
 ///     platforms: vec!["nostr".to_string(), "mastodon".to_string()],
 ///     auto_thread: false,
+///     visibility: Visibility::Public,
 /// };
 ///
 /// let response = service.validate(request);
@@ -73,6 +76,8 @@ pub struct ValidationRequest {
     pub platforms: Vec<String>,
     /// If true, content will be auto-threaded (skip character limit checks)
     pub auto_thread: bool,
+    /// Who the post should be visible to
+    pub visibility: Visibility,
 }
 
 /// Response containing validation results
@@ -113,6 +118,7 @@ impl ValidationService {
     /// - Empty/whitespace-only content
     /// - Content size (MAX_CONTENT_LENGTH)
     /// - Platform-specific character limits (unless auto_thread is enabled)
+    /// - Platform support for the requested visibility
     ///
     /// # Arguments
     ///
@@ -126,8 +132,12 @@ impl ValidationService {
         let mut all_valid = true;
 
         for platform in &request.platforms {
-            let validation =
-                self.validate_for_platform(&request.content, platform, request.auto_thread);
+            let validation = self.validate_for_platform(
+                &request.content,
+                platform,
+                request.auto_thread,
+                request.visibility,
+            );
             if !validation.valid {
                 all_valid = false;
             }
@@ -157,6 +167,7 @@ impl ValidationService {
             content: content.to_string(),
             platforms: platforms.to_vec(),
             auto_thread: false,
+            visibility: Visibility::Public,
         };
         self.validate(request).valid
     }
@@ -196,11 +207,13 @@ impl ValidationService {
     /// * `content` - Content to validate
     /// * `platform` - Platform to validate for
     /// * `auto_thread` - If true, skip character limit checks (content will be split into threads)
+    /// * `visibility` - Who the post should be visible to
     fn validate_for_platform(
         &self,
         content: &str,
         platform: &str,
         auto_thread: bool,
+        visibility: Visibility,
     ) -> PlatformValidation {
         let mut errors = Vec::new();
         let mut warnings = Vec::new();
@@ -244,6 +257,10 @@ impl ValidationService {
             }
         }
 
+        if let Some(error) = visibility_error(platform, visibility) {
+            errors.push(error);
+        }
+
         PlatformValidation {
             platform: platform.to_string(),
             valid: errors.is_empty(),
@@ -317,6 +334,26 @@ impl ValidationService {
     }
 }
 
+/// Why a platform can't post with the given visibility, if it can't
+///
+/// Mastodon supports every visibility. Nostr notes and SSB messages are
+/// readable by anyone who receives them, so only public posts make sense.
+fn visibility_error(platform: &str, visibility: Visibility) -> Option<String> {
+    if visibility == Visibility::Public {
+        return None;
+    }
+
+    let name = match platform {
+        "nostr" => "Nostr",
+        "ssb" => "SSB",
+        _ => return None,
+    };
+    Some(format!(
+        "{} posts are always public; visibility '{}' is only supported on Mastodon",
+        name, visibility
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,6 +363,7 @@ mod tests {
         Config {
             database: DatabaseConfig {
                 path: ":memory:".to_string(),
+                encrypt_content: false,
             },
             credentials: None,
             nostr: None,
@@ -333,6 +371,8 @@ mod tests {
             ssb: None,
             defaults: DefaultsConfig::default(),
             scheduling: None,
+            retention: None,
+            bot: None,
         }
     }
 
@@ -345,6 +385,7 @@ mod tests {
             content: "Hello world!".to_string(),
             platforms: vec!["nostr".to_string()],
             auto_thread: false,
+            visibility: Visibility::Public,
         };
 
         let response = service.validate(request);
@@ -367,6 +408,7 @@ mod tests {
                 "ssb".to_string(),
             ],
             auto_thread: false,
+            visibility: Visibility::Public,
         };
 
         let response = service.validate(request);
@@ -388,6 +430,7 @@ mod tests {
             content: "".to_string(),
             platforms: vec!["nostr".to_string()],
             auto_thread: false,
+            visibility: Visibility::Public,
         };
 
         let response = service.validate(request);
@@ -407,6 +450,7 @@ mod tests {
             content: "   \n\t  ".to_string(),
             platforms: vec!["nostr".to_string()],
             auto_thread: false,
+            visibility: Visibility::Public,
         };
 
         let response = service.validate(request);
@@ -427,6 +471,7 @@ mod tests {
             content: large_content,
             platforms: vec!["nostr".to_string()],
             auto_thread: false,
+            visibility: Visibility::Public,
         };
 
         let response = service.validate(request);
@@ -450,6 +495,7 @@ mod tests {
             content: long_content,
             platforms: vec!["nostr".to_string()],
             auto_thread: false,
+            visibility: Visibility::Public,
         };
 
         let response = service.validate(request);
@@ -472,6 +518,7 @@ mod tests {
             content: long_content,
             platforms: vec!["mastodon".to_string()],
             auto_thread: false,
+            visibility: Visibility::Public,
         };
 
         let response = service.validate(request);
@@ -495,6 +542,7 @@ mod tests {
             content: long_content,
             platforms: vec!["mastodon".to_string()],
             auto_thread: true, // With auto-thread enabled
+            visibility: Visibility::Public,
         };
 
         let response = service.validate(request);
@@ -557,6 +605,7 @@ mod tests {
             content: "Hello world!".to_string(),
             platforms: vec!["unknown_platform".to_string()],
             auto_thread: false,
+            visibility: Visibility::Public,
         };
 
         let response = service.validate(request);
@@ -577,6 +626,7 @@ mod tests {
             content: content.clone(),
             platforms: vec!["mastodon".to_string()],
             auto_thread: false,
+            visibility: Visibility::Public,
         };
 
         let response = service.validate(request);
@@ -589,6 +639,7 @@ mod tests {
             content: content_over,
             platforms: vec!["mastodon".to_string()],
             auto_thread: false,
+            visibility: Visibility::Public,
         };
 
         let response_over = service.validate(request_over);
@@ -606,6 +657,7 @@ mod tests {
             content: "Test".to_string(),
             platforms: vec!["nostr".to_string()],
             auto_thread: false,
+            visibility: Visibility::Public,
         };
 
         let response1 = service.validate(request.clone());
@@ -613,4 +665,49 @@ mod tests {
 
         assert_eq!(response1.valid, response2.valid);
     }
+
+    #[test]
+    fn test_visibility_supported_only_on_mastodon() {
+        let config = Arc::new(create_test_config());
+        let service = ValidationService::new(config);
+
+        let request = ValidationRequest {
+            content: "Followers only".to_string(),
+            platforms: vec![
+                "mastodon".to_string(),
+                "nostr".to_string(),
+                "ssb".to_string(),
+            ],
+            auto_thread: false,
+            visibility: Visibility::Private,
+        };
+
+        let response = service.validate(request);
+        assert!(!response.valid);
+        assert!(response.results[0].valid);
+        assert_eq!(
+            response.results[1].errors,
+            vec![
+                "Nostr posts are always public; visibility 'private' is only supported on Mastodon"
+                    .to_string()
+            ]
+        );
+        assert!(!response.results[2].valid);
+        assert!(response.results[2].errors[0].starts_with("SSB posts are always public"));
+    }
+
+    #[test]
+    fn test_public_visibility_valid_everywhere() {
+        let config = Arc::new(create_test_config());
+        let service = ValidationService::new(config);
+
+        let request = ValidationRequest {
+            content: "Hello".to_string(),
+            platforms: vec!["nostr".to_string(), "ssb".to_string()],
+            auto_thread: false,
+            visibility: Visibility::Public,
+        };
+
+        assert!(service.validate(request).valid);
+    }
 }
@@ -0,0 +1,423 @@
+//! Verify service for checking published posts are still there
+//!
+//! Looks the published copies of posts up on their platforms and records
+//! whether each was found. A post whose every copy is gone (deleted on the
+//! platform, or on no relay any more) is marked deleted in history. Copies
+//! that went missing or turned up again since the previous check are the
+//! drift `plur-verify` reports.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use rand::seq::SliceRandom;
+
+use crate::credentials::CredentialScope;
+use crate::db::PostWithRecords;
+use crate::error::{PlatformError, PlurcastError};
+use crate::platforms::Platform;
+use crate::poster::create_platforms_scoped;
+use crate::service::engagement::{copies, PublishedCopy};
+use crate::service::history::HistoryQuery;
+use crate::{Config, Database, PostCheck, PostStatus, Result};
+
+/// Reason recorded on the tombstone of posts found gone everywhere
+pub const VANISHED_REASON: &str = "vanished";
+
+/// Verify service
+///
+/// Checks that published posts can still be found and records the outcome.
+#[derive(Clone)]
+pub struct VerifyService {
+    db: Arc<Database>,
+    config: Arc<Config>,
+}
+
+/// Outcome of looking up one published copy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyCheck {
+    pub check: PostCheck,
+    /// Whether the copy was found at the previous check, if it was checked
+    /// before
+    pub was_present: Option<bool>,
+}
+
+impl CopyCheck {
+    /// Whether the copy went missing since the previous check (or was
+    /// missing at its first)
+    pub fn vanished(&self) -> bool {
+        !self.check.present && self.was_present != Some(false)
+    }
+
+    /// Whether a copy missing at the previous check was found again
+    pub fn reappeared(&self) -> bool {
+        self.check.present && self.was_present == Some(false)
+    }
+}
+
+/// A published copy that couldn't be looked up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyFailure {
+    pub platform: String,
+    pub platform_post_id: String,
+    pub error: String,
+}
+
+/// Outcome of verifying posts
+#[derive(Debug, Clone, Default)]
+pub struct VerifyReport {
+    /// Copies that were looked up
+    pub checks: Vec<CopyCheck>,
+    /// Copies that couldn't be looked up
+    pub failures: Vec<VerifyFailure>,
+    /// Posts marked deleted in history because no copy was left
+    pub marked_deleted: Vec<String>,
+}
+
+impl VerifyReport {
+    /// Copies that weren't found
+    pub fn missing(&self) -> impl Iterator<Item = &CopyCheck> {
+        self.checks.iter().filter(|c| !c.check.present)
+    }
+
+    /// Copies whose state changed since their previous check
+    pub fn drift(&self) -> impl Iterator<Item = &CopyCheck> {
+        self.checks
+            .iter()
+            .filter(|c| c.vanished() || c.reappeared())
+    }
+}
+
+impl VerifyService {
+    /// Create a new verify service
+    pub fn new(db: Arc<Database>, config: Arc<Config>) -> Self {
+        Self { db, config }
+    }
+
+    /// Look up the published copies of the posted posts matching `query`
+    ///
+    /// With `sample`, only that many copies, picked at random, are looked
+    /// up. Copies are looked up as the account they were posted from, with
+    /// read-scoped credentials. Platforms that can't look posts up (SSB) and
+    /// platforms that aren't enabled are skipped; errors are collected per
+    /// copy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database can't be read or written, or a
+    /// platform client can't be created.
+    pub async fn verify(
+        &self,
+        query: &HistoryQuery,
+        sample: Option<usize>,
+    ) -> Result<VerifyReport> {
+        let posts = self.posted(query).await?;
+
+        let mut checkable: Vec<PublishedCopy> = posts
+            .iter()
+            .flat_map(|post| copies(post, query))
+            .filter(|copy| supports_verification(&copy.platform))
+            .collect();
+        if let Some(sample) = sample {
+            checkable.shuffle(&mut rand::thread_rng());
+            checkable.truncate(sample);
+        }
+
+        let mut by_account: BTreeMap<String, Vec<PublishedCopy>> = BTreeMap::new();
+        for copy in checkable {
+            by_account
+                .entry(copy.account.clone())
+                .or_default()
+                .push(copy);
+        }
+
+        let mut report = VerifyReport::default();
+        for (account, copies) in by_account {
+            let mut platforms: Vec<String> = copies.iter().map(|c| c.platform.clone()).collect();
+            platforms.sort();
+            platforms.dedup();
+
+            let clients = create_platforms_scoped(
+                &self.config,
+                Some(platforms.as_slice()),
+                Some(&account),
+                CredentialScope::Read,
+            )
+            .await?;
+            let part = verify_with(&self.db, &clients, &copies).await?;
+            report.checks.extend(part.checks);
+            report.failures.extend(part.failures);
+        }
+
+        report.marked_deleted = mark_vanished(&self.db, &posts, &report.checks).await?;
+        Ok(report)
+    }
+
+    /// Copies missing at their latest check, longest missing first,
+    /// without looking them up again
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database can't be read.
+    pub async fn missing(&self, platform: Option<&str>) -> Result<Vec<PostCheck>> {
+        Ok(self
+            .db
+            .get_missing_post_checks()
+            .await?
+            .into_iter()
+            .filter(|check| platform.is_none_or(|platform| check.platform == platform))
+            .collect())
+    }
+
+    /// Posted posts matching `query`, whatever status it asks for
+    async fn posted(&self, query: &HistoryQuery) -> Result<Vec<PostWithRecords>> {
+        let query = HistoryQuery {
+            status: Some(PostStatus::Posted),
+            ..query.clone()
+        };
+        self.db.query_posts(&query.to_post_query()?).await
+    }
+}
+
+/// Whether posts can be looked up on a platform
+///
+/// SSB feeds are append-only and replicated: a message can't vanish from
+/// the feed it was published to.
+pub fn supports_verification(platform: &str) -> bool {
+    matches!(platform, "nostr" | "mastodon")
+}
+
+/// Look each copy up with its platform client and save the outcome
+async fn verify_with(
+    db: &Database,
+    platforms: &[Box<dyn Platform>],
+    copies: &[PublishedCopy],
+) -> Result<VerifyReport> {
+    let now = chrono::Utc::now().timestamp();
+    let mut report = VerifyReport::default();
+
+    for copy in copies {
+        let client = match platforms.iter().find(|p| p.name() == copy.platform) {
+            Some(client) => client,
+            None => {
+                tracing::debug!(
+                    "{} is not enabled, skipping {}",
+                    copy.platform,
+                    copy.post_id
+                );
+                continue;
+            }
+        };
+
+        let present = match client.exists(&copy.platform_post_id).await {
+            Ok(present) => present,
+            Err(PlurcastError::Platform(PlatformError::NotImplemented(message))) => {
+                tracing::debug!("Skipping {}: {}", copy.platform, message);
+                continue;
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to look up {} {}: {}",
+                    copy.platform,
+                    copy.platform_post_id,
+                    e
+                );
+                report.failures.push(VerifyFailure {
+                    platform: copy.platform.clone(),
+                    platform_post_id: copy.platform_post_id.clone(),
+                    error: e.to_string(),
+                });
+                continue;
+            }
+        };
+
+        let previous = db
+            .get_post_checks(&copy.post_id)
+            .await?
+            .into_iter()
+            .find(|c| c.platform == copy.platform && c.platform_post_id == copy.platform_post_id);
+        // A copy missing again keeps the time it was first found missing
+        let missing_since = match previous.as_ref() {
+            _ if present => None,
+            Some(previous) => previous.missing_since.or(Some(now)),
+            None => Some(now),
+        };
+
+        let check = PostCheck {
+            post_id: copy.post_id.clone(),
+            platform: copy.platform.clone(),
+            platform_post_id: copy.platform_post_id.clone(),
+            present,
+            checked_at: now,
+            missing_since,
+        };
+        db.save_post_check(&check).await?;
+        report.checks.push(CopyCheck {
+            check,
+            was_present: previous.map(|p| p.present),
+        });
+    }
+
+    Ok(report)
+}
+
+/// Mark deleted the posts of missing copies that have nothing left
+///
+/// A post is gone when every published copy of it, on any platform, was
+/// missing at its latest check and at the one before: a single miss may be
+/// a platform hiccup. Posts with a copy on a platform that can't be checked
+/// are left alone.
+async fn mark_vanished(
+    db: &Database,
+    posts: &[PostWithRecords],
+    checks: &[CopyCheck],
+) -> Result<Vec<String>> {
+    let by_id: HashMap<&str, &PostWithRecords> =
+        posts.iter().map(|p| (p.post.id.as_str(), p)).collect();
+
+    let mut candidates: Vec<&str> = checks
+        .iter()
+        .filter(|c| !c.check.present)
+        .map(|c| c.check.post_id.as_str())
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+
+    let mut marked = Vec::new();
+    for post_id in candidates {
+        let post = match by_id.get(post_id) {
+            Some(post) => post,
+            None => continue,
+        };
+        let latest = db.get_post_checks(post_id).await?;
+        let gone = copies(post, &HistoryQuery::default()).iter().all(|copy| {
+            let same = |check: &PostCheck| {
+                check.post_id == post_id
+                    && check.platform == copy.platform
+                    && check.platform_post_id == copy.platform_post_id
+            };
+            match checks.iter().find(|c| same(&c.check)) {
+                Some(c) => !c.check.present && c.was_present == Some(false),
+                // Not looked up this time: missing since before its latest check
+                None => latest.iter().any(|c| {
+                    same(c)
+                        && !c.present
+                        && c.missing_since.is_some_and(|since| since < c.checked_at)
+                }),
+            }
+        });
+
+        if gone && db.soft_delete_post(post_id, Some(VANISHED_REASON)).await? {
+            tracing::info!("Post {} is gone from every platform", post_id);
+            marked.push(post_id.to_string());
+        }
+    }
+
+    Ok(marked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::mock::{MockConfig, MockPlatform};
+    use crate::service::test_support::{create_sent_post, setup_db};
+    use tempfile::TempDir;
+
+    async fn setup() -> (VerifyService, Arc<Database>, TempDir) {
+        let (db, config, temp_dir) = setup_db().await;
+        let service = VerifyService::new(Arc::clone(&db), config);
+        (service, db, temp_dir)
+    }
+
+    /// A mock platform where the given posts were deleted
+    fn platform(name: &str, deleted: &[&str]) -> Box<dyn Platform> {
+        let config = MockConfig {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        config
+            .deleted_posts
+            .lock()
+            .unwrap()
+            .extend(deleted.iter().map(|id| id.to_string()));
+        Box::new(MockPlatform::new(config))
+    }
+
+    async fn check(
+        service: &VerifyService,
+        db: &Database,
+        platforms: &[Box<dyn Platform>],
+    ) -> VerifyReport {
+        let posts = service.posted(&HistoryQuery::default()).await.unwrap();
+        let copies: Vec<PublishedCopy> = posts
+            .iter()
+            .flat_map(|post| copies(post, &HistoryQuery::default()))
+            .collect();
+        let mut report = verify_with(db, platforms, &copies).await.unwrap();
+        report.marked_deleted = mark_vanished(db, &posts, &report.checks).await.unwrap();
+        report
+    }
+
+    #[tokio::test]
+    async fn test_missing_copies_are_recorded_as_drift() {
+        let (service, db, _temp_dir) = setup().await;
+        let kept = create_sent_post(&db, &[("mastodon", "110"), ("nostr", "note1a")]).await;
+        let gone = create_sent_post(&db, &[("mastodon", "120")]).await;
+
+        let report = check(&service, &db, &[platform("mastodon", &["110", "120"])]).await;
+        assert_eq!(report.checks.len(), 2);
+        assert_eq!(report.missing().count(), 2);
+        assert_eq!(report.drift().count(), 2);
+        // Missing once isn't gone yet
+        assert!(report.marked_deleted.is_empty());
+        assert!(db.get_tombstone(&gone).await.unwrap().is_none());
+
+        let checks = db.get_post_checks(&kept).await.unwrap();
+        assert_eq!(checks.len(), 1);
+        assert!(!checks[0].present);
+        let missing_since = checks[0].missing_since;
+        assert!(missing_since.is_some());
+
+        // Still missing is no longer drift; found again is
+        let report = check(
+            &service,
+            &db,
+            &[
+                platform("mastodon", &["110", "120"]),
+                platform("nostr", &[]),
+            ],
+        )
+        .await;
+        let by_id: HashMap<&str, &CopyCheck> = report
+            .checks
+            .iter()
+            .map(|c| (c.check.platform_post_id.as_str(), c))
+            .collect();
+        assert!(!by_id["110"].vanished());
+        assert_eq!(by_id["110"].check.missing_since, missing_since);
+        assert!(by_id["note1a"].check.present);
+        assert_eq!(by_id["note1a"].was_present, None);
+        assert_eq!(report.drift().count(), 0);
+        // Missing on two checks is gone; the first post's nostr copy is there
+        assert_eq!(report.marked_deleted, vec![gone.clone()]);
+        assert!(db.get_tombstone(&gone).await.unwrap().is_some());
+        assert!(db.get_tombstone(&kept).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_lookup_failures_are_not_missing() {
+        let (service, db, _temp_dir) = setup().await;
+        let post = create_sent_post(&db, &[("mastodon", "110")]).await;
+
+        let failing: Box<dyn Platform> = Box::new(MockPlatform::new(MockConfig {
+            name: "mastodon".to_string(),
+            timeline_succeeds: false,
+            ..Default::default()
+        }));
+        let report = check(&service, &db, &[failing]).await;
+        assert!(report.checks.is_empty());
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].platform_post_id, "110");
+        assert!(db.get_post_checks(&post).await.unwrap().is_empty());
+        assert!(report.marked_deleted.is_empty());
+    }
+}
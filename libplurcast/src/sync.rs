@@ -0,0 +1,352 @@
+//! Multi-device sync via mergeable changesets
+//!
+//! A changeset is a JSON document holding every published post changed
+//! since a point in time, with its platform records and labels. Exporting
+//! on one machine and merging on another reconciles the two histories:
+//!
+//! - posts are matched by UUID, so nothing is duplicated
+//! - when both sides have a post, the one changed most recently wins
+//!   (ties keep the local copy)
+//! - platform records are unioned; identical records are not added twice
+//! - labels are unioned
+//!
+//! Only published history syncs: posts that were sent or deleted. Drafts,
+//! queued posts (pending or scheduled) and failed posts waiting for a retry
+//! stay on the device they were written on, since their platform schedules,
+//! series and attachments don't travel and plur-send would send them from
+//! both devices.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PlurcastError, Result};
+use crate::types::{Post, PostRecord, PostStatus};
+use crate::Database;
+
+/// Current changeset format version
+pub const CHANGESET_VERSION: u32 = 1;
+
+/// Posts changed since a point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Changeset {
+    pub format_version: u32,
+    /// When the changeset was exported (Unix timestamp)
+    pub exported_at: i64,
+    /// Lower bound the export was taken from, if any
+    pub since: Option<i64>,
+    pub posts: Vec<ChangedPost>,
+}
+
+/// A post as it stands on the exporting device
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangedPost {
+    pub post: Post,
+    /// When the post last changed (Unix timestamp)
+    pub updated_at: i64,
+    #[serde(default)]
+    pub records: Vec<PostRecord>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+/// Outcome of [`merge_changes`]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MergeReport {
+    /// Posts that didn't exist locally
+    pub inserted: usize,
+    /// Local posts replaced by a newer incoming version
+    pub updated: usize,
+    /// Posts where the local copy was as new or newer
+    pub kept_local: usize,
+    /// Platform records added
+    pub records_added: usize,
+    /// Incoming posts that aren't published history, left out
+    pub skipped: usize,
+}
+
+/// Whether a post is published history, the only posts that sync
+fn is_synced(status: &PostStatus) -> bool {
+    matches!(status, PostStatus::Posted | PostStatus::Deleted)
+}
+
+/// Export every published post changed at or after `since`
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub async fn export_changes(db: &Database, since: Option<i64>) -> Result<Changeset> {
+    let mut posts = Vec::new();
+
+    for (post_id, updated_at) in db.find_posts_changed_since(since).await? {
+        let post = match db.get_post(&post_id).await? {
+            Some(post) if is_synced(&post.status) => post,
+            _ => continue,
+        };
+
+        let mut records = db.get_post_records(&post_id).await?;
+        for record in &mut records {
+            record.id = None;
+        }
+
+        posts.push(ChangedPost {
+            post,
+            updated_at,
+            records,
+            labels: db.get_labels(&post_id).await?,
+        });
+    }
+
+    Ok(Changeset {
+        format_version: CHANGESET_VERSION,
+        exported_at: chrono::Utc::now().timestamp(),
+        since,
+        posts,
+    })
+}
+
+/// Merge a changeset from another device into the local database
+///
+/// Posts that aren't published history are skipped (a changeset from an
+/// older version may carry them).
+///
+/// # Errors
+///
+/// Returns `InvalidInput` for a changeset written by a newer format, or an
+/// error if a database write fails.
+pub async fn merge_changes(db: &Database, changeset: &Changeset) -> Result<MergeReport> {
+    if changeset.format_version > CHANGESET_VERSION {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Changeset format version {} is newer than supported ({})",
+            changeset.format_version, CHANGESET_VERSION
+        )));
+    }
+
+    let mut report = MergeReport::default();
+
+    for changed in &changeset.posts {
+        if !is_synced(&changed.post.status) {
+            report.skipped += 1;
+            continue;
+        }
+
+        match db.get_post_updated_at(&changed.post.id).await? {
+            None => {
+                db.put_synced_post(&changed.post, changed.updated_at)
+                    .await?;
+                report.inserted += 1;
+            }
+            Some(local) if changed.updated_at > local => {
+                db.put_synced_post(&changed.post, changed.updated_at)
+                    .await?;
+                report.updated += 1;
+            }
+            Some(_) => report.kept_local += 1,
+        }
+
+        for record in &changed.records {
+            let record = PostRecord {
+                id: None,
+                post_id: changed.post.id.clone(),
+                ..record.clone()
+            };
+            if db.merge_post_record(&record).await? {
+                report.records_added += 1;
+            }
+        }
+
+        db.add_labels(&changed.post.id, &changed.labels).await?;
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PostStatus;
+    use tempfile::TempDir;
+
+    async fn open_db(temp_dir: &TempDir, name: &str) -> Database {
+        let db_path = temp_dir.path().join(name);
+        Database::new(db_path.to_str().unwrap()).await.unwrap()
+    }
+
+    fn published(content: &str) -> Post {
+        Post {
+            status: PostStatus::Posted,
+            ..Post::new(content.to_string())
+        }
+    }
+
+    fn record(post_id: &str, platform: &str) -> PostRecord {
+        PostRecord {
+            id: None,
+            post_id: post_id.to_string(),
+            platform: platform.to_string(),
+            platform_post_id: Some(format!("{}-id", platform)),
+            posted_at: Some(1_700_000_000),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merge_inserts_new_posts_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let laptop = open_db(&temp_dir, "laptop.db").await;
+        let server = open_db(&temp_dir, "server.db").await;
+
+        let post = published("Written on the laptop");
+        laptop.create_post(&post).await.unwrap();
+        laptop
+            .create_post_record(&record(&post.id, "nostr"))
+            .await
+            .unwrap();
+        laptop
+            .add_labels(&post.id, &["series:tips".to_string()])
+            .await
+            .unwrap();
+
+        let changeset = export_changes(&laptop, None).await.unwrap();
+        let report = merge_changes(&server, &changeset).await.unwrap();
+        assert_eq!(report.inserted, 1);
+        assert_eq!(report.records_added, 1);
+
+        // Merging the same changeset again changes nothing
+        let again = merge_changes(&server, &changeset).await.unwrap();
+        assert_eq!(again.inserted, 0);
+        assert_eq!(again.kept_local, 1);
+        assert_eq!(again.records_added, 0);
+
+        assert_eq!(server.get_post_records(&post.id).await.unwrap().len(), 1);
+        assert_eq!(
+            server.get_labels(&post.id).await.unwrap(),
+            vec!["series:tips".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_newer_change_wins() {
+        let temp_dir = TempDir::new().unwrap();
+        let laptop = open_db(&temp_dir, "laptop.db").await;
+        let server = open_db(&temp_dir, "server.db").await;
+
+        let post = published("Sent");
+        laptop.put_synced_post(&post, 1_000).await.unwrap();
+        server.put_synced_post(&post, 1_000).await.unwrap();
+
+        // The server edits the post later than the laptop does
+        let mut laptop_edit = post.clone();
+        laptop_edit.content = "Laptop edit".to_string();
+        laptop.put_synced_post(&laptop_edit, 2_000).await.unwrap();
+
+        let mut server_edit = post.clone();
+        server_edit.content = "Server edit".to_string();
+        server.put_synced_post(&server_edit, 3_000).await.unwrap();
+
+        let to_server = export_changes(&laptop, None).await.unwrap();
+        let report = merge_changes(&server, &to_server).await.unwrap();
+        assert_eq!(report.kept_local, 1);
+
+        let to_laptop = export_changes(&server, None).await.unwrap();
+        let report = merge_changes(&laptop, &to_laptop).await.unwrap();
+        assert_eq!(report.updated, 1);
+
+        for db in [&laptop, &server] {
+            let merged = db.get_post(&post.id).await.unwrap().unwrap();
+            assert_eq!(merged.content, "Server edit");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_since_skips_older_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = open_db(&temp_dir, "db.db").await;
+
+        let old = published("Old");
+        db.put_synced_post(&old, 1_000).await.unwrap();
+        let recent = published("Recent");
+        db.put_synced_post(&recent, 5_000).await.unwrap();
+
+        let changeset = export_changes(&db, Some(2_000)).await.unwrap();
+        assert_eq!(changeset.posts.len(), 1);
+        assert_eq!(changeset.posts[0].post.id, recent.id);
+    }
+
+    #[tokio::test]
+    async fn test_only_published_history_syncs() {
+        let temp_dir = TempDir::new().unwrap();
+        let laptop = open_db(&temp_dir, "laptop.db").await;
+        let server = open_db(&temp_dir, "server.db").await;
+
+        let sent = published("Sent");
+        laptop.create_post(&sent).await.unwrap();
+        for status in [
+            PostStatus::Draft,
+            PostStatus::Pending,
+            PostStatus::Scheduled,
+            PostStatus::Failed,
+        ] {
+            laptop
+                .create_post(&Post {
+                    status,
+                    ..Post::new("Not sent".to_string())
+                })
+                .await
+                .unwrap();
+        }
+
+        let changeset = export_changes(&laptop, None).await.unwrap();
+        assert_eq!(changeset.posts.len(), 1);
+        assert_eq!(changeset.posts[0].post.id, sent.id);
+
+        // Queued posts in a changeset from an older version are skipped too
+        let mut older = changeset.clone();
+        older.posts.push(ChangedPost {
+            post: Post {
+                status: PostStatus::Scheduled,
+                scheduled_at: Some(2_000_000_000),
+                ..Post::new("Queued elsewhere".to_string())
+            },
+            updated_at: 1_000,
+            records: vec![],
+            labels: vec![],
+        });
+        let report = merge_changes(&server, &older).await.unwrap();
+        assert_eq!(report.inserted, 1);
+        assert_eq!(report.skipped, 1);
+        assert!(server.get_scheduled_posts().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_status_change_bumps_updated_at() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = open_db(&temp_dir, "db.db").await;
+
+        let post = Post::new("Queued".to_string());
+        db.put_synced_post(&post, 1_000).await.unwrap();
+        db.update_post_status(&post.id, PostStatus::Posted)
+            .await
+            .unwrap();
+
+        let updated_at = db.get_post_updated_at(&post.id).await.unwrap().unwrap();
+        assert!(updated_at > 1_000);
+    }
+
+    #[tokio::test]
+    async fn test_newer_format_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = open_db(&temp_dir, "db.db").await;
+
+        let changeset = Changeset {
+            format_version: CHANGESET_VERSION + 1,
+            exported_at: 0,
+            since: None,
+            posts: vec![],
+        };
+        assert!(matches!(
+            merge_changes(&db, &changeset).await,
+            Err(PlurcastError::InvalidInput(_))
+        ));
+    }
+}
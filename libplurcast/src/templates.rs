@@ -0,0 +1,262 @@
+//! Named post templates with variable substitution
+//!
+//! `plur-post --template release --var version=1.2.0` posts the template
+//! `release` from the `templates` directory next to the config file
+//! (typically `~/.config/plurcast/templates/release.md`). `{{name}}` in the
+//! template is replaced by the `--var` of that name, or by a built-in:
+//!
+//! - `{{date}}` - today's date, e.g. `2026-10-16`
+//! - `{{time}}` - the current time, e.g. `14:30`
+//! - `{{year}}` - the current year
+//! - `{{weekday}}` - the day of the week, e.g. `Friday`
+//!
+//! Built-ins use local time, and a `--var` with the same name overrides one.
+
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::resolve_config_path;
+use crate::error::{PlurcastError, Result};
+
+/// File extensions tried, in order, when looking up a template by name
+const TEMPLATE_EXTENSIONS: [&str; 2] = ["md", "txt"];
+
+/// The directory templates are loaded from
+///
+/// This is `templates` in the same directory as the config file, so it
+/// follows `PLURCAST_CONFIG`.
+pub fn templates_dir() -> Result<PathBuf> {
+    let config_path = resolve_config_path()?;
+    let config_dir = config_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    Ok(config_dir.join("templates"))
+}
+
+/// Load a template by name from [`templates_dir`]
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if the name isn't a plain file name or no
+/// template by that name exists.
+pub fn load(name: &str) -> Result<String> {
+    load_from(&templates_dir()?, name)
+}
+
+/// Load a template by name from a directory
+///
+/// Tries `<name>.md`, `<name>.txt` and then `<name>` as written.
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if the name isn't a plain file name or no
+/// template by that name exists.
+pub fn load_from(dir: &Path, name: &str) -> Result<String> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Invalid template name '{}': use letters, digits, '-', '_' and '.'",
+            name
+        )));
+    }
+
+    let candidates = TEMPLATE_EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("{}.{}", name, ext)))
+        .chain(std::iter::once(dir.join(name)));
+    for path in candidates {
+        if path.is_file() {
+            return std::fs::read_to_string(&path).map_err(|e| {
+                PlurcastError::InvalidInput(format!(
+                    "Failed to read template {}: {}",
+                    path.display(),
+                    e
+                ))
+            });
+        }
+    }
+
+    Err(PlurcastError::InvalidInput(format!(
+        "Template '{}' not found in {}",
+        name,
+        dir.display()
+    )))
+}
+
+/// Parse a `--var` value of the form `KEY=VALUE`
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if there is no `=` or the key is empty.
+pub fn parse_var(spec: &str) -> Result<(String, String)> {
+    match spec.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(PlurcastError::InvalidInput(format!(
+            "Invalid variable '{}': expected KEY=VALUE, e.g. version=1.2.0",
+            spec
+        ))),
+    }
+}
+
+/// Substitute `{{name}}` placeholders in a template
+///
+/// Whitespace inside the braces is ignored (`{{ version }}` works). Text
+/// between braces that isn't a variable name is left alone.
+///
+/// # Errors
+///
+/// Returns `InvalidInput` naming every placeholder that is neither a
+/// given variable nor a built-in.
+pub fn render(
+    template: &str,
+    vars: &HashMap<String, String>,
+    now: DateTime<Local>,
+) -> Result<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut missing: Vec<String> = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+
+        let end = match after.find("}}") {
+            Some(end) => end,
+            None => {
+                // Unclosed braces are plain text
+                rendered.push_str(&rest[start..]);
+                rest = "";
+                break;
+            }
+        };
+
+        let name = after[..end].trim();
+        if !is_variable_name(name) {
+            rendered.push_str(&rest[start..start + 2 + end + 2]);
+        } else if let Some(value) = vars.get(name) {
+            rendered.push_str(value);
+        } else if let Some(value) = builtin(name, now) {
+            rendered.push_str(&value);
+        } else if !missing.iter().any(|m| m == name) {
+            missing.push(name.to_string());
+        }
+        rest = &after[end + 2..];
+    }
+    rendered.push_str(rest);
+
+    if !missing.is_empty() {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Template variables not set: {} (pass them with --var NAME=VALUE)",
+            missing.join(", ")
+        )));
+    }
+
+    Ok(rendered)
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+fn builtin(name: &str, now: DateTime<Local>) -> Option<String> {
+    let format = match name {
+        "date" => "%Y-%m-%d",
+        "time" => "%H:%M",
+        "year" => "%Y",
+        "weekday" => "%A",
+        _ => return None,
+    };
+    Some(now.format(format).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn now() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 10, 16, 14, 30, 0).unwrap()
+    }
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_render_variables_and_builtins() {
+        let rendered = render(
+            "Release {{version}} is out ({{ date }}, {{weekday}} {{time}})",
+            &vars(&[("version", "1.2.0")]),
+            now(),
+        )
+        .unwrap();
+        assert_eq!(rendered, "Release 1.2.0 is out (2026-10-16, Friday 14:30)");
+    }
+
+    #[test]
+    fn test_render_var_overrides_builtin() {
+        let rendered = render("{{date}}", &vars(&[("date", "launch day")]), now()).unwrap();
+        assert_eq!(rendered, "launch day");
+    }
+
+    #[test]
+    fn test_render_reports_every_missing_variable() {
+        let err = render("{{version}} {{name}} {{version}}", &HashMap::new(), now())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("version, name"));
+    }
+
+    #[test]
+    fn test_render_leaves_non_variables_alone() {
+        let template = "{{ not a var }} and {{unclosed";
+        assert_eq!(render(template, &HashMap::new(), now()).unwrap(), template);
+    }
+
+    #[test]
+    fn test_parse_var() {
+        assert_eq!(
+            parse_var("version=1.2.0").unwrap(),
+            ("version".to_string(), "1.2.0".to_string())
+        );
+        assert_eq!(
+            parse_var("note=a=b").unwrap(),
+            ("note".to_string(), "a=b".to_string())
+        );
+        assert!(parse_var("version").is_err());
+        assert!(parse_var("=1.2.0").is_err());
+    }
+
+    #[test]
+    fn test_load_from_tries_extensions() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("release.md"), "Release {{version}}").unwrap();
+        std::fs::write(dir.path().join("weekly.txt"), "Weekly notes").unwrap();
+
+        assert_eq!(
+            load_from(dir.path(), "release").unwrap(),
+            "Release {{version}}"
+        );
+        assert_eq!(load_from(dir.path(), "weekly").unwrap(), "Weekly notes");
+        assert!(load_from(dir.path(), "missing")
+            .unwrap_err()
+            .to_string()
+            .contains("Template 'missing' not found"));
+        assert!(load_from(dir.path(), "../release").is_err());
+    }
+}
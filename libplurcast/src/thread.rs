@@ -0,0 +1,132 @@
+//! Threads written by hand with `plur-thread`
+//!
+//! A thread is one text with its parts divided by separator lines (`---`
+//! by default, alone on a line). Each part can be numbered, "1/5" after the
+//! text by default, with the format and position configurable.
+
+use crate::error::{PlurcastError, Result};
+
+/// Line dividing the parts of a thread unless another is given
+pub const DEFAULT_SEPARATOR: &str = "---";
+
+/// Numbering used unless another format is given
+pub const DEFAULT_NUMBERING: &str = "{n}/{total}";
+
+/// Seconds between the parts of a scheduled thread
+pub const SCHEDULE_GAP_SECS: i64 = 60;
+
+/// Where a part's number goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumberPosition {
+    /// After the text, separated by a space
+    #[default]
+    Suffix,
+    /// Before the text, separated by a space
+    Prefix,
+}
+
+/// Split a text into thread parts at lines that are exactly `separator`
+///
+/// Surrounding whitespace is trimmed from each part and empty parts are
+/// dropped, so leading, trailing or doubled separators don't make empty
+/// posts.
+pub fn split(text: &str, separator: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    for line in text.lines() {
+        if line.trim_end() == separator {
+            parts.push(std::mem::take(&mut current));
+        } else {
+            current.push_str(line);
+            current.push('\n');
+        }
+    }
+    parts.push(current);
+
+    parts
+        .into_iter()
+        .map(|part| part.trim().to_string())
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+/// Number the parts of a thread
+///
+/// `format` is the number's text, with `{n}` for the part's position (from
+/// 1) and `{total}` for the number of parts. A thread of one part is left
+/// as it is.
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if `format` has no `{n}`.
+pub fn number(parts: &[String], format: &str, position: NumberPosition) -> Result<Vec<String>> {
+    if !format.contains("{n}") {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Numbering format '{}' needs {{n}} for the part number (e.g. \"{}\")",
+            format, DEFAULT_NUMBERING
+        )));
+    }
+    if parts.len() < 2 {
+        return Ok(parts.to_vec());
+    }
+
+    let total = parts.len().to_string();
+    Ok(parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| {
+            let label = format
+                .replace("{n}", &(i + 1).to_string())
+                .replace("{total}", &total);
+            match position {
+                NumberPosition::Suffix => format!("{} {}", part, label),
+                NumberPosition::Prefix => format!("{} {}", label, part),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_at_separator_lines() {
+        let text =
+            "---\nFirst part\nstill first\n---\n\nSecond --- not a separator\n---  \n---\nThird\n";
+        assert_eq!(
+            split(text, DEFAULT_SEPARATOR),
+            vec![
+                "First part\nstill first",
+                "Second --- not a separator",
+                "Third"
+            ]
+        );
+
+        assert_eq!(split("One\n===\nTwo", "==="), vec!["One", "Two"]);
+        assert_eq!(split("Just one", DEFAULT_SEPARATOR), vec!["Just one"]);
+        assert!(split("\n---\n", DEFAULT_SEPARATOR).is_empty());
+    }
+
+    #[test]
+    fn test_number_parts() {
+        let parts = vec!["One".to_string(), "Two".to_string(), "Three".to_string()];
+
+        assert_eq!(
+            number(&parts, DEFAULT_NUMBERING, NumberPosition::Suffix).unwrap(),
+            vec!["One 1/3", "Two 2/3", "Three 3/3"]
+        );
+        assert_eq!(
+            number(&parts, "({n})", NumberPosition::Prefix).unwrap(),
+            vec!["(1) One", "(2) Two", "(3) Three"]
+        );
+        assert_eq!(
+            number(&parts[..1], DEFAULT_NUMBERING, NumberPosition::Suffix).unwrap(),
+            vec!["One"]
+        );
+        assert!(matches!(
+            number(&parts, "{total}", NumberPosition::Suffix),
+            Err(PlurcastError::InvalidInput(_))
+        ));
+    }
+}
@@ -34,6 +34,44 @@ pub enum PostStatus {
     Pending,
     Posted,
     Failed,
+    /// Cancelled or cleared; kept with a tombstone until purged
+    Deleted,
+    /// Fell due while plur-send was down and held by the catch-up policy
+    /// until it's rescheduled
+    Missed,
+}
+
+impl PostStatus {
+    /// Every status, in lifecycle order
+    pub const ALL: [PostStatus; 7] = [
+        PostStatus::Draft,
+        PostStatus::Scheduled,
+        PostStatus::Pending,
+        PostStatus::Posted,
+        PostStatus::Failed,
+        PostStatus::Deleted,
+        PostStatus::Missed,
+    ];
+
+    /// Parse a status name (e.g., "failed")
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|status| status.as_str() == s.trim().to_lowercase())
+    }
+
+    /// Get the status name as stored in the database (e.g., "posted")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Scheduled => "scheduled",
+            Self::Pending => "pending",
+            Self::Posted => "posted",
+            Self::Failed => "failed",
+            Self::Deleted => "deleted",
+            Self::Missed => "missed",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +86,223 @@ pub struct PostRecord {
     pub account_name: String,
 }
 
+/// Audit record for a soft-deleted post
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tombstone {
+    pub post_id: String,
+    /// When the post was deleted (Unix timestamp)
+    pub deleted_at: i64,
+    /// Status the post had before deletion
+    pub previous_status: PostStatus,
+    pub reason: Option<String>,
+}
+
+/// Set while scheduled posting is paused (`plur-queue pause`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuePause {
+    /// When the queue was paused (Unix timestamp)
+    pub paused_at: i64,
+    pub reason: Option<String>,
+}
+
+/// The plur-send instance that owns the queue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendLease {
+    pub holder: String,
+    /// When the holder took the lease (Unix timestamp)
+    pub acquired_at: i64,
+    /// When the holder last renewed it (Unix timestamp)
+    pub renewed_at: i64,
+}
+
+/// Why and when plur-send pushed back a scheduled post
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostDeferral {
+    pub post_id: String,
+    /// When the post was last deferred (Unix timestamp)
+    pub deferred_at: i64,
+    /// The scheduled time it was moved to
+    pub deferred_until: i64,
+    /// The scheduled time before the first deferral
+    pub original_scheduled_at: Option<i64>,
+    pub reason: String,
+}
+
+/// When a staggered post goes to one platform (`--schedule-for`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatformSchedule {
+    pub post_id: String,
+    pub platform: String,
+    /// Unix timestamp
+    pub scheduled_at: i64,
+    /// When plur-send sent it to this platform, if it has
+    pub sent_at: Option<i64>,
+}
+
+/// A sensitive action recorded in the audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub id: i64,
+    /// What happened, e.g. "credential_revealed"
+    pub event: String,
+    /// What it concerned, e.g. "mastodon/work"
+    pub subject: String,
+    /// OS user who did it
+    pub actor: String,
+    /// Unix timestamp
+    pub created_at: i64,
+}
+
+/// A published post to delete when it expires (`--expires`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledDeletion {
+    pub id: i64,
+    pub post_id: String,
+    pub platform: String,
+    pub platform_post_id: String,
+    pub account_name: String,
+    /// Unix timestamp
+    pub delete_at: i64,
+    /// When the platform post was deleted, if it has been
+    pub deleted_at: Option<i64>,
+    /// Failed deletion attempts so far
+    pub attempts: i64,
+    /// Error of the last failed attempt
+    pub error: Option<String>,
+}
+
+/// Engagement of one published copy of a post, as last read
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EngagementRecord {
+    pub post_id: String,
+    pub platform: String,
+    pub platform_post_id: String,
+    pub engagement: crate::platforms::Engagement,
+    /// When the counts were read (Unix timestamp)
+    pub fetched_at: i64,
+}
+
+/// Whether one published copy of a post could still be found, as last
+/// checked
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PostCheck {
+    pub post_id: String,
+    pub platform: String,
+    pub platform_post_id: String,
+    /// Whether the platform still had the post
+    pub present: bool,
+    /// When it was checked (Unix timestamp)
+    pub checked_at: i64,
+    /// When it was first found missing, for checks since then that all
+    /// found it missing
+    pub missing_since: Option<i64>,
+}
+
+/// Timing of a single publish attempt on one platform
+///
+/// Every try is recorded, including ones that failed and were retried, so
+/// slow relays/instances and retry behaviour show up in history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostAttempt {
+    pub post_id: String,
+    pub platform: String,
+    /// 1-based attempt number within one send
+    pub attempt: u32,
+    /// Unix timestamp in milliseconds when the attempt began
+    pub started_at_ms: i64,
+    /// Unix timestamp in milliseconds when the platform answered
+    pub finished_at_ms: i64,
+    pub duration_ms: i64,
+    pub success: bool,
+    pub error_message: Option<String>,
+}
+
+/// A recurring post: the template each occurrence is created from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostSeries {
+    pub id: String,
+    /// 5-field cron expression, evaluated in local time
+    pub cron: String,
+    pub content: String,
+    /// Post metadata (platforms, Nostr options) copied onto each occurrence
+    pub metadata: Option<String>,
+    pub labels: Vec<String>,
+    /// Paused series schedule no further occurrences
+    pub paused: bool,
+    pub created_at: i64,
+}
+
+/// How one Nostr relay answered a published event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelayResult {
+    pub relay_url: String,
+    /// Whether the relay acknowledged the event with an OK
+    pub accepted: bool,
+    /// Relay message (the rejection reason, or an error reaching the relay)
+    pub message: Option<String>,
+    /// Milliseconds until the relay answered
+    pub latency_ms: Option<i64>,
+}
+
+/// Who can see a post
+///
+/// Only Mastodon distinguishes audiences; Nostr and SSB posts are always
+/// public. Stored in post metadata as `"visibility"` when not public.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    /// Visible to everyone and listed in public timelines
+    #[default]
+    Public,
+    /// Visible to everyone but kept out of public timelines
+    Unlisted,
+    /// Followers only
+    Private,
+    /// Mentioned users only
+    Direct,
+}
+
+impl Visibility {
+    /// All visibilities, from widest audience to narrowest
+    pub const ALL: [Visibility; 4] = [
+        Visibility::Public,
+        Visibility::Unlisted,
+        Visibility::Private,
+        Visibility::Direct,
+    ];
+
+    /// Parse a visibility name (e.g., "unlisted")
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s.trim().to_lowercase())
+    }
+
+    /// Get the visibility name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Unlisted => "unlisted",
+            Self::Private => "private",
+            Self::Direct => "direct",
+        }
+    }
+
+    /// The visibility recorded in a post's metadata (public if none)
+    pub fn from_metadata(metadata: Option<&str>) -> Self {
+        metadata
+            .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+            .and_then(|m| m.get("visibility")?.as_str().and_then(Self::parse))
+            .unwrap_or_default()
+    }
+}
+
+impl std::fmt::Display for Visibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 // ============================================================================
 // Attachment Types
 // ============================================================================
@@ -857,4 +1112,32 @@ mod tests {
         assert_eq!(upload.platform, cloned.platform);
         assert_eq!(upload.status, cloned.status);
     }
+
+    #[test]
+    fn test_post_status_parse() {
+        assert_eq!(PostStatus::parse("failed"), Some(PostStatus::Failed));
+        assert_eq!(PostStatus::parse(" Deleted "), Some(PostStatus::Deleted));
+        assert_eq!(PostStatus::parse("sent"), None);
+        for status in PostStatus::ALL {
+            assert_eq!(PostStatus::parse(status.as_str()), Some(status));
+        }
+    }
+
+    #[test]
+    fn test_visibility_parse_and_metadata() {
+        assert_eq!(Visibility::parse("unlisted"), Some(Visibility::Unlisted));
+        assert_eq!(Visibility::parse(" Direct "), Some(Visibility::Direct));
+        assert_eq!(Visibility::parse("friends"), None);
+        assert_eq!(Visibility::Private.to_string(), "private");
+
+        assert_eq!(
+            Visibility::from_metadata(Some(r#"{"visibility":"private"}"#)),
+            Visibility::Private
+        );
+        assert_eq!(
+            Visibility::from_metadata(Some(r#"{"platforms":["nostr"]}"#)),
+            Visibility::Public
+        );
+        assert_eq!(Visibility::from_metadata(None), Visibility::Public);
+    }
 }
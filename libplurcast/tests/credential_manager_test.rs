@@ -12,6 +12,7 @@ fn test_credential_manager_basic_operations() {
         storage: StorageBackend::Encrypted,
         path: temp_path.clone(),
         master_password: Some("test_password_123".to_string()),
+        commands: Default::default(),
     };
 
     let manager = CredentialManager::new(config).unwrap();
@@ -46,6 +47,7 @@ fn test_credential_manager_encrypted_storage() {
         storage: StorageBackend::Encrypted,
         path: temp_path.clone(),
         master_password: Some("test-password-12345".to_string()),
+        commands: Default::default(),
     };
 
     let manager = CredentialManager::new(config).unwrap();
@@ -79,6 +81,7 @@ fn test_credential_manager_fallback_logic() {
         storage: StorageBackend::Keyring,
         path: temp_path.clone(),
         master_password: Some("test_password_123".to_string()),
+        commands: Default::default(),
     };
 
     let manager = CredentialManager::new(config).unwrap();
@@ -100,6 +103,7 @@ fn test_credential_manager_retrieve_not_found() {
         storage: StorageBackend::Encrypted,
         path: temp_path,
         master_password: Some("test_password_123".to_string()),
+        commands: Default::default(),
     };
 
     let manager = CredentialManager::new(config).unwrap();
@@ -118,6 +122,7 @@ fn test_credential_manager_backends() {
         storage: StorageBackend::Encrypted,
         path: temp_path,
         master_password: Some("test_password_123".to_string()),
+        commands: Default::default(),
     };
 
     let manager = CredentialManager::new(config).unwrap();
@@ -139,6 +144,7 @@ fn test_credential_manager_security_checks() {
         storage: StorageBackend::Encrypted,
         path: temp_path.clone(),
         master_password: Some("secure-password-123".to_string()),
+        commands: Default::default(),
     };
 
     let encrypted_manager = CredentialManager::new(encrypted_config).unwrap();
@@ -150,6 +156,7 @@ fn test_credential_manager_security_checks() {
         storage: StorageBackend::Keyring,
         path: temp_path.clone(),
         master_password: Some("secure-password-123".to_string()),
+        commands: Default::default(),
     };
 
     // Keyring might not be available in test environment, so we handle both cases
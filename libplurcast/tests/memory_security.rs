@@ -19,6 +19,7 @@ fn test_nostr_keys_not_exposed_in_debug() {
         keys_file: "".to_string(),
         relays: vec![],
         default_pow_difficulty: None,
+        media_server: None,
     };
 
     let mut platform = NostrPlatform::new(&config);
@@ -53,6 +54,7 @@ fn test_keys_loaded_after_load_keys_from_string() {
         keys_file: "".to_string(),
         relays: vec![],
         default_pow_difficulty: None,
+        media_server: None,
     };
 
     let mut platform = NostrPlatform::new(&config);
@@ -78,6 +80,7 @@ fn test_nsec_keys_not_exposed_in_debug() {
         keys_file: "".to_string(),
         relays: vec![],
         default_pow_difficulty: None,
+        media_server: None,
     };
 
     let mut platform = NostrPlatform::new(&config);
@@ -108,6 +111,7 @@ fn test_drop_called_on_platform() {
         keys_file: "".to_string(),
         relays: vec![],
         default_pow_difficulty: None,
+        media_server: None,
     };
 
     // Create platform in inner scope
@@ -137,6 +141,7 @@ fn test_multiple_platforms_with_different_keys() {
         keys_file: "".to_string(),
         relays: vec![],
         default_pow_difficulty: None,
+        media_server: None,
     };
 
     let mut platform1 = NostrPlatform::new(&config.clone());
@@ -165,6 +170,7 @@ fn test_invalid_keys_not_stored() {
         keys_file: "".to_string(),
         relays: vec![],
         default_pow_difficulty: None,
+        media_server: None,
     };
 
     let mut platform = NostrPlatform::new(&config);
@@ -194,6 +200,7 @@ async fn test_keys_protected_after_authentication() {
         keys_file: "".to_string(),
         relays: vec![], // No relays to avoid network calls
         default_pow_difficulty: None,
+        media_server: None,
     };
 
     let mut platform = NostrPlatform::new(&config);
@@ -216,6 +223,7 @@ fn test_shared_test_key_protected() {
         keys_file: "".to_string(),
         relays: vec![],
         default_pow_difficulty: None,
+        media_server: None,
     };
 
     let mut platform = NostrPlatform::new(&config);
@@ -247,6 +255,7 @@ fn test_hex_key_memory_protection() {
         keys_file: "".to_string(),
         relays: vec![],
         default_pow_difficulty: None,
+        media_server: None,
     };
 
     let mut platform = NostrPlatform::new(&config);
@@ -271,6 +280,7 @@ fn test_bech32_key_memory_protection() {
         keys_file: "".to_string(),
         relays: vec![],
         default_pow_difficulty: None,
+        media_server: None,
     };
 
     let mut platform = NostrPlatform::new(&config);
@@ -295,6 +305,7 @@ fn test_keys_with_whitespace_protected() {
         keys_file: "".to_string(),
         relays: vec![],
         default_pow_difficulty: None,
+        media_server: None,
     };
 
     let mut platform = NostrPlatform::new(&config);
@@ -37,6 +37,7 @@ impl TestEnv {
             storage: StorageBackend::Encrypted,
             path: temp_path.join("credentials").to_string_lossy().to_string(),
             master_password: Some("test_password_123".to_string()),
+            commands: Default::default(),
         };
         let credential_manager = CredentialManager::new(cred_config)?;
 
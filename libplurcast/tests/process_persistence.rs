@@ -41,6 +41,7 @@ impl TestEnv {
             storage: StorageBackend::Encrypted,
             path: self.cred_path.clone(),
             master_password: Some("test_password_123".to_string()),
+            commands: Default::default(),
         };
         Ok(CredentialManager::new(config)?)
     }
@@ -249,6 +250,7 @@ fn test_keyring_persistence_windows() -> Result<()> {
             storage: StorageBackend::Keyring,
             path: env_vars.cred_path.clone(),
             master_password: Some("test_password_123".to_string()),
+            commands: Default::default(),
         };
 
         // Try to create keyring manager (may fall back to encrypted)
@@ -271,6 +273,7 @@ fn test_keyring_persistence_windows() -> Result<()> {
             storage: StorageBackend::Keyring,
             path: env_vars.cred_path.clone(),
             master_password: Some("test_password_123".to_string()),
+            commands: Default::default(),
         };
 
         if let Ok(manager) = CredentialManager::new(config) {
@@ -310,6 +313,7 @@ fn test_concurrent_read_access() -> Result<()> {
                     storage: StorageBackend::Encrypted,
                     path: cred_path,
                     master_password: Some("test_password_123".to_string()),
+                    commands: Default::default(),
                 };
                 let cred_manager = CredentialManager::new(config)?;
 
@@ -98,6 +98,7 @@ async fn test_error_messages_dont_leak_credentials() {
         keys_file: keys_file.to_str().unwrap().to_string(),
         relays: vec!["wss://invalid.relay".to_string()],
         default_pow_difficulty: None,
+        media_server: None,
     };
 
     let mut platform = NostrPlatform::new(&config);
@@ -127,12 +128,14 @@ fn test_config_doesnt_store_credentials_directly() {
     let config = Config {
         database: DatabaseConfig {
             path: ":memory:".to_string(),
+            encrypt_content: false,
         },
         nostr: Some(NostrConfig {
             enabled: true,
             keys_file: "/path/to/keys".to_string(),
             relays: vec!["wss://relay.damus.io".to_string()],
             default_pow_difficulty: None,
+            media_server: None,
         }),
         mastodon: Some(MastodonConfig {
             enabled: true,
@@ -147,6 +150,8 @@ fn test_config_doesnt_store_credentials_directly() {
         }),
         defaults: DefaultsConfig::default(),
         scheduling: None,
+        retention: None,
+        bot: None,
     };
 
     // Serialize config to verify it only contains file paths, not actual credentials
@@ -329,6 +334,7 @@ fn test_file_paths_are_expanded_safely() {
         keys_file: "~/. config/plurcast/nostr.keys".to_string(),
         relays: vec!["wss://relay.damus.io".to_string()],
         default_pow_difficulty: None,
+        media_server: None,
     };
 
     // Expand path
@@ -7,7 +7,7 @@ use std::collections::HashMap;
 use libplurcast::service::{
     history::HistoryQuery, posting::PostRequest, validation::ValidationRequest, PlurcastService,
 };
-use libplurcast::Config;
+use libplurcast::{Config, Visibility};
 use tempfile::TempDir;
 
 /// Setup test service with temporary database
@@ -18,13 +18,19 @@ async fn setup_test_service() -> (PlurcastService, TempDir) {
     let config = Config {
         database: libplurcast::config::DatabaseConfig {
             path: db_path.to_str().unwrap().to_string(),
+            encrypt_content: false,
         },
         nostr: None,
         mastodon: None,
         ssb: None,
-        defaults: libplurcast::config::DefaultsConfig { platforms: vec![] },
+        defaults: libplurcast::config::DefaultsConfig {
+            platforms: vec![],
+            language: None,
+        },
         credentials: None,
         scheduling: None,
+        retention: None,
+        bot: None,
     };
 
     let service = PlurcastService::from_config(config).await.unwrap();
@@ -95,6 +101,7 @@ async fn test_validation_before_posting() {
         content: "Hello world!".to_string(),
         platforms: vec!["nostr".to_string()],
         auto_thread: false,
+        visibility: Visibility::Public,
     };
     let response = service.validation().validate(valid_request);
     assert!(response.valid);
@@ -104,6 +111,7 @@ async fn test_validation_before_posting() {
         content: "".to_string(),
         platforms: vec!["nostr".to_string()],
         auto_thread: false,
+        visibility: Visibility::Public,
     };
     let response = service.validation().validate(invalid_request);
     assert!(!response.valid);
@@ -114,6 +122,7 @@ async fn test_validation_before_posting() {
         content: long_content,
         platforms: vec!["mastodon".to_string()],
         auto_thread: false,
+        visibility: Visibility::Public,
     };
     let response = service.validation().validate(mastodon_request);
     assert!(!response.valid);
@@ -135,6 +144,15 @@ async fn test_history_queries_after_posting() {
         reply_to: HashMap::new(),
         thread_parent_uuid: None,
         thread_sequence: None,
+        labels: vec![],
+        content_warning: None,
+        visibility: Visibility::Public,
+        language: None,
+        hashtags: vec![],
+        expires_in: None,
+        poll: None,
+        fail_fast: false,
+        media: vec![],
     };
     let response1 = service.posting().post(request1).await.unwrap();
 
@@ -149,6 +167,15 @@ async fn test_history_queries_after_posting() {
         reply_to: HashMap::new(),
         thread_parent_uuid: None,
         thread_sequence: None,
+        labels: vec![],
+        content_warning: None,
+        visibility: Visibility::Public,
+        language: None,
+        hashtags: vec![],
+        expires_in: None,
+        poll: None,
+        fail_fast: false,
+        media: vec![],
     };
     let _response2 = service.posting().post(request2).await.unwrap();
 
@@ -161,6 +188,9 @@ async fn test_history_queries_after_posting() {
         search: None,
         limit: Some(10),
         offset: None,
+        label: None,
+        account: None,
+        cursor: None,
     };
     let posts = service.history().list_posts(query).await.unwrap();
 
@@ -185,6 +215,9 @@ async fn test_history_queries_after_posting() {
         search: None,
         limit: Some(100),
         offset: None,
+        label: None,
+        account: None,
+        cursor: None,
     };
     let stats = service.history().get_stats(stats_query).await.unwrap();
     assert_eq!(stats.total_posts, 2);
@@ -209,6 +242,15 @@ async fn test_event_subscription() {
         reply_to: HashMap::new(),
         thread_parent_uuid: None,
         thread_sequence: None,
+        labels: vec![],
+        content_warning: None,
+        visibility: Visibility::Public,
+        language: None,
+        hashtags: vec![],
+        expires_in: None,
+        poll: None,
+        fail_fast: false,
+        media: vec![],
     };
 
     let response = service.posting().post(request).await.unwrap();
@@ -262,6 +304,9 @@ async fn test_count_posts() {
         search: None,
         limit: Some(100),
         offset: None,
+        label: None,
+        account: None,
+        cursor: None,
     };
     let count = service.history().count_posts(query.clone()).await.unwrap();
     assert_eq!(count, 0);
@@ -278,6 +323,15 @@ async fn test_count_posts() {
         reply_to: HashMap::new(),
         thread_parent_uuid: None,
         thread_sequence: None,
+        labels: vec![],
+        content_warning: None,
+        visibility: Visibility::Public,
+        language: None,
+        hashtags: vec![],
+        expires_in: None,
+        poll: None,
+        fail_fast: false,
+        media: vec![],
     };
     service.posting().post(request).await.unwrap();
 
@@ -303,6 +357,15 @@ async fn test_scheduled_post_workflow() {
         reply_to: HashMap::new(),
         thread_parent_uuid: None,
         thread_sequence: None,
+        labels: vec![],
+        content_warning: None,
+        visibility: Visibility::Public,
+        language: None,
+        hashtags: vec![],
+        expires_in: None,
+        poll: None,
+        fail_fast: false,
+        media: vec![],
     };
 
     let response = service.posting().post(request).await.unwrap();
@@ -359,6 +422,15 @@ async fn test_scheduled_post_no_duplicate_creation() {
         reply_to: HashMap::new(),
         thread_parent_uuid: None,
         thread_sequence: None,
+        labels: vec![],
+        content_warning: None,
+        visibility: Visibility::Public,
+        language: None,
+        hashtags: vec![],
+        expires_in: None,
+        poll: None,
+        fail_fast: false,
+        media: vec![],
     };
 
     let response = service.posting().post(request).await.unwrap();
@@ -392,6 +464,9 @@ async fn test_scheduled_post_no_duplicate_creation() {
         search: Some("Test duplicate detection".to_string()),
         limit: Some(100),
         offset: None,
+        label: None,
+        account: None,
+        cursor: None,
     };
     let posts = service.history().list_posts(query).await.unwrap();
 
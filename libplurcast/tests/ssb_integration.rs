@@ -326,6 +326,7 @@ async fn test_initialize_with_credentials_success() {
         storage: StorageBackend::Encrypted,
         path: cred_path.to_string_lossy().to_string(),
         master_password: Some("test-password-12345".to_string()),
+        commands: Default::default(),
     };
     let credentials = CredentialManager::new(cred_config).unwrap();
 
@@ -374,6 +375,7 @@ async fn test_initialize_with_credentials_not_found() {
         storage: StorageBackend::Encrypted,
         path: cred_path.to_string_lossy().to_string(),
         master_password: Some("test-password-12345".to_string()),
+        commands: Default::default(),
     };
     let credentials = CredentialManager::new(cred_config).unwrap();
 
@@ -412,6 +414,7 @@ async fn test_initialize_with_credentials_invalid_keypair() {
         storage: StorageBackend::Encrypted,
         path: cred_path.to_string_lossy().to_string(),
         master_password: Some("test-password-12345".to_string()),
+        commands: Default::default(),
     };
     let credentials = CredentialManager::new(cred_config).unwrap();
 
@@ -461,6 +464,7 @@ async fn test_initialize_twice() {
         storage: StorageBackend::Encrypted,
         path: cred_path.to_string_lossy().to_string(),
         master_password: Some("test-password-12345".to_string()),
+        commands: Default::default(),
     };
     let credentials = CredentialManager::new(cred_config).unwrap();
 
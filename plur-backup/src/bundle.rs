@@ -0,0 +1,197 @@
+//! Backup bundle format
+//!
+//! A bundle is a single JSON document (database snapshot, config file and
+//! optionally credentials) encrypted with an age passphrase. The manifest
+//! fields record which schema the database snapshot was taken at so that a
+//! restore can refuse bundles written by a newer Plurcast.
+
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use libplurcast::db::Database;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+/// Current bundle format version
+///
+/// Bump when the bundle layout changes incompatibly.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Everything needed to move a Plurcast installation to another machine
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Bundle {
+    /// Bundle layout version
+    pub format_version: u32,
+    /// When the backup was taken (Unix timestamp)
+    pub created_at: i64,
+    /// Plurcast version that wrote the bundle
+    pub plurcast_version: String,
+    /// Highest migration applied to the database snapshot
+    pub schema_version: i64,
+    /// SQLite database snapshot (base64)
+    pub database: String,
+    /// Contents of config.toml, if one existed
+    pub config: Option<String>,
+    /// Exported credentials (empty unless requested)
+    #[serde(default)]
+    pub credentials: Vec<BundledCredential>,
+}
+
+/// A single credential from the credential store
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundledCredential {
+    pub service: String,
+    pub key: String,
+    pub account: String,
+    pub value: String,
+}
+
+impl Bundle {
+    /// Create a bundle from a database snapshot
+    pub fn new(database: &[u8], schema_version: i64, config: Option<String>) -> Self {
+        Self {
+            format_version: FORMAT_VERSION,
+            created_at: chrono::Utc::now().timestamp(),
+            plurcast_version: env!("CARGO_PKG_VERSION").to_string(),
+            schema_version,
+            database: BASE64.encode(database),
+            config,
+            credentials: Vec::new(),
+        }
+    }
+
+    /// Decode the database snapshot
+    pub fn database_bytes(&self) -> Result<Vec<u8>> {
+        BASE64
+            .decode(&self.database)
+            .context("Backup contains a corrupt database snapshot")
+    }
+
+    /// Check that this build can restore the bundle
+    ///
+    /// Older schemas are fine (migrations run on restore); newer ones are not,
+    /// because this build cannot know what the extra migrations changed.
+    pub fn check_compatible(&self) -> Result<()> {
+        if self.format_version > FORMAT_VERSION {
+            bail!(
+                "Backup format version {} is newer than supported ({}). Upgrade Plurcast to restore it.",
+                self.format_version,
+                FORMAT_VERSION
+            );
+        }
+
+        let latest = Database::latest_schema_version();
+        if self.schema_version > latest {
+            bail!(
+                "Backup database schema {} is newer than this Plurcast supports ({}). \
+                 It was written by plurcast {}; upgrade to at least that version to restore it.",
+                self.schema_version,
+                latest,
+                self.plurcast_version
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Serialize and encrypt the bundle with a passphrase
+    pub fn seal(&self, passphrase: &str) -> Result<Vec<u8>> {
+        let json = serde_json::to_vec(self).context("Failed to serialize backup")?;
+
+        let encryptor =
+            age::Encryptor::with_user_passphrase(age::secrecy::Secret::new(passphrase.to_string()));
+
+        let mut sealed = vec![];
+        let mut writer = encryptor
+            .wrap_output(&mut sealed)
+            .context("Failed to encrypt backup")?;
+        writer
+            .write_all(&json)
+            .context("Failed to encrypt backup")?;
+        writer.finish().context("Failed to encrypt backup")?;
+
+        Ok(sealed)
+    }
+
+    /// Decrypt and parse a bundle
+    pub fn open(data: &[u8], passphrase: &str) -> Result<Self> {
+        let decryptor = match age::Decryptor::new(data) {
+            Ok(age::Decryptor::Passphrase(d)) => d,
+            Ok(_) => bail!("Not a Plurcast backup (expected passphrase encryption)"),
+            Err(e) => bail!("Not a Plurcast backup: {}", e),
+        };
+
+        let mut reader = decryptor
+            .decrypt(&age::secrecy::Secret::new(passphrase.to_string()), None)
+            .map_err(|_| anyhow::anyhow!("Failed to decrypt backup (wrong passphrase?)"))?;
+
+        let mut json = vec![];
+        reader
+            .read_to_end(&mut json)
+            .context("Failed to decrypt backup")?;
+
+        serde_json::from_slice(&json).context("Backup contents are not valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_bundle() -> Bundle {
+        Bundle::new(
+            b"SQLite format 3\0",
+            Database::latest_schema_version(),
+            Some("[database]\npath = \"/tmp/posts.db\"\n".to_string()),
+        )
+    }
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let mut bundle = sample_bundle();
+        bundle.credentials.push(BundledCredential {
+            service: "plurcast.nostr".to_string(),
+            key: "private_key".to_string(),
+            account: "default".to_string(),
+            value: "nsec1test".to_string(),
+        });
+
+        let sealed = bundle.seal("correct horse").unwrap();
+        let opened = Bundle::open(&sealed, "correct horse").unwrap();
+
+        assert_eq!(opened.database_bytes().unwrap(), b"SQLite format 3\0");
+        assert_eq!(opened.config, bundle.config);
+        assert_eq!(opened.credentials.len(), 1);
+        assert_eq!(opened.credentials[0].value, "nsec1test");
+    }
+
+    #[test]
+    fn test_open_with_wrong_passphrase_fails() {
+        let sealed = sample_bundle().seal("correct horse").unwrap();
+        let err = Bundle::open(&sealed, "battery staple").unwrap_err();
+        assert!(err.to_string().contains("wrong passphrase"));
+    }
+
+    #[test]
+    fn test_current_bundle_is_compatible() {
+        assert!(sample_bundle().check_compatible().is_ok());
+    }
+
+    #[test]
+    fn test_newer_schema_is_rejected() {
+        let mut bundle = sample_bundle();
+        bundle.schema_version = Database::latest_schema_version() + 1;
+
+        let err = bundle.check_compatible().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("newer than this Plurcast supports"));
+    }
+
+    #[test]
+    fn test_newer_format_is_rejected() {
+        let mut bundle = sample_bundle();
+        bundle.format_version = FORMAT_VERSION + 1;
+
+        assert!(bundle.check_compatible().is_err());
+    }
+}
@@ -0,0 +1,603 @@
+//! plur-backup - Back up and restore a Plurcast installation
+//!
+//! Produces a single passphrase-encrypted bundle containing the post
+//! database, config file and (optionally) credentials, and restores it on
+//! another machine. Also exports and merges changesets for keeping two
+//! installations in sync.
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use libplurcast::accounts::AccountManager;
+use libplurcast::config::{resolve_config_path, resolve_db_path, Config};
+use libplurcast::credentials::bundle::PLATFORM_KEYS;
+use libplurcast::credentials::CredentialManager;
+use libplurcast::db::Database;
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use libplurcast::sync::{self, Changeset};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+pub mod bundle;
+
+use bundle::{Bundle, BundledCredential};
+
+#[derive(Parser)]
+#[command(name = "plur-backup")]
+#[command(version, about = "Back up and restore Plurcast data")]
+#[command(long_about = r#"Back up and restore Plurcast data.
+
+A backup is a single file encrypted with a passphrase. It contains a
+consistent snapshot of the post database, your config.toml and, with
+--include-credentials, your platform credentials.
+
+EXAMPLES:
+    # Create a backup
+    plur-backup create -o plurcast-backup.age
+
+    # Include credentials (keys, tokens) in the backup
+    plur-backup create -o plurcast-backup.age --include-credentials
+
+    # Show what a backup contains
+    plur-backup inspect plurcast-backup.age
+
+    # Restore on a new machine
+    plur-backup restore plurcast-backup.age
+
+    # Non-interactive use
+    PLURCAST_BACKUP_PASSPHRASE=... plur-backup create -o backup.age
+
+    # Keep the history of a laptop and a server in sync (sent and
+    # deleted posts, matched by UUID; the most recently changed copy wins)
+    plur-backup export-changes --since 2025-10-01 -o laptop.json
+    plur-backup merge-changes laptop.json
+    ssh server plur-backup export-changes | plur-backup merge-changes -
+
+EXIT CODES:
+    0 - Success
+    1 - Error (wrong passphrase, incompatible backup, existing data, etc.)
+"#)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+
+    /// Verbose output
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT",
+        global = true
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL",
+        global = true
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Create an encrypted backup bundle
+    Create {
+        /// Output file
+        #[arg(short, long, value_name = "FILE")]
+        output: PathBuf,
+
+        /// Include platform credentials and the database encryption key
+        #[arg(long)]
+        include_credentials: bool,
+
+        /// Overwrite the output file if it exists
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Restore a backup bundle
+    Restore {
+        /// Backup file
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+
+        /// Overwrite an existing database and config file
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Show the contents of a backup bundle without restoring it
+    Inspect {
+        /// Backup file
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+    },
+
+    /// Export sent and deleted posts changed since a date as a mergeable changeset (JSON)
+    ExportChanges {
+        /// Only posts changed since this date (Unix timestamp, YYYY-MM-DD or RFC 3339)
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+
+        /// Output file (default: stdout)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Merge a changeset exported on another device
+    MergeChanges {
+        /// Changeset file, or - for stdin
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    let result = match cli.command {
+        Commands::Create {
+            output,
+            include_credentials,
+            force,
+        } => cmd_create(&output, include_credentials, force).await,
+        Commands::Restore { input, force } => cmd_restore(&input, force).await,
+        Commands::Inspect { input } => cmd_inspect(&input),
+        Commands::ExportChanges { since, output } => {
+            cmd_export_changes(since.as_deref(), output.as_deref()).await
+        }
+        Commands::MergeChanges { input } => cmd_merge_changes(&input).await,
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {:#}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Create a backup bundle
+async fn cmd_create(output: &Path, include_credentials: bool, force: bool) -> Result<()> {
+    if output.exists() && !force {
+        bail!(
+            "{} already exists. Use --force to overwrite it.",
+            output.display()
+        );
+    }
+
+    let config_path = resolve_config_path()?;
+    let config = Config::load().context("Failed to load configuration")?;
+    let db = Database::from_config(&config)
+        .await
+        .context("Failed to open database")?;
+
+    // The snapshot is plaintext: stage it in a private directory next to the
+    // database rather than the shared temp dir. Dropping the directory
+    // removes it, whichever way this returns.
+    let db_path = resolve_db_path(Some(&config.database.path))?;
+    let staging = tempfile::Builder::new()
+        .prefix(".plur-backup-")
+        .tempdir_in(db_path.parent().unwrap_or(Path::new(".")))
+        .context("Failed to create snapshot directory")?;
+    let snapshot_path = staging.path().join("snapshot.db");
+    db.backup_to(&snapshot_path)
+        .await
+        .context("Failed to snapshot database")?;
+    let snapshot = std::fs::read(&snapshot_path).context("Failed to read database snapshot")?;
+    drop(staging);
+
+    let config_text = std::fs::read_to_string(&config_path).ok();
+    let mut bundle = Bundle::new(&snapshot, db.schema_version().await?, config_text);
+
+    if include_credentials {
+        bundle.credentials = export_credentials(&config)?;
+    }
+
+    let passphrase = read_passphrase(true)?;
+    let sealed = bundle.seal(&passphrase)?;
+    write_private_file(output, &sealed)?;
+
+    info!("Backup written to {}", output.display());
+    println!(
+        "Backed up database (schema {}), {}, {} credential(s) to {}",
+        bundle.schema_version,
+        if bundle.config.is_some() {
+            "config"
+        } else {
+            "no config"
+        },
+        bundle.credentials.len(),
+        output.display()
+    );
+
+    Ok(())
+}
+
+/// Restore a backup bundle
+///
+/// Everything is validated before anything is written: the bundle must be
+/// compatible, and existing data is only replaced with `--force`.
+async fn cmd_restore(input: &Path, force: bool) -> Result<()> {
+    let data =
+        std::fs::read(input).with_context(|| format!("Failed to read {}", input.display()))?;
+    let passphrase = read_passphrase(false)?;
+    let bundle = Bundle::open(&data, &passphrase)?;
+    bundle.check_compatible()?;
+    let database = bundle.database_bytes()?;
+
+    // Stage the config so the database path can be resolved from it
+    let config_path = resolve_config_path()?;
+    let config = match &bundle.config {
+        Some(text) => {
+            if config_path.exists() && !force {
+                bail!(
+                    "Config file {} already exists. Use --force to replace it.",
+                    config_path.display()
+                );
+            }
+            let staged = config_path.with_extension("toml.restore");
+            if let Some(parent) = staged.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&staged, text)?;
+            match Config::load_from_path(&staged) {
+                Ok(config) => config,
+                Err(e) => {
+                    let _ = std::fs::remove_file(&staged);
+                    return Err(e).context("Backup contains an invalid config file");
+                }
+            }
+        }
+        None => Config::load().context("Failed to load configuration")?,
+    };
+
+    let db_path = resolve_db_path(Some(&config.database.path))?;
+    if db_path.exists() && !force {
+        let _ = std::fs::remove_file(config_path.with_extension("toml.restore"));
+        bail!(
+            "Database {} already exists. Use --force to replace it.",
+            db_path.display()
+        );
+    }
+
+    if bundle.config.is_some() {
+        std::fs::rename(config_path.with_extension("toml.restore"), &config_path)
+            .context("Failed to restore config file")?;
+    }
+
+    restore_database(&db_path, &database)?;
+
+    // Opening the database runs any migrations newer than the snapshot
+    let db = Database::new(&db_path.to_string_lossy())
+        .await
+        .context("Failed to open restored database")?;
+    let schema_version = db.schema_version().await?;
+
+    if !bundle.credentials.is_empty() {
+        import_credentials(&config, &bundle.credentials)?;
+    }
+
+    let has_content_key = bundle
+        .credentials
+        .iter()
+        .any(|cred| cred.service == libplurcast::encryption::KEY_SERVICE);
+    if config.database.encrypt_content && !has_content_key {
+        eprintln!(
+            "Warning: the database has encrypted content but the backup has no \
+             encryption key (created without --include-credentials). Post content \
+             stays unreadable until the key is restored to the credential store."
+        );
+    }
+
+    println!(
+        "Restored database to {} (schema {} -> {}), {} credential(s)",
+        db_path.display(),
+        bundle.schema_version,
+        schema_version,
+        bundle.credentials.len()
+    );
+
+    Ok(())
+}
+
+/// Print a bundle's manifest
+fn cmd_inspect(input: &Path) -> Result<()> {
+    let data =
+        std::fs::read(input).with_context(|| format!("Failed to read {}", input.display()))?;
+    let passphrase = read_passphrase(false)?;
+    let bundle = Bundle::open(&data, &passphrase)?;
+
+    let created = chrono::DateTime::from_timestamp(bundle.created_at, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| bundle.created_at.to_string());
+
+    println!("Created:          {}", created);
+    println!("Plurcast version: {}", bundle.plurcast_version);
+    println!(
+        "Schema version:   {} (this build: {})",
+        bundle.schema_version,
+        Database::latest_schema_version()
+    );
+    println!("Database size:    {} bytes", bundle.database_bytes()?.len());
+    println!(
+        "Config:           {}",
+        if bundle.config.is_some() {
+            "included"
+        } else {
+            "not included"
+        }
+    );
+    println!("Credentials:      {}", bundle.credentials.len());
+    for cred in &bundle.credentials {
+        println!("  {}.{} ({})", cred.service, cred.key, cred.account);
+    }
+
+    if let Err(e) = bundle.check_compatible() {
+        println!();
+        println!("Warning: {}", e);
+    }
+
+    Ok(())
+}
+
+/// Export a changeset of posts changed since a date
+async fn cmd_export_changes(since: Option<&str>, output: Option<&Path>) -> Result<()> {
+    let since = since.map(parse_since).transpose()?;
+
+    let config = Config::load().context("Failed to load configuration")?;
+    let db = Database::from_config(&config)
+        .await
+        .context("Failed to open database")?;
+
+    let changeset = sync::export_changes(&db, since)
+        .await
+        .context("Failed to export changes")?;
+    let json = serde_json::to_string_pretty(&changeset)?;
+
+    match output {
+        Some(path) => {
+            write_private_file(path, json.as_bytes())?;
+            info!(
+                "Exported {} changed post(s) to {}",
+                changeset.posts.len(),
+                path.display()
+            );
+        }
+        None => println!("{}", json),
+    }
+
+    Ok(())
+}
+
+/// Merge a changeset into the local database
+async fn cmd_merge_changes(input: &Path) -> Result<()> {
+    let json = if input == Path::new("-") {
+        let mut json = String::new();
+        std::io::stdin()
+            .read_to_string(&mut json)
+            .context("Failed to read changeset from stdin")?;
+        json
+    } else {
+        std::fs::read_to_string(input)
+            .with_context(|| format!("Failed to read {}", input.display()))?
+    };
+    let changeset: Changeset = serde_json::from_str(&json).context("Not a Plurcast changeset")?;
+
+    let config = Config::load().context("Failed to load configuration")?;
+    let db = Database::from_config(&config)
+        .await
+        .context("Failed to open database")?;
+
+    let report = sync::merge_changes(&db, &changeset)
+        .await
+        .context("Failed to merge changes")?;
+
+    println!(
+        "Merged {} post(s): {} new, {} updated, {} kept local; {} platform record(s) added",
+        changeset.posts.len() - report.skipped,
+        report.inserted,
+        report.updated,
+        report.kept_local,
+        report.records_added
+    );
+    if report.skipped > 0 {
+        println!(
+            "Skipped {} post(s) that aren't published yet (only sent and deleted posts sync)",
+            report.skipped
+        );
+    }
+
+    Ok(())
+}
+
+/// Parse a --since value as a Unix timestamp, date or RFC 3339 time
+fn parse_since(value: &str) -> Result<i64> {
+    if let Ok(timestamp) = value.parse::<i64>() {
+        return Ok(timestamp);
+    }
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.timestamp());
+    }
+    match chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Ok(date) => Ok(date.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp()),
+        Err(_) => bail!(
+            "Invalid date '{}'. Use a Unix timestamp, YYYY-MM-DD or RFC 3339.",
+            value
+        ),
+    }
+}
+
+/// Collect every known credential from the credential store
+///
+/// Covers every platform key in [`PLATFORM_KEYS`], across all accounts,
+/// plus the database encryption key.
+fn export_credentials(config: &Config) -> Result<Vec<BundledCredential>> {
+    let manager = CredentialManager::new(config.credentials.clone().unwrap_or_default())
+        .context("Failed to open credential store")?;
+
+    let platform_keys = PLATFORM_KEYS
+        .iter()
+        .flat_map(|(_, service, keys)| keys.iter().map(move |key| (*service, *key)));
+    let encryption_key = std::iter::once((
+        libplurcast::encryption::KEY_SERVICE,
+        libplurcast::encryption::KEY_NAME,
+    ));
+
+    let mut credentials = Vec::new();
+    for (service, key) in platform_keys.chain(encryption_key) {
+        for account in manager.list_accounts(service, key)? {
+            let value = manager.retrieve_account(service, key, &account)?;
+            credentials.push(BundledCredential {
+                service: service.to_string(),
+                key: key.to_string(),
+                account,
+                value,
+            });
+        }
+    }
+
+    Ok(credentials)
+}
+
+/// Store bundled credentials in the local credential store
+///
+/// Platform accounts are registered with the account manager so they show
+/// up in `plur-creds` and resolve by name.
+fn import_credentials(config: &Config, credentials: &[BundledCredential]) -> Result<()> {
+    let manager = CredentialManager::new(config.credentials.clone().unwrap_or_default())
+        .context("Failed to open credential store")?;
+    let accounts = AccountManager::new().context("Failed to open account registry")?;
+
+    for cred in credentials {
+        manager
+            .store_account(&cred.service, &cred.key, &cred.account, &cred.value)
+            .with_context(|| {
+                format!(
+                    "Failed to restore credential {}.{} ({})",
+                    cred.service, cred.key, cred.account
+                )
+            })?;
+
+        if let Some((platform, _, _)) = PLATFORM_KEYS
+            .iter()
+            .find(|(_, service, _)| *service == cred.service)
+        {
+            accounts
+                .register_account(platform, &cred.account)
+                .with_context(|| format!("Failed to register {}/{}", platform, cred.account))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Replace the database file with a snapshot
+///
+/// Stale WAL and shared-memory files from a previous database would be
+/// replayed on top of the snapshot, so they are removed first.
+fn restore_database(db_path: &Path, database: &[u8]) -> Result<()> {
+    if let Some(parent) = db_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let staged = db_path.with_extension("db.restore");
+    write_private_file(&staged, database)?;
+
+    for suffix in ["-wal", "-shm"] {
+        let mut sidecar = db_path.as_os_str().to_owned();
+        sidecar.push(suffix);
+        let _ = std::fs::remove_file(PathBuf::from(sidecar));
+    }
+
+    std::fs::rename(&staged, db_path).context("Failed to restore database file")?;
+    Ok(())
+}
+
+/// Write a file readable only by the current user
+///
+/// The file is created with its final permissions, so the data is never
+/// readable by others, not even briefly. An existing file is replaced
+/// rather than written into, as it may be readable.
+fn write_private_file(path: &Path, data: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    match std::fs::remove_file(path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e).with_context(|| format!("Failed to replace {}", path.display())),
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    file.write_all(data)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    Ok(())
+}
+
+/// Get the backup passphrase
+///
+/// Reads PLURCAST_BACKUP_PASSPHRASE, or prompts on a TTY. New passphrases
+/// are confirmed to avoid creating a backup nobody can open.
+fn read_passphrase(confirm: bool) -> Result<String> {
+    if let Ok(passphrase) = std::env::var("PLURCAST_BACKUP_PASSPHRASE") {
+        if passphrase.is_empty() {
+            bail!("PLURCAST_BACKUP_PASSPHRASE is empty");
+        }
+        return Ok(passphrase);
+    }
+
+    if !atty::is(atty::Stream::Stdin) {
+        bail!("No passphrase available. Set PLURCAST_BACKUP_PASSPHRASE or run interactively.");
+    }
+
+    let passphrase = rpassword::prompt_password("Backup passphrase: ")?;
+    if passphrase.is_empty() {
+        bail!("Passphrase cannot be empty");
+    }
+
+    if confirm {
+        let again = rpassword::prompt_password("Confirm passphrase: ")?;
+        if again != passphrase {
+            bail!("Passphrases do not match");
+        }
+    }
+
+    Ok(passphrase)
+}
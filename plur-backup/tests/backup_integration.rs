@@ -0,0 +1,262 @@
+//! Integration tests for plur-backup create/restore/inspect
+
+use assert_cmd::Command;
+use libplurcast::{Database, Post, PostStatus};
+use predicates::prelude::*;
+use std::path::Path;
+use tempfile::TempDir;
+
+const PASSPHRASE: &str = "test-backup-passphrase";
+
+/// Write a config file pointing at `db_path`
+fn write_config(config_path: &Path, db_path: &Path) {
+    std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+    std::fs::write(
+        config_path,
+        format!(
+            "[database]\npath = \"{}\"\n\n[defaults]\nplatforms = []\n",
+            db_path.to_string_lossy().replace('\\', "/")
+        ),
+    )
+    .unwrap();
+}
+
+/// Create a database with one post
+async fn create_database(db_path: &Path) -> String {
+    let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content: "Backed up post".to_string(),
+        created_at: chrono::Utc::now().timestamp(),
+        scheduled_at: None,
+        status: PostStatus::Draft,
+        metadata: None,
+    };
+    db.create_post(&post).await.unwrap();
+    post.id
+}
+
+fn plur_backup(config_path: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("plur-backup").unwrap();
+    cmd.env("PLURCAST_CONFIG", config_path)
+        .env("PLURCAST_BACKUP_PASSPHRASE", PASSPHRASE)
+        .env_remove("PLURCAST_DB_PATH");
+    cmd
+}
+
+#[tokio::test]
+async fn test_backup_and_restore_roundtrip() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("data").join("posts.db");
+    let config_path = temp_dir.path().join("config").join("config.toml");
+    let backup_path = temp_dir.path().join("backup.age");
+
+    write_config(&config_path, &db_path);
+    let post_id = create_database(&db_path).await;
+
+    plur_backup(&config_path)
+        .args(["create", "-o"])
+        .arg(&backup_path)
+        .assert()
+        .success();
+    assert!(backup_path.exists());
+
+    // Simulate a new machine: no config, no database
+    std::fs::remove_dir_all(temp_dir.path().join("data")).unwrap();
+    std::fs::remove_file(&config_path).unwrap();
+
+    plur_backup(&config_path)
+        .arg("restore")
+        .arg(&backup_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Restored database"));
+
+    assert!(config_path.exists());
+    let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+    let post = db.get_post(&post_id).await.unwrap().unwrap();
+    assert_eq!(post.content, "Backed up post");
+}
+
+#[tokio::test]
+async fn test_restore_refuses_to_overwrite_without_force() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("posts.db");
+    let config_path = temp_dir.path().join("config.toml");
+    let backup_path = temp_dir.path().join("backup.age");
+
+    write_config(&config_path, &db_path);
+    create_database(&db_path).await;
+
+    plur_backup(&config_path)
+        .args(["create", "-o"])
+        .arg(&backup_path)
+        .assert()
+        .success();
+
+    plur_backup(&config_path)
+        .arg("restore")
+        .arg(&backup_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--force"));
+
+    plur_backup(&config_path)
+        .args(["restore", "--force"])
+        .arg(&backup_path)
+        .assert()
+        .success();
+}
+
+#[tokio::test]
+async fn test_wrong_passphrase_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("posts.db");
+    let config_path = temp_dir.path().join("config.toml");
+    let backup_path = temp_dir.path().join("backup.age");
+
+    write_config(&config_path, &db_path);
+    create_database(&db_path).await;
+
+    plur_backup(&config_path)
+        .args(["create", "-o"])
+        .arg(&backup_path)
+        .assert()
+        .success();
+
+    plur_backup(&config_path)
+        .env("PLURCAST_BACKUP_PASSPHRASE", "not-the-passphrase")
+        .arg("inspect")
+        .arg(&backup_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("wrong passphrase"));
+}
+
+#[tokio::test]
+async fn test_inspect_shows_manifest() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("posts.db");
+    let config_path = temp_dir.path().join("config.toml");
+    let backup_path = temp_dir.path().join("backup.age");
+
+    write_config(&config_path, &db_path);
+    create_database(&db_path).await;
+
+    plur_backup(&config_path)
+        .args(["create", "-o"])
+        .arg(&backup_path)
+        .assert()
+        .success();
+
+    plur_backup(&config_path)
+        .arg("inspect")
+        .arg(&backup_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Schema version"))
+        .stdout(predicate::str::contains("Config:           included"));
+}
+
+#[tokio::test]
+async fn test_create_refuses_to_overwrite_output() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("posts.db");
+    let config_path = temp_dir.path().join("config.toml");
+    let backup_path = temp_dir.path().join("backup.age");
+
+    write_config(&config_path, &db_path);
+    create_database(&db_path).await;
+    std::fs::write(&backup_path, b"existing").unwrap();
+
+    plur_backup(&config_path)
+        .args(["create", "-o"])
+        .arg(&backup_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_create_writes_private_bundle_and_cleans_up() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let data_dir = temp_dir.path().join("data");
+    let db_path = data_dir.join("posts.db");
+    let config_path = temp_dir.path().join("config.toml");
+    let backup_path = temp_dir.path().join("backup.age");
+
+    write_config(&config_path, &db_path);
+    create_database(&db_path).await;
+    std::fs::write(&backup_path, b"existing").unwrap();
+    std::fs::set_permissions(&backup_path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+    plur_backup(&config_path)
+        .args(["create", "--force", "-o"])
+        .arg(&backup_path)
+        .assert()
+        .success();
+
+    let mode = std::fs::metadata(&backup_path)
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o600);
+    // The plaintext snapshot is gone
+    let leftovers: Vec<_> = std::fs::read_dir(&data_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .filter(|name| name.to_string_lossy().starts_with(".plur-backup-"))
+        .collect();
+    assert!(leftovers.is_empty(), "{:?}", leftovers);
+}
+
+#[tokio::test]
+async fn test_export_and_merge_changes_between_devices() {
+    let temp_dir = TempDir::new().unwrap();
+    let laptop_db = temp_dir.path().join("laptop.db");
+    let laptop_config = temp_dir.path().join("laptop.toml");
+    let server_db = temp_dir.path().join("server.db");
+    let server_config = temp_dir.path().join("server.toml");
+    let changes_path = temp_dir.path().join("changes.json");
+
+    write_config(&laptop_config, &laptop_db);
+    write_config(&server_config, &server_db);
+    // The draft stays on the laptop; only the sent post syncs
+    let draft_id = create_database(&laptop_db).await;
+    let laptop = Database::new(laptop_db.to_str().unwrap()).await.unwrap();
+    let sent = Post {
+        status: PostStatus::Posted,
+        ..Post::new("Sent from the laptop".to_string())
+    };
+    laptop.create_post(&sent).await.unwrap();
+    Database::new(server_db.to_str().unwrap()).await.unwrap();
+
+    plur_backup(&laptop_config)
+        .args(["export-changes", "-o"])
+        .arg(&changes_path)
+        .assert()
+        .success();
+
+    plur_backup(&server_config)
+        .arg("merge-changes")
+        .arg(&changes_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 new"));
+
+    // A second merge is a no-op
+    plur_backup(&server_config)
+        .arg("merge-changes")
+        .arg(&changes_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0 new, 0 updated, 1 kept local"));
+
+    let db = Database::new(server_db.to_str().unwrap()).await.unwrap();
+    let post = db.get_post(&sent.id).await.unwrap().unwrap();
+    assert_eq!(post.content, "Sent from the laptop");
+    assert!(db.get_post(&draft_id).await.unwrap().is_none());
+}
@@ -0,0 +1,173 @@
+//! Reading feeds and turning entries into post text
+//!
+//! As with `plur-import rss`, an entry's post comes from a template over
+//! its fields, `{{title}}\n\n{{link}}` by default, and entries are handled
+//! oldest first.
+
+use chrono::Local;
+use feed_rs::model::{Entry, Feed};
+use libplurcast::error::PlatformError;
+use libplurcast::platforms::mastodon::html_to_text;
+use libplurcast::{templates, PlurcastError, Result};
+use std::collections::HashMap;
+
+/// Template used for feeds without one
+pub const DEFAULT_TEMPLATE: &str = "{{title}}\n\n{{link}}";
+
+/// Entry fields available to templates
+const FIELDS: [&str; 4] = ["title", "link", "summary", "published"];
+
+/// Fail early on a template using anything but entry fields and built-ins
+pub fn check_template(template: &str) -> Result<()> {
+    let vars: HashMap<String, String> = FIELDS
+        .iter()
+        .map(|field| (field.to_string(), String::new()))
+        .collect();
+    templates::render(template, &vars, Local::now()).map_err(|_| {
+        PlurcastError::InvalidInput(format!(
+            "Unknown field in template: use {}, or the built-ins {{{{date}}}}, {{{{time}}}}, {{{{year}}}} and {{{{weekday}}}}",
+            FIELDS
+                .iter()
+                .map(|field| format!("{{{{{}}}}}", field))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    })?;
+    Ok(())
+}
+
+/// Download or read a feed and parse it
+///
+/// `location` is an `http(s)://` URL or a local file.
+pub async fn read(location: &str) -> Result<Feed> {
+    let body = if location.starts_with("http://") || location.starts_with("https://") {
+        let fetch_error = |e: reqwest::Error| {
+            PlatformError::Network(format!("Failed to fetch {}: {}", location, e))
+        };
+        reqwest::get(location)
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(fetch_error)?
+            .bytes()
+            .await
+            .map_err(fetch_error)?
+            .to_vec()
+    } else {
+        std::fs::read(location).map_err(|e| {
+            PlurcastError::InvalidInput(format!("Failed to read feed {}: {}", location, e))
+        })?
+    };
+
+    feed_rs::parser::parse(body.as_slice()).map_err(|e| {
+        PlurcastError::InvalidInput(format!("Failed to parse feed {}: {}", location, e))
+    })
+}
+
+/// A feed's entries, oldest first
+pub fn entries_oldest_first(feed: Feed) -> Vec<Entry> {
+    let mut entries = feed.entries;
+    entries.sort_by_key(entry_date);
+    entries
+}
+
+/// When an entry was published, or last updated if that is all it has
+fn entry_date(entry: &Entry) -> Option<i64> {
+    entry
+        .published
+        .or(entry.updated)
+        .map(|date| date.timestamp())
+}
+
+/// Post text for an entry; empty if the template leaves nothing
+pub fn render(template: &str, entry: &Entry) -> Result<String> {
+    Ok(
+        templates::render(template, &entry_fields(entry), Local::now())?
+            .trim()
+            .to_string(),
+    )
+}
+
+/// Template fields for an entry
+fn entry_fields(entry: &Entry) -> HashMap<String, String> {
+    let title = entry
+        .title
+        .as_ref()
+        .map(|t| html_to_text(&t.content))
+        .unwrap_or_default();
+    let link = entry
+        .links
+        .first()
+        .map(|l| l.href.clone())
+        .unwrap_or_default();
+    let summary = entry
+        .summary
+        .as_ref()
+        .map(|s| s.content.clone())
+        .or_else(|| entry.content.as_ref().and_then(|c| c.body.clone()))
+        .map(|html| html_to_text(&html))
+        .unwrap_or_default();
+    let published = entry
+        .published
+        .or(entry.updated)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+
+    HashMap::from([
+        ("title".to_string(), title),
+        ("link".to_string(), link),
+        ("summary".to_string(), summary),
+        ("published".to_string(), published),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ATOM: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Releases</title>
+  <id>urn:releases</id>
+  <updated>2024-03-02T12:00:00Z</updated>
+  <entry>
+    <title>v1.1 &amp; friends</title>
+    <id>tag:example.com,2024:v1.1</id>
+    <link href="https://example.com/v1.1"/>
+    <updated>2024-03-02T12:00:00Z</updated>
+    <summary type="html">&lt;p&gt;Faster&lt;/p&gt;</summary>
+  </entry>
+  <entry>
+    <title>v1.0</title>
+    <id>tag:example.com,2024:v1.0</id>
+    <link href="https://example.com/v1.0"/>
+    <updated>2024-03-01T12:00:00Z</updated>
+  </entry>
+</feed>"#;
+
+    #[test]
+    fn test_entries_render_oldest_first() {
+        let feed = feed_rs::parser::parse(ATOM.as_bytes()).unwrap();
+        let entries = entries_oldest_first(feed);
+
+        assert_eq!(entries[0].id, "tag:example.com,2024:v1.0");
+        assert_eq!(
+            render(DEFAULT_TEMPLATE, &entries[0]).unwrap(),
+            "v1.0\n\nhttps://example.com/v1.0"
+        );
+        assert_eq!(
+            render("New: {{title}} ({{published}}) {{summary}}", &entries[1]).unwrap(),
+            "New: v1.1 & friends (2024-03-02) Faster"
+        );
+        assert_eq!(render("{{summary}}", &entries[0]).unwrap(), "");
+    }
+
+    #[test]
+    fn test_check_template() {
+        assert!(check_template(DEFAULT_TEMPLATE).is_ok());
+        assert!(check_template("{{summary}} ({{date}})").is_ok());
+        match check_template("{{author}}") {
+            Err(PlurcastError::InvalidInput(msg)) => assert!(msg.contains("{{title}}"), "{}", msg),
+            other => panic!("Expected InvalidInput, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,467 @@
+//! plur-bot - Post new entries from RSS and Atom feeds
+//!
+//! Unix-style tool: checks the feeds in the config's [[bot.feeds]] and
+//! posts each new entry through a per-feed template, within the feed's
+//! rate limits. Entries already handled are recorded in the database, so
+//! nothing is posted twice. Runs until interrupted, or once with --once.
+
+use clap::Parser;
+use libplurcast::config::FeedConfig;
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use libplurcast::service::posting::{PostRequest, PostResponse};
+use libplurcast::service::validation::ValidationRequest;
+use libplurcast::service::PlurcastService;
+use libplurcast::{Config, PlurcastError, Result, Visibility};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+mod feed;
+
+#[derive(Parser, Debug)]
+#[command(name = "plur-bot")]
+#[command(version)]
+#[command(about = "Post new entries from RSS and Atom feeds")]
+#[command(long_about = "\
+plur-bot - Post new entries from RSS and Atom feeds
+
+DESCRIPTION:
+    plur-bot bridges feeds to your platforms: project release feeds, blogs,
+    anything with RSS or Atom. Every [bot] poll_interval seconds it reads
+    the feeds in the config and posts their new entries, oldest first.
+
+    The first time a feed is checked, the entries already in it are marked
+    seen, not posted; only entries that appear after that go out. Entries
+    handled are kept in the database, so each is posted once, also across
+    restarts.
+
+CONFIGURATION:
+    [bot]
+    poll_interval = 900                # seconds between checks
+
+    [[bot.feeds]]
+    name = \"releases\"                  # key for the feed's state
+    url = \"https://example.com/releases.atom\"
+    template = \"New release: {{title}}\\n\\n{{link}}\"
+    platforms = [\"nostr\", \"mastodon\"]  # default: [defaults] platforms
+    account = \"work\"                   # default: the active account
+    tags = [\"bot:releases\"]            # labels for plur-history --label
+    max_per_check = 1                  # entries posted per check
+    min_interval = 3600                # seconds between posts from the feed
+
+    Templates may use {{title}}, {{link}}, {{summary}} and {{published}},
+    and the built-ins {{date}}, {{time}}, {{year}} and {{weekday}}.
+
+USAGE EXAMPLES:
+    # Run in the foreground
+    plur-bot
+
+    # Check once, e.g. from cron
+    plur-bot --once
+
+    # See what the next check would post
+    plur-bot --dry-run --feed releases
+
+EXIT CODES:
+    0 - Success
+    1 - A feed couldn't be read or an entry couldn't be posted
+    2 - Authentication error
+    3 - Invalid input (no feeds configured, bad template, etc.)
+
+For more information, visit: https://github.com/plurcast/plurcast
+")]
+struct Cli {
+    /// Check the feeds once, then exit
+    #[arg(long)]
+    once: bool,
+
+    /// Only check these feeds, by name (comma-separated)
+    #[arg(long, value_name = "NAME", value_delimiter = ',')]
+    feed: Vec<String>,
+
+    /// Show what would be posted, without posting or recording anything
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Output format (text, json)
+    #[arg(short = 'f', long, default_value = "text", value_name = "FORMAT")]
+    #[arg(value_parser = ["text", "json"])]
+    format: String,
+
+    /// Verbose output
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT"
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL"
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    match run(cli).await {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
+
+/// Check the feeds once, or until interrupted
+///
+/// Returns whether every check read every feed and posted every entry it
+/// tried to. A feed that fails is logged and tried again on the next check.
+async fn run(cli: Cli) -> Result<bool> {
+    let config = Config::load()?;
+    let bot = config.bot.clone().unwrap_or_default();
+    let feeds = select_feeds(&bot.feeds, &cli.feed)?;
+    for feed in &feeds {
+        feed::check_template(template(feed)).map_err(|e| match e {
+            PlurcastError::InvalidInput(msg) => {
+                PlurcastError::InvalidInput(format!("Feed '{}': {}", feed.name, msg))
+            }
+            other => other,
+        })?;
+    }
+
+    let service = PlurcastService::from_config(config.clone()).await?;
+
+    let mut ok = check_feeds(&cli, &config, &service, &feeds).await;
+    if cli.once || cli.dry_run {
+        return Ok(ok);
+    }
+
+    info!(
+        "Checking {} feed(s) every {}s",
+        feeds.len(),
+        bot.poll_interval
+    );
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                info!("Interrupted, stopping");
+                return Ok(ok);
+            }
+            _ = tokio::time::sleep(Duration::from_secs(bot.poll_interval)) => {}
+        }
+
+        ok &= check_feeds(&cli, &config, &service, &feeds).await;
+    }
+}
+
+/// The configured feeds, narrowed to `--feed`
+fn select_feeds(feeds: &[FeedConfig], names: &[String]) -> Result<Vec<FeedConfig>> {
+    if feeds.is_empty() {
+        return Err(PlurcastError::InvalidInput(
+            "No feeds configured. Add [[bot.feeds]] entries to the config".to_string(),
+        ));
+    }
+    if let Some(unknown) = names
+        .iter()
+        .find(|name| !feeds.iter().any(|feed| &feed.name == *name))
+    {
+        return Err(PlurcastError::InvalidInput(format!(
+            "No feed named '{}' in the config (feeds: {})",
+            unknown,
+            feeds
+                .iter()
+                .map(|feed| feed.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+    }
+
+    Ok(feeds
+        .iter()
+        .filter(|feed| names.is_empty() || names.contains(&feed.name))
+        .cloned()
+        .collect())
+}
+
+fn template(feed: &FeedConfig) -> &str {
+    feed.template.as_deref().unwrap_or(feed::DEFAULT_TEMPLATE)
+}
+
+/// Check every feed; returns whether all went well
+async fn check_feeds(
+    cli: &Cli,
+    config: &Config,
+    service: &PlurcastService,
+    feeds: &[FeedConfig],
+) -> bool {
+    let mut ok = true;
+    for feed in feeds {
+        match check_feed(cli, config, service, feed).await {
+            Ok(checked) => ok &= checked,
+            Err(e) => {
+                warn!("Feed '{}' failed: {}", feed.name, e);
+                ok = false;
+            }
+        }
+    }
+    ok
+}
+
+/// Post a feed's new entries, as many as its rate limits allow
+///
+/// An entry whose post fails on every platform stays unseen and is tried
+/// again on the next check; one that got out anywhere is recorded, so it
+/// isn't posted twice.
+async fn check_feed(
+    cli: &Cli,
+    config: &Config,
+    service: &PlurcastService,
+    feed: &FeedConfig,
+) -> Result<bool> {
+    let db = service.database();
+    let entries = feed::entries_oldest_first(feed::read(&feed.location()?).await?);
+    debug!("Feed '{}' has {} entr(ies)", feed.name, entries.len());
+
+    if db.get_bot_feed_first_checked(&feed.name).await?.is_none() {
+        info!(
+            "First check of feed '{}': marking its {} entr(ies) as seen",
+            feed.name,
+            entries.len()
+        );
+        if !cli.dry_run {
+            let now = chrono::Utc::now().timestamp();
+            for entry in &entries {
+                db.mark_bot_entry_seen(&feed.name, &entry.id, None, now)
+                    .await?;
+            }
+            db.add_bot_feed(&feed.name, now).await?;
+        }
+        return Ok(true);
+    }
+
+    let platforms = if feed.platforms.is_empty() {
+        config.defaults.platforms.clone()
+    } else {
+        feed.platforms.clone()
+    };
+    if platforms.is_empty() {
+        return Err(PlurcastError::InvalidInput(
+            "No platforms: set platforms for the feed or [defaults] platforms".to_string(),
+        ));
+    }
+
+    let mut ok = true;
+    let mut posted = 0;
+    for entry in &entries {
+        if db.is_bot_entry_seen(&feed.name, &entry.id).await? {
+            continue;
+        }
+        if posted >= feed.max_per_check {
+            info!(
+                "Feed '{}': max_per_check reached, more entries wait for the next check",
+                feed.name
+            );
+            break;
+        }
+        if let Some(min_interval) = feed.min_interval {
+            let last = db.last_bot_post_at(&feed.name).await?;
+            if last.is_some_and(|last| chrono::Utc::now().timestamp() - last < min_interval as i64)
+            {
+                info!(
+                    "Feed '{}': posted less than {}s ago, entries wait for a later check",
+                    feed.name, min_interval
+                );
+                break;
+            }
+        }
+
+        let content = feed::render(template(feed), entry)?;
+        let validation = service.validation().validate(ValidationRequest {
+            content: content.clone(),
+            platforms: platforms.clone(),
+            auto_thread: false,
+            visibility: Visibility::Public,
+        });
+        if content.is_empty() || !validation.valid {
+            warn!(
+                "Feed '{}': skipping entry {}, its post is empty or too long",
+                feed.name, entry.id
+            );
+            if !cli.dry_run {
+                let now = chrono::Utc::now().timestamp();
+                db.mark_bot_entry_seen(&feed.name, &entry.id, None, now)
+                    .await?;
+            }
+            continue;
+        }
+
+        if cli.dry_run {
+            report_dry_run(&cli.format, &feed.name, &entry.id, &content);
+            posted += 1;
+            continue;
+        }
+
+        let response = service
+            .posting()
+            .post(PostRequest {
+                content,
+                platforms: platforms.clone(),
+                draft: false,
+                account: feed.account.clone(),
+                scheduled_at: None,
+                nostr_pow: None,
+                nostr_21e8: false,
+                reply_to: Default::default(),
+                thread_parent_uuid: None,
+                thread_sequence: None,
+                labels: feed.tags.clone(),
+                content_warning: None,
+                visibility: Visibility::Public,
+                language: config.defaults.language.clone(),
+                hashtags: vec![],
+                expires_in: None,
+                poll: None,
+                fail_fast: false,
+                media: vec![],
+            })
+            .await?;
+        report(&cli.format, &feed.name, &entry.id, &response);
+
+        if !response.results.iter().any(|r| r.success) {
+            // Try this entry again next time rather than skip ahead
+            return Ok(false);
+        }
+        let now = chrono::Utc::now().timestamp();
+        db.mark_bot_entry_seen(&feed.name, &entry.id, Some(&response.post_id), now)
+            .await?;
+        ok &= response.overall_success;
+        posted += 1;
+    }
+
+    Ok(ok)
+}
+
+/// Print what an entry's post would be
+fn report_dry_run(format: &str, feed: &str, entry_id: &str, content: &str) {
+    if format == "json" {
+        println!(
+            "{}",
+            serde_json::json!({
+                "feed": feed,
+                "entry_id": entry_id,
+                "content": content,
+                "dry_run": true,
+            })
+        );
+    } else {
+        println!("[{}] Would post {}:\n{}\n", feed, entry_id, content);
+    }
+}
+
+/// Print where an entry was posted
+fn report(format: &str, feed: &str, entry_id: &str, response: &PostResponse) {
+    if format == "json" {
+        let results: Vec<serde_json::Value> = response
+            .results
+            .iter()
+            .map(|r| {
+                serde_json::json!({
+                    "platform": r.platform,
+                    "success": r.success,
+                    "post_id": r.post_id,
+                    "error": r.error,
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "feed": feed,
+                "entry_id": entry_id,
+                "post_id": response.post_id,
+                "results": results,
+            })
+        );
+        return;
+    }
+
+    for result in &response.results {
+        match (&result.post_id, &result.error) {
+            (Some(post_id), _) if result.success => {
+                println!("[{}] {}:{}", feed, result.platform, post_id)
+            }
+            (_, Some(error)) => eprintln!("Error [{}] {}: {}", result.platform, entry_id, error),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(name: &str) -> FeedConfig {
+        FeedConfig {
+            name: name.to_string(),
+            url: format!("https://example.com/{}.xml", name),
+            template: None,
+            platforms: vec![],
+            account: None,
+            tags: vec![],
+            max_per_check: 1,
+            min_interval: None,
+        }
+    }
+
+    #[test]
+    fn test_select_feeds() {
+        let feeds = vec![feed("releases"), feed("blog")];
+
+        assert_eq!(select_feeds(&feeds, &[]).unwrap().len(), 2);
+        let selected = select_feeds(&feeds, &["blog".to_string()]).unwrap();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].name, "blog");
+
+        match select_feeds(&feeds, &["news".to_string()]) {
+            Err(PlurcastError::InvalidInput(msg)) => {
+                assert!(msg.contains("releases, blog"), "{}", msg)
+            }
+            other => panic!("Expected InvalidInput, got {:?}", other),
+        }
+        assert!(matches!(
+            select_feeds(&[], &[]),
+            Err(PlurcastError::InvalidInput(_))
+        ));
+    }
+}
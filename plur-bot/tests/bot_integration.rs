@@ -0,0 +1,137 @@
+//! Integration tests for plur-bot
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn rss(items: &[(&str, &str)]) -> String {
+    let items: String = items
+        .iter()
+        .map(|(guid, date)| {
+            format!(
+                "<item><title>Release {0}</title><link>https://example.com/{0}</link>\
+                 <guid>{0}</guid><pubDate>{1}</pubDate></item>",
+                guid, date
+            )
+        })
+        .collect();
+    format!(
+        "<?xml version=\"1.0\"?><rss version=\"2.0\"><channel><title>Releases</title>{}</channel></rss>",
+        items
+    )
+}
+
+/// Write a config with one bot feed reading `feed_path`
+fn write_config(dir: &Path, feed_path: &Path, template: &str) -> PathBuf {
+    let config_path = dir.join("config.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "[database]\npath = \"{}\"\n\n[defaults]\nplatforms = []\n\n\
+             [[bot.feeds]]\nname = \"releases\"\nurl = \"{}\"\ntemplate = \"{}\"\nplatforms = [\"mastodon\"]\n",
+            dir.join("posts.db").to_string_lossy().replace('\\', "/"),
+            feed_path.to_string_lossy().replace('\\', "/"),
+            template
+        ),
+    )
+    .unwrap();
+    config_path
+}
+
+fn plur_bot(config_path: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("plur-bot").unwrap();
+    cmd.env("PLURCAST_CONFIG", config_path)
+        .env_remove("PLURCAST_DB_PATH");
+    cmd
+}
+
+#[test]
+fn test_only_entries_after_the_first_check_are_posted() {
+    let temp_dir = TempDir::new().unwrap();
+    let feed_path = temp_dir.path().join("feed.xml");
+    std::fs::write(
+        &feed_path,
+        rss(&[("v1.0", "Fri, 01 Mar 2024 12:00:00 GMT")]),
+    )
+    .unwrap();
+    let config_path = write_config(temp_dir.path(), &feed_path, "New: {{title}} {{link}}");
+
+    // First check: v1.0 is already there, so it is only marked seen
+    plur_bot(&config_path)
+        .arg("--once")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    std::fs::write(
+        &feed_path,
+        rss(&[
+            ("v1.2", "Mon, 01 Apr 2024 12:00:00 GMT"),
+            ("v1.1", "Fri, 15 Mar 2024 12:00:00 GMT"),
+            ("v1.0", "Fri, 01 Mar 2024 12:00:00 GMT"),
+        ]),
+    )
+    .unwrap();
+
+    // One entry per check by default, oldest first
+    let output = plur_bot(&config_path)
+        .args(["--dry-run", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let lines: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0]["entry_id"], "v1.1");
+    assert_eq!(
+        lines[0]["content"],
+        "New: Release v1.1 https://example.com/v1.1"
+    );
+}
+
+#[test]
+fn test_invalid_setup() {
+    let temp_dir = TempDir::new().unwrap();
+    let feed_path = temp_dir.path().join("feed.xml");
+    std::fs::write(&feed_path, rss(&[])).unwrap();
+
+    let config_path = write_config(temp_dir.path(), &feed_path, "{{author}}");
+    plur_bot(&config_path)
+        .arg("--once")
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Feed 'releases'"));
+
+    let config_path = write_config(temp_dir.path(), &feed_path, "{{title}}");
+    plur_bot(&config_path)
+        .args(["--once", "--feed", "blog"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("No feed named 'blog'"));
+
+    std::fs::write(
+        &config_path,
+        "[database]\npath = \"posts.db\"\n\n[defaults]\nplatforms = []\n",
+    )
+    .unwrap();
+    plur_bot(&config_path)
+        .arg("--once")
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("No feeds configured"));
+}
+
+#[test]
+fn test_unreadable_feed() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = write_config(
+        temp_dir.path(),
+        &temp_dir.path().join("missing.xml"),
+        "{{title}}",
+    );
+
+    plur_bot(&config_path).arg("--once").assert().code(1);
+}
@@ -0,0 +1,1711 @@
+//! plur-creds - Credential management tool for Plurcast
+//!
+//! This tool provides commands for managing platform credentials securely.
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use libplurcast::accounts::AccountManager;
+use libplurcast::config::{resolve_db_path, Config};
+use libplurcast::credentials::bundle::CredentialBundle;
+use libplurcast::credentials::{CredentialManager, CredentialScope};
+use libplurcast::db::Database;
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use tracing::error;
+
+#[derive(Parser)]
+#[command(name = "plur-creds")]
+#[command(about = "Manage Plurcast platform credentials securely", long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+
+    /// Enable verbose logging
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT",
+        global = true
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL",
+        global = true
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Store credentials for a platform
+    Set {
+        /// Platform name (nostr, mastodon, ssb)
+        platform: String,
+
+        /// Account name (default: "default")
+        #[arg(long, default_value = "default")]
+        account: String,
+
+        /// Read credential from stdin (for automation/agents)
+        #[arg(long)]
+        stdin: bool,
+
+        /// Generate new keypair (SSB only)
+        #[arg(long)]
+        generate: bool,
+
+        /// Import from ~/.ssb/secret (SSB only)
+        #[arg(long)]
+        import: bool,
+
+        /// Scope of the credential: "write" (default) or "read" for a
+        /// read-only token used by reading features (Mastodon only)
+        #[arg(long, default_value = "write")]
+        scope: String,
+    },
+
+    /// Log in through the browser and store the resulting token (Mastodon)
+    ///
+    /// Registers Plurcast with the instance, opens its authorization page and
+    /// catches the code on a localhost redirect (or asks for it with --oob).
+    /// The token, instance and app credentials are stored for the account.
+    Login {
+        /// Platform name (mastodon)
+        platform: String,
+
+        /// Account name (default: "default")
+        #[arg(long, default_value = "default")]
+        account: String,
+
+        /// Instance to log in to (default: the instance in the [mastodon] config)
+        #[arg(long)]
+        instance: Option<String>,
+
+        /// Paste the code the instance shows instead of using a localhost redirect
+        #[arg(long)]
+        oob: bool,
+
+        /// Print the authorization URL instead of opening a browser
+        #[arg(long)]
+        no_browser: bool,
+
+        /// Ask for "write" access (default) or a "read"-only token
+        #[arg(long, default_value = "write")]
+        scope: String,
+    },
+
+    /// Export credentials in a portable, encrypted form
+    ///
+    /// With a platform, prints that account's key (nostr, with --nip49).
+    /// With --all, writes every account to a passphrase-encrypted age
+    /// bundle for `plur-creds import` on another machine.
+    Export {
+        /// Platform name (nostr); omit with --all
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        platform: Option<String>,
+
+        /// Account name (default: "default")
+        #[arg(long, default_value = "default")]
+        account: String,
+
+        /// Encrypt with a passphrase as a NIP-49 ncryptsec
+        #[arg(long)]
+        nip49: bool,
+
+        /// Export every account on every platform to an encrypted bundle
+        #[arg(long, requires = "output")]
+        all: bool,
+
+        /// File to write the bundle to (e.g. creds.age)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Import accounts from a bundle written by `export --all`
+    Import {
+        /// Bundle file (e.g. creds.age)
+        input: PathBuf,
+
+        /// Replace credentials for accounts that already exist
+        #[arg(long)]
+        overwrite: bool,
+    },
+
+    /// Show a stored credential; the secret itself only with --reveal
+    ///
+    /// --reveal asks for confirmation, prints the secret once and records
+    /// who revealed it, and when, in the audit log.
+    Show {
+        /// Platform name (nostr, mastodon, ssb)
+        platform: String,
+
+        /// Account name (default: "default")
+        #[arg(long, default_value = "default")]
+        account: String,
+
+        /// Print the secret (interactive confirmation required)
+        #[arg(long)]
+        reveal: bool,
+    },
+
+    /// List stored credentials (without showing values)
+    List {
+        /// Filter by platform (optional)
+        #[arg(long)]
+        platform: Option<String>,
+    },
+
+    /// Delete credentials for a platform
+    Delete {
+        /// Platform name (nostr, mastodon, ssb)
+        platform: String,
+
+        /// Account name (default: "default")
+        #[arg(long, default_value = "default")]
+        account: String,
+
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Set active account for a platform
+    Use {
+        /// Platform name (nostr, mastodon, ssb)
+        platform: String,
+
+        /// Account name to set as active
+        #[arg(long)]
+        account: String,
+    },
+
+    /// Test credentials by authenticating with the platform
+    Test {
+        /// Platform name (nostr, mastodon, ssb), or --all for all platforms
+        platform: Option<String>,
+
+        /// Account name (default: active account)
+        #[arg(long, default_value = "default")]
+        account: String,
+
+        /// Test all configured platforms
+        #[arg(short, long)]
+        all: bool,
+    },
+
+    /// Migrate credentials from plain text files to secure storage
+    Migrate,
+
+    /// Audit credential security
+    Audit,
+}
+
+/// Run plur-creds with a command line, program name first
+///
+/// The `plur-creds` binary and `plur creds` both start here.
+#[tokio::main]
+pub async fn main_from(args: Vec<OsString>) -> Result<()> {
+    let cli = Cli::parse_from(args);
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    // Execute command
+    if let Err(e) = run_command(cli.command).await {
+        error!("{}", e);
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+async fn run_command(command: Commands) -> Result<()> {
+    match command {
+        Commands::Set {
+            platform,
+            account,
+            stdin,
+            generate,
+            import,
+            scope,
+        } => {
+            let scope = scope.parse()?;
+            set_credentials(&platform, &account, stdin, generate, import, scope).await
+        }
+        Commands::Login {
+            platform,
+            account,
+            instance,
+            oob,
+            no_browser,
+            scope,
+        } => {
+            let scope = scope.parse()?;
+            login(
+                &platform,
+                &account,
+                instance.as_deref(),
+                oob,
+                no_browser,
+                scope,
+            )
+            .await
+        }
+        Commands::Export {
+            platform,
+            account,
+            nip49,
+            all,
+            output,
+        } => match (platform, output) {
+            (Some(platform), None) => export_credentials(&platform, &account, nip49).await,
+            (_, Some(output)) if all => export_bundle(&output).await,
+            _ => anyhow::bail!("--output is only used with --all"),
+        },
+        Commands::Import { input, overwrite } => import_bundle(&input, overwrite).await,
+        Commands::Show {
+            platform,
+            account,
+            reveal,
+        } => show_credentials(&platform, &account, reveal).await,
+        Commands::List { platform } => list_credentials(platform.as_deref()).await,
+        Commands::Delete {
+            platform,
+            account,
+            force,
+        } => delete_credentials(&platform, &account, force).await,
+        Commands::Use { platform, account } => use_account(&platform, &account).await,
+        Commands::Test {
+            platform,
+            account,
+            all,
+        } => {
+            if all {
+                test_all_credentials().await
+            } else if let Some(platform) = platform {
+                test_credentials(&platform, &account).await
+            } else {
+                anyhow::bail!("Either specify a platform or use --all flag");
+            }
+        }
+        Commands::Migrate => migrate_credentials().await,
+        Commands::Audit => audit_credentials().await,
+    }
+}
+
+/// Set SSB credentials (keypair)
+async fn set_ssb_credentials(
+    manager: &CredentialManager,
+    account_manager: &AccountManager,
+    account: &str,
+    use_stdin: bool,
+    generate: bool,
+    import: bool,
+) -> Result<()> {
+    use libplurcast::platforms::ssb::{SSBKeypair, SSBPlatform};
+
+    // Check for conflicting flags
+    if (generate as u8 + import as u8 + use_stdin as u8) > 1 {
+        anyhow::bail!("Cannot use --generate, --import, and --stdin together. Choose one.");
+    }
+
+    // If a credential already exists, require explicit confirmation before overwriting
+    if manager.exists_account("plurcast.ssb", "keypair", account)? {
+        if use_stdin || !atty::is(atty::Stream::Stdin) {
+            anyhow::bail!(
+                "SSB credentials for account '{}' already exist. Refusing to overwrite in non-interactive mode. \
+                 Run interactively or delete first with 'plur-creds delete ssb --account {}'.",
+                account, account
+            );
+        } else {
+            use std::io::{self, Write};
+            println!(
+                "\n⚠️  SSB keypair already exists for account '{}'. This will OVERWRITE the existing keypair.",
+                account
+            );
+            print!("Type 'overwrite' to confirm (or anything else to cancel): ");
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            if input.trim() != "overwrite" {
+                println!("Cancelled");
+                return Ok(());
+            }
+        }
+    }
+
+    let keypair = if generate {
+        // Generate new keypair
+        println!("Generating new SSB keypair...");
+        let kp = SSBKeypair::generate();
+        println!("✓ Generated new SSB keypair");
+        println!("  Feed ID: {}", kp.id);
+        kp
+    } else if import {
+        // Import from ~/.ssb/secret
+        println!("Importing SSB keypair from ~/.ssb/secret...");
+
+        let home = dirs::home_dir()
+            .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
+        let secret_path = home.join(".ssb").join("secret");
+
+        if !secret_path.exists() {
+            anyhow::bail!(
+                "SSB secret file not found at {}. \
+                 Use --generate to create a new keypair instead.",
+                secret_path.display()
+            );
+        }
+
+        let secret_content = std::fs::read_to_string(&secret_path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", secret_path.display(), e))?;
+
+        // Parse SSB secret file format (JSON with comments)
+        let json_content = secret_content
+            .lines()
+            .filter(|line| !line.trim().starts_with('#'))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let kp = SSBKeypair::from_json(&json_content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse SSB secret file: {}", e))?;
+
+        println!("✓ Imported SSB keypair from {}", secret_path.display());
+        println!("  Feed ID: {}", kp.id);
+        kp
+    } else if use_stdin {
+        // Read keypair JSON from stdin
+        use std::io::{self, Read};
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+
+        let kp = SSBKeypair::from_json(buffer.trim())
+            .map_err(|e| anyhow::anyhow!("Failed to parse SSB keypair JSON: {}", e))?;
+
+        kp
+    } else {
+        // Interactive mode: prompt for choice
+        if !atty::is(atty::Stream::Stdin) {
+            anyhow::bail!(
+                "Not a TTY. Use --generate to create a new keypair, --import to import from ~/.ssb/secret, \
+                 or --stdin to read keypair JSON from stdin."
+            );
+        }
+
+        use std::io::{self, Write};
+
+        println!("\nSSB Keypair Setup for account '{}'", account);
+        println!("Choose an option:");
+        println!("  1. Generate new keypair");
+        println!("  2. Import from ~/.ssb/secret");
+        println!("  3. Enter keypair JSON manually");
+        print!("\nChoice [1-3]: ");
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        match input.trim() {
+            "1" => {
+                println!("\nGenerating new SSB keypair...");
+                let kp = SSBKeypair::generate();
+                println!("✓ Generated new SSB keypair");
+                println!("  Feed ID: {}", kp.id);
+                kp
+            }
+            "2" => {
+                println!("\nImporting SSB keypair from ~/.ssb/secret...");
+
+                let home = dirs::home_dir()
+                    .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
+                let secret_path = home.join(".ssb").join("secret");
+
+                if !secret_path.exists() {
+                    anyhow::bail!(
+                        "SSB secret file not found at {}. \
+                         Choose option 1 to generate a new keypair instead.",
+                        secret_path.display()
+                    );
+                }
+
+                let secret_content = std::fs::read_to_string(&secret_path).map_err(|e| {
+                    anyhow::anyhow!("Failed to read {}: {}", secret_path.display(), e)
+                })?;
+
+                // Parse SSB secret file format (JSON with comments)
+                let json_content = secret_content
+                    .lines()
+                    .filter(|line| !line.trim().starts_with('#'))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                let kp = SSBKeypair::from_json(&json_content)
+                    .map_err(|e| anyhow::anyhow!("Failed to parse SSB secret file: {}", e))?;
+
+                println!("✓ Imported SSB keypair from {}", secret_path.display());
+                println!("  Feed ID: {}", kp.id);
+                kp
+            }
+            "3" => {
+                use std::io::Read;
+                println!("\nEnter SSB keypair JSON (paste and press Ctrl+D when done):");
+                let mut buffer = String::new();
+                io::stdin().read_to_string(&mut buffer)?;
+
+                let kp = SSBKeypair::from_json(buffer.trim())
+                    .map_err(|e| anyhow::anyhow!("Failed to parse SSB keypair JSON: {}", e))?;
+
+                println!("✓ Parsed SSB keypair");
+                println!("  Feed ID: {}", kp.id);
+                kp
+            }
+            _ => anyhow::bail!("Invalid choice. Please enter 1, 2, or 3."),
+        }
+    };
+
+    // Validate keypair
+    keypair
+        .validate()
+        .map_err(|e| anyhow::anyhow!("Invalid SSB keypair: {}", e))?;
+
+    // Store the keypair (force=true because we already checked for overwrites above)
+    SSBPlatform::store_keypair(manager, &keypair, account, true)?;
+
+    // Register account with AccountManager
+    account_manager.register_account("ssb", account)?;
+
+    println!(
+        "✓ Stored SSB keypair for account '{}' securely using {} backend",
+        account,
+        manager.primary_backend().unwrap_or("unknown")
+    );
+
+    Ok(())
+}
+
+/// Set credentials for a platform
+async fn set_credentials(
+    platform: &str,
+    account: &str,
+    use_stdin: bool,
+    generate: bool,
+    import: bool,
+    scope: CredentialScope,
+) -> Result<()> {
+    // Validate account name
+    AccountManager::validate_account_name(account)?;
+
+    if scope == CredentialScope::Read && !platform.eq_ignore_ascii_case("mastodon") {
+        anyhow::bail!("Read-only credentials are only supported for mastodon");
+    }
+
+    // Load config to get credential configuration
+    let config = Config::load()?;
+
+    // Get or create credential config
+    let cred_config = config.credentials.unwrap_or_default();
+
+    // Create credential manager and account manager
+    let manager = CredentialManager::new(cred_config)?;
+    let account_manager = AccountManager::new()?;
+
+    // Handle SSB separately due to keypair generation/import
+    if platform.to_lowercase() == "ssb" {
+        return set_ssb_credentials(
+            &manager,
+            &account_manager,
+            account,
+            use_stdin,
+            generate,
+            import,
+        )
+        .await;
+    }
+
+    // Determine service and key based on platform
+    let (service, key, prompt) = match platform.to_lowercase().as_str() {
+        "nostr" => (
+            "plurcast.nostr",
+            "private_key",
+            format!(
+                "Enter Nostr private key for account '{}' (hex, nsec or ncryptsec format): ",
+                account
+            ),
+        ),
+        "mastodon" => (
+            "plurcast.mastodon",
+            "access_token",
+            format!(
+                "Enter Mastodon OAuth {} token for account '{}': ",
+                if scope == CredentialScope::Read {
+                    "read-only access"
+                } else {
+                    "access"
+                },
+                account
+            ),
+        ),
+        _ => anyhow::bail!(
+            "Unknown platform: {}. Supported platforms: nostr, mastodon, ssb",
+            platform
+        ),
+    };
+    let key = scope.key(key);
+    let key = key.as_str();
+
+    // If a credential already exists, require explicit confirmation before overwriting
+    if manager.exists_account(service, key, account)? {
+        if use_stdin || !atty::is(atty::Stream::Stdin) {
+            anyhow::bail!(
+                "Credentials for '{}' account '{}' already exist. Refusing to overwrite in non-interactive mode. \
+                 Run interactively or delete first with 'plur-creds delete {} --account {}'.",
+                platform, account, platform, account
+            );
+        } else {
+            use std::io::{self, Write};
+            println!(
+                "\n⚠️  A credential already exists for '{}' account '{}'. This will OVERWRITE the existing secret.",
+                platform, account
+            );
+            print!("Type 'overwrite' to confirm (or anything else to cancel): ");
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            if input.trim() != "overwrite" {
+                println!("Cancelled");
+                return Ok(());
+            }
+        }
+    }
+
+    // Get credential value: either from stdin or interactive prompt
+    let value = if use_stdin {
+        // Explicit stdin mode: for automation/agents
+        use std::io::{self, Read};
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        buffer.trim().to_string()
+    } else {
+        // Interactive mode: secure password prompt
+        if !atty::is(atty::Stream::Stdin) {
+            anyhow::bail!(
+                "Not a TTY. Use --stdin flag to read credentials from stdin for automation."
+            );
+        }
+        rpassword::prompt_password(&prompt)?
+    };
+
+    if value.is_empty() {
+        anyhow::bail!("Credential value cannot be empty");
+    }
+
+    // NIP-49 keys are stored decrypted so posting doesn't need the passphrase
+    let value = if platform.eq_ignore_ascii_case("nostr")
+        && value.starts_with(libplurcast::platforms::nostr::NCRYPTSEC_PREFIX)
+    {
+        let passphrase = nip49_passphrase(false)?;
+        libplurcast::platforms::nostr::decrypt_ncryptsec(&value, &passphrase)?
+    } else {
+        value
+    };
+
+    // Validate credential format for Nostr
+    if platform.to_lowercase() == "nostr" {
+        let trimmed = value.trim();
+        if trimmed.len() != 64 && !trimmed.starts_with("nsec") {
+            anyhow::bail!(
+                "Invalid Nostr key format. Must be 64-character hex, bech32 nsec or NIP-49 ncryptsec format."
+            );
+        }
+    }
+
+    // Store the credential
+    manager.store_account(service, key, account, &value)?;
+
+    // Register account with AccountManager
+    account_manager.register_account(platform, account)?;
+
+    println!(
+        "✓ Stored {} credentials for account '{}' securely using {} backend",
+        platform,
+        account,
+        manager.primary_backend().unwrap_or("unknown")
+    );
+
+    Ok(())
+}
+
+/// Log in to a platform with OAuth and store the token
+async fn login(
+    platform: &str,
+    account: &str,
+    instance: Option<&str>,
+    oob: bool,
+    no_browser: bool,
+    scope: CredentialScope,
+) -> Result<()> {
+    use libplurcast::platforms::mastodon;
+    use std::io::{self, Write};
+
+    // Validate account name
+    AccountManager::validate_account_name(account)?;
+
+    if !platform.eq_ignore_ascii_case("mastodon") {
+        anyhow::bail!(
+            "Login is only supported for mastodon. Use 'plur-creds set {}' to store credentials.",
+            platform
+        );
+    }
+
+    let config = Config::load()?;
+    let instance = match instance {
+        Some(instance) => instance.to_string(),
+        None => match config.mastodon.as_ref().map(|m| m.instance.trim()) {
+            Some(instance) if !instance.is_empty() => instance.to_string(),
+            _ => anyhow::bail!(
+                "No Mastodon instance given. Use --instance (e.g. --instance mastodon.social) \
+                 or set 'instance' in the [mastodon] section of the config."
+            ),
+        },
+    };
+
+    let cred_config = config.credentials.unwrap_or_default();
+    let manager = CredentialManager::new(cred_config)?;
+    let account_manager = AccountManager::new()?;
+
+    // If a credential already exists, require explicit confirmation before overwriting
+    let token_key = scope.key("access_token");
+    if manager.exists_account("plurcast.mastodon", &token_key, account)? {
+        if !atty::is(atty::Stream::Stdin) {
+            anyhow::bail!(
+                "Credentials for 'mastodon' account '{}' already exist. Refusing to overwrite in non-interactive mode. \
+                 Run interactively or delete first with 'plur-creds delete mastodon --account {}'.",
+                account, account
+            );
+        }
+        println!(
+            "\n⚠️  A credential already exists for 'mastodon' account '{}'. Logging in will OVERWRITE it.",
+            account
+        );
+        print!("Type 'overwrite' to confirm (or anything else to cancel): ");
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if input.trim() != "overwrite" {
+            println!("Cancelled");
+            return Ok(());
+        }
+    }
+
+    // The instance redirects to a one-shot listener here, unless the user
+    // copies the code by hand
+    let listener = if oob {
+        None
+    } else {
+        Some(std::net::TcpListener::bind("127.0.0.1:0")?)
+    };
+    let redirect_uri = match &listener {
+        Some(listener) => format!(
+            "http://127.0.0.1:{}/callback",
+            listener.local_addr()?.port()
+        ),
+        None => mastodon::OOB_REDIRECT_URI.to_string(),
+    };
+
+    println!("Registering Plurcast with {}...", instance);
+    let app = mastodon::register_app(&instance, &redirect_uri, scope).await?;
+
+    println!(
+        "\nOpen this URL to authorize Plurcast:\n\n  {}\n",
+        app.authorize_url
+    );
+    if !no_browser {
+        if let Err(e) = open_url(&app.authorize_url) {
+            println!("Could not open a browser ({}); open the URL yourself.", e);
+        }
+    }
+
+    let code = match listener {
+        Some(listener) => {
+            println!("Waiting for the instance to redirect back (Ctrl+C to cancel)...");
+            tokio::task::spawn_blocking(move || wait_for_code(listener)).await??
+        }
+        None => {
+            print!("Paste the authorization code: ");
+            io::stdout().flush()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            input.trim().to_string()
+        }
+    };
+    if code.is_empty() {
+        anyhow::bail!("No authorization code given");
+    }
+
+    let token = mastodon::exchange_code(&app, &code).await?;
+
+    manager.store_account("plurcast.mastodon", &token_key, account, &token)?;
+    manager.store_account("plurcast.mastodon", "instance", account, &app.instance_url)?;
+    manager.store_account("plurcast.mastodon", "client_id", account, &app.client_id)?;
+    manager.store_account(
+        "plurcast.mastodon",
+        "client_secret",
+        account,
+        &app.client_secret,
+    )?;
+    account_manager.register_account("mastodon", account)?;
+
+    println!(
+        "✓ Logged in to {} as mastodon account '{}' ({} access); stored securely using {} backend",
+        app.instance_url,
+        account,
+        scope,
+        manager.primary_backend().unwrap_or("unknown")
+    );
+
+    Ok(())
+}
+
+/// Accept connections until one carries the OAuth redirect, and return its code
+fn wait_for_code(listener: std::net::TcpListener) -> Result<String> {
+    use std::io::{BufRead, BufReader, Write};
+
+    for stream in listener.incoming() {
+        let mut stream = stream?;
+        let mut request_line = String::new();
+        BufReader::new(&stream).read_line(&mut request_line)?;
+
+        // GET /callback?code=...&state=... HTTP/1.1
+        let target = request_line.split_whitespace().nth(1).unwrap_or("");
+        let query = match target.split_once('?') {
+            Some((path, query)) if path == "/callback" => query,
+            _ => {
+                // Browsers also ask for /favicon.ico and the like
+                stream.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")?;
+                continue;
+            }
+        };
+        let param = |name: &str| {
+            query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| percent_decode(value))
+        };
+
+        let (body, result) = match (param("code"), param("error")) {
+            (Some(code), _) => ("Plurcast is authorized. You can close this tab.", Ok(code)),
+            (None, error) => (
+                "Authorization failed. Check the terminal.",
+                Err(anyhow::anyhow!(
+                    "Authorization failed: {}",
+                    error.unwrap_or_else(|| "no code in the redirect".to_string())
+                )),
+            ),
+        };
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )?;
+        return result;
+    }
+
+    anyhow::bail!("Stopped listening before the instance redirected back")
+}
+
+/// Decode `%XX` escapes and `+` in a query string value
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Open a URL with `$BROWSER` (which may include arguments), or the
+/// system's default handler
+fn open_url(url: &str) -> Result<()> {
+    let browser = match std::env::var("BROWSER") {
+        Ok(browser) if !browser.trim().is_empty() => browser,
+        _ if cfg!(target_os = "macos") => "open".to_string(),
+        _ if cfg!(target_os = "windows") => "explorer".to_string(),
+        _ => "xdg-open".to_string(),
+    };
+    let mut parts = browser.split_whitespace();
+    // Checked non-empty above
+    let program = parts.next().unwrap_or("xdg-open");
+
+    std::process::Command::new(program)
+        .args(parts)
+        .arg(url)
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("failed to run '{}': {}", program, e))?;
+    Ok(())
+}
+
+/// Environment variable holding the NIP-49 passphrase, for automation
+const NIP49_PASSPHRASE_ENV: &str = "PLURCAST_NIP49_PASSPHRASE";
+
+/// Environment variable holding the credential bundle passphrase, for automation
+const BUNDLE_PASSPHRASE_ENV: &str = "PLURCAST_BUNDLE_PASSPHRASE";
+
+/// Get the NIP-49 passphrase from the environment or a prompt
+///
+/// With `confirm`, the prompt asks twice (for a new passphrase).
+fn nip49_passphrase(confirm: bool) -> Result<String> {
+    read_passphrase(NIP49_PASSPHRASE_ENV, "NIP-49 passphrase: ", confirm)
+}
+
+/// Read a passphrase from `env`, or prompt for it
+fn read_passphrase(env: &str, prompt: &str, confirm: bool) -> Result<String> {
+    if let Ok(passphrase) = std::env::var(env) {
+        if !passphrase.is_empty() {
+            return Ok(passphrase);
+        }
+    }
+
+    let passphrase = rpassword::prompt_password(prompt).map_err(|e| {
+        anyhow::anyhow!(
+            "Failed to read the passphrase ({}). Set {} for non-interactive use.",
+            e,
+            env
+        )
+    })?;
+    if passphrase.is_empty() {
+        anyhow::bail!("Passphrase cannot be empty");
+    }
+    if confirm && rpassword::prompt_password("Repeat passphrase: ")? != passphrase {
+        anyhow::bail!("Passphrases do not match");
+    }
+    Ok(passphrase)
+}
+
+/// Export a stored key, encrypted
+async fn export_credentials(platform: &str, account: &str, nip49: bool) -> Result<()> {
+    // Validate account name
+    AccountManager::validate_account_name(account)?;
+
+    if !platform.eq_ignore_ascii_case("nostr") {
+        anyhow::bail!("Export is only supported for nostr");
+    }
+    if !nip49 {
+        anyhow::bail!("Keys are only exported encrypted. Use --nip49 to export an ncryptsec.");
+    }
+
+    let config = Config::load()?;
+    let cred_config = config.credentials.unwrap_or_default();
+    let manager = CredentialManager::new(cred_config)?;
+
+    if !manager.exists_account("plurcast.nostr", "private_key", account)? {
+        anyhow::bail!(
+            "No credentials found for nostr account '{}'. Use 'plur-creds set nostr --account {}' to store them.",
+            account,
+            account
+        );
+    }
+    let key = manager.retrieve_account("plurcast.nostr", "private_key", account)?;
+
+    let passphrase = nip49_passphrase(true)?;
+    let ncryptsec = libplurcast::platforms::nostr::encrypt_ncryptsec(&key, &passphrase)?;
+    println!("{}", ncryptsec);
+
+    Ok(())
+}
+
+/// Write every stored account to an encrypted bundle
+async fn export_bundle(output: &Path) -> Result<()> {
+    let config = Config::load()?;
+    let cred_config = config.credentials.unwrap_or_default();
+    let manager = CredentialManager::new(cred_config)?;
+    let account_manager = AccountManager::new()?;
+
+    let bundle = CredentialBundle::collect(&manager, &account_manager)?;
+    if bundle.accounts.is_empty() {
+        anyhow::bail!("No credentials found to export.");
+    }
+
+    let passphrase = read_passphrase(BUNDLE_PASSPHRASE_ENV, "Bundle passphrase: ", true)?;
+    let sealed = bundle.seal(&passphrase)?;
+
+    std::fs::write(output, sealed)
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", output.display(), e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(output, std::fs::Permissions::from_mode(0o600))?;
+    }
+
+    println!(
+        "✓ Exported {} account(s) to {}",
+        bundle.accounts.len(),
+        output.display()
+    );
+    for account in &bundle.accounts {
+        println!("  {} ({})", account.platform, account.account);
+    }
+
+    Ok(())
+}
+
+/// Restore accounts from a bundle written by `export --all`
+async fn import_bundle(input: &Path, overwrite: bool) -> Result<()> {
+    let sealed = std::fs::read(input)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", input.display(), e))?;
+    let passphrase = read_passphrase(BUNDLE_PASSPHRASE_ENV, "Bundle passphrase: ", false)?;
+    let bundle = CredentialBundle::open(&sealed, &passphrase)?;
+
+    let config = Config::load()?;
+    let cred_config = config.credentials.unwrap_or_default();
+    let manager = CredentialManager::new(cred_config)?;
+    let account_manager = AccountManager::new()?;
+
+    let report = bundle.restore(&manager, &account_manager, overwrite)?;
+
+    println!(
+        "✓ Imported {} account(s) from {}",
+        report.imported.len(),
+        input.display()
+    );
+    for account in &report.imported {
+        println!("  {}", account);
+    }
+    if !report.skipped.is_empty() {
+        println!(
+            "ℹ Skipped {} account(s) that already exist (use --overwrite to replace):",
+            report.skipped.len()
+        );
+        for account in &report.skipped {
+            println!("  {}", account);
+        }
+    }
+
+    Ok(())
+}
+
+/// Set active account for a platform
+async fn use_account(platform: &str, account: &str) -> Result<()> {
+    // Validate account name
+    AccountManager::validate_account_name(account)?;
+
+    // Validate platform
+    let platform_lower = platform.to_lowercase();
+    if !["nostr", "mastodon", "ssb"].contains(&platform_lower.as_str()) {
+        anyhow::bail!(
+            "Unknown platform: {}. Supported platforms: nostr, mastodon, ssb",
+            platform
+        );
+    }
+
+    // Load config and credential manager to check if credentials exist
+    let config = Config::load()?;
+    let cred_config = config.credentials.unwrap_or_default();
+    let manager = CredentialManager::new(cred_config)?;
+
+    // Determine service and key based on platform
+    let (service, key) = match platform_lower.as_str() {
+        "nostr" => ("plurcast.nostr", "private_key"),
+        "mastodon" => ("plurcast.mastodon", "access_token"),
+        "ssb" => ("plurcast.ssb", "keypair"),
+        _ => unreachable!(), // Already validated above
+    };
+
+    // Check if credentials exist for this account
+    if !manager.exists_account(service, key, account)? {
+        anyhow::bail!(
+            "Account '{}' not found for platform '{}'. Use 'plur-creds set {} --account {}' to create it.",
+            account, platform, platform, account
+        );
+    }
+
+    // Load account manager and set as active account
+    let account_manager = AccountManager::new()?;
+    account_manager.set_active_account(&platform_lower, account)?;
+
+    println!("✓ Set '{}' as active account for {}", account, platform);
+
+    Ok(())
+}
+
+/// Audit log event for `show --reveal`
+const REVEAL_EVENT: &str = "credential_revealed";
+
+/// Show a stored credential, printing the secret only with `--reveal`
+async fn show_credentials(platform: &str, account: &str, reveal: bool) -> Result<()> {
+    use std::io::{self, Write};
+
+    // Validate account name
+    AccountManager::validate_account_name(account)?;
+
+    let platform_lower = platform.to_lowercase();
+    let (service, key, credential_type) = match platform_lower.as_str() {
+        "nostr" => ("plurcast.nostr", "private_key", "private key"),
+        "mastodon" => ("plurcast.mastodon", "access_token", "access token"),
+        "ssb" => ("plurcast.ssb", "keypair", "keypair"),
+        _ => anyhow::bail!(
+            "Unknown platform: {}. Supported platforms: nostr, mastodon, ssb",
+            platform
+        ),
+    };
+
+    let config = Config::load()?;
+    let manager = CredentialManager::new(config.credentials.clone().unwrap_or_default())?;
+
+    if !manager.exists_account(service, key, account)? {
+        anyhow::bail!(
+            "No credentials found for {} account '{}'",
+            platform_lower,
+            account
+        );
+    }
+
+    if !reveal {
+        println!(
+            "{} ({}): {} stored",
+            platform_lower, account, credential_type
+        );
+        println!("Use --reveal to print it (asks first, and is recorded in the audit log).");
+        return Ok(());
+    }
+
+    if !atty::is(atty::Stream::Stdin) {
+        anyhow::bail!("--reveal must be confirmed interactively; run it in a terminal");
+    }
+    print!(
+        "Print the {} for {} account '{}'? This is recorded in the audit log. Type 'reveal' to confirm: ",
+        credential_type, platform_lower, account
+    );
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    if input.trim() != "reveal" {
+        println!("Cancelled");
+        return Ok(());
+    }
+
+    let secret = manager.retrieve_account(service, key, account)?;
+
+    // Record first: a reveal that can't be audited doesn't happen
+    let actor = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string());
+    let now = chrono::Utc::now().timestamp();
+    let db = Database::from_config(&config).await?;
+    db.record_audit_event(
+        REVEAL_EVENT,
+        &format!("{}/{}", platform_lower, account),
+        &actor,
+        now,
+    )
+    .await?;
+
+    println!("{}", secret);
+
+    Ok(())
+}
+
+/// List stored credentials
+async fn list_credentials(platform_filter: Option<&str>) -> Result<()> {
+    // Load config to get credential configuration
+    let config = Config::load()?;
+
+    // Get or create credential config
+    let cred_config = config.credentials.unwrap_or_default();
+
+    // Create credential manager and account manager
+    let manager = CredentialManager::new(cred_config)?;
+    let account_manager = AccountManager::new()?;
+
+    println!("Stored credentials:");
+    println!();
+
+    // Define platforms to check
+    let all_platforms = vec![
+        ("nostr", "plurcast.nostr", "private_key", "Private Key"),
+        (
+            "mastodon",
+            "plurcast.mastodon",
+            "access_token",
+            "Access Token",
+        ),
+        (
+            "mastodon",
+            "plurcast.mastodon",
+            "access_token_read",
+            "Read-only Token",
+        ),
+        ("ssb", "plurcast.ssb", "keypair", "Keypair"),
+    ];
+
+    // Filter platforms if requested
+    let platforms: Vec<_> = if let Some(filter) = platform_filter {
+        all_platforms
+            .into_iter()
+            .filter(|(name, _, _, _)| name.eq_ignore_ascii_case(filter))
+            .collect()
+    } else {
+        all_platforms
+    };
+
+    if platforms.is_empty() {
+        anyhow::bail!(
+            "Unknown platform: {}. Supported platforms: nostr, mastodon, ssb",
+            platform_filter.unwrap_or("")
+        );
+    }
+
+    let mut found_any = false;
+
+    for (platform_name, service, key, credential_type) in platforms.iter().copied() {
+        // Get all accounts for this platform from AccountManager
+        // (CredentialManager.list_accounts() returns empty for keyring since it can't enumerate)
+        let accounts = account_manager.list_accounts(platform_name);
+
+        if !accounts.is_empty() {
+            // Get active account for this platform
+            let active_account = account_manager.get_active_account(platform_name);
+
+            for account in &accounts {
+                // Verify the credential actually exists
+                if !manager.exists_account(service, key, account)? {
+                    continue; // Skip if credential doesn't exist (stale registry entry)
+                }
+
+                // Find which backend has it
+                let backend = manager.primary_backend().unwrap_or("unknown");
+
+                // Mark active account
+                let active_marker = if account == &active_account {
+                    " [active]"
+                } else {
+                    ""
+                };
+
+                println!(
+                    "  ✓ {} ({}): {} (stored in {}){}",
+                    platform_name, account, credential_type, backend, active_marker
+                );
+                found_any = true;
+            }
+        }
+    }
+
+    // Environment variables, secret files and [credentials.commands] entries
+    // are read at use time, not stored
+    for backend in ["environment", "command"] {
+        for (platform_name, service, key, credential_type) in &platforms {
+            for account in manager.list_accounts_from(backend, service, key)? {
+                println!(
+                    "  ✓ {} ({}): {} (from {})",
+                    platform_name, account, credential_type, backend
+                );
+                found_any = true;
+            }
+        }
+    }
+
+    if !found_any {
+        println!("  No credentials found.");
+        println!();
+        println!("Use 'plur-creds set <platform> --account <name>' to store credentials.");
+    }
+
+    Ok(())
+}
+
+/// Delete credentials for a platform
+async fn delete_credentials(platform: &str, account: &str, force: bool) -> Result<()> {
+    // Validate account name
+    AccountManager::validate_account_name(account)?;
+
+    // Load config to get credential configuration
+    let config = Config::load()?;
+
+    // Get or create credential config
+    let cred_config = config.credentials.unwrap_or_default();
+
+    // Create credential manager and account manager
+    let manager = CredentialManager::new(cred_config)?;
+    let account_manager = AccountManager::new()?;
+
+    // Determine service and key based on platform
+    let platform_lower = platform.to_lowercase();
+    let (service, key) = match platform_lower.as_str() {
+        "nostr" => ("plurcast.nostr", "private_key"),
+        "mastodon" => ("plurcast.mastodon", "access_token"),
+        "ssb" => ("plurcast.ssb", "keypair"),
+        _ => anyhow::bail!(
+            "Unknown platform: {}. Supported platforms: nostr, mastodon, ssb",
+            platform
+        ),
+    };
+
+    // Check if credential exists (a read-only token counts)
+    if !manager.exists_scoped(service, key, account, CredentialScope::Read)? {
+        println!(
+            "No credentials found for {} account '{}'",
+            platform, account
+        );
+        return Ok(());
+    }
+
+    // Confirm deletion unless --force is used
+    if !force && atty::is(atty::Stream::Stdin) {
+        use std::io::{self, Write};
+        print!(
+            "Delete {} credentials for account '{}'? [y/N]: ",
+            platform, account
+        );
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Cancelled");
+            return Ok(());
+        }
+    }
+
+    // Check if deleting active account BEFORE deletion
+    let active_account = account_manager.get_active_account(&platform_lower);
+    let is_active = active_account == account;
+
+    // Delete the credential
+    manager.delete_account(service, key, account)?;
+
+    // `plur-creds login` also stored the instance and app credentials, and
+    // the account may have a read-only token
+    if platform_lower == "mastodon" {
+        for extra in [
+            "access_token_read",
+            "instance",
+            "client_id",
+            "client_secret",
+        ] {
+            if manager.exists_account(service, extra, account)? {
+                manager.delete_account(service, extra, account)?;
+            }
+        }
+    }
+
+    // Unregister account with AccountManager
+    account_manager.unregister_account(&platform_lower, account)?;
+
+    println!(
+        "✓ Deleted {} credentials for account '{}'",
+        platform, account
+    );
+
+    // If we deleted the active account, reset to "default" if it exists
+    if is_active {
+        // Check if "default" account exists
+        if manager.exists_account(service, key, "default")? {
+            account_manager.set_active_account(&platform_lower, "default")?;
+            println!("ℹ Active account was '{}', reset to 'default'", account);
+        } else {
+            // If no default account, just clear the active account
+            // (get_active_account will return "default" anyway as fallback)
+            println!(
+                "ℹ Active account was '{}', no default account configured",
+                account
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Migrate credentials from plain text files to secure storage
+async fn migrate_credentials() -> Result<()> {
+    // Load config to get credential configuration
+    let config = Config::load()?;
+
+    // Get or create credential config
+    let cred_config = config.credentials.unwrap_or_default();
+
+    // Create credential manager
+    let manager = CredentialManager::new(cred_config)?;
+
+    // Check if using insecure storage
+    if manager.is_insecure() {
+        anyhow::bail!(
+            "Cannot migrate to plain text storage. \
+            Configure keyring or encrypted storage in config.toml first."
+        );
+    }
+
+    println!("Migrating credentials to multi-account format...");
+    println!();
+
+    // Perform multi-account migration
+    let report = manager.migrate_to_multi_account()?;
+
+    // Display results
+    println!("Migration complete:");
+    println!("  ✓ Migrated: {}", report.migrated.len());
+    println!("  ✗ Failed: {}", report.failed.len());
+    println!("  ⊘ Skipped: {}", report.skipped.len());
+    println!();
+
+    // Show details
+    if !report.migrated.is_empty() {
+        println!("Successfully migrated to 'default' account:");
+        for cred in &report.migrated {
+            println!("  ✓ {}", cred);
+        }
+        println!();
+    }
+
+    if !report.failed.is_empty() {
+        println!("Failed to migrate:");
+        for (cred, error) in &report.failed {
+            println!("  ✗ {}: {}", cred, error);
+        }
+        println!();
+    }
+
+    if !report.skipped.is_empty() {
+        println!("Skipped (already migrated):");
+        for cred in &report.skipped {
+            println!("  ⊘ {}", cred);
+        }
+        println!();
+    }
+
+    // Offer to delete old format credentials if migration was successful
+    if report.is_success() && !report.migrated.is_empty() {
+        if atty::is(atty::Stream::Stdin) {
+            use std::io::{self, Write};
+            print!("Delete old format credentials? [y/N]: ");
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+
+            if input.trim().eq_ignore_ascii_case("y") {
+                println!("ℹ Old format credentials kept for backward compatibility.");
+                println!("  They will not interfere with multi-account operations.");
+            } else {
+                println!("Old format credentials kept for backward compatibility.");
+            }
+        }
+    } else if !report.is_success() {
+        println!("⚠ Some migrations failed. Old format credentials were not deleted.");
+        println!("Fix the errors and run migration again.");
+    }
+
+    Ok(())
+}
+
+/// Audit credential security
+async fn audit_credentials() -> Result<()> {
+    println!("Auditing credential security...");
+    println!();
+
+    let mut issues_found = false;
+
+    // Load config to get credential configuration
+    let config = Config::load()?;
+
+    // Check credential storage configuration
+    if let Some(cred_config) = &config.credentials {
+        println!("Credential storage configuration:");
+        println!("  Backend: {:?}", cred_config.storage);
+        println!("  Path: {}", cred_config.path);
+        println!();
+
+        // Create credential manager
+        let manager = CredentialManager::new(cred_config.clone())?;
+
+        // Check if using insecure storage
+        if manager.is_insecure() {
+            println!("⚠ SECURITY ISSUE: Using plain text credential storage");
+            println!("  Recommendation: Configure keyring or encrypted storage");
+            println!("  Run: plur-creds migrate");
+            println!();
+            issues_found = true;
+        } else {
+            println!(
+                "✓ Using secure credential storage: {}",
+                manager.primary_backend().unwrap_or("unknown")
+            );
+            println!();
+        }
+    } else {
+        println!("⚠ No credential storage configured (using legacy plain text files)");
+        println!("  Recommendation: Add [credentials] section to config.toml");
+        println!();
+        issues_found = true;
+    }
+
+    // Check for plain text credential files
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| anyhow::anyhow!("Config directory not found"))?
+        .join("plurcast");
+
+    let known_files = vec![
+        ("nostr.keys", "Nostr private key"),
+        ("mastodon.token", "Mastodon access token"),
+        ("ssb.keypair", "SSB keypair"),
+    ];
+
+    let mut plain_files_found = Vec::new();
+
+    for (filename, description) in &known_files {
+        let file_path = config_dir.join(filename);
+        if file_path.exists() {
+            plain_files_found.push((file_path.clone(), description));
+
+            // Check file permissions on Unix
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let metadata = std::fs::metadata(&file_path)?;
+                let permissions = metadata.permissions();
+                let mode = permissions.mode() & 0o777;
+
+                if mode != 0o600 {
+                    println!("⚠ SECURITY ISSUE: Incorrect file permissions");
+                    println!("  File: {}", file_path.display());
+                    println!("  Current: {:o}", mode);
+                    println!("  Expected: 600 (owner read/write only)");
+                    println!("  Fix: chmod 600 {}", file_path.display());
+                    println!();
+                    issues_found = true;
+                }
+            }
+        }
+    }
+
+    if !plain_files_found.is_empty() {
+        println!("⚠ SECURITY ISSUE: Plain text credential files found:");
+        for (path, desc) in &plain_files_found {
+            println!("  - {} ({})", path.display(), desc);
+        }
+        println!("  Recommendation: Run 'plur-creds migrate' to move to secure storage");
+        println!();
+        issues_found = true;
+    }
+
+    // Secrets printed with `show --reveal`
+    match recent_reveals(&config).await {
+        Ok(reveals) if reveals.is_empty() => {
+            println!("✓ No credential reveals recorded");
+            println!();
+        }
+        Ok(reveals) => {
+            println!("Recent credential reveals:");
+            for event in &reveals {
+                let when = chrono::DateTime::from_timestamp(event.created_at, 0)
+                    .map(|t| {
+                        t.with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string()
+                    })
+                    .unwrap_or_else(|| event.created_at.to_string());
+                println!("  {}  {} by {}", when, event.subject, event.actor);
+            }
+            println!();
+        }
+        Err(e) => {
+            println!(
+                "⚠ Could not read credential reveals from the audit log: {}",
+                e
+            );
+            println!();
+        }
+    }
+
+    // Summary
+    if issues_found {
+        println!("Security audit complete: Issues found");
+        println!("Follow the recommendations above to improve security.");
+        std::process::exit(1);
+    } else {
+        println!("✓ Security audit complete: No issues found");
+        Ok(())
+    }
+}
+
+/// The last ten `show --reveal` events in the audit log, newest first
+///
+/// The database is only read: an audit doesn't create or migrate one just
+/// to look, so without a database there are no reveals.
+async fn recent_reveals(config: &Config) -> Result<Vec<libplurcast::AuditEvent>> {
+    let db_path = resolve_db_path(Some(&config.database.path))?;
+    if !db_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let db = Database::open_read_only(&db_path.to_string_lossy()).await?;
+    Ok(db
+        .get_audit_events(100)
+        .await?
+        .into_iter()
+        .filter(|e| e.event == REVEAL_EVENT)
+        .take(10)
+        .collect())
+}
+
+/// Test credentials for a specific platform
+async fn test_credentials(platform: &str, account: &str) -> Result<()> {
+    // Validate account name
+    AccountManager::validate_account_name(account)?;
+
+    // Load account manager to determine which account to use
+    let account_manager = AccountManager::new()?;
+
+    // If account is "default" and not explicitly set, use active account
+    let platform_lower = platform.to_lowercase();
+    let account_to_use = if account == "default" {
+        account_manager.get_active_account(&platform_lower)
+    } else {
+        account.to_string()
+    };
+
+    println!(
+        "Testing {} credentials for account '{}'...",
+        platform, account_to_use
+    );
+
+    // For now, just check if credentials exist
+    // Full authentication testing would require platform client integration
+    let config = Config::load()?;
+    let cred_config = config.credentials.unwrap_or_default();
+    let manager = CredentialManager::new(cred_config)?;
+
+    let (service, key) = match platform_lower.as_str() {
+        "nostr" => ("plurcast.nostr", "private_key"),
+        "mastodon" => ("plurcast.mastodon", "access_token"),
+        "ssb" => ("plurcast.ssb", "keypair"),
+        _ => anyhow::bail!(
+            "Unknown platform: {}. Supported platforms: nostr, mastodon, ssb",
+            platform
+        ),
+    };
+
+    if manager.exists_account(service, key, &account_to_use)? {
+        // For SSB, also validate and display the keypair info
+        if platform_lower == "ssb" {
+            use libplurcast::platforms::ssb::SSBPlatform;
+
+            match SSBPlatform::retrieve_keypair(&manager, &account_to_use) {
+                Ok(keypair) => match keypair.validate() {
+                    Ok(_) => {
+                        println!(
+                            "✓ SSB credentials found and valid for account '{}'",
+                            account_to_use
+                        );
+                        println!("  Feed ID: {}", keypair.id);
+                        println!("  Keypair is properly formatted and ready to use");
+                    }
+                    Err(e) => {
+                        anyhow::bail!(
+                            "SSB credentials found but invalid for account '{}': {}",
+                            account_to_use,
+                            e
+                        );
+                    }
+                },
+                Err(e) => {
+                    anyhow::bail!(
+                        "Failed to retrieve SSB credentials for account '{}': {}",
+                        account_to_use,
+                        e
+                    );
+                }
+            }
+        } else {
+            println!(
+                "✓ {} credentials found for account '{}'",
+                platform, account_to_use
+            );
+            println!("  Note: Full authentication testing requires platform client integration");
+        }
+        Ok(())
+    } else {
+        anyhow::bail!(
+            "No credentials found for {} account '{}'",
+            platform,
+            account_to_use
+        );
+    }
+}
+
+/// Test all platform credentials
+async fn test_all_credentials() -> Result<()> {
+    println!("Testing all platform credentials...");
+    println!();
+
+    let platforms = vec!["nostr", "mastodon", "ssb"];
+    let mut found = 0;
+    let mut not_found = 0;
+
+    for platform in platforms {
+        // Use "default" account for testing all
+        match test_credentials(platform, "default").await {
+            Ok(_) => found += 1,
+            Err(_) => {
+                println!("✗ {} credentials not found", platform);
+                not_found += 1;
+            }
+        }
+    }
+
+    println!();
+    println!("Summary: {} found, {} not found", found, not_found);
+
+    if not_found > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
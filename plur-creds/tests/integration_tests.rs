@@ -12,7 +12,6 @@ use tempfile::TempDir;
 struct TestEnv {
     _temp_dir: TempDir,
     config_dir: PathBuf,
-    #[allow(dead_code)] // Reserved for future tests
     data_dir: PathBuf,
 }
 
@@ -537,3 +536,367 @@ fn test_ssb_validation_on_test() {
         .failure()
         .stderr(predicate::str::contains("Failed to parse SSB keypair JSON"));
 }
+
+#[test]
+fn test_login_only_supports_mastodon() {
+    let env = TestEnv::new();
+
+    env.cmd()
+        .args(&["login", "nostr"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("only supported for mastodon"));
+}
+
+#[test]
+fn test_login_requires_instance() {
+    let env = TestEnv::new();
+
+    // No --instance and no [mastodon] section in the config
+    env.cmd()
+        .args(&["login", "mastodon", "--account", "test"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No Mastodon instance given"));
+}
+
+#[test]
+fn test_login_overwrite_protection() {
+    let env = TestEnv::new();
+
+    env.cmd()
+        .args(&["set", "mastodon", "--account", "test", "--stdin"])
+        .write_stdin("existing-token")
+        .assert()
+        .success();
+
+    // Refuses before contacting the instance
+    env.cmd()
+        .args(&[
+            "login",
+            "mastodon",
+            "--account",
+            "test",
+            "--instance",
+            "mastodon.example",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Refusing to overwrite"));
+}
+
+#[test]
+fn test_nip49_export_and_import() {
+    let env = TestEnv::new();
+
+    env.cmd()
+        .args(&["set", "nostr", "--account", "test", "--stdin"])
+        .write_stdin("0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef")
+        .assert()
+        .success();
+
+    let output = env
+        .cmd()
+        .args(&["export", "nostr", "--account", "test", "--nip49"])
+        .env("PLURCAST_NIP49_PASSPHRASE", "correct horse")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let ncryptsec = String::from_utf8(output.stdout).unwrap();
+    assert!(ncryptsec.trim().starts_with("ncryptsec1"));
+
+    // The encrypted key loads into another account with the passphrase
+    env.cmd()
+        .args(&["set", "nostr", "--account", "copy", "--stdin"])
+        .env("PLURCAST_NIP49_PASSPHRASE", "correct horse")
+        .write_stdin(ncryptsec.clone())
+        .assert()
+        .success();
+
+    // ...and not with the wrong one
+    env.cmd()
+        .args(&["set", "nostr", "--account", "wrong", "--stdin"])
+        .env("PLURCAST_NIP49_PASSPHRASE", "wrong horse")
+        .write_stdin(ncryptsec)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("wrong passphrase"));
+}
+
+#[test]
+fn test_export_requires_nip49() {
+    let env = TestEnv::new();
+
+    env.cmd()
+        .args(&["set", "nostr", "--account", "test", "--stdin"])
+        .write_stdin("0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef")
+        .assert()
+        .success();
+
+    env.cmd()
+        .args(&["export", "nostr", "--account", "test"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--nip49"));
+}
+
+#[test]
+fn test_export_all_and_import_bundle() {
+    let source = TestEnv::new();
+    let target = TestEnv::new();
+    let bundle = source.config_dir.join("creds.age");
+
+    source
+        .cmd()
+        .args(&["set", "nostr", "--account", "bundle-test", "--stdin"])
+        .write_stdin("0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef")
+        .assert()
+        .success();
+
+    source
+        .cmd()
+        .args(&["export", "--all", "--output"])
+        .arg(&bundle)
+        .env("PLURCAST_BUNDLE_PASSPHRASE", "bundle-passphrase")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("nostr (bundle-test)"));
+
+    let sealed = fs::read(&bundle).unwrap();
+    assert!(sealed.starts_with(b"age-encryption.org/v1"));
+
+    // Wrong passphrase imports nothing
+    target
+        .cmd()
+        .arg("import")
+        .arg(&bundle)
+        .env("PLURCAST_BUNDLE_PASSPHRASE", "not-the-passphrase")
+        .assert()
+        .failure();
+
+    target
+        .cmd()
+        .arg("import")
+        .arg(&bundle)
+        .env("PLURCAST_BUNDLE_PASSPHRASE", "bundle-passphrase")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("nostr/bundle-test"));
+
+    // The key is now stored on the target
+    target
+        .cmd()
+        .args(&["export", "nostr", "--account", "bundle-test", "--nip49"])
+        .env("PLURCAST_NIP49_PASSPHRASE", "correct horse")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("ncryptsec1"));
+
+    // Importing again leaves existing accounts alone
+    target
+        .cmd()
+        .arg("import")
+        .arg(&bundle)
+        .env("PLURCAST_BUNDLE_PASSPHRASE", "bundle-passphrase")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Skipped"));
+}
+
+#[test]
+fn test_export_all_requires_output() {
+    let env = TestEnv::new();
+
+    env.cmd().args(&["export", "--all"]).assert().failure();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_command_backed_credentials() {
+    let env = TestEnv::new();
+    let config_path = env.config_dir.join("config.toml");
+    let mut config = fs::read_to_string(&config_path).unwrap();
+    config.push_str(
+        r#"
+[credentials.commands]
+"nostr.cmd.private_key" = "echo 0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
+"#,
+    );
+    fs::write(&config_path, config).unwrap();
+
+    env.cmd()
+        .args(&["list", "--platform", "nostr"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "nostr (cmd): Private Key (from command)",
+        ));
+
+    // The key comes from the command at use time
+    env.cmd()
+        .args(&["export", "nostr", "--account", "cmd", "--nip49"])
+        .env("PLURCAST_NIP49_PASSPHRASE", "correct horse")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("ncryptsec1"));
+
+    // Other accounts are still stored in the configured backend
+    env.cmd()
+        .args(&["set", "nostr", "--account", "other", "--stdin"])
+        .write_stdin("0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef")
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_environment_credentials_without_master_password() {
+    let env = TestEnv::new();
+    let secrets = env.config_dir.join("secrets");
+    fs::create_dir_all(&secrets).unwrap();
+    fs::write(
+        secrets.join("plurcast_nostr_mounted_key"),
+        "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef\n",
+    )
+    .unwrap();
+
+    // No keyring, no master password, no TTY: like a container or CI job
+    env.cmd()
+        .env_remove("PLURCAST_MASTER_PASSWORD")
+        .env(
+            "PLURCAST_NOSTR_CI_KEY",
+            "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+        )
+        .env("PLURCAST_SECRETS_DIR", &secrets)
+        .args(&["list", "--platform", "nostr"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "nostr (ci): Private Key (from environment)",
+        ))
+        .stdout(predicate::str::contains(
+            "nostr (mounted): Private Key (from environment)",
+        ));
+
+    env.cmd()
+        .env_remove("PLURCAST_MASTER_PASSWORD")
+        .env("PLURCAST_SECRETS_DIR", &secrets)
+        .env("PLURCAST_NIP49_PASSPHRASE", "correct horse")
+        .args(&["export", "nostr", "--account", "mounted", "--nip49"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("ncryptsec1"));
+}
+
+#[test]
+fn test_read_only_mastodon_token() {
+    let env = TestEnv::new();
+
+    env.cmd()
+        .args(&[
+            "set",
+            "mastodon",
+            "--account",
+            "reader",
+            "--scope",
+            "read",
+            "--stdin",
+        ])
+        .write_stdin("read-only-token")
+        .assert()
+        .success();
+
+    env.cmd()
+        .args(&["list", "--platform", "mastodon"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "mastodon (reader): Read-only Token",
+        ))
+        .stdout(predicate::str::contains("mastodon (reader): Access Token").not());
+
+    // A read-only token is its own credential, not a replacement for the full one
+    env.cmd()
+        .args(&["set", "mastodon", "--account", "reader", "--stdin"])
+        .write_stdin("full-token")
+        .assert()
+        .success();
+
+    env.cmd()
+        .args(&["delete", "mastodon", "--account", "reader", "--force"])
+        .assert()
+        .success();
+
+    env.cmd()
+        .args(&["list", "--platform", "mastodon"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(reader)").not());
+}
+
+#[test]
+fn test_read_scope_rejected_for_nostr() {
+    let env = TestEnv::new();
+
+    env.cmd()
+        .args(&[
+            "set",
+            "nostr",
+            "--account",
+            "test",
+            "--scope",
+            "read",
+            "--stdin",
+        ])
+        .write_stdin("0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("only supported for mastodon"));
+}
+
+#[test]
+fn test_show_hides_secret_without_reveal() {
+    let env = TestEnv::new();
+    let key = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    env.cmd()
+        .args(&["set", "nostr", "--account", "shown", "--stdin"])
+        .write_stdin(key)
+        .assert()
+        .success();
+
+    env.cmd()
+        .args(&["show", "nostr", "--account", "shown"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "nostr (shown): private key stored",
+        ))
+        .stdout(predicate::str::contains(key).not());
+
+    // Revealing needs someone at a terminal to confirm
+    env.cmd()
+        .args(&["show", "nostr", "--account", "shown", "--reveal"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(key).not())
+        .stderr(predicate::str::contains("confirmed interactively"));
+
+    env.cmd()
+        .args(&["show", "nostr", "--account", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No credentials found"));
+}
+
+#[test]
+fn test_audit_does_not_create_database() {
+    let env = TestEnv::new();
+    let db_path = env.data_dir.join("posts.db");
+
+    // Whatever else the audit finds, a missing database has no reveals
+    env.cmd()
+        .arg("audit")
+        .assert()
+        .stdout(predicate::str::contains("No credential reveals recorded"));
+    assert!(!db_path.exists());
+}
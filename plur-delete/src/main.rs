@@ -0,0 +1,334 @@
+//! plur-delete - Delete posts on every platform they were sent to
+//!
+//! Unix-style tool: takes Plurcast UUIDs or platform post IDs as arguments
+//! or on stdin, deletes each post wherever the platform allows it, and
+//! marks it deleted in history.
+
+use clap::Parser;
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use libplurcast::service::delete::{DeleteOutcome, DeleteReport, DeleteTarget, PlatformCopy};
+use libplurcast::service::PlurcastService;
+use libplurcast::{Config, PlurcastError, Result};
+use std::io::{self, BufRead, Write};
+
+#[derive(Parser, Debug)]
+#[command(name = "plur-delete")]
+#[command(version)]
+#[command(about = "Delete posts on every platform they were sent to")]
+#[command(long_about = "\
+plur-delete - Delete posts on every platform they were sent to
+
+DESCRIPTION:
+    plur-delete takes posts by their Plurcast UUID or by their ID on one
+    platform. A platform ID found in history stands for the whole post, as
+    with plur-post --reply-to, so the post is deleted on every platform it
+    was sent to. IDs not in history are deleted on their own platform only.
+
+    Mastodon statuses are deleted; Nostr notes get a NIP-09 deletion request,
+    which relays may or may not honour. SSB feeds are append-only, so SSB
+    messages stay. Once nothing is left to delete, the post is marked deleted
+    in history (see plur-history --status deleted).
+
+USAGE EXAMPLES:
+    # Delete a post everywhere, by its Plurcast UUID or any platform ID
+    plur-delete 550e8400-e29b-41d4-a716-446655440000
+    plur-delete note1abc...
+
+    # Only delete the Mastodon copy
+    plur-delete 550e8400-e29b-41d4-a716-446655440000 --platform mastodon
+
+    # See what would be deleted
+    plur-delete 109876543210 --dry-run
+
+    # Delete everything labelled campaign:old
+    plur-history --label campaign:old --ids-only | plur-delete - --force
+
+EXIT CODES:
+    0 - Success
+    1 - A deletion failed on some platform
+    2 - Authentication error
+    3 - Invalid input (unknown post ID, no confirmation, etc.)
+
+For more information, visit: https://github.com/plurcast/plurcast
+")]
+struct Cli {
+    /// Post IDs: Plurcast UUIDs or platform post IDs, or - to read them
+    /// from stdin (one per line)
+    #[arg(value_name = "ID", required = true)]
+    ids: Vec<String>,
+
+    /// Only delete on these platforms (comma-separated)
+    #[arg(short, long, value_name = "PLATFORM", value_delimiter = ',')]
+    platform: Option<Vec<String>>,
+
+    /// Account to delete from for IDs not in history (default: the active
+    /// account)
+    #[arg(long, value_name = "ACCOUNT")]
+    account: Option<String>,
+
+    /// Show what would be deleted without deleting anything
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Skip confirmation prompt
+    #[arg(short, long)]
+    force: bool,
+
+    /// Output format: text or json
+    #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+    format: String,
+
+    /// Verbose output
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT"
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL"
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    match run(cli).await {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
+
+/// Delete the posts; returns whether every deletion succeeded
+async fn run(cli: Cli) -> Result<bool> {
+    let from_stdin = cli.ids.iter().any(|id| id == "-");
+    let ids = read_ids(&cli.ids)?;
+    if ids.is_empty() {
+        return Err(PlurcastError::InvalidInput("No post IDs given".to_string()));
+    }
+
+    let config = Config::load()?;
+    let service = PlurcastService::from_config(config).await?;
+
+    // Resolve everything first, so a bad ID stops the run before anything
+    // is deleted
+    let mut targets = Vec::with_capacity(ids.len());
+    for id in &ids {
+        targets.push(service.delete().resolve(id, cli.account.as_deref()).await?);
+    }
+    let platforms = cli.platform.as_deref();
+
+    if cli.dry_run {
+        print_plan(&targets, platforms, &cli.format);
+        return Ok(true);
+    }
+
+    if !cli.force {
+        if from_stdin {
+            return Err(PlurcastError::InvalidInput(
+                "Reading IDs from stdin needs --force, as stdin can't also answer the confirmation prompt"
+                    .to_string(),
+            ));
+        }
+        if !confirm(&targets, platforms)? {
+            return Err(PlurcastError::InvalidInput("Cancelled by user".to_string()));
+        }
+    }
+
+    let mut reports = Vec::with_capacity(targets.len());
+    for target in &targets {
+        reports.push(service.delete().delete(target, platforms).await?);
+    }
+
+    match cli.format.as_str() {
+        "json" => println!(
+            "{}",
+            serde_json::to_string_pretty(&reports.iter().map(report_json).collect::<Vec<_>>())
+                .unwrap_or_default()
+        ),
+        _ => {
+            for report in &reports {
+                print_report(report);
+            }
+        }
+    }
+
+    Ok(reports.iter().all(DeleteReport::success))
+}
+
+/// IDs from the arguments, with `-` replaced by the lines of stdin
+fn read_ids(args: &[String]) -> Result<Vec<String>> {
+    let mut ids = Vec::new();
+    for arg in args {
+        if arg == "-" {
+            for line in io::stdin().lock().lines() {
+                let line = line.map_err(|e| {
+                    PlurcastError::InvalidInput(format!("Failed to read IDs from stdin: {}", e))
+                })?;
+                let line = line.trim();
+                if !line.is_empty() {
+                    ids.push(line.to_string());
+                }
+            }
+        } else {
+            ids.push(arg.trim().to_string());
+        }
+    }
+    Ok(ids)
+}
+
+/// The copies of a target that will be deleted
+fn selected<'a>(
+    target: &'a DeleteTarget,
+    platforms: Option<&'a [String]>,
+) -> impl Iterator<Item = &'a PlatformCopy> {
+    target
+        .copies
+        .iter()
+        .filter(move |c| platforms.is_none_or(|platforms| platforms.contains(&c.platform)))
+}
+
+/// Describe a target, e.g. "550e8400-... (mastodon 110, nostr note1...)"
+fn describe(target: &DeleteTarget, platforms: Option<&[String]>) -> String {
+    let copies: Vec<String> = selected(target, platforms)
+        .map(|c| format!("{} {}", c.platform, c.platform_post_id))
+        .collect();
+    let name = target.post_id.as_deref().unwrap_or(&target.id);
+    if copies.is_empty() {
+        format!("{} (not on any platform)", name)
+    } else {
+        format!("{} ({})", name, copies.join(", "))
+    }
+}
+
+fn print_plan(targets: &[DeleteTarget], platforms: Option<&[String]>, format: &str) {
+    if format == "json" {
+        let plan: Vec<serde_json::Value> = targets
+            .iter()
+            .map(|target| {
+                serde_json::json!({
+                    "id": target.id,
+                    "post_id": target.post_id,
+                    "copies": selected(target, platforms)
+                        .map(|c| serde_json::json!({
+                            "platform": c.platform,
+                            "platform_post_id": c.platform_post_id,
+                            "account": c.account,
+                        }))
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&plan).unwrap_or_default()
+        );
+        return;
+    }
+
+    for target in targets {
+        println!("Would delete {}", describe(target, platforms));
+    }
+}
+
+fn confirm(targets: &[DeleteTarget], platforms: Option<&[String]>) -> Result<bool> {
+    for target in targets {
+        eprintln!("  {}", describe(target, platforms));
+    }
+    eprint!("Delete {} post(s)? (y/N): ", targets.len());
+    io::stderr().flush().map_err(|e| {
+        PlurcastError::InvalidInput(format!("Failed to write confirmation prompt: {}", e))
+    })?;
+
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .map_err(|e| PlurcastError::InvalidInput(format!("Failed to read confirmation: {}", e)))?;
+
+    Ok(input.trim().eq_ignore_ascii_case("y"))
+}
+
+fn print_report(report: &DeleteReport) {
+    let target = &report.target;
+    println!("{}", target.post_id.as_deref().unwrap_or(&target.id));
+    for result in &report.results {
+        let copy = &result.copy;
+        match result.outcome {
+            DeleteOutcome::Deleted => {
+                println!("  {}: deleted {}", copy.platform, copy.platform_post_id)
+            }
+            DeleteOutcome::Unsupported(ref reason) => {
+                println!("  {}: kept ({})", copy.platform, reason)
+            }
+            DeleteOutcome::Failed(ref error) => {
+                println!("  {}: failed: {}", copy.platform, error)
+            }
+        }
+    }
+    if report.marked_deleted {
+        println!("  marked deleted in history");
+    }
+}
+
+fn report_json(report: &DeleteReport) -> serde_json::Value {
+    let results: Vec<serde_json::Value> = report
+        .results
+        .iter()
+        .map(|result| {
+            let (status, message) = match result.outcome {
+                DeleteOutcome::Deleted => ("deleted", None),
+                DeleteOutcome::Unsupported(ref reason) => ("unsupported", Some(reason)),
+                DeleteOutcome::Failed(ref error) => ("failed", Some(error)),
+            };
+            serde_json::json!({
+                "platform": result.copy.platform,
+                "platform_post_id": result.copy.platform_post_id,
+                "account": result.copy.account,
+                "status": status,
+                "message": message,
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "id": report.target.id,
+        "post_id": report.target.post_id,
+        "results": results,
+        "marked_deleted": report.marked_deleted,
+    })
+}
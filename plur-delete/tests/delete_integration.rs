@@ -0,0 +1,143 @@
+//! Integration tests for plur-delete
+
+use assert_cmd::Command;
+use libplurcast::{Database, Post, PostRecord, PostStatus};
+use predicates::prelude::*;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Write a config file pointing at `db_path`, with no platforms enabled
+fn write_config(dir: &Path, db_path: &Path) -> PathBuf {
+    let config_path = dir.join("config.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "[database]\npath = \"{}\"\n\n[defaults]\nplatforms = []\n",
+            db_path.to_string_lossy().replace('\\', "/")
+        ),
+    )
+    .unwrap();
+    config_path
+}
+
+/// Create a posted post with a copy on each platform
+async fn create_sent_post(db: &Database, copies: &[(&str, &str)]) -> String {
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content: "Regrettable post".to_string(),
+        created_at: chrono::Utc::now().timestamp(),
+        scheduled_at: None,
+        status: PostStatus::Posted,
+        metadata: None,
+    };
+    db.create_post(&post).await.unwrap();
+    for (platform, platform_post_id) in copies {
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: platform.to_string(),
+            platform_post_id: Some(platform_post_id.to_string()),
+            posted_at: Some(post.created_at),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
+    }
+    post.id
+}
+
+async fn setup() -> (TempDir, Database, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("posts.db");
+    let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+    let config_path = write_config(temp_dir.path(), &db_path);
+    (temp_dir, db, config_path)
+}
+
+fn plur_delete(config_path: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("plur-delete").unwrap();
+    cmd.env("PLURCAST_CONFIG", config_path)
+        .env_remove("PLURCAST_DB_PATH");
+    cmd
+}
+
+#[tokio::test]
+async fn test_dry_run_resolves_platform_id_to_every_copy() {
+    let (_temp_dir, db, config_path) = setup().await;
+    let post_id = create_sent_post(&db, &[("mastodon", "110"), ("ssb", "%abc=.sha256")]).await;
+
+    plur_delete(&config_path)
+        .args(["110", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "Would delete {}",
+            post_id
+        )))
+        .stdout(predicate::str::contains("mastodon 110"))
+        .stdout(predicate::str::contains("ssb %abc=.sha256"));
+
+    let post = db.get_post(&post_id).await.unwrap().unwrap();
+    assert_eq!(post.status, PostStatus::Posted);
+}
+
+#[tokio::test]
+async fn test_delete_marks_post_deleted() {
+    let (_temp_dir, db, config_path) = setup().await;
+    let post_id = create_sent_post(&db, &[("ssb", "%abc=.sha256")]).await;
+
+    plur_delete(&config_path)
+        .args([post_id.as_str(), "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ssb: kept"))
+        .stdout(predicate::str::contains("marked deleted in history"));
+
+    let post = db.get_post(&post_id).await.unwrap().unwrap();
+    assert_eq!(post.status, PostStatus::Deleted);
+}
+
+#[tokio::test]
+async fn test_reads_ids_from_stdin() {
+    let (_temp_dir, db, config_path) = setup().await;
+    let first = create_sent_post(&db, &[("ssb", "%one=.sha256")]).await;
+    let second = create_sent_post(&db, &[("ssb", "%two=.sha256")]).await;
+
+    // Without --force there is nothing to answer the prompt
+    plur_delete(&config_path)
+        .arg("-")
+        .write_stdin(format!("{}\n", first))
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("--force"));
+
+    plur_delete(&config_path)
+        .args(["-", "--force", "--format", "json"])
+        .write_stdin(format!("{}\n\n%two=.sha256\n", first))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"marked_deleted\": true"));
+
+    for post_id in [first, second] {
+        let post = db.get_post(&post_id).await.unwrap().unwrap();
+        assert_eq!(post.status, PostStatus::Deleted);
+    }
+}
+
+#[tokio::test]
+async fn test_unknown_ids_are_invalid_input() {
+    let (_temp_dir, db, config_path) = setup().await;
+    let post_id = create_sent_post(&db, &[("ssb", "%abc=.sha256")]).await;
+
+    plur_delete(&config_path)
+        .args([post_id.as_str(), "not-a-post-id", "--force"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Could not detect platform"));
+
+    // Nothing is deleted when any ID is bad
+    let post = db.get_post(&post_id).await.unwrap().unwrap();
+    assert_eq!(post.status, PostStatus::Posted);
+}
@@ -0,0 +1,503 @@
+//! The individual diagnostics
+//!
+//! Each check looks at one part of the setup and reports ok, a warning
+//! (works, but worth knowing) or a failure (something won't work). Checks
+//! only read: the database is opened read-only and never migrated, and
+//! nothing is written or created.
+
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use libplurcast::config::resolve_db_path;
+use libplurcast::credentials::KeyringStore;
+use libplurcast::platforms::mastodon::instance_url;
+use libplurcast::platforms::ssb::PubAddress;
+use libplurcast::types::SendLease;
+use libplurcast::{Config, CredentialManager, Database, StorageBackend};
+use tokio::net::TcpStream;
+use tokio::time::{timeout, Duration};
+
+/// Outcome of a check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Warn => "warn",
+            Self::Fail => "fail",
+        }
+    }
+}
+
+/// One diagnostic and what it found
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: String,
+    pub status: Status,
+    pub detail: String,
+}
+
+impl Check {
+    pub fn ok(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self::new(name, Status::Ok, detail)
+    }
+
+    pub fn warn(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self::new(name, Status::Warn, detail)
+    }
+
+    pub fn fail(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self::new(name, Status::Fail, detail)
+    }
+
+    fn new(name: impl Into<String>, status: Status, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Load the configuration without creating a default one
+///
+/// A missing config file is a warning and the built-in defaults are used
+/// for the remaining checks; one that can't be parsed or fails validation
+/// is a failure and the checks that need it are skipped.
+pub fn check_config(path: &Path) -> (Check, Option<Config>) {
+    if !path.exists() {
+        return (
+            Check::warn(
+                "config",
+                format!(
+                    "{} not found; defaults are used (run plur-setup to create it)",
+                    path.display()
+                ),
+            ),
+            Some(Config::default_config()),
+        );
+    }
+
+    match Config::load_from_path(&path.to_path_buf()) {
+        Ok(config) => (
+            Check::ok("config", path.display().to_string()),
+            Some(config),
+        ),
+        Err(e) => (Check::fail("config", e.to_string()), None),
+    }
+}
+
+/// Schema version and integrity of the database, opened read-only
+///
+/// Returns the open database for the checks that read it.
+pub async fn check_database(config: &Config) -> (Vec<Check>, Option<Database>) {
+    let db_path = match resolve_db_path(Some(&config.database.path)) {
+        Ok(path) => path,
+        Err(e) => return (vec![Check::fail("database", e.to_string())], None),
+    };
+    let location = db_path.to_string_lossy().to_string();
+
+    if !db_path.exists() {
+        return (
+            vec![Check::warn(
+                "database",
+                format!("{} not created yet (created on first use)", location),
+            )],
+            None,
+        );
+    }
+
+    let db = match Database::open_read_only(&location).await {
+        Ok(db) => db,
+        Err(e) => return (vec![Check::fail("database", e.to_string())], None),
+    };
+    let mut checks = vec![Check::ok("database", location)];
+
+    checks.push(match db.schema_version().await {
+        Ok(version) => schema_check(version, Database::latest_schema_version()),
+        Err(e) => Check::fail("schema", e.to_string()),
+    });
+
+    checks.push(match db.integrity_check().await {
+        Ok(rows) if rows.len() == 1 && rows[0] == "ok" => Check::ok("integrity", "ok"),
+        Ok(rows) => Check::fail(
+            "integrity",
+            format!("{} (restore from plur-backup)", rows.join("; ")),
+        ),
+        Err(e) => Check::fail("integrity", e.to_string()),
+    });
+
+    (checks, Some(db))
+}
+
+/// Compare the database's schema with the one this build expects
+fn schema_check(version: i64, latest: i64) -> Check {
+    if version == latest {
+        Check::ok("schema", format!("version {}", version))
+    } else if version < latest {
+        Check::warn(
+            "schema",
+            format!(
+                "version {}, this build has {}; upgraded the next time a tool opens it",
+                version, latest
+            ),
+        )
+    } else {
+        Check::fail(
+            "schema",
+            format!(
+                "version {} is newer than this build's {}; upgrade plurcast",
+                version, latest
+            ),
+        )
+    }
+}
+
+/// Whether the configured credential backend can be used
+///
+/// Never prompts: without `PLURCAST_MASTER_PASSWORD`, encrypted storage is
+/// reported as needing the password rather than asked for it.
+pub fn check_credentials(config: &Config) -> Check {
+    let Some(credentials) = &config.credentials else {
+        return Check::warn(
+            "credentials",
+            "no [credentials] section; secrets are read from plain files (run plur-creds migrate)",
+        );
+    };
+
+    let encrypted = |fallback: &str| match credentials.master_password {
+        Some(_) => match CredentialManager::new(credentials.clone()) {
+            Ok(_) => Check::ok(
+                "credentials",
+                format!("{}encrypted files in {}", fallback, credentials.path),
+            ),
+            Err(e) => Check::fail("credentials", e.to_string()),
+        },
+        None => Check::warn(
+            "credentials",
+            format!(
+                "{}encrypted files need a master password; set PLURCAST_MASTER_PASSWORD or \
+                 tools will prompt for it",
+                fallback
+            ),
+        ),
+    };
+
+    match credentials.storage {
+        StorageBackend::Keyring => match KeyringStore::new() {
+            Ok(_) => match CredentialManager::new(credentials.clone()) {
+                Ok(_) => Check::ok("credentials", "OS keyring"),
+                Err(e) => Check::fail("credentials", e.to_string()),
+            },
+            Err(e) => {
+                let mut check = encrypted(&format!("OS keyring unavailable ({}); ", e));
+                if check.status == Status::Ok {
+                    check.status = Status::Warn;
+                }
+                check
+            }
+        },
+        StorageBackend::Encrypted => encrypted(""),
+    }
+}
+
+/// Where to connect to reach each enabled platform's servers
+pub fn endpoints(config: &Config) -> Vec<(String, Option<(String, u16)>)> {
+    let mut endpoints = Vec::new();
+
+    if let Some(nostr) = config.nostr.as_ref().filter(|n| n.enabled) {
+        for relay in &nostr.relays {
+            endpoints.push((format!("relay {}", relay), url_endpoint(relay)));
+        }
+    }
+    if let Some(mastodon) = config.mastodon.as_ref().filter(|m| m.enabled) {
+        let url = instance_url(&mastodon.instance);
+        endpoints.push((format!("instance {}", url), url_endpoint(&url)));
+    }
+    if let Some(ssb) = config.ssb.as_ref().filter(|s| s.enabled) {
+        for address in &ssb.pubs {
+            let endpoint = PubAddress::parse(address).ok().map(|a| (a.host, a.port));
+            endpoints.push((format!("pub {}", address), endpoint));
+        }
+    }
+
+    endpoints
+}
+
+/// Host and port of a relay or instance URL, with the scheme's default port
+pub fn url_endpoint(url: &str) -> Option<(String, u16)> {
+    let (scheme, rest) = url.trim().split_once("://")?;
+    let default_port = match scheme {
+        "wss" | "https" => 443,
+        "ws" | "http" => 80,
+        _ => return None,
+    };
+
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let authority = authority.rsplit_once('@').map_or(authority, |(_, a)| a);
+    if authority.is_empty() {
+        return None;
+    }
+
+    if let Some(bracketed) = authority.strip_prefix('[') {
+        let (host, after) = bracketed.split_once(']')?;
+        let port = match after.strip_prefix(':') {
+            Some(port) => port.parse().ok()?,
+            None => default_port,
+        };
+        return Some((host.to_string(), port));
+    }
+
+    match authority.rsplit_once(':') {
+        Some((host, port)) => Some((host.to_string(), port.parse().ok()?)),
+        None => Some((authority.to_string(), default_port)),
+    }
+}
+
+/// Try a TCP connection to every relay, instance and pub, all at once
+pub async fn check_reachability(config: &Config, wait: Duration) -> Vec<Check> {
+    let handles: Vec<_> = endpoints(config)
+        .into_iter()
+        .map(|(name, endpoint)| {
+            tokio::spawn(async move {
+                let Some((host, port)) = endpoint else {
+                    return Check::fail(name, "not a valid address");
+                };
+                match timeout(wait, TcpStream::connect((host.as_str(), port))).await {
+                    Ok(Ok(_)) => Check::ok(name, format!("reachable at {}:{}", host, port)),
+                    Ok(Err(e)) => Check::fail(name, format!("{}:{}: {}", host, port, e)),
+                    Err(_) => Check::fail(
+                        name,
+                        format!("{}:{}: no answer within {}s", host, port, wait.as_secs()),
+                    ),
+                }
+            })
+        })
+        .collect();
+
+    let mut checks = Vec::new();
+    for handle in handles {
+        if let Ok(check) = handle.await {
+            checks.push(check);
+        }
+    }
+    checks
+}
+
+/// Whether a plur-send instance is looking after the queue
+pub async fn check_send(config: &Config, db: &Database) -> Check {
+    let lease = match db.get_send_lease().await {
+        Ok(lease) => lease,
+        Err(e) => return Check::fail("plur-send", e.to_string()),
+    };
+    let due = match db.get_scheduled_posts_due().await {
+        Ok(due) => due.len(),
+        Err(e) => return Check::fail("plur-send", e.to_string()),
+    };
+    let poll_interval = config
+        .scheduling
+        .as_ref()
+        .map(|s| s.poll_interval)
+        .unwrap_or(60);
+
+    send_status(
+        lease.as_ref(),
+        due,
+        lease_ttl(poll_interval),
+        Utc::now().timestamp(),
+    )
+}
+
+/// How long plur-send's lease lasts without renewal
+///
+/// The same lifetime plur-send uses: three missed polls, at least five
+/// minutes.
+fn lease_ttl(poll_interval: u64) -> i64 {
+    (poll_interval as i64 * 3).max(300)
+}
+
+/// Judge plur-send's liveness from its lease and the posts waiting for it
+fn send_status(lease: Option<&SendLease>, due: usize, ttl: i64, now: i64) -> Check {
+    let waiting = match due {
+        0 => String::new(),
+        1 => "; 1 scheduled post is due".to_string(),
+        n => format!("; {} scheduled posts are due", n),
+    };
+
+    match lease {
+        Some(lease) if now - lease.renewed_at <= ttl => Check::ok(
+            "plur-send",
+            format!(
+                "running ({}), last seen {}s ago",
+                lease.holder,
+                (now - lease.renewed_at).max(0)
+            ),
+        ),
+        Some(lease) => Check::warn(
+            "plur-send",
+            format!(
+                "stopped without releasing the queue ({}), last seen {}s ago{}",
+                lease.holder,
+                now - lease.renewed_at,
+                waiting
+            ),
+        ),
+        None if due > 0 => Check::warn("plur-send", format!("not running{}", waiting)),
+        None => Check::ok("plur-send", "not running; nothing is due"),
+    }
+}
+
+/// Files whose permissions matter, and whether each holds secrets
+pub fn sensitive_files(config_path: &Path, config: &Config) -> Vec<(PathBuf, bool)> {
+    let mut files = vec![(config_path.to_path_buf(), false)];
+
+    if let Ok(db_path) = resolve_db_path(Some(&config.database.path)) {
+        files.push((db_path, false));
+    }
+    if let Some(path) = config
+        .nostr
+        .as_ref()
+        .and_then(|n| n.expand_keys_file_path().ok())
+    {
+        files.push((path, true));
+    }
+    if let Some(path) = config
+        .mastodon
+        .as_ref()
+        .and_then(|m| m.expand_token_file_path().ok())
+    {
+        files.push((path, true));
+    }
+    if let Some(credentials) = &config.credentials {
+        let dir = PathBuf::from(shellexpand::tilde(&credentials.path).to_string());
+        if let Ok(entries) = std::fs::read_dir(&dir) {
+            let mut stored: Vec<PathBuf> = entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.is_file())
+                .collect();
+            stored.sort();
+            files.extend(stored.into_iter().map(|p| (p, true)));
+        }
+    }
+
+    files.retain(|(path, _)| path.exists());
+    files
+}
+
+/// Secrets readable by others, and config or database writable by others
+#[cfg(unix)]
+pub fn check_permissions(files: &[(PathBuf, bool)]) -> Vec<Check> {
+    use std::os::unix::fs::PermissionsExt;
+
+    files
+        .iter()
+        .map(|(path, secret)| {
+            let mut check = match std::fs::metadata(path) {
+                Ok(metadata) => permission_check(metadata.permissions().mode(), *secret),
+                Err(e) => Check::fail("permissions", e.to_string()),
+            };
+            check.detail = format!("{}: {}", path.display(), check.detail);
+            check
+        })
+        .collect()
+}
+
+/// File permissions aren't modes outside Unix
+#[cfg(not(unix))]
+pub fn check_permissions(_files: &[(PathBuf, bool)]) -> Vec<Check> {
+    Vec::new()
+}
+
+/// Judge a file's mode: secrets are owner-only, the rest not writable by others
+#[cfg_attr(not(unix), allow(dead_code))]
+fn permission_check(mode: u32, secret: bool) -> Check {
+    let mode = mode & 0o777;
+    if secret && mode & 0o077 != 0 {
+        Check::fail(
+            "permissions",
+            format!("{:o}, secrets readable by others (chmod 600)", mode),
+        )
+    } else if mode & 0o022 != 0 {
+        Check::warn(
+            "permissions",
+            format!("{:o}, writable by others (chmod go-w)", mode),
+        )
+    } else {
+        Check::ok("permissions", format!("{:o}", mode))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_endpoint() {
+        assert_eq!(
+            url_endpoint("wss://relay.damus.io"),
+            Some(("relay.damus.io".to_string(), 443))
+        );
+        assert_eq!(
+            url_endpoint("ws://localhost:7777/"),
+            Some(("localhost".to_string(), 7777))
+        );
+        assert_eq!(
+            url_endpoint("https://mastodon.social/@me"),
+            Some(("mastodon.social".to_string(), 443))
+        );
+        assert_eq!(
+            url_endpoint("http://[::1]:8080"),
+            Some(("::1".to_string(), 8080))
+        );
+        assert_eq!(url_endpoint("relay.damus.io"), None);
+        assert_eq!(url_endpoint("wss://"), None);
+        assert_eq!(url_endpoint("wss://relay:port"), None);
+    }
+
+    #[test]
+    fn test_schema_check() {
+        assert_eq!(schema_check(25, 25).status, Status::Ok);
+        assert_eq!(schema_check(20, 25).status, Status::Warn);
+        assert_eq!(schema_check(26, 25).status, Status::Fail);
+    }
+
+    #[test]
+    fn test_send_status() {
+        let now = 1_700_000_000;
+        let lease = |seen_ago: i64| SendLease {
+            holder: "pid 42".to_string(),
+            acquired_at: now - 3600,
+            renewed_at: now - seen_ago,
+        };
+        let ttl = lease_ttl(60);
+        assert_eq!(ttl, 300);
+
+        let running = send_status(Some(&lease(30)), 2, ttl, now);
+        assert_eq!(running.status, Status::Ok);
+        assert!(running.detail.contains("pid 42"));
+
+        let stale = send_status(Some(&lease(900)), 2, ttl, now);
+        assert_eq!(stale.status, Status::Warn);
+        assert!(stale.detail.contains("2 scheduled posts are due"));
+
+        assert_eq!(send_status(None, 1, ttl, now).status, Status::Warn);
+        assert_eq!(send_status(None, 0, ttl, now).status, Status::Ok);
+    }
+
+    #[test]
+    fn test_permission_check() {
+        let check = |mode, secret| permission_check(mode, secret).status;
+        assert_eq!(check(0o100600, true), Status::Ok);
+        assert_eq!(check(0o100644, true), Status::Fail);
+        assert_eq!(check(0o100644, false), Status::Ok);
+        assert_eq!(check(0o100666, false), Status::Warn);
+        assert_eq!(check(0o100620, true), Status::Fail);
+    }
+}
@@ -0,0 +1,239 @@
+//! plur-doctor - Diagnose your plurcast setup
+//!
+//! Unix-style tool: checks the configuration, database, credential
+//! storage, network reachability, plur-send and file permissions, and
+//! reports what it found as text or as JSON for bug reports.
+
+mod checks;
+
+use checks::{Check, Status};
+use clap::Parser;
+use libplurcast::config::resolve_config_path;
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use libplurcast::Result;
+use tokio::time::Duration;
+use tracing::info;
+
+/// Widest check name the text output pads to
+const NAME_WIDTH: usize = 12;
+
+#[derive(Parser, Debug)]
+#[command(name = "plur-doctor")]
+#[command(version)]
+#[command(about = "Diagnose your plurcast setup")]
+#[command(long_about = "\
+plur-doctor - Diagnose your plurcast setup
+
+DESCRIPTION:
+    plur-doctor checks that everything plurcast needs is in order:
+
+        config       the config file loads and validates
+        database     schema version against this build, integrity check
+        credentials  the configured backend (OS keyring or encrypted
+                     files) can be used
+        reachability a connection to every relay, instance and pub of
+                     the enabled platforms (skip with --offline)
+        plur-send    an instance holds the queue and keeps renewing it
+        permissions  keys, tokens and stored credentials are readable by
+                     you alone; config and database writable by you alone
+
+    Each check is ok, a warning (works, but worth knowing) or a failure
+    (something won't work). Nothing is changed: the database is opened
+    read-only and never upgraded, and no default config is created.
+
+    --format json prints the plurcast version, platform and every check,
+    ready to attach to a bug report. Review it first: it contains paths
+    and server addresses, but no secrets.
+
+USAGE EXAMPLES:
+    # Check everything
+    plur-doctor
+
+    # Without touching the network
+    plur-doctor --offline
+
+    # For a bug report
+    plur-doctor --format json > doctor.json
+
+    # Just the problems
+    plur-doctor --format json | jq '.checks[] | select(.status != \"ok\")'
+
+EXIT CODES:
+    0 - Success: no check failed (there may be warnings)
+    1 - At least one check failed
+
+For more information, visit: https://github.com/plurcast/plurcast
+")]
+struct Cli {
+    /// Skip the reachability checks
+    #[arg(long)]
+    offline: bool,
+
+    /// Seconds to wait for each server to answer
+    #[arg(long, default_value = "5", value_name = "SECONDS")]
+    timeout: u64,
+
+    /// Output format (text, json)
+    #[arg(short, long, default_value = "text", value_name = "FORMAT")]
+    #[arg(value_parser = ["text", "json"])]
+    format: String,
+
+    /// Verbose output
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT"
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL"
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    match run(cli).await {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
+
+/// Run every check and print them; returns whether none failed
+async fn run(cli: Cli) -> Result<bool> {
+    let checks = diagnose(&cli).await;
+    info!("Ran {} checks", checks.len());
+    output(&cli.format, &checks);
+
+    Ok(checks.iter().all(|c| c.status != Status::Fail))
+}
+
+/// Run the checks in order, skipping those whose prerequisites failed
+async fn diagnose(cli: &Cli) -> Vec<Check> {
+    let config_path = match resolve_config_path() {
+        Ok(path) => path,
+        Err(e) => return vec![Check::fail("config", e.to_string())],
+    };
+    let (config_check, config) = checks::check_config(&config_path);
+    let mut results = vec![config_check];
+    let Some(config) = config else {
+        return results;
+    };
+
+    let (database_checks, db) = checks::check_database(&config).await;
+    results.extend(database_checks);
+    results.push(checks::check_credentials(&config));
+
+    if !cli.offline {
+        let wait = Duration::from_secs(cli.timeout.max(1));
+        results.extend(checks::check_reachability(&config, wait).await);
+    }
+
+    if let Some(ref db) = db {
+        results.push(checks::check_send(&config, db).await);
+    }
+
+    let files = checks::sensitive_files(&config_path, &config);
+    results.extend(checks::check_permissions(&files));
+
+    results
+}
+
+/// Print the checks and a summary
+fn output(format: &str, checks: &[Check]) {
+    let count = |status: Status| checks.iter().filter(|c| c.status == status).count();
+
+    if format == "json" {
+        let output = serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "os": std::env::consts::OS,
+            "arch": std::env::consts::ARCH,
+            "checks": checks
+                .iter()
+                .map(|c| {
+                    serde_json::json!({
+                        "check": c.name,
+                        "status": c.status.as_str(),
+                        "detail": c.detail,
+                    })
+                })
+                .collect::<Vec<_>>(),
+            "summary": {
+                "ok": count(Status::Ok),
+                "warn": count(Status::Warn),
+                "fail": count(Status::Fail),
+            },
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output).unwrap_or_default()
+        );
+        return;
+    }
+
+    println!(
+        "plurcast {} ({} {})",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    println!();
+    // Long names (relay URLs) overflow rather than widen every line
+    let width = checks
+        .iter()
+        .map(|c| c.name.len())
+        .max()
+        .unwrap_or(0)
+        .min(NAME_WIDTH);
+    for check in checks {
+        println!(
+            "{:<4}  {:<width$}  {}",
+            check.status.as_str(),
+            check.name,
+            check.detail,
+            width = width
+        );
+    }
+    println!();
+    println!(
+        "{} ok, {} warnings, {} failed",
+        count(Status::Ok),
+        count(Status::Warn),
+        count(Status::Fail)
+    );
+}
@@ -0,0 +1,144 @@
+//! Integration tests for plur-doctor
+
+use assert_cmd::Command;
+use libplurcast::Database;
+use predicates::prelude::*;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Write a config file pointing at `db_path`, with no platforms enabled
+fn write_config(dir: &Path, db_path: &Path, extra: &str) -> PathBuf {
+    let config_path = dir.join("config.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "[database]\npath = \"{}\"\n\n[defaults]\nplatforms = []\n{}",
+            db_path.to_string_lossy().replace('\\', "/"),
+            extra
+        ),
+    )
+    .unwrap();
+    config_path
+}
+
+async fn setup() -> (TempDir, Database, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("posts.db");
+    let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+    let config_path = write_config(temp_dir.path(), &db_path, "");
+    (temp_dir, db, config_path)
+}
+
+fn plur_doctor(config_path: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("plur-doctor").unwrap();
+    cmd.env("PLURCAST_CONFIG", config_path)
+        .env_remove("PLURCAST_DB_PATH")
+        .arg("--offline");
+    cmd
+}
+
+/// Run plur-doctor with JSON output; returns the exit code and report
+fn report(config_path: &Path) -> (Option<i32>, serde_json::Value) {
+    let output = plur_doctor(config_path)
+        .args(["--format", "json"])
+        .output()
+        .unwrap();
+    let report = serde_json::from_slice(&output.stdout).unwrap();
+    (output.status.code(), report)
+}
+
+/// Status of the first check with this name
+fn status<'a>(report: &'a serde_json::Value, check: &str) -> &'a str {
+    report["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["check"] == check)
+        .unwrap_or_else(|| panic!("no {} check in {}", check, report))["status"]
+        .as_str()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_healthy_setup_passes() {
+    let (_temp_dir, _db, config_path) = setup().await;
+
+    let (code, report) = report(&config_path);
+    assert_eq!(code, Some(0));
+    assert_eq!(report["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(report["summary"]["fail"], 0);
+    for check in ["config", "database", "schema", "integrity", "plur-send"] {
+        assert_eq!(status(&report, check), "ok", "{}", check);
+    }
+    assert_eq!(status(&report, "credentials"), "warn");
+
+    plur_doctor(&config_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0 failed"));
+}
+
+#[tokio::test]
+async fn test_missing_database_is_not_created() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("posts.db");
+    let config_path = write_config(temp_dir.path(), &db_path, "");
+
+    let (code, report) = report(&config_path);
+    assert_eq!(code, Some(0));
+    assert_eq!(status(&report, "database"), "warn");
+    assert!(!db_path.exists());
+}
+
+#[tokio::test]
+async fn test_broken_config_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    std::fs::write(&config_path, "[database\npath = ").unwrap();
+
+    let (code, report) = report(&config_path);
+    assert_eq!(code, Some(1));
+    assert_eq!(status(&report, "config"), "fail");
+    assert_eq!(report["checks"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_stale_send_lease_warns() {
+    let (_temp_dir, db, config_path) = setup().await;
+    db.acquire_send_lease("pid 4242", 1_700_000_000, 300)
+        .await
+        .unwrap();
+
+    let (code, report) = report(&config_path);
+    assert_eq!(code, Some(0));
+    assert_eq!(status(&report, "plur-send"), "warn");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_readable_token_file_fails() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("posts.db");
+    Database::new(db_path.to_str().unwrap()).await.unwrap();
+    let token_path = temp_dir.path().join("mastodon.token");
+    std::fs::write(&token_path, "secret").unwrap();
+    std::fs::set_permissions(&token_path, std::fs::Permissions::from_mode(0o644)).unwrap();
+    let config_path = write_config(
+        temp_dir.path(),
+        &db_path,
+        &format!(
+            "\n[mastodon]\nenabled = false\ninstance = \"mastodon.social\"\ntoken_file = \"{}\"\n",
+            token_path.to_string_lossy()
+        ),
+    );
+
+    plur_doctor(&config_path)
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("chmod 600"));
+
+    std::fs::set_permissions(&token_path, std::fs::Permissions::from_mode(0o600)).unwrap();
+    plur_doctor(&config_path).assert().success();
+}
@@ -0,0 +1,252 @@
+//! ActivityPub outbox export
+//!
+//! Writes published posts as an `outbox.json` like the one in a Mastodon
+//! archive: an ActivityStreams `OrderedCollection` of `Create` activities,
+//! each wrapping a `Note` with the post's publish time, content warning,
+//! language, addressing and attached images. Servers and tools that import Mastodon archives
+//! can read it.
+//!
+//! Posts already on Mastodon keep their status URI as the note ID; the rest
+//! get an ID under the `--actor` URL. Direct posts are left out, since who
+//! they were addressed to is not recorded.
+
+use crate::media::MediaBundle;
+use crate::output::open_output;
+use crate::posts::{query_published_posts, PublishedPost};
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use libplurcast::db::{Database, PostQuery};
+use libplurcast::types::Visibility;
+use serde_json::{json, Value};
+use std::io::Write;
+use tracing::info;
+
+/// ActivityStreams public collection
+const PUBLIC: &str = "https://www.w3.org/ns/activitystreams#Public";
+
+/// Export published posts matching `filter` as an ActivityPub outbox
+///
+/// `actor` is the account's ActivityPub ID, e.g.
+/// `https://mastodon.social/users/alice`. With `bundle_media`, attachments
+/// are copied next to the output file, as a Mastodon archive holds them.
+pub async fn export_activitypub(
+    db: &Database,
+    instance: Option<&str>,
+    actor: &str,
+    filter: &PostQuery,
+    output_file: Option<String>,
+    bundle_media: bool,
+) -> Result<()> {
+    let mut posts = query_published_posts(db, instance, filter).await?;
+    if bundle_media {
+        let mut bundle = MediaBundle::for_output(output_file.as_deref())?;
+        bundle.add_posts(&mut posts)?;
+        bundle.finish();
+    }
+    let outbox = render_outbox(&posts, actor);
+    let count = outbox["totalItems"].as_u64().unwrap_or(0);
+
+    let mut document =
+        serde_json::to_string_pretty(&outbox).context("Failed to serialize outbox")?;
+    document.push('\n');
+    open_output(output_file)?
+        .write_all(document.as_bytes())
+        .context("Failed to write outbox to output")?;
+
+    info!("Successfully exported {} post(s) as an outbox", count);
+    Ok(())
+}
+
+/// Render posts (oldest first) as an outbox collection
+pub fn render_outbox(posts: &[PublishedPost], actor: &str) -> Value {
+    let actor = actor.trim_end_matches('/');
+    let items: Vec<Value> = posts
+        .iter()
+        .filter(|post| post.visibility != Visibility::Direct)
+        .map(|post| create_activity(post, actor))
+        .collect();
+
+    json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": "outbox.json",
+        "type": "OrderedCollection",
+        "totalItems": items.len(),
+        "orderedItems": items,
+    })
+}
+
+/// A `Create` activity wrapping the post as a `Note`
+fn create_activity(post: &PublishedPost, actor: &str) -> Value {
+    let mastodon = post.copies.iter().find(|c| c.platform == "mastodon");
+    let stored = |field: &str| {
+        mastodon
+            .and_then(|c| c.raw_response.as_ref())
+            .and_then(|raw| raw.get(field)?.as_str())
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let id = stored("uri").unwrap_or_else(|| format!("{}/statuses/{}", actor, post.id));
+    let url = stored("url").or_else(|| mastodon.and_then(|c| c.url.clone()));
+
+    let followers = format!("{}/followers", actor);
+    let (to, cc) = match post.visibility {
+        Visibility::Public => (vec![PUBLIC.to_string()], vec![followers]),
+        Visibility::Unlisted => (vec![followers], vec![PUBLIC.to_string()]),
+        Visibility::Private | Visibility::Direct => (vec![followers], Vec::new()),
+    };
+    let published = DateTime::from_timestamp(post.created_at, 0)
+        .map(|date| date.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+        .unwrap_or_default();
+    let content = content_html(&post.content);
+
+    let mut note = json!({
+        "id": id,
+        "type": "Note",
+        "attributedTo": actor,
+        "published": published,
+        "to": to,
+        "cc": cc,
+        "summary": post.content_warning,
+        "sensitive": post.content_warning.is_some(),
+        "content": content,
+    });
+    if let Some(url) = url {
+        note["url"] = json!(url);
+    }
+    if let Some(ref language) = post.language {
+        note["contentMap"] = json!({ language.as_str(): content });
+    }
+    let attachments: Vec<Value> = post
+        .media
+        .iter()
+        .filter_map(|media| {
+            Some(json!({
+                "type": "Document",
+                "mediaType": media.mime_type.as_str(),
+                "url": media.url.as_ref()?,
+                "name": media.alt_text,
+            }))
+        })
+        .collect();
+    if !attachments.is_empty() {
+        note["attachment"] = json!(attachments);
+    }
+
+    json!({
+        "id": format!("{}/activity", id),
+        "type": "Create",
+        "actor": actor,
+        "published": published,
+        "to": note["to"],
+        "cc": note["cc"],
+        "object": note,
+    })
+}
+
+/// Plain text as HTML paragraphs, the way Mastodon renders statuses
+fn content_html(text: &str) -> String {
+    text.trim()
+        .split("\n\n")
+        .filter(|paragraph| !paragraph.trim().is_empty())
+        .map(|paragraph| format!("<p>{}</p>", escape_html(paragraph).replace('\n', "<br />")))
+        .collect()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::posts::{PublishedCopy, PublishedMedia};
+    use libplurcast::types::ImageMimeType;
+
+    const ACTOR: &str = "https://example.social/users/alice";
+
+    fn post(id: &str, visibility: Visibility, copies: Vec<PublishedCopy>) -> PublishedPost {
+        PublishedPost {
+            id: id.to_string(),
+            content: "Fish & chips\n\nfor <lunch>\ntoday".to_string(),
+            created_at: 1_709_294_400,
+            content_warning: None,
+            visibility,
+            language: Some("en".to_string()),
+            labels: Vec::new(),
+            copies,
+            media: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_content_html() {
+        assert_eq!(
+            content_html("Fish & chips\n\nfor <lunch>\ntoday"),
+            "<p>Fish &amp; chips</p><p>for &lt;lunch&gt;<br />today</p>"
+        );
+    }
+
+    #[test]
+    fn test_render_outbox() {
+        let on_mastodon = PublishedCopy {
+            platform: "mastodon".to_string(),
+            platform_post_id: "110".to_string(),
+            url: Some("https://example.social/@alice/110".to_string()),
+            raw_response: Some(json!({
+                "uri": "https://example.social/users/alice/statuses/110",
+                "url": "https://example.social/@alice/110",
+            })),
+        };
+        let mut unlisted = post("p2", Visibility::Unlisted, Vec::new());
+        unlisted.media.push(PublishedMedia {
+            file_path: "/home/alice/cat.png".to_string(),
+            mime_type: ImageMimeType::Png,
+            file_size: 3,
+            file_hash: "ab".repeat(32),
+            alt_text: Some("A cat".to_string()),
+            url: Some("media/cat.png".to_string()),
+        });
+        let posts = vec![
+            post("p1", Visibility::Public, vec![on_mastodon]),
+            unlisted,
+            post("p3", Visibility::Direct, Vec::new()),
+        ];
+
+        let outbox = render_outbox(&posts, &format!("{}/", ACTOR));
+        assert_eq!(outbox["type"], "OrderedCollection");
+        assert_eq!(outbox["totalItems"], 2);
+
+        let first = &outbox["orderedItems"][0];
+        assert_eq!(first["type"], "Create");
+        assert_eq!(
+            first["id"],
+            "https://example.social/users/alice/statuses/110/activity"
+        );
+        assert_eq!(first["object"]["url"], "https://example.social/@alice/110");
+        assert_eq!(first["object"]["to"], json!([PUBLIC]));
+        assert_eq!(first["object"]["published"], "2024-03-01T12:00:00Z");
+        assert_eq!(
+            first["object"]["contentMap"]["en"],
+            first["object"]["content"]
+        );
+
+        let second = &outbox["orderedItems"][1]["object"];
+        assert_eq!(second["id"], format!("{}/statuses/p2", ACTOR));
+        assert_eq!(second["to"], json!([format!("{}/followers", ACTOR)]));
+        assert_eq!(second["cc"], json!([PUBLIC]));
+        assert!(second.get("url").is_none());
+        assert_eq!(
+            second["attachment"],
+            json!([{
+                "type": "Document",
+                "mediaType": "image/png",
+                "url": "media/cat.png",
+                "name": "A cat",
+            }])
+        );
+        assert!(first["object"].get("attachment").is_none());
+    }
+}
@@ -0,0 +1,338 @@
+//! JSON Feed and RSS export
+//!
+//! Renders the most recent published posts as a feed that can be hosted
+//! as is: [JSON Feed 1.1](https://www.jsonfeed.org/version/1.1/) or
+//! RSS 2.0. Items are newest first, identified by their Plurcast post ID,
+//! and link to the post on the first platform that has a web view (the
+//! `--platform` one, when given). Attached images become JSON Feed
+//! attachments or an RSS enclosure.
+
+use crate::media::MediaBundle;
+use crate::output::open_output;
+use crate::posts::{query_published_posts, PublishedPost};
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use libplurcast::db::{Database, PostQuery};
+use serde_json::json;
+use std::io::Write;
+use tracing::info;
+
+/// Posts in a feed when `--limit` is not given
+pub const DEFAULT_FEED_LIMIT: usize = 20;
+
+/// Feed document format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    JsonFeed,
+    Rss,
+}
+
+/// Feed-level details
+#[derive(Debug, Clone)]
+pub struct FeedInfo {
+    pub title: String,
+    /// Home page of the feed; RSS requires one, so the newest post's link
+    /// stands in when not given
+    pub link: Option<String>,
+}
+
+/// Export the most recent published posts matching `filter` as a feed
+///
+/// Without a limit in `filter`, the feed holds [`DEFAULT_FEED_LIMIT`]
+/// posts. With a platform, items link to the post there. With
+/// `bundle_media`, attachments are copied next to the output file.
+pub async fn export_feed(
+    db: &Database,
+    instance: Option<&str>,
+    format: FeedFormat,
+    info: &FeedInfo,
+    filter: &PostQuery,
+    output_file: Option<String>,
+    bundle_media: bool,
+) -> Result<()> {
+    let filter = PostQuery {
+        limit: Some(filter.limit.unwrap_or(DEFAULT_FEED_LIMIT)),
+        ..filter.clone()
+    };
+    let mut posts = query_published_posts(db, instance, &filter).await?;
+    posts.reverse();
+
+    if bundle_media {
+        let mut bundle = MediaBundle::for_output(output_file.as_deref())?;
+        bundle.add_posts(&mut posts)?;
+        bundle.finish();
+    }
+
+    if let Some(ref platform) = filter.platform {
+        for post in &mut posts {
+            post.copies.sort_by_key(|copy| copy.platform != platform);
+        }
+    }
+
+    let document = match format {
+        FeedFormat::JsonFeed => render_json_feed(&posts, info)?,
+        FeedFormat::Rss => render_rss(&posts, info),
+    };
+
+    open_output(output_file)?
+        .write_all(document.as_bytes())
+        .context("Failed to write feed to output")?;
+
+    info!("Successfully exported {} post(s) as a feed", posts.len());
+    Ok(())
+}
+
+/// Web link of a post, from the first copy that has one
+fn item_link(post: &PublishedPost) -> Option<&str> {
+    post.copies.iter().find_map(|copy| copy.url.as_deref())
+}
+
+/// Render posts (newest first) as a JSON Feed 1.1 document
+pub fn render_json_feed(posts: &[PublishedPost], info: &FeedInfo) -> Result<String> {
+    let items: Vec<serde_json::Value> = posts
+        .iter()
+        .map(|post| {
+            let mut item = json!({
+                "id": post.id,
+                "content_text": post.content,
+                "date_published": format_date(post.created_at, "%Y-%m-%dT%H:%M:%SZ"),
+                "_plurcast": {
+                    "ids": post
+                        .copies
+                        .iter()
+                        .map(|copy| (copy.platform.clone(), json!(copy.platform_post_id)))
+                        .collect::<serde_json::Map<_, _>>(),
+                },
+            });
+            if let Some(url) = item_link(post) {
+                item["url"] = json!(url);
+            }
+            if !post.labels.is_empty() {
+                item["tags"] = json!(post.labels);
+            }
+            let attachments: Vec<serde_json::Value> = post
+                .media
+                .iter()
+                .filter_map(|media| {
+                    let mut attachment = json!({
+                        "url": media.url.as_ref()?,
+                        "mime_type": media.mime_type.as_str(),
+                        "size_in_bytes": media.file_size,
+                    });
+                    if let Some(ref alt) = media.alt_text {
+                        attachment["title"] = json!(alt);
+                    }
+                    Some(attachment)
+                })
+                .collect();
+            if !attachments.is_empty() {
+                item["attachments"] = json!(attachments);
+            }
+            if let Some(ref cw) = post.content_warning {
+                item["_plurcast"]["content_warning"] = json!(cw);
+            }
+            item
+        })
+        .collect();
+
+    let mut feed = json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": info.title,
+        "items": items,
+    });
+    if let Some(ref link) = info.link {
+        feed["home_page_url"] = json!(link);
+    }
+
+    let mut document =
+        serde_json::to_string_pretty(&feed).context("Failed to serialize JSON Feed")?;
+    document.push('\n');
+    Ok(document)
+}
+
+/// Render posts (newest first) as an RSS 2.0 document
+pub fn render_rss(posts: &[PublishedPost], info: &FeedInfo) -> String {
+    let link = info
+        .link
+        .as_deref()
+        .or_else(|| posts.iter().find_map(item_link))
+        .unwrap_or("");
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<rss version=\"2.0\">\n<channel>\n");
+    out.push_str(&format!("  <title>{}</title>\n", escape_xml(&info.title)));
+    out.push_str(&format!("  <link>{}</link>\n", escape_xml(link)));
+    out.push_str(&format!(
+        "  <description>{}</description>\n",
+        escape_xml(&info.title)
+    ));
+    if let Some(newest) = posts.first() {
+        out.push_str(&format!(
+            "  <lastBuildDate>{}</lastBuildDate>\n",
+            rfc2822(newest.created_at)
+        ));
+    }
+
+    for post in posts {
+        out.push_str("  <item>\n");
+        if let Some(ref cw) = post.content_warning {
+            out.push_str(&format!("    <title>CW: {}</title>\n", escape_xml(cw)));
+        }
+        if let Some(url) = item_link(post) {
+            out.push_str(&format!("    <link>{}</link>\n", escape_xml(url)));
+        }
+        out.push_str(&format!(
+            "    <description>{}</description>\n",
+            escape_xml(&post.content)
+        ));
+        out.push_str(&format!(
+            "    <guid isPermaLink=\"false\">urn:uuid:{}</guid>\n",
+            escape_xml(&post.id)
+        ));
+        out.push_str(&format!(
+            "    <pubDate>{}</pubDate>\n",
+            rfc2822(post.created_at)
+        ));
+        // RSS allows one enclosure per item
+        if let Some(media) = post.media.iter().find(|media| media.url.is_some()) {
+            out.push_str(&format!(
+                "    <enclosure url=\"{}\" length=\"{}\" type=\"{}\" />\n",
+                escape_xml(media.url.as_deref().unwrap_or("")),
+                media.file_size,
+                media.mime_type.as_str()
+            ));
+        }
+        for label in &post.labels {
+            out.push_str(&format!("    <category>{}</category>\n", escape_xml(label)));
+        }
+        out.push_str("  </item>\n");
+    }
+
+    out.push_str("</channel>\n</rss>\n");
+    out
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn format_date(timestamp: i64, format: &str) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|date| date.format(format).to_string())
+        .unwrap_or_default()
+}
+
+fn rfc2822(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|date| date.to_rfc2822())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::posts::{PublishedCopy, PublishedMedia};
+    use libplurcast::types::{ImageMimeType, Visibility};
+
+    fn posts() -> Vec<PublishedPost> {
+        vec![
+            PublishedPost {
+                id: "22222222-0000-0000-0000-000000000000".to_string(),
+                content: "Fish & chips <3".to_string(),
+                created_at: 1_709_380_800,
+                content_warning: Some("food".to_string()),
+                visibility: Visibility::Public,
+                language: None,
+                labels: vec!["lunch".to_string()],
+                copies: vec![PublishedCopy {
+                    platform: "nostr".to_string(),
+                    platform_post_id: "note1fish".to_string(),
+                    url: Some("https://njump.me/note1fish".to_string()),
+                    raw_response: None,
+                }],
+                media: vec![PublishedMedia {
+                    file_path: "/home/alice/fish.jpg".to_string(),
+                    mime_type: ImageMimeType::Jpeg,
+                    file_size: 2048,
+                    file_hash: "ab".repeat(32),
+                    alt_text: Some("Fish & chips".to_string()),
+                    url: Some("media/fish.jpg".to_string()),
+                }],
+            },
+            PublishedPost {
+                id: "11111111-0000-0000-0000-000000000000".to_string(),
+                content: "Hello".to_string(),
+                created_at: 1_709_294_400,
+                content_warning: None,
+                visibility: Visibility::Public,
+                language: None,
+                labels: Vec::new(),
+                copies: vec![PublishedCopy {
+                    platform: "ssb".to_string(),
+                    platform_post_id: "%hello.sha256".to_string(),
+                    url: None,
+                    raw_response: None,
+                }],
+                media: Vec::new(),
+            },
+        ]
+    }
+
+    fn info(link: Option<&str>) -> FeedInfo {
+        FeedInfo {
+            title: "My posts".to_string(),
+            link: link.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_render_json_feed() {
+        let feed: serde_json::Value = serde_json::from_str(
+            &render_json_feed(&posts(), &info(Some("https://example.com/"))).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(feed["version"], "https://jsonfeed.org/version/1.1");
+        assert_eq!(feed["home_page_url"], "https://example.com/");
+        let items = feed["items"].as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0]["id"], "22222222-0000-0000-0000-000000000000");
+        assert_eq!(items[0]["url"], "https://njump.me/note1fish");
+        assert_eq!(items[0]["date_published"], "2024-03-02T12:00:00Z");
+        assert_eq!(items[0]["tags"], json!(["lunch"]));
+        assert_eq!(items[0]["_plurcast"]["ids"]["nostr"], "note1fish");
+        assert_eq!(
+            items[0]["attachments"],
+            json!([{
+                "url": "media/fish.jpg",
+                "mime_type": "image/jpeg",
+                "size_in_bytes": 2048,
+                "title": "Fish & chips",
+            }])
+        );
+        assert!(items[1].get("url").is_none());
+        assert!(items[1].get("attachments").is_none());
+    }
+
+    #[test]
+    fn test_render_rss() {
+        let rss = render_rss(&posts(), &info(None));
+
+        // Channel link falls back to the newest post's link
+        assert!(rss.contains("<link>https://njump.me/note1fish</link>\n  <description>"));
+        assert!(rss.contains("<description>Fish &amp; chips &lt;3</description>"));
+        assert!(rss.contains("<title>CW: food</title>"));
+        assert!(rss.contains(
+            "<guid isPermaLink=\"false\">urn:uuid:11111111-0000-0000-0000-000000000000</guid>"
+        ));
+        assert!(rss.contains("Mar 2024 12:00:00 +0000</pubDate>"));
+        assert!(rss.contains("<category>lunch</category>"));
+        assert!(rss
+            .contains("<enclosure url=\"media/fish.jpg\" length=\"2048\" type=\"image/jpeg\" />"));
+        assert_eq!(rss.matches("<enclosure").count(), 1);
+        assert_eq!(rss.matches("<item>").count(), 2);
+    }
+}
@@ -0,0 +1,265 @@
+//! plur-export - Export posts to various formats
+//!
+//! This tool exports Plurcast posts to platform-specific formats
+//! for backup, migration, or sharing with other tools.
+
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use libplurcast::config::Config;
+use libplurcast::db::{Database, PostQuery};
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use libplurcast::service::history::{self, HistoryQuery};
+use libplurcast::types::PostStatus;
+use std::ffi::OsString;
+use tracing::{error, info};
+
+pub mod activitypub;
+pub mod feed;
+pub mod markdown;
+pub mod media;
+pub mod nostr;
+pub mod output;
+pub mod posts;
+pub mod ssb;
+
+#[derive(Parser)]
+#[command(name = "plur-export")]
+#[command(about = "Export posts to various formats", long_about = None)]
+struct Cli {
+    /// Export format
+    #[arg(short, long, value_enum)]
+    format: ExportFormat,
+
+    /// Output file (default: stdout), or directory for markdown
+    #[arg(short, long, visible_alias = "out")]
+    output: Option<String>,
+
+    /// Feed title
+    #[arg(long, default_value = "Posts")]
+    title: String,
+
+    /// Home page the feed belongs to (default: the newest post's link)
+    #[arg(long, value_name = "URL")]
+    link: Option<String>,
+
+    /// Your ActivityPub actor ID, e.g. https://mastodon.social/users/alice
+    /// (activitypub)
+    #[arg(long, value_name = "URL")]
+    actor: Option<String>,
+
+    /// Only posts published to this platform (nostr, mastodon, ssb); feeds
+    /// also link items to it
+    #[arg(long, value_name = "PLATFORM")]
+    platform: Option<String>,
+
+    /// Only posts created since this date (Unix timestamp, YYYY-MM-DD, or
+    /// ISO 8601)
+    #[arg(long, value_name = "DATE")]
+    since: Option<String>,
+
+    /// Only posts created until this date (Unix timestamp, YYYY-MM-DD, or
+    /// ISO 8601)
+    #[arg(long, value_name = "DATE")]
+    until: Option<String>,
+
+    /// Only posts with this status, as in plur-history (default: posted for
+    /// markdown, feeds and activitypub; any for ssb and nostr)
+    #[arg(long, value_name = "STATUS")]
+    #[arg(value_parser = ["posted", "failed", "draft", "scheduled", "deleted", "pending", "missed"])]
+    status: Option<String>,
+
+    /// Only the N most recent matching posts (feeds default to 20)
+    #[arg(long, value_name = "N")]
+    limit: Option<usize>,
+
+    /// Copy attached media into media/ next to the export and link to the
+    /// copies, for a self-contained archive (needs --output)
+    #[arg(long)]
+    bundle_media: bool,
+
+    /// Verbose output
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT"
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL"
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+enum ExportFormat {
+    /// SSB message format (JSON lines)
+    Ssb,
+    /// A Markdown file per published post with front matter (Hugo, Jekyll)
+    Markdown,
+    /// JSON Feed 1.1 of the most recent published posts
+    Jsonfeed,
+    /// RSS 2.0 feed of the most recent published posts
+    Rss,
+    /// Signed Nostr events as published (JSON lines), for rebroadcasting
+    Nostr,
+    /// ActivityPub outbox.json, as in a Mastodon archive
+    Activitypub,
+}
+
+impl ExportFormat {
+    /// The platform a format exports from, for formats tied to one
+    fn platform(&self) -> Option<&'static str> {
+        match self {
+            ExportFormat::Ssb => Some("ssb"),
+            ExportFormat::Nostr => Some("nostr"),
+            _ => None,
+        }
+    }
+}
+
+/// The posts to export, filtered the way plur-history filters them
+fn export_filter(cli: &Cli) -> Result<PostQuery> {
+    if let (Some(implied), Some(asked)) = (cli.format.platform(), cli.platform.as_deref()) {
+        if asked != implied {
+            anyhow::bail!(
+                "--format {} only exports {} posts; drop --platform {}",
+                implied,
+                implied,
+                asked
+            );
+        }
+    }
+
+    let since = match cli.since {
+        Some(ref date) => Some(history::parse_date(date)?),
+        None => None,
+    };
+    let until = match cli.until {
+        Some(ref date) => Some(history::parse_date(date)?),
+        None => None,
+    };
+
+    let query = HistoryQuery {
+        platform: cli.platform.clone(),
+        // The value parser only lets through known statuses
+        status: cli.status.as_deref().and_then(PostStatus::parse),
+        since,
+        until,
+        limit: cli.limit,
+        ..Default::default()
+    };
+    Ok(query.to_post_query()?)
+}
+
+/// Run plur-export with a command line, program name first
+///
+/// The `plur-export` binary and `plur export` both start here.
+#[tokio::main]
+pub async fn main_from(args: Vec<OsString>) -> Result<()> {
+    let cli = Cli::parse_from(args);
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    let filter = export_filter(&cli)?;
+
+    // Load configuration
+    let config = Config::load().context("Failed to load configuration")?;
+
+    // Initialize database
+    let db = Database::from_config(&config)
+        .await
+        .context("Failed to initialize database")?;
+
+    let instance = config.mastodon.as_ref().map(|m| m.instance.as_str());
+    let feed_info = feed::FeedInfo {
+        title: cli.title.clone(),
+        link: cli.link.clone(),
+    };
+
+    // Execute export
+    let result = match cli.format {
+        ExportFormat::Ssb => ssb::export_ssb(&db, &filter, cli.output, cli.bundle_media).await,
+        ExportFormat::Nostr if cli.bundle_media => Err(anyhow::anyhow!(
+            "--bundle-media can't rewrite links in signed Nostr events"
+        )),
+        ExportFormat::Nostr => nostr::export_nostr(&db, &filter, cli.output).await,
+        ExportFormat::Activitypub => match cli.actor {
+            Some(ref actor) => {
+                activitypub::export_activitypub(
+                    &db,
+                    instance,
+                    actor,
+                    &filter,
+                    cli.output,
+                    cli.bundle_media,
+                )
+                .await
+            }
+            None => Err(anyhow::anyhow!(
+                "--format activitypub needs --actor URL, your account's ActivityPub ID (e.g. https://mastodon.social/users/alice)"
+            )),
+        },
+        ExportFormat::Markdown => match cli.output {
+            Some(ref dir) => {
+                markdown::export_markdown(&db, instance, &filter, dir, cli.bundle_media).await
+            }
+            None => Err(anyhow::anyhow!(
+                "--format markdown writes a file per post: pass --out DIR"
+            )),
+        },
+        ExportFormat::Jsonfeed | ExportFormat::Rss => {
+            let format = match cli.format {
+                ExportFormat::Rss => feed::FeedFormat::Rss,
+                _ => feed::FeedFormat::JsonFeed,
+            };
+            feed::export_feed(
+                &db,
+                instance,
+                format,
+                &feed_info,
+                &filter,
+                cli.output,
+                cli.bundle_media,
+            )
+            .await
+        }
+    };
+
+    match result {
+        Ok(()) => {
+            info!("Export completed successfully");
+            std::process::exit(0);
+        }
+        Err(e) => {
+            error!("Export failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
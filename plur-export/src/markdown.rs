@@ -0,0 +1,327 @@
+//! Markdown export for static site generators
+//!
+//! Writes one Markdown file per published post into a directory, with YAML
+//! front matter holding its date, platforms, platform post IDs and links.
+//! Files are named `YYYY-MM-DD-<id>.md`, which Jekyll's `_posts/` expects
+//! and Hugo accepts in any content section. Attached images follow the
+//! content, linked where they are hosted or to their bundled copy.
+
+use crate::media::MediaBundle;
+use crate::posts::{query_published_posts, PublishedPost};
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use libplurcast::db::{Database, PostQuery};
+use std::path::Path;
+use tracing::info;
+
+/// Characters of the first line used as the page title
+const TITLE_CHARS: usize = 60;
+
+/// Export published posts matching `filter` as Markdown files into `out_dir`
+///
+/// Existing files with the same names are overwritten, so exporting again
+/// into the same directory updates it. With `bundle_media`, attachments are
+/// copied into `out_dir/media` too.
+pub async fn export_markdown(
+    db: &Database,
+    instance: Option<&str>,
+    filter: &PostQuery,
+    out_dir: &str,
+    bundle_media: bool,
+) -> Result<()> {
+    let mut posts = query_published_posts(db, instance, filter).await?;
+
+    if posts.is_empty() {
+        info!("No published posts found to export");
+        return Ok(());
+    }
+
+    let out_dir = shellexpand::tilde(out_dir).to_string();
+    let out_dir = Path::new(&out_dir);
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create output directory: {}", out_dir.display()))?;
+    info!("Exporting to directory: {}", out_dir.display());
+
+    if bundle_media {
+        let mut bundle = MediaBundle::new(out_dir);
+        bundle.add_posts(&mut posts)?;
+        bundle.finish();
+    }
+
+    for post in &posts {
+        let path = out_dir.join(file_name(post));
+        std::fs::write(&path, render_post(post))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    info!("Successfully exported {} post(s) as Markdown", posts.len());
+    Ok(())
+}
+
+/// `YYYY-MM-DD-<first 8 characters of the post ID>.md`
+pub fn file_name(post: &PublishedPost) -> String {
+    let short_id: String = post.id.chars().take(8).collect();
+    format!(
+        "{}-{}.md",
+        format_date(post.created_at, "%Y-%m-%d"),
+        short_id
+    )
+}
+
+/// Front matter followed by the post content
+pub fn render_post(post: &PublishedPost) -> String {
+    let mut out = String::from("---\n");
+    out.push_str(&format!("title: {}\n", quote(&post.title(TITLE_CHARS))));
+    out.push_str(&format!(
+        "date: {}\n",
+        format_date(post.created_at, "%Y-%m-%dT%H:%M:%SZ")
+    ));
+    out.push_str(&format!("plurcast_id: {}\n", quote(&post.id)));
+    out.push_str(&format!(
+        "platforms: [{}]\n",
+        post.copies
+            .iter()
+            .map(|c| quote(&c.platform))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+    out.push_str("ids:\n");
+    for copy in &post.copies {
+        out.push_str(&format!(
+            "  {}: {}\n",
+            copy.platform,
+            quote(&copy.platform_post_id)
+        ));
+    }
+    if post.copies.iter().any(|c| c.url.is_some()) {
+        out.push_str("links:\n");
+        for copy in &post.copies {
+            if let Some(ref url) = copy.url {
+                out.push_str(&format!("  {}: {}\n", copy.platform, quote(url)));
+            }
+        }
+    }
+    if let Some(ref cw) = post.content_warning {
+        out.push_str(&format!("content_warning: {}\n", quote(cw)));
+    }
+    if !post.labels.is_empty() {
+        out.push_str(&format!(
+            "tags: [{}]\n",
+            post.labels
+                .iter()
+                .map(|l| quote(l))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    out.push_str("---\n\n");
+    out.push_str(post.content.trim_end());
+    out.push('\n');
+    for media in &post.media {
+        if let Some(ref url) = media.url {
+            out.push_str(&format!(
+                "\n![{}]({})\n",
+                media
+                    .alt_text
+                    .as_deref()
+                    .unwrap_or("")
+                    .replace(['[', ']'], ""),
+                url
+            ));
+        }
+    }
+    out
+}
+
+/// A YAML double-quoted string (JSON strings are valid YAML)
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+fn format_date(timestamp: i64, format: &str) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|date| date.format(format).to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::posts::{PublishedCopy, PublishedMedia};
+    use libplurcast::types::{Attachment, ImageMimeType, Post, PostRecord, PostStatus, Visibility};
+    use tempfile::TempDir;
+
+    fn published() -> PublishedPost {
+        PublishedPost {
+            id: "0f8e2c1a-5b6d-4e7f-8a9b-0c1d2e3f4a5b".to_string(),
+            content: "Release \"1.0\" is out!\n\nDetails soon.".to_string(),
+            created_at: 1_709_294_400,
+            content_warning: None,
+            visibility: Visibility::Public,
+            language: None,
+            labels: vec!["release".to_string()],
+            copies: vec![
+                PublishedCopy {
+                    platform: "nostr".to_string(),
+                    platform_post_id: "note1abc".to_string(),
+                    url: Some("https://njump.me/note1abc".to_string()),
+                    raw_response: None,
+                },
+                PublishedCopy {
+                    platform: "ssb".to_string(),
+                    platform_post_id: "%msg.sha256".to_string(),
+                    url: None,
+                    raw_response: None,
+                },
+            ],
+            media: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_file_name_is_dated() {
+        assert_eq!(file_name(&published()), "2024-03-01-0f8e2c1a.md");
+    }
+
+    #[test]
+    fn test_render_post_front_matter() {
+        let rendered = render_post(&published());
+        assert_eq!(
+            rendered,
+            "---\n\
+             title: \"Release \\\"1.0\\\" is out!\"\n\
+             date: 2024-03-01T12:00:00Z\n\
+             plurcast_id: \"0f8e2c1a-5b6d-4e7f-8a9b-0c1d2e3f4a5b\"\n\
+             platforms: [\"nostr\", \"ssb\"]\n\
+             ids:\n  nostr: \"note1abc\"\n  ssb: \"%msg.sha256\"\n\
+             links:\n  nostr: \"https://njump.me/note1abc\"\n\
+             tags: [\"release\"]\n\
+             ---\n\n\
+             Release \"1.0\" is out!\n\nDetails soon.\n"
+        );
+    }
+
+    #[test]
+    fn test_render_post_links_media() {
+        let mut post = published();
+        post.media = vec![
+            PublishedMedia {
+                file_path: "/home/alice/cat.png".to_string(),
+                mime_type: ImageMimeType::Png,
+                file_size: 3,
+                file_hash: "ab".repeat(32),
+                alt_text: Some("A [sleepy] cat".to_string()),
+                url: Some("media/cat.png".to_string()),
+            },
+            PublishedMedia {
+                file_path: "/home/alice/gone.png".to_string(),
+                mime_type: ImageMimeType::Png,
+                file_size: 3,
+                file_hash: "cd".repeat(32),
+                alt_text: None,
+                url: None,
+            },
+        ];
+
+        let rendered = render_post(&post);
+        assert!(rendered.ends_with("Details soon.\n\n![A sleepy cat](media/cat.png)\n"));
+    }
+
+    #[tokio::test]
+    async fn test_export_markdown_writes_a_file_per_post() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let db = Database::new(temp_dir.path().join("test.db").to_str().unwrap()).await?;
+        db.create_post(&Post {
+            id: "abcdef12-0000-0000-0000-000000000000".to_string(),
+            content: "Hello".to_string(),
+            created_at: 1_709_294_400,
+            scheduled_at: None,
+            status: PostStatus::Posted,
+            metadata: None,
+        })
+        .await?;
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: "abcdef12-0000-0000-0000-000000000000".to_string(),
+            platform: "mastodon".to_string(),
+            platform_post_id: Some("110".to_string()),
+            posted_at: Some(1_709_294_400),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await?;
+
+        let out_dir = temp_dir.path().join("content");
+        export_markdown(
+            &db,
+            Some("mastodon.social"),
+            &PostQuery::default(),
+            out_dir.to_str().unwrap(),
+            false,
+        )
+        .await?;
+
+        let page = std::fs::read_to_string(out_dir.join("2024-03-01-abcdef12.md"))?;
+        assert!(page.contains("ids:\n  mastodon: \"110\"\n"), "{}", page);
+        assert!(page.contains("mastodon: \"https://mastodon.social/web/statuses/110\""));
+        assert!(page.ends_with("---\n\nHello\n"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_markdown_bundles_media() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let db = Database::new(temp_dir.path().join("test.db").to_str().unwrap()).await?;
+        let post_id = "abcdef12-0000-0000-0000-000000000000";
+        db.create_post(&Post {
+            id: post_id.to_string(),
+            content: "Look".to_string(),
+            created_at: 1_709_294_400,
+            scheduled_at: None,
+            status: PostStatus::Posted,
+            metadata: None,
+        })
+        .await?;
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post_id.to_string(),
+            platform: "nostr".to_string(),
+            platform_post_id: Some("note1look".to_string()),
+            posted_at: Some(1_709_294_400),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await?;
+        let image = temp_dir.path().join("cat.png");
+        std::fs::write(&image, b"png bytes")?;
+        db.create_attachment(&Attachment::new(
+            post_id.to_string(),
+            image.to_str().unwrap().to_string(),
+            ImageMimeType::Png,
+            9,
+            "ab".repeat(32),
+            Some("A cat".to_string()),
+        ))
+        .await?;
+
+        let out_dir = temp_dir.path().join("site");
+        export_markdown(
+            &db,
+            None,
+            &PostQuery::default(),
+            out_dir.to_str().unwrap(),
+            true,
+        )
+        .await?;
+
+        let bundled = format!("media/{}.png", "ab".repeat(32));
+        assert_eq!(std::fs::read(out_dir.join(&bundled))?, b"png bytes");
+        let page = std::fs::read_to_string(out_dir.join("2024-03-01-abcdef12.md"))?;
+        assert!(page.ends_with(&format!("Look\n\n![A cat]({})\n", bundled)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+//! Media bundling
+//!
+//! With `--bundle-media`, attachment files are copied into a `media/`
+//! directory next to the export and the export links to those copies, so
+//! the directory can be archived or hosted on its own. Files are named after
+//! their SHA-256 hash, so an image attached to several posts is copied once
+//! and exporting again into the same directory copies nothing new.
+
+use crate::posts::PublishedPost;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+/// Directory inside the export directory that holds the media
+pub const MEDIA_DIR: &str = "media";
+
+/// Media files copied into an export directory
+pub struct MediaBundle {
+    dir: PathBuf,
+    copied: usize,
+    missing: usize,
+}
+
+impl MediaBundle {
+    /// Bundle into `<export_dir>/media`, created on the first copy
+    pub fn new(export_dir: &Path) -> Self {
+        Self {
+            dir: export_dir.join(MEDIA_DIR),
+            copied: 0,
+            missing: 0,
+        }
+    }
+
+    /// Bundle media next to an output file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error without an output file, since media can't be
+    /// bundled next to stdout.
+    pub fn for_output(output_file: Option<&str>) -> Result<Self> {
+        match output_file {
+            Some(path) => {
+                let path = PathBuf::from(shellexpand::tilde(path).to_string());
+                let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+                Ok(Self::new(&dir))
+            }
+            None => anyhow::bail!("--bundle-media copies files next to the export: pass --output"),
+        }
+    }
+
+    /// Copy a file into the bundle
+    ///
+    /// Returns its link relative to the export directory, or `None` when
+    /// the file no longer exists (its link is left as it was).
+    pub fn add(
+        &mut self,
+        file_path: &str,
+        file_hash: &str,
+        extension: &str,
+    ) -> Result<Option<String>> {
+        let source = Path::new(file_path);
+        if !source.is_file() {
+            warn!("Attachment not found, not bundled: {}", file_path);
+            self.missing += 1;
+            return Ok(None);
+        }
+
+        let name = format!("{}.{}", file_hash, extension);
+        let target = self.dir.join(&name);
+        if target.is_file() {
+            debug!("Already bundled: {}", name);
+        } else {
+            std::fs::create_dir_all(&self.dir).with_context(|| {
+                format!("Failed to create media directory: {}", self.dir.display())
+            })?;
+            std::fs::copy(source, &target)
+                .with_context(|| format!("Failed to copy {} to {}", file_path, target.display()))?;
+            self.copied += 1;
+        }
+
+        Ok(Some(format!("{}/{}", MEDIA_DIR, name)))
+    }
+
+    /// Bundle the media of published posts, linking them to their copies
+    pub fn add_posts(&mut self, posts: &mut [PublishedPost]) -> Result<()> {
+        for media in posts.iter_mut().flat_map(|post| post.media.iter_mut()) {
+            if let Some(link) = self.add(
+                &media.file_path,
+                &media.file_hash,
+                media.mime_type.extension(),
+            )? {
+                media.url = Some(link);
+            }
+        }
+        Ok(())
+    }
+
+    /// Log what was bundled
+    pub fn finish(self) {
+        if self.missing > 0 {
+            warn!(
+                "{} attachment(s) were missing on disk and keep their original links",
+                self.missing
+            );
+        }
+        info!(
+            "Copied {} media file(s) into {}",
+            self.copied,
+            self.dir.display()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_add_copies_once_by_hash() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let source = temp_dir.path().join("photo.png");
+        std::fs::write(&source, b"png bytes")?;
+        let export_dir = temp_dir.path().join("export");
+
+        let mut bundle = MediaBundle::new(&export_dir);
+        let link = bundle.add(source.to_str().unwrap(), "abc123", "png")?;
+        assert_eq!(link.as_deref(), Some("media/abc123.png"));
+        let again = bundle.add(source.to_str().unwrap(), "abc123", "png")?;
+        assert_eq!(again, link);
+        assert_eq!(bundle.copied, 1);
+        assert_eq!(
+            std::fs::read(export_dir.join("media/abc123.png"))?,
+            b"png bytes"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_skips_missing_files() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut bundle = MediaBundle::new(temp_dir.path());
+
+        let link = bundle.add("/nonexistent/photo.png", "abc123", "png")?;
+        assert_eq!(link, None);
+        assert_eq!(bundle.missing, 1);
+        assert!(!temp_dir.path().join(MEDIA_DIR).exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_for_output_needs_a_file() {
+        assert!(MediaBundle::for_output(None).is_err());
+        let bundle = MediaBundle::for_output(Some("site/feed.json")).unwrap();
+        assert_eq!(bundle.dir, Path::new("site/media"));
+    }
+}
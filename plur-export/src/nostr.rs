@@ -0,0 +1,214 @@
+//! Nostr event export
+//!
+//! Writes the signed events stored when notes were published, one per line
+//! (JSON lines), oldest first. The events are the ones the relays accepted,
+//! signatures included, so tools such as `nak` can rebroadcast them to new
+//! relays.
+
+use crate::output::open_output;
+use anyhow::{Context, Result};
+use libplurcast::db::{Database, PostQuery};
+use serde_json::Value;
+use std::io::Write;
+use tracing::{debug, info, warn};
+
+/// Query the signed events of notes published to Nostr, oldest first
+///
+/// `filter` narrows the posts; its platform is always Nostr. Notes recorded
+/// without their event (published before events were stored) are left out
+/// with a warning.
+pub async fn query_nostr_events(db: &Database, filter: &PostQuery) -> Result<Vec<Value>> {
+    debug!("Querying Nostr events from database");
+
+    let posts = db
+        .query_posts(&PostQuery {
+            platform: Some("nostr".to_string()),
+            ..filter.clone()
+        })
+        .await
+        .context("Failed to query Nostr posts")?;
+
+    let mut events = Vec::new();
+    let mut missing = 0;
+    for found in posts.into_iter().rev() {
+        if !found
+            .records
+            .iter()
+            .any(|r| r.platform == "nostr" && r.success)
+        {
+            continue;
+        }
+
+        let event = db
+            .get_raw_responses(&found.post.id)
+            .await
+            .context("Failed to read platform responses")?
+            .remove("nostr")
+            .and_then(|mut raw| raw.get_mut("event").map(Value::take))
+            .filter(is_signed_event);
+        match event {
+            Some(event) => events.push(event),
+            None => {
+                debug!("No stored event for post {}", found.post.id);
+                missing += 1;
+            }
+        }
+    }
+
+    if missing > 0 {
+        warn!(
+            "{} Nostr post(s) have no stored event and were not exported",
+            missing
+        );
+    }
+    info!("Found {} Nostr event(s) to export", events.len());
+    Ok(events)
+}
+
+/// Fields every signed event has (NIP-01)
+const EVENT_FIELDS: [&str; 7] = [
+    "id",
+    "pubkey",
+    "created_at",
+    "kind",
+    "tags",
+    "content",
+    "sig",
+];
+
+/// Whether a value has the fields of a signed event
+fn is_signed_event(event: &Value) -> bool {
+    EVENT_FIELDS.iter().all(|field| event.get(field).is_some())
+}
+
+/// Export signed Nostr events to output as JSON lines
+pub async fn export_nostr(
+    db: &Database,
+    filter: &PostQuery,
+    output_file: Option<String>,
+) -> Result<()> {
+    let events = query_nostr_events(db, filter).await?;
+
+    if events.is_empty() {
+        info!("No Nostr events found to export");
+        return Ok(());
+    }
+
+    let mut output = open_output(output_file)?;
+    for event in &events {
+        let json = serde_json::to_string(event).context("Failed to serialize event to JSON")?;
+        writeln!(output, "{}", json).context("Failed to write event to output")?;
+    }
+
+    info!("Successfully exported {} Nostr events", events.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libplurcast::types::{Post, PostRecord, PostStatus};
+    use tempfile::TempDir;
+
+    fn event(id: &str, created_at: i64) -> Value {
+        serde_json::json!({
+            "id": id,
+            "pubkey": "ab".repeat(32),
+            "created_at": created_at,
+            "kind": 1,
+            "tags": [],
+            "content": "Hello",
+            "sig": "cd".repeat(64),
+        })
+    }
+
+    async fn add_note(db: &Database, post_id: &str, created_at: i64, raw: Option<Value>) {
+        db.create_post(&Post {
+            id: post_id.to_string(),
+            content: "Hello".to_string(),
+            created_at,
+            scheduled_at: None,
+            status: PostStatus::Posted,
+            metadata: None,
+        })
+        .await
+        .unwrap();
+        db.create_post_record_with_response(
+            &PostRecord {
+                id: None,
+                post_id: post_id.to_string(),
+                platform: "nostr".to_string(),
+                platform_post_id: Some(format!("note1{}", post_id)),
+                posted_at: Some(created_at),
+                success: true,
+                error_message: None,
+                account_name: "default".to_string(),
+            },
+            raw.as_ref(),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_query_nostr_events() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let db = Database::new(temp_dir.path().join("test.db").to_str().unwrap()).await?;
+
+        add_note(
+            &db,
+            "second",
+            2_000,
+            Some(serde_json::json!({ "event": event("e2", 2_000), "relays": {} })),
+        )
+        .await;
+        add_note(
+            &db,
+            "first",
+            1_000,
+            Some(serde_json::json!({ "event": event("e1", 1_000) })),
+        )
+        .await;
+        add_note(
+            &db,
+            "unsigned",
+            3_000,
+            Some(serde_json::json!({ "event": null })),
+        )
+        .await;
+        add_note(&db, "bare", 4_000, None).await;
+
+        let events = query_nostr_events(&db, &PostQuery::default()).await?;
+        assert_eq!(events, vec![event("e1", 1_000), event("e2", 2_000)]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_to_file() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let db = Database::new(temp_dir.path().join("test.db").to_str().unwrap()).await?;
+        add_note(
+            &db,
+            "note",
+            1_000,
+            Some(serde_json::json!({ "event": event("e1", 1_000) })),
+        )
+        .await;
+
+        let output_path = temp_dir.path().join("events.jsonl");
+        export_nostr(
+            &db,
+            &PostQuery::default(),
+            Some(output_path.to_str().unwrap().to_string()),
+        )
+        .await?;
+
+        let contents = std::fs::read_to_string(&output_path)?;
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(serde_json::from_str::<Value>(lines[0])?, event("e1", 1_000));
+
+        Ok(())
+    }
+}
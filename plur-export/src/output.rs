@@ -0,0 +1,19 @@
+//! Output destination shared by the single-document exports
+
+use anyhow::{Context, Result};
+use std::io::Write;
+use tracing::{debug, info};
+
+/// Open the output file, or stdout when none is given
+pub fn open_output(output_file: Option<String>) -> Result<Box<dyn Write>> {
+    if let Some(path) = output_file {
+        let expanded_path = shellexpand::tilde(&path).to_string();
+        let file = std::fs::File::create(&expanded_path)
+            .with_context(|| format!("Failed to create output file: {}", expanded_path))?;
+        info!("Exporting to file: {}", expanded_path);
+        Ok(Box::new(file))
+    } else {
+        debug!("Exporting to stdout");
+        Ok(Box::new(std::io::stdout()))
+    }
+}
@@ -0,0 +1,268 @@
+//! Published posts, as read by the document exports
+//!
+//! Markdown and feed exports describe each post once, with where it went:
+//! the platform post ID, its web link (see
+//! [`libplurcast::platforms::links`]) and the stored platform response,
+//! along with its attached media.
+
+use anyhow::{Context, Result};
+use libplurcast::db::{Database, PostQuery};
+use libplurcast::language::LANGUAGE_METADATA_KEY;
+use libplurcast::platforms::links::post_url;
+use libplurcast::types::{AttachmentStatus, ImageMimeType, PostStatus, Visibility};
+use tracing::{debug, info};
+
+/// A post that was published to at least one platform
+#[derive(Debug, Clone)]
+pub struct PublishedPost {
+    /// Post ID in Plurcast database
+    pub id: String,
+    pub content: String,
+    /// Unix timestamp
+    pub created_at: i64,
+    pub content_warning: Option<String>,
+    pub visibility: Visibility,
+    /// Language tag, e.g. "en"
+    pub language: Option<String>,
+    pub labels: Vec<String>,
+    /// One copy per platform, in the order they were published
+    pub copies: Vec<PublishedCopy>,
+    /// Attached media, in the order they were attached
+    pub media: Vec<PublishedMedia>,
+}
+
+/// Where a post was published on one platform
+#[derive(Debug, Clone)]
+pub struct PublishedCopy {
+    pub platform: String,
+    pub platform_post_id: String,
+    /// Web link, for platforms that have one
+    pub url: Option<String>,
+    /// Response stored when the post was published, if any
+    pub raw_response: Option<serde_json::Value>,
+}
+
+/// A media file attached to a post
+#[derive(Debug, Clone)]
+pub struct PublishedMedia {
+    /// Path to the file on disk
+    pub file_path: String,
+    pub mime_type: ImageMimeType,
+    /// File size in bytes
+    pub file_size: u64,
+    /// SHA-256 of the file content (hex)
+    pub file_hash: String,
+    pub alt_text: Option<String>,
+    /// Link to the file: where a platform hosts it, or its bundled copy
+    pub url: Option<String>,
+}
+
+impl PublishedPost {
+    /// First line of the content, shortened to `max_chars`
+    pub fn title(&self, max_chars: usize) -> String {
+        let line = self
+            .content
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or("");
+        if line.chars().count() > max_chars {
+            let cut: String = line.chars().take(max_chars.saturating_sub(1)).collect();
+            format!("{}…", cut.trim_end())
+        } else {
+            line.to_string()
+        }
+    }
+}
+
+/// Query published posts matching `filter`, oldest first
+///
+/// Without a status in `filter`, only posted posts are returned; either
+/// way, posts with no successful platform record are left out. With a
+/// limit, the most recent ones are kept. `instance` is the configured
+/// Mastodon instance, used for links to statuses without a stored response.
+pub async fn query_published_posts(
+    db: &Database,
+    instance: Option<&str>,
+    filter: &PostQuery,
+) -> Result<Vec<PublishedPost>> {
+    debug!("Querying published posts from database");
+
+    let found = db
+        .query_posts(&PostQuery {
+            status: filter.status.clone().or(Some(PostStatus::Posted)),
+            ..filter.clone()
+        })
+        .await
+        .context("Failed to query posts")?;
+
+    let mut posts = Vec::new();
+    for found in found.into_iter().rev() {
+        let post = found.post;
+        let raw_responses = db
+            .get_raw_responses(&post.id)
+            .await
+            .context("Failed to read platform responses")?;
+
+        // Records come newest first; keep the latest copy per platform
+        let mut copies: Vec<PublishedCopy> = Vec::new();
+        for record in found.records.into_iter().filter(|r| r.success) {
+            let platform_post_id = match record.platform_post_id {
+                Some(id) => id,
+                None => continue,
+            };
+            if copies.iter().any(|c| c.platform == record.platform) {
+                continue;
+            }
+            let raw_response = raw_responses.get(&record.platform).cloned();
+            copies.push(PublishedCopy {
+                url: post_url(
+                    &record.platform,
+                    &platform_post_id,
+                    raw_response.as_ref(),
+                    instance,
+                ),
+                platform: record.platform,
+                platform_post_id,
+                raw_response,
+            });
+        }
+        if copies.is_empty() {
+            continue;
+        }
+        copies.reverse();
+
+        let metadata = post
+            .metadata
+            .as_deref()
+            .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok());
+        let metadata_str = |key: &str| {
+            metadata
+                .as_ref()
+                .and_then(|m| m.get(key)?.as_str().map(String::from))
+        };
+        let content_warning = metadata_str("content_warning");
+        let language = metadata_str(LANGUAGE_METADATA_KEY);
+        let visibility = Visibility::from_metadata(post.metadata.as_deref());
+        let labels = db
+            .get_labels(&post.id)
+            .await
+            .context("Failed to read labels")?;
+        let media = query_media(db, &post.id).await?;
+
+        posts.push(PublishedPost {
+            id: post.id,
+            content: post.content,
+            created_at: post.created_at,
+            content_warning,
+            visibility,
+            language,
+            labels,
+            copies,
+            media,
+        });
+    }
+
+    info!("Found {} published post(s) to export", posts.len());
+    Ok(posts)
+}
+
+/// Media attached to a post, linked to the first platform that hosts them
+async fn query_media(db: &Database, post_id: &str) -> Result<Vec<PublishedMedia>> {
+    let attachments = db
+        .get_attachments_for_post(post_id)
+        .await
+        .context("Failed to read attachments")?;
+
+    let mut media = Vec::with_capacity(attachments.len());
+    for attachment in attachments {
+        let url = db
+            .get_attachment_uploads(&attachment.id)
+            .await
+            .context("Failed to read attachment uploads")?
+            .into_iter()
+            .filter(|upload| upload.status == AttachmentStatus::Uploaded)
+            .find_map(|upload| upload.remote_url);
+        media.push(PublishedMedia {
+            file_path: attachment.file_path,
+            mime_type: attachment.mime_type,
+            file_size: attachment.file_size,
+            file_hash: attachment.file_hash,
+            alt_text: attachment.alt_text,
+            url,
+        });
+    }
+    Ok(media)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libplurcast::types::{Post, PostRecord};
+    use tempfile::TempDir;
+
+    fn post(content: &str) -> PublishedPost {
+        PublishedPost {
+            id: "id".to_string(),
+            content: content.to_string(),
+            created_at: 0,
+            content_warning: None,
+            visibility: Visibility::Public,
+            language: None,
+            labels: Vec::new(),
+            copies: Vec::new(),
+            media: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_title_is_first_line() {
+        assert_eq!(post("\n  Hello world  \nMore").title(40), "Hello world");
+        assert_eq!(post("abcdefghij").title(5), "abcd…");
+        assert_eq!(post("").title(5), "");
+    }
+
+    #[tokio::test]
+    async fn test_query_published_posts() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let db = Database::new(temp_dir.path().join("test.db").to_str().unwrap()).await?;
+
+        for (id, status, created_at) in [
+            ("newer", PostStatus::Posted, 2_000),
+            ("older", PostStatus::Posted, 1_000),
+            ("draft", PostStatus::Draft, 1_500),
+        ] {
+            db.create_post(&Post {
+                id: id.to_string(),
+                content: format!("Post {}", id),
+                created_at,
+                scheduled_at: None,
+                status,
+                metadata: Some(r#"{"content_warning":"cw"}"#.to_string()),
+            })
+            .await?;
+            db.create_post_record(&PostRecord {
+                id: None,
+                post_id: id.to_string(),
+                platform: "nostr".to_string(),
+                platform_post_id: Some(format!("note1{}", id)),
+                posted_at: Some(created_at),
+                success: true,
+                error_message: None,
+                account_name: "default".to_string(),
+            })
+            .await?;
+        }
+
+        let posts = query_published_posts(&db, None, &PostQuery::default()).await?;
+        let ids: Vec<&str> = posts.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, ["older", "newer"]);
+        assert_eq!(posts[0].content_warning.as_deref(), Some("cw"));
+        assert_eq!(
+            posts[0].copies[0].url.as_deref(),
+            Some("https://njump.me/note1older")
+        );
+
+        Ok(())
+    }
+}
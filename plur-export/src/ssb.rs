@@ -1,9 +1,11 @@
 //! SSB export functionality
 
+use crate::media::MediaBundle;
+use crate::output::open_output;
 use anyhow::{Context, Result};
-use libplurcast::db::Database;
+use libplurcast::db::{Database, PostQuery};
+use libplurcast::types::ImageMimeType;
 use serde::{Deserialize, Serialize};
-use sqlx::Row;
 use std::io::Write;
 use tracing::{debug, info};
 
@@ -26,51 +28,83 @@ pub struct SsbExportMessage {
 
     /// Post ID in Plurcast database
     pub post_id: String,
+
+    /// Media attached to the post
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<ExportedAttachment>,
 }
 
-/// Query SSB posts from database
-pub async fn query_ssb_posts(db: &Database) -> Result<Vec<SsbExportMessage>> {
+/// A media attachment referenced by an exported post
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedAttachment {
+    /// Path to the file on disk, or relative to the export when bundled
+    pub file_path: String,
+
+    /// MIME type (e.g. "image/png")
+    pub mime_type: String,
+
+    /// SHA-256 of the file content (hex)
+    pub file_hash: String,
+
+    /// Alt text, if provided
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alt_text: Option<String>,
+}
+
+/// Query SSB posts from database, oldest first
+///
+/// `filter` narrows the posts; its platform is always SSB.
+pub async fn query_ssb_posts(db: &Database, filter: &PostQuery) -> Result<Vec<SsbExportMessage>> {
     debug!("Querying SSB posts from database");
 
-    let query = r#"
-        SELECT 
-            p.id as post_id,
-            p.content,
-            p.created_at,
-            p.metadata,
-            pr.platform_post_id
-        FROM posts p
-        INNER JOIN post_records pr ON p.id = pr.post_id
-        WHERE pr.platform = 'ssb' AND pr.success = 1
-        ORDER BY p.created_at ASC
-    "#;
-
-    let rows = sqlx::query(query)
-        .fetch_all(db.pool())
+    let posts = db
+        .query_posts(&PostQuery {
+            platform: Some("ssb".to_string()),
+            ..filter.clone()
+        })
         .await
         .context("Failed to query SSB posts")?;
 
     let mut messages = Vec::new();
 
-    for row in rows {
-        let post_id: String = row.try_get("post_id")?;
-        let content: String = row.try_get("content")?;
-        let created_at: i64 = row.try_get("created_at")?;
-        let platform_post_id: Option<String> = row.try_get("platform_post_id")?;
-        let metadata: Option<String> = row.try_get("metadata")?;
+    for found in posts.into_iter().rev() {
+        // Records come newest first; export the latest SSB message
+        let record = match found
+            .records
+            .into_iter()
+            .find(|r| r.platform == "ssb" && r.success)
+        {
+            Some(record) => record,
+            None => continue,
+        };
+        let post = found.post;
 
         // Try to extract sequence from metadata if available
-        let sequence = metadata
+        let sequence = post
+            .metadata
             .as_ref()
             .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
             .and_then(|v| v.get("sequence").and_then(|s| s.as_i64()));
 
+        let attachments = db
+            .get_attachments_for_post(&post.id)
+            .await?
+            .into_iter()
+            .map(|attachment| ExportedAttachment {
+                file_path: attachment.file_path,
+                mime_type: attachment.mime_type.as_str().to_string(),
+                file_hash: attachment.file_hash,
+                alt_text: attachment.alt_text,
+            })
+            .collect();
+
         messages.push(SsbExportMessage {
-            message_id: platform_post_id,
-            content,
-            timestamp: created_at,
+            message_id: record.platform_post_id,
+            content: post.content,
+            timestamp: post.created_at,
             sequence,
-            post_id,
+            post_id: post.id,
+            attachments,
         });
     }
 
@@ -79,26 +113,40 @@ pub async fn query_ssb_posts(db: &Database) -> Result<Vec<SsbExportMessage>> {
 }
 
 /// Export SSB posts to output
-pub async fn export_ssb(db: &Database, output_file: Option<String>) -> Result<()> {
+///
+/// With `bundle_media`, attachments are copied next to the output file and
+/// their paths point to the copies.
+pub async fn export_ssb(
+    db: &Database,
+    filter: &PostQuery,
+    output_file: Option<String>,
+    bundle_media: bool,
+) -> Result<()> {
     // Query posts
-    let messages = query_ssb_posts(db).await?;
+    let mut messages = query_ssb_posts(db, filter).await?;
 
     if messages.is_empty() {
         info!("No SSB posts found to export");
         return Ok(());
     }
 
+    if bundle_media {
+        let mut bundle = MediaBundle::for_output(output_file.as_deref())?;
+        for attachment in messages.iter_mut().flat_map(|m| m.attachments.iter_mut()) {
+            let extension = ImageMimeType::from_mime_str(&attachment.mime_type)
+                .map(|mime| mime.extension())
+                .unwrap_or("bin");
+            if let Some(link) =
+                bundle.add(&attachment.file_path, &attachment.file_hash, extension)?
+            {
+                attachment.file_path = link;
+            }
+        }
+        bundle.finish();
+    }
+
     // Format as JSON lines (one message per line)
-    let mut output: Box<dyn Write> = if let Some(path) = output_file {
-        let expanded_path = shellexpand::tilde(&path).to_string();
-        let file = std::fs::File::create(&expanded_path)
-            .with_context(|| format!("Failed to create output file: {}", expanded_path))?;
-        info!("Exporting to file: {}", expanded_path);
-        Box::new(file)
-    } else {
-        debug!("Exporting to stdout");
-        Box::new(std::io::stdout())
-    };
+    let mut output = open_output(output_file)?;
 
     // Write each message as a JSON line
     for message in &messages {
@@ -125,7 +173,7 @@ mod tests {
     #[tokio::test]
     async fn test_query_empty_database() -> Result<()> {
         let (db, _temp_dir) = setup_test_db().await?;
-        let messages = query_ssb_posts(&db).await?;
+        let messages = query_ssb_posts(&db, &PostQuery::default()).await?;
         assert_eq!(messages.len(), 0);
         Ok(())
     }
@@ -158,7 +206,7 @@ mod tests {
         .await?;
 
         // Query posts
-        let messages = query_ssb_posts(&db).await?;
+        let messages = query_ssb_posts(&db, &PostQuery::default()).await?;
         assert_eq!(messages.len(), 1);
         assert_eq!(messages[0].content, content);
         assert_eq!(messages[0].message_id, Some("ssb:%abc123".to_string()));
@@ -166,6 +214,98 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_query_ssb_posts_includes_attachments() -> Result<()> {
+        use libplurcast::{Attachment, ImageMimeType};
+
+        let (db, _temp_dir) = setup_test_db().await?;
+        let post_id = uuid::Uuid::new_v4().to_string();
+
+        sqlx::query(
+            "INSERT INTO posts (id, content, created_at, status) VALUES (?, 'With image', ?, 'posted')",
+        )
+        .bind(&post_id)
+        .bind(chrono::Utc::now().timestamp())
+        .execute(db.pool())
+        .await?;
+        sqlx::query(
+            "INSERT INTO post_records (post_id, platform, platform_post_id, success) VALUES (?, 'ssb', '%img.sha256', 1)"
+        )
+        .bind(&post_id)
+        .execute(db.pool())
+        .await?;
+
+        db.create_attachment(&Attachment::new(
+            post_id.clone(),
+            "/home/user/photo.png".to_string(),
+            ImageMimeType::Png,
+            1024,
+            "ab".repeat(32),
+            Some("A photo".to_string()),
+        ))
+        .await?;
+
+        let messages = query_ssb_posts(&db, &PostQuery::default()).await?;
+        assert_eq!(messages[0].attachments.len(), 1);
+        assert_eq!(messages[0].attachments[0].mime_type, "image/png");
+        assert_eq!(
+            messages[0].attachments[0].alt_text.as_deref(),
+            Some("A photo")
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_ssb_posts_applies_filter() -> Result<()> {
+        use libplurcast::types::{Post, PostRecord, PostStatus};
+
+        let (db, _temp_dir) = setup_test_db().await?;
+        for created_at in [1_000, 2_000, 3_000] {
+            let post_id = format!("post-{}", created_at);
+            db.create_post(&Post {
+                id: post_id.clone(),
+                content: format!("Posted at {}", created_at),
+                created_at,
+                scheduled_at: None,
+                status: PostStatus::Posted,
+                metadata: None,
+            })
+            .await?;
+            db.create_post_record(&PostRecord {
+                id: None,
+                post_id,
+                platform: "ssb".to_string(),
+                platform_post_id: Some(format!("%{}.sha256", created_at)),
+                posted_at: Some(created_at),
+                success: true,
+                error_message: None,
+                account_name: "default".to_string(),
+            })
+            .await?;
+        }
+
+        let between = PostQuery {
+            since: Some(1_500),
+            until: Some(3_000),
+            ..Default::default()
+        };
+        let messages = query_ssb_posts(&db, &between).await?;
+        let ids: Vec<&str> = messages.iter().map(|m| m.post_id.as_str()).collect();
+        assert_eq!(ids, ["post-2000", "post-3000"]);
+
+        // A limit keeps the most recent posts, still written oldest first
+        let latest = PostQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let messages = query_ssb_posts(&db, &latest).await?;
+        let ids: Vec<&str> = messages.iter().map(|m| m.post_id.as_str()).collect();
+        assert_eq!(ids, ["post-2000", "post-3000"]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_export_to_stdout() -> Result<()> {
         let (db, _temp_dir) = setup_test_db().await?;
@@ -190,7 +330,7 @@ mod tests {
         .await?;
 
         // Export (to stdout, which we can't easily capture in test)
-        let result = export_ssb(&db, None).await;
+        let result = export_ssb(&db, &PostQuery::default(), None, false).await;
         assert!(result.is_ok());
 
         Ok(())
@@ -222,7 +362,13 @@ mod tests {
 
         // Export to file
         let output_path = temp_dir.path().join("export.jsonl");
-        export_ssb(&db, Some(output_path.to_str().unwrap().to_string())).await?;
+        export_ssb(
+            &db,
+            &PostQuery::default(),
+            Some(output_path.to_str().unwrap().to_string()),
+            false,
+        )
+        .await?;
 
         // Verify file contents
         let contents = std::fs::read_to_string(&output_path)?;
@@ -0,0 +1,476 @@
+//! Static archive for `plur-history export`
+//!
+//! Renders posts as a small browsable site: `index.{html,md}` lists every
+//! post by month, newest first, and `posts/<id>.{html,md}` holds each post
+//! with its labels, thread position and a link to it on every platform it
+//! was published to (see [`libplurcast::platforms::links`]). HTML opens
+//! directly in a browser; Markdown suits static site generators.
+
+use std::collections::HashSet;
+
+use libplurcast::platforms::links::post_url;
+
+use crate::{HistoryEntry, PlatformStatus};
+
+/// Characters of content shown per post on the index
+const PREVIEW_CHARS: usize = 80;
+
+/// Stylesheet inlined into every HTML page
+const STYLE: &str =
+    "body{font-family:sans-serif;max-width:42rem;margin:2rem auto;padding:0 1rem;line-height:1.5}\
+time{color:#666}li{margin:.4rem 0}.cw{font-weight:bold}.failed{color:#a00}";
+
+/// Output format of an archive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Html,
+    Markdown,
+}
+
+impl ArchiveFormat {
+    /// Parse `html` or `markdown`
+    pub fn parse(format: &str) -> Option<Self> {
+        match format {
+            "html" => Some(Self::Html),
+            "markdown" => Some(Self::Markdown),
+            _ => None,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Markdown => "md",
+        }
+    }
+}
+
+/// A file of the archive, with its path relative to the output directory
+#[derive(Debug)]
+pub struct ArchiveFile {
+    pub path: String,
+    pub contents: String,
+}
+
+/// Render the archive for entries in display order (newest first)
+///
+/// `instance` is the configured Mastodon instance, used for links to
+/// statuses without a stored response.
+pub fn render(
+    entries: &[HistoryEntry],
+    format: ArchiveFormat,
+    instance: Option<&str>,
+) -> Vec<ArchiveFile> {
+    let exported: HashSet<&str> = entries.iter().map(|e| e.post_id.as_str()).collect();
+    let mut files = vec![ArchiveFile {
+        path: format!("index.{}", format.extension()),
+        contents: render_index(entries, format, instance),
+    }];
+
+    for entry in entries {
+        files.push(ArchiveFile {
+            path: format!("posts/{}", page_name(&entry.post_id, format)),
+            contents: render_post(entry, format, instance, &exported),
+        });
+    }
+
+    files
+}
+
+fn render_index(entries: &[HistoryEntry], format: ArchiveFormat, instance: Option<&str>) -> String {
+    let mut out = String::new();
+    let mut month = String::new();
+
+    match format {
+        ArchiveFormat::Html => {
+            out.push_str(&html_head("Post archive"));
+            out.push_str(&format!(
+                "<h1>Post archive</h1>\n<p>{} post(s)</p>\n",
+                entries.len()
+            ));
+        }
+        ArchiveFormat::Markdown => {
+            out.push_str(&format!("# Post archive\n\n{} post(s)\n", entries.len()));
+        }
+    }
+
+    for entry in entries {
+        let entry_month = format_date(entry.created_at, "%Y-%m");
+        if entry_month != month {
+            match format {
+                ArchiveFormat::Html => {
+                    if !month.is_empty() {
+                        out.push_str("</ul>\n");
+                    }
+                    out.push_str(&format!("<h2>{}</h2>\n<ul>\n", entry_month));
+                }
+                ArchiveFormat::Markdown => out.push_str(&format!("\n## {}\n\n", entry_month)),
+            }
+            month = entry_month;
+        }
+
+        let date = format_date(entry.created_at, "%Y-%m-%d %H:%M");
+        let page = format!("posts/{}", page_name(&entry.post_id, format));
+        let links: Vec<(String, String)> = entry
+            .platforms
+            .iter()
+            .filter_map(|platform| Some((platform.platform.clone(), link(platform, instance)?)))
+            .collect();
+
+        match format {
+            ArchiveFormat::Html => {
+                let links: Vec<String> = links
+                    .iter()
+                    .map(|(name, url)| {
+                        format!("<a href=\"{}\">{}</a>", escape_html(url), escape_html(name))
+                    })
+                    .collect();
+                out.push_str(&format!(
+                    "<li><time>{}</time> <a href=\"{}\">{}</a>",
+                    date,
+                    escape_html(&page),
+                    escape_html(&preview(entry))
+                ));
+                if !links.is_empty() {
+                    out.push_str(&format!(" ({})", links.join(" · ")));
+                }
+                out.push_str("</li>\n");
+            }
+            ArchiveFormat::Markdown => {
+                let links: Vec<String> = links
+                    .iter()
+                    .map(|(name, url)| format!("[{}]({})", name, url))
+                    .collect();
+                out.push_str(&format!(
+                    "- {} [{}]({})",
+                    date,
+                    escape_markdown(&preview(entry)),
+                    page
+                ));
+                if !links.is_empty() {
+                    out.push_str(&format!(" ({})", links.join(" · ")));
+                }
+                out.push('\n');
+            }
+        }
+    }
+
+    if format == ArchiveFormat::Html {
+        if !month.is_empty() {
+            out.push_str("</ul>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+    }
+    out
+}
+
+fn render_post(
+    entry: &HistoryEntry,
+    format: ArchiveFormat,
+    instance: Option<&str>,
+    exported: &HashSet<&str>,
+) -> String {
+    let date = format_date(entry.created_at, "%Y-%m-%d %H:%M:%S UTC");
+    // Link the thread's first part when it's in the archive too
+    let thread = entry.thread_id.as_deref().map(|thread_id| {
+        let first = exported
+            .contains(thread_id)
+            .then(|| page_name(thread_id, format));
+        (entry.thread_part.unwrap_or(1), first)
+    });
+
+    match format {
+        ArchiveFormat::Html => {
+            let mut out = html_head(&format!("Post {}", date));
+            out.push_str("<p><a href=\"../index.html\">← All posts</a></p>\n");
+            out.push_str(&format!("<h1><time>{}</time></h1>\n", date));
+            if let Some(ref cw) = entry.content_warning {
+                out.push_str(&format!("<p class=\"cw\">CW: {}</p>\n", escape_html(cw)));
+            }
+            for paragraph in entry.content.split("\n\n") {
+                out.push_str(&format!(
+                    "<p>{}</p>\n",
+                    escape_html(paragraph).replace('\n', "<br>\n")
+                ));
+            }
+            if let Some((part, first)) = thread {
+                match first {
+                    Some(first) => out.push_str(&format!(
+                        "<p>Part {} of a <a href=\"{}\">thread</a></p>\n",
+                        part,
+                        escape_html(&first)
+                    )),
+                    None => out.push_str(&format!("<p>Part {} of a thread</p>\n", part)),
+                }
+            }
+            if !entry.labels.is_empty() {
+                out.push_str(&format!(
+                    "<p>Labels: {}</p>\n",
+                    escape_html(&entry.labels.join(", "))
+                ));
+            }
+            for attachment in &entry.attachments {
+                let name = attachment
+                    .alt_text
+                    .as_deref()
+                    .unwrap_or(&attachment.file_path);
+                out.push_str(&format!("<p>Attachment: {}</p>\n", escape_html(name)));
+            }
+            if !entry.platforms.is_empty() {
+                out.push_str("<h2>Published</h2>\n<ul>\n");
+                for platform in &entry.platforms {
+                    let name = escape_html(&platform_name(platform));
+                    match (link(platform, instance), &platform.platform_post_id) {
+                        (Some(url), Some(id)) => out.push_str(&format!(
+                            "<li>{}: <a href=\"{}\">{}</a></li>\n",
+                            name,
+                            escape_html(&url),
+                            escape_html(id)
+                        )),
+                        (None, Some(id)) if platform.success => {
+                            out.push_str(&format!("<li>{}: {}</li>\n", name, escape_html(id)))
+                        }
+                        _ => out.push_str(&format!(
+                            "<li class=\"failed\">{}: failed{}</li>\n",
+                            name,
+                            failure_reason(platform)
+                                .map(|e| format!(" ({})", escape_html(e)))
+                                .unwrap_or_default()
+                        )),
+                    }
+                }
+                out.push_str("</ul>\n");
+            }
+            out.push_str("</body>\n</html>\n");
+            out
+        }
+        ArchiveFormat::Markdown => {
+            let mut out = format!("# {}\n\n[← All posts](../index.md)\n\n", date);
+            if let Some(ref cw) = entry.content_warning {
+                out.push_str(&format!("**CW: {}**\n\n", escape_markdown(cw)));
+            }
+            out.push_str(entry.content.trim_end());
+            out.push_str("\n\n");
+            if let Some((part, first)) = thread {
+                match first {
+                    Some(first) => {
+                        out.push_str(&format!("Part {} of a [thread]({})\n\n", part, first))
+                    }
+                    None => out.push_str(&format!("Part {} of a thread\n\n", part)),
+                }
+            }
+            if !entry.labels.is_empty() {
+                out.push_str(&format!(
+                    "Labels: {}\n\n",
+                    escape_markdown(&entry.labels.join(", "))
+                ));
+            }
+            for attachment in &entry.attachments {
+                let name = attachment
+                    .alt_text
+                    .as_deref()
+                    .unwrap_or(&attachment.file_path);
+                out.push_str(&format!("Attachment: {}\n\n", escape_markdown(name)));
+            }
+            if !entry.platforms.is_empty() {
+                out.push_str("## Published\n\n");
+                for platform in &entry.platforms {
+                    let name = platform_name(platform);
+                    match (link(platform, instance), &platform.platform_post_id) {
+                        (Some(url), Some(id)) => {
+                            out.push_str(&format!("- {}: [{}]({})\n", name, id, url))
+                        }
+                        (None, Some(id)) if platform.success => {
+                            out.push_str(&format!("- {}: `{}`\n", name, id))
+                        }
+                        _ => out.push_str(&format!(
+                            "- {}: failed{}\n",
+                            name,
+                            failure_reason(platform)
+                                .map(|e| format!(" ({})", escape_markdown(e)))
+                                .unwrap_or_default()
+                        )),
+                    }
+                }
+            }
+            out
+        }
+    }
+}
+
+/// Web link for a successful platform result
+fn link(platform: &PlatformStatus, instance: Option<&str>) -> Option<String> {
+    if !platform.success {
+        return None;
+    }
+    post_url(
+        &platform.platform,
+        platform.platform_post_id.as_deref()?,
+        platform.raw_response.as_ref(),
+        instance,
+    )
+}
+
+fn failure_reason(platform: &PlatformStatus) -> Option<&str> {
+    platform.error.as_deref().filter(|e| !e.is_empty())
+}
+
+/// Platform name, with the account when it isn't the default one
+fn platform_name(platform: &PlatformStatus) -> String {
+    if platform.account == "default" {
+        platform.platform.clone()
+    } else {
+        format!("{} ({})", platform.platform, platform.account)
+    }
+}
+
+/// File name of a post's page; IDs are UUIDs, but imported posts may differ
+fn page_name(post_id: &str, format: ArchiveFormat) -> String {
+    let safe: String = post_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}.{}", safe, format.extension())
+}
+
+/// First line of the content, shortened for the index
+fn preview(entry: &HistoryEntry) -> String {
+    if let Some(ref cw) = entry.content_warning {
+        return format!("CW: {}", cw);
+    }
+    let first_line = entry.content.lines().next().unwrap_or("");
+    if first_line.chars().count() > PREVIEW_CHARS {
+        let cut: String = first_line.chars().take(PREVIEW_CHARS).collect();
+        format!("{}...", cut)
+    } else if first_line.is_empty() {
+        "(no text)".to_string()
+    } else {
+        first_line.to_string()
+    }
+}
+
+fn format_date(timestamp: i64, format: &str) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .unwrap_or_else(chrono::Utc::now)
+        .format(format)
+        .to_string()
+}
+
+fn html_head(title: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n",
+        escape_html(title),
+        STYLE
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape characters that would turn inline text into links or emphasis
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '[' | ']' | '*' | '_' | '`' | '<' | '>') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(post_id: &str, content: &str, platforms: Vec<PlatformStatus>) -> HistoryEntry {
+        HistoryEntry {
+            post_id: post_id.to_string(),
+            content: content.to_string(),
+            created_at: 1_700_000_000,
+            platforms,
+            labels: Vec::new(),
+            content_warning: None,
+            attachments: Vec::new(),
+            thread_id: None,
+            thread_part: None,
+        }
+    }
+
+    fn status(platform: &str, id: Option<&str>, error: Option<&str>) -> PlatformStatus {
+        PlatformStatus {
+            platform: platform.to_string(),
+            account: "default".to_string(),
+            success: id.is_some(),
+            platform_post_id: id.map(String::from),
+            error: error.map(String::from),
+            sequence: None,
+            message_hash: None,
+            raw_response: None,
+            attempts: Vec::new(),
+            relays: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_html_archive_links_and_escapes() {
+        let entries = vec![entry(
+            "abc",
+            "<b>hi</b> & bye",
+            vec![
+                status("nostr", Some("note1xyz"), None),
+                status("mastodon", Some("42"), None),
+                status("ssb", None, Some("Authentication failed")),
+            ],
+        )];
+        let files = render(&entries, ArchiveFormat::Html, Some("mastodon.social"));
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "index.html");
+        assert_eq!(files[1].path, "posts/abc.html");
+
+        let index = &files[0].contents;
+        assert!(index.contains("<h2>2023-11</h2>"));
+        assert!(index.contains("href=\"posts/abc.html\""));
+        assert!(index.contains("&lt;b&gt;hi&lt;/b&gt; &amp; bye"));
+        assert!(index.contains("<a href=\"https://njump.me/note1xyz\">nostr</a>"));
+
+        let page = &files[1].contents;
+        assert!(page.contains("<a href=\"https://mastodon.social/web/statuses/42\">42</a>"));
+        assert!(page.contains("ssb: failed (Authentication failed)"));
+        assert!(!page.contains("<b>hi"));
+    }
+
+    #[test]
+    fn test_markdown_archive() {
+        let entries = vec![entry(
+            "a/b",
+            "Hello [world]\nsecond line",
+            vec![status("ssb", Some("%abc=.sha256"), None)],
+        )];
+        let files = render(&entries, ArchiveFormat::Markdown, None);
+        assert_eq!(files[1].path, "posts/a_b.md");
+        assert!(files[0]
+            .contents
+            .contains("- 2023-11-14 22:13 [Hello \\[world\\]](posts/a_b.md)"));
+        assert!(files[1].contents.contains("Hello [world]\nsecond line"));
+        assert!(files[1].contents.contains("- ssb: `%abc=.sha256`"));
+    }
+}
@@ -0,0 +1,72 @@
+//! CSV output for `--format csv`
+//!
+//! Fields are quoted as RFC 4180 asks: only when they contain a comma,
+//! quote, CR or LF, with quotes doubled. `--columns` picks the columns from
+//! the template fields (see [`crate::template`]) plus `timestamp`, the Unix
+//! time the post was created.
+
+/// Columns printed when `--columns` isn't given
+pub const DEFAULT_COLUMNS: &[&str] = &[
+    "post_id",
+    "timestamp",
+    "platform",
+    "success",
+    "platform_post_id",
+    "error",
+    "content",
+];
+
+/// Every column `--columns` accepts
+pub const COLUMNS: &[&str] = &[
+    "post_id",
+    "timestamp",
+    "created_at",
+    "date",
+    "content",
+    "labels",
+    "content_warning",
+    "thread_id",
+    "thread_part",
+    "platform",
+    "account",
+    "success",
+    "platform_post_id",
+    "error",
+];
+
+/// Quote a field if it needs it
+pub fn field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Join fields into one CSV line
+pub fn row<'a>(values: impl IntoIterator<Item = &'a str>) -> String {
+    values.into_iter().map(field).collect::<Vec<_>>().join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_quoting() {
+        assert_eq!(field("plain"), "plain");
+        assert_eq!(field(""), "");
+        assert_eq!(field("a,b"), "\"a,b\"");
+        assert_eq!(field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(field("cr\rlf"), "\"cr\rlf\"");
+    }
+
+    #[test]
+    fn test_row() {
+        assert_eq!(
+            row(["id", "nostr", "Hello, world"]),
+            "id,nostr,\"Hello, world\""
+        );
+    }
+}
@@ -0,0 +1,1347 @@
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use libplurcast::platforms::links;
+use libplurcast::service::history::{self, HistoryQuery as ServiceHistoryQuery};
+use libplurcast::service::PlurcastService;
+use libplurcast::{retention, Config, PostStatus, RelayResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ffi::OsString;
+
+mod archive;
+mod csv;
+mod template;
+
+#[derive(Parser, Debug)]
+#[command(name = "plur-history")]
+#[command(version, about = "Query local posting history")]
+#[command(
+    long_about = r#"Query local posting history with filtering and formatting options.
+
+EXAMPLES:
+    # Show last 20 posts (default)
+    plur-history
+
+    # Show more posts
+    plur-history --limit 50
+
+    # Filter by platform
+    plur-history --platform nostr
+    plur-history --platform mastodon
+    plur-history --platform ssb
+
+    # Filter by date range
+    plur-history --since "2025-10-01" --until "2025-10-05"
+    plur-history --since "2025-10-01T09:00:00Z"
+
+    # Search content
+    plur-history --search "rust"
+    plur-history --search "announcement"
+
+    # Filter by label (set with plur-post --label)
+    plur-history --label campaign:launch
+
+    # Only posts sent from one account (see plur-creds)
+    plur-history --account work
+
+    # Every post that failed everywhere, e.g. to repost it
+    plur-history --status failed --limit 1000 --format json
+
+    # Cancelled and cleared posts (hidden otherwise)
+    plur-history --status deleted
+
+    # Combine filters
+    plur-history --platform nostr --since "2025-10-01" --limit 10
+
+    # Page through a large history (next cursor is printed after each page)
+    plur-history --limit 100
+    plur-history --limit 100 --cursor <NEXT_CURSOR>
+
+    # JSON output for scripting
+    plur-history --format json
+    plur-history --format json | jq '.[] | .content'
+    plur-history --format json | jq '.[] | select(.platforms[].success == false)'
+
+    # JSONL output (one JSON object per line)
+    plur-history --format jsonl
+
+    # Post IDs only, one per line, for other tools
+    plur-history --search "oops" --ids-only
+    plur-history --status scheduled --label campaign:old --ids-only | xargs -n1 plur-queue cancel
+
+    # Shape each line with a template (one line per platform when platform fields are used)
+    plur-history --template '{{created_at}} {{platform}} {{post_id}}'
+    plur-history --template '{{post_id}}\t{{content}}'
+
+    # Export to CSV for analysis
+    plur-history --format csv > posts.csv
+    plur-history --format csv | cut -d, -f3 | sort | uniq -c
+
+    # Pick CSV columns (fields are quoted per RFC 4180)
+    plur-history --format csv --columns post_id,platform,success,content
+
+    # Unix composability examples
+    plur-history --format json | jq -r '.[] | .platforms[] | select(.platform == "nostr") | .platform_post_id'
+    plur-history --platform nostr --format csv | grep ",true,"
+
+    # Per-attempt publish timing (find slow relays/instances)
+    plur-history --format json | jq '.[].platforms[] | {platform, attempts}'
+
+    # Inspect what the platform returned (status URL, signed event, relay OKs)
+    plur-history --verbose --format json | jq '.[0].platforms[].raw_response'
+
+    # Which relays accepted a note, and how fast (shown under each post with --verbose)
+    plur-history --verbose --platform nostr
+
+    # Watch a plur-send box: print each result as it is written (Ctrl-C to stop)
+    plur-history --follow
+    plur-history --follow --platform nostr --format jsonl | jq -r .platform_post_id
+
+    # Posting statistics: per day/week, success rates, busiest hours, threads
+    plur-history stats
+    plur-history stats --platform nostr --since "2025-10-01" --format json
+
+    # Open a post in the browser, or just print its URL
+    plur-history open <POST_ID>
+    plur-history open <POST_ID> --platform mastodon --print
+
+    # Publishable archive: index plus a page per post, linked to each platform
+    plur-history export --out archive/
+    plur-history export --format markdown --since "2025-01-01" --out site/posts/
+
+    # Preview and apply the retention policy from [retention] in config.toml
+    plur-history prune --dry-run
+    plur-history prune --older-than 90
+
+OUTPUT FORMATS:
+    text  - Human-readable text with timestamps and platform status (default)
+    json  - JSON array (complete data structure)
+    jsonl - JSON lines, one object per line (streaming-friendly)
+    csv   - CSV with headers (spreadsheet-compatible)
+
+EXIT CODES:
+    0 - Success (including empty results)
+    1 - Error (database not found, query failed, etc.)
+"#
+)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Filter by platform (nostr, mastodon, ssb)
+    #[arg(short, long, value_name = "PLATFORM", global = true)]
+    #[arg(help = "Filter results to specific platform (nostr, mastodon, or ssb)")]
+    platform: Option<String>,
+
+    /// Filter posts since this date (Unix timestamp or ISO 8601 format)
+    #[arg(long, value_name = "DATE", global = true)]
+    #[arg(help = "Show posts since this date (Unix timestamp, YYYY-MM-DD, or ISO 8601 format)")]
+    since: Option<String>,
+
+    /// Filter posts until this date (Unix timestamp or ISO 8601 format)
+    #[arg(long, value_name = "DATE", global = true)]
+    #[arg(help = "Show posts until this date (Unix timestamp, YYYY-MM-DD, or ISO 8601 format)")]
+    until: Option<String>,
+
+    /// Search posts by content
+    #[arg(short, long, value_name = "TERM")]
+    #[arg(help = "Search posts containing this text (case-insensitive substring match)")]
+    search: Option<String>,
+
+    /// Filter by label
+    #[arg(long, value_name = "LABEL", global = true)]
+    #[arg(help = "Show only posts carrying this label (set with plur-post --label)")]
+    label: Option<String>,
+
+    /// Filter by account
+    #[arg(long, value_name = "ACCOUNT", global = true)]
+    #[arg(help = "Show only posts sent from this account (e.g. 'default', 'work')")]
+    account: Option<String>,
+
+    /// Filter by post status
+    #[arg(long, value_name = "STATUS", global = true)]
+    #[arg(value_parser = ["posted", "failed", "draft", "scheduled", "deleted", "pending", "missed"])]
+    #[arg(
+        help = "Show only posts with this status: posted (on at least one platform), failed (on every platform), draft, scheduled, deleted (cancelled or cleared, hidden otherwise), pending or missed"
+    )]
+    status: Option<String>,
+
+    /// Maximum number of posts to return
+    #[arg(short, long, default_value = "20", value_name = "N")]
+    #[arg(help = "Maximum number of posts to return (default: 20)")]
+    limit: usize,
+
+    /// Continue from a previous page
+    #[arg(long, value_name = "CURSOR")]
+    #[arg(
+        help = "Show the page after this cursor (printed as 'Next cursor' when more posts exist)"
+    )]
+    cursor: Option<String>,
+
+    /// Output format
+    #[arg(short, long, default_value = "text", value_name = "FORMAT")]
+    #[arg(
+        help = "Output format: text (human-readable), json (array), jsonl (streaming), or csv (spreadsheet)"
+    )]
+    #[arg(value_parser = ["text", "json", "jsonl", "csv"])]
+    format: String,
+
+    /// Columns for CSV output
+    #[arg(long, value_name = "COLUMNS", value_delimiter = ',')]
+    #[arg(value_parser = clap::builder::PossibleValuesParser::new(csv::COLUMNS.iter().copied()))]
+    #[arg(
+        help = "Comma-separated columns for --format csv (default: post_id,timestamp,platform,success,platform_post_id,error,content). Any template field or timestamp; a row per platform result when a platform column is chosen"
+    )]
+    columns: Option<Vec<String>>,
+
+    /// Print each post with a template, e.g. '{{created_at}} {{platform}} {{post_id}}'
+    #[arg(long, value_name = "TEMPLATE", conflicts_with = "format")]
+    #[arg(
+        help = "Print each post with a template instead of a format, e.g. '{{date}} {{platform}} {{platform_post_id}}'. Post fields: post_id, created_at, date, content, labels, content_warning, thread_id, thread_part. Platform fields (a line per platform when used): platform, account, success, platform_post_id, error. \\n and \\t are unescaped."
+    )]
+    template: Option<String>,
+
+    /// Print only post IDs, one per line
+    #[arg(long, conflicts_with_all = ["format", "template", "follow"])]
+    #[arg(
+        help = "Print only the post IDs, one per line, for piping into other tools (e.g. xargs plur-queue cancel)"
+    )]
+    ids_only: bool,
+
+    /// Keep running and print new platform results as they are written
+    #[arg(
+        long,
+        conflicts_with_all = ["search", "label", "since", "until", "status", "cursor", "template"]
+    )]
+    #[arg(
+        help = "Keep running and print each platform result as it is written (e.g. by plur-send), text or jsonl. --platform and --account still filter"
+    )]
+    follow: bool,
+
+    /// Seconds between checks for new results with --follow
+    #[arg(long, default_value = "2", value_name = "SECONDS", requires = "follow")]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    interval: u64,
+
+    /// Verbose output (show additional metadata like SSB sequence numbers, hashes, relay acknowledgements and raw responses)
+    #[arg(short, long, global = true)]
+    #[arg(
+        help = "Show additional metadata (SSB sequence numbers, message hashes, Nostr relay acknowledgements, raw platform responses in JSON output)"
+    )]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT",
+        global = true
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL",
+        global = true
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Prune old published posts according to the retention policy
+    ///
+    /// Uses [retention] from config.toml (default: published posts older than
+    /// 180 days, keeping any post that failed on a platform). Drafts and
+    /// scheduled posts are never pruned.
+    Prune {
+        /// Override the policy's maximum age in days
+        #[arg(long, value_name = "DAYS")]
+        older_than: Option<u32>,
+
+        /// Also prune posts that failed on one or more platforms
+        #[arg(long)]
+        include_failures: bool,
+
+        /// Report what would be pruned without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Output format for the report
+        #[arg(short, long, default_value = "text", value_name = "FORMAT")]
+        #[arg(value_parser = ["text", "json"])]
+        format: String,
+    },
+
+    /// Show posting statistics
+    ///
+    /// Posts per day and week, success rate per platform, the busiest hours
+    /// and average thread length, with days and hours in local time. Posts
+    /// are counted as they are listed: deleted posts only with --status
+    /// deleted. --platform, --since, --until, --label, --account, --status
+    /// and --search narrow the posts counted.
+    Stats {
+        /// Output format
+        #[arg(short, long, default_value = "text", value_name = "FORMAT")]
+        #[arg(value_parser = ["text", "json"])]
+        format: String,
+
+        /// Days to list in text output (JSON always has every day)
+        #[arg(long, default_value = "14", value_name = "N")]
+        days: usize,
+    },
+
+    /// Open a post on a platform in the web browser
+    ///
+    /// Resolves the platform post ID to a web URL (njump.me for Nostr, the
+    /// status URL for Mastodon) and opens it with $BROWSER, or the system's
+    /// default browser. Use --platform to choose when the post went to
+    /// several platforms; otherwise the first one with a link is used.
+    Open {
+        /// Post ID (UUID, as shown by plur-history)
+        #[arg(value_name = "POST_ID")]
+        post_id: String,
+
+        /// Print the URL instead of opening it
+        #[arg(long)]
+        print: bool,
+    },
+
+    /// Export posts as a browsable static archive
+    ///
+    /// Writes an index listing every post by month and a page per post with
+    /// a link to it on each platform (njump.me for Nostr, the status URL for
+    /// Mastodon). --platform, --since, --until, --label, --account and
+    /// --status choose the posts exported.
+    Export {
+        /// Archive format
+        #[arg(short, long, default_value = "html", value_name = "FORMAT")]
+        #[arg(value_parser = ["html", "markdown"])]
+        format: String,
+
+        /// Directory to write the archive to (created if missing)
+        #[arg(short, long, value_name = "DIR")]
+        out: std::path::PathBuf,
+    },
+}
+
+/// Posts fetched per query while exporting
+const EXPORT_PAGE_SIZE: usize = 500;
+
+/// Query parameters for history
+#[derive(Debug)]
+struct HistoryQuery {
+    platform: Option<String>,
+    status: Option<PostStatus>,
+    since: Option<i64>,
+    until: Option<i64>,
+    search: Option<String>,
+    label: Option<String>,
+    account: Option<String>,
+    limit: usize,
+    cursor: Option<String>,
+    /// Include raw platform responses (verbose mode)
+    include_raw: bool,
+}
+
+/// A single post with its platform results
+#[derive(Debug, Serialize, Deserialize)]
+struct HistoryEntry {
+    post_id: String,
+    content: String,
+    created_at: i64,
+    platforms: Vec<PlatformStatus>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    labels: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content_warning: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<AttachmentInfo>,
+    /// ID of the first post of the thread this post is part of
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    thread_id: Option<String>,
+    /// Position in the thread, starting at 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    thread_part: Option<usize>,
+}
+
+/// A media attachment on a post
+#[derive(Debug, Serialize, Deserialize)]
+struct AttachmentInfo {
+    file_path: String,
+    mime_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    alt_text: Option<String>,
+}
+
+/// Status of a post on a specific platform
+#[derive(Debug, Serialize, Deserialize)]
+struct PlatformStatus {
+    platform: String,
+    account: String,
+    success: bool,
+    platform_post_id: Option<String>,
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sequence: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    raw_response: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attempts: Vec<AttemptInfo>,
+    /// How each Nostr relay answered (verbose mode)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    relays: Vec<RelayResult>,
+}
+
+/// Timing of one publish attempt on a platform
+#[derive(Debug, Serialize, Deserialize)]
+struct AttemptInfo {
+    attempt: u32,
+    started_at_ms: i64,
+    duration_ms: i64,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// A platform result printed by `--follow`
+#[derive(Debug, Serialize)]
+struct FollowEvent {
+    post_id: String,
+    content: String,
+    posted_at: Option<i64>,
+    platform: String,
+    account: String,
+    success: bool,
+    platform_post_id: Option<String>,
+    error: Option<String>,
+}
+
+/// Query history using service layer
+///
+/// Returns the entries and the cursor for the next page, if any.
+async fn query_history(
+    service: &PlurcastService,
+    query: &HistoryQuery,
+) -> Result<(Vec<HistoryEntry>, Option<String>)> {
+    // Map CLI query to service layer query
+    let service_query = ServiceHistoryQuery {
+        platform: query.platform.clone(),
+        status: query.status.clone(),
+        since: query
+            .since
+            .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0)),
+        until: query
+            .until
+            .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0)),
+        search: query.search.clone(),
+        limit: Some(query.limit),
+        offset: None,
+        label: query.label.clone(),
+        account: query.account.clone(),
+        cursor: query.cursor.clone(),
+    };
+
+    // Query via service layer
+    let page = service
+        .history()
+        .list_page(service_query)
+        .await
+        .context("Failed to query history")?;
+
+    // Map service layer types to CLI types
+    let mut entries = Vec::new();
+    for pwr in page.posts {
+        let mut raw_responses = if query.include_raw {
+            service
+                .database()
+                .get_raw_responses(&pwr.post.id)
+                .await
+                .context("Failed to load platform responses")?
+        } else {
+            Default::default()
+        };
+
+        let attempts = service
+            .database()
+            .get_post_attempts(&pwr.post.id)
+            .await
+            .context("Failed to load publish attempts")?;
+
+        let mut relay_results = if query.include_raw {
+            service
+                .history()
+                .get_relay_results(&pwr.post.id)
+                .await
+                .context("Failed to load relay results")?
+        } else {
+            Default::default()
+        };
+
+        let platforms = pwr
+            .records
+            .iter()
+            .map(|record| {
+                // Extract SSB-specific metadata from platform_post_id if available
+                let (sequence, message_hash): (Option<i64>, Option<String>) =
+                    if record.platform == "ssb" {
+                        if let Some(ref post_id) = record.platform_post_id {
+                            // SSB message IDs are in format: ssb:%<hash>
+                            // For now, extract hash from the ID
+                            // Sequence number would come from database metadata (added in earlier tasks)
+                            let hash = if post_id.starts_with("ssb:%") {
+                                Some(post_id[5..].to_string())
+                            } else {
+                                Some(post_id.clone())
+                            };
+                            (None, hash) // Sequence would be populated from DB metadata
+                        } else {
+                            (None, None)
+                        }
+                    } else {
+                        (None, None)
+                    };
+
+                PlatformStatus {
+                    platform: record.platform.clone(),
+                    account: record.account_name.clone(),
+                    success: record.success,
+                    platform_post_id: record.platform_post_id.clone(),
+                    error: record.error_message.clone(),
+                    sequence,
+                    message_hash,
+                    raw_response: if record.success {
+                        raw_responses.remove(&record.platform)
+                    } else {
+                        None
+                    },
+                    attempts: attempts
+                        .iter()
+                        .filter(|a| a.platform == record.platform)
+                        .map(|a| AttemptInfo {
+                            attempt: a.attempt,
+                            started_at_ms: a.started_at_ms,
+                            duration_ms: a.duration_ms,
+                            success: a.success,
+                            error: a.error_message.clone(),
+                        })
+                        .collect(),
+                    relays: record
+                        .id
+                        .and_then(|id| relay_results.remove(&id))
+                        .unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        let labels = service
+            .database()
+            .get_labels(&pwr.post.id)
+            .await
+            .context("Failed to load labels")?;
+
+        let attachments = service
+            .database()
+            .get_attachments_for_post(&pwr.post.id)
+            .await
+            .context("Failed to load attachments")?
+            .into_iter()
+            .map(|attachment| AttachmentInfo {
+                file_path: attachment.file_path,
+                mime_type: attachment.mime_type.as_str().to_string(),
+                alt_text: attachment.alt_text,
+            })
+            .collect();
+
+        let thread = service
+            .history()
+            .get_thread_position(&pwr.post)
+            .await
+            .context("Failed to resolve thread")?;
+
+        let content_warning = pwr
+            .post
+            .metadata
+            .as_deref()
+            .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+            .and_then(|m| m.get("content_warning")?.as_str().map(String::from));
+
+        entries.push(HistoryEntry {
+            post_id: pwr.post.id,
+            content: pwr.post.content,
+            created_at: pwr.post.created_at,
+            platforms,
+            labels,
+            content_warning,
+            attachments,
+            thread_id: thread.as_ref().map(|t| t.thread_id.clone()),
+            thread_part: thread.map(|t| t.part),
+        });
+    }
+
+    Ok((group_threads(entries), page.next_cursor))
+}
+
+/// Move the parts of each thread together, in thread order
+///
+/// A thread takes the place of its newest part on the page, so the page
+/// stays newest first while a thread reads top to bottom.
+fn group_threads(entries: Vec<HistoryEntry>) -> Vec<HistoryEntry> {
+    let mut grouped: Vec<Vec<HistoryEntry>> = Vec::new();
+    let mut thread_index: HashMap<String, usize> = HashMap::new();
+
+    for entry in entries {
+        match entry.thread_id.clone() {
+            Some(thread_id) => match thread_index.get(&thread_id) {
+                Some(&i) => grouped[i].push(entry),
+                None => {
+                    thread_index.insert(thread_id, grouped.len());
+                    grouped.push(vec![entry]);
+                }
+            },
+            None => grouped.push(vec![entry]),
+        }
+    }
+
+    grouped
+        .into_iter()
+        .flat_map(|mut group| {
+            group.sort_by_key(|entry| entry.thread_part);
+            group
+        })
+        .collect()
+}
+
+/// Field values of one entry, a map per output line
+///
+/// With `per_platform` there's a map per platform result (or one with the
+/// platform fields empty for a post without results); otherwise one per
+/// post. Used by `--template` and `--columns`.
+fn entry_values(entry: &HistoryEntry, per_platform: bool) -> Vec<HashMap<&'static str, String>> {
+    let date = chrono::DateTime::from_timestamp(entry.created_at, 0)
+        .unwrap_or_else(chrono::Utc::now)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string();
+    let mut values: HashMap<&'static str, String> = HashMap::from([
+        ("post_id", entry.post_id.clone()),
+        ("created_at", entry.created_at.to_string()),
+        ("timestamp", entry.created_at.to_string()),
+        ("date", date),
+        ("content", entry.content.clone()),
+        ("labels", entry.labels.join(",")),
+        (
+            "content_warning",
+            entry.content_warning.clone().unwrap_or_default(),
+        ),
+        ("thread_id", entry.thread_id.clone().unwrap_or_default()),
+        (
+            "thread_part",
+            entry
+                .thread_part
+                .map(|part| part.to_string())
+                .unwrap_or_default(),
+        ),
+    ]);
+
+    if !per_platform || entry.platforms.is_empty() {
+        return vec![values];
+    }
+
+    entry
+        .platforms
+        .iter()
+        .map(|platform| {
+            values.insert("platform", platform.platform.clone());
+            values.insert("account", platform.account.clone());
+            values.insert("success", platform.success.to_string());
+            values.insert(
+                "platform_post_id",
+                platform.platform_post_id.clone().unwrap_or_default(),
+            );
+            values.insert("error", platform.error.clone().unwrap_or_default());
+            values.clone()
+        })
+        .collect()
+}
+
+/// Render one entry with a `--template`
+fn render_entry(template: &template::Template, entry: &HistoryEntry) -> Vec<String> {
+    entry_values(entry, template.uses_platform_fields())
+        .iter()
+        .map(|values| template.render(values))
+        .collect()
+}
+
+/// Apply the retention policy and print a report
+async fn run_prune(
+    older_than: Option<u32>,
+    include_failures: bool,
+    dry_run: bool,
+    format: &str,
+) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+
+    let mut policy = config.retention.clone().unwrap_or_default();
+    if let Some(days) = older_than {
+        policy.max_age_days = days;
+    }
+    if include_failures {
+        policy.keep_failures = false;
+    }
+
+    let service = PlurcastService::from_config(config)
+        .await
+        .context("Failed to initialize service")?;
+
+    let report = retention::prune(service.database(), &policy, dry_run)
+        .await
+        .context("Failed to prune history")?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let cutoff = chrono::DateTime::from_timestamp(report.cutoff, 0)
+        .unwrap_or_else(chrono::Utc::now)
+        .format("%Y-%m-%d");
+
+    if report.dry_run {
+        println!(
+            "Would prune {} post(s) published before {}",
+            report.post_ids.len(),
+            cutoff
+        );
+        for post_id in &report.post_ids {
+            println!("  {}", post_id);
+        }
+    } else {
+        println!(
+            "Pruned {} post(s) published before {}",
+            report.deleted, cutoff
+        );
+    }
+
+    Ok(())
+}
+
+/// Open (or print) the web URL of a post on one of its platforms
+async fn run_open(post_id: &str, platform: Option<&str>, print: bool) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let instance = config.mastodon.as_ref().map(|m| m.instance.clone());
+
+    let service = PlurcastService::from_config(config)
+        .await
+        .context("Failed to initialize service. Have you posted anything yet?")?;
+
+    let post = match service
+        .history()
+        .get_post(post_id)
+        .await
+        .context("Failed to load post")?
+    {
+        Some(post) => post,
+        None => anyhow::bail!("No post with ID {}", post_id),
+    };
+    let raw_responses = service
+        .database()
+        .get_raw_responses(post_id)
+        .await
+        .context("Failed to load platform responses")?;
+
+    let published: Vec<_> = post
+        .records
+        .iter()
+        .filter(|record| {
+            record.success && (platform.is_none() || platform == Some(record.platform.as_str()))
+        })
+        .collect();
+    if published.is_empty() {
+        match platform {
+            Some(platform) => anyhow::bail!("Post {} was not published to {}", post_id, platform),
+            None => anyhow::bail!("Post {} was not published anywhere", post_id),
+        }
+    }
+
+    let url = published.iter().find_map(|record| {
+        links::post_url(
+            &record.platform,
+            record.platform_post_id.as_deref()?,
+            raw_responses.get(&record.platform),
+            instance.as_deref(),
+        )
+    });
+    let url = match url {
+        Some(url) => url,
+        None => {
+            let platforms: Vec<&str> = published.iter().map(|r| r.platform.as_str()).collect();
+            anyhow::bail!(
+                "No web link for post {} on {} (SSB has no web view; Mastodon needs [mastodon] instance in config.toml)",
+                post_id,
+                platforms.join(", ")
+            );
+        }
+    };
+
+    if print {
+        println!("{}", url);
+        return Ok(());
+    }
+    open_url(&url)
+}
+
+/// Open a URL with `$BROWSER` (which may include arguments), or the
+/// system's default handler
+fn open_url(url: &str) -> Result<()> {
+    let browser = match std::env::var("BROWSER") {
+        Ok(browser) if !browser.trim().is_empty() => browser,
+        _ if cfg!(target_os = "macos") => "open".to_string(),
+        _ if cfg!(target_os = "windows") => "explorer".to_string(),
+        _ => "xdg-open".to_string(),
+    };
+    let mut parts = browser.split_whitespace();
+    // Checked non-empty above
+    let program = parts.next().unwrap_or("xdg-open");
+
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(url)
+        .status()
+        .with_context(|| format!("Failed to run '{}' to open {}", program, url))?;
+    // explorer.exe exits with 1 even when it opened the URL
+    if !status.success() && !cfg!(target_os = "windows") {
+        anyhow::bail!("'{}' exited with {} opening {}", program, status, url);
+    }
+    Ok(())
+}
+
+/// Write the posts the filters select as a static archive
+async fn run_export(
+    mut query: HistoryQuery,
+    format: archive::ArchiveFormat,
+    out: &std::path::Path,
+) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let instance = config.mastodon.as_ref().map(|m| m.instance.clone());
+
+    let service = PlurcastService::from_config(config)
+        .await
+        .context("Failed to initialize service. Have you posted anything yet?")?;
+
+    // Page through the whole selection, then regroup threads split by pages
+    let mut entries = Vec::new();
+    loop {
+        let (page, next_cursor) = query_history(&service, &query)
+            .await
+            .context("Failed to query history")?;
+        entries.extend(page);
+        match next_cursor {
+            Some(cursor) => query.cursor = Some(cursor),
+            None => break,
+        }
+    }
+    let entries = group_threads(entries);
+
+    std::fs::create_dir_all(out.join("posts"))
+        .with_context(|| format!("Failed to create {}", out.display()))?;
+    let files = archive::render(&entries, format, instance.as_deref());
+    for file in &files {
+        let path = out.join(&file.path);
+        std::fs::write(&path, &file.contents)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    println!(
+        "Exported {} post(s) to {}",
+        entries.len(),
+        out.join(&files[0].path).display()
+    );
+    Ok(())
+}
+
+/// Print platform results as they are written, until interrupted
+///
+/// Polls `post_records` for rows newer than the last one seen, so only
+/// results written after startup are printed.
+async fn run_follow(
+    service: &PlurcastService,
+    platform: Option<&str>,
+    account: Option<&str>,
+    format: &str,
+    interval: u64,
+) -> Result<()> {
+    let db = service.database();
+    let mut last_id = db
+        .get_last_post_record_id()
+        .await
+        .context("Failed to read post records")?
+        .unwrap_or(0);
+    let mut contents: HashMap<String, String> = HashMap::new();
+
+    loop {
+        let records = db
+            .get_post_records_after(last_id, 100)
+            .await
+            .context("Failed to read post records")?;
+
+        for record in &records {
+            if let Some(id) = record.id {
+                last_id = last_id.max(id);
+            }
+            if platform.is_some_and(|p| p != record.platform)
+                || account.is_some_and(|a| a != record.account_name)
+            {
+                continue;
+            }
+
+            if !contents.contains_key(&record.post_id) {
+                let content = db
+                    .get_post(&record.post_id)
+                    .await
+                    .context("Failed to load post")?
+                    .map(|post| post.content)
+                    .unwrap_or_default();
+                contents.insert(record.post_id.clone(), content);
+            }
+
+            let event = FollowEvent {
+                post_id: record.post_id.clone(),
+                content: contents[&record.post_id].clone(),
+                posted_at: record.posted_at,
+                platform: record.platform.clone(),
+                account: record.account_name.clone(),
+                success: record.success,
+                platform_post_id: record.platform_post_id.clone(),
+                error: record.error_message.clone(),
+            };
+
+            if format == "jsonl" {
+                println!("{}", serde_json::to_string(&event)?);
+            } else {
+                let timestamp = event
+                    .posted_at
+                    .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                    .unwrap_or_else(chrono::Utc::now)
+                    .format("%Y-%m-%d %H:%M:%S");
+                let symbol = if event.success { "✓" } else { "✗" };
+                let name = if event.account == "default" {
+                    event.platform.clone()
+                } else {
+                    format!("{} ({})", event.platform, event.account)
+                };
+                let detail = event
+                    .platform_post_id
+                    .as_deref()
+                    .or(event.error.as_deref())
+                    .unwrap_or("");
+                let preview: String = event.content.chars().take(60).collect();
+                println!(
+                    "{} | {} | {} {}: {} | {}",
+                    timestamp, event.post_id, symbol, name, detail, preview
+                );
+            }
+        }
+
+        // A full batch means more may be waiting; otherwise wait for new ones
+        if records.len() < 100 {
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+        }
+    }
+}
+
+/// Print statistics for the posts the filters select
+async fn run_stats(query: ServiceHistoryQuery, format: &str, days: usize) -> Result<()> {
+    let service = PlurcastService::new()
+        .await
+        .context("Failed to initialize service. Have you posted anything yet?")?;
+
+    let stats = service
+        .history()
+        .get_stats(query)
+        .await
+        .context("Failed to compute statistics")?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+
+    println!("Posts: {}", stats.total_posts);
+    if stats.threads > 0 {
+        println!(
+            "Threads: {} (average {:.1} posts)",
+            stats.threads, stats.average_thread_length
+        );
+    }
+
+    if !stats.platform_stats.is_empty() {
+        println!();
+        println!("Success rate by platform:");
+        for (platform, platform_stats) in &stats.platform_stats {
+            println!(
+                "  {:<10} {:>5.1}%  ({} of {})",
+                platform,
+                platform_stats.success_rate,
+                platform_stats.successful,
+                platform_stats.total
+            );
+        }
+    }
+
+    if !stats.busiest_hours.is_empty() {
+        println!();
+        println!("Busiest hours (local time):");
+        for hour in stats.busiest_hours.iter().take(3) {
+            println!("  {:02}:00  {} post(s)", hour.hour, hour.posts);
+        }
+    }
+
+    if !stats.per_week.is_empty() {
+        println!();
+        println!("Posts per week:");
+        for week in &stats.per_week {
+            println!("  {}  {}", week.period, week.posts);
+        }
+    }
+
+    if !stats.per_day.is_empty() && days > 0 {
+        println!();
+        println!("Posts per day (last {} active days):", days);
+        let skip = stats.per_day.len().saturating_sub(days);
+        for day in stats.per_day.iter().skip(skip) {
+            println!("  {}  {}", day.period, day.posts);
+        }
+    }
+
+    Ok(())
+}
+
+/// Run plur-history with a command line, program name first
+///
+/// The `plur-history` binary and `plur history` both start here.
+#[tokio::main]
+pub async fn main_from(args: Vec<OsString>) -> Result<()> {
+    let args = Args::parse_from(args);
+
+    // Initialize logging with centralized configuration
+    let log_format = args.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if args.verbose {
+        "debug".to_string()
+    } else {
+        args.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, args.verbose);
+    logging_config.init();
+
+    tracing::debug!("plur-history started with args: {:?}", args);
+
+    if let Some(Command::Prune {
+        older_than,
+        include_failures,
+        dry_run,
+        format,
+    }) = &args.command
+    {
+        return run_prune(*older_than, *include_failures, *dry_run, format).await;
+    }
+
+    // Parse date arguments
+    let since = if let Some(ref since_str) = args.since {
+        Some(history::parse_date(since_str)?.timestamp())
+    } else {
+        None
+    };
+
+    let until = if let Some(ref until_str) = args.until {
+        Some(history::parse_date(until_str)?.timestamp())
+    } else {
+        None
+    };
+
+    // The value parser only lets through known statuses
+    let status = args.status.as_deref().and_then(PostStatus::parse);
+
+    if let Some(Command::Stats { format, days }) = &args.command {
+        let query = ServiceHistoryQuery {
+            platform: args.platform,
+            since: since.and_then(|ts| chrono::DateTime::from_timestamp(ts, 0)),
+            until: until.and_then(|ts| chrono::DateTime::from_timestamp(ts, 0)),
+            label: args.label,
+            account: args.account,
+            status,
+            search: args.search,
+            ..Default::default()
+        };
+        return run_stats(query, format, *days).await;
+    }
+
+    if args.columns.is_some() && args.format != "csv" {
+        anyhow::bail!("--columns only applies to --format csv");
+    }
+
+    if args.follow && args.format != "text" && args.format != "jsonl" {
+        anyhow::bail!("--follow prints as it goes; use --format text or jsonl");
+    }
+
+    if let Some(Command::Open { post_id, print }) = &args.command {
+        return run_open(post_id, args.platform.as_deref(), *print).await;
+    }
+
+    if let Some(Command::Export { format, out }) = &args.command {
+        let query = HistoryQuery {
+            platform: args.platform,
+            status,
+            since,
+            until,
+            search: None,
+            label: args.label,
+            account: args.account,
+            limit: EXPORT_PAGE_SIZE,
+            cursor: None,
+            include_raw: true,
+        };
+        // The value parser only lets through known formats
+        let format = archive::ArchiveFormat::parse(format).unwrap_or(archive::ArchiveFormat::Html);
+        return run_export(query, format, out).await;
+    }
+
+    // Initialize service layer
+    let service = PlurcastService::new()
+        .await
+        .context("Failed to initialize service. Have you posted anything yet?")?;
+
+    if args.follow {
+        return run_follow(
+            &service,
+            args.platform.as_deref(),
+            args.account.as_deref(),
+            &args.format,
+            args.interval,
+        )
+        .await;
+    }
+
+    // Build query
+    let query = HistoryQuery {
+        platform: args.platform,
+        status,
+        since,
+        until,
+        search: args.search,
+        label: args.label,
+        account: args.account,
+        limit: args.limit,
+        cursor: args.cursor,
+        include_raw: args.verbose,
+    };
+
+    // Check the template before querying anything
+    let template = args
+        .template
+        .as_deref()
+        .map(template::Template::parse)
+        .transpose()?;
+
+    // Execute query
+    let (entries, next_cursor) = query_history(&service, &query)
+        .await
+        .context("Failed to query history")?;
+
+    if args.ids_only {
+        for entry in &entries {
+            println!("{}", entry.post_id);
+        }
+        if let Some(cursor) = next_cursor {
+            eprintln!("Next cursor: {}", cursor);
+        }
+        return Ok(());
+    }
+
+    if let Some(template) = template {
+        for entry in &entries {
+            for line in render_entry(&template, entry) {
+                println!("{}", line);
+            }
+        }
+        if let Some(cursor) = next_cursor {
+            eprintln!("Next cursor: {}", cursor);
+        }
+        return Ok(());
+    }
+
+    // Output results based on format
+    match args.format.as_str() {
+        "json" => {
+            let json = serde_json::to_string_pretty(&entries)?;
+            println!("{}", json);
+        }
+        "jsonl" => {
+            for entry in entries {
+                let json = serde_json::to_string(&entry)?;
+                println!("{}", json);
+            }
+        }
+        "csv" => {
+            let columns: Vec<&str> = match args.columns {
+                Some(ref columns) => columns.iter().map(String::as_str).collect(),
+                None => csv::DEFAULT_COLUMNS.to_vec(),
+            };
+            let per_platform = columns
+                .iter()
+                .any(|column| template::PLATFORM_FIELDS.contains(column));
+
+            println!("{}", csv::row(columns.iter().copied()));
+            for entry in &entries {
+                for values in entry_values(entry, per_platform) {
+                    println!(
+                        "{}",
+                        csv::row(
+                            columns
+                                .iter()
+                                .map(|column| values.get(column).map_or("", String::as_str))
+                        )
+                    );
+                }
+            }
+        }
+        "text" => {
+            // Human-readable text format
+            if entries.is_empty() {
+                // Empty results - output nothing and exit 0
+                std::process::exit(0);
+            }
+
+            // Parts of each thread on this page, for the thread headers
+            let mut thread_sizes: HashMap<String, usize> = HashMap::new();
+            for thread_id in entries.iter().filter_map(|e| e.thread_id.as_ref()) {
+                *thread_sizes.entry(thread_id.clone()).or_default() += 1;
+            }
+            let mut current_thread: Option<String> = None;
+
+            for entry in entries {
+                if entry.thread_id != current_thread {
+                    if let Some(ref thread_id) = entry.thread_id {
+                        println!(
+                            "Thread {} ({} part(s) shown)",
+                            thread_id, thread_sizes[thread_id]
+                        );
+                    }
+                    current_thread = entry.thread_id.clone();
+                }
+
+                // Format timestamp
+                let dt = chrono::DateTime::from_timestamp(entry.created_at, 0)
+                    .unwrap_or_else(chrono::Utc::now);
+                let timestamp = dt.format("%Y-%m-%d %H:%M:%S");
+
+                // Truncate content for preview
+                let content_preview = if entry.content.len() > 60 {
+                    format!("{}...", &entry.content[..60])
+                } else {
+                    entry.content.clone()
+                };
+
+                match entry.thread_part {
+                    Some(part) => println!(
+                        "{} | {} | [{}] {}",
+                        timestamp, entry.post_id, part, content_preview
+                    ),
+                    None => println!("{} | {} | {}", timestamp, entry.post_id, content_preview),
+                }
+
+                if !entry.labels.is_empty() {
+                    println!("  Labels: {}", entry.labels.join(", "));
+                }
+
+                if let Some(ref cw) = entry.content_warning {
+                    println!("  CW: {}", cw);
+                }
+
+                // Show platform results
+                for platform in &entry.platforms {
+                    let symbol = if platform.success { "✓" } else { "✗" };
+                    // Only name the account when it isn't the default one
+                    let name = if platform.account == "default" {
+                        platform.platform.clone()
+                    } else {
+                        format!("{} ({})", platform.platform, platform.account)
+                    };
+                    if let Some(ref post_id) = platform.platform_post_id {
+                        println!("  {} {}: {}", symbol, name, post_id);
+
+                        // Show SSB-specific metadata in verbose mode
+                        if args.verbose && platform.platform == "ssb" {
+                            if let Some(seq) = platform.sequence {
+                                println!("    Sequence: {}", seq);
+                            }
+                            if let Some(ref hash) = platform.message_hash {
+                                println!("    Hash: {}", hash);
+                            }
+                        }
+                    } else if let Some(ref error) = platform.error {
+                        println!("  {} {}: {}", symbol, name, error);
+                    } else {
+                        println!("  {} {}", symbol, name);
+                    }
+
+                    // Relay acknowledgements are only loaded in verbose mode
+                    for relay in &platform.relays {
+                        let symbol = if relay.accepted { "✓" } else { "✗" };
+                        let latency = relay
+                            .latency_ms
+                            .map(|ms| format!(" ({} ms)", ms))
+                            .unwrap_or_default();
+                        match relay.message {
+                            Some(ref message) => println!(
+                                "    {} {}{}: {}",
+                                symbol, relay.relay_url, latency, message
+                            ),
+                            None => println!("    {} {}{}", symbol, relay.relay_url, latency),
+                        }
+                    }
+                }
+
+                // Show attachments
+                for attachment in &entry.attachments {
+                    match attachment.alt_text {
+                        Some(ref alt) => {
+                            println!("  Attachment: {} ({})", attachment.file_path, alt)
+                        }
+                        None => println!("  Attachment: {}", attachment.file_path),
+                    }
+                }
+                println!(); // Blank line between entries
+            }
+        }
+        _ => {
+            eprintln!(
+                "Error: Invalid format '{}'. Valid formats: text, json, jsonl, csv",
+                args.format
+            );
+            std::process::exit(1);
+        }
+    }
+
+    // On stderr so json/jsonl/csv output stays parseable
+    if let Some(cursor) = next_cursor {
+        eprintln!("Next cursor: {}", cursor);
+    }
+
+    Ok(())
+}
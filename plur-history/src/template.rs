@@ -0,0 +1,169 @@
+//! Output templates for `--template`
+//!
+//! A template is plain text with `{{field}}` placeholders, printed once per
+//! post, or once per platform result when it uses a platform field:
+//!
+//! ```text
+//! {{date}} {{platform}} {{platform_post_id}}
+//! ```
+//!
+//! Post fields are `post_id`, `created_at` (Unix timestamp), `date`
+//! (`YYYY-MM-DD HH:MM:SS` UTC), `content`, `labels` (comma-separated),
+//! `content_warning`, `thread_id` and `thread_part`. Platform fields are
+//! `platform`, `account`, `success` (`true`/`false`), `platform_post_id`
+//! and `error`. `\n`, `\t` and `\\` are unescaped so one template can
+//! span lines or make TSV.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+
+/// Fields that come from the post itself
+pub const POST_FIELDS: &[&str] = &[
+    "post_id",
+    "created_at",
+    "date",
+    "content",
+    "labels",
+    "content_warning",
+    "thread_id",
+    "thread_part",
+];
+
+/// Fields that come from one platform's result
+pub const PLATFORM_FIELDS: &[&str] = &[
+    "platform",
+    "account",
+    "success",
+    "platform_post_id",
+    "error",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Text(String),
+    Field(String),
+}
+
+/// A parsed `--template`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Parse a template, checking every field name
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the field if a placeholder isn't a known
+    /// field, or if a `{{` is never closed.
+    pub fn parse(template: &str) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut rest = template;
+
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                segments.push(Segment::Text(unescape(&rest[..start])));
+            }
+            let after = &rest[start + 2..];
+            let end = match after.find("}}") {
+                Some(end) => end,
+                None => bail!("Unclosed '{{{{' in template: {}", template),
+            };
+
+            let name = after[..end].trim();
+            if !POST_FIELDS.contains(&name) && !PLATFORM_FIELDS.contains(&name) {
+                bail!(
+                    "Unknown template field '{}'. Available fields: {}, {}",
+                    name,
+                    POST_FIELDS.join(", "),
+                    PLATFORM_FIELDS.join(", ")
+                );
+            }
+            segments.push(Segment::Field(name.to_string()));
+            rest = &after[end + 2..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Text(unescape(rest)));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Whether the template prints a line per platform result
+    pub fn uses_platform_fields(&self) -> bool {
+        self.segments.iter().any(|segment| match segment {
+            Segment::Field(name) => PLATFORM_FIELDS.contains(&name.as_str()),
+            Segment::Text(_) => false,
+        })
+    }
+
+    /// Fill in the template; fields without a value render empty
+    pub fn render(&self, values: &HashMap<&str, String>) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.as_str(),
+                Segment::Field(name) => values.get(name.as_str()).map_or("", String::as_str),
+            })
+            .collect()
+    }
+}
+
+/// Turn `\n`, `\t` and `\\` into the characters they stand for
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some('\\') => unescaped.push('\\'),
+            Some(other) => {
+                unescaped.push('\\');
+                unescaped.push(other);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let template = Template::parse("{{created_at}} {{ platform }}\\t{{post_id}}").unwrap();
+        assert!(template.uses_platform_fields());
+
+        let values = HashMap::from([
+            ("created_at", "1700000000".to_string()),
+            ("platform", "nostr".to_string()),
+            ("post_id", "abc".to_string()),
+        ]);
+        assert_eq!(template.render(&values), "1700000000 nostr\tabc");
+
+        // Missing values render empty
+        assert_eq!(template.render(&HashMap::new()), " \t");
+    }
+
+    #[test]
+    fn test_post_only_template() {
+        let template = Template::parse("{{post_id}}: {{content}}").unwrap();
+        assert!(!template.uses_platform_fields());
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_fields() {
+        let err = Template::parse("{{post_id}} {{url}}").unwrap_err();
+        assert!(err.to_string().contains("Unknown template field 'url'"));
+        assert!(Template::parse("{{post_id").is_err());
+    }
+}
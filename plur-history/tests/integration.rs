@@ -550,3 +550,763 @@ async fn test_history_ssb_search() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_history_prune_dry_run_reports_without_deleting() -> Result<()> {
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args([
+            "prune",
+            "--older-than",
+            "1",
+            "--dry-run",
+            "--format",
+            "json",
+        ])
+        .output()?;
+
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["dry_run"], true);
+    assert_eq!(report["deleted"], 0);
+    // Only the two-day-old post qualifies; yesterday's post has a failure
+    assert_eq!(report["post_ids"].as_array().unwrap().len(), 1);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("SSB exclusive content"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_prune_removes_old_posts() -> Result<()> {
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["prune", "--older-than", "1"])
+        .output()?;
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("Pruned 1 post(s)"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(!stdout.contains("SSB exclusive content"));
+    assert!(stdout.contains("Multi-platform post about rust"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_filter_by_label() -> Result<()> {
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+
+    // Label the Nostr post
+    let pool = SqlitePool::connect(&format!("sqlite://{}", db_path)).await?;
+    sqlx::query(
+        "INSERT INTO post_labels (post_id, label, created_at)
+         SELECT id, 'campaign:launch', created_at FROM posts WHERE content = 'Hello from Nostr'",
+    )
+    .execute(&pool)
+    .await?;
+    pool.close().await;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--label", "campaign:launch", "--format", "json"])
+        .output()?;
+
+    assert!(output.status.success());
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let entries = entries.as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["content"], "Hello from Nostr");
+    assert_eq!(entries[0]["labels"][0], "campaign:launch");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_shows_content_warning() -> Result<()> {
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+
+    let pool = SqlitePool::connect(&format!("sqlite://{}", db_path)).await?;
+    sqlx::query(
+        "UPDATE posts SET metadata = '{\"platforms\":[\"nostr\"],\"content_warning\":\"Spoilers\"}'
+         WHERE content = 'Hello from Nostr'",
+    )
+    .execute(&pool)
+    .await?;
+    pool.close().await;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--search", "Hello", "--format", "json"])
+        .output()?;
+    assert!(output.status.success());
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(entries[0]["content_warning"], "Spoilers");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("  CW: Spoilers"));
+    assert_eq!(stdout.matches("CW:").count(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_filter_by_account() -> Result<()> {
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+
+    // Move the Nostr post onto a second account
+    let pool = SqlitePool::connect(&format!("sqlite://{}", db_path)).await?;
+    sqlx::query(
+        "UPDATE post_records SET account_name = 'work'
+         WHERE post_id IN (SELECT id FROM posts WHERE content = 'Hello from Nostr')",
+    )
+    .execute(&pool)
+    .await?;
+    pool.close().await;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--account", "work", "--format", "json"])
+        .output()?;
+
+    assert!(output.status.success());
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let entries = entries.as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["content"], "Hello from Nostr");
+    assert_eq!(entries[0]["platforms"][0]["account"], "work");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_cursor_pagination() -> Result<()> {
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+
+    let first = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--limit", "2", "--format", "json"])
+        .output()?;
+    assert!(first.status.success());
+    let first_page: serde_json::Value = serde_json::from_slice(&first.stdout)?;
+    assert_eq!(first_page.as_array().unwrap().len(), 2);
+
+    let stderr = String::from_utf8(first.stderr)?;
+    let cursor = stderr
+        .lines()
+        .find_map(|line| line.strip_prefix("Next cursor: "))
+        .expect("first page should print a next cursor")
+        .to_string();
+
+    let second = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--limit", "2", "--format", "json", "--cursor", &cursor])
+        .output()?;
+    assert!(second.status.success());
+    let second_page: serde_json::Value = serde_json::from_slice(&second.stdout)?;
+    let second_page = second_page.as_array().unwrap();
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page[0]["content"], "SSB exclusive content");
+    assert!(!String::from_utf8(second.stderr)?.contains("Next cursor"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_json_includes_attempt_timing() -> Result<()> {
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+
+    // A failed attempt followed by a successful retry
+    let pool = SqlitePool::connect(&format!("sqlite://{}", db_path)).await?;
+    for (attempt, success, duration_ms) in [(1, 0, 5000), (2, 1, 850)] {
+        sqlx::query(
+            "INSERT INTO post_attempts (post_id, platform, attempt, started_at_ms, finished_at_ms, duration_ms, success)
+             SELECT id, 'nostr', ?, ?, ?, ?, ? FROM posts WHERE content = 'Hello from Nostr'",
+        )
+        .bind(attempt)
+        .bind(1_700_000_000_000_i64 + attempt * 10_000)
+        .bind(1_700_000_000_000_i64 + attempt * 10_000 + duration_ms)
+        .bind(duration_ms)
+        .bind(success)
+        .execute(&pool)
+        .await?;
+    }
+    pool.close().await;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args([
+            "--platform",
+            "nostr",
+            "--search",
+            "Hello",
+            "--format",
+            "json",
+        ])
+        .output()?;
+
+    assert!(output.status.success());
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let attempts = &entries[0]["platforms"][0]["attempts"];
+    assert_eq!(attempts.as_array().unwrap().len(), 2);
+    assert_eq!(attempts[0]["success"], false);
+    assert_eq!(attempts[1]["attempt"], 2);
+    assert_eq!(attempts[1]["duration_ms"], 850);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_verbose_shows_relay_results() -> Result<()> {
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+
+    let pool = SqlitePool::connect(&format!("sqlite://{}", db_path)).await?;
+    for (relay_url, accepted, message) in [
+        ("wss://relay.damus.io", 1, None),
+        ("wss://nos.lol", 0, Some("blocked: pow too low")),
+    ] {
+        sqlx::query(
+            "INSERT INTO relay_results (post_record_id, relay_url, accepted, message, latency_ms)
+             SELECT pr.id, ?, ?, ?, 120 FROM post_records pr
+             JOIN posts p ON p.id = pr.post_id WHERE p.content = 'Hello from Nostr'",
+        )
+        .bind(relay_url)
+        .bind(accepted)
+        .bind(message)
+        .execute(&pool)
+        .await?;
+    }
+    pool.close().await;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--search", "Hello", "--verbose"])
+        .output()?;
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("✓ wss://relay.damus.io (120 ms)"));
+    assert!(stdout.contains("✗ wss://nos.lol (120 ms): blocked: pow too low"));
+
+    // Relays are only listed in verbose mode
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--search", "Hello"])
+        .output()?;
+
+    assert!(output.status.success());
+    assert!(!String::from_utf8(output.stdout)?.contains("wss://nos.lol"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_stats() -> Result<()> {
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["stats", "--format", "json"])
+        .output()?;
+
+    assert!(output.status.success());
+    let stats: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(stats["total_posts"], 3);
+    assert_eq!(stats["platform_stats"]["nostr"]["successful"], 2);
+    assert_eq!(stats["platform_stats"]["ssb"]["total"], 2);
+    assert_eq!(stats["platform_stats"]["ssb"]["failed"], 1);
+    assert_eq!(stats["per_day"].as_array().unwrap().len(), 3);
+    assert_eq!(stats["threads"], 0);
+
+    // Filters narrow the posts counted
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["stats", "--platform", "ssb"])
+        .output()?;
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("Posts: 2"));
+    assert!(stdout.contains("ssb"));
+    assert!(stdout.contains("(1 of 2)"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--search", "rust", "stats"])
+        .output()?;
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("Posts: 1"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_filter_by_status() -> Result<()> {
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+
+    let pool = SqlitePool::connect(&format!("sqlite://{}", db_path)).await?;
+    sqlx::query("INSERT INTO posts (id, content, created_at, status) VALUES (?, ?, ?, ?)")
+        .bind("failed-post")
+        .bind("Nobody saw this")
+        .bind(chrono::Utc::now().timestamp() - 3600)
+        .bind("failed")
+        .execute(&pool)
+        .await?;
+    pool.close().await;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--status", "failed", "--format", "json"])
+        .output()?;
+
+    assert!(output.status.success());
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let entries = entries.as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["post_id"], "failed-post");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--status", "posted", "--format", "json"])
+        .output()?;
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(entries.as_array().unwrap().len(), 3);
+
+    // Unknown statuses are rejected by the argument parser
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--status", "sent"])
+        .output()?;
+    assert!(!output.status.success());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_template_output() -> Result<()> {
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+
+    // Platform fields give a line per platform result
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args([
+            "--template",
+            "{{platform}} {{success}} {{platform_post_id}}",
+        ])
+        .output()?;
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines.contains(&"nostr true note1abc123"));
+    assert!(lines.contains(&"ssb false "));
+
+    // Post fields only give a line per post
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--search", "rust", "--template", "{{content}}"])
+        .output()?;
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "Multi-platform post about rust\n"
+    );
+
+    // Unknown fields are rejected before querying
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--template", "{{url}}"])
+        .output()?;
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("Unknown template field 'url'"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_groups_thread_parts() -> Result<()> {
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+
+    // A two-part thread with a post in between the parts
+    let pool = SqlitePool::connect(&format!("sqlite://{}", db_path)).await?;
+    let now = chrono::Utc::now().timestamp();
+    for (id, content, created_at, metadata) in [
+        ("thread-1", "Thread 1/2", now + 10, None),
+        ("between", "Unrelated", now + 20, None),
+        (
+            "thread-2",
+            "Thread 2/2",
+            now + 30,
+            Some(r#"{"reply_to":{"mastodon":"777"}}"#),
+        ),
+    ] {
+        sqlx::query(
+            "INSERT INTO posts (id, content, created_at, status, metadata) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(id)
+        .bind(content)
+        .bind(created_at)
+        .bind("posted")
+        .bind(metadata)
+        .execute(&pool)
+        .await?;
+    }
+    sqlx::query(
+        "INSERT INTO post_records (post_id, platform, platform_post_id, posted_at, success) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind("thread-1")
+    .bind("mastodon")
+    .bind("777")
+    .bind(now + 10)
+    .bind(1)
+    .execute(&pool)
+    .await?;
+    pool.close().await;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--format", "json", "--limit", "3"])
+        .output()?;
+
+    assert!(output.status.success());
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let ids: Vec<&str> = entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["post_id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec!["thread-1", "thread-2", "between"]);
+    assert_eq!(entries[0]["thread_id"], "thread-1");
+    assert_eq!(entries[0]["thread_part"], 1);
+    assert_eq!(entries[1]["thread_id"], "thread-1");
+    assert_eq!(entries[1]["thread_part"], 2);
+    assert!(entries[2].get("thread_id").is_none());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--limit", "3"])
+        .output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("Thread thread-1 (2 part(s) shown)"));
+    assert!(stdout.contains("[2] Thread 2/2"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_follow_prints_new_records() -> Result<()> {
+    use std::io::BufRead;
+
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--follow", "--interval", "1", "--format", "jsonl"])
+        .stdout(std::process::Stdio::piped())
+        .spawn()?;
+
+    // Read lines on a thread so a missing line fails the test instead of hanging
+    let stdout = child.stdout.take().unwrap();
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::BufReader::new(stdout).lines() {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    // Give it time to note the existing records, which are not printed
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+
+    let pool = SqlitePool::connect(&format!("sqlite://{}", db_path)).await?;
+    let now = chrono::Utc::now().timestamp();
+    sqlx::query("INSERT INTO posts (id, content, created_at, status) VALUES (?, ?, ?, ?)")
+        .bind("followed")
+        .bind("Sent while following")
+        .bind(now)
+        .bind("posted")
+        .execute(&pool)
+        .await?;
+    sqlx::query(
+        "INSERT INTO post_records (post_id, platform, platform_post_id, posted_at, success) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind("followed")
+    .bind("mastodon")
+    .bind("999")
+    .bind(now)
+    .bind(1)
+    .execute(&pool)
+    .await?;
+    pool.close().await;
+
+    let line = rx.recv_timeout(std::time::Duration::from_secs(10));
+    child.kill()?;
+    child.wait()?;
+
+    let event: serde_json::Value = serde_json::from_str(&line??)?;
+    assert_eq!(event["post_id"], "followed");
+    assert_eq!(event["platform"], "mastodon");
+    assert_eq!(event["platform_post_id"], "999");
+    assert_eq!(event["content"], "Sent while following");
+
+    Ok(())
+}
+
+#[test]
+fn test_history_follow_rejects_json_format() -> Result<()> {
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .args(["--follow", "--format", "json"])
+        .env("PLURCAST_CONFIG", "/nonexistent/config.toml")
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("use --format text or jsonl"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_export_archive() -> Result<()> {
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+    let out_dir = TempDir::new()?;
+    let archive = out_dir.path().join("archive");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["export", "--out"])
+        .arg(&archive)
+        .output()?;
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Exported 3 post(s)"));
+
+    let index = std::fs::read_to_string(archive.join("index.html"))?;
+    assert!(index.contains("Hello from Nostr"));
+    assert!(index.contains("https://njump.me/note1abc123"));
+    assert_eq!(std::fs::read_dir(archive.join("posts"))?.count(), 3);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args([
+            "export",
+            "--format",
+            "markdown",
+            "--platform",
+            "ssb",
+            "--out",
+        ])
+        .arg(&archive)
+        .output()?;
+
+    assert!(output.status.success());
+    let index = std::fs::read_to_string(archive.join("index.md"))?;
+    assert!(index.contains("SSB exclusive content"));
+    assert!(!index.contains("Hello from Nostr"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_ids_only() -> Result<()> {
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--ids-only"])
+        .output()?;
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    let ids: Vec<&str> = stdout.lines().collect();
+    assert_eq!(ids.len(), 3);
+    for id in &ids {
+        assert!(uuid::Uuid::parse_str(id).is_ok(), "not a UUID: {}", id);
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--search", "rust", "--ids-only"])
+        .output()?;
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout)?.lines().count(), 1);
+
+    // IDs only has no format to choose
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--ids-only", "--format", "json"])
+        .output()?;
+    assert_eq!(output.status.code(), Some(2));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_csv_columns_and_quoting() -> Result<()> {
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+
+    let pool = SqlitePool::connect(&format!("sqlite://{}", db_path)).await?;
+    let now = chrono::Utc::now().timestamp();
+    sqlx::query("INSERT INTO posts (id, content, created_at, status) VALUES (?, ?, ?, ?)")
+        .bind("quoted")
+        .bind("Hello, \"world\"\nsecond line")
+        .bind(now + 60)
+        .bind("posted")
+        .execute(&pool)
+        .await?;
+    sqlx::query(
+        "INSERT INTO post_records (post_id, platform, success, error_message) VALUES (?, ?, ?, ?)",
+    )
+    .bind("quoted")
+    .bind("mastodon")
+    .bind(0)
+    .bind("Rate limited, retry later")
+    .execute(&pool)
+    .await?;
+    pool.close().await;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args([
+            "--format",
+            "csv",
+            "--columns",
+            "post_id,platform,error,content",
+        ])
+        .args(["--limit", "1"])
+        .output()?;
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert_eq!(
+        stdout,
+        "post_id,platform,error,content\n\
+         quoted,mastodon,\"Rate limited, retry later\",\"Hello, \"\"world\"\"\nsecond line\"\n"
+    );
+
+    // Post-only columns give one row per post
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args([
+            "--format",
+            "csv",
+            "--columns",
+            "post_id",
+            "--search",
+            "rust",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout)?.lines().count(), 2);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--format", "csv", "--columns", "post_id,url"])
+        .output()?;
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--columns", "post_id"])
+        .output()?;
+    assert!(!output.status.success());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_open_prints_url() -> Result<()> {
+    let (_temp_dir, db_path) = create_test_database().await?;
+    let config_dir = TempDir::new()?;
+    let config_path = create_test_config(config_dir.path(), &db_path)?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["--format", "json", "--search", "Hello from Nostr"])
+        .output()?;
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let post_id = entries[0]["post_id"].as_str().unwrap().to_string();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["open", &post_id, "--print"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "https://njump.me/note1abc123\n"
+    );
+
+    // Not published there
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["open", &post_id, "--platform", "mastodon", "--print"])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not published to mastodon"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-history"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["open", "no-such-post", "--print"])
+        .output()?;
+    assert!(!output.status.success());
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+//! Import cursors shared by all importers
+//!
+//! Each import source (an SSB feed, a Mastodon account, a Nostr key, an
+//! RSS feed) saves the position of the last item it handled, so the next
+//! run starts after it instead of reading everything again. The position
+//! is importer-specific: a feed sequence, a status ID, a timestamp.
+//!
+//! The cursor moves after every item, so an interrupted import resumes
+//! where it stopped. After the first failed item it stops moving for the
+//! rest of the run: the next run retries from there, and duplicate checks
+//! skip the items that did make it in. Dry runs never move cursors.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use libplurcast::db::Database;
+use tracing::debug;
+
+/// Saved position of one import source
+pub struct ImportCursor<'a> {
+    db: &'a Database,
+    source: String,
+    saved: Option<String>,
+    dry_run: bool,
+    held: bool,
+}
+
+impl<'a> ImportCursor<'a> {
+    /// Load the saved position for `source`
+    ///
+    /// With `full`, the saved position is ignored so every item is read,
+    /// and the run saves a new one as usual.
+    pub async fn load(db: &'a Database, source: String, full: bool, dry_run: bool) -> Result<Self> {
+        let saved = if full {
+            None
+        } else {
+            db.get_import_cursor(&source)
+                .await
+                .context("Failed to load import position")?
+        };
+        if let Some(ref position) = saved {
+            debug!("Resuming {} after {}", source, position);
+        }
+
+        Ok(Self {
+            db,
+            source,
+            saved,
+            dry_run,
+            held: false,
+        })
+    }
+
+    /// Position the previous run left off at
+    pub fn saved(&self) -> Option<&str> {
+        self.saved.as_deref()
+    }
+
+    /// Move past an item handled without error
+    pub async fn advance(&mut self, position: &str) -> Result<()> {
+        if self.dry_run || self.held {
+            return Ok(());
+        }
+        self.db
+            .set_import_cursor(&self.source, position, Utc::now().timestamp())
+            .await
+            .context("Failed to save import position")
+    }
+
+    /// Stop moving for the rest of the run, after an item failed
+    pub fn hold(&mut self) {
+        self.held = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_cursor_holds_after_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::new(temp_dir.path().join("cursor.db").to_str().unwrap())
+            .await
+            .unwrap();
+
+        let mut cursor = ImportCursor::load(&db, "ssb:default".to_string(), false, false)
+            .await
+            .unwrap();
+        assert_eq!(cursor.saved(), None);
+        cursor.advance("1").await.unwrap();
+        cursor.hold();
+        cursor.advance("3").await.unwrap();
+
+        let cursor = ImportCursor::load(&db, "ssb:default".to_string(), false, false)
+            .await
+            .unwrap();
+        assert_eq!(cursor.saved(), Some("1"));
+
+        let full = ImportCursor::load(&db, "ssb:default".to_string(), true, false)
+            .await
+            .unwrap();
+        assert_eq!(full.saved(), None);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_leaves_cursor() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::new(temp_dir.path().join("cursor.db").to_str().unwrap())
+            .await
+            .unwrap();
+
+        let mut cursor = ImportCursor::load(&db, "nostr:default".to_string(), false, true)
+            .await
+            .unwrap();
+        cursor.advance("1700000000").await.unwrap();
+
+        assert_eq!(db.get_import_cursor("nostr:default").await.unwrap(), None);
+    }
+}
@@ -0,0 +1,206 @@
+//! Duplicate detection shared by all importers
+//!
+//! An item is a duplicate when any of these match what is already in the
+//! database, or an earlier item of the same run:
+//!
+//! 1. its platform post ID (a Mastodon status ID, Nostr note ID, SSB
+//!    message ID) is recorded for that platform
+//! 2. its ID within the import source (e.g. a feed entry GUID) has been
+//!    imported before
+//! 3. a post with the same content hash was created within
+//!    [`CONTENT_MATCH_WINDOW`] of it, which catches the same text posted
+//!    by hand to several platforms and imported from each
+//!
+//! Checks only read, so dry runs use the same rules as real imports.
+
+use anyhow::{Context, Result};
+use libplurcast::db::Database;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+/// How far apart two posts with the same content may have been created
+/// and still count as one post
+///
+/// Short enough that a repeated daily post ("gm") is never mistaken for
+/// a duplicate.
+pub const CONTENT_MATCH_WINDOW: i64 = 6 * 60 * 60;
+
+/// Most posts compared by content around one item
+const CONTENT_MATCH_LIMIT: usize = 1000;
+
+/// One item about to be imported
+#[derive(Debug, Clone, Copy)]
+pub struct ImportItem<'a> {
+    /// Platform and platform post ID, for items already published
+    pub platform_id: Option<(&'a str, &'a str)>,
+    /// Import source and the item's ID in it, e.g. `("rss:<url>", guid)`
+    pub source_id: Option<(&'a str, &'a str)>,
+    pub content: &'a str,
+    /// When the item was originally created (Unix timestamp)
+    pub created_at: i64,
+}
+
+/// Why an item counts as a duplicate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Duplicate {
+    PlatformId,
+    SourceId,
+    Content,
+}
+
+/// Hash of a post's content, ignoring differences in whitespace
+pub fn content_hash(content: &str) -> String {
+    let normalized = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    format!("{:x}", Sha256::digest(normalized.as_bytes()))
+}
+
+/// Duplicate checks for one import run
+pub struct Deduper<'a> {
+    db: &'a Database,
+    seen_ids: HashSet<(String, String)>,
+    seen_hashes: HashSet<(String, i64)>,
+}
+
+impl<'a> Deduper<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        Self {
+            db,
+            seen_ids: HashSet::new(),
+            seen_hashes: HashSet::new(),
+        }
+    }
+
+    /// Check an item, and remember it for the rest of the run if it is new
+    pub async fn check(&mut self, item: &ImportItem<'_>) -> Result<Option<Duplicate>> {
+        if let Some((platform, id)) = item.platform_id {
+            let key = (platform.to_string(), id.to_string());
+            if self.seen_ids.contains(&key)
+                || self
+                    .db
+                    .get_post_id_by_platform_post_id(platform, id)
+                    .await
+                    .context("Failed to check for existing post")?
+                    .is_some()
+            {
+                return Ok(Some(Duplicate::PlatformId));
+            }
+        }
+
+        if let Some((source, id)) = item.source_id {
+            let key = (source.to_string(), id.to_string());
+            if self.seen_ids.contains(&key)
+                || self
+                    .db
+                    .is_item_imported(source, id)
+                    .await
+                    .context("Failed to check for imported item")?
+            {
+                return Ok(Some(Duplicate::SourceId));
+            }
+        }
+
+        let hash = content_hash(item.content);
+        if self.matches_content(&hash, item.created_at).await? {
+            return Ok(Some(Duplicate::Content));
+        }
+
+        for (kind, id) in [item.platform_id, item.source_id].into_iter().flatten() {
+            self.seen_ids.insert((kind.to_string(), id.to_string()));
+        }
+        self.seen_hashes.insert((hash, item.created_at));
+        Ok(None)
+    }
+
+    async fn matches_content(&self, hash: &str, created_at: i64) -> Result<bool> {
+        let near = |other: i64| (other - created_at).abs() <= CONTENT_MATCH_WINDOW;
+        if self
+            .seen_hashes
+            .iter()
+            .any(|(seen, at)| seen == hash && near(*at))
+        {
+            return Ok(true);
+        }
+
+        let posts = self
+            .db
+            .filter_by_date_range(
+                Some(created_at - CONTENT_MATCH_WINDOW),
+                Some(created_at + CONTENT_MATCH_WINDOW),
+                CONTENT_MATCH_LIMIT,
+            )
+            .await
+            .context("Failed to look up posts by date")?;
+        Ok(posts.iter().any(|p| content_hash(&p.post.content) == hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libplurcast::types::{Post, PostStatus};
+    use tempfile::TempDir;
+
+    fn item(content: &str, created_at: i64) -> ImportItem<'_> {
+        ImportItem {
+            platform_id: None,
+            source_id: None,
+            content,
+            created_at,
+        }
+    }
+
+    #[test]
+    fn test_content_hash_ignores_whitespace() {
+        assert_eq!(content_hash("Hello  world\n"), content_hash(" Hello world"));
+        assert_ne!(content_hash("Hello world"), content_hash("Hello World"));
+    }
+
+    #[tokio::test]
+    async fn test_duplicates_by_id_and_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::new(temp_dir.path().join("dedup.db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.create_post(&Post {
+            id: "existing".to_string(),
+            content: "Cross-posted by hand".to_string(),
+            created_at: 10_000,
+            scheduled_at: None,
+            status: PostStatus::Posted,
+            metadata: None,
+        })
+        .await
+        .unwrap();
+
+        let mut dedup = Deduper::new(&db);
+        assert_eq!(
+            dedup
+                .check(&item("Cross-posted  by hand", 10_000 + 600))
+                .await
+                .unwrap(),
+            Some(Duplicate::Content)
+        );
+        // Same text a day later is a new post
+        assert_eq!(
+            dedup
+                .check(&item("Cross-posted by hand", 10_000 + 86_400))
+                .await
+                .unwrap(),
+            None
+        );
+
+        let note = ImportItem {
+            platform_id: Some(("nostr", "note1abc")),
+            ..item("Fresh", 50_000)
+        };
+        assert_eq!(dedup.check(&note).await.unwrap(), None);
+        let again = ImportItem {
+            platform_id: Some(("nostr", "note1abc")),
+            ..item("Edited", 90_000)
+        };
+        assert_eq!(
+            dedup.check(&again).await.unwrap(),
+            Some(Duplicate::PlatformId)
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! plur-import - Import posts from platform exports
+//!
+//! This tool imports existing posts from various platform export formats
+//! into the Plurcast database, preserving timestamps and metadata.
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use libplurcast::config::Config;
+use libplurcast::db::Database;
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{error, info};
+
+pub mod cursor;
+pub mod dedup;
+pub mod mastodon;
+pub mod nostr;
+pub mod rss;
+pub mod ssb;
+pub mod summary;
+
+#[derive(Parser)]
+#[command(name = "plur-import")]
+#[command(about = "Import posts from platform exports", long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+
+    /// Verbose output
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    /// Show how many items are new, duplicates or skipped without writing
+    /// anything to the database
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Read every item again instead of starting where the last import of
+    /// the same source left off (duplicates are still skipped)
+    #[arg(long, global = true)]
+    full: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT",
+        global = true
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL",
+        global = true
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Import from SSB feed
+    Ssb {
+        /// Account name to use (default: "default")
+        #[arg(long, default_value = "default")]
+        account: String,
+    },
+
+    /// Import from a Mastodon archive export
+    Mastodon {
+        /// Archive downloaded from Preferences → Import and export (.tar.gz),
+        /// its extracted directory, or its outbox.json
+        #[arg(long, value_name = "PATH")]
+        archive: PathBuf,
+
+        /// Account name to record posts under (default: "default")
+        #[arg(long, default_value = "default")]
+        account: String,
+    },
+
+    /// Backfill your own notes from the configured Nostr relays
+    Nostr {
+        /// Account whose key the notes are signed with (default: "default")
+        #[arg(long, default_value = "default")]
+        account: String,
+
+        /// Seconds to wait for the relays on each request
+        #[arg(long, default_value = "30", value_name = "SECONDS")]
+        timeout: u64,
+    },
+
+    /// Import entries from an RSS or Atom feed
+    Rss {
+        /// Feed URL (http:// or https://) or local file
+        url: String,
+
+        /// Post content for each entry, from {{title}}, {{link}}, {{summary}}
+        /// and {{published}}
+        #[arg(long, default_value = rss::DEFAULT_TEMPLATE)]
+        template: String,
+
+        /// Import entries as drafts or as already-posted history
+        #[arg(long = "as", value_enum, default_value = "draft", value_name = "KIND")]
+        import_as: rss::ImportAs,
+    },
+}
+
+/// Run plur-import with a command line, program name first
+///
+/// The `plur-import` binary and `plur import` both start here.
+#[tokio::main]
+pub async fn main_from(args: Vec<OsString>) -> Result<()> {
+    let cli = Cli::parse_from(args);
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    // Load configuration
+    let config = Config::load().context("Failed to load configuration")?;
+
+    // Initialize database
+    let db = Database::from_config(&config)
+        .await
+        .context("Failed to initialize database")?;
+
+    // Execute command
+    let result = match cli.command {
+        Commands::Ssb { account } => {
+            ssb::import_ssb(&config, &db, &account, cli.dry_run, cli.full).await
+        }
+        Commands::Mastodon { archive, account } => {
+            mastodon::import_mastodon(&db, &archive, &account, cli.dry_run, cli.full).await
+        }
+        Commands::Nostr { account, timeout } => {
+            nostr::import_nostr(
+                &config,
+                &db,
+                &account,
+                Duration::from_secs(timeout),
+                cli.dry_run,
+                cli.full,
+            )
+            .await
+        }
+        Commands::Rss {
+            url,
+            template,
+            import_as,
+        } => rss::import_rss(&db, &url, &template, import_as, cli.dry_run, cli.full).await,
+    };
+
+    match result {
+        Ok(()) => {
+            info!("Import completed successfully");
+            Ok(())
+        }
+        Err(e) => {
+            error!("Import failed: {}", e);
+            std::process::exit(2);
+        }
+    }
+}
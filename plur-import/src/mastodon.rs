@@ -0,0 +1,438 @@
+//! Mastodon archive import functionality
+//!
+//! This module imports statuses from the archive Mastodon offers under
+//! Preferences → Import and export → Request your archive. The archive is
+//! a `.tar.gz` holding `outbox.json`, an ActivityPub `OrderedCollection` of
+//! the account's activities; each `Create` of a `Note` becomes a post with
+//! its original publish time, status ID and visibility. Boosts (`Announce`)
+//! are not the account's own posts and are skipped.
+
+use crate::cursor::ImportCursor;
+use crate::dedup::{Deduper, ImportItem};
+use crate::summary::ImportSummary;
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use libplurcast::db::Database;
+pub use libplurcast::platforms::mastodon::html_to_text;
+use libplurcast::types::{Post, PostRecord, PostStatus, Visibility};
+use serde_json::Value;
+use std::io::Read;
+use std::path::Path;
+use tracing::{debug, info, warn};
+
+/// ActivityStreams public collection, addressed by public and unlisted posts
+const PUBLIC: &str = "https://www.w3.org/ns/activitystreams#Public";
+
+/// Name of the outbox file inside the archive
+const OUTBOX_FILE: &str = "outbox.json";
+
+/// A status read from the outbox
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchivedStatus {
+    /// Status ID (the last segment of the ActivityPub object ID)
+    pub id: String,
+    /// ActivityPub object ID, e.g. `https://mastodon.social/users/alice/statuses/1`
+    pub uri: String,
+    /// Web URL of the status
+    pub url: Option<String>,
+    /// Publish time (Unix timestamp)
+    pub published: i64,
+    /// Plain-text content
+    pub content: String,
+    pub content_warning: Option<String>,
+    pub visibility: Visibility,
+    pub language: Option<String>,
+}
+
+/// Import posts from a Mastodon archive
+///
+/// `archive` may be the downloaded `.tar.gz`, an extracted archive
+/// directory, or `outbox.json` itself.
+pub async fn import_mastodon(
+    db: &Database,
+    archive: &Path,
+    account: &str,
+    dry_run: bool,
+    full: bool,
+) -> Result<()> {
+    info!(
+        "Starting Mastodon import from {} for account '{}'",
+        archive.display(),
+        account
+    );
+
+    let outbox = read_outbox(archive)?;
+    let activities = outbox
+        .get("orderedItems")
+        .and_then(|items| items.as_array())
+        .context("outbox.json has no orderedItems")?;
+
+    info!("Found {} activit(ies) in outbox", activities.len());
+
+    let mut summary = ImportSummary::new("activities", Some("boosts and non-posts"), dry_run);
+    summary.total = activities.len();
+    let mut dedup = Deduper::new(db);
+
+    let mut statuses = Vec::new();
+    for activity in activities {
+        match parse_activity(activity) {
+            Ok(Some(status)) => statuses.push(status),
+            Ok(None) => summary.skipped += 1,
+            Err(e) => {
+                let error_msg = format!(
+                    "Failed to read activity {}: {}",
+                    activity.get("id").and_then(|id| id.as_str()).unwrap_or("?"),
+                    e
+                );
+                warn!("{}", error_msg);
+                summary.errors.push(error_msg);
+            }
+        }
+    }
+
+    // Oldest first, so the position only ever moves forward
+    statuses.sort_by_key(|status| status.published);
+
+    // Position is the ID of the last status handled; status IDs grow over time
+    let mut cursor = ImportCursor::load(db, format!("mastodon:{}", account), full, dry_run).await?;
+    let last_id = cursor.saved().and_then(|id| id.parse::<u64>().ok());
+
+    for status in &statuses {
+        let id = status.id.parse::<u64>().ok();
+        if matches!((id, last_id), (Some(id), Some(last)) if id <= last) {
+            summary.before_cursor += 1;
+            continue;
+        }
+
+        match import_status(db, &mut dedup, status, account, &mut summary).await {
+            Ok(()) => cursor.advance(&status.id).await?,
+            Err(e) => {
+                let error_msg = format!("Failed to import status {}: {}", status.id, e);
+                warn!("{}", error_msg);
+                summary.errors.push(error_msg);
+                cursor.hold();
+            }
+        }
+    }
+
+    summary.display();
+
+    if !summary.errors.is_empty() {
+        anyhow::bail!("Import completed with {} error(s)", summary.errors.len());
+    }
+
+    Ok(())
+}
+
+/// Read and parse `outbox.json` from an archive, directory or file
+pub fn read_outbox(archive: &Path) -> Result<Value> {
+    if !archive.exists() {
+        anyhow::bail!("Mastodon archive not found at: {}", archive.display());
+    }
+
+    let json = if archive.is_dir() {
+        let path = archive.join(OUTBOX_FILE);
+        std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+    } else if archive.extension().and_then(|s| s.to_str()) == Some("json") {
+        std::fs::read_to_string(archive)
+            .with_context(|| format!("Failed to read {}", archive.display()))?
+    } else {
+        read_outbox_from_tar(archive)?
+    };
+
+    serde_json::from_str(&json).context("Failed to parse outbox.json")
+}
+
+/// Find `outbox.json` in a gzipped tar archive
+fn read_outbox_from_tar(path: &Path) -> Result<String> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open archive: {}", path.display()))?;
+    let mut tar = tar::Archive::new(GzDecoder::new(file));
+
+    let entries = tar
+        .entries()
+        .with_context(|| format!("Failed to read archive: {}", path.display()))?;
+    for entry in entries {
+        let mut entry = entry.context("Failed to read archive entry")?;
+        let is_outbox = entry
+            .path()
+            .ok()
+            .and_then(|p| p.file_name().map(|name| name == OUTBOX_FILE))
+            .unwrap_or(false);
+        if is_outbox {
+            let mut json = String::new();
+            entry
+                .read_to_string(&mut json)
+                .context("Failed to read outbox.json from archive")?;
+            return Ok(json);
+        }
+    }
+
+    anyhow::bail!("No {} found in {}", OUTBOX_FILE, path.display())
+}
+
+/// Turn a `Create` activity into a status
+///
+/// Returns `Ok(None)` for activities that are not the account's own notes,
+/// such as boosts.
+pub fn parse_activity(activity: &Value) -> Result<Option<ArchivedStatus>> {
+    if activity.get("type").and_then(|t| t.as_str()) != Some("Create") {
+        return Ok(None);
+    }
+    let object = match activity.get("object") {
+        Some(object) if object.is_object() => object,
+        _ => return Ok(None),
+    };
+    if !matches!(
+        object.get("type").and_then(|t| t.as_str()),
+        Some("Note") | Some("Question")
+    ) {
+        return Ok(None);
+    }
+
+    let uri = object
+        .get("id")
+        .and_then(|id| id.as_str())
+        .context("Note has no id")?
+        .to_string();
+    let id = uri
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .filter(|id| !id.is_empty())
+        .context("Note id has no status ID")?
+        .to_string();
+
+    let published = object
+        .get("published")
+        .or_else(|| activity.get("published"))
+        .and_then(|p| p.as_str())
+        .context("Note has no published time")?;
+    let published = chrono::DateTime::parse_from_rfc3339(published)
+        .with_context(|| format!("Invalid published time '{}'", published))?
+        .timestamp();
+
+    let content = html_to_text(object.get("content").and_then(|c| c.as_str()).unwrap_or(""));
+    let content_warning = object
+        .get("summary")
+        .and_then(|s| s.as_str())
+        .filter(|s| !s.is_empty())
+        .map(str::to_string);
+    let language = object
+        .get("contentMap")
+        .and_then(|m| m.as_object())
+        .and_then(|m| m.keys().next().cloned());
+
+    Ok(Some(ArchivedStatus {
+        id,
+        uri,
+        url: object
+            .get("url")
+            .and_then(|u| u.as_str())
+            .map(str::to_string),
+        published,
+        content,
+        content_warning,
+        visibility: visibility(object),
+        language,
+    }))
+}
+
+/// Work out a note's visibility from its addressing
+///
+/// Public notes are addressed to the public collection, unlisted ones only
+/// copy it, followers-only ones go to the followers collection and direct
+/// messages to the mentioned accounts alone.
+fn visibility(object: &Value) -> Visibility {
+    let addressed = |field: &str| -> Vec<String> {
+        match object.get(field) {
+            Some(Value::String(s)) => vec![s.clone()],
+            Some(Value::Array(items)) => items
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect(),
+            _ => Vec::new(),
+        }
+    };
+    let to = addressed("to");
+    let cc = addressed("cc");
+
+    if to.iter().any(|a| a == PUBLIC) {
+        Visibility::Public
+    } else if cc.iter().any(|a| a == PUBLIC) {
+        Visibility::Unlisted
+    } else if to
+        .iter()
+        .chain(cc.iter())
+        .any(|a| a.ends_with("/followers"))
+    {
+        Visibility::Private
+    } else {
+        Visibility::Direct
+    }
+}
+
+/// Import a single status into the database
+async fn import_status(
+    db: &Database,
+    dedup: &mut Deduper<'_>,
+    status: &ArchivedStatus,
+    account: &str,
+    summary: &mut ImportSummary,
+) -> Result<()> {
+    let item = ImportItem {
+        platform_id: Some(("mastodon", &status.id)),
+        source_id: None,
+        content: &status.content,
+        created_at: status.published,
+    };
+    if let Some(duplicate) = dedup.check(&item).await? {
+        debug!("Skipping duplicate status {} ({:?})", status.id, duplicate);
+        summary.duplicate(duplicate);
+        return Ok(());
+    }
+
+    if summary.dry_run() {
+        summary.imported += 1;
+        return Ok(());
+    }
+
+    let mut metadata = serde_json::json!({ "platforms": ["mastodon"] });
+    if let Some(ref cw) = status.content_warning {
+        metadata["content_warning"] = serde_json::json!(cw);
+    }
+    if status.visibility != Visibility::Public {
+        metadata["visibility"] = serde_json::json!(status.visibility);
+    }
+    if let Some(ref language) = status.language {
+        metadata[libplurcast::language::LANGUAGE_METADATA_KEY] = serde_json::json!(language);
+    }
+
+    let post_id = uuid::Uuid::new_v4().to_string();
+    let post = Post {
+        id: post_id.clone(),
+        content: status.content.clone(),
+        created_at: status.published,
+        scheduled_at: None,
+        status: PostStatus::Posted,
+        metadata: Some(metadata.to_string()),
+    };
+
+    db.create_post(&post)
+        .await
+        .context("Failed to insert post")?;
+
+    let record = PostRecord {
+        id: None,
+        post_id,
+        platform: "mastodon".to_string(),
+        platform_post_id: Some(status.id.clone()),
+        posted_at: Some(status.published),
+        success: true,
+        error_message: None,
+        account_name: account.to_string(),
+    };
+
+    // Keep the status URL so plur-history can link to it
+    let raw = serde_json::json!({
+        "id": status.id,
+        "uri": status.uri,
+        "url": status.url,
+        "visibility": status.visibility,
+    });
+    db.create_post_record_with_response(&record, Some(&raw))
+        .await
+        .context("Failed to insert post record")?;
+
+    info!("Imported Mastodon status {}", status.id);
+    summary.imported += 1;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(to: &[&str], cc: &[&str]) -> Value {
+        serde_json::json!({
+            "id": "https://example.social/users/alice/statuses/110/activity",
+            "type": "Create",
+            "published": "2024-03-01T12:00:00Z",
+            "object": {
+                "id": "https://example.social/users/alice/statuses/110",
+                "type": "Note",
+                "url": "https://example.social/@alice/110",
+                "published": "2024-03-01T12:00:00Z",
+                "summary": null,
+                "content": "<p>Hello &amp; welcome</p><p>Second<br />line</p>",
+                "contentMap": { "en": "<p>Hello &amp; welcome</p>" },
+                "to": to,
+                "cc": cc,
+            }
+        })
+    }
+
+    #[test]
+    fn test_parse_create_note() {
+        let status = parse_activity(&note(&[PUBLIC], &[]))
+            .unwrap()
+            .expect("note should parse");
+        assert_eq!(status.id, "110");
+        assert_eq!(status.published, 1_709_294_400);
+        assert_eq!(status.content, "Hello & welcome\n\nSecond\nline");
+        assert_eq!(
+            status.url.as_deref(),
+            Some("https://example.social/@alice/110")
+        );
+        assert_eq!(status.visibility, Visibility::Public);
+        assert_eq!(status.language.as_deref(), Some("en"));
+        assert_eq!(status.content_warning, None);
+    }
+
+    #[test]
+    fn test_visibility_from_addressing() {
+        let followers = "https://example.social/users/alice/followers";
+        let friend = "https://other.social/users/bob";
+        let parse =
+            |to: &[&str], cc: &[&str]| parse_activity(&note(to, cc)).unwrap().unwrap().visibility;
+        assert_eq!(parse(&[followers], &[PUBLIC]), Visibility::Unlisted);
+        assert_eq!(parse(&[followers], &[friend]), Visibility::Private);
+        assert_eq!(parse(&[friend], &[]), Visibility::Direct);
+    }
+
+    #[test]
+    fn test_boosts_are_skipped() {
+        let boost = serde_json::json!({
+            "type": "Announce",
+            "published": "2024-03-01T12:00:00Z",
+            "object": "https://other.social/users/bob/statuses/1",
+        });
+        assert_eq!(parse_activity(&boost).unwrap(), None);
+    }
+
+    #[test]
+    fn test_read_outbox_from_tar_gz() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("archive.tar.gz");
+        let outbox = serde_json::json!({ "orderedItems": [note(&[PUBLIC], &[])] }).to_string();
+
+        let gz = flate2::write::GzEncoder::new(
+            std::fs::File::create(&path).unwrap(),
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(gz);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(outbox.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "outbox.json", outbox.as_bytes())
+            .unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let read = read_outbox(&path).unwrap();
+        assert_eq!(read["orderedItems"].as_array().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,176 @@
+//! Nostr relay import functionality
+//!
+//! This module backfills the account's own text notes (kind 1) from its
+//! configured relays into the Plurcast database, keeping each note's
+//! creation time and event ID.
+
+use crate::cursor::ImportCursor;
+use crate::dedup::{Deduper, ImportItem};
+use crate::summary::ImportSummary;
+use anyhow::{Context, Result};
+use libplurcast::config::Config;
+use libplurcast::credentials::CredentialManager;
+use libplurcast::db::Database;
+use libplurcast::platforms::nostr::{FetchedNote, NostrPlatform};
+use libplurcast::platforms::Platform;
+use libplurcast::types::{Post, PostRecord, PostStatus};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Import the account's notes from its Nostr relays
+///
+/// `timeout` bounds each request to the relays.
+pub async fn import_nostr(
+    config: &Config,
+    db: &Database,
+    account: &str,
+    timeout: Duration,
+    dry_run: bool,
+    full: bool,
+) -> Result<()> {
+    info!("Starting Nostr import for account '{}'", account);
+
+    let nostr_config = config
+        .nostr
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("Nostr is not configured"))?;
+
+    if !nostr_config.enabled {
+        anyhow::bail!("Nostr is not enabled in configuration");
+    }
+    if nostr_config.relays.is_empty() {
+        anyhow::bail!("No Nostr relays configured");
+    }
+
+    let cred_config = config
+        .credentials
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("Credential configuration is missing"))?;
+    let credentials =
+        CredentialManager::new(cred_config).context("Failed to initialize credential manager")?;
+    let private_key = credentials
+        .retrieve_account("plurcast.nostr", "private_key", account)
+        .with_context(|| {
+            format!(
+                "No Nostr credentials found for account '{}'. Run 'plur-creds set nostr --account {}'",
+                account, account
+            )
+        })?;
+
+    let mut platform = NostrPlatform::new(nostr_config);
+    platform
+        .load_keys_from_string(&private_key)
+        .context("Failed to load Nostr keys")?;
+    platform
+        .authenticate()
+        .await
+        .context("Failed to connect to Nostr relays")?;
+
+    // Position is the creation time of the last note handled. Relays
+    // include notes created at `since`, and duplicate checks skip the ones
+    // already imported.
+    let mut cursor = ImportCursor::load(db, format!("nostr:{}", account), full, dry_run).await?;
+    let since = cursor.saved().and_then(|s| s.parse::<i64>().ok());
+
+    match since {
+        Some(since) => info!(
+            "Fetching notes since {} from {} relay(s)",
+            since,
+            nostr_config.relays.len()
+        ),
+        None => info!("Fetching notes from {} relay(s)", nostr_config.relays.len()),
+    }
+
+    let notes = platform
+        .fetch_own_notes(since, timeout)
+        .await
+        .context("Failed to fetch notes from relays")?;
+
+    info!("Found {} note(s) on relays", notes.len());
+
+    let mut summary = ImportSummary::new("notes", None, dry_run);
+    summary.total = notes.len();
+    let mut dedup = Deduper::new(db);
+
+    for note in &notes {
+        match import_note(db, &mut dedup, note, account, &mut summary).await {
+            Ok(()) => cursor.advance(&note.created_at.to_string()).await?,
+            Err(e) => {
+                let error_msg = format!("Failed to import note {}: {}", note.note_id, e);
+                warn!("{}", error_msg);
+                summary.errors.push(error_msg);
+                cursor.hold();
+            }
+        }
+    }
+
+    summary.display();
+
+    if !summary.errors.is_empty() {
+        anyhow::bail!("Import completed with {} error(s)", summary.errors.len());
+    }
+
+    Ok(())
+}
+
+/// Import a single note into the database
+async fn import_note(
+    db: &Database,
+    dedup: &mut Deduper<'_>,
+    note: &FetchedNote,
+    account: &str,
+    summary: &mut ImportSummary,
+) -> Result<()> {
+    let item = ImportItem {
+        platform_id: Some(("nostr", &note.note_id)),
+        source_id: None,
+        content: &note.content,
+        created_at: note.created_at,
+    };
+    if let Some(duplicate) = dedup.check(&item).await? {
+        debug!("Skipping duplicate note {} ({:?})", note.note_id, duplicate);
+        summary.duplicate(duplicate);
+        return Ok(());
+    }
+
+    if summary.dry_run() {
+        summary.imported += 1;
+        return Ok(());
+    }
+
+    let post_id = uuid::Uuid::new_v4().to_string();
+    let post = Post {
+        id: post_id.clone(),
+        content: note.content.clone(),
+        created_at: note.created_at,
+        scheduled_at: None,
+        status: PostStatus::Posted,
+        metadata: Some(serde_json::json!({ "platforms": ["nostr"] }).to_string()),
+    };
+
+    db.create_post(&post)
+        .await
+        .context("Failed to insert post")?;
+
+    let record = PostRecord {
+        id: None,
+        post_id,
+        platform: "nostr".to_string(),
+        platform_post_id: Some(note.note_id.clone()),
+        posted_at: Some(note.created_at),
+        success: true,
+        error_message: None,
+        account_name: account.to_string(),
+    };
+
+    // Same shape as the response stored when publishing
+    let raw = serde_json::json!({ "event": note.event });
+    db.create_post_record_with_response(&record, Some(&raw))
+        .await
+        .context("Failed to insert post record")?;
+
+    info!("Imported Nostr note {}", note.note_id);
+    summary.imported += 1;
+
+    Ok(())
+}
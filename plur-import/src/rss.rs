@@ -0,0 +1,319 @@
+//! RSS/Atom feed import functionality
+//!
+//! This module turns feed entries into drafts (to edit and publish later)
+//! or posted history entries. Each entry's content comes from a template
+//! over its fields, `{{title}}\n\n{{link}}` by default. Entry GUIDs are
+//! remembered per feed, and the feed's import position is the date and
+//! GUID of the newest entry handled, so re-running an import only brings
+//! in new entries.
+
+use crate::cursor::ImportCursor;
+use crate::dedup::{Deduper, ImportItem};
+use crate::summary::ImportSummary;
+use anyhow::{Context, Result};
+use chrono::{Local, Utc};
+use clap::ValueEnum;
+use feed_rs::model::Entry;
+use libplurcast::db::Database;
+use libplurcast::templates;
+use libplurcast::types::{Post, PostStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::{debug, info, warn};
+
+/// Template used when `--template` is not given
+pub const DEFAULT_TEMPLATE: &str = "{{title}}\n\n{{link}}";
+
+/// Entry fields available to templates
+const FIELDS: [&str; 4] = ["title", "link", "summary", "published"];
+
+/// What each imported entry becomes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportAs {
+    /// A draft, to edit and publish later
+    Draft,
+    /// A post already published, dated with the entry
+    Posted,
+}
+
+/// Where a previous import of a feed left off
+#[derive(Debug, Serialize, Deserialize)]
+struct FeedPosition {
+    /// Date of the last entry handled (Unix timestamp)
+    published: i64,
+    /// GUID of the last entry handled
+    guid: String,
+}
+
+impl FeedPosition {
+    /// Whether an entry was handled by the run that saved this position
+    ///
+    /// Undated entries are always read again; duplicate checks skip them.
+    fn covers(&self, entry: &Entry) -> bool {
+        entry.id == self.guid
+            || entry_date(entry).is_some_and(|published| published < self.published)
+    }
+}
+
+/// Import entries from an RSS or Atom feed
+///
+/// `location` is an `http(s)://` URL or a local file.
+pub async fn import_rss(
+    db: &Database,
+    location: &str,
+    template: &str,
+    import_as: ImportAs,
+    dry_run: bool,
+    full: bool,
+) -> Result<()> {
+    info!("Starting feed import from {}", location);
+
+    check_template(template)?;
+
+    let feed = read_feed(location).await?;
+    let source = format!("rss:{}", location);
+
+    info!("Found {} entr(ies) in feed", feed.entries.len());
+
+    let mut summary = ImportSummary::new("entries", Some("empty"), dry_run);
+    summary.total = feed.entries.len();
+    let mut dedup = Deduper::new(db);
+
+    // Oldest first, so drafts list in publication order
+    let mut entries = feed.entries;
+    entries.sort_by_key(entry_date);
+
+    let mut cursor = ImportCursor::load(db, source.clone(), full, dry_run).await?;
+    let position = cursor
+        .saved()
+        .and_then(|saved| serde_json::from_str::<FeedPosition>(saved).ok());
+
+    for entry in &entries {
+        if position.as_ref().is_some_and(|p| p.covers(entry)) {
+            summary.before_cursor += 1;
+            continue;
+        }
+
+        match import_entry(
+            db,
+            &mut dedup,
+            &source,
+            entry,
+            template,
+            import_as,
+            &mut summary,
+        )
+        .await
+        {
+            Ok(()) => {
+                if let Some(published) = entry_date(entry) {
+                    let position = FeedPosition {
+                        published,
+                        guid: entry.id.clone(),
+                    };
+                    cursor.advance(&serde_json::to_string(&position)?).await?;
+                }
+            }
+            Err(e) => {
+                let error_msg = format!("Failed to import entry {}: {}", entry.id, e);
+                warn!("{}", error_msg);
+                summary.errors.push(error_msg);
+                cursor.hold();
+            }
+        }
+    }
+
+    summary.display();
+
+    if !summary.errors.is_empty() {
+        anyhow::bail!("Import completed with {} error(s)", summary.errors.len());
+    }
+
+    Ok(())
+}
+
+/// Fail early on a template using anything but entry fields and built-ins
+fn check_template(template: &str) -> Result<()> {
+    let vars: HashMap<String, String> = FIELDS
+        .iter()
+        .map(|field| (field.to_string(), String::new()))
+        .collect();
+    templates::render(template, &vars, Local::now()).map_err(|_| {
+        anyhow::anyhow!(
+            "Unknown field in --template: use {}, or the built-ins {{{{date}}}}, {{{{time}}}}, {{{{year}}}} and {{{{weekday}}}}",
+            FIELDS
+                .iter()
+                .map(|field| format!("{{{{{}}}}}", field))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    })?;
+    Ok(())
+}
+
+/// Download or read a feed and parse it
+async fn read_feed(location: &str) -> Result<feed_rs::model::Feed> {
+    let body = if location.starts_with("http://") || location.starts_with("https://") {
+        let response = reqwest::get(location)
+            .await
+            .with_context(|| format!("Failed to fetch feed {}", location))?
+            .error_for_status()
+            .with_context(|| format!("Failed to fetch feed {}", location))?;
+        response
+            .bytes()
+            .await
+            .context("Failed to read feed response")?
+            .to_vec()
+    } else {
+        let path = Path::new(location);
+        if !path.exists() {
+            anyhow::bail!("Feed not found at: {}", location);
+        }
+        std::fs::read(path).with_context(|| format!("Failed to read feed {}", location))?
+    };
+
+    feed_rs::parser::parse(body.as_slice())
+        .with_context(|| format!("Failed to parse feed {}", location))
+}
+
+/// When an entry was published, or last updated if that is all it has
+fn entry_date(entry: &Entry) -> Option<i64> {
+    entry
+        .published
+        .or(entry.updated)
+        .map(|date| date.timestamp())
+}
+
+/// Template fields for an entry
+fn entry_fields(entry: &Entry) -> HashMap<String, String> {
+    let title = entry
+        .title
+        .as_ref()
+        .map(|t| crate::mastodon::html_to_text(&t.content))
+        .unwrap_or_default();
+    let link = entry
+        .links
+        .first()
+        .map(|l| l.href.clone())
+        .unwrap_or_default();
+    let summary = entry
+        .summary
+        .as_ref()
+        .map(|s| s.content.clone())
+        .or_else(|| entry.content.as_ref().and_then(|c| c.body.clone()))
+        .map(|html| crate::mastodon::html_to_text(&html))
+        .unwrap_or_default();
+    let published = entry
+        .published
+        .or(entry.updated)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+
+    HashMap::from([
+        ("title".to_string(), title),
+        ("link".to_string(), link),
+        ("summary".to_string(), summary),
+        ("published".to_string(), published),
+    ])
+}
+
+/// Import a single entry into the database
+async fn import_entry(
+    db: &Database,
+    dedup: &mut Deduper<'_>,
+    source: &str,
+    entry: &Entry,
+    template: &str,
+    import_as: ImportAs,
+    summary: &mut ImportSummary,
+) -> Result<()> {
+    let content = templates::render(template, &entry_fields(entry), Local::now())?
+        .trim()
+        .to_string();
+    if content.is_empty() {
+        debug!("Skipping entry with no content: {}", entry.id);
+        summary.skipped += 1;
+        return Ok(());
+    }
+
+    let now = Utc::now().timestamp();
+    let published = entry_date(entry);
+
+    let item = ImportItem {
+        platform_id: None,
+        source_id: Some((source, &entry.id)),
+        content: &content,
+        created_at: published.unwrap_or(now),
+    };
+    if let Some(duplicate) = dedup.check(&item).await? {
+        debug!("Skipping duplicate entry {} ({:?})", entry.id, duplicate);
+        summary.duplicate(duplicate);
+        return Ok(());
+    }
+
+    if summary.dry_run() {
+        summary.imported += 1;
+        return Ok(());
+    }
+
+    let (status, created_at) = match import_as {
+        ImportAs::Draft => (PostStatus::Draft, now),
+        ImportAs::Posted => (PostStatus::Posted, published.unwrap_or(now)),
+    };
+
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content,
+        created_at,
+        scheduled_at: None,
+        status,
+        metadata: None,
+    };
+
+    db.create_post(&post)
+        .await
+        .context("Failed to insert post")?;
+    db.record_imported_item(source, &entry.id, &post.id, now)
+        .await
+        .context("Failed to record imported entry")?;
+
+    info!("Imported feed entry {} as {}", entry.id, post.id);
+    summary.imported += 1;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = r#"<?xml version="1.0"?>
+<rss version="2.0"><channel><title>Blog</title>
+<item>
+  <title>Hello &amp; welcome</title>
+  <link>https://example.com/hello</link>
+  <guid>https://example.com/hello</guid>
+  <description>&lt;p&gt;First post&lt;/p&gt;</description>
+  <pubDate>Fri, 01 Mar 2024 12:00:00 GMT</pubDate>
+</item>
+</channel></rss>"#;
+
+    #[test]
+    fn test_entry_fields() {
+        let feed = feed_rs::parser::parse(RSS.as_bytes()).unwrap();
+        let fields = entry_fields(&feed.entries[0]);
+        assert_eq!(fields["title"], "Hello & welcome");
+        assert_eq!(fields["link"], "https://example.com/hello");
+        assert_eq!(fields["summary"], "First post");
+        assert_eq!(fields["published"], "2024-03-01");
+    }
+
+    #[test]
+    fn test_check_template() {
+        assert!(check_template(DEFAULT_TEMPLATE).is_ok());
+        assert!(check_template("{{summary}} ({{date}})").is_ok());
+        let err = check_template("{{author}}").unwrap_err().to_string();
+        assert!(err.contains("{{title}}"), "{}", err);
+    }
+}
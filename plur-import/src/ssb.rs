@@ -3,52 +3,25 @@
 //! This module handles importing posts from a local SSB feed database
 //! into the Plurcast database.
 
+use crate::cursor::ImportCursor;
+use crate::dedup::{Deduper, ImportItem};
+use crate::summary::ImportSummary;
 use anyhow::{Context, Result};
 use libplurcast::config::Config;
 use libplurcast::credentials::CredentialManager;
 use libplurcast::db::Database;
 use libplurcast::platforms::ssb::{SSBMessage, SSBPlatform};
-use std::collections::HashSet;
 use std::path::PathBuf;
 use tracing::{debug, info, warn};
 
-/// Import summary statistics
-#[derive(Debug, Default)]
-struct ImportSummary {
-    total_messages: usize,
-    imported: usize,
-    skipped_duplicates: usize,
-    skipped_non_posts: usize,
-    errors: Vec<String>,
-}
-
-impl ImportSummary {
-    fn display(&self) {
-        println!("\n=== Import Summary ===");
-        println!("Total messages found: {}", self.total_messages);
-        println!("Imported: {}", self.imported);
-        println!("Skipped (duplicates): {}", self.skipped_duplicates);
-        println!("Skipped (non-posts): {}", self.skipped_non_posts);
-
-        if !self.errors.is_empty() {
-            println!("\nErrors encountered: {}", self.errors.len());
-            for (i, error) in self.errors.iter().enumerate() {
-                println!("  {}. {}", i + 1, error);
-            }
-        }
-
-        if self.imported > 0 {
-            println!("\n✓ Successfully imported {} post(s)", self.imported);
-        } else if self.total_messages == 0 {
-            println!("\nNo messages found in SSB feed");
-        } else {
-            println!("\nNo new posts to import");
-        }
-    }
-}
-
 /// Import posts from SSB feed
-pub async fn import_ssb(config: &Config, db: &Database, account: &str) -> Result<()> {
+pub async fn import_ssb(
+    config: &Config,
+    db: &Database,
+    account: &str,
+    dry_run: bool,
+    full: bool,
+) -> Result<()> {
     info!("Starting SSB import for account '{}'", account);
 
     // Check if SSB is configured and enabled
@@ -84,26 +57,28 @@ pub async fn import_ssb(config: &Config, db: &Database, account: &str) -> Result
 
     info!("Found {} message(s) in SSB feed", messages.len());
 
-    // Get existing SSB message IDs to avoid duplicates
-    let existing_ids = get_existing_ssb_message_ids(db).await?;
-    debug!(
-        "Found {} existing SSB message(s) in database",
-        existing_ids.len()
-    );
-
     // Import messages
-    let mut summary = ImportSummary {
-        total_messages: messages.len(),
-        ..Default::default()
-    };
+    let mut summary = ImportSummary::new("messages", Some("non-posts"), dry_run);
+    summary.total = messages.len();
+    let mut dedup = Deduper::new(db);
+
+    // Position is the sequence number of the last message handled
+    let mut cursor = ImportCursor::load(db, format!("ssb:{}", account), full, dry_run).await?;
+    let last_sequence = cursor.saved().and_then(|s| s.parse::<u64>().ok());
 
     for message in messages {
-        match import_message(db, &message, &existing_ids, &mut summary).await {
-            Ok(()) => {}
+        if last_sequence.is_some_and(|last| message.sequence <= last) {
+            summary.before_cursor += 1;
+            continue;
+        }
+
+        match import_message(db, &mut dedup, &message, &mut summary).await {
+            Ok(()) => cursor.advance(&message.sequence.to_string()).await?,
             Err(e) => {
                 let error_msg = format!("Failed to import message {}: {}", message.sequence, e);
                 warn!("{}", error_msg);
                 summary.errors.push(error_msg);
+                cursor.hold();
             }
         }
     }
@@ -168,29 +143,11 @@ fn query_feed_messages(feed_path: &PathBuf) -> Result<Vec<SSBMessage>> {
     Ok(messages)
 }
 
-/// Get existing SSB message IDs from the database
-async fn get_existing_ssb_message_ids(db: &Database) -> Result<HashSet<String>> {
-    // Use Database API to query existing posts
-    // query_posts_with_records(platform, since, until, search_term, limit)
-    let posts = db
-        .query_posts_with_records(Some("ssb"), None, None, None, 10000)
-        .await
-        .context("Failed to query existing SSB posts")?;
-
-    let ids: HashSet<String> = posts
-        .into_iter()
-        .flat_map(|p| p.records)
-        .filter_map(|r| r.platform_post_id)
-        .collect();
-
-    Ok(ids)
-}
-
 /// Import a single message into the database
 async fn import_message(
     db: &Database,
+    dedup: &mut Deduper<'_>,
     message: &SSBMessage,
-    existing_ids: &HashSet<String>,
     summary: &mut ImportSummary,
 ) -> Result<()> {
     // Calculate message ID
@@ -204,13 +161,6 @@ async fn import_message(
         format!("ssb:{}", message_id)
     };
 
-    // Check if already imported
-    if existing_ids.contains(&ssb_message_id) {
-        debug!("Skipping duplicate message: {}", ssb_message_id);
-        summary.skipped_duplicates += 1;
-        return Ok(());
-    }
-
     // Check if it's a post message
     let content_obj = message
         .content
@@ -224,7 +174,7 @@ async fn import_message(
 
     if msg_type != "post" {
         debug!("Skipping non-post message (type: {})", msg_type);
-        summary.skipped_non_posts += 1;
+        summary.skipped += 1;
         return Ok(());
     }
 
@@ -234,9 +184,31 @@ async fn import_message(
         .and_then(|v| v.as_str())
         .context("Post message has no text field")?;
 
+    let created_at = message.timestamp / 1000; // Convert milliseconds to seconds
+
+    // Check if already imported
+    let item = ImportItem {
+        platform_id: Some(("ssb", &ssb_message_id)),
+        source_id: None,
+        content: text,
+        created_at,
+    };
+    if let Some(duplicate) = dedup.check(&item).await? {
+        debug!(
+            "Skipping duplicate message {} ({:?})",
+            ssb_message_id, duplicate
+        );
+        summary.duplicate(duplicate);
+        return Ok(());
+    }
+
+    if summary.dry_run() {
+        summary.imported += 1;
+        return Ok(());
+    }
+
     // Create post record
     let post_id = uuid::Uuid::new_v4().to_string();
-    let created_at = message.timestamp / 1000; // Convert milliseconds to seconds
 
     debug!(
         "Importing post: sequence={}, id={}, length={}",
@@ -0,0 +1,100 @@
+//! Import summary shared by all importers
+
+use crate::dedup::Duplicate;
+
+/// Counts for one import run, printed when it finishes
+#[derive(Debug)]
+pub struct ImportSummary {
+    /// What the source holds, plural (e.g. "entries")
+    items: &'static str,
+    /// Why items are skipped besides being duplicates (e.g. "empty")
+    skip_reason: Option<&'static str>,
+    dry_run: bool,
+    pub total: usize,
+    pub imported: usize,
+    pub duplicates: usize,
+    /// Duplicates found by content rather than by ID
+    pub duplicate_content: usize,
+    pub skipped: usize,
+    /// Items at or before the position an earlier run left off at
+    pub before_cursor: usize,
+    pub errors: Vec<String>,
+}
+
+impl ImportSummary {
+    pub fn new(items: &'static str, skip_reason: Option<&'static str>, dry_run: bool) -> Self {
+        Self {
+            items,
+            skip_reason,
+            dry_run,
+            total: 0,
+            imported: 0,
+            duplicates: 0,
+            duplicate_content: 0,
+            skipped: 0,
+            before_cursor: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Whether this run only counts, writing nothing
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Count a duplicate found by [`Deduper`](crate::dedup::Deduper)
+    pub fn duplicate(&mut self, duplicate: Duplicate) {
+        self.duplicates += 1;
+        if duplicate == Duplicate::Content {
+            self.duplicate_content += 1;
+        }
+    }
+
+    pub fn display(&self) {
+        if self.dry_run {
+            println!("\n=== Import Summary (dry run) ===");
+        } else {
+            println!("\n=== Import Summary ===");
+        }
+        println!("Total {} found: {}", self.items, self.total);
+        if self.dry_run {
+            println!("New (would import): {}", self.imported);
+        } else {
+            println!("Imported: {}", self.imported);
+        }
+        if self.duplicate_content > 0 {
+            println!(
+                "Skipped (duplicates): {} ({} by matching content)",
+                self.duplicates, self.duplicate_content
+            );
+        } else {
+            println!("Skipped (duplicates): {}", self.duplicates);
+        }
+        if self.before_cursor > 0 {
+            println!(
+                "Skipped (handled by an earlier run): {}",
+                self.before_cursor
+            );
+        }
+        if let Some(reason) = self.skip_reason {
+            println!("Skipped ({}): {}", reason, self.skipped);
+        }
+
+        if !self.errors.is_empty() {
+            println!("\nErrors encountered: {}", self.errors.len());
+            for (i, error) in self.errors.iter().enumerate() {
+                println!("  {}. {}", i + 1, error);
+            }
+        }
+
+        if self.dry_run {
+            println!("\nDry run: nothing was written to the database");
+        } else if self.imported > 0 {
+            println!("\n✓ Successfully imported {} post(s)", self.imported);
+        } else if self.total == 0 {
+            println!("\nNo {} found", self.items);
+        } else {
+            println!("\nNo new posts to import");
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! Integration tests for Mastodon archive import
+//!
+//! Builds a small archive like the one Mastodon exports, runs
+//! `plur-import mastodon --archive` against it and checks the database.
+
+use libplurcast::db::Database;
+use libplurcast::types::{PostStatus, Visibility};
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+const PUBLIC: &str = "https://www.w3.org/ns/activitystreams#Public";
+
+fn create_note(id: u64, published: &str, content: &str, to: &str, cc: &str) -> serde_json::Value {
+    let uri = format!("https://example.social/users/alice/statuses/{}", id);
+    serde_json::json!({
+        "id": format!("{}/activity", uri),
+        "type": "Create",
+        "published": published,
+        "object": {
+            "id": uri,
+            "type": "Note",
+            "url": format!("https://example.social/@alice/{}", id),
+            "published": published,
+            "content": content,
+            "to": [to],
+            "cc": [cc],
+        }
+    })
+}
+
+/// Write an archive.tar.gz holding outbox.json
+fn create_archive(dir: &Path) -> std::path::PathBuf {
+    let followers = "https://example.social/users/alice/followers";
+    let outbox = serde_json::json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": "outbox.json",
+        "type": "OrderedCollection",
+        "totalItems": 3,
+        "orderedItems": [
+            create_note(1, "2023-05-01T08:00:00Z", "<p>First toot</p>", PUBLIC, followers),
+            create_note(2, "2023-06-01T08:00:00Z", "<p>Quiet toot</p>", followers, PUBLIC),
+            {
+                "id": "https://example.social/users/alice/statuses/3/activity",
+                "type": "Announce",
+                "published": "2023-07-01T08:00:00Z",
+                "object": "https://other.social/users/bob/statuses/9",
+            },
+        ]
+    })
+    .to_string();
+
+    let path = dir.join("archive.tar.gz");
+    let gz = flate2::write::GzEncoder::new(
+        std::fs::File::create(&path).unwrap(),
+        flate2::Compression::default(),
+    );
+    let mut builder = tar::Builder::new(gz);
+    let mut header = tar::Header::new_gnu();
+    header.set_size(outbox.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, "outbox.json", outbox.as_bytes())
+        .unwrap();
+    builder.into_inner().unwrap().finish().unwrap();
+    path
+}
+
+fn create_config(dir: &Path, db_path: &Path) -> std::path::PathBuf {
+    let config_path = dir.join("config.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "[database]\npath = \"{}\"\n",
+            db_path.display().to_string().replace('\\', "/")
+        ),
+    )
+    .unwrap();
+    config_path
+}
+
+fn run_import(config: &Path, archive: &Path) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_plur-import"))
+        .env("PLURCAST_CONFIG", config)
+        .args(["mastodon", "--archive"])
+        .arg(archive)
+        .output()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_import_mastodon_archive() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let config = create_config(temp_dir.path(), &db_path);
+    let archive = create_archive(temp_dir.path());
+
+    let output = run_import(&config, &archive);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Imported: 2"), "stdout: {}", stdout);
+    assert!(stdout.contains("Skipped (boosts and non-posts): 1"));
+
+    let db = Database::new(&db_path.to_string_lossy()).await.unwrap();
+    let post_id = db
+        .get_post_id_by_platform_post_id("mastodon", "1")
+        .await
+        .unwrap()
+        .expect("status 1 should be imported");
+    let post = db.get_post(&post_id).await.unwrap().unwrap();
+    assert_eq!(post.content, "First toot");
+    assert_eq!(post.created_at, 1_682_928_000);
+    assert!(matches!(post.status, PostStatus::Posted));
+
+    let quiet_id = db
+        .get_post_id_by_platform_post_id("mastodon", "2")
+        .await
+        .unwrap()
+        .expect("status 2 should be imported");
+    let quiet = db.get_post(&quiet_id).await.unwrap().unwrap();
+    assert_eq!(
+        Visibility::from_metadata(quiet.metadata.as_deref()),
+        Visibility::Unlisted
+    );
+}
+
+#[tokio::test]
+async fn test_reimport_skips_duplicates() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let config = create_config(temp_dir.path(), &db_path);
+    let archive = create_archive(temp_dir.path());
+
+    assert!(run_import(&config, &archive).status.success());
+    let output = run_import(&config, &archive);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Imported: 0"), "stdout: {}", stdout);
+    assert!(stdout.contains("Skipped (handled by an earlier run): 2"));
+
+    // --full reads every status again and skips them by status ID
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-import"))
+        .env("PLURCAST_CONFIG", &config)
+        .args(["--full", "mastodon", "--archive"])
+        .arg(&archive)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Imported: 0"), "stdout: {}", stdout);
+    assert!(stdout.contains("Skipped (duplicates): 2"));
+}
+
+#[tokio::test]
+async fn test_dry_run_counts_without_writing() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let config = create_config(temp_dir.path(), &db_path);
+    let archive = create_archive(temp_dir.path());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_plur-import"))
+        .env("PLURCAST_CONFIG", &config)
+        .args(["--dry-run", "mastodon", "--archive"])
+        .arg(&archive)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Import Summary (dry run)"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("New (would import): 2"));
+    assert!(stdout.contains("Skipped (boosts and non-posts): 1"));
+
+    let db = Database::new(&db_path.to_string_lossy()).await.unwrap();
+    assert!(db
+        .get_post_id_by_platform_post_id("mastodon", "1")
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_same_text_posted_elsewhere_is_a_duplicate() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let config = create_config(temp_dir.path(), &db_path);
+    let archive = create_archive(temp_dir.path());
+
+    // The first toot, cross-posted by hand to Nostr ten minutes later
+    let db = Database::new(&db_path.to_string_lossy()).await.unwrap();
+    db.create_post(&libplurcast::types::Post {
+        id: "cross-posted".to_string(),
+        content: "First toot".to_string(),
+        created_at: 1_682_928_000 + 600,
+        scheduled_at: None,
+        status: PostStatus::Posted,
+        metadata: None,
+    })
+    .await
+    .unwrap();
+    db.pool().close().await;
+
+    let output = run_import(&config, &archive);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Imported: 1"), "stdout: {}", stdout);
+    assert!(stdout.contains("Skipped (duplicates): 1 (1 by matching content)"));
+}
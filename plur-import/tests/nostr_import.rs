@@ -0,0 +1,42 @@
+//! Integration tests for Nostr relay import
+//!
+//! Fetching from real relays needs network access, so these cover the
+//! checks made before any relay is contacted.
+
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run_import(config_content: &str) -> std::process::Output {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let config_path = temp_dir.path().join("config.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "[database]\npath = \"{}\"\n{}",
+            db_path.display().to_string().replace('\\', "/"),
+            config_content
+        ),
+    )
+    .unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_plur-import"))
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("nostr")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_nostr_import_requires_nostr_config() {
+    let output = run_import("");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Nostr is not configured"));
+}
+
+#[test]
+fn test_nostr_import_requires_relays() {
+    let output = run_import("\n[nostr]\nkeys_file = \"/nonexistent\"\nrelays = []\n");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No Nostr relays configured"));
+}
@@ -0,0 +1,176 @@
+//! Integration tests for RSS/Atom feed import
+
+use libplurcast::db::Database;
+use sqlx::Row;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+const ATOM: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Example Blog</title>
+  <id>urn:uuid:60a76c80-d399-11d9-b93C-0003939e0af6</id>
+  <updated>2024-03-02T12:00:00Z</updated>
+  <entry>
+    <title>Second post</title>
+    <link href="https://example.com/second"/>
+    <id>urn:uuid:2</id>
+    <updated>2024-03-02T12:00:00Z</updated>
+    <summary>More news</summary>
+  </entry>
+  <entry>
+    <title>First post</title>
+    <link href="https://example.com/first"/>
+    <id>urn:uuid:1</id>
+    <updated>2024-03-01T12:00:00Z</updated>
+    <summary>Some news</summary>
+  </entry>
+</feed>"#;
+
+fn setup() -> (
+    TempDir,
+    std::path::PathBuf,
+    std::path::PathBuf,
+    std::path::PathBuf,
+) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let config_path = temp_dir.path().join("config.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "[database]\npath = \"{}\"\n",
+            db_path.display().to_string().replace('\\', "/")
+        ),
+    )
+    .unwrap();
+    let feed_path = temp_dir.path().join("feed.xml");
+    std::fs::write(&feed_path, ATOM).unwrap();
+    (temp_dir, config_path, db_path, feed_path)
+}
+
+fn run_import(config: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_plur-import"))
+        .env("PLURCAST_CONFIG", config)
+        .arg("rss")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+async fn posts(db_path: &Path) -> Vec<(String, String, i64)> {
+    let db = Database::new(&db_path.to_string_lossy()).await.unwrap();
+    sqlx::query("SELECT content, status, created_at FROM posts ORDER BY created_at, rowid")
+        .fetch_all(db.pool())
+        .await
+        .unwrap()
+        .iter()
+        .map(|row| (row.get(0), row.get(1), row.get(2)))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_rss_import_drafts_and_skip_seen_entries() {
+    let (_temp_dir, config, db_path, feed) = setup();
+    let feed = feed.to_string_lossy().to_string();
+
+    let output = run_import(&config, &[&feed]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Imported: 2"));
+
+    let imported = posts(&db_path).await;
+    assert_eq!(imported.len(), 2);
+    assert_eq!(imported[0].0, "First post\n\nhttps://example.com/first");
+    assert!(imported.iter().all(|(_, status, _)| status == "draft"));
+
+    // A second run finds nothing new, even with another template
+    let output = run_import(&config, &[&feed, "--template", "{{summary}}"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Imported: 0"), "stdout: {}", stdout);
+    assert!(stdout.contains("Skipped (handled by an earlier run): 2"));
+    assert_eq!(posts(&db_path).await.len(), 2);
+
+    // Reading the whole feed again still skips the entries by GUID
+    let output = run_import(&config, &[&feed, "--full"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Imported: 0"), "stdout: {}", stdout);
+    assert!(stdout.contains("Skipped (duplicates): 2"));
+    assert_eq!(posts(&db_path).await.len(), 2);
+}
+
+#[tokio::test]
+async fn test_rss_import_resumes_after_newest_entry() {
+    let (temp_dir, config, db_path, feed) = setup();
+    let feed = feed.to_string_lossy().to_string();
+    assert!(run_import(&config, &[&feed]).status.success());
+
+    let third = r#"<entry>
+    <title>Third post</title>
+    <link href="https://example.com/third"/>
+    <id>urn:uuid:3</id>
+    <updated>2024-03-03T12:00:00Z</updated>
+  </entry>
+  <entry>
+    <title>Second post</title>"#;
+    std::fs::write(
+        temp_dir.path().join("feed.xml"),
+        ATOM.replacen("<entry>\n    <title>Second post</title>", third, 1),
+    )
+    .unwrap();
+
+    let output = run_import(&config, &[&feed]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Imported: 1"), "stdout: {}", stdout);
+    assert!(stdout.contains("Skipped (handled by an earlier run): 2"));
+    assert_eq!(
+        posts(&db_path).await.last().unwrap().0,
+        "Third post\n\nhttps://example.com/third"
+    );
+}
+
+#[tokio::test]
+async fn test_rss_import_as_posted_with_template() {
+    let (_temp_dir, config, db_path, feed) = setup();
+    let feed = feed.to_string_lossy().to_string();
+
+    let output = run_import(
+        &config,
+        &[
+            &feed,
+            "--as",
+            "posted",
+            "--template",
+            "{{summary}} {{link}}",
+        ],
+    );
+    assert!(output.status.success());
+
+    let imported = posts(&db_path).await;
+    assert_eq!(
+        imported[0],
+        (
+            "Some news https://example.com/first".to_string(),
+            "posted".to_string(),
+            1_709_294_400
+        )
+    );
+}
+
+#[test]
+fn test_rss_import_rejects_unknown_template_field() {
+    let (_temp_dir, config, _db_path, feed) = setup();
+
+    let output = run_import(
+        &config,
+        &[&feed.to_string_lossy(), "--template", "{{author}}"],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown field in --template"));
+}
@@ -26,6 +26,7 @@ fn create_test_config(temp_dir: &TempDir) -> Config {
     Config {
         database: DatabaseConfig {
             path: db_path.to_string_lossy().to_string(),
+            encrypt_content: false,
         },
         ssb: Some(SSBConfig {
             enabled: true,
@@ -36,6 +37,7 @@ fn create_test_config(temp_dir: &TempDir) -> Config {
             storage: "plain".to_string(),
             path: creds_path.to_string_lossy().to_string(),
             master_password: None,
+            commands: Default::default(),
         }),
         nostr: None,
         mastodon: None,
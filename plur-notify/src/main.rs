@@ -0,0 +1,329 @@
+//! plur-notify - Report new mentions and replies to your posts
+//!
+//! Unix-style tool: checks Nostr and Mastodon for posts mentioning you,
+//! prints each new one as a JSON line (with the Plurcast ID of the post it
+//! replies to) and optionally runs a hook for it. Runs once, or keeps
+//! watching with --watch.
+
+use clap::Parser;
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use libplurcast::platforms::TimelineKind;
+use libplurcast::service::timeline::{Mention, TimelineRequest, DEFAULT_LIMIT};
+use libplurcast::service::PlurcastService;
+use libplurcast::{Config, PlurcastError, Result};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tracing::{info, warn};
+
+#[derive(Parser, Debug)]
+#[command(name = "plur-notify")]
+#[command(version)]
+#[command(about = "Report new mentions and replies to your posts")]
+#[command(long_about = "\
+plur-notify - Report new mentions and replies to your posts
+
+DESCRIPTION:
+    plur-notify reads posts mentioning you on Nostr and Mastodon (as
+    plur-timeline --mentions does) and reports the ones it hasn't reported
+    before, oldest first, one JSON object per line. Replies to posts in your
+    history carry the post's Plurcast ID in \"post_id\".
+
+    Each mention is reported once: what has been seen is kept in the
+    database, so plur-notify can run from cron or with --watch.
+
+HOOKS:
+    --hook runs a shell command for each new mention, with the mention as
+    JSON on stdin and these environment variables:
+
+        PLURCAST_MENTION_PLATFORM   nostr or mastodon
+        PLURCAST_MENTION_ID         ID of the mentioning post
+        PLURCAST_MENTION_AUTHOR     Author handle
+        PLURCAST_MENTION_CONTENT    Text of the post
+        PLURCAST_MENTION_URL        Web link, if any
+        PLURCAST_MENTION_POST_ID    Plurcast ID of your post it replies to, if any
+
+USAGE EXAMPLES:
+    # Skip what's already there, then report what's new from now on
+    plur-notify --mark-seen
+    plur-notify
+
+    # Desktop notification for every reply to one of your posts
+    plur-notify --watch --replies-only \\
+        --hook 'notify-send \"Reply from $PLURCAST_MENTION_AUTHOR\" \"$PLURCAST_MENTION_CONTENT\"'
+
+    # Forward mentions to a webhook
+    plur-notify --hook 'curl -s -H \"Content-Type: application/json\" -d @- https://example.com/hook'
+
+EXIT CODES:
+    0 - Success
+    1 - A platform couldn't be read or a hook failed
+    2 - Authentication error
+    3 - Invalid input (no platform with mentions, etc.)
+
+For more information, visit: https://github.com/plurcast/plurcast
+")]
+struct Cli {
+    /// Keep checking for new mentions until interrupted
+    #[arg(short, long)]
+    watch: bool,
+
+    /// Seconds between checks with --watch
+    #[arg(long, default_value_t = 60, value_name = "SECONDS")]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    interval: u64,
+
+    /// Shell command to run for each new mention
+    #[arg(long, value_name = "COMMAND")]
+    hook: Option<String>,
+
+    /// Only report replies to your own posts
+    #[arg(long)]
+    replies_only: bool,
+
+    /// Record the current mentions as seen without reporting them
+    #[arg(long, conflicts_with = "watch")]
+    mark_seen: bool,
+
+    /// Only check these platforms (comma-separated)
+    #[arg(short, long, value_name = "PLATFORM", value_delimiter = ',')]
+    platform: Option<Vec<String>>,
+
+    /// Account to check (default: the active account)
+    #[arg(long, value_name = "ACCOUNT")]
+    account: Option<String>,
+
+    /// Most recent mentions to read from each platform per check
+    #[arg(short = 'n', long, default_value_t = DEFAULT_LIMIT as u64, value_name = "N")]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    limit: u64,
+
+    /// Verbose output
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT"
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL"
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    match run(cli).await {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
+
+/// Check for mentions once, or until interrupted with --watch
+///
+/// Returns whether every check read every platform and every hook
+/// succeeded. With --watch, only a failure on the first check is fatal;
+/// later ones are logged and the next check tries again.
+async fn run(cli: Cli) -> Result<bool> {
+    let config = Config::load()?;
+    let service = PlurcastService::from_config(config).await?;
+
+    let request = TimelineRequest {
+        kind: TimelineKind::Mentions,
+        platforms: cli.platform.clone(),
+        account: cli.account.clone(),
+        limit: cli.limit as usize,
+    };
+
+    let mut ok = check(&cli, &service, &request).await?;
+    if !cli.watch {
+        return Ok(ok);
+    }
+
+    info!("Checking for mentions every {}s", cli.interval);
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                info!("Interrupted, stopping");
+                return Ok(ok);
+            }
+            _ = tokio::time::sleep(Duration::from_secs(cli.interval)) => {}
+        }
+
+        match check(&cli, &service, &request).await {
+            Ok(checked) => ok &= checked,
+            Err(e) => {
+                warn!("Check failed: {}", e);
+                ok = false;
+            }
+        }
+    }
+}
+
+/// Read mentions and report the new ones
+async fn check(cli: &Cli, service: &PlurcastService, request: &TimelineRequest) -> Result<bool> {
+    let timeline = service.timeline().fetch(request).await?;
+    if timeline.platforms.is_empty() && timeline.failures.is_empty() {
+        return Err(PlurcastError::InvalidInput(
+            "No enabled platform has mentions to read. Enable Nostr or Mastodon in the config"
+                .to_string(),
+        ));
+    }
+    let mut ok = timeline.failures.is_empty();
+    for failure in &timeline.failures {
+        warn!("Could not read {}: {}", failure.platform, failure.error);
+    }
+
+    let mentions = service.timeline().new_mentions(&timeline.entries).await?;
+    if cli.mark_seen {
+        info!("Marked {} mention(s) as seen", mentions.len());
+        return Ok(ok);
+    }
+
+    for mention in &mentions {
+        if cli.replies_only && mention.post_id.is_none() {
+            continue;
+        }
+        println!("{}", serde_json::to_string(mention).unwrap_or_default());
+        if let Some(ref hook) = cli.hook {
+            if let Err(e) = run_hook(hook, mention).await {
+                warn!("Hook failed for {}: {}", mention.entry.id, e);
+                ok = false;
+            }
+        }
+    }
+
+    Ok(ok)
+}
+
+/// Run the hook for a mention, with the mention as JSON on stdin
+async fn run_hook(command: &str, mention: &Mention) -> std::result::Result<(), String> {
+    let mut process = if cfg!(windows) {
+        let mut process = tokio::process::Command::new("cmd");
+        process.arg("/C").arg(command);
+        process
+    } else {
+        let mut process = tokio::process::Command::new("sh");
+        process.arg("-c").arg(command);
+        process
+    };
+
+    let entry = &mention.entry;
+    let mut child = process
+        .env("PLURCAST_MENTION_PLATFORM", &entry.platform)
+        .env("PLURCAST_MENTION_ID", &entry.id)
+        .env("PLURCAST_MENTION_AUTHOR", &entry.author)
+        .env("PLURCAST_MENTION_CONTENT", &entry.content)
+        .env("PLURCAST_MENTION_URL", entry.url.as_deref().unwrap_or(""))
+        .env(
+            "PLURCAST_MENTION_POST_ID",
+            mention.post_id.as_deref().unwrap_or(""),
+        )
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run '{}': {}", command, e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let json = serde_json::to_string(mention).unwrap_or_default();
+        // A hook that doesn't read stdin closes it early; that's fine
+        let _ = stdin.write_all(json.as_bytes()).await;
+    }
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| format!("Failed to run '{}': {}", command, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("'{}' exited with {}", command, status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libplurcast::platforms::TimelineEntry;
+
+    fn mention() -> Mention {
+        Mention {
+            entry: TimelineEntry {
+                platform: "mastodon".to_string(),
+                kind: TimelineKind::Mentions,
+                id: "111".to_string(),
+                author: "bob@example.social".to_string(),
+                author_name: None,
+                content: "@alice Nice post!".to_string(),
+                content_warning: None,
+                created_at: 1_709_294_400,
+                url: Some("https://example.social/@bob/111".to_string()),
+                reply_to: Some("109".to_string()),
+            },
+            post_id: Some("550e8400-e29b-41d4-a716-446655440000".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_mention_json_is_flat() {
+        let json = serde_json::to_value(mention()).unwrap();
+        assert_eq!(json["platform"], "mastodon");
+        assert_eq!(json["kind"], "mentions");
+        assert_eq!(json["reply_to"], "109");
+        assert_eq!(json["post_id"], "550e8400-e29b-41d4-a716-446655440000");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_hook_passes_mention() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let out = temp_dir.path().join("hook.txt");
+        let command = format!(
+            "echo \"$PLURCAST_MENTION_AUTHOR $PLURCAST_MENTION_POST_ID\" > '{}'; cat >> '{}'",
+            out.display(),
+            out.display()
+        );
+
+        run_hook(&command, &mention()).await.unwrap();
+
+        let written = std::fs::read_to_string(&out).unwrap();
+        assert!(written.starts_with("bob@example.social 550e8400-e29b-41d4-a716-446655440000\n"));
+        assert!(written.contains("\"id\":\"111\""));
+
+        assert!(run_hook("exit 3", &mention()).await.is_err());
+    }
+}
@@ -0,0 +1,50 @@
+//! Integration tests for plur-notify
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Write a config file with no platforms enabled
+fn write_config(dir: &Path) -> PathBuf {
+    let config_path = dir.join("config.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "[database]\npath = \"{}\"\n\n[defaults]\nplatforms = []\n",
+            dir.join("posts.db").to_string_lossy().replace('\\', "/")
+        ),
+    )
+    .unwrap();
+    config_path
+}
+
+fn plur_notify(config_path: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("plur-notify").unwrap();
+    cmd.env("PLURCAST_CONFIG", config_path)
+        .env_remove("PLURCAST_DB_PATH");
+    cmd
+}
+
+#[test]
+fn test_no_platforms_is_invalid_input() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = write_config(temp_dir.path());
+
+    plur_notify(&config_path)
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("No enabled platform has mentions"));
+}
+
+#[test]
+fn test_mark_seen_conflicts_with_watch() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = write_config(temp_dir.path());
+
+    plur_notify(&config_path)
+        .args(["--mark-seen", "--watch"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
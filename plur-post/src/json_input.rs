@@ -0,0 +1,152 @@
+//! Structured input for `--input json`
+//!
+//! A single JSON document on stdin carries the post and its options, so
+//! scripts don't have to quote content and flags for a shell:
+//!
+//! ```json
+//! {
+//!   "content": "Release 1.2.0 is out!",
+//!   "platforms": ["nostr", "mastodon"],
+//!   "schedule": "2h",
+//!   "cw": "Release notes",
+//!   "media": ["shot.png", {"path": "chart.png", "alt": "Downloads per day"}],
+//!   "reply_to": "550e8400-e29b-41d4-a716-446655440000",
+//!   "labels": ["release"],
+//!   "account": "work",
+//!   "visibility": "unlisted"
+//! }
+//! ```
+//!
+//! Only `content` is required. Unknown keys are rejected so a misspelt
+//! option fails loudly instead of being dropped.
+
+use std::path::PathBuf;
+
+use libplurcast::{media::MediaFile, PlurcastError, Result, Visibility};
+use serde::Deserialize;
+
+/// A post and its options as read from `--input json`
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct JsonInput {
+    pub content: String,
+    #[serde(default)]
+    pub platforms: Vec<String>,
+    #[serde(default)]
+    pub schedule: Option<String>,
+    #[serde(default)]
+    pub cw: Option<String>,
+    #[serde(default)]
+    pub media: Vec<JsonMedia>,
+    #[serde(default)]
+    pub reply_to: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub account: Option<String>,
+    #[serde(default)]
+    pub visibility: Option<Visibility>,
+}
+
+/// An attachment: a bare path, or a path with alt text
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum JsonMedia {
+    Path(PathBuf),
+    File {
+        path: PathBuf,
+        #[serde(default)]
+        alt: Option<String>,
+    },
+}
+
+impl From<&JsonMedia> for MediaFile {
+    fn from(media: &JsonMedia) -> Self {
+        match media {
+            JsonMedia::Path(path) => MediaFile {
+                path: path.clone(),
+                alt_text: None,
+            },
+            JsonMedia::File { path, alt } => MediaFile {
+                path: path.clone(),
+                alt_text: alt
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|alt| !alt.is_empty())
+                    .map(String::from),
+            },
+        }
+    }
+}
+
+/// Parse a `--input json` document
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if the document isn't valid JSON, has an unknown
+/// key or a value of the wrong type, or lacks `content`.
+pub fn parse(text: &str) -> Result<JsonInput> {
+    serde_json::from_str(text)
+        .map_err(|e| PlurcastError::InvalidInput(format!("Invalid JSON input: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full_document() {
+        let input = parse(
+            r#"{
+                "content": "Hello \"world\"",
+                "platforms": ["nostr", "mastodon"],
+                "schedule": "2h",
+                "cw": "Spoilers",
+                "media": ["a.png", {"path": "b.png", "alt": "A chart"}],
+                "reply_to": "note1abc",
+                "labels": ["release"],
+                "account": "work",
+                "visibility": "unlisted"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(input.content, "Hello \"world\"");
+        assert_eq!(input.platforms, vec!["nostr", "mastodon"]);
+        assert_eq!(input.schedule.as_deref(), Some("2h"));
+        assert_eq!(input.cw.as_deref(), Some("Spoilers"));
+        assert_eq!(input.reply_to.as_deref(), Some("note1abc"));
+        assert_eq!(input.labels, vec!["release"]);
+        assert_eq!(input.account.as_deref(), Some("work"));
+        assert_eq!(input.visibility, Some(Visibility::Unlisted));
+
+        let media: Vec<MediaFile> = input.media.iter().map(MediaFile::from).collect();
+        assert_eq!(media[0].path, PathBuf::from("a.png"));
+        assert_eq!(media[0].alt_text, None);
+        assert_eq!(media[1].path, PathBuf::from("b.png"));
+        assert_eq!(media[1].alt_text.as_deref(), Some("A chart"));
+    }
+
+    #[test]
+    fn test_parse_content_only() {
+        let input = parse(r#"{"content": "Just text"}"#).unwrap();
+        assert_eq!(
+            input,
+            JsonInput {
+                content: "Just text".to_string(),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_documents() {
+        assert!(parse("not json").is_err());
+        assert!(parse(r#"{"platforms": ["nostr"]}"#).is_err());
+        assert!(parse(r#"{"content": "x", "platfroms": ["nostr"]}"#)
+            .unwrap_err()
+            .to_string()
+            .contains("unknown field"));
+        assert!(parse(r#"{"content": "x", "visibility": "friends"}"#).is_err());
+    }
+}
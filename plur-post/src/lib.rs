@@ -0,0 +1,2999 @@
+//! plur-post - Post content to decentralized social platforms
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::io::{self, BufRead, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+use serde_json::json;
+
+use libplurcast::{
+    config::{resolve_db_path, Config},
+    db::Database,
+    frontmatter,
+    logging::{LogFormat, LoggingConfig},
+    media::MediaFile,
+    platforms::id_detection::detect_platform_from_id,
+    service::{
+        posting::{PostRequest, PostResponse},
+        validation::ValidationRequest,
+        PlatformResult, PlurcastService,
+    },
+    templates, Attachment, PlurcastError, Result, Visibility,
+};
+
+mod json_input;
+
+/// Maximum content length in bytes (100KB)
+///
+/// This limit prevents memory exhaustion and DoS attacks while allowing
+/// for long-form posts. Most social platforms have much lower limits:
+/// - Nostr: ~32KB practical limit
+/// - Mastodon: 500 characters default (configurable)
+/// - SSB: No hard limit (practical limit ~8KB per message)
+///
+/// 100KB provides headroom for future features while protecting against abuse.
+/// This addresses security issue H2: Missing Input Validation on Content Length.
+///
+/// Rationale for 100,000 bytes:
+/// - Sufficient for very long posts (≈50,000 words)
+/// - Well above any platform's actual limits
+/// - Small enough to prevent memory exhaustion attacks
+/// - Easy to remember and document
+/// - Prevents DoS via unbounded input streams (e.g., `cat /dev/zero | plur-post`)
+const MAX_CONTENT_LENGTH: usize = 100_000;
+
+/// Maximum size of an `--input json` document in bytes
+///
+/// Twice the content limit, leaving room for escaping and options; the
+/// content inside is still held to MAX_CONTENT_LENGTH.
+const MAX_JSON_INPUT_LENGTH: usize = 2 * MAX_CONTENT_LENGTH;
+
+/// Maximum length for a single thread part (in characters)
+/// Set to 450 to leave room for potential link shortening and be safely under Mastodon's 500 limit
+const MAX_THREAD_PART_LENGTH: usize = 450;
+
+/// Time gap between scheduled thread parts (60 seconds)
+const THREAD_SCHEDULE_GAP_SECS: i64 = 60;
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "plur-post")]
+#[command(version)]
+#[command(about = "Post content to decentralized social platforms")]
+#[command(long_about = "\
+plur-post - Post content to decentralized social platforms
+
+DESCRIPTION:
+    plur-post is a Unix-style tool for posting content to decentralized social
+    media platforms like Nostr, Mastodon, and SSB. It follows Unix philosophy:
+    reads from stdin or arguments, outputs to stdout, and uses meaningful exit codes.
+
+USAGE EXAMPLES:
+    # Post from command line argument
+    plur-post \"Hello decentralized world!\"
+
+    # Post from stdin (pipe)
+    echo \"Hello from stdin\" | plur-post
+    cat message.txt | plur-post
+
+    # Post to all enabled platforms (from config defaults)
+    plur-post \"Multi-platform post\"
+
+    # Post to specific platform only
+    plur-post \"Nostr-only post\" --platform nostr
+
+    # Post to multiple specific platforms
+    plur-post \"Selective post\" --platform nostr --platform mastodon
+
+    # Save as draft without posting
+    echo \"Draft content\" | plur-post --draft
+
+    # Mastodon at 9:00, Nostr at 12:00 (plur-send sends each on time)
+    plur-post \"Launch!\" --schedule-for mastodon=9:00 --schedule-for nostr=12:00
+
+    # Post or schedule a markdown file; frontmatter sets platforms, schedule, tags, cw, account
+    plur-post --from-file posts/launch.md
+
+    # Every .md file in a directory, one post each
+    plur-post --from-file posts/
+
+    # Hide the post behind a content warning
+    plur-post \"Season finale thoughts\" --cw \"Spoilers\"
+
+    # Followers-only post on Mastodon
+    plur-post \"Just for you all\" --platform mastodon --visibility private
+
+    # Attach images, with alt text after the colon
+    plur-post \"New bike!\" --media bike.jpg:\"A red bicycle\" --media map.png
+
+    # Label posts to find them later (plur-history --label campaign:launch)
+    plur-post \"We're live!\" --label campaign:launch --label series:announcements
+
+    # See what would be sent without posting anything
+    plur-post \"Long announcement...\" --auto-thread --dry-run
+
+    # Post a template with variables (~/.config/plurcast/templates/release.md)
+    plur-post --template release --var version=1.2.0
+
+    # Content and options as one JSON document on stdin
+    echo '{\"content\": \"Hello\", \"platforms\": [\"nostr\"], \"cw\": \"Test\"}' | plur-post --input json
+
+    # Tag the post's language (or set [defaults] language in the config)
+    plur-post \"Guten Morgen!\" --lang de
+
+    # Add hashtags (appended on Mastodon, 't' tags on Nostr)
+    plur-post \"New release\" --tag rust --tag release
+
+    # Write the post in $EDITOR (an empty buffer aborts)
+    plur-post --edit
+
+    # Preview and answer y/N before anything goes out
+    plur-post \"Big announcement\" --platform nostr --platform mastodon --confirm
+
+    # Succeed if at least one platform took the post
+    plur-post \"Hello\" --platform nostr --platform mastodon --require any
+
+    # Post to one platform at a time, stopping at the first failure
+    plur-post \"Announcement\" --platform mastodon --platform nostr --fail-fast
+
+    # Post one document per line, with a JSON result per line
+    cat posts.ndjson | plur-post --batch
+
+    # Ask a question, open for two days
+    plur-post \"Tabs or spaces?\" --poll \"Tabs|Spaces\" --poll-duration 2d
+
+    # Post something that deletes itself after two hours
+    plur-post \"Flash sale until noon\" --expires 2h
+
+    # Fail unless at least 3 Nostr relays accept the note
+    plur-post \"Important update\" --platform nostr --wait-relays 3
+
+    # Get machine-readable JSON output
+    plur-post \"Test post\" --format json
+
+    # Enable verbose logging for debugging
+    plur-post \"Debug post\" --verbose
+
+    # Unix composability examples
+    fortune | plur-post --platform nostr
+    echo \"Status: $(date)\" | plur-post
+    cat draft.txt | sed 's/foo/bar/g' | plur-post
+
+CONFIGURATION:
+    Configuration file: ~/.config/plurcast/config.toml
+    Database location: ~/.local/share/plurcast/posts.db
+    
+    Override with environment variables:
+        PLURCAST_CONFIG    - Path to config file
+        PLURCAST_DB_PATH   - Path to database file
+
+EXIT CODES:
+    0 - Success on all platforms
+    1 - Posting failed on at least one platform
+    2 - Authentication error (missing/invalid credentials)
+    3 - Invalid input (empty content, malformed arguments)
+
+OUTPUT FORMAT:
+    Text format (default): platform:post_id (one per line)
+        Example: nostr:note1abc123...
+    
+    JSON format (--format json): Machine-readable JSON array
+        Example: [{\"platform\":\"nostr\",\"success\":true,\"post_id\":\"note1...\"}]
+
+For more information, visit: https://github.com/plurcast/plurcast
+")]
+struct Cli {
+    /// Content to post (reads from stdin if not provided)
+    #[arg(value_name = "CONTENT")]
+    content: Option<String>,
+
+    /// Post from a file (or every .md file in a directory) with optional frontmatter
+    #[arg(long = "from-file", value_name = "PATH", conflicts_with = "content")]
+    #[arg(
+        help = "Read the post from a markdown file instead of CONTENT or stdin. YAML (---) or TOML (+++) frontmatter can set platforms, schedule, tags (added as labels), cw and account; flags given on the command line take precedence. With a directory, each .md file in it is posted as its own post, in file name order."
+    )]
+    from_file: Option<PathBuf>,
+
+    /// Read the post and its options from stdin as JSON
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        default_value = "text",
+        value_parser = ["text", "json"],
+        conflicts_with_all = ["content", "from_file", "template"]
+    )]
+    #[arg(
+        help = "How to read stdin: 'text' (default, the post content) or 'json', a single document with \"content\" and optional \"platforms\", \"schedule\", \"cw\", \"media\" (paths or {\"path\", \"alt\"}), \"reply_to\", \"labels\", \"account\" and \"visibility\". Flags given on the command line take precedence."
+    )]
+    input: String,
+
+    /// Post one JSON document per stdin line, printing one JSON result per line
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "content", "from_file", "template", "edit", "input", "auto_thread",
+            "dry_run", "confirm", "schedule_for", "poll", "jitter"
+        ]
+    )]
+    #[arg(
+        help = "Read posts from stdin as NDJSON: one --input json document per line, each posted on its own. One JSON result is printed per line as it finishes, with \"line\", \"status\" (posted, failed, scheduled or draft), \"post_id\" and the platform \"results\", or an \"error\" if the line couldn't be posted; later lines are still posted. Flags given on the command line apply to every line and take precedence."
+    )]
+    batch: bool,
+
+    /// Compose the post in $EDITOR
+    #[arg(short, long, conflicts_with_all = ["content", "from_file", "input"])]
+    #[arg(
+        help = "Write the post in $VISUAL or $EDITOR (falling back to vi), starting from the --template if one is given. The saved text is posted; an empty buffer aborts without posting, like git commit."
+    )]
+    edit: bool,
+
+    /// Post a named template (see --var)
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["content", "from_file"])]
+    #[arg(
+        help = "Post the template NAME from the templates directory next to the config file (e.g. ~/.config/plurcast/templates/NAME.md). {{name}} placeholders are filled from --var and the built-ins {{date}}, {{time}}, {{year}} and {{weekday}}. Frontmatter in the template works as with --from-file."
+    )]
+    template: Option<String>,
+
+    /// Set a template variable (can be specified multiple times)
+    #[arg(long, value_name = "KEY=VALUE", requires = "template", value_parser = parse_var)]
+    #[arg(
+        help = "Set a template variable, e.g. --var version=1.2.0. Can be specified multiple times; overrides a built-in of the same name."
+    )]
+    var: Vec<(String, String)>,
+
+    /// Target specific platform(s) (can be specified multiple times)
+    #[arg(short, long, value_name = "PLATFORM")]
+    #[arg(
+        help = "Target specific platform (nostr, mastodon, or ssb). Can be specified multiple times. If not specified, uses default platforms from config."
+    )]
+    #[arg(value_parser = ["nostr", "mastodon", "ssb"])]
+    platform: Vec<String>,
+
+    /// Account to use for posting (uses active account if not specified)
+    #[arg(short, long, value_name = "ACCOUNT")]
+    #[arg(
+        help = "Account to use for posting. If not specified, uses the active account for each platform."
+    )]
+    account: Option<String>,
+
+    /// Proof of Work difficulty for Nostr events (NIP-13)
+    #[arg(long, value_name = "DIFFICULTY")]
+    #[arg(
+        help = "Proof of Work difficulty for Nostr events (NIP-13). Higher values require more computation but provide better spam protection. Recommended: 20-25 (takes 1-5 seconds), maximum: 64. Only applies when posting to Nostr platform."
+    )]
+    nostr_pow: Option<u8>,
+
+    /// Easter egg: require 21e8 pattern in PoW hash (hidden flag)
+    #[arg(long = "21e8", hide = true)]
+    nostr_21e8: bool,
+
+    /// Reply to a specific post (creates a thread)
+    #[arg(long, value_name = "POST_ID")]
+    #[arg(
+        help = "Reply to a specific post to create a thread. Accepts either a plurcast UUID (automatically resolves to platform-specific IDs) or a platform-specific ID (note1... for Nostr, status ID for Mastodon)."
+    )]
+    reply_to: Option<String>,
+
+    /// Automatically split long content into a thread
+    #[arg(long)]
+    #[arg(
+        help = "Automatically split content that exceeds platform limits into a thread. Each part replies to the previous, creating a cohesive thread."
+    )]
+    auto_thread: bool,
+
+    /// Save as draft without posting
+    #[arg(short, long)]
+    #[arg(help = "Save as draft without posting to any platform")]
+    draft: bool,
+
+    /// Show what would be posted without posting
+    #[arg(long, conflicts_with = "draft")]
+    #[arg(
+        help = "Print exactly what would be sent to each platform (thread parts, character counts against each platform's limit, schedule, options and validation warnings) and exit without saving the post or making any network calls"
+    )]
+    dry_run: bool,
+
+    /// Preview the post and ask before posting
+    #[arg(long, conflicts_with_all = ["draft", "dry_run"])]
+    #[arg(
+        help = "Show what will be sent to each platform (as --dry-run does, on stderr) and ask y/N on the terminal before posting or scheduling anything. Anything but y or yes cancels."
+    )]
+    confirm: bool,
+
+    /// Require at least N Nostr relays to accept the note
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u16).range(1..),
+        conflicts_with_all = ["draft", "schedule", "schedule_for"]
+    )]
+    #[arg(
+        help = "Only count the Nostr post as a success once at least N relays have answered OK. With fewer, the Nostr result is reported as failed and plur-post exits non-zero (the note stays on the relays that took it). JSON output lists every relay's answer."
+    )]
+    wait_relays: Option<u16>,
+
+    /// Which platforms must succeed for exit code 0: all or any
+    #[arg(
+        long,
+        value_name = "WHICH",
+        default_value = "all",
+        value_parser = ["all", "any"]
+    )]
+    #[arg(
+        help = "When to exit 0: 'all' (default) only if every platform accepted the post, 'any' if at least one did. Failures are still reported either way."
+    )]
+    require: String,
+
+    /// Post to one platform at a time and stop at the first failure
+    #[arg(long, conflicts_with_all = ["draft", "schedule", "schedule_for"])]
+    #[arg(
+        help = "Post to the platforms one at a time, in the order given, and stop at the first failure instead of posting to all of them at once. Platforms that were skipped are reported as failed; a thread stops after the first part that failed anywhere."
+    )]
+    fail_fast: bool,
+
+    /// Schedule post for later (e.g., "30m", "2h", "tomorrow", "random:10m-20m", "best", "cron:0 9 * * MON", "after:<id>+30m")
+    #[arg(short, long, value_name = "TIME")]
+    #[arg(
+        help = "Schedule post for later. Supports duration (\"30m\", \"2h\", \"1d\"), natural language (\"tomorrow\"), random (\"random:10m-20m\"), \"best\" (the hour you usually post, 09:00 without history) a recurring cron expression in local time (\"cron:0 9 * * MON\") or a delay after another queued post is actually sent (\"after:<POST_ID>+30m\")"
+    )]
+    schedule: Option<String>,
+
+    /// Send within this long either side of the scheduled time (e.g., "10m")
+    #[arg(long, value_name = "DURATION", requires = "schedule")]
+    #[arg(
+        help = "Let plur-send post up to this long before or after the scheduled time (e.g. \"10m\"), so posts don't land exactly on the minute. Overrides [scheduling] jitter; \"0s\" disables it."
+    )]
+    jitter: Option<String>,
+
+    /// Schedule for one platform (can be specified multiple times)
+    #[arg(
+        long = "schedule-for",
+        value_name = "PLATFORM=TIME",
+        conflicts_with_all = ["draft", "auto_thread"]
+    )]
+    #[arg(
+        help = "Send to one platform at its own time, e.g. \"mastodon=9:00\" (TIME takes the same formats as --schedule). Can be specified multiple times. Without --platform the post goes to the platforms named here plus, if --schedule is given, the configured defaults at that time."
+    )]
+    schedule_for: Vec<String>,
+
+    /// Content warning shown before the post
+    #[arg(long, value_name = "TEXT")]
+    #[arg(
+        help = "Hide the post behind a content warning, e.g. --cw \"Spoilers\". Sent as Mastodon spoiler text and a Nostr NIP-36 content-warning tag, and shown by plur-history."
+    )]
+    cw: Option<String>,
+
+    /// Who can see the post: public, unlisted, private or direct
+    #[arg(long, value_name = "VISIBILITY", default_value = "public", value_parser = parse_visibility)]
+    #[arg(
+        help = "Who can see the post: 'public' (default), 'unlisted', 'private' (followers only) or 'direct' (mentioned users only). Only Mastodon supports anything but public; posting fails if another target platform can't honor it."
+    )]
+    visibility: Visibility,
+
+    /// Language the post is written in (ISO 639-1, e.g. "de")
+    #[arg(long, value_name = "CODE", value_parser = parse_lang)]
+    #[arg(
+        help = "Language the post is written in, as a two-letter ISO 639-1 code (e.g. \"de\"). Sent as the Mastodon status language and a NIP-32 language label on Nostr. Defaults to [defaults] language in the config."
+    )]
+    lang: Option<String>,
+
+    /// Add a hashtag (can be specified multiple times)
+    #[arg(long, value_name = "TAG", value_parser = parse_tag)]
+    #[arg(
+        help = "Add a hashtag, with or without the '#'. Can be specified multiple times. Appended to the text on Mastodon and SSB unless the text already has it, and sent as NIP-24 't' tags on Nostr."
+    )]
+    tag: Vec<String>,
+
+    /// Delete the post again after this long (e.g. "2h")
+    #[arg(long, value_name = "DURATION", conflicts_with = "draft", value_parser = parse_expires)]
+    #[arg(
+        help = "Make the post ephemeral: it expires this long after it is sent (e.g. \"2h\", \"1d\"). Nostr notes get a NIP-40 expiration tag; on Mastodon plur-send deletes the post when it expires, so plur-send has to be running. Not supported on SSB, whose feeds are append-only."
+    )]
+    expires: Option<i64>,
+
+    /// Attach a poll with choices separated by '|' (e.g. "Yes|No")
+    #[arg(long, value_name = "CHOICES", conflicts_with_all = ["media", "nostr_pow"])]
+    #[arg(
+        help = "Attach a poll to the post, with the choices separated by '|', e.g. --poll \"Tea|Coffee|Water\". Sent as a Mastodon poll and as a NIP-88 poll event on Nostr; posting fails before anything is sent if a target platform can't take it (SSB has no polls, Mastodon allows at most 4 choices of up to 50 characters)."
+    )]
+    poll: Option<String>,
+
+    /// How long the poll stays open (default: 1d)
+    #[arg(long, value_name = "DURATION", requires = "poll")]
+    #[arg(
+        help = "How long the poll stays open after the post is sent, e.g. \"30m\", \"2d\" (default: 1d). Mastodon polls run between 5 minutes and 30 days."
+    )]
+    poll_duration: Option<String>,
+
+    /// Attach an image (can be specified multiple times)
+    #[arg(long, value_name = "PATH[:ALT]", value_parser = parse_media)]
+    #[arg(
+        help = "Attach an image (jpg, png, gif or webp), with optional alt text after a colon, e.g. \"bike.jpg:A red bicycle\". Can be specified multiple times. Posting fails before anything is sent if a target platform can't take the files."
+    )]
+    media: Vec<MediaFile>,
+
+    /// Label the post (can be specified multiple times)
+    #[arg(short, long, value_name = "LABEL")]
+    #[arg(
+        help = "Attach a label to the post for organizing history (e.g. \"campaign:launch\"). Can be specified multiple times. Filter with plur-history --label."
+    )]
+    label: Vec<String>,
+
+    /// Output format: text or json
+    #[arg(short = 'f', long, default_value = "text", value_name = "FORMAT")]
+    #[arg(
+        help = "Output format: 'text' (default, one line per platform) or 'json' (machine-readable array)"
+    )]
+    format: String,
+
+    /// Enable verbose logging to stderr
+    #[arg(short, long)]
+    #[arg(help = "Enable verbose logging to stderr (useful for debugging)")]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT"
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL"
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+#[derive(Debug)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl OutputFormat {
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(PlurcastError::InvalidInput(format!(
+                "Invalid format '{}', must be 'text' or 'json'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Run plur-post with a command line, program name first
+///
+/// The `plur-post` binary and `plur post` both start here.
+#[tokio::main]
+pub async fn main_from(args: Vec<OsString>) {
+    let cli = Cli::parse_from(args);
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3); // Exit code 3 for invalid input
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    // Run the main logic and handle errors
+    if let Err(e) = run(cli).await {
+        eprintln!("Error: {}", e);
+        std::process::exit(e.exit_code());
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    if let Some(name) = cli.template.clone() {
+        return post_template(cli, &name).await;
+    }
+    if cli.edit {
+        let text = compose_in_editor("")?;
+        let mut edited_cli = cli;
+        edited_cli.content = Some(text);
+        return post_content(edited_cli).await;
+    }
+    if cli.input == "json" {
+        return post_json_input(cli).await;
+    }
+    if cli.batch {
+        return post_batch(cli).await;
+    }
+
+    match cli.from_file.clone() {
+        Some(path) => run_from_files(cli, &path).await,
+        None => post_content(cli).await,
+    }
+}
+
+/// Post each file under `--from-file`, with its frontmatter filling in flags
+///
+/// Every file is read and its frontmatter checked before anything is posted,
+/// so a mistake in one file doesn't leave the others half-sent.
+async fn run_from_files(cli: Cli, path: &Path) -> Result<()> {
+    let mut posts = Vec::new();
+    for file in collect_post_files(path)? {
+        let text = std::fs::read_to_string(&file).map_err(|e| {
+            PlurcastError::InvalidInput(format!("Failed to read {}: {}", file.display(), e))
+        })?;
+        let (fm, body) = frontmatter::parse(&text).map_err(|e| match e {
+            PlurcastError::InvalidInput(msg) => {
+                PlurcastError::InvalidInput(format!("{}: {}", file.display(), msg))
+            }
+            other => other,
+        })?;
+        posts.push((file, cli_for_file(&cli, &fm, body)?));
+    }
+
+    for (file, file_cli) in posts {
+        if cli.verbose {
+            eprintln!("Posting {}", file.display());
+        }
+        post_content(file_cli).await?;
+    }
+
+    Ok(())
+}
+
+/// Render a `--template` and post it
+///
+/// Variables are substituted before the frontmatter is read, so they can be
+/// used there too (e.g. in `tags`).
+async fn post_template(cli: Cli, name: &str) -> Result<()> {
+    let vars: HashMap<String, String> = cli.var.iter().cloned().collect();
+    let mut rendered = templates::render(&templates::load(name)?, &vars, chrono::Local::now())?;
+    if cli.edit {
+        rendered = compose_in_editor(&rendered)?;
+    }
+    let (fm, body) = frontmatter::parse(&rendered).map_err(|e| match e {
+        PlurcastError::InvalidInput(msg) => {
+            PlurcastError::InvalidInput(format!("template '{}': {}", name, msg))
+        }
+        other => other,
+    })?;
+
+    post_content(cli_for_file(&cli, &fm, body)?).await
+}
+
+/// Read an `--input json` document from stdin and post it
+async fn post_json_input(cli: Cli) -> Result<()> {
+    let stdin = io::stdin();
+    if stdin.is_terminal() {
+        return Err(PlurcastError::InvalidInput(
+            "--input json reads a JSON document from stdin".to_string(),
+        ));
+    }
+
+    let mut buffer = String::new();
+    stdin
+        .lock()
+        .take((MAX_JSON_INPUT_LENGTH + 1) as u64)
+        .read_to_string(&mut buffer)
+        .map_err(|e| PlurcastError::InvalidInput(format!("Failed to read from stdin: {}", e)))?;
+    if buffer.len() > MAX_JSON_INPUT_LENGTH {
+        return Err(PlurcastError::InvalidInput(format!(
+            "JSON input too large: exceeds {} bytes",
+            MAX_JSON_INPUT_LENGTH
+        )));
+    }
+
+    let input = json_input::parse(&buffer)?;
+    post_content(cli_for_json_input(&cli, input)?).await
+}
+
+/// Post each line of NDJSON on stdin, printing one JSON result per line
+///
+/// Lines are posted one after another and each result is printed as soon as
+/// the line is done, so a pipeline can follow along. A line that can't be
+/// parsed or posted gets an `"error"` result and the following lines still
+/// go out; blank lines are skipped. The exit code is the first non-zero code
+/// any line would have had on its own.
+async fn post_batch(cli: Cli) -> Result<()> {
+    let stdin = io::stdin();
+    if stdin.is_terminal() {
+        return Err(PlurcastError::InvalidInput(
+            "--batch reads one JSON document per line from stdin".to_string(),
+        ));
+    }
+    if cli.schedule.is_some() && cli.draft {
+        return Err(PlurcastError::InvalidInput(
+            "cannot use --schedule with --draft".to_string(),
+        ));
+    }
+
+    let config = Config::load()?;
+    let service = PlurcastService::from_config(config.clone()).await?;
+
+    let mut reader = stdin.lock();
+    let mut exit_code = 0;
+    let mut line_number = 0;
+    loop {
+        let mut line = String::new();
+        let read = reader
+            .by_ref()
+            .take((MAX_JSON_INPUT_LENGTH + 1) as u64)
+            .read_line(&mut line)
+            .map_err(|e| {
+                PlurcastError::InvalidInput(format!("Failed to read from stdin: {}", e))
+            })?;
+        if read == 0 {
+            break;
+        }
+        line_number += 1;
+        if line.len() > MAX_JSON_INPUT_LENGTH {
+            return Err(PlurcastError::InvalidInput(format!(
+                "Line {} too large: exceeds {} bytes",
+                line_number, MAX_JSON_INPUT_LENGTH
+            )));
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (mut output, code) = match post_batch_line(&service, &config, &cli, &line).await {
+            Ok(posted) => posted,
+            Err(e) => (json!({ "error": e.to_string() }), e.exit_code()),
+        };
+        output["line"] = json!(line_number);
+        println!("{}", output);
+
+        if exit_code == 0 {
+            exit_code = code;
+        }
+    }
+
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
+
+    Ok(())
+}
+
+/// Post one `--batch` line, returning its JSON result and exit code
+async fn post_batch_line(
+    service: &PlurcastService,
+    config: &Config,
+    cli: &Cli,
+    line: &str,
+) -> Result<(serde_json::Value, i32)> {
+    let line_cli = cli_for_json_input(cli, json_input::parse(line)?)?;
+    let content = get_content(&line_cli)?;
+
+    let content_warning = match line_cli.cw.as_deref().map(str::trim) {
+        Some("") => {
+            return Err(PlurcastError::InvalidInput(
+                "--cw cannot be empty".to_string(),
+            ))
+        }
+        cw => cw.map(String::from),
+    };
+
+    let (cron, anchor) = schedule_kind(line_cli.schedule.as_deref())?;
+    if cron.is_some() && !line_cli.media.is_empty() {
+        return Err(PlurcastError::InvalidInput(
+            "cannot use a cron schedule with media".to_string(),
+        ));
+    }
+    let jitter = line_cli
+        .jitter
+        .as_deref()
+        .map(libplurcast::scheduling::parse_jitter)
+        .transpose()?;
+    let poll = parse_poll(&line_cli)?;
+
+    let scheduled_at = match &line_cli.schedule {
+        Some(schedule) => Some(resolve_schedule(service.database(), schedule).await?),
+        None => None,
+    };
+
+    let target_platforms = determine_platforms(&line_cli, config)?;
+    if let Some(poll) = &poll {
+        check_poll_platforms(poll, &target_platforms)?;
+    }
+
+    if line_cli.expires.is_some() {
+        if let Some(platform) = target_platforms
+            .iter()
+            .find(|p| !libplurcast::expiry::supports_expiry(p))
+        {
+            return Err(PlurcastError::InvalidInput(format!(
+                "--expires is not supported on {}: posts there can't be deleted",
+                platform
+            )));
+        }
+    }
+
+    if !line_cli.draft {
+        let validation_response = service.validation().validate(ValidationRequest {
+            content: libplurcast::hashtags::append(&content, &line_cli.tag),
+            platforms: target_platforms.clone(),
+            auto_thread: false,
+            visibility: line_cli.visibility,
+        });
+        if !validation_response.valid {
+            let errors: Vec<String> = validation_response
+                .results
+                .iter()
+                .flat_map(|r| r.errors.iter().cloned())
+                .collect();
+            return Err(PlurcastError::InvalidInput(format!(
+                "Content validation failed:\n{}",
+                errors.join("\n")
+            )));
+        }
+    }
+
+    let (reply_to, target_platforms) = match &line_cli.reply_to {
+        Some(id) if is_uuid(id) => {
+            let platform_ids = service.database().get_platform_post_ids(id).await?;
+            if platform_ids.is_empty() {
+                return Err(PlurcastError::InvalidInput(format!(
+                    "Post ID not found in database: {}",
+                    id
+                )));
+            }
+            (platform_ids, target_platforms)
+        }
+        Some(id) => {
+            resolve_platform_specific_reply_to(
+                id,
+                &target_platforms,
+                service.database(),
+                line_cli.verbose,
+            )
+            .await?
+        }
+        None => (HashMap::new(), target_platforms),
+    };
+
+    let request = PostRequest {
+        content,
+        platforms: target_platforms,
+        draft: line_cli.draft,
+        account: line_cli.account.clone(),
+        scheduled_at,
+        nostr_pow: line_cli.nostr_pow,
+        nostr_21e8: line_cli.nostr_21e8,
+        // Replies to scheduled posts are resolved at send time
+        reply_to: if scheduled_at.is_some() {
+            HashMap::new()
+        } else {
+            reply_to
+        },
+        thread_parent_uuid: None,
+        thread_sequence: None,
+        labels: line_cli.label.clone(),
+        content_warning,
+        visibility: line_cli.visibility,
+        language: line_cli
+            .lang
+            .clone()
+            .or_else(|| config.defaults.language.clone()),
+        hashtags: line_cli.tag.clone(),
+        expires_in: line_cli.expires,
+        poll,
+        fail_fast: line_cli.fail_fast,
+        media: line_cli.media.clone(),
+    };
+    let mut response = service.posting().post(request).await?;
+
+    if line_cli.draft {
+        return Ok((json!({ "status": "draft", "post_id": response.post_id }), 0));
+    }
+    if let Some(scheduled_at) = scheduled_at {
+        let db = service.database();
+        record_schedule(db, &[response.post_id.clone()], anchor.as_ref(), jitter).await?;
+
+        let mut output = json!({
+            "status": "scheduled",
+            "post_id": response.post_id,
+            "scheduled_at": scheduled_at,
+            "scheduled_time": format_scheduled_time(scheduled_at),
+        });
+        if let Some(expression) = cron {
+            let series =
+                libplurcast::recurrence::start_series(db, &response.post_id, expression).await?;
+            output["series_id"] = json!(series.id);
+            output["cron"] = json!(series.cron);
+        }
+        return Ok((output, 0));
+    }
+
+    if let Some(quorum) = line_cli.wait_relays {
+        apply_relay_quorum(&mut response.results, usize::from(quorum));
+    }
+    let code = determine_exit_code(&response.results, &line_cli.require);
+    let results: Vec<_> = response.results.iter().map(result_json).collect();
+    let status = if code == 0 { "posted" } else { "failed" };
+
+    Ok((
+        json!({
+            "status": status,
+            "post_id": response.post_id,
+            "results": results,
+        }),
+        code,
+    ))
+}
+
+/// Flags for a JSON document: command-line flags first, then the document
+fn cli_for_json_input(cli: &Cli, input: json_input::JsonInput) -> Result<Cli> {
+    let mut json_cli = cli.clone();
+    json_cli.content = Some(input.content);
+
+    if json_cli.platform.is_empty() && json_cli.schedule_for.is_empty() {
+        for platform in input.platforms {
+            let platform = platform.to_lowercase();
+            if !["nostr", "mastodon", "ssb"].contains(&platform.as_str()) {
+                return Err(PlurcastError::InvalidInput(format!(
+                    "Invalid platform '{}' in JSON input (expected nostr, mastodon or ssb)",
+                    platform
+                )));
+            }
+            json_cli.platform.push(platform);
+        }
+    }
+
+    if json_cli.schedule.is_none() && !json_cli.draft {
+        json_cli.schedule = input.schedule;
+    }
+    if json_cli.account.is_none() {
+        json_cli.account = input.account;
+    }
+    if json_cli.cw.is_none() {
+        json_cli.cw = input.cw;
+    }
+    if json_cli.reply_to.is_none() {
+        json_cli.reply_to = input.reply_to;
+    }
+    if json_cli.media.is_empty() {
+        json_cli.media = input.media.iter().map(MediaFile::from).collect();
+    }
+    // Public is the flag's default, so only a narrower flag wins
+    if json_cli.visibility == Visibility::Public {
+        json_cli.visibility = input.visibility.unwrap_or_default();
+    }
+    for label in input.labels {
+        if !json_cli.label.contains(&label) {
+            json_cli.label.push(label);
+        }
+    }
+
+    Ok(json_cli)
+}
+
+/// Let the user write the post in their editor, starting from `initial`
+///
+/// An empty (or whitespace-only) buffer aborts the post.
+fn compose_in_editor(initial: &str) -> Result<String> {
+    let text = libplurcast::editor::edit(initial, &format!("plur-post-{}.md", std::process::id()))?;
+    if text.trim().is_empty() {
+        return Err(PlurcastError::InvalidInput(
+            "Aborting post due to empty content".to_string(),
+        ));
+    }
+    Ok(text)
+}
+
+/// The file itself, or the .md files in a directory sorted by name
+fn collect_post_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let entries = std::fs::read_dir(path).map_err(|e| {
+        PlurcastError::InvalidInput(format!("Failed to read {}: {}", path.display(), e))
+    })?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|file| file.is_file())
+        .filter(|file| {
+            matches!(
+                file.extension().and_then(|ext| ext.to_str()),
+                Some("md") | Some("markdown")
+            )
+        })
+        .collect();
+    files.sort();
+
+    if files.is_empty() {
+        return Err(PlurcastError::InvalidInput(format!(
+            "No .md files found in {}",
+            path.display()
+        )));
+    }
+
+    Ok(files)
+}
+
+/// Flags for one file: command-line flags first, then the file's frontmatter
+fn cli_for_file(cli: &Cli, fm: &frontmatter::Frontmatter, body: String) -> Result<Cli> {
+    let mut file_cli = cli.clone();
+    file_cli.from_file = None;
+    file_cli.content = Some(body.trim().to_string());
+
+    if file_cli.platform.is_empty() && file_cli.schedule_for.is_empty() {
+        for platform in &fm.platforms {
+            let platform = platform.to_lowercase();
+            if !["nostr", "mastodon", "ssb"].contains(&platform.as_str()) {
+                return Err(PlurcastError::InvalidInput(format!(
+                    "Invalid platform '{}' in frontmatter (expected nostr, mastodon or ssb)",
+                    platform
+                )));
+            }
+            file_cli.platform.push(platform);
+        }
+    }
+
+    // A draft ignores the file's schedule rather than failing on it
+    if file_cli.schedule.is_none() && !file_cli.draft {
+        file_cli.schedule = fm.schedule.clone();
+    }
+    if file_cli.account.is_none() {
+        file_cli.account = fm.account.clone();
+    }
+    if file_cli.cw.is_none() {
+        file_cli.cw = fm.cw.clone();
+    }
+    for tag in &fm.tags {
+        if !file_cli.label.contains(tag) {
+            file_cli.label.push(tag.clone());
+        }
+    }
+
+    Ok(file_cli)
+}
+
+/// Parse a `--var KEY=VALUE` value
+fn parse_var(spec: &str) -> std::result::Result<(String, String), String> {
+    templates::parse_var(spec).map_err(|e| match e {
+        PlurcastError::InvalidInput(msg) => msg,
+        other => other.to_string(),
+    })
+}
+
+/// Parse a `--lang` value
+fn parse_lang(value: &str) -> std::result::Result<String, String> {
+    libplurcast::language::parse(value).map_err(|e| match e {
+        PlurcastError::InvalidInput(msg) => msg,
+        other => other.to_string(),
+    })
+}
+
+/// Parse a `--tag` value
+fn parse_expires(value: &str) -> std::result::Result<i64, String> {
+    libplurcast::expiry::parse(value).map_err(|e| match e {
+        PlurcastError::InvalidInput(msg) => msg,
+        other => other.to_string(),
+    })
+}
+
+fn parse_tag(value: &str) -> std::result::Result<String, String> {
+    libplurcast::hashtags::parse(value).map_err(|e| match e {
+        PlurcastError::InvalidInput(msg) => msg,
+        other => other.to_string(),
+    })
+}
+
+/// Parse a `--visibility` value
+fn parse_visibility(value: &str) -> std::result::Result<Visibility, String> {
+    Visibility::parse(value).ok_or_else(|| {
+        format!(
+            "invalid visibility '{}' (expected public, unlisted, private or direct)",
+            value
+        )
+    })
+}
+
+/// Parse a `--media PATH[:ALT]` value
+///
+/// A path that exists as written is taken whole, so file names containing
+/// colons still work; otherwise the alt text starts after the first colon
+/// that ends an existing path, or failing that the first colon.
+fn parse_media(spec: &str) -> std::result::Result<MediaFile, String> {
+    let (path, alt) = if Path::new(spec).exists() {
+        (spec, "")
+    } else {
+        spec.match_indices(':')
+            .map(|(i, _)| (&spec[..i], &spec[i + 1..]))
+            .find(|(path, _)| Path::new(path).is_file())
+            .or_else(|| spec.split_once(':'))
+            .unwrap_or((spec, ""))
+    };
+
+    if path.is_empty() {
+        return Err("expected PATH or PATH:ALT".to_string());
+    }
+
+    let alt = alt.trim();
+    Ok(MediaFile {
+        path: PathBuf::from(path),
+        alt_text: if alt.is_empty() {
+            None
+        } else {
+            Some(alt.to_string())
+        },
+    })
+}
+
+/// The recurring and follow-up forms of a `--schedule`: its cron expression
+/// (`cron:...`) and its anchor (`after:<id>+<delay>`), if it is either
+fn schedule_kind(
+    schedule: Option<&str>,
+) -> Result<(Option<&str>, Option<libplurcast::anchor::Anchor>)> {
+    let cron = schedule.and_then(|s| s.strip_prefix(libplurcast::recurrence::CRON_PREFIX));
+    let anchor = schedule
+        .and_then(|s| s.strip_prefix(libplurcast::anchor::ANCHOR_PREFIX))
+        .map(libplurcast::anchor::Anchor::parse)
+        .transpose()?;
+    Ok((cron, anchor))
+}
+
+/// When a `--schedule` first puts a post out
+///
+/// Single posts and `--batch` lines both come through here, so every form
+/// works in both: `cron:` is its next occurrence, `after:<id>+<delay>` is
+/// estimated from when that post goes out, `best` is picked from when
+/// earlier posts went out, and anything else is a time, delay or random
+/// range for `scheduling::parse_schedule`.
+async fn resolve_schedule(db: &Database, schedule: &str) -> Result<i64> {
+    if let Some(expression) = schedule.strip_prefix(libplurcast::recurrence::CRON_PREFIX) {
+        let now = chrono::Utc::now().timestamp();
+        return libplurcast::recurrence::next_occurrence(expression, now);
+    }
+
+    if let Some(spec) = schedule.strip_prefix(libplurcast::anchor::ANCHOR_PREFIX) {
+        let anchor = libplurcast::anchor::Anchor::parse(spec)?;
+        return libplurcast::anchor::estimate(db, &anchor).await;
+    }
+
+    if schedule == libplurcast::scheduling::BEST_SCHEDULE {
+        let now = chrono::Utc::now();
+        let since = now.timestamp() - libplurcast::scheduling::BEST_TIME_HISTORY_DAYS * 86400;
+        let history = db.get_publish_times(since).await?;
+        return Ok(libplurcast::scheduling::best_time(&history, now).timestamp());
+    }
+
+    // Random schedules space themselves out from the last scheduled post
+    let last_scheduled = db.get_last_scheduled_timestamp().await?;
+    Ok(libplurcast::scheduling::parse_schedule(schedule, last_scheduled)?.timestamp())
+}
+
+/// Record what plur-send needs to keep scheduled posts on time
+///
+/// `post_ids` are the parts of one post in thread order. Each part gets the
+/// jitter, and parts following an anchor keep the thread's spacing after it.
+async fn record_schedule(
+    db: &Database,
+    post_ids: &[String],
+    anchor: Option<&libplurcast::anchor::Anchor>,
+    jitter: Option<i64>,
+) -> Result<()> {
+    if anchor.is_none() && jitter.is_none() {
+        return Ok(());
+    }
+
+    for (i, post_id) in post_ids.iter().enumerate() {
+        let post = match db.get_post(post_id).await? {
+            Some(post) => post,
+            None => continue,
+        };
+        let mut metadata = post.metadata.clone();
+        if let Some(jitter) = jitter {
+            metadata = Some(libplurcast::scheduling::with_jitter(
+                metadata.as_deref(),
+                jitter,
+            ));
+        }
+        if let Some(anchor) = anchor {
+            let part_anchor = libplurcast::anchor::Anchor {
+                post_id: anchor.post_id.clone(),
+                delay: anchor.delay + i as i64 * THREAD_SCHEDULE_GAP_SECS,
+            };
+            metadata = Some(libplurcast::anchor::with_anchor(
+                metadata.as_deref(),
+                &part_anchor,
+            ));
+        }
+        if let Some(metadata) = metadata {
+            db.update_post_metadata(&post.id, &metadata).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The database a dry run reads
+///
+/// The real one opened read-only, or an empty one in memory when there is
+/// none yet: a dry run doesn't create or migrate the database. A database
+/// from an older version is refused, as its tables may not have what the
+/// dry run reads.
+async fn dry_run_database(config: &Config) -> Result<Database> {
+    let db_path = resolve_db_path(Some(&config.database.path))?;
+    if !db_path.exists() {
+        return Database::in_memory().await;
+    }
+
+    let db = Database::open_read_only_from_config(config).await?;
+    // A file with no migrations applied isn't a database yet
+    let version = db.schema_version().await.unwrap_or(0);
+    let latest = Database::latest_schema_version();
+    if version == 0 {
+        Database::in_memory().await
+    } else if version < latest {
+        Err(PlurcastError::InvalidInput(format!(
+            "Database {} is at schema version {} and this version of Plurcast needs {}; \
+             a dry run doesn't upgrade it. Run plur-post once without --dry-run first.",
+            db_path.display(),
+            version,
+            latest
+        )))
+    } else {
+        Ok(db)
+    }
+}
+
+/// The `--poll` of the flags, if any
+fn parse_poll(cli: &Cli) -> Result<Option<libplurcast::poll::Poll>> {
+    cli.poll
+        .as_deref()
+        .map(|choices| {
+            libplurcast::poll::parse(
+                choices,
+                cli.poll_duration
+                    .as_deref()
+                    .unwrap_or(libplurcast::poll::DEFAULT_POLL_DURATION),
+            )
+        })
+        .transpose()
+}
+
+/// Check every target can take the poll
+fn check_poll_platforms(poll: &libplurcast::poll::Poll, platforms: &[String]) -> Result<()> {
+    let problems: Vec<String> = platforms
+        .iter()
+        .filter_map(|platform| libplurcast::poll::platform_error(platform, poll))
+        .collect();
+    if !problems.is_empty() {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Cannot attach poll: {}",
+            problems.join("; ")
+        )));
+    }
+    Ok(())
+}
+
+/// Post one piece of content as described by the flags
+async fn post_content(cli: Cli) -> Result<()> {
+    // Validate format parameter first (fail fast on invalid input)
+    let output_format = OutputFormat::from_str(&cli.format)?;
+
+    let content_warning = match cli.cw.as_deref().map(str::trim) {
+        Some("") => {
+            return Err(PlurcastError::InvalidInput(
+                "--cw cannot be empty".to_string(),
+            ))
+        }
+        cw => cw.map(String::from),
+    };
+
+    // Validate --schedule and --draft cannot be used together
+    if cli.schedule.is_some() && cli.draft {
+        return Err(PlurcastError::InvalidInput(
+            "cannot use --schedule with --draft".to_string(),
+        ));
+    }
+
+    // Validate --21e8 requires --nostr-pow
+    if cli.nostr_21e8 && cli.nostr_pow.is_none() {
+        return Err(PlurcastError::InvalidInput(
+            "--21e8 requires --nostr-pow to be specified".to_string(),
+        ));
+    }
+
+    // Recurring schedules repeat a single post; follow-ups are scheduled
+    // relative to when another post goes out
+    let (cron, anchor) = schedule_kind(cli.schedule.as_deref())?;
+    if cron.is_some() && cli.auto_thread {
+        return Err(PlurcastError::InvalidInput(
+            "cannot use a cron schedule with --auto-thread".to_string(),
+        ));
+    }
+    if cron.is_some() && !cli.media.is_empty() {
+        return Err(PlurcastError::InvalidInput(
+            "cannot use a cron schedule with --media".to_string(),
+        ));
+    }
+
+    // Per-platform schedules; a cron or after: --schedule sets one time for all
+    let schedule_for = parse_schedule_for(&cli.schedule_for)?;
+    if !schedule_for.is_empty() && (cron.is_some() || anchor.is_some()) {
+        return Err(PlurcastError::InvalidInput(
+            "cannot use --schedule-for with a cron or after: schedule".to_string(),
+        ));
+    }
+
+    // Validate jitter before anything is written
+    let jitter = cli
+        .jitter
+        .as_deref()
+        .map(libplurcast::scheduling::parse_jitter)
+        .transpose()?;
+
+    // Validate the poll before anything is written
+    let poll = parse_poll(&cli)?;
+
+    // Get content from args or stdin (fail fast on invalid input)
+    let content = get_content(&cli)?;
+
+    // Load configuration (only after input is validated)
+    let config = Config::load()?;
+
+    // Initialize service layer; a dry run only reads the database
+    let service = if cli.dry_run {
+        PlurcastService::with_database(dry_run_database(&config).await?, config.clone())
+    } else {
+        PlurcastService::from_config(config.clone()).await?
+    };
+
+    // Parse schedule time if provided
+    let scheduled_at = match &cli.schedule {
+        Some(schedule) => Some(resolve_schedule(service.database(), schedule).await?),
+        None => None,
+    };
+
+    let language = cli
+        .lang
+        .clone()
+        .or_else(|| config.defaults.language.clone());
+
+    // Determine target platforms
+    let target_platforms = determine_platforms(&cli, &config)?;
+    tracing::info!("Targeting platforms: {}", target_platforms.join(", "));
+
+    // Every target has to be able to take the poll
+    if let Some(poll) = &poll {
+        check_poll_platforms(poll, &target_platforms)?;
+    }
+
+    // Expiring posts have to be deletable on every target
+    if cli.expires.is_some() {
+        let unsupported: Vec<&str> = target_platforms
+            .iter()
+            .map(String::as_str)
+            .filter(|p| !libplurcast::expiry::supports_expiry(p))
+            .collect();
+        if !unsupported.is_empty() {
+            return Err(PlurcastError::InvalidInput(format!(
+                "--expires is not supported on {}: posts there can't be deleted",
+                unsupported.join(", ")
+            )));
+        }
+    }
+
+    // A relay quorum has to be reachable with the configured relays
+    if let Some(quorum) = cli.wait_relays {
+        if !target_platforms.iter().any(|p| p == "nostr") {
+            return Err(PlurcastError::InvalidInput(
+                "--wait-relays only applies when posting to Nostr".to_string(),
+            ));
+        }
+        let relays = config.nostr.as_ref().map_or(0, |nostr| nostr.relays.len());
+        if usize::from(quorum) > relays {
+            return Err(PlurcastError::InvalidInput(format!(
+                "--wait-relays {} is more than the {} Nostr relay(s) configured",
+                quorum, relays
+            )));
+        }
+    }
+
+    // Each platform's time; the post first comes due at the earliest
+    let platform_times = platform_schedule(&schedule_for, scheduled_at, &target_platforms)?;
+    let scheduled_at = platform_times
+        .iter()
+        .map(|(_, at)| *at)
+        .min()
+        .or(scheduled_at);
+
+    // Validate content using ValidationService (skip for draft mode)
+    let mut validation_warnings: HashMap<String, Vec<String>> = HashMap::new();
+    if !cli.draft {
+        // Check the text as Mastodon and SSB will get it, hashtags included
+        let validation_request = ValidationRequest {
+            content: libplurcast::hashtags::append(&content, &cli.tag),
+            platforms: target_platforms.clone(),
+            auto_thread: cli.auto_thread,
+            visibility: cli.visibility,
+        };
+        let validation_response = service.validation().validate(validation_request);
+
+        if !validation_response.valid {
+            let errors: Vec<String> = validation_response
+                .results
+                .iter()
+                .flat_map(|r| r.errors.iter().cloned())
+                .collect();
+            return Err(PlurcastError::InvalidInput(format!(
+                "Content validation failed:\n{}",
+                errors.join("\n")
+            )));
+        }
+
+        for result in validation_response.results {
+            validation_warnings.insert(result.platform, result.warnings);
+        }
+    }
+
+    // Handle auto-threading: split content if needed
+    let thread_parts = if cli.auto_thread {
+        let parts = split_into_thread_parts(&content, MAX_THREAD_PART_LENGTH);
+        if parts.len() > 1 {
+            tracing::info!("Auto-thread: splitting content into {} parts", parts.len());
+            if cli.verbose {
+                eprintln!(
+                    "Auto-thread: splitting into {} parts ({} chars each max)",
+                    parts.len(),
+                    MAX_THREAD_PART_LENGTH
+                );
+            }
+        }
+        parts
+    } else {
+        vec![content]
+    };
+
+    // Track all responses for final output
+    let mut all_responses: Vec<PostResponse> = Vec::new();
+
+    // Resolve reply_to: if it's a UUID, look up platform-specific IDs from database
+    // If it's a platform-specific ID, detect platform and try cross-platform lookup
+    let (mut current_reply_to, target_platforms) = if let Some(ref id) = cli.reply_to {
+        if is_uuid(id) {
+            // Look up platform-specific IDs from database
+            let platform_ids = service.database().get_platform_post_ids(id).await?;
+            if platform_ids.is_empty() {
+                return Err(PlurcastError::InvalidInput(format!(
+                    "Post ID not found in database: {}",
+                    id
+                )));
+            }
+            tracing::debug!("Resolved UUID {} to platform IDs: {:?}", id, platform_ids);
+            (platform_ids, target_platforms)
+        } else {
+            // Platform-specific ID provided - detect platform and resolve
+            resolve_platform_specific_reply_to(
+                id,
+                &target_platforms,
+                service.database(),
+                cli.verbose,
+            )
+            .await?
+        }
+    } else {
+        (HashMap::new(), target_platforms)
+    };
+
+    // Show what would be sent and stop (or ask) before anything is stored or sent
+    if cli.dry_run || cli.confirm {
+        let mut media = Vec::with_capacity(cli.media.len());
+        for file in &cli.media {
+            media.push(
+                libplurcast::media::load_file("dry-run", &file.path, file.alt_text.clone()).await?,
+            );
+        }
+
+        let report = dry_run_report(&DryRun {
+            parts: &thread_parts,
+            platforms: &target_platforms,
+            limits: &service.validation().get_limits(&target_platforms),
+            warnings: &validation_warnings,
+            scheduled_at,
+            platform_times: &platform_times,
+            reply_to: &current_reply_to,
+            content_warning: content_warning.as_deref(),
+            visibility: cli.visibility,
+            language: language.as_deref(),
+            hashtags: &cli.tag,
+            expires_in: cli.expires,
+            poll: poll.as_ref(),
+            labels: &cli.label,
+            media: &media,
+            nostr_pow: cli.nostr_pow,
+        });
+        if cli.dry_run {
+            output_dry_run(&report, &output_format);
+            return Ok(());
+        }
+
+        eprintln!("{}\n", preview_lines(&report).join("\n"));
+        let question = if scheduled_at.is_some() {
+            "Schedule this post?"
+        } else {
+            "Post this?"
+        };
+        // stdin is free for the answer unless the post itself came from it
+        let stdin_used = cli.content.is_none() || cli.input == "json";
+        if !ask_to_confirm(question, stdin_used)? {
+            eprintln!("Cancelled: nothing was posted");
+            return Ok(());
+        }
+    }
+
+    // For scheduled threads: track the previous post's UUID so plur-send can resolve threading
+    // For immediate threads: track platform IDs directly for reply_to
+    let mut previous_post_uuid: Option<String> = None;
+
+    for (part_index, part_content) in thread_parts.iter().enumerate() {
+        // Calculate scheduled time for this part (stagger by 60s for threads)
+        let part_scheduled_at = scheduled_at
+            .map(|base_time| base_time + (part_index as i64 * THREAD_SCHEDULE_GAP_SECS));
+
+        // For scheduled threads, we use UUID chain instead of platform IDs.
+        // The thread_parent_uuid will be resolved to platform IDs at send time by plur-send.
+        // For immediate threads, we use platform IDs directly in reply_to.
+        let is_scheduled = scheduled_at.is_some();
+
+        // Create post request for this part
+        let request = PostRequest {
+            content: part_content.clone(),
+            platforms: target_platforms.clone(),
+            draft: cli.draft,
+            account: cli.account.clone(),
+            scheduled_at: part_scheduled_at,
+            nostr_pow: cli.nostr_pow,
+            nostr_21e8: cli.nostr_21e8,
+            // For immediate posts: use platform IDs. For scheduled: empty (resolved at send time)
+            reply_to: if is_scheduled {
+                HashMap::new()
+            } else {
+                current_reply_to.clone()
+            },
+            // For scheduled threads: store parent's UUID for later resolution
+            thread_parent_uuid: if is_scheduled {
+                previous_post_uuid.clone()
+            } else {
+                None
+            },
+            // Track position in thread for scheduled threads
+            thread_sequence: if is_scheduled && thread_parts.len() > 1 {
+                Some(part_index as u32)
+            } else {
+                None
+            },
+            labels: cli.label.clone(),
+            content_warning: content_warning.clone(),
+            visibility: cli.visibility,
+            language: language.clone(),
+            // Hashtags go with the first part of a thread
+            hashtags: if part_index == 0 {
+                cli.tag.clone()
+            } else {
+                Vec::new()
+            },
+            expires_in: cli.expires,
+            // The poll goes with the first part of a thread
+            poll: if part_index == 0 { poll.clone() } else { None },
+            fail_fast: cli.fail_fast,
+            // Media goes with the first part of a thread
+            media: if part_index == 0 {
+                cli.media.clone()
+            } else {
+                Vec::new()
+            },
+        };
+
+        // Post using PostingService
+        let response = if cli.verbose {
+            if thread_parts.len() > 1 {
+                eprintln!(
+                    "\n--- Thread part {}/{} ---",
+                    part_index + 1,
+                    thread_parts.len()
+                );
+            }
+            post_with_progress(&service, request).await?
+        } else {
+            service.posting().post(request).await?
+        };
+
+        // For threading: track info for next part
+        if part_index < thread_parts.len() - 1 {
+            if is_scheduled {
+                // For scheduled threads: track this post's UUID for next part's parent reference
+                // At send time, plur-send will resolve this UUID to platform-specific IDs
+                previous_post_uuid = Some(response.post_id.clone());
+            } else {
+                // For immediate threads: collect platform IDs for reply_to in next part
+                let mut next_reply_to: HashMap<String, String> = HashMap::new();
+                for result in &response.results {
+                    if result.success {
+                        if let Some(ref post_id) = result.post_id {
+                            next_reply_to.insert(result.platform.clone(), post_id.clone());
+                        }
+                    }
+                }
+                current_reply_to = next_reply_to;
+            }
+        }
+
+        let failed = response.results.iter().any(|r| !r.success);
+        all_responses.push(response);
+
+        // With --fail-fast, don't go on to the next part of a thread
+        if cli.fail_fast && failed {
+            break;
+        }
+    }
+
+    // If draft mode, output draft results and exit
+    if cli.draft {
+        for (i, response) in all_responses.iter().enumerate() {
+            if thread_parts.len() > 1 {
+                output_draft_result_with_part(&response.post_id, &output_format, i + 1);
+            } else {
+                output_draft_result(&response.post_id, &output_format);
+            }
+        }
+        return Ok(());
+    }
+
+    // Record jitter and anchor for plur-send (and for series occurrences)
+    if scheduled_at.is_some() {
+        let post_ids: Vec<String> = all_responses.iter().map(|r| r.post_id.clone()).collect();
+        record_schedule(service.database(), &post_ids, anchor.as_ref(), jitter).await?;
+    }
+
+    // Record per-platform times for plur-send, each thread part a gap later
+    if !platform_times.is_empty() {
+        let db = service.database();
+        for (i, response) in all_responses.iter().enumerate() {
+            let offset = i as i64 * THREAD_SCHEDULE_GAP_SECS;
+            let part_times: Vec<(String, i64)> = platform_times
+                .iter()
+                .map(|(platform, at)| (platform.clone(), at + offset))
+                .collect();
+            db.set_platform_schedules(&response.post_id, &part_times)
+                .await?;
+        }
+    }
+
+    // If recurring, record the series and exit
+    if let (Some(expression), Some(first_at)) = (cron, scheduled_at) {
+        let series = libplurcast::recurrence::start_series(
+            service.database(),
+            &all_responses[0].post_id,
+            expression,
+        )
+        .await?;
+        output_series_result(&series, &all_responses[0].post_id, first_at, &output_format);
+        return Ok(());
+    }
+
+    // If scheduled per platform, output each platform's time and exit
+    if !platform_times.is_empty() {
+        output_platform_schedule_result(&all_responses[0].post_id, &platform_times, &output_format);
+        return Ok(());
+    }
+
+    // If scheduled, output schedule results and exit
+    if let Some(base_time) = scheduled_at {
+        for (i, response) in all_responses.iter().enumerate() {
+            let part_time = base_time + (i as i64 * THREAD_SCHEDULE_GAP_SECS);
+            if thread_parts.len() > 1 {
+                output_schedule_result_with_part(
+                    &response.post_id,
+                    part_time,
+                    &output_format,
+                    i + 1,
+                );
+            } else {
+                output_schedule_result(&response.post_id, part_time, &output_format);
+            }
+        }
+        return Ok(());
+    }
+
+    // Hold Nostr to the relay quorum before reporting anything
+    if let Some(quorum) = cli.wait_relays {
+        for response in &mut all_responses {
+            apply_relay_quorum(&mut response.results, usize::from(quorum));
+        }
+    }
+
+    // Output results for all parts
+    for (i, response) in all_responses.iter().enumerate() {
+        if thread_parts.len() > 1 && !matches!(output_format, OutputFormat::Json) {
+            println!("--- Thread part {}/{} ---", i + 1, thread_parts.len());
+        }
+        output_results(&response.results, &output_format, cli.verbose)?;
+    }
+
+    // Determine exit code (fail if any part failed)
+    let exit_code = all_responses
+        .iter()
+        .map(|r| determine_exit_code(&r.results, &cli.require))
+        .find(|&code| code != 0)
+        .unwrap_or(0);
+
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
+
+    Ok(())
+}
+
+/// Everything `--dry-run` reports about a post that wasn't sent
+struct DryRun<'a> {
+    parts: &'a [String],
+    platforms: &'a [String],
+    limits: &'a HashMap<String, Option<usize>>,
+    warnings: &'a HashMap<String, Vec<String>>,
+    scheduled_at: Option<i64>,
+    platform_times: &'a [(String, i64)],
+    reply_to: &'a HashMap<String, String>,
+    content_warning: Option<&'a str>,
+    visibility: Visibility,
+    language: Option<&'a str>,
+    hashtags: &'a [String],
+    expires_in: Option<i64>,
+    poll: Option<&'a libplurcast::poll::Poll>,
+    labels: &'a [String],
+    media: &'a [Attachment],
+    nostr_pow: Option<u8>,
+}
+
+/// Build the `--dry-run` report
+///
+/// The text output is rendered from this same value, so both formats always
+/// agree on what would have been sent.
+fn dry_run_report(dry_run: &DryRun) -> serde_json::Value {
+    let platforms: Vec<serde_json::Value> = dry_run
+        .platforms
+        .iter()
+        .map(|platform| {
+            let scheduled_at = dry_run
+                .platform_times
+                .iter()
+                .find(|(name, _)| name == platform)
+                .map(|(_, at)| *at)
+                .or(dry_run.scheduled_at);
+            json!({
+                "platform": platform,
+                "character_limit": dry_run.limits.get(platform).copied().flatten(),
+                "scheduled_at": scheduled_at,
+                "scheduled_time": scheduled_at.map(format_scheduled_time),
+                "reply_to": dry_run.reply_to.get(platform),
+                "warnings": dry_run.warnings.get(platform).cloned().unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    let parts: Vec<serde_json::Value> = dry_run
+        .parts
+        .iter()
+        .enumerate()
+        .map(|(i, content)| {
+            json!({
+                "part": i + 1,
+                "content": content,
+                "characters": content.chars().count(),
+            })
+        })
+        .collect();
+
+    let media: Vec<serde_json::Value> = dry_run
+        .media
+        .iter()
+        .map(|attachment| {
+            json!({
+                "path": attachment.file_path,
+                "alt_text": attachment.alt_text,
+                "mime_type": attachment.mime_type.as_str(),
+                "size": attachment.file_size,
+            })
+        })
+        .collect();
+
+    json!({
+        "dry_run": true,
+        "platforms": platforms,
+        "parts": parts,
+        "content_warning": dry_run.content_warning,
+        "visibility": dry_run.visibility,
+        "language": dry_run.language,
+        "hashtags": libplurcast::hashtags::dedup(dry_run.hashtags),
+        "expires_in": dry_run.expires_in,
+        "poll": dry_run.poll,
+        "labels": dry_run.labels,
+        "media": media,
+        "nostr_pow": dry_run.nostr_pow,
+    })
+}
+
+/// Output the `--dry-run` report
+fn output_dry_run(report: &serde_json::Value, format: &OutputFormat) {
+    match format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(report).unwrap());
+        }
+        OutputFormat::Text => {
+            println!("dry-run: nothing was posted");
+            println!("{}", preview_lines(report).join("\n"));
+        }
+    }
+}
+
+/// The text rendering of a `--dry-run` report, also shown by `--confirm`
+fn preview_lines(report: &serde_json::Value) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for platform in report["platforms"].as_array().into_iter().flatten() {
+        let mut details = Vec::new();
+        if let Some(limit) = platform["character_limit"].as_u64() {
+            details.push(format!("limit {} characters", limit));
+        }
+        if let Some(time) = platform["scheduled_time"].as_str() {
+            details.push(format!("scheduled {}", time));
+        }
+        if let Some(reply_to) = platform["reply_to"].as_str() {
+            details.push(format!("reply to {}", reply_to));
+        }
+        let name = platform["platform"].as_str().unwrap_or_default();
+        if details.is_empty() {
+            lines.push(format!("platform: {}", name));
+        } else {
+            lines.push(format!("platform: {} ({})", name, details.join(", ")));
+        }
+        for warning in platform["warnings"].as_array().into_iter().flatten() {
+            lines.push(format!(
+                "  warning: {}",
+                warning.as_str().unwrap_or_default()
+            ));
+        }
+    }
+
+    if let Some(cw) = report["content_warning"].as_str() {
+        lines.push(format!("cw: {}", cw));
+    }
+    if report["visibility"] != "public" {
+        lines.push(format!(
+            "visibility: {}",
+            report["visibility"].as_str().unwrap_or_default()
+        ));
+    }
+    if let Some(language) = report["language"].as_str() {
+        lines.push(format!("language: {}", language));
+    }
+    let hashtags: Vec<String> = report["hashtags"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|tag| tag.as_str().map(|tag| format!("#{}", tag)))
+        .collect();
+    if !hashtags.is_empty() {
+        lines.push(format!("hashtags: {}", hashtags.join(" ")));
+    }
+    if let Some(expires_in) = report["expires_in"].as_i64() {
+        lines.push(format!("expires: {} seconds after sending", expires_in));
+    }
+    if let Some(options) = report["poll"]["options"].as_array() {
+        let options: Vec<&str> = options.iter().filter_map(|o| o.as_str()).collect();
+        lines.push(format!(
+            "poll: {} (open {} seconds)",
+            options.join(" | "),
+            report["poll"]["duration"]
+        ));
+    }
+    if let Some(difficulty) = report["nostr_pow"].as_u64() {
+        lines.push(format!("nostr pow: {}", difficulty));
+    }
+    let labels: Vec<&str> = report["labels"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|label| label.as_str())
+        .collect();
+    if !labels.is_empty() {
+        lines.push(format!("labels: {}", labels.join(", ")));
+    }
+    for media in report["media"].as_array().into_iter().flatten() {
+        let mut line = format!(
+            "media: {} ({}, {} bytes)",
+            media["path"].as_str().unwrap_or_default(),
+            media["mime_type"].as_str().unwrap_or_default(),
+            media["size"]
+        );
+        if let Some(alt) = media["alt_text"].as_str() {
+            line.push_str(&format!(" alt: {}", alt));
+        }
+        lines.push(line);
+    }
+
+    let parts = report["parts"].as_array().cloned().unwrap_or_default();
+    for part in &parts {
+        lines.push(String::new());
+        if parts.len() > 1 {
+            lines.push(format!(
+                "--- Thread part {}/{} ({} characters) ---",
+                part["part"],
+                parts.len(),
+                part["characters"]
+            ));
+        } else {
+            lines.push(format!("--- {} characters ---", part["characters"]));
+        }
+        lines.push(part["content"].as_str().unwrap_or_default().to_string());
+    }
+
+    lines
+}
+
+/// Ask a yes/no question, defaulting to no
+///
+/// The answer is read from stdin, or from the controlling terminal when the
+/// post was read from stdin, so `--confirm` still works with piped content.
+fn ask_to_confirm(question: &str, stdin_used: bool) -> Result<bool> {
+    use std::io::{BufRead, Write};
+
+    eprint!("{} [y/N] ", question);
+    let _ = io::stderr().flush();
+
+    let mut answer = String::new();
+    let read = if !stdin_used {
+        io::stdin().lock().read_line(&mut answer)
+    } else {
+        std::fs::File::open("/dev/tty")
+            .and_then(|tty| io::BufReader::new(tty).read_line(&mut answer))
+    };
+    read.map_err(|e| {
+        PlurcastError::InvalidInput(format!("--confirm needs a terminal to ask on: {}", e))
+    })?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Output draft result with thread part number
+fn output_draft_result_with_part(post_id: &str, format: &OutputFormat, part_num: usize) {
+    match format {
+        OutputFormat::Json => {
+            let output = serde_json::json!({
+                "draft": true,
+                "post_id": post_id,
+                "thread_part": part_num
+            });
+            println!("{}", output);
+        }
+        OutputFormat::Text => {
+            println!("draft[{}]:{}", part_num, post_id);
+        }
+    }
+}
+
+/// Output schedule result with thread part number
+fn output_schedule_result_with_part(
+    post_id: &str,
+    scheduled_at: i64,
+    format: &OutputFormat,
+    part_num: usize,
+) {
+    let scheduled_time = format_scheduled_time(scheduled_at);
+
+    match format {
+        OutputFormat::Json => {
+            let output = serde_json::json!({
+                "scheduled": true,
+                "post_id": post_id,
+                "scheduled_at": scheduled_at,
+                "scheduled_time": scheduled_time,
+                "thread_part": part_num
+            });
+            println!("{}", output);
+        }
+        OutputFormat::Text => {
+            println!("scheduled[{}]:{} ({})", part_num, post_id, scheduled_time);
+        }
+    }
+}
+
+/// Task 7.2: Get content from CLI argument or stdin
+fn get_content(cli: &Cli) -> Result<String> {
+    if let Some(content) = &cli.content {
+        // Content provided as argument
+        // Validate length BEFORE any processing (Security: Issue H2)
+        if content.len() > MAX_CONTENT_LENGTH {
+            return Err(PlurcastError::InvalidInput(format!(
+                "Content too large: {} bytes (maximum: {} bytes)",
+                content.len(),
+                MAX_CONTENT_LENGTH
+            )));
+        }
+
+        if content.trim().is_empty() {
+            return Err(PlurcastError::InvalidInput(
+                "Content cannot be empty".to_string(),
+            ));
+        }
+        return Ok(content.clone());
+    }
+
+    // Check if stdin is a TTY
+    let stdin = io::stdin();
+    if stdin.is_terminal() {
+        return Err(PlurcastError::InvalidInput(
+            "No content provided. Provide content as argument or pipe via stdin".to_string(),
+        ));
+    }
+
+    // Read from stdin with size limit (Security: Issue H2)
+    // Use take() to limit bytes read - prevents reading infinite streams
+    // Read MAX_CONTENT_LENGTH + 1 to detect if limit was exceeded
+    let mut buffer = String::new();
+    stdin
+        .lock()
+        .take((MAX_CONTENT_LENGTH + 1) as u64)
+        .read_to_string(&mut buffer)
+        .map_err(|e| PlurcastError::InvalidInput(format!("Failed to read from stdin: {}", e)))?;
+
+    // Check if we hit the limit
+    if buffer.len() > MAX_CONTENT_LENGTH {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Content too large: exceeds {} bytes (maximum: {} bytes)",
+            MAX_CONTENT_LENGTH, MAX_CONTENT_LENGTH
+        )));
+    }
+
+    if buffer.trim().is_empty() {
+        return Err(PlurcastError::InvalidInput(
+            "Content cannot be empty".to_string(),
+        ));
+    }
+
+    Ok(buffer)
+}
+
+/// Check if a string is a valid UUID (plurcast internal post ID)
+///
+/// Returns true for UUIDs like "550e8400-e29b-41d4-a716-446655440000"
+/// Returns false for platform-specific IDs like "note1abc..." or "12345678"
+fn is_uuid(s: &str) -> bool {
+    uuid::Uuid::parse_str(s).is_ok()
+}
+
+/// Resolve a platform-specific reply-to ID to a HashMap for all target platforms
+///
+/// This function implements intelligent cross-platform reply-to resolution:
+///
+/// 1. Detect which platform the ID belongs to based on format
+/// 2. Try database lookup to find cross-platform IDs if the post was made via plurcast
+/// 3. If found in DB, return all platform IDs for that post (enables cross-platform replies)
+/// 4. If not found, only apply reply-to to the matching platform (skip others)
+///
+/// # Arguments
+///
+/// * `id` - The platform-specific post ID (e.g., "note1abc...", "12345678")
+/// * `target_platforms` - The platforms the user wants to post to
+/// * `db` - Database for cross-platform lookup
+/// * `verbose` - Whether to print verbose output
+///
+/// # Returns
+///
+/// A tuple of (reply_to HashMap, filtered target_platforms)
+/// The target_platforms may be filtered if cross-platform mapping wasn't found
+async fn resolve_platform_specific_reply_to(
+    id: &str,
+    target_platforms: &[String],
+    db: &Database,
+    verbose: bool,
+) -> Result<(HashMap<String, String>, Vec<String>)> {
+    let detected = detect_platform_from_id(id);
+
+    // Get the platform name from the detected platform
+    let detected_platform = match detected.as_platform_name() {
+        Some(name) => name,
+        None => {
+            // Unknown format - we can't determine the platform
+            tracing::warn!("Could not detect platform for ID '{}'. Unknown format.", id);
+            return Err(PlurcastError::InvalidInput(format!(
+                "Could not detect platform for reply-to ID '{}'. \
+                 Expected formats: note1... (Nostr), numeric ID (Mastodon), \
+                 %...=.sha256 (SSB), or a plurcast UUID.",
+                id
+            )));
+        }
+    };
+
+    tracing::debug!("Detected {} ID: {}", detected_platform, id);
+
+    // Try to find cross-platform IDs from database
+    if let Some(post_id) = db
+        .get_post_id_by_platform_post_id(detected_platform, id)
+        .await?
+    {
+        // Found in database - get all platform IDs
+        let platform_ids = db.get_platform_post_ids(&post_id).await?;
+        if !platform_ids.is_empty() {
+            tracing::info!(
+                "Found cross-platform IDs for {} {}: {:?}",
+                detected_platform,
+                id,
+                platform_ids.keys().collect::<Vec<_>>()
+            );
+            if verbose {
+                eprintln!(
+                    "Found cross-platform reply-to mapping: {:?}",
+                    platform_ids.keys().collect::<Vec<_>>()
+                );
+            }
+            // Filter to only target platforms and return
+            let filtered_ids: HashMap<String, String> = platform_ids
+                .into_iter()
+                .filter(|(p, _)| target_platforms.contains(p))
+                .collect();
+            return Ok((filtered_ids, target_platforms.to_vec()));
+        }
+    }
+
+    // Not in database - only apply to the matching platform, skip others
+    let mut reply_to = HashMap::new();
+    let mut filtered_platforms = Vec::new();
+
+    if target_platforms.contains(&detected_platform.to_string()) {
+        reply_to.insert(detected_platform.to_string(), id.to_string());
+        filtered_platforms.push(detected_platform.to_string());
+
+        // Find platforms that will be skipped
+        let skipped: Vec<_> = target_platforms
+            .iter()
+            .filter(|p| p.as_str() != detected_platform)
+            .collect();
+
+        if !skipped.is_empty() {
+            tracing::warn!(
+                "Reply-to ID '{}' is a {} ID. No cross-platform mapping found in database. \
+                 Only posting to {} as a reply. Skipping: {:?}",
+                id,
+                detected_platform,
+                detected_platform,
+                skipped
+            );
+            if verbose {
+                eprintln!(
+                    "Warning: Reply-to ID '{}' is a {} ID.",
+                    id, detected_platform
+                );
+                eprintln!("         No cross-platform mapping found in database.");
+                eprintln!(
+                    "         Only posting to {} as a reply. Skipping: {:?}",
+                    detected_platform, skipped
+                );
+            }
+        }
+    } else {
+        // The detected platform is not in the target list
+        tracing::warn!(
+            "Reply-to ID '{}' is for {} but that platform is not in target list {:?}. \
+             Cannot post a reply without the target platform.",
+            id,
+            detected_platform,
+            target_platforms
+        );
+        return Err(PlurcastError::InvalidInput(format!(
+            "Reply-to ID '{}' is a {} ID, but {} is not in the target platforms ({:?}). \
+             Cannot post a reply to a platform you're not posting to.",
+            id, detected_platform, detected_platform, target_platforms
+        )));
+    }
+
+    Ok((reply_to, filtered_platforms))
+}
+
+/// Split content into thread parts at word boundaries
+///
+/// Each part will be at most `max_len` characters, splitting at the last space
+/// before the limit to avoid breaking words.
+fn split_into_thread_parts(content: &str, max_len: usize) -> Vec<String> {
+    let content = content.trim();
+
+    // Handle empty content
+    if content.is_empty() {
+        return vec![];
+    }
+
+    // If content fits in one part, return as-is
+    if content.chars().count() <= max_len {
+        return vec![content.to_string()];
+    }
+
+    let mut parts = Vec::new();
+    let mut remaining = content;
+
+    while !remaining.is_empty() {
+        let char_count = remaining.chars().count();
+
+        if char_count <= max_len {
+            parts.push(remaining.trim().to_string());
+            break;
+        }
+
+        // Find the last space before max_len characters
+        let chars: Vec<char> = remaining.chars().collect();
+        let search_end = max_len.min(chars.len());
+
+        // Look for last space in the allowed range
+        let split_at = (0..search_end)
+            .rev()
+            .find(|&i| chars[i] == ' ')
+            .unwrap_or(search_end); // If no space found, split at max_len
+
+        // Extract the part (as string from chars)
+        let part: String = chars[..split_at].iter().collect();
+        parts.push(part.trim().to_string());
+
+        // Move past the split point (skip the space)
+        let skip = if split_at < chars.len() && chars[split_at] == ' ' {
+            split_at + 1
+        } else {
+            split_at
+        };
+        remaining = &remaining[chars[..skip].iter().collect::<String>().len()..];
+        remaining = remaining.trim_start();
+    }
+
+    // Filter out any empty parts
+    parts.into_iter().filter(|p| !p.is_empty()).collect()
+}
+
+/// Task 7.1: Determine which platforms to post to
+fn determine_platforms(cli: &Cli, config: &Config) -> Result<Vec<String>> {
+    if cli.platform.is_empty() && !cli.schedule_for.is_empty() {
+        // Platforms named by --schedule-for, plus the defaults at --schedule
+        let mut platforms = if cli.schedule.is_some() {
+            config.defaults.platforms.clone()
+        } else {
+            Vec::new()
+        };
+        for (platform, _) in parse_schedule_for(&cli.schedule_for)? {
+            if !platforms.contains(&platform) {
+                platforms.push(platform);
+            }
+        }
+        Ok(platforms)
+    } else if !cli.platform.is_empty() {
+        // Use platforms from CLI flags
+        let platforms: Vec<String> = cli.platform.iter().map(|s| s.to_lowercase()).collect();
+
+        Ok(platforms)
+    } else {
+        // Use default platforms from config
+        if config.defaults.platforms.is_empty() {
+            return Err(PlurcastError::InvalidInput(
+                "No default platforms configured".to_string(),
+            ));
+        }
+
+        Ok(config.defaults.platforms.clone())
+    }
+}
+
+/// Parse --schedule-for values ("mastodon=9:00") into platform and time
+fn parse_schedule_for(values: &[String]) -> Result<Vec<(String, String)>> {
+    let mut schedules: Vec<(String, String)> = Vec::new();
+
+    for value in values {
+        let (platform, time) = value.split_once('=').ok_or_else(|| {
+            PlurcastError::InvalidInput(format!(
+                "Invalid --schedule-for '{}': expected PLATFORM=TIME, e.g. mastodon=9:00",
+                value
+            ))
+        })?;
+        let platform = platform.trim().to_lowercase();
+
+        if !["nostr", "mastodon", "ssb"].contains(&platform.as_str()) {
+            return Err(PlurcastError::InvalidInput(format!(
+                "Invalid --schedule-for platform '{}': expected nostr, mastodon or ssb",
+                platform
+            )));
+        }
+        if schedules.iter().any(|(p, _)| *p == platform) {
+            return Err(PlurcastError::InvalidInput(format!(
+                "--schedule-for given twice for {}",
+                platform
+            )));
+        }
+
+        schedules.push((platform, time.trim().to_string()));
+    }
+
+    Ok(schedules)
+}
+
+/// Resolve each target platform's send time for a --schedule-for post
+///
+/// Platforms without their own time use the --schedule time. Returns an
+/// empty list when --schedule-for isn't used.
+fn platform_schedule(
+    schedule_for: &[(String, String)],
+    default_time: Option<i64>,
+    target_platforms: &[String],
+) -> Result<Vec<(String, i64)>> {
+    if schedule_for.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut times = Vec::new();
+    for platform in target_platforms {
+        let own_time = schedule_for
+            .iter()
+            .find(|(p, _)| p == platform)
+            .map(|(_, time)| libplurcast::scheduling::parse_schedule(time, None))
+            .transpose()?
+            .map(|dt| dt.timestamp());
+
+        let at = own_time.or(default_time).ok_or_else(|| {
+            PlurcastError::InvalidInput(format!(
+                "No schedule for {}: add --schedule-for {}=TIME or --schedule",
+                platform, platform
+            ))
+        })?;
+        times.push((platform.clone(), at));
+    }
+
+    for (platform, _) in schedule_for {
+        if !target_platforms.contains(platform) {
+            return Err(PlurcastError::InvalidInput(format!(
+                "--schedule-for {} but {} is not a target platform",
+                platform, platform
+            )));
+        }
+    }
+
+    Ok(times)
+}
+
+/// Post with progress output for verbose mode
+async fn post_with_progress(
+    service: &PlurcastService,
+    request: PostRequest,
+) -> Result<PostResponse> {
+    // Display which account is being used
+    if let Some(ref account) = request.account {
+        eprintln!("Using account: {}", account);
+    } else {
+        eprintln!("Using active account for each platform");
+    }
+
+    eprintln!("Posting to {} platform(s)...", request.platforms.len());
+
+    let response = service.posting().post(request).await?;
+
+    for result in &response.results {
+        if result.success {
+            eprintln!(
+                "✓ {}: {}",
+                result.platform,
+                result.post_id.as_ref().unwrap()
+            );
+        } else {
+            eprintln!("✗ {}: {}", result.platform, result.error.as_ref().unwrap());
+        }
+    }
+
+    Ok(response)
+}
+
+/// Task 7.2: Output results in the specified format
+/// Successful posts go to stdout, errors go to stderr
+fn output_results(results: &[PlatformResult], format: &OutputFormat, verbose: bool) -> Result<()> {
+    match format {
+        OutputFormat::Text => {
+            // Output successful posts to stdout
+            for result in results {
+                if result.success {
+                    if let Some(post_id) = &result.post_id {
+                        println!("{}:{}", result.platform, post_id);
+                    }
+                }
+            }
+
+            // Output errors to stderr (unless already shown in verbose mode)
+            if !verbose {
+                for result in results {
+                    if !result.success {
+                        if let Some(error) = &result.error {
+                            eprintln!("Error [{}]: {}", result.platform, error);
+                        }
+                    }
+                }
+            }
+        }
+        OutputFormat::Json => {
+            let json_results: Vec<_> = results.iter().map(result_json).collect();
+
+            println!("{}", serde_json::to_string_pretty(&json_results).unwrap());
+        }
+    }
+
+    Ok(())
+}
+
+/// One platform's result as JSON, with relay answers for Nostr
+fn result_json(r: &PlatformResult) -> serde_json::Value {
+    let mut result = json!({
+        "platform": r.platform,
+        "success": r.success,
+        "post_id": r.post_id,
+        "error": r.error,
+    });
+    if !r.relay_results.is_empty() {
+        result["relays"] = json!(r.relay_results);
+    }
+    result
+}
+
+/// Fail a Nostr result that fewer than `quorum` relays accepted
+///
+/// The note was still published to the relays that took it, so its ID is
+/// kept for the output; only the success flag and error change.
+fn apply_relay_quorum(results: &mut [PlatformResult], quorum: usize) {
+    for result in results
+        .iter_mut()
+        .filter(|r| r.platform == "nostr" && r.success)
+    {
+        let accepted = result.relay_results.iter().filter(|r| r.accepted).count();
+        if accepted < quorum {
+            result.success = false;
+            result.error = Some(format!(
+                "Only {} of {} required relays accepted the note{}",
+                accepted,
+                quorum,
+                result
+                    .post_id
+                    .as_deref()
+                    .map(|id| format!(" ({})", id))
+                    .unwrap_or_default()
+            ));
+        }
+    }
+}
+
+/// Output draft result
+fn output_draft_result(post_id: &str, format: &OutputFormat) {
+    match format {
+        OutputFormat::Text => {
+            println!("draft:{}", post_id);
+        }
+        OutputFormat::Json => {
+            let result = json!({
+                "status": "draft",
+                "post_id": post_id,
+            });
+            println!("{}", serde_json::to_string_pretty(&result).unwrap());
+        }
+    }
+}
+
+/// Output the first occurrence of a new recurring series
+fn output_series_result(
+    series: &libplurcast::PostSeries,
+    post_id: &str,
+    scheduled_at: i64,
+    format: &OutputFormat,
+) {
+    let scheduled_time = format_scheduled_time(scheduled_at);
+    match format {
+        OutputFormat::Text => {
+            println!("scheduled:{}:for:{}", post_id, scheduled_time);
+            println!("series:{}:cron:{}", series.id, series.cron);
+        }
+        OutputFormat::Json => {
+            let output = serde_json::json!({
+                "scheduled": true,
+                "post_id": post_id,
+                "scheduled_at": scheduled_at,
+                "scheduled_time": scheduled_time,
+                "series_id": series.id,
+                "cron": series.cron,
+            });
+            println!("{}", output);
+        }
+    }
+}
+
+/// Output scheduled post result
+fn output_schedule_result(post_id: &str, scheduled_at: i64, format: &OutputFormat) {
+    let scheduled_time = format_scheduled_time(scheduled_at);
+
+    match format {
+        OutputFormat::Text => {
+            println!("scheduled:{}:for:{}", post_id, scheduled_time);
+        }
+        OutputFormat::Json => {
+            let result = json!({
+                "scheduled": true,
+                "post_id": post_id,
+                "scheduled_at": scheduled_at,
+                "scheduled_time": scheduled_time,
+            });
+            println!("{}", serde_json::to_string_pretty(&result).unwrap());
+        }
+    }
+}
+
+/// Output the per-platform times of a staggered post
+fn output_platform_schedule_result(post_id: &str, times: &[(String, i64)], format: &OutputFormat) {
+    let mut times = times.to_vec();
+    times.sort_by_key(|(_, at)| *at);
+
+    match format {
+        OutputFormat::Text => {
+            for (platform, at) in &times {
+                println!(
+                    "scheduled:{}:{}:for:{}",
+                    post_id,
+                    platform,
+                    format_scheduled_time(*at)
+                );
+            }
+        }
+        OutputFormat::Json => {
+            let platforms: Vec<_> = times
+                .iter()
+                .map(|(platform, at)| {
+                    json!({
+                        "platform": platform,
+                        "scheduled_at": at,
+                        "scheduled_time": format_scheduled_time(*at),
+                    })
+                })
+                .collect();
+            let result = json!({
+                "scheduled": true,
+                "post_id": post_id,
+                "scheduled_at": times.first().map(|(_, at)| *at),
+                "platforms": platforms,
+            });
+            println!("{}", serde_json::to_string_pretty(&result).unwrap());
+        }
+    }
+}
+
+/// Task 7.3: Determine exit code based on results
+/// Exit 0 if all platforms succeed (with `--require any`: if any platform succeeds)
+/// Exit 1 if at least one platform fails (non-auth)
+/// Exit 2 if any platform has authentication error
+/// Exit 3 for invalid input (handled elsewhere)
+fn determine_exit_code(results: &[PlatformResult], require: &str) -> i32 {
+    let succeeded = if require == "any" {
+        results.iter().any(|r| r.success)
+    } else {
+        results.iter().all(|r| r.success)
+    };
+
+    if succeeded {
+        0 // Enough platforms succeeded
+    } else {
+        // Check if any errors are authentication errors
+        let has_auth_error = results.iter().any(|r| {
+            r.error
+                .as_ref()
+                .map(|e| {
+                    e.contains("Authentication")
+                        || e.contains("authentication")
+                        || e.contains("Invalid token")
+                        || e.contains("Invalid credentials")
+                        || e.contains("No Nostr credentials found")
+                        || e.contains("No Mastodon credentials found")
+                        || e.contains("keys file not found")
+                        || e.contains("token file not found")
+                        || e.contains("auth file not found")
+                })
+                .unwrap_or(false)
+        });
+
+        if has_auth_error {
+            2 // Authentication error
+        } else {
+            1 // Posting failure (non-auth)
+        }
+    }
+}
+
+/// Format a scheduled timestamp into a human-readable string.
+///
+/// Returns a string combining relative time and absolute time:
+/// - "in 30 minutes (Jan 7 15:30 UTC)"
+/// - "tomorrow (Jan 8 12:00 UTC)"
+/// - "in 3 days (Jan 10 09:00 UTC)"
+///
+/// For past times, returns "overdue (date)".
+fn format_scheduled_time(scheduled_at: i64) -> String {
+    use chrono::{DateTime, Utc};
+
+    let now = Utc::now().timestamp();
+    let scheduled_dt = DateTime::from_timestamp(scheduled_at, 0);
+
+    // Format absolute time portion
+    let absolute = scheduled_dt
+        .map(|dt| dt.format("%b %-d %H:%M UTC").to_string())
+        .unwrap_or_else(|| scheduled_at.to_string());
+
+    // Calculate relative time
+    let diff = scheduled_at - now;
+
+    let relative = if diff < 0 {
+        "overdue".to_string()
+    } else {
+        let seconds = diff;
+        let minutes = seconds / 60;
+        let hours = minutes / 60;
+        let days = hours / 24;
+
+        if days > 1 {
+            format!("in {} days", days)
+        } else if days == 1 || (hours >= 18 && is_different_day(now, scheduled_at)) {
+            "tomorrow".to_string()
+        } else if hours > 0 {
+            format!("in {} hour{}", hours, if hours == 1 { "" } else { "s" })
+        } else if minutes > 0 {
+            format!(
+                "in {} minute{}",
+                minutes,
+                if minutes == 1 { "" } else { "s" }
+            )
+        } else {
+            "in <1 minute".to_string()
+        }
+    };
+
+    format!("{} ({})", relative, absolute)
+}
+
+/// Check if two timestamps fall on different calendar days (UTC).
+fn is_different_day(ts1: i64, ts2: i64) -> bool {
+    use chrono::DateTime;
+
+    let dt1 = DateTime::from_timestamp(ts1, 0);
+    let dt2 = DateTime::from_timestamp(ts2, 0);
+
+    match (dt1, dt2) {
+        (Some(d1), Some(d2)) => d1.date_naive() != d2.date_naive(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_media_with_and_without_alt() {
+        let media = parse_media("photo.jpg:A red bicycle").unwrap();
+        assert_eq!(media.path, PathBuf::from("photo.jpg"));
+        assert_eq!(media.alt_text.as_deref(), Some("A red bicycle"));
+
+        let media = parse_media("photo.jpg").unwrap();
+        assert_eq!(media.alt_text, None);
+
+        let media = parse_media("photo.jpg: ").unwrap();
+        assert_eq!(media.alt_text, None);
+
+        assert!(parse_media(":alt only").is_err());
+    }
+
+    #[test]
+    fn test_parse_media_keeps_colons_in_existing_paths() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("shot 12:30.png");
+        std::fs::write(&path, b"image").unwrap();
+        let path_str = path.to_str().unwrap();
+
+        let media = parse_media(path_str).unwrap();
+        assert_eq!(media.path, path);
+        assert_eq!(media.alt_text, None);
+
+        let media = parse_media(&format!("{}:Lunch: soup", path_str)).unwrap();
+        assert_eq!(media.path, path);
+        assert_eq!(media.alt_text.as_deref(), Some("Lunch: soup"));
+    }
+
+    #[test]
+    fn test_parse_lang() {
+        assert_eq!(parse_lang("DE").unwrap(), "de");
+        assert!(parse_lang("german")
+            .unwrap_err()
+            .contains("two-letter ISO 639-1 code"));
+    }
+
+    #[test]
+    fn test_parse_tag() {
+        assert_eq!(parse_tag("#rust").unwrap(), "rust");
+        assert!(parse_tag("two words")
+            .unwrap_err()
+            .contains("use only letters, digits and '_'"));
+    }
+
+    #[test]
+    fn test_parse_visibility() {
+        assert_eq!(parse_visibility("unlisted").unwrap(), Visibility::Unlisted);
+        assert_eq!(parse_visibility("DIRECT").unwrap(), Visibility::Direct);
+        assert!(parse_visibility("friends")
+            .unwrap_err()
+            .contains("expected public, unlisted, private or direct"));
+    }
+
+    // Tests for split_into_thread_parts function
+
+    #[test]
+    fn test_apply_relay_quorum() {
+        let relay = |url: &str, accepted: bool| libplurcast::RelayResult {
+            relay_url: url.to_string(),
+            accepted,
+            message: (!accepted).then(|| "blocked".to_string()),
+            latency_ms: Some(40),
+        };
+        let nostr = PlatformResult {
+            platform: "nostr".to_string(),
+            success: true,
+            post_id: Some("note1abc".to_string()),
+            error: None,
+            raw_response: None,
+            relay_results: vec![
+                relay("wss://a.example", true),
+                relay("wss://b.example", true),
+                relay("wss://c.example", false),
+            ],
+        };
+        let mastodon = PlatformResult {
+            platform: "mastodon".to_string(),
+            success: true,
+            post_id: Some("123".to_string()),
+            error: None,
+            raw_response: None,
+            relay_results: Vec::new(),
+        };
+
+        let mut results = vec![nostr.clone(), mastodon.clone()];
+        apply_relay_quorum(&mut results, 2);
+        assert!(results.iter().all(|r| r.success));
+
+        let mut results = vec![nostr, mastodon];
+        apply_relay_quorum(&mut results, 3);
+        assert!(!results[0].success);
+        assert_eq!(
+            results[0].error.as_deref(),
+            Some("Only 2 of 3 required relays accepted the note (note1abc)")
+        );
+        assert_eq!(results[0].post_id.as_deref(), Some("note1abc"));
+        assert!(results[1].success);
+        assert_eq!(determine_exit_code(&results, "all"), 1);
+    }
+
+    #[test]
+    fn test_determine_exit_code_require_any() {
+        let ok = PlatformResult {
+            platform: "mastodon".to_string(),
+            success: true,
+            post_id: Some("109".to_string()),
+            error: None,
+            raw_response: None,
+            relay_results: Vec::new(),
+        };
+        let failed = PlatformResult {
+            platform: "nostr".to_string(),
+            success: false,
+            post_id: None,
+            error: Some("Relay rejected the note".to_string()),
+            raw_response: None,
+            relay_results: Vec::new(),
+        };
+
+        let results = vec![ok, failed.clone()];
+        assert_eq!(determine_exit_code(&results, "all"), 1);
+        assert_eq!(determine_exit_code(&results, "any"), 0);
+        assert_eq!(determine_exit_code(&[failed], "any"), 1);
+    }
+
+    #[test]
+    fn test_split_short_content_no_split() {
+        let content = "This is a short message.";
+        let parts = split_into_thread_parts(content, 450);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0], "This is a short message.");
+    }
+
+    #[test]
+    fn test_split_exactly_at_limit() {
+        // Create content exactly at the limit
+        let content = "a".repeat(450);
+        let parts = split_into_thread_parts(&content, 450);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].chars().count(), 450);
+    }
+
+    #[test]
+    fn test_split_long_content_at_word_boundary() {
+        // Create content that needs to be split
+        let content = "word ".repeat(100); // 500 chars
+        let parts = split_into_thread_parts(&content, 50);
+
+        // Each part should be under the limit
+        for part in &parts {
+            assert!(part.chars().count() <= 50);
+        }
+
+        // All parts combined should contain all the words
+        let rejoined = parts.join(" ");
+        let original_words: Vec<&str> = content.split_whitespace().collect();
+        let rejoined_words: Vec<&str> = rejoined.split_whitespace().collect();
+        assert_eq!(original_words.len(), rejoined_words.len());
+    }
+
+    #[test]
+    fn test_split_respects_word_boundaries() {
+        let content = "Hello world this is a test message";
+        let parts = split_into_thread_parts(content, 15);
+
+        // Check that no part ends with a partial word (no cut mid-word)
+        for part in &parts {
+            assert!(!part.ends_with('-')); // No hyphenation
+            assert!(part.chars().count() <= 15);
+        }
+    }
+
+    #[test]
+    fn test_split_empty_content() {
+        let parts = split_into_thread_parts("", 450);
+        assert!(parts.is_empty());
+    }
+
+    #[test]
+    fn test_split_whitespace_only() {
+        let parts = split_into_thread_parts("   ", 450);
+        assert!(parts.is_empty());
+    }
+
+    #[test]
+    fn test_split_unicode_characters() {
+        // Unicode chars should be counted as single chars, not bytes
+        // "🎉 " = 2 chars per repeat (emoji + space), 250 repeats = 500 chars
+        let content = "🎉 ".repeat(250);
+        let parts = split_into_thread_parts(&content, 450);
+
+        // Should need at least 2 parts (500 chars > 450 limit)
+        assert!(parts.len() >= 2, "Expected >= 2 parts, got {}", parts.len());
+
+        // Each part should be under the char limit
+        for part in &parts {
+            assert!(part.chars().count() <= 450);
+        }
+    }
+
+    #[test]
+    fn test_split_very_long_word() {
+        // A word longer than max_len should be split at max_len
+        let content = "a".repeat(100);
+        let parts = split_into_thread_parts(&content, 50);
+
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].chars().count(), 50);
+        assert_eq!(parts[1].chars().count(), 50);
+    }
+
+    #[test]
+    fn test_split_preserves_content() {
+        let content = "This is a test message that should be split into multiple parts. Each part should contain complete words and the content should be fully preserved.";
+        let parts = split_into_thread_parts(content, 50);
+
+        // Rejoin and compare (allowing for slight spacing differences)
+        let rejoined = parts.join(" ");
+        let original_words: Vec<&str> = content.split_whitespace().collect();
+        let rejoined_words: Vec<&str> = rejoined.split_whitespace().collect();
+
+        // All original words should be present
+        assert_eq!(original_words, rejoined_words);
+    }
+
+    #[test]
+    fn test_split_with_max_thread_part_length_constant() {
+        // Test with the actual constant used in production
+        let content = "a ".repeat(300); // 600 chars
+        let parts = split_into_thread_parts(&content, MAX_THREAD_PART_LENGTH);
+
+        assert!(parts.len() >= 2);
+        for part in &parts {
+            assert!(part.chars().count() <= MAX_THREAD_PART_LENGTH);
+        }
+    }
+
+    #[test]
+    fn test_split_trims_parts() {
+        let content = "  hello   world  ";
+        let parts = split_into_thread_parts(content, 450);
+
+        assert_eq!(parts.len(), 1);
+        // Should be trimmed
+        assert!(!parts[0].starts_with(' '));
+        assert!(!parts[0].ends_with(' '));
+    }
+
+    // Tests for is_uuid function (cross-platform reply-to detection)
+
+    #[test]
+    fn test_is_uuid_valid() {
+        // Standard UUID format
+        assert!(is_uuid("550e8400-e29b-41d4-a716-446655440000"));
+        // With uppercase
+        assert!(is_uuid("550E8400-E29B-41D4-A716-446655440000"));
+    }
+
+    #[test]
+    fn test_is_uuid_invalid_nostr_id() {
+        // Nostr note1... format should not be a UUID
+        assert!(!is_uuid("note1abc123def456"));
+        // Nostr hex event ID
+        assert!(!is_uuid(
+            "abc123def456abc123def456abc123def456abc123def456abc123def456abcd"
+        ));
+    }
+
+    #[test]
+    fn test_is_uuid_invalid_mastodon_id() {
+        // Mastodon numeric ID
+        assert!(!is_uuid("12345678"));
+        // Longer numeric ID
+        assert!(!is_uuid("109876543210987654"));
+    }
+
+    #[test]
+    fn test_is_uuid_invalid_empty() {
+        assert!(!is_uuid(""));
+    }
+
+    #[test]
+    fn test_is_uuid_invalid_random_string() {
+        assert!(!is_uuid("not-a-uuid"));
+        assert!(!is_uuid("hello world"));
+    }
+
+    // Tests for format_scheduled_time function
+
+    #[test]
+    fn test_format_scheduled_time_minutes() {
+        use chrono::Utc;
+
+        // 30 minutes from now
+        let now = Utc::now().timestamp();
+        let scheduled = now + 30 * 60;
+        let result = format_scheduled_time(scheduled);
+
+        assert!(result.starts_with("in 30 minutes"));
+        assert!(result.contains("UTC"));
+        assert!(result.contains("("));
+        assert!(result.contains(")"));
+    }
+
+    #[test]
+    fn test_format_scheduled_time_hours() {
+        use chrono::Utc;
+
+        // 3 hours from now
+        let now = Utc::now().timestamp();
+        let scheduled = now + 3 * 60 * 60;
+        let result = format_scheduled_time(scheduled);
+
+        assert!(result.starts_with("in 3 hours"));
+        assert!(result.contains("UTC"));
+    }
+
+    #[test]
+    fn test_format_scheduled_time_single_hour() {
+        use chrono::Utc;
+
+        // 1 hour from now
+        let now = Utc::now().timestamp();
+        let scheduled = now + 60 * 60;
+        let result = format_scheduled_time(scheduled);
+
+        // Should say "in 1 hour" (singular)
+        assert!(result.starts_with("in 1 hour"));
+        assert!(!result.starts_with("in 1 hours"));
+    }
+
+    #[test]
+    fn test_format_scheduled_time_days() {
+        use chrono::Utc;
+
+        // 3 days from now
+        let now = Utc::now().timestamp();
+        let scheduled = now + 3 * 24 * 60 * 60;
+        let result = format_scheduled_time(scheduled);
+
+        assert!(result.starts_with("in 3 days"));
+        assert!(result.contains("UTC"));
+    }
+
+    #[test]
+    fn test_format_scheduled_time_overdue() {
+        use chrono::Utc;
+
+        // 1 hour in the past
+        let now = Utc::now().timestamp();
+        let scheduled = now - 60 * 60;
+        let result = format_scheduled_time(scheduled);
+
+        assert!(result.starts_with("overdue"));
+        assert!(result.contains("UTC"));
+    }
+
+    #[test]
+    fn test_format_scheduled_time_less_than_minute() {
+        use chrono::Utc;
+
+        // 30 seconds from now
+        let now = Utc::now().timestamp();
+        let scheduled = now + 30;
+        let result = format_scheduled_time(scheduled);
+
+        assert!(result.starts_with("in <1 minute"));
+    }
+
+    #[test]
+    fn test_format_scheduled_time_contains_date() {
+        use chrono::Utc;
+
+        // 1 day from now
+        let now = Utc::now().timestamp();
+        let scheduled = now + 24 * 60 * 60;
+        let result = format_scheduled_time(scheduled);
+
+        // Should contain month abbreviation and time
+        assert!(result.contains("UTC"));
+        // Format is "Month Day HH:MM UTC" inside parens
+        assert!(result.contains(":"));
+    }
+
+    #[test]
+    fn test_is_different_day_same_day() {
+        use chrono::Utc;
+
+        let now = Utc::now().timestamp();
+        // 1 hour later (same day usually)
+        let later = now + 60 * 60;
+
+        // This might be different day if close to midnight, but usually same
+        // The function should handle this correctly
+        let _ = is_different_day(now, later);
+    }
+
+    #[test]
+    fn test_is_different_day_clearly_different() {
+        use chrono::Utc;
+
+        let now = Utc::now().timestamp();
+        // 48 hours later is definitely a different day
+        let later = now + 48 * 60 * 60;
+
+        assert!(is_different_day(now, later));
+    }
+}
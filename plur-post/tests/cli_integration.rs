@@ -672,3 +672,359 @@ fn test_no_content_samples_in_error_messages() {
     assert!(!stderr.contains("SECRET_DATA"));
     assert!(stderr.contains("Content too large"));
 }
+
+#[test]
+fn test_dry_run_shows_thread_parts_without_posting() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+
+    let content = "This sentence is padding for a long post. ".repeat(20);
+
+    let output = Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg(content.trim())
+        .arg("--auto-thread")
+        .arg("--dry-run")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["dry_run"], true);
+    assert_eq!(report["platforms"][0]["platform"], "nostr");
+    let parts = report["parts"].as_array().unwrap();
+    assert!(parts.len() > 1);
+    for part in parts {
+        assert!(part["characters"].as_u64().unwrap() <= 500);
+    }
+
+    // Nothing is stored; the database isn't even created
+    assert!(!std::path::Path::new(&db_path).exists());
+
+    // Text output shows the content as it would be sent
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Hello dry run")
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("dry-run: nothing was posted"))
+        .stdout(predicate::str::contains("platform: nostr"))
+        .stdout(predicate::str::contains("--- 13 characters ---"))
+        .stdout(predicate::str::contains("Hello dry run"));
+}
+
+#[test]
+fn test_dry_run_refuses_outdated_database() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+
+    // A database from an older version: its latest migration isn't applied
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let db = libplurcast::Database::new(&db_path).await.unwrap();
+        sqlx::query("DELETE FROM _sqlx_migrations WHERE version = ?")
+            .bind(libplurcast::Database::latest_schema_version())
+            .execute(db.pool())
+            .await
+            .unwrap();
+        db.pool().close().await;
+    });
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Hello dry run")
+        .arg("--dry-run")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("a dry run doesn't upgrade it"));
+}
+
+#[test]
+fn test_dry_run_treats_empty_file_as_no_database() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+
+    fs::write(&db_path, "").unwrap();
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Hello dry run")
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("dry-run: nothing was posted"));
+    assert_eq!(fs::metadata(&db_path).unwrap().len(), 0);
+}
+
+#[test]
+fn test_json_input_carries_options() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("--input")
+        .arg("json")
+        .write_stdin(
+            r#"{"content": "It's \"quoted\" & $safe", "platforms": ["nostr"], "schedule": "2h", "cw": "Test", "labels": ["json"]}"#,
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("scheduled:"));
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let (posts, labels) = rt.block_on(async {
+        let db = libplurcast::Database::new(&db_path).await.unwrap();
+        let posts = db.get_scheduled_posts().await.unwrap();
+        let labels = db.get_labels(&posts[0].id).await.unwrap();
+        (posts, labels)
+    });
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].content, "It's \"quoted\" & $safe");
+    let metadata: serde_json::Value =
+        serde_json::from_str(posts[0].metadata.as_ref().unwrap()).unwrap();
+    assert_eq!(metadata["content_warning"], "Test");
+    assert_eq!(labels, vec!["json".to_string()]);
+}
+
+#[test]
+fn test_json_input_rejects_unknown_keys() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env();
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("--input")
+        .arg("json")
+        .write_stdin(r#"{"content": "Hello", "platfroms": ["nostr"]}"#)
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Invalid JSON input"))
+        .stderr(predicate::str::contains("unknown field `platfroms`"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_edit_posts_saved_buffer_and_aborts_when_empty() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (temp_dir, config_path, _db_path) = setup_test_env();
+
+    let editor = temp_dir.path().join("editor.sh");
+    fs::write(
+        &editor,
+        "#!/bin/sh\necho 'Written in the editor' > \"$1\"\n",
+    )
+    .unwrap();
+    fs::set_permissions(&editor, fs::Permissions::from_mode(0o755)).unwrap();
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .env_remove("VISUAL")
+        .env("EDITOR", &editor)
+        .arg("--edit")
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Written in the editor"));
+
+    // An editor that saves nothing aborts the post
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .env_remove("VISUAL")
+        .env("EDITOR", "true")
+        .arg("--edit")
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains(
+            "Aborting post due to empty content",
+        ));
+}
+
+#[test]
+fn test_confirm_asks_before_posting() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+
+    // Anything but yes cancels
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Fat-fingered broadcast")
+        .arg("--schedule")
+        .arg("1h")
+        .arg("--confirm")
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("--- 22 characters ---"))
+        .stderr(predicate::str::contains("Schedule this post? [y/N]"))
+        .stderr(predicate::str::contains("Cancelled: nothing was posted"));
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let scheduled = |rt: &tokio::runtime::Runtime| {
+        rt.block_on(async {
+            let db = libplurcast::Database::new(&db_path).await.unwrap();
+            db.get_scheduled_posts().await.unwrap()
+        })
+    };
+    assert!(scheduled(&rt).is_empty());
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Intended broadcast")
+        .arg("--schedule")
+        .arg("1h")
+        .arg("--confirm")
+        .write_stdin("y\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("scheduled:"));
+    assert_eq!(scheduled(&rt).len(), 1);
+}
+
+#[test]
+fn test_wait_relays_checks_quorum_against_configured_relays() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env();
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Needs two relays")
+        .arg("--wait-relays")
+        .arg("0")
+        .assert()
+        .code(2);
+
+    // The test config has a single relay
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Needs two relays")
+        .arg("--wait-relays")
+        .arg("2")
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains(
+            "--wait-relays 2 is more than the 1 Nostr relay(s) configured",
+        ));
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Later")
+        .arg("--wait-relays")
+        .arg("1")
+        .arg("--schedule")
+        .arg("1h")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_batch_reports_one_result_per_line() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env();
+
+    let output = Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("--batch")
+        .arg("--draft")
+        .write_stdin(
+            "{\"content\": \"First\"}\n\n{\"content\": \"Second\", \"colour\": \"red\"}\n{\"content\": \"Third\", \"labels\": [\"batch\"]}\n",
+        )
+        .assert()
+        .code(3)
+        .get_output()
+        .stdout
+        .clone();
+
+    let lines: Vec<serde_json::Value> = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["line"], 1);
+    assert_eq!(lines[0]["status"], "draft");
+    assert_eq!(lines[1]["line"], 3);
+    assert!(lines[1]["error"]
+        .as_str()
+        .unwrap()
+        .contains("unknown field `colour`"));
+    assert_eq!(lines[2]["line"], 4);
+    assert_eq!(lines[2]["status"], "draft");
+    assert_ne!(lines[0]["post_id"], lines[2]["post_id"]);
+}
+
+#[test]
+fn test_expires_sets_post_lifetime() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env();
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Never mind")
+        .arg("--expires")
+        .arg("soon")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Could not parse duration"));
+
+    let output = Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Flash sale until noon")
+        .arg("--expires")
+        .arg("2h")
+        .arg("--dry-run")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["expires_in"], 7200);
+}
+
+#[test]
+fn test_poll_is_checked_and_previewed() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env();
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Tabs or spaces?")
+        .arg("--poll")
+        .arg("Tabs")
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("at least two choices"));
+
+    let output = Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Tabs or spaces?")
+        .arg("--poll")
+        .arg("Tabs|Spaces")
+        .arg("--poll-duration")
+        .arg("2d")
+        .arg("--dry-run")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        report["poll"]["options"],
+        serde_json::json!(["Tabs", "Spaces"])
+    );
+    assert_eq!(report["poll"]["duration"], 172800);
+}
@@ -0,0 +1,221 @@
+//! Integration tests for plur-post --from-file
+//!
+//! Posts read from markdown files, with YAML or TOML frontmatter supplying
+//! platforms, schedule, tags, cw and account.
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Helper to escape path for TOML on Windows
+fn escape_path_for_toml(path: &str) -> String {
+    path.replace('\\', "\\\\")
+}
+
+/// Helper to create a test environment with config and database
+fn setup_test_env() -> (TempDir, String, String) {
+    let temp_dir = TempDir::new().unwrap();
+
+    // Create config directory
+    let config_dir = temp_dir.path().join("config");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    // Create data directory
+    let data_dir = temp_dir.path().join("data");
+    fs::create_dir_all(&data_dir).unwrap();
+
+    // Create config file
+    let config_path = config_dir.join("config.toml");
+    let db_path = data_dir.join("posts.db");
+    let keys_path = config_dir.join("nostr.keys");
+
+    let config_content = format!(
+        r#"
+[database]
+path = "{}"
+
+[nostr]
+enabled = true
+keys_file = "{}"
+relays = ["wss://relay.damus.io"]
+
+[defaults]
+platforms = ["nostr"]
+"#,
+        escape_path_for_toml(&db_path.to_string_lossy()),
+        escape_path_for_toml(&keys_path.to_string_lossy())
+    );
+
+    fs::write(&config_path, config_content).unwrap();
+
+    // Generate test Nostr keys
+    let test_keys = nostr_sdk::Keys::generate();
+    let hex_key = test_keys.secret_key().to_secret_hex();
+    fs::write(&keys_path, hex_key).unwrap();
+
+    (
+        temp_dir,
+        config_path.to_string_lossy().to_string(),
+        db_path.to_string_lossy().to_string(),
+    )
+}
+
+fn scheduled_posts(db_path: &str) -> Vec<libplurcast::Post> {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let db = libplurcast::Database::new(db_path).await.unwrap();
+        db.get_scheduled_posts().await.unwrap()
+    })
+}
+
+#[test]
+fn test_from_file_uses_frontmatter() {
+    let (temp_dir, config_path, db_path) = setup_test_env();
+    let file = temp_dir.path().join("launch.md");
+    fs::write(
+        &file,
+        "---\ntitle: Launch\nplatforms: [nostr]\nschedule: 2h\ntags:\n  - campaign:launch\ncw: Spoilers\n---\n\nWe're live!\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("--from-file")
+        .arg(&file)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("scheduled:"));
+
+    let posts = scheduled_posts(&db_path);
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].content, "We're live!");
+
+    let metadata: serde_json::Value =
+        serde_json::from_str(posts[0].metadata.as_ref().unwrap()).unwrap();
+    assert_eq!(metadata["platforms"], serde_json::json!(["nostr"]));
+    assert_eq!(metadata["content_warning"], "Spoilers");
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let labels = rt.block_on(async {
+        let db = libplurcast::Database::new(&db_path).await.unwrap();
+        db.get_labels(&posts[0].id).await.unwrap()
+    });
+    assert_eq!(labels, vec!["campaign:launch".to_string()]);
+}
+
+#[test]
+fn test_from_file_flags_override_frontmatter() {
+    let (temp_dir, config_path, db_path) = setup_test_env();
+    let file = temp_dir.path().join("post.md");
+    fs::write(&file, "+++\nschedule = \"1d\"\n+++\nBody\n").unwrap();
+
+    let before = chrono::Utc::now().timestamp();
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("--from-file")
+        .arg(&file)
+        .arg("--schedule")
+        .arg("1h")
+        .assert()
+        .success();
+
+    let posts = scheduled_posts(&db_path);
+    assert_eq!(posts.len(), 1);
+    let at = posts[0].scheduled_at.unwrap();
+    assert!(at >= before + 3600 && at < before + 7200);
+}
+
+#[test]
+fn test_cw_flag_overrides_frontmatter() {
+    let (temp_dir, config_path, db_path) = setup_test_env();
+    let file = temp_dir.path().join("post.md");
+    fs::write(&file, "---\nschedule: 1h\ncw: Politics\n---\nBody\n").unwrap();
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("--from-file")
+        .arg(&file)
+        .arg("--cw")
+        .arg("Spoilers")
+        .assert()
+        .success();
+
+    let posts = scheduled_posts(&db_path);
+    let metadata: serde_json::Value =
+        serde_json::from_str(posts[0].metadata.as_ref().unwrap()).unwrap();
+    assert_eq!(metadata["content_warning"], "Spoilers");
+}
+
+#[test]
+fn test_from_file_directory_posts_each_file() {
+    let (temp_dir, config_path, db_path) = setup_test_env();
+    let dir = temp_dir.path().join("posts");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("01-first.md"), "---\nschedule: 1h\n---\nFirst\n").unwrap();
+    fs::write(dir.join("02-second.md"), "---\nschedule: 2h\n---\nSecond\n").unwrap();
+    fs::write(dir.join("notes.txt"), "Not a post").unwrap();
+
+    let output = Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("--from-file")
+        .arg(&dir)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.matches("scheduled:").count(), 2);
+
+    let mut contents: Vec<String> = scheduled_posts(&db_path)
+        .into_iter()
+        .map(|post| post.content)
+        .collect();
+    contents.sort();
+    assert_eq!(contents, vec!["First", "Second"]);
+}
+
+#[test]
+fn test_from_file_bad_frontmatter_posts_nothing() {
+    let (temp_dir, config_path, db_path) = setup_test_env();
+    let dir = temp_dir.path().join("posts");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("01-good.md"), "---\nschedule: 1h\n---\nGood\n").unwrap();
+    fs::write(
+        dir.join("02-bad.md"),
+        "---\nplatforms: [myspace]\n---\nBad\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("--from-file")
+        .arg(&dir)
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("Invalid platform 'myspace'"));
+
+    assert!(!std::path::Path::new(&db_path).exists() || scheduled_posts(&db_path).is_empty());
+}
+
+#[test]
+fn test_from_file_conflicts_with_content() {
+    let (temp_dir, config_path, _db_path) = setup_test_env();
+    let file = temp_dir.path().join("post.md");
+    fs::write(&file, "Body\n").unwrap();
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Inline content")
+        .arg("--from-file")
+        .arg(&file)
+        .assert()
+        .failure();
+}
@@ -0,0 +1,140 @@
+//! Integration tests for plur-post --media
+//!
+//! Image attachments given on the command line, with optional alt text.
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Helper to escape path for TOML on Windows
+fn escape_path_for_toml(path: &str) -> String {
+    path.replace('\\', "\\\\")
+}
+
+/// Helper to create a test environment with config and database
+fn setup_test_env() -> (TempDir, String, String) {
+    let temp_dir = TempDir::new().unwrap();
+
+    // Create config directory
+    let config_dir = temp_dir.path().join("config");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    // Create data directory
+    let data_dir = temp_dir.path().join("data");
+    fs::create_dir_all(&data_dir).unwrap();
+
+    // Create config file
+    let config_path = config_dir.join("config.toml");
+    let db_path = data_dir.join("posts.db");
+    let keys_path = config_dir.join("nostr.keys");
+
+    let config_content = format!(
+        r#"
+[database]
+path = "{}"
+
+[nostr]
+enabled = true
+keys_file = "{}"
+relays = ["wss://relay.damus.io"]
+
+[defaults]
+platforms = ["nostr"]
+"#,
+        escape_path_for_toml(&db_path.to_string_lossy()),
+        escape_path_for_toml(&keys_path.to_string_lossy())
+    );
+
+    fs::write(&config_path, config_content).unwrap();
+
+    // Generate test Nostr keys
+    let test_keys = nostr_sdk::Keys::generate();
+    let hex_key = test_keys.secret_key().to_secret_hex();
+    fs::write(&keys_path, hex_key).unwrap();
+
+    (
+        temp_dir,
+        config_path.to_string_lossy().to_string(),
+        db_path.to_string_lossy().to_string(),
+    )
+}
+
+fn scheduled_attachments(db_path: &str) -> Vec<Vec<libplurcast::Attachment>> {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let db = libplurcast::Database::new(db_path).await.unwrap();
+        let mut attachments = Vec::new();
+        for post in db.get_scheduled_posts().await.unwrap() {
+            attachments.push(db.get_attachments_for_post(&post.id).await.unwrap());
+        }
+        attachments
+    })
+}
+
+#[test]
+fn test_media_is_attached_to_scheduled_post() {
+    let (temp_dir, config_path, db_path) = setup_test_env();
+    let bike = temp_dir.path().join("bike.jpg");
+    let map = temp_dir.path().join("map.png");
+    fs::write(&bike, b"fake jpeg").unwrap();
+    fs::write(&map, b"fake png").unwrap();
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("New bike!")
+        .arg("--schedule")
+        .arg("2h")
+        .arg("--media")
+        .arg(format!("{}:A red bicycle", bike.display()))
+        .arg("--media")
+        .arg(&map)
+        .assert()
+        .success();
+
+    let attachments = scheduled_attachments(&db_path);
+    assert_eq!(attachments.len(), 1);
+    assert_eq!(attachments[0].len(), 2);
+    assert_eq!(attachments[0][0].alt_text.as_deref(), Some("A red bicycle"));
+    assert!(attachments[0][0].file_path.ends_with("bike.jpg"));
+    assert_eq!(attachments[0][1].alt_text, None);
+}
+
+#[test]
+fn test_missing_media_is_rejected_before_saving() {
+    let (temp_dir, config_path, db_path) = setup_test_env();
+    let missing = temp_dir.path().join("missing.png");
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Where's the picture?")
+        .arg("--schedule")
+        .arg("2h")
+        .arg("--media")
+        .arg(&missing)
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Cannot attach"));
+
+    assert!(scheduled_attachments(&db_path).is_empty());
+}
+
+#[test]
+fn test_unsupported_media_type_is_rejected() {
+    let (temp_dir, config_path, _db_path) = setup_test_env();
+    let notes = temp_dir.path().join("notes.txt");
+    fs::write(&notes, "not an image").unwrap();
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Text file")
+        .arg("--draft")
+        .arg("--media")
+        .arg(&notes)
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("unsupported file type"));
+}
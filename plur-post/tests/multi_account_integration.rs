@@ -63,6 +63,7 @@ platforms = ["nostr"]
         storage: StorageBackend::Encrypted,
         path: creds_dir.to_string_lossy().to_string(),
         master_password: Some("test_password".to_string()),
+        commands: Default::default(),
     };
     let cred_manager = CredentialManager::new(cred_config).unwrap();
 
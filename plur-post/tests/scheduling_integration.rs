@@ -92,6 +92,111 @@ fn test_schedule_with_duration_minutes() {
         .stdout(predicate::str::contains("scheduled:"));
 }
 
+#[test]
+fn test_schedule_with_content_warning() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Season finale thoughts")
+        .arg("--schedule")
+        .arg("1h")
+        .arg("--cw")
+        .arg("Spoilers")
+        .assert()
+        .success();
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let posts = rt.block_on(async {
+        let db = libplurcast::Database::new(&db_path).await.unwrap();
+        db.get_scheduled_posts().await.unwrap()
+    });
+    let metadata: serde_json::Value =
+        serde_json::from_str(posts[0].metadata.as_ref().unwrap()).unwrap();
+    assert_eq!(metadata["content_warning"], "Spoilers");
+
+    // A blank warning is a mistake, not "no warning"
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Another post")
+        .arg("--schedule")
+        .arg("1h")
+        .arg("--cw")
+        .arg(" ")
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("--cw cannot be empty"));
+}
+
+#[test]
+fn test_schedule_with_hashtags() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Shipping #Rust today")
+        .arg("--schedule")
+        .arg("1h")
+        .arg("--tag")
+        .arg("#rust")
+        .arg("--tag")
+        .arg("release")
+        .arg("--tag")
+        .arg("Release")
+        .assert()
+        .success();
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let posts = rt.block_on(async {
+        let db = libplurcast::Database::new(&db_path).await.unwrap();
+        db.get_scheduled_posts().await.unwrap()
+    });
+    // The text is stored as written; hashtags are applied per platform when sent
+    assert_eq!(posts[0].content, "Shipping #Rust today");
+    let metadata: serde_json::Value =
+        serde_json::from_str(posts[0].metadata.as_ref().unwrap()).unwrap();
+    assert_eq!(metadata["hashtags"], serde_json::json!(["rust", "release"]));
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Bad tag")
+        .arg("--tag")
+        .arg("two words")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("use only letters, digits and '_'"));
+}
+
+#[test]
+fn test_schedule_rejects_visibility_platform_cannot_honor() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Followers only")
+        .arg("--schedule")
+        .arg("1h")
+        .arg("--visibility")
+        .arg("private")
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains(
+            "Nostr posts are always public; visibility 'private' is only supported on Mastodon",
+        ));
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let posts = rt.block_on(async {
+        let db = libplurcast::Database::new(&db_path).await.unwrap();
+        db.get_scheduled_posts().await.unwrap()
+    });
+    assert!(posts.is_empty());
+}
+
 #[test]
 fn test_schedule_with_duration_hours() {
     let (_temp_dir, config_path, _db_path) = setup_test_env();
@@ -325,3 +430,386 @@ fn test_schedule_with_platform_flag() {
         .success()
         .stdout(predicate::str::contains("scheduled:"));
 }
+
+// RECURRING SCHEDULE TESTS
+
+#[tokio::test]
+async fn test_schedule_cron_creates_series() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+
+    let mut cmd = Command::cargo_bin("plur-post").unwrap();
+
+    let output = cmd
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Weekly update")
+        .arg("--schedule")
+        .arg("cron:0 9 * * MON")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["cron"], "0 9 * * MON");
+
+    let db = libplurcast::Database::new(&db_path).await.unwrap();
+    let series = db.list_series().await.unwrap();
+    assert_eq!(series.len(), 1);
+    assert_eq!(series[0].id, result["series_id"].as_str().unwrap());
+    assert_eq!(series[0].content, "Weekly update");
+
+    let post = db
+        .get_post(result["post_id"].as_str().unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        libplurcast::recurrence::series_id(&post).as_deref(),
+        Some(series[0].id.as_str())
+    );
+}
+
+#[test]
+fn test_schedule_invalid_cron() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env();
+
+    let mut cmd = Command::cargo_bin("plur-post").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("Test post")
+        .arg("--schedule")
+        .arg("cron:0 25 * * *")
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("Invalid cron expression"));
+}
+
+#[test]
+fn test_schedule_best_without_history() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env();
+
+    let mut cmd = Command::cargo_bin("plur-post").unwrap();
+
+    let output = cmd
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Test post")
+        .arg("--schedule")
+        .arg("best")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let scheduled_at = result["scheduled_at"].as_i64().unwrap();
+
+    // Falls back to the next 09:00 local time
+    let now = chrono::Utc::now().timestamp();
+    assert!(scheduled_at > now && scheduled_at <= now + 2 * 86400);
+    let local = chrono::DateTime::from_timestamp(scheduled_at, 0)
+        .unwrap()
+        .with_timezone(&chrono::Local);
+    assert_eq!(chrono::Timelike::hour(&local), 9);
+}
+
+#[tokio::test]
+async fn test_schedule_with_jitter_stores_metadata() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+
+    let mut cmd = Command::cargo_bin("plur-post").unwrap();
+
+    let output = cmd
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Test post")
+        .arg("--schedule")
+        .arg("1h")
+        .arg("--jitter")
+        .arg("10m")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let db = libplurcast::Database::new(&db_path).await.unwrap();
+    let post = db
+        .get_post(result["post_id"].as_str().unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(libplurcast::scheduling::post_jitter(&post), Some(600));
+}
+
+#[test]
+fn test_jitter_requires_schedule() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env();
+
+    let mut cmd = Command::cargo_bin("plur-post").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("Test post")
+        .arg("--jitter")
+        .arg("10m")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_jitter_too_large() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env();
+
+    let mut cmd = Command::cargo_bin("plur-post").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("Test post")
+        .arg("--schedule")
+        .arg("1h")
+        .arg("--jitter")
+        .arg("1d")
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("Jitter must be at most"));
+}
+
+/// Schedule a post and return its ID
+fn schedule_post(config_path: &str, content: &str, schedule: &str) -> serde_json::Value {
+    let output = Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", config_path)
+        .arg(content)
+        .arg("--schedule")
+        .arg(schedule)
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[tokio::test]
+async fn test_schedule_after_anchor_post() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+
+    let anchor = schedule_post(&config_path, "Announcement", "2h");
+    let anchor_id = anchor["post_id"].as_str().unwrap();
+    let follow_up = schedule_post(
+        &config_path,
+        "Follow-up",
+        &format!("after:{}+30m", anchor_id),
+    );
+
+    // Estimated from the anchor's scheduled time until it's sent
+    assert_eq!(
+        follow_up["scheduled_at"].as_i64().unwrap(),
+        anchor["scheduled_at"].as_i64().unwrap() + 1800
+    );
+
+    let db = libplurcast::Database::new(&db_path).await.unwrap();
+    let post = db
+        .get_post(follow_up["post_id"].as_str().unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    let stored = libplurcast::anchor::post_anchor(&post).unwrap();
+    assert_eq!(stored.post_id, anchor_id);
+    assert_eq!(stored.delay, 1800);
+}
+
+#[test]
+fn test_schedule_after_unknown_post() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env();
+
+    let mut cmd = Command::cargo_bin("plur-post").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("Follow-up")
+        .arg("--schedule")
+        .arg(format!("after:{}+30m", uuid::Uuid::new_v4()))
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("Anchor post not found"));
+}
+
+#[tokio::test]
+async fn test_schedule_for_stores_platform_times() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+
+    let output = Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("Staggered post")
+        .arg("--schedule-for")
+        .arg("nostr=1h")
+        .arg("--schedule-for")
+        .arg("mastodon=3h")
+        .arg("--format")
+        .arg("json")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["platforms"].as_array().unwrap().len(), 2);
+    assert_eq!(result["platforms"][0]["platform"], "nostr");
+
+    let db = libplurcast::Database::new(&db_path).await.unwrap();
+    let post_id = result["post_id"].as_str().unwrap();
+    let post = db.get_post(post_id).await.unwrap().unwrap();
+    let schedules = db.get_platform_schedules(post_id).await.unwrap();
+    assert_eq!(schedules.len(), 2);
+    assert_eq!(schedules[0].platform, "nostr");
+    assert_eq!(schedules[1].platform, "mastodon");
+    assert_eq!(post.scheduled_at, Some(schedules[0].scheduled_at));
+    assert!(schedules[1].scheduled_at - schedules[0].scheduled_at >= 2 * 3600 - 5);
+}
+
+#[tokio::test]
+async fn test_schedule_for_offsets_thread_parts() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+
+    let content = "This sentence is padding for a long post. ".repeat(20);
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg(content.trim())
+        .arg("--auto-thread")
+        .arg("--schedule-for")
+        .arg("nostr=1h")
+        .arg("--schedule-for")
+        .arg("mastodon=3h")
+        .assert()
+        .success();
+
+    let db = libplurcast::Database::new(&db_path).await.unwrap();
+    let parts = db.get_scheduled_posts().await.unwrap();
+    assert!(parts.len() > 1);
+
+    // Each part goes out a gap after the one before, on every platform
+    let first = db.get_platform_schedules(&parts[0].id).await.unwrap();
+    for (i, part) in parts.iter().enumerate() {
+        let schedules = db.get_platform_schedules(&part.id).await.unwrap();
+        assert_eq!(schedules.len(), 2);
+        for (schedule, first) in schedules.iter().zip(&first) {
+            assert_eq!(schedule.platform, first.platform);
+            assert_eq!(schedule.scheduled_at, first.scheduled_at + i as i64 * 60);
+        }
+        assert_eq!(part.scheduled_at, Some(schedules[0].scheduled_at));
+    }
+}
+
+#[test]
+fn test_schedule_for_needs_time_for_every_platform() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env();
+
+    let mut cmd = Command::cargo_bin("plur-post").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("Staggered post")
+        .arg("--platform")
+        .arg("nostr")
+        .arg("--platform")
+        .arg("mastodon")
+        .arg("--schedule-for")
+        .arg("nostr=1h")
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("No schedule for mastodon"));
+}
+
+// BATCH SCHEDULE TESTS
+
+/// Post NDJSON lines with --batch and return one result per line
+fn post_batch(config_path: &str, lines: &[serde_json::Value]) -> Vec<serde_json::Value> {
+    let stdin: String = lines.iter().map(|line| format!("{}\n", line)).collect();
+    let output = Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", config_path)
+        .arg("--batch")
+        .write_stdin(stdin)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_batch_schedule_best() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env();
+
+    let results = post_batch(
+        &config_path,
+        &[serde_json::json!({"content": "Best time", "schedule": "best"})],
+    );
+
+    assert_eq!(results[0]["status"], "scheduled");
+    let scheduled_at = results[0]["scheduled_at"].as_i64().unwrap();
+    let local = chrono::DateTime::from_timestamp(scheduled_at, 0)
+        .unwrap()
+        .with_timezone(&chrono::Local);
+    assert_eq!(chrono::Timelike::hour(&local), 9);
+}
+
+#[tokio::test]
+async fn test_batch_schedule_cron_creates_series() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+
+    let results = post_batch(
+        &config_path,
+        &[serde_json::json!({"content": "Weekly update", "schedule": "cron:0 9 * * MON"})],
+    );
+
+    assert_eq!(results[0]["status"], "scheduled");
+    assert_eq!(results[0]["cron"], "0 9 * * MON");
+
+    let db = libplurcast::Database::new(&db_path).await.unwrap();
+    let series = db.list_series().await.unwrap();
+    assert_eq!(series.len(), 1);
+    assert_eq!(series[0].id, results[0]["series_id"].as_str().unwrap());
+}
+
+#[tokio::test]
+async fn test_batch_schedule_after_anchor_post() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+
+    let anchor = schedule_post(&config_path, "Announcement", "2h");
+    let anchor_id = anchor["post_id"].as_str().unwrap();
+    let results = post_batch(
+        &config_path,
+        &[serde_json::json!({
+            "content": "Follow-up",
+            "schedule": format!("after:{}+30m", anchor_id),
+        })],
+    );
+
+    assert_eq!(results[0]["status"], "scheduled");
+    assert_eq!(
+        results[0]["scheduled_at"].as_i64().unwrap(),
+        anchor["scheduled_at"].as_i64().unwrap() + 1800
+    );
+
+    let db = libplurcast::Database::new(&db_path).await.unwrap();
+    let post = db
+        .get_post(results[0]["post_id"].as_str().unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    let stored = libplurcast::anchor::post_anchor(&post).unwrap();
+    assert_eq!(stored.post_id, anchor_id);
+    assert_eq!(stored.delay, 1800);
+}
@@ -0,0 +1,140 @@
+//! Integration tests for plur-post --template
+//!
+//! Templates live in the `templates` directory next to the config file and
+//! are rendered with --var values and built-ins before posting.
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Helper to escape path for TOML on Windows
+fn escape_path_for_toml(path: &str) -> String {
+    path.replace('\\', "\\\\")
+}
+
+/// Helper to create a test environment with config and database
+fn setup_test_env() -> (TempDir, String, String) {
+    let temp_dir = TempDir::new().unwrap();
+
+    // Create config directory
+    let config_dir = temp_dir.path().join("config");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    // Create data directory
+    let data_dir = temp_dir.path().join("data");
+    fs::create_dir_all(&data_dir).unwrap();
+
+    // Create config file
+    let config_path = config_dir.join("config.toml");
+    let db_path = data_dir.join("posts.db");
+    let keys_path = config_dir.join("nostr.keys");
+
+    let config_content = format!(
+        r#"
+[database]
+path = "{}"
+
+[nostr]
+enabled = true
+keys_file = "{}"
+relays = ["wss://relay.damus.io"]
+
+[defaults]
+platforms = ["nostr"]
+"#,
+        escape_path_for_toml(&db_path.to_string_lossy()),
+        escape_path_for_toml(&keys_path.to_string_lossy())
+    );
+
+    fs::write(&config_path, config_content).unwrap();
+
+    // Generate test Nostr keys
+    let test_keys = nostr_sdk::Keys::generate();
+    let hex_key = test_keys.secret_key().to_secret_hex();
+    fs::write(&keys_path, hex_key).unwrap();
+
+    (
+        temp_dir,
+        config_path.to_string_lossy().to_string(),
+        db_path.to_string_lossy().to_string(),
+    )
+}
+
+fn scheduled_posts(db_path: &str) -> Vec<libplurcast::Post> {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let db = libplurcast::Database::new(db_path).await.unwrap();
+        db.get_scheduled_posts().await.unwrap()
+    })
+}
+
+fn write_template(config_path: &str, name: &str, text: &str) {
+    let dir = std::path::Path::new(config_path)
+        .parent()
+        .unwrap()
+        .join("templates");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join(name), text).unwrap();
+}
+
+#[test]
+fn test_template_renders_variables_and_frontmatter() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+    write_template(
+        &config_path,
+        "release.md",
+        "---\nschedule: 2h\ntags: [release]\n---\n\nRelease {{version}} is out ({{ date }})\n",
+    );
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("--template")
+        .arg("release")
+        .arg("--var")
+        .arg("version=1.2.0")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("scheduled:"));
+
+    let posts = scheduled_posts(&db_path);
+    assert_eq!(posts.len(), 1);
+    assert!(posts[0].content.starts_with("Release 1.2.0 is out ("));
+    assert!(!posts[0].content.contains("{{"));
+}
+
+#[test]
+fn test_template_missing_variable_fails() {
+    let (_temp_dir, config_path, db_path) = setup_test_env();
+    write_template(&config_path, "release.md", "Release {{version}}\n");
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("--template")
+        .arg("release")
+        .arg("--schedule")
+        .arg("1h")
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains(
+            "Template variables not set: version",
+        ));
+
+    assert!(scheduled_posts(&db_path).is_empty());
+}
+
+#[test]
+fn test_template_not_found() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env();
+
+    Command::cargo_bin("plur-post")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("--template")
+        .arg("nope")
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Template 'nope' not found"));
+}
@@ -0,0 +1,169 @@
+//! iCalendar (RFC 5545) export of the scheduled queue
+
+use chrono::{DateTime, Utc};
+use libplurcast::Post;
+
+/// How long each post occupies in the calendar
+const EVENT_DURATION: &str = "PT15M";
+
+/// Longest SUMMARY, in characters, before it's cut off
+const SUMMARY_CHARS: usize = 60;
+
+/// Render scheduled posts as a VCALENDAR document
+///
+/// Each post with a scheduled time becomes a 15 minute VEVENT at that time
+/// (UTC), with the post ID as its UID so re-imports update rather than
+/// duplicate events.
+pub fn calendar(posts: &[Post], platforms_of: impl Fn(&Post) -> Vec<String>) -> String {
+    let stamp = format_time(Utc::now().timestamp());
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Plurcast//plur-queue//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Plurcast queue".to_string(),
+    ];
+
+    for post in posts {
+        let scheduled_at = match post.scheduled_at {
+            Some(at) => at,
+            None => continue,
+        };
+        let platforms = platforms_of(post);
+
+        let mut description = post.content.clone();
+        description.push_str(&format!("\n\nPost ID: {}", post.id));
+        if !platforms.is_empty() {
+            description.push_str(&format!("\nPlatforms: {}", platforms.join(", ")));
+        }
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@plurcast", post.id));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("DTSTART:{}", format_time(scheduled_at)));
+        lines.push(format!("DURATION:{}", EVENT_DURATION));
+        lines.push(format!("SUMMARY:{}", escape(&summary(&post.content))));
+        lines.push(format!("DESCRIPTION:{}", escape(&description)));
+        if !platforms.is_empty() {
+            let categories: Vec<String> = platforms.iter().map(|p| escape(p)).collect();
+            lines.push(format!("CATEGORIES:{}", categories.join(",")));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+/// UTC date-time in iCalendar basic format (20250101T090000Z)
+fn format_time(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .unwrap_or_default()
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+/// First line of the content, cut to a calendar-friendly length
+fn summary(content: &str) -> String {
+    let first_line = content.lines().next().unwrap_or("").trim();
+    if first_line.chars().count() <= SUMMARY_CHARS {
+        first_line.to_string()
+    } else {
+        let cut: String = first_line.chars().take(SUMMARY_CHARS).collect();
+        format!("{}...", cut.trim_end())
+    }
+}
+
+/// Escape a TEXT value
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Fold a content line at 75 octets without splitting a character
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + line.len() / 74 * 3);
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += len;
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libplurcast::PostStatus;
+
+    fn post(id: &str, content: &str, scheduled_at: Option<i64>) -> Post {
+        Post {
+            id: id.to_string(),
+            content: content.to_string(),
+            created_at: 0,
+            scheduled_at,
+            status: PostStatus::Scheduled,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_calendar_has_event_per_scheduled_post() {
+        let posts = vec![
+            post("a", "Morning post", Some(1_735_722_000)),
+            post("b", "No time", None),
+        ];
+        let ics = calendar(&posts, |_| vec!["nostr".to_string()]);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(ics.contains("UID:a@plurcast\r\n"));
+        assert!(ics.contains("DTSTART:20250101T090000Z\r\n"));
+        assert!(ics.contains("SUMMARY:Morning post\r\n"));
+        assert!(ics.contains("CATEGORIES:nostr\r\n"));
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape("a, b; c\\d\nnext"), "a\\, b\\; c\\\\d\\nnext");
+    }
+
+    #[test]
+    fn test_fold_long_lines() {
+        let line = format!("DESCRIPTION:{}", "é".repeat(60));
+        let folded = fold(&line);
+        for part in folded.split("\r\n") {
+            assert!(part.len() <= 75);
+        }
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+
+    #[test]
+    fn test_summary_uses_first_line() {
+        assert_eq!(summary("Title\nBody"), "Title");
+        assert_eq!(summary(&"x".repeat(80)), format!("{}...", "x".repeat(60)));
+    }
+}
@@ -0,0 +1,366 @@
+//! Reading scheduled posts for `plur-queue import`
+//!
+//! Two formats are read:
+//!
+//! - **CSV** with a header row naming the columns `time`, `content`,
+//!   `platforms`, `tags` and `hashtags` (only `time` and `content` are
+//!   required). As in `plur-post --from-file` frontmatter, `tags` are
+//!   labels (`labels` is accepted too). Fields are RFC 4180: quoted when
+//!   they hold commas, quotes or line breaks. Lists may be separated by
+//!   commas, semicolons, `|` or spaces.
+//! - **JSON**: an array of objects with the same fields, or an object
+//!   holding such an array under `posts` as written by
+//!   `plur-queue export --format json`. Objects may also carry
+//!   `scheduled_at` (Unix time) instead of `time`, the post's `id`,
+//!   `metadata`, `priority` and `platform_schedules`, so exports and
+//!   `plur-queue list --format json` output import as is.
+//!
+//! Times are Unix timestamps, RFC 3339, or anything `plur-post --schedule`
+//! accepts ("2025-11-20 15:00", "tomorrow 9am").
+
+use libplurcast::{PlurcastError, Result};
+use serde_json::{Map, Value};
+
+/// One post to schedule, as read from the file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportRow {
+    /// CSV line or JSON entry (from 1) the row came from, for reports
+    pub line: usize,
+    /// Post ID to keep, from an export
+    pub id: Option<String>,
+    pub time: ImportTime,
+    pub content: String,
+    pub platforms: Vec<String>,
+    pub hashtags: Vec<String>,
+    pub labels: Vec<String>,
+    pub metadata: Option<Map<String, Value>>,
+    pub priority: Option<i64>,
+    /// Per-platform send times of a staggered post
+    pub platform_schedules: Vec<(String, i64)>,
+}
+
+/// When a row should be sent
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportTime {
+    Timestamp(i64),
+    Text(String),
+}
+
+impl ImportTime {
+    /// Resolve the time to a Unix timestamp
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if the time can't be parsed.
+    pub fn resolve(&self) -> Result<i64> {
+        let text = match self {
+            Self::Timestamp(timestamp) => return Ok(*timestamp),
+            Self::Text(text) => text.trim(),
+        };
+        if let Ok(timestamp) = text.parse::<i64>() {
+            return Ok(timestamp);
+        }
+        if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(text) {
+            return Ok(dt.timestamp());
+        }
+        Ok(libplurcast::scheduling::parse_schedule(text, None)?.timestamp())
+    }
+}
+
+/// Read rows from CSV text
+///
+/// # Errors
+///
+/// Returns `InvalidInput` for a missing or unknown column, an unclosed
+/// quote, or a row with more fields than the header.
+pub fn parse_csv(text: &str) -> Result<Vec<ImportRow>> {
+    let mut records = csv_records(text)?.into_iter();
+    let (_, header) = match records.next() {
+        Some(header) => header,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut columns = Vec::with_capacity(header.len());
+    for name in &header {
+        let name = name.trim().to_ascii_lowercase();
+        if !["time", "content", "platforms", "tags", "labels", "hashtags"].contains(&name.as_str())
+        {
+            return Err(PlurcastError::InvalidInput(format!(
+                "Unknown CSV column '{}' (expected time, content, platforms, tags, hashtags)",
+                name
+            )));
+        }
+        columns.push(name);
+    }
+    for required in ["time", "content"] {
+        if !columns.iter().any(|c| c == required) {
+            return Err(PlurcastError::InvalidInput(format!(
+                "CSV header has no '{}' column",
+                required
+            )));
+        }
+    }
+
+    let mut rows = Vec::new();
+    for (line, fields) in records {
+        if fields.len() > columns.len() {
+            return Err(PlurcastError::InvalidInput(format!(
+                "line {}: {} fields but the header has {} columns",
+                line,
+                fields.len(),
+                columns.len()
+            )));
+        }
+        let field = |name: &str| {
+            columns
+                .iter()
+                .position(|c| c == name)
+                .and_then(|i| fields.get(i))
+                .map(String::as_str)
+                .unwrap_or("")
+        };
+
+        rows.push(ImportRow {
+            line,
+            id: None,
+            time: ImportTime::Text(field("time").to_string()),
+            content: field("content").to_string(),
+            platforms: split_list(field("platforms")),
+            hashtags: split_list(field("hashtags")),
+            labels: split_list(field("tags"))
+                .into_iter()
+                .chain(split_list(field("labels")))
+                .collect(),
+            metadata: None,
+            priority: None,
+            platform_schedules: Vec::new(),
+        });
+    }
+    Ok(rows)
+}
+
+/// Read rows from JSON text
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if the text isn't JSON of the expected shape.
+pub fn parse_json(text: &str) -> Result<Vec<ImportRow>> {
+    let value: Value = serde_json::from_str(text)
+        .map_err(|e| PlurcastError::InvalidInput(format!("Invalid JSON: {}", e)))?;
+    let entries = match value {
+        Value::Array(entries) => entries,
+        Value::Object(mut object) => match object.remove("posts") {
+            Some(Value::Array(entries)) => entries,
+            _ => {
+                return Err(PlurcastError::InvalidInput(
+                    "Expected a JSON array of posts or an object with a 'posts' array".to_string(),
+                ))
+            }
+        },
+        _ => {
+            return Err(PlurcastError::InvalidInput(
+                "Expected a JSON array of posts or an object with a 'posts' array".to_string(),
+            ))
+        }
+    };
+
+    let mut rows = Vec::with_capacity(entries.len());
+    for (i, entry) in entries.into_iter().enumerate() {
+        let line = i + 1;
+        let object = match entry {
+            Value::Object(object) => object,
+            _ => {
+                return Err(PlurcastError::InvalidInput(format!(
+                    "entry {}: expected an object",
+                    line
+                )))
+            }
+        };
+
+        let time = match (object.get("time"), object.get("scheduled_at")) {
+            (Some(Value::String(text)), _) => ImportTime::Text(text.clone()),
+            (Some(Value::Number(n)), _) | (None, Some(Value::Number(n))) => match n.as_i64() {
+                Some(timestamp) => ImportTime::Timestamp(timestamp),
+                None => {
+                    return Err(PlurcastError::InvalidInput(format!(
+                        "entry {}: time must be a whole Unix timestamp",
+                        line
+                    )))
+                }
+            },
+            _ => ImportTime::Text(String::new()),
+        };
+
+        rows.push(ImportRow {
+            line,
+            id: object.get("id").and_then(Value::as_str).map(String::from),
+            time,
+            content: object
+                .get("content")
+                .and_then(Value::as_str)
+                .unwrap_or("")
+                .to_string(),
+            platforms: json_list(object.get("platforms")),
+            hashtags: json_list(object.get("hashtags")),
+            labels: json_list(object.get("tags").or_else(|| object.get("labels"))),
+            metadata: match object.get("metadata") {
+                Some(Value::Object(metadata)) => Some(metadata.clone()),
+                // Exports from the database hold metadata as a JSON string
+                Some(Value::String(metadata)) => serde_json::from_str(metadata).ok(),
+                _ => None,
+            },
+            priority: object.get("priority").and_then(Value::as_i64),
+            platform_schedules: match object.get("platform_schedules") {
+                Some(Value::Object(schedules)) => schedules
+                    .iter()
+                    .filter_map(|(platform, at)| Some((platform.clone(), at.as_i64()?)))
+                    .collect(),
+                _ => Vec::new(),
+            },
+        });
+    }
+    Ok(rows)
+}
+
+/// A list given as a JSON array or a delimited string
+fn json_list(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .flat_map(split_list)
+            .collect(),
+        Some(Value::String(text)) => split_list(text),
+        _ => Vec::new(),
+    }
+}
+
+/// Split a list on commas, semicolons, `|` or whitespace
+fn split_list(text: &str) -> Vec<String> {
+    text.split(|c: char| c == ',' || c == ';' || c == '|' || c.is_whitespace())
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Split CSV text into records, each with the line it starts on
+///
+/// Blank lines are skipped.
+fn csv_records(text: &str) -> Result<Vec<(usize, Vec<String>)>> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut start_line = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => fields.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                if !(fields.len() == 1 && fields[0].is_empty()) {
+                    records.push((start_line, std::mem::take(&mut fields)));
+                }
+                fields.clear();
+                line += 1;
+                start_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(PlurcastError::InvalidInput(format!(
+            "line {}: unclosed quote",
+            start_line
+        )));
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        records.push((start_line, fields));
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let csv = "time,content,platforms,tags,hashtags\r\n\
+                   2030-01-01T09:00:00Z,\"Hello, \"\"world\"\"\",nostr;mastodon,launch,rust\r\n\
+                   \r\n\
+                   +1d,\"Two\nlines\",,,\n";
+        let rows = parse_csv(csv).unwrap();
+        assert_eq!(rows.len(), 2);
+
+        assert_eq!(rows[0].line, 2);
+        assert_eq!(rows[0].content, "Hello, \"world\"");
+        assert_eq!(rows[0].platforms, vec!["nostr", "mastodon"]);
+        assert_eq!(rows[0].labels, vec!["launch"]);
+        assert_eq!(rows[0].hashtags, vec!["rust"]);
+        assert_eq!(rows[0].time.resolve().unwrap(), 1_893_488_400);
+
+        assert_eq!(rows[1].line, 4);
+        assert_eq!(rows[1].content, "Two\nlines");
+        assert!(rows[1].platforms.is_empty());
+    }
+
+    #[test]
+    fn test_parse_csv_errors() {
+        assert!(parse_csv("when,content\n").is_err());
+        assert!(parse_csv("content\n").is_err());
+        assert!(parse_csv("time,content\nnow,\"open\n").is_err());
+        assert!(parse_csv("time,content\nnow,a,b\n").is_err());
+        assert!(parse_csv("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let json = r#"{"posts": [
+            {"id": "a", "scheduled_at": 1893488400, "content": "A", "metadata": "{\"platforms\":[\"ssb\"]}", "labels": ["x"], "priority": 2, "platform_schedules": {"ssb": 1893492000}},
+            {"time": "2030-01-01 09:00", "content": "B", "platforms": "nostr,mastodon", "hashtags": ["rust"]}
+        ]}"#;
+        let rows = parse_json(json).unwrap();
+        assert_eq!(rows.len(), 2);
+
+        assert_eq!(rows[0].time, ImportTime::Timestamp(1_893_488_400));
+        assert_eq!(rows[0].metadata.as_ref().unwrap()["platforms"][0], "ssb");
+        assert_eq!(rows[0].labels, vec!["x"]);
+        assert_eq!(rows[0].priority, Some(2));
+        assert_eq!(rows[0].id.as_deref(), Some("a"));
+        assert_eq!(
+            rows[0].platform_schedules,
+            vec![("ssb".to_string(), 1_893_492_000)]
+        );
+
+        assert_eq!(rows[1].line, 2);
+        assert_eq!(rows[1].platforms, vec!["nostr", "mastodon"]);
+        assert_eq!(rows[1].hashtags, vec!["rust"]);
+        assert!(rows[1].id.is_none());
+
+        assert!(parse_json("[1]").is_err());
+        assert!(parse_json("{}").is_err());
+    }
+}
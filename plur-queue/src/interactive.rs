@@ -0,0 +1,135 @@
+//! Keyboard commands for `plur-queue interactive`
+//!
+//! The interactive mode lists the queue a page at a time and reads one
+//! command per line. Posts are picked by their number in the listing:
+//!
+//! - `c 3` cancel post 3 (asks first)
+//! - `r 3 tomorrow 9am` reschedule post 3 (relative times like `+2h` work)
+//! - `s 3` send post 3 now
+//! - `v 3` show post 3 as each platform will receive it
+//! - `b 3` bump post 3's priority
+//! - `n` / `p` next or previous page, `l` list again
+//! - `?` help, `q` quit
+
+use libplurcast::{PlurcastError, Result};
+
+/// Posts shown per page
+pub const PAGE_SIZE: usize = 10;
+
+/// Help printed for `?`
+pub const HELP: &str = "\
+Commands (N is the post number in the listing):
+  c N          cancel post N
+  r N TIME     reschedule post N (e.g. \"r 2 tomorrow 9am\", \"r 2 +1h\")
+  s N          send post N now
+  v N          show post N as each platform will receive it
+  b N          bump post N's priority
+  n, p         next or previous page
+  l            list the queue again
+  ?            show this help
+  q            quit";
+
+/// One command read at the prompt
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Cancel(usize),
+    Reschedule(usize, String),
+    SendNow(usize),
+    Show(usize),
+    Bump(usize),
+    NextPage,
+    PreviousPage,
+    List,
+    Help,
+    Quit,
+}
+
+/// Parse a line typed at the prompt
+///
+/// Post numbers are the 1-based numbers shown in the listing.
+///
+/// # Errors
+///
+/// Returns `InvalidInput` for an unknown command, or a missing or
+/// non-numeric post number.
+pub fn parse(line: &str) -> Result<Action> {
+    let line = line.trim();
+    let (command, rest) = match line.split_once(char::is_whitespace) {
+        Some((command, rest)) => (command, rest.trim()),
+        None => (line, ""),
+    };
+
+    let action = match command {
+        "c" | "cancel" => Action::Cancel(number(rest)?),
+        "s" | "send" => Action::SendNow(number(rest)?),
+        "v" | "view" | "show" => Action::Show(number(rest)?),
+        "b" | "bump" => Action::Bump(number(rest)?),
+        "r" | "reschedule" => {
+            let (n, time) = rest.split_once(char::is_whitespace).ok_or_else(|| {
+                PlurcastError::InvalidInput("Usage: r N TIME (e.g. \"r 2 +1h\")".to_string())
+            })?;
+            Action::Reschedule(number(n)?, time.trim().to_string())
+        }
+        "n" | "next" => Action::NextPage,
+        "p" | "prev" => Action::PreviousPage,
+        "l" | "list" | "" => Action::List,
+        "?" | "h" | "help" => Action::Help,
+        "q" | "quit" | "exit" => Action::Quit,
+        _ => {
+            return Err(PlurcastError::InvalidInput(format!(
+                "Unknown command '{}' (? for help)",
+                command
+            )))
+        }
+    };
+    Ok(action)
+}
+
+/// Parse a post number from the listing
+fn number(text: &str) -> Result<usize> {
+    match text.trim().parse::<usize>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ if text.trim().is_empty() => Err(PlurcastError::InvalidInput(
+            "Which post? Give its number from the listing".to_string(),
+        )),
+        _ => Err(PlurcastError::InvalidInput(format!(
+            "'{}' is not a post number",
+            text.trim()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("c 3").unwrap(), Action::Cancel(3));
+        assert_eq!(parse("  s 1 ").unwrap(), Action::SendNow(1));
+        assert_eq!(parse("v 2").unwrap(), Action::Show(2));
+        assert_eq!(parse("bump 4").unwrap(), Action::Bump(4));
+        assert_eq!(
+            parse("r 2 tomorrow 9am").unwrap(),
+            Action::Reschedule(2, "tomorrow 9am".to_string())
+        );
+        assert_eq!(
+            parse("r 1 -30m").unwrap(),
+            Action::Reschedule(1, "-30m".to_string())
+        );
+        assert_eq!(parse("n").unwrap(), Action::NextPage);
+        assert_eq!(parse("p").unwrap(), Action::PreviousPage);
+        assert_eq!(parse("").unwrap(), Action::List);
+        assert_eq!(parse("?").unwrap(), Action::Help);
+        assert_eq!(parse("q").unwrap(), Action::Quit);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("x").is_err());
+        assert!(parse("c").is_err());
+        assert!(parse("c 0").is_err());
+        assert!(parse("c two").is_err());
+        assert!(parse("r 2").is_err());
+    }
+}
@@ -0,0 +1,2833 @@
+//! plur-queue - Manage scheduled posts
+//!
+//! Unix-style tool for managing the scheduled post queue.
+
+use clap::{Parser, Subcommand};
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use libplurcast::{Config, Database, Result};
+use std::collections::HashMap;
+use std::ffi::OsString;
+
+mod ics;
+mod import;
+mod interactive;
+
+#[derive(Parser, Debug)]
+#[command(name = "plur-queue")]
+#[command(version)]
+#[command(about = "Manage scheduled posts")]
+#[command(long_about = "\
+plur-queue - Manage scheduled posts
+
+DESCRIPTION:
+    plur-queue is a Unix-style tool for managing scheduled posts in the Plurcast queue.
+    Use it to list, cancel, reschedule, or view statistics about your scheduled posts.
+
+COMMANDS:
+    list        List all scheduled posts
+    show        Preview a post as each platform will receive it
+    interactive Browse the queue and cancel, reschedule or send posts
+    cancel      Cancel a scheduled post
+    reschedule  Reschedule a post to a different time
+    shift       Move every (or every matching) scheduled post by an offset
+    edit        Change the content, platforms or metadata of a scheduled post
+    bump        Change a post's priority when several are due at once
+    now         Post a scheduled post immediately
+    update      Update post metadata (e.g., Nostr PoW difficulty)
+    stats       Show statistics about scheduled posts
+    import      Load scheduled posts from a CSV or JSON content calendar
+    export      Write the queue as an iCalendar (.ics) file or JSON backup
+    pause       Stop plur-send from sending anything (nothing is cancelled)
+    resume      Let plur-send send again after a pause
+    series      List, pause, resume or cancel recurring posts
+    purge       Permanently remove cancelled and cleared posts
+    maintenance Check database integrity and compact it
+
+USAGE EXAMPLES:
+    # List all scheduled posts
+    plur-queue list
+
+    # List posts in JSON format
+    plur-queue list --format json
+
+    # See what each platform will get, when, and from which account
+    plur-queue show <POST_ID>
+
+    # Page through the queue and act on posts by number (? for help)
+    plur-queue interactive
+
+    # Cancel a specific post
+    plur-queue cancel <POST_ID>
+
+    # Reschedule a post
+    plur-queue reschedule <POST_ID> \"tomorrow 3pm\"
+
+    # Load a content calendar (columns: time, content, platforms, tags)
+    plur-queue import schedule.csv --dry-run
+    plur-queue import schedule.csv
+
+    # Push the whole queue back two hours (preview first)
+    plur-queue shift --all +2h --dry-run
+    plur-queue shift --all +2h
+
+    # Move only the Nostr posts a day later
+    plur-queue reschedule --filter platform=nostr \"+1d\"
+
+    # Fix a typo in a scheduled post (opens $EDITOR)
+    plur-queue edit <POST_ID>
+
+    # Replace the content from a pipe
+    echo \"Corrected text\" | plur-queue edit <POST_ID>
+
+    # Change where and how a scheduled post goes out (content is kept)
+    plur-queue edit <POST_ID> --platform nostr,mastodon --content-warning \"Spoilers\"
+
+    # Send a post before others due in the same poll
+    plur-queue bump <POST_ID>
+    plur-queue bump <POST_ID> --priority 10
+
+    # Post a scheduled post immediately
+    plur-queue now <POST_ID>
+
+    # Update Nostr PoW difficulty for a post
+    plur-queue update <POST_ID> --nostr-pow 28
+
+    # View queue statistics
+    plur-queue stats
+
+    # See the queue in your calendar app
+    plur-queue export --format ics -o queue.ics
+
+    # Back up the queue before an upgrade, and load it back if needed
+    plur-queue export --format json -o queue.json
+    plur-queue import queue.json
+
+    # Freeze all scheduled posting during an incident, then carry on
+    plur-queue pause --reason \"incident\"
+    plur-queue resume
+
+    # List recurring posts (plur-post --schedule \"cron:0 9 * * MON\")
+    plur-queue series list
+
+    # Stop a recurring post for a while, then pick it up again
+    plur-queue series pause <SERIES_ID>
+    plur-queue series resume <SERIES_ID>
+
+    # Permanently remove posts cancelled more than 30 days ago
+    plur-queue purge --older-than 30
+
+    # Check integrity, VACUUM and ANALYZE the database
+    plur-queue maintenance
+
+CONFIGURATION:
+    Configuration file: ~/.config/plurcast/config.toml
+    Database location: ~/.local/share/plurcast/posts.db
+
+    Override with environment variables:
+        PLURCAST_CONFIG    - Path to config file
+        PLURCAST_DB_PATH   - Path to database file
+
+EXIT CODES:
+    0 - Success
+    1 - Operation failed
+    2 - Database or configuration error
+    3 - Invalid input (bad post ID, time format, etc.)
+
+For more information, visit: https://github.com/plurcast/plurcast
+")]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+
+    /// Enable verbose logging to stderr
+    #[arg(short, long, global = true)]
+    #[arg(help = "Enable verbose logging to stderr (useful for debugging)")]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT",
+        global = true
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL",
+        global = true
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// List scheduled posts
+    List {
+        /// Output format: text or json
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Filter by platform
+        #[arg(short, long)]
+        platform: Option<String>,
+    },
+
+    /// Preview a post as each platform will receive it
+    ///
+    /// Shows the content with hashtags applied, the send time in local
+    /// time, the account it goes out under, and any content warning,
+    /// visibility or thread it belongs to.
+    Show {
+        /// Post ID to show
+        post_id: String,
+
+        /// Output format: text or json
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+
+    /// Browse the queue and act on posts from a prompt
+    ///
+    /// Lists the queue a page at a time and reads one command per line:
+    /// `c N` cancel, `r N TIME` reschedule, `s N` send now, `v N` preview,
+    /// `b N` bump, `n`/`p` page, `?` help, `q` quit.
+    Interactive,
+
+    /// Cancel a scheduled post
+    Cancel {
+        /// Post ID to cancel
+        post_id: Option<String>,
+
+        /// Cancel all scheduled posts
+        #[arg(long)]
+        all: bool,
+
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Reschedule a post, or every post matching --filter
+    ///
+    /// With --filter the only argument is a relative time ("+1d", "-30m")
+    /// applied to each matching post, as with `shift`.
+    Reschedule {
+        /// Post ID to reschedule (the time, when --filter is used)
+        #[arg(allow_hyphen_values = true)]
+        post_id: String,
+
+        /// New schedule time (e.g., "tomorrow 3pm", "+2h")
+        #[arg(allow_hyphen_values = true)]
+        time: Option<String>,
+
+        /// Reschedule the posts matching KEY=VALUE (platform, account or label; repeatable)
+        #[arg(long, value_name = "KEY=VALUE", value_parser = parse_filter)]
+        filter: Vec<QueueFilter>,
+
+        /// With --filter, show what would move without changing anything
+        #[arg(long, requires = "filter")]
+        dry_run: bool,
+    },
+
+    /// Move many scheduled posts by the same amount
+    ///
+    /// Shifts every scheduled post (--all) or those matching --filter by a
+    /// relative offset. Nothing is changed if any post would land in the
+    /// past.
+    Shift {
+        /// How far to move the posts (e.g., "+2h", "-30m", "1d")
+        #[arg(allow_hyphen_values = true)]
+        offset: String,
+
+        /// Shift every scheduled post
+        #[arg(long, conflicts_with = "filter")]
+        all: bool,
+
+        /// Shift the posts matching KEY=VALUE (platform, account or label; repeatable)
+        #[arg(long, value_name = "KEY=VALUE", value_parser = parse_filter)]
+        filter: Vec<QueueFilter>,
+
+        /// Show what would move without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Edit the content, platforms or metadata of a scheduled post
+    ///
+    /// Takes the new content as an argument or from stdin; otherwise opens
+    /// $VISUAL/$EDITOR on the current content. When only options are given
+    /// the content is kept. The post is validated for its (new) platforms
+    /// and visibility before anything is saved.
+    Edit {
+        /// Post ID to edit
+        post_id: String,
+
+        /// New content (reads stdin or opens $EDITOR when omitted)
+        content: Option<String>,
+
+        /// Send to these platforms instead (comma-separated or repeated)
+        #[arg(long, value_name = "PLATFORM", value_delimiter = ',')]
+        #[arg(value_parser = ["nostr", "mastodon", "ssb"])]
+        platform: Vec<String>,
+
+        /// Set the content warning
+        #[arg(long, value_name = "TEXT", conflicts_with = "no_content_warning")]
+        content_warning: Option<String>,
+
+        /// Remove the content warning
+        #[arg(long)]
+        no_content_warning: bool,
+
+        /// Set the visibility (Mastodon only)
+        #[arg(long, value_name = "VISIBILITY")]
+        #[arg(value_parser = ["public", "unlisted", "private", "direct"])]
+        visibility: Option<String>,
+
+        /// Set the language (ISO 639-1 code, e.g. en)
+        #[arg(long, value_name = "CODE")]
+        lang: Option<String>,
+    },
+
+    /// Change a scheduled post's priority
+    ///
+    /// When several posts are due in the same plur-send poll, higher
+    /// priorities are sent first. Posts start at priority 0.
+    Bump {
+        /// Post ID to bump
+        post_id: String,
+
+        /// Set this priority instead of raising it by one
+        #[arg(long, allow_negative_numbers = true)]
+        priority: Option<i64>,
+    },
+
+    /// Post immediately
+    Now {
+        /// Post ID to post now
+        post_id: String,
+    },
+
+    /// Show queue statistics
+    Stats {
+        /// Output format: text or json
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+
+    /// Import scheduled posts from a CSV or JSON file
+    ///
+    /// CSV needs a header naming its columns: time and content, optionally
+    /// platforms, tags (labels) and hashtags. JSON is an array of objects with
+    /// the same fields, or a `plur-queue export --format json` backup (posts
+    /// still in the queue are skipped). Every row is validated first; if any
+    /// is invalid, nothing is imported.
+    Import {
+        /// File to import ("-" reads stdin)
+        #[arg(value_name = "FILE")]
+        file: String,
+
+        /// File format (default: from the file extension, else csv)
+        #[arg(short, long, value_name = "FORMAT")]
+        #[arg(value_parser = ["csv", "json"])]
+        format: Option<String>,
+
+        /// Validate and report without importing
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Export scheduled posts as a calendar or a JSON backup
+    Export {
+        /// Output format: ics, or json for a backup `plur-queue import` can load
+        #[arg(short, long, default_value = "ics")]
+        format: String,
+
+        /// Write to FILE instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<std::path::PathBuf>,
+
+        /// Filter by platform
+        #[arg(short, long)]
+        platform: Option<String>,
+    },
+
+    /// Pause all scheduled posting
+    ///
+    /// plur-send keeps running but sends nothing until `plur-queue resume`.
+    /// Overdue posts go out once the queue is resumed.
+    Pause {
+        /// Why the queue is paused (shown in stats)
+        #[arg(long)]
+        reason: Option<String>,
+    },
+
+    /// Resume scheduled posting after a pause
+    Resume,
+
+    /// Manage failed posts
+    Failed {
+        #[command(subcommand)]
+        action: FailedAction,
+    },
+
+    /// Manage recurring post series
+    Series {
+        #[command(subcommand)]
+        action: SeriesAction,
+    },
+
+    /// Update post metadata
+    Update {
+        /// Post ID to update
+        post_id: String,
+
+        /// Update Nostr Proof of Work difficulty (0-64)
+        #[arg(long, value_name = "DIFFICULTY")]
+        nostr_pow: Option<u8>,
+    },
+
+    /// Permanently remove cancelled and cleared posts
+    ///
+    /// Cancelled posts and cleared failures are kept with a tombstone so
+    /// they stay auditable; purging removes them for good.
+    Purge {
+        /// Only purge posts deleted more than this many days ago
+        #[arg(long, value_name = "DAYS")]
+        older_than: Option<u32>,
+
+        /// Show what would be purged without removing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Check database integrity, then VACUUM and ANALYZE
+    Maintenance {
+        /// Output format: text or json
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum FailedAction {
+    /// List failed posts
+    List {
+        /// Output format: text or json
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+
+    /// Delete all failed posts
+    Clear {
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Delete a specific failed post
+    Delete {
+        /// Post ID to delete
+        post_id: String,
+
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum SeriesAction {
+    /// List recurring post series
+    List {
+        /// Output format: text or json
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+
+    /// Stop scheduling occurrences (the pending one is cancelled)
+    Pause {
+        /// Series ID to pause
+        series_id: String,
+    },
+
+    /// Schedule occurrences again, starting from the next one due
+    Resume {
+        /// Series ID to resume
+        series_id: String,
+    },
+
+    /// Cancel a series and its pending occurrence
+    Cancel {
+        /// Series ID to cancel
+        series_id: String,
+
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+/// Run plur-queue with a command line, program name first
+///
+/// The `plur-queue` binary and `plur queue` both start here.
+#[tokio::main]
+pub async fn main_from(args: Vec<OsString>) {
+    let cli = Cli::parse_from(args);
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    // Run the main logic and handle errors
+    if let Err(e) = run(cli).await {
+        eprintln!("Error: {}", e);
+        std::process::exit(e.exit_code());
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    // Load configuration
+    let config = Config::load()?;
+
+    // Initialize database
+    let db = Database::from_config(&config).await?;
+
+    // Execute command
+    match cli.command {
+        Commands::List { format, platform } => {
+            cmd_list(&db, &format, platform.as_deref()).await?;
+        }
+        Commands::Show { post_id, format } => {
+            cmd_show(&db, &config, &post_id, &format).await?;
+        }
+        Commands::Interactive => {
+            cmd_interactive(&db, &config).await?;
+        }
+        Commands::Cancel {
+            post_id,
+            all,
+            force,
+        } => {
+            cmd_cancel(&db, post_id.as_deref(), all, force).await?;
+        }
+        Commands::Reschedule {
+            post_id,
+            time,
+            filter,
+            dry_run,
+        } => {
+            if filter.is_empty() {
+                let time = time.ok_or_else(|| {
+                    libplurcast::PlurcastError::InvalidInput(
+                        "Missing TIME (e.g. \"tomorrow 3pm\" or \"+2h\")".to_string(),
+                    )
+                })?;
+                cmd_reschedule(&db, &post_id, &time).await?;
+            } else if time.is_some() {
+                return Err(libplurcast::PlurcastError::InvalidInput(
+                    "With --filter, give only the relative time (e.g. \"+1d\")".to_string(),
+                ));
+            } else {
+                cmd_shift(&db, &config, &post_id, &filter, dry_run).await?;
+            }
+        }
+        Commands::Shift {
+            offset,
+            all,
+            filter,
+            dry_run,
+        } => {
+            if !all && filter.is_empty() {
+                return Err(libplurcast::PlurcastError::InvalidInput(
+                    "Must provide either --all or --filter".to_string(),
+                ));
+            }
+            cmd_shift(&db, &config, &offset, &filter, dry_run).await?;
+        }
+        Commands::Edit {
+            post_id,
+            content,
+            platform,
+            content_warning,
+            no_content_warning,
+            visibility,
+            lang,
+        } => {
+            let changes = MetadataChanges {
+                platforms: platform,
+                content_warning: if no_content_warning {
+                    Some(None)
+                } else {
+                    content_warning.map(Some)
+                },
+                visibility,
+                language: lang,
+            };
+            cmd_edit(&db, &config, &post_id, content, changes).await?;
+        }
+        Commands::Bump { post_id, priority } => {
+            cmd_bump(&db, &post_id, priority).await?;
+        }
+        Commands::Now { post_id } => {
+            cmd_now(&db, &post_id).await?;
+        }
+        Commands::Stats { format } => {
+            cmd_stats(&db, &format).await?;
+        }
+        Commands::Export {
+            format,
+            output,
+            platform,
+        } => {
+            cmd_export(
+                &db,
+                &config,
+                &format,
+                output.as_deref(),
+                platform.as_deref(),
+            )
+            .await?;
+        }
+        Commands::Import {
+            file,
+            format,
+            dry_run,
+        } => {
+            cmd_import(&db, &config, &file, format.as_deref(), dry_run).await?;
+        }
+        Commands::Pause { reason } => {
+            cmd_pause(&db, reason.as_deref()).await?;
+        }
+        Commands::Resume => {
+            cmd_resume(&db).await?;
+        }
+        Commands::Failed { action } => match action {
+            FailedAction::List { format } => {
+                cmd_failed_list(&db, &format).await?;
+            }
+            FailedAction::Clear { force } => {
+                cmd_failed_clear(&db, force).await?;
+            }
+            FailedAction::Delete { post_id, force } => {
+                cmd_failed_delete(&db, &post_id, force).await?;
+            }
+        },
+        Commands::Series { action } => match action {
+            SeriesAction::List { format } => {
+                cmd_series_list(&db, &format).await?;
+            }
+            SeriesAction::Pause { series_id } => {
+                cmd_series_pause(&db, &series_id).await?;
+            }
+            SeriesAction::Resume { series_id } => {
+                cmd_series_resume(&db, &series_id).await?;
+            }
+            SeriesAction::Cancel { series_id, force } => {
+                cmd_series_cancel(&db, &series_id, force).await?;
+            }
+        },
+        Commands::Update { post_id, nostr_pow } => {
+            cmd_update(&db, &post_id, nostr_pow).await?;
+        }
+        Commands::Purge {
+            older_than,
+            dry_run,
+            force,
+        } => {
+            cmd_purge(&db, older_than, dry_run, force).await?;
+        }
+        Commands::Maintenance { format } => {
+            cmd_maintenance(&db, &format).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// List scheduled posts
+async fn cmd_list(db: &Database, format: &str, platform: Option<&str>) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    // Validate format
+    if format != "text" && format != "json" {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Invalid format '{}'. Must be 'text' or 'json'",
+            format
+        )));
+    }
+
+    // Get scheduled posts
+    let mut posts = db.get_scheduled_posts().await?;
+
+    // Filter by platform if specified
+    if let Some(plat) = platform {
+        posts.retain(|p| {
+            if let Some(ref metadata) = p.metadata {
+                metadata.contains(&format!("\"{}\"", plat))
+            } else {
+                false
+            }
+        });
+    }
+
+    let priorities = db.get_scheduled_priorities().await?;
+
+    // Output based on format
+    if format == "json" {
+        output_list_json(&posts, &priorities);
+    } else {
+        output_list_text(&posts, &priorities);
+    }
+
+    Ok(())
+}
+
+/// Output posts as JSON
+fn output_list_json(posts: &[libplurcast::Post], priorities: &HashMap<String, i64>) {
+    let json: Vec<serde_json::Value> = posts
+        .iter()
+        .map(|p| {
+            serde_json::json!({
+                "id": p.id,
+                "content": p.content,
+                "scheduled_at": p.scheduled_at,
+                "created_at": p.created_at,
+                "status": format!("{:?}", p.status),
+                "priority": priorities.get(&p.id).copied().unwrap_or(0),
+            })
+        })
+        .collect();
+
+    println!("{}", serde_json::to_string_pretty(&json).unwrap());
+}
+
+/// Output posts as human-readable text
+fn output_list_text(posts: &[libplurcast::Post], priorities: &HashMap<String, i64>) {
+    use chrono::Utc;
+
+    if posts.is_empty() {
+        return;
+    }
+
+    let now = Utc::now().timestamp();
+
+    for post in posts {
+        let content = &post.content;
+        let time_until = post
+            .scheduled_at
+            .map(|ts| format_time_until(now, ts))
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let priority = priorities.get(&post.id).copied().unwrap_or(0);
+
+        println!(
+            "{} | {} | {} | priority {}",
+            post.id, content, time_until, priority
+        );
+    }
+}
+
+/// Truncate content to max length with ellipsis
+fn truncate_content(content: &str, max_len: usize) -> String {
+    if content.chars().count() <= max_len {
+        content.to_string()
+    } else {
+        let truncated: String = content.chars().take(max_len).collect();
+        format!("{}...", truncated)
+    }
+}
+
+/// Format time until scheduled time in human-readable format
+fn format_time_until(now: i64, scheduled_at: i64) -> String {
+    let diff = scheduled_at - now;
+
+    if diff < 0 {
+        return "overdue".to_string();
+    }
+
+    let minutes = diff / 60;
+    let hours = minutes / 60;
+    let days = hours / 24;
+
+    if days > 0 {
+        format!("in {} day{}", days, if days == 1 { "" } else { "s" })
+    } else if hours > 0 {
+        format!("in {} hour{}", hours, if hours == 1 { "" } else { "s" })
+    } else if minutes > 0 {
+        format!(
+            "in {} minute{}",
+            minutes,
+            if minutes == 1 { "" } else { "s" }
+        )
+    } else {
+        "in <1 minute".to_string()
+    }
+}
+
+/// How a post will go out on one platform
+struct PlatformPreview {
+    platform: String,
+    account: String,
+    scheduled_at: Option<i64>,
+    content: String,
+    /// Hashtags sent as tags rather than in the text (Nostr)
+    tags: Vec<String>,
+    char_count: usize,
+    char_limit: Option<usize>,
+}
+
+/// Preview a post as each of its platforms will receive it
+async fn cmd_show(db: &Database, config: &Config, post_id: &str, format: &str) -> Result<()> {
+    use libplurcast::service::validation::ValidationService;
+    use libplurcast::{PlurcastError, Visibility};
+
+    if format != "text" && format != "json" {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Invalid format '{}'. Must be 'text' or 'json'",
+            format
+        )));
+    }
+
+    // Validate post_id format
+    validate_post_id(post_id)?;
+
+    let post = db.get_post(post_id).await?;
+    let post = post.ok_or_else(|| PlurcastError::InvalidInput("Post not found".to_string()))?;
+
+    let metadata: serde_json::Value = post
+        .metadata
+        .as_deref()
+        .and_then(|m| serde_json::from_str(m).ok())
+        .unwrap_or_default();
+    let meta_str = |key: &str| metadata.get(key).and_then(|v| v.as_str()).map(String::from);
+
+    let mut platforms = post
+        .metadata
+        .as_deref()
+        .map(extract_platforms)
+        .unwrap_or_default();
+    if platforms.is_empty() {
+        platforms = config.defaults.platforms.clone();
+    }
+
+    // Staggered posts (--schedule-for) have a time per platform
+    let schedules = db.get_platform_schedules(post_id).await?;
+    let limits = ValidationService::new(std::sync::Arc::new(config.clone())).get_limits(&platforms);
+    let account_manager = libplurcast::AccountManager::new().ok();
+    let requested_account = meta_str("account");
+    let hashtags = libplurcast::hashtags::from_metadata(post.metadata.as_deref());
+
+    let previews: Vec<PlatformPreview> = platforms
+        .iter()
+        .map(|platform| {
+            // Nostr carries hashtags as `t` tags; the others append them
+            let (content, tags) = if platform == "nostr" {
+                (post.content.clone(), hashtags.clone())
+            } else {
+                (
+                    libplurcast::hashtags::append(&post.content, &hashtags),
+                    Vec::new(),
+                )
+            };
+            PlatformPreview {
+                platform: platform.clone(),
+                account: match &account_manager {
+                    Some(manager) => {
+                        manager.resolve_account(platform, requested_account.as_deref())
+                    }
+                    None => requested_account
+                        .clone()
+                        .unwrap_or_else(|| "default".to_string()),
+                },
+                scheduled_at: schedules
+                    .iter()
+                    .find(|s| &s.platform == platform)
+                    .map(|s| s.scheduled_at)
+                    .or(post.scheduled_at),
+                char_count: content.chars().count(),
+                char_limit: limits.get(platform).copied().flatten(),
+                content,
+                tags,
+            }
+        })
+        .collect();
+
+    let content_warning = meta_str("content_warning");
+    let visibility = Visibility::from_metadata(post.metadata.as_deref());
+    let thread_parent = meta_str("thread_parent_uuid");
+    let thread_sequence = metadata.get("thread_sequence").and_then(|v| v.as_u64());
+
+    if format == "json" {
+        let json = serde_json::json!({
+            "id": post.id,
+            "status": format!("{:?}", post.status),
+            "scheduled_at": post.scheduled_at,
+            "content_warning": content_warning,
+            "visibility": visibility.as_str(),
+            "thread_parent": thread_parent,
+            "thread_sequence": thread_sequence,
+            "platforms": previews
+                .iter()
+                .map(|p| {
+                    serde_json::json!({
+                        "platform": p.platform,
+                        "account": p.account,
+                        "scheduled_at": p.scheduled_at,
+                        "content": p.content,
+                        "tags": p.tags,
+                        "char_count": p.char_count,
+                        "char_limit": p.char_limit,
+                    })
+                })
+                .collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        return Ok(());
+    }
+
+    println!("Post {} ({:?})", post.id, post.status);
+    if let Some(scheduled_at) = post.scheduled_at {
+        println!("Scheduled: {}", format_local_time(scheduled_at));
+    }
+    if visibility != Visibility::Public {
+        println!("Visibility: {}", visibility.as_str());
+    }
+    if let Some(sequence) = thread_sequence {
+        match &thread_parent {
+            Some(parent) => println!("Thread: part {}, replying to {}", sequence + 1, parent),
+            None => println!("Thread: part {}", sequence + 1),
+        }
+    }
+
+    for preview in &previews {
+        println!();
+        println!(
+            "=== {} (account: {}) ===",
+            preview.platform, preview.account
+        );
+        if preview.scheduled_at != post.scheduled_at {
+            if let Some(scheduled_at) = preview.scheduled_at {
+                println!("Scheduled: {}", format_local_time(scheduled_at));
+            }
+        }
+        // SSB has no content warnings
+        if let Some(cw) = content_warning
+            .as_ref()
+            .filter(|_| preview.platform != "ssb")
+        {
+            println!("CW: {}", cw);
+        }
+        println!("{}", preview.content);
+        if !preview.tags.is_empty() {
+            let tags: Vec<String> = preview.tags.iter().map(|t| format!("#{}", t)).collect();
+            println!("Tags: {}", tags.join(" "));
+        }
+        match preview.char_limit {
+            Some(limit) if preview.char_count > limit => println!(
+                "[{}/{} characters - over the limit]",
+                preview.char_count, limit
+            ),
+            Some(limit) => println!("[{}/{} characters]", preview.char_count, limit),
+            None => println!("[{} characters]", preview.char_count),
+        }
+    }
+
+    Ok(())
+}
+
+/// Format a timestamp in the local timezone
+fn format_local_time(timestamp: i64) -> String {
+    use chrono::TimeZone;
+
+    chrono::Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|dt| dt.format("%Y-%m-%d %H:%M %Z").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+/// Manage the queue from a prompt
+async fn cmd_interactive(db: &Database, config: &Config) -> Result<()> {
+    use interactive::Action;
+    use libplurcast::PlurcastError;
+    use std::io::{self, Write};
+
+    let mut posts = db.get_scheduled_posts().await?;
+    let mut page = 0;
+    print_queue_page(&posts, page);
+    println!("? for help, q to quit");
+
+    loop {
+        print!("queue> ");
+        io::stdout()
+            .flush()
+            .map_err(|e| PlurcastError::InvalidInput(format!("Failed to write prompt: {}", e)))?;
+
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .map_err(|e| PlurcastError::InvalidInput(format!("Failed to read command: {}", e)))?;
+        if read == 0 {
+            // End of input
+            println!();
+            break;
+        }
+
+        let action = match interactive::parse(&line) {
+            Ok(action) => action,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                continue;
+            }
+        };
+
+        let pages = posts.len().div_ceil(interactive::PAGE_SIZE).max(1);
+        let pick = |n: usize| {
+            posts
+                .get(n - 1)
+                .map(|post| post.id.clone())
+                .ok_or_else(|| PlurcastError::InvalidInput(format!("No post {} in the queue", n)))
+        };
+
+        let result = match action {
+            Action::Quit => break,
+            Action::Help => {
+                println!("{}", interactive::HELP);
+                continue;
+            }
+            Action::List => {
+                print_queue_page(&posts, page);
+                continue;
+            }
+            Action::NextPage | Action::PreviousPage => {
+                page = if action == Action::NextPage {
+                    (page + 1).min(pages - 1)
+                } else {
+                    page.saturating_sub(1)
+                };
+                print_queue_page(&posts, page);
+                continue;
+            }
+            Action::Show(n) => match pick(n) {
+                Ok(id) => cmd_show(db, config, &id, "text").await,
+                Err(e) => Err(e),
+            },
+            Action::Cancel(n) => match pick(n) {
+                Ok(id) if confirm_cancel(Some(&id), false)? => cancel_single_post(db, &id).await,
+                Ok(_) => {
+                    println!("Kept post {}", n);
+                    continue;
+                }
+                Err(e) => Err(e),
+            },
+            Action::Reschedule(n, ref time) => match pick(n) {
+                Ok(id) => cmd_reschedule(db, &id, time).await,
+                Err(e) => Err(e),
+            },
+            Action::SendNow(n) => match pick(n) {
+                Ok(id) => cmd_now(db, &id).await,
+                Err(e) => Err(e),
+            },
+            Action::Bump(n) => match pick(n) {
+                Ok(id) => cmd_bump(db, &id, None).await,
+                Err(e) => Err(e),
+            },
+        };
+
+        if let Err(e) = result {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        // The queue changed; list it again
+        if !matches!(action, Action::Show(_)) {
+            posts = db.get_scheduled_posts().await?;
+            let pages = posts.len().div_ceil(interactive::PAGE_SIZE).max(1);
+            page = page.min(pages - 1);
+            print_queue_page(&posts, page);
+        }
+    }
+
+    Ok(())
+}
+
+/// Print one page of the queue, numbering posts across pages
+fn print_queue_page(posts: &[libplurcast::Post], page: usize) {
+    if posts.is_empty() {
+        println!("No scheduled posts");
+        return;
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let start = page * interactive::PAGE_SIZE;
+    let end = (start + interactive::PAGE_SIZE).min(posts.len());
+    println!(
+        "Scheduled posts {}-{} of {} (page {}/{})",
+        start + 1,
+        end,
+        posts.len(),
+        page + 1,
+        posts.len().div_ceil(interactive::PAGE_SIZE)
+    );
+
+    for (i, post) in posts.iter().enumerate().take(end).skip(start) {
+        let time_until = post
+            .scheduled_at
+            .map(|ts| format_time_until(now, ts))
+            .unwrap_or_else(|| "unknown".to_string());
+        let platforms = post
+            .metadata
+            .as_deref()
+            .map(extract_platforms)
+            .unwrap_or_default();
+        let platforms = if platforms.is_empty() {
+            "default".to_string()
+        } else {
+            platforms.join(",")
+        };
+        println!(
+            "{:>3}. {} | {} | {}",
+            i + 1,
+            time_until,
+            platforms,
+            truncate_content(&post.content.replace('\n', " "), 50)
+        );
+    }
+}
+
+/// Cancel scheduled post(s)
+async fn cmd_cancel(db: &Database, post_id: Option<&str>, all: bool, force: bool) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    // Validate arguments
+    validate_cancel_args(post_id, all)?;
+
+    // Confirm if not forced
+    if !force && !confirm_cancel(post_id, all)? {
+        return Err(PlurcastError::InvalidInput("Cancelled by user".to_string()));
+    }
+
+    // Execute cancellation
+    if let Some(id) = post_id {
+        cancel_single_post(db, id).await?;
+    } else {
+        cancel_all_posts(db).await?;
+    }
+
+    Ok(())
+}
+
+/// Validate cancel command arguments
+fn validate_cancel_args(post_id: Option<&str>, all: bool) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    if post_id.is_none() && !all {
+        return Err(PlurcastError::InvalidInput(
+            "Must provide either POST_ID or --all".to_string(),
+        ));
+    }
+
+    if post_id.is_some() && all {
+        return Err(PlurcastError::InvalidInput(
+            "Cannot use both POST_ID and --all".to_string(),
+        ));
+    }
+
+    // Validate UUID format if post_id provided
+    if let Some(id) = post_id {
+        if uuid::Uuid::parse_str(id).is_err() {
+            return Err(PlurcastError::InvalidInput(
+                "Invalid post ID format".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Prompt user for confirmation
+fn confirm_cancel(_post_id: Option<&str>, all: bool) -> Result<bool> {
+    use libplurcast::PlurcastError;
+    use std::io::{self, Write};
+
+    let message = if all {
+        "Cancel all scheduled posts? (y/N): "
+    } else {
+        "Cancel this post? (y/N): "
+    };
+
+    eprint!("{}", message);
+    io::stderr().flush().map_err(|e| {
+        PlurcastError::InvalidInput(format!("Failed to write confirmation prompt: {}", e))
+    })?;
+
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .map_err(|e| PlurcastError::InvalidInput(format!("Failed to read confirmation: {}", e)))?;
+
+    Ok(input.trim().eq_ignore_ascii_case("y"))
+}
+
+/// Cancel a single post by ID
+async fn cancel_single_post(db: &Database, post_id: &str) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    // Mark the post deleted, keeping a tombstone until it is purged
+    if !db.soft_delete_post(post_id, Some("cancelled")).await? {
+        return Err(PlurcastError::InvalidInput("Post not found".to_string()));
+    }
+
+    println!("Cancelled post {}", post_id);
+    Ok(())
+}
+
+/// Cancel all scheduled posts
+async fn cancel_all_posts(db: &Database) -> Result<()> {
+    let posts = db.get_scheduled_posts().await?;
+
+    if posts.is_empty() {
+        println!("No scheduled posts to cancel");
+        return Ok(());
+    }
+
+    let count = posts.len();
+    for post in posts {
+        db.soft_delete_post(&post.id, Some("cancelled")).await?;
+    }
+
+    println!(
+        "Cancelled {} post{}",
+        count,
+        if count == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
+/// Reschedule a post
+async fn cmd_reschedule(db: &Database, post_id: &str, time: &str) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    // Validate post_id format
+    validate_post_id(post_id)?;
+
+    // Check if post exists and get current time
+    let post = db.get_post(post_id).await?;
+    let post = post.ok_or_else(|| PlurcastError::InvalidInput("Post not found".to_string()))?;
+
+    // Parse new schedule time
+    let new_time = parse_reschedule_time(time, post.scheduled_at)?;
+
+    // Validate not in past
+    let now = chrono::Utc::now().timestamp();
+    if new_time <= now {
+        return Err(PlurcastError::InvalidInput(
+            "Cannot schedule in the past".to_string(),
+        ));
+    }
+
+    // Update scheduled_at in database
+    db.update_post_schedule(post_id, Some(new_time)).await?;
+
+    // Rescheduling a missed post puts it back in the queue
+    if post.status == libplurcast::PostStatus::Missed {
+        db.update_post_status(post_id, libplurcast::PostStatus::Scheduled)
+            .await?;
+    }
+
+    println!("Rescheduled post {} for {}", post_id, new_time);
+    Ok(())
+}
+
+/// Which scheduled posts a bulk reschedule applies to
+#[derive(Debug, Clone, PartialEq)]
+enum QueueFilter {
+    Platform(String),
+    Account(String),
+    Label(String),
+}
+
+/// Parse a `--filter KEY=VALUE` value
+fn parse_filter(value: &str) -> std::result::Result<QueueFilter, String> {
+    let (key, filter_value) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", value))?;
+    let filter_value = filter_value.trim().to_string();
+    if filter_value.is_empty() {
+        return Err(format!("missing value for '{}'", key));
+    }
+
+    match key.trim() {
+        "platform" => Ok(QueueFilter::Platform(filter_value)),
+        "account" => Ok(QueueFilter::Account(filter_value)),
+        "label" => Ok(QueueFilter::Label(filter_value)),
+        other => Err(format!(
+            "unknown filter '{}' (expected platform, account or label)",
+            other
+        )),
+    }
+}
+
+/// Move every scheduled post matching the filters by a relative offset
+///
+/// All posts are checked before any is moved, so an offset that would put
+/// one of them in the past changes nothing.
+async fn cmd_shift(
+    db: &Database,
+    config: &Config,
+    offset: &str,
+    filters: &[QueueFilter],
+    dry_run: bool,
+) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    // A bare duration shifts forward
+    let offset = if offset.starts_with('+') || offset.starts_with('-') {
+        offset.to_string()
+    } else {
+        format!("+{}", offset)
+    };
+    // Check the offset even when no post matches
+    parse_relative_adjustment(&offset, Some(0))?;
+
+    let mut moves = Vec::new();
+    for post in db.get_scheduled_posts().await? {
+        if !matches_filters(db, config, &post, filters).await? {
+            continue;
+        }
+        if let Some(scheduled_at) = post.scheduled_at {
+            let new_time = parse_relative_adjustment(&offset, Some(scheduled_at))?;
+            moves.push((post, scheduled_at, new_time));
+        }
+    }
+
+    if moves.is_empty() {
+        println!("No scheduled posts match");
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let in_past = moves.iter().filter(|(_, _, new)| *new <= now).count();
+
+    println!(
+        "{} {} post(s) by {}:",
+        if dry_run { "Would shift" } else { "Shifting" },
+        moves.len(),
+        offset
+    );
+    for (post, old, new) in &moves {
+        println!(
+            "  {} | {} -> {}{} | {}",
+            post.id,
+            format_schedule_time(*old),
+            format_schedule_time(*new),
+            if *new <= now { " (in the past)" } else { "" },
+            truncate_content(&post.content, 40)
+        );
+    }
+
+    if in_past > 0 {
+        return Err(PlurcastError::InvalidInput(format!(
+            "{} post(s) would be scheduled in the past; nothing was changed",
+            in_past
+        )));
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    for (post, _, new) in &moves {
+        db.update_post_schedule(&post.id, Some(*new)).await?;
+    }
+    println!("Shifted {} post(s)", moves.len());
+    Ok(())
+}
+
+/// Whether a scheduled post matches every filter
+async fn matches_filters(
+    db: &Database,
+    config: &Config,
+    post: &libplurcast::Post,
+    filters: &[QueueFilter],
+) -> Result<bool> {
+    let metadata: serde_json::Value = post
+        .metadata
+        .as_deref()
+        .and_then(|m| serde_json::from_str(m).ok())
+        .unwrap_or_default();
+
+    for filter in filters {
+        let matched = match filter {
+            QueueFilter::Platform(platform) => {
+                let mut platforms = post
+                    .metadata
+                    .as_deref()
+                    .map(extract_platforms)
+                    .unwrap_or_default();
+                if platforms.is_empty() {
+                    platforms = config.defaults.platforms.clone();
+                }
+                platforms.contains(platform)
+            }
+            QueueFilter::Account(account) => {
+                let post_account = metadata
+                    .get("account")
+                    .and_then(|a| a.as_str())
+                    .unwrap_or("default");
+                post_account == account
+            }
+            QueueFilter::Label(label) => db.get_labels(&post.id).await?.contains(label),
+        };
+        if !matched {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Format a scheduled time for bulk reschedule previews
+fn format_schedule_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+/// Edit the content, platforms and metadata of a scheduled post
+async fn cmd_edit(
+    db: &Database,
+    config: &Config,
+    post_id: &str,
+    content: Option<String>,
+    changes: MetadataChanges,
+) -> Result<()> {
+    use libplurcast::service::validation::{ValidationRequest, ValidationService};
+    use libplurcast::{PlurcastError, PostStatus, Visibility};
+    use std::io::IsTerminal;
+
+    // Validate post_id format
+    validate_post_id(post_id)?;
+
+    let post = db.get_post(post_id).await?;
+    let post = post.ok_or_else(|| PlurcastError::InvalidInput("Post not found".to_string()))?;
+    if post.status != PostStatus::Scheduled {
+        return Err(PlurcastError::InvalidInput(
+            "Only scheduled posts can be edited".to_string(),
+        ));
+    }
+
+    let new_content = match content {
+        Some(content) => content,
+        // Only metadata is being changed
+        None if !changes.is_empty() => post.content.clone(),
+        None if !std::io::stdin().is_terminal() => read_stdin_content()?,
+        None => {
+            libplurcast::editor::edit(&post.content, &format!("plur-queue-edit-{}.txt", post_id))?
+        }
+    };
+
+    let mut metadata: serde_json::Value = post
+        .metadata
+        .as_deref()
+        .and_then(|m| serde_json::from_str(m).ok())
+        .filter(|m: &serde_json::Value| m.is_object())
+        .unwrap_or_else(|| serde_json::json!({}));
+    let old_metadata = metadata.clone();
+    changes.apply(&mut metadata)?;
+
+    if new_content == post.content && metadata == old_metadata {
+        println!("No changes to post {}", post_id);
+        return Ok(());
+    }
+
+    // Validate for the platforms the post will go to
+    let metadata_str = metadata.to_string();
+    let mut platforms = extract_platforms(&metadata_str);
+    if platforms.is_empty() {
+        platforms = config.defaults.platforms.clone();
+    }
+
+    let validation =
+        ValidationService::new(std::sync::Arc::new(config.clone())).validate(ValidationRequest {
+            content: new_content.clone(),
+            platforms,
+            auto_thread: false,
+            visibility: Visibility::from_metadata(Some(&metadata_str)),
+        });
+    if !validation.valid {
+        let errors: Vec<String> = validation
+            .results
+            .iter()
+            .flat_map(|r| r.errors.iter().cloned())
+            .collect();
+        return Err(PlurcastError::InvalidInput(format!(
+            "Content validation failed:\n{}",
+            errors.join("\n")
+        )));
+    }
+
+    if new_content != post.content {
+        db.update_post_content(post_id, new_content).await?;
+    }
+    if metadata != old_metadata {
+        db.update_post_metadata(post_id, &metadata_str).await?;
+    }
+
+    println!("Updated post {}", post_id);
+    Ok(())
+}
+
+/// Metadata changes requested with `plur-queue edit`
+#[derive(Debug, Default)]
+struct MetadataChanges {
+    /// Replace the platforms (empty keeps them)
+    platforms: Vec<String>,
+    /// `Some(None)` removes the content warning
+    content_warning: Option<Option<String>>,
+    visibility: Option<String>,
+    language: Option<String>,
+}
+
+impl MetadataChanges {
+    fn is_empty(&self) -> bool {
+        self.platforms.is_empty()
+            && self.content_warning.is_none()
+            && self.visibility.is_none()
+            && self.language.is_none()
+    }
+
+    /// Apply the changes to a post's metadata object, checking each value
+    fn apply(&self, metadata: &mut serde_json::Value) -> Result<()> {
+        use libplurcast::{PlurcastError, Visibility};
+
+        if !self.platforms.is_empty() {
+            let mut platforms: Vec<String> = Vec::new();
+            for platform in &self.platforms {
+                if !platforms.contains(platform) {
+                    platforms.push(platform.clone());
+                }
+            }
+            metadata["platforms"] = serde_json::json!(platforms);
+        }
+
+        if let Some(ref content_warning) = self.content_warning {
+            let object = metadata.as_object_mut();
+            match content_warning.as_deref().map(str::trim) {
+                Some("") => {
+                    return Err(PlurcastError::InvalidInput(
+                        "Content warning cannot be empty (use --no-content-warning to remove it)"
+                            .to_string(),
+                    ))
+                }
+                Some(cw) => {
+                    if let Some(object) = object {
+                        object.insert("content_warning".to_string(), serde_json::json!(cw));
+                    }
+                }
+                None => {
+                    if let Some(object) = object {
+                        object.remove("content_warning");
+                    }
+                }
+            }
+        }
+
+        if let Some(ref visibility) = self.visibility {
+            let visibility = Visibility::parse(visibility).ok_or_else(|| {
+                PlurcastError::InvalidInput(format!("Invalid visibility '{}'", visibility))
+            })?;
+            if let Some(object) = metadata.as_object_mut() {
+                // Public is the default and isn't stored
+                if visibility == Visibility::Public {
+                    object.remove("visibility");
+                } else {
+                    object.insert("visibility".to_string(), serde_json::json!(visibility));
+                }
+            }
+        }
+
+        if let Some(ref language) = self.language {
+            let language = libplurcast::language::parse(language)?;
+            metadata[libplurcast::language::LANGUAGE_METADATA_KEY] = serde_json::json!(language);
+        }
+
+        Ok(())
+    }
+}
+
+/// Read replacement content from stdin
+fn read_stdin_content() -> Result<String> {
+    use libplurcast::PlurcastError;
+    use std::io::Read;
+
+    let mut buffer = String::new();
+    std::io::stdin()
+        .read_to_string(&mut buffer)
+        .map_err(|e| PlurcastError::InvalidInput(format!("Failed to read from stdin: {}", e)))?;
+
+    Ok(buffer.trim_end_matches('\n').to_string())
+}
+
+/// Change a scheduled post's priority
+async fn cmd_bump(db: &Database, post_id: &str, priority: Option<i64>) -> Result<()> {
+    use libplurcast::{PlurcastError, PostStatus};
+
+    // Validate post_id format
+    validate_post_id(post_id)?;
+
+    let post = db.get_post(post_id).await?;
+    let post = post.ok_or_else(|| PlurcastError::InvalidInput("Post not found".to_string()))?;
+    if post.status != PostStatus::Scheduled {
+        return Err(PlurcastError::InvalidInput(
+            "Only scheduled posts can be bumped".to_string(),
+        ));
+    }
+
+    let new_priority = match priority {
+        Some(priority) => priority,
+        None => db
+            .get_post_priority(post_id)
+            .await?
+            .unwrap_or(0)
+            .saturating_add(1),
+    };
+    db.set_post_priority(post_id, new_priority).await?;
+
+    println!("Bumped post {} to priority {}", post_id, new_priority);
+    Ok(())
+}
+
+/// Validate post ID format
+fn validate_post_id(post_id: &str) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    if uuid::Uuid::parse_str(post_id).is_err() {
+        return Err(PlurcastError::InvalidInput(
+            "Invalid post ID format".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Parse reschedule time, supporting absolute and relative formats
+fn parse_reschedule_time(time: &str, current_scheduled: Option<i64>) -> Result<i64> {
+    // Check for relative adjustment (+1h, -30m)
+    if time.starts_with('+') || time.starts_with('-') {
+        return parse_relative_adjustment(time, current_scheduled);
+    }
+
+    // Parse as absolute time (duration or natural language)
+    let dt = libplurcast::scheduling::parse_schedule(time, None)?;
+    Ok(dt.timestamp())
+}
+
+/// Parse relative time adjustment (+1h, -30m)
+fn parse_relative_adjustment(time: &str, current_scheduled: Option<i64>) -> Result<i64> {
+    use libplurcast::PlurcastError;
+
+    let current = current_scheduled.ok_or_else(|| {
+        PlurcastError::InvalidInput("Post has no scheduled time to adjust".to_string())
+    })?;
+
+    let is_addition = time.starts_with('+');
+    let duration_str = &time[1..]; // Remove +/- prefix
+
+    let duration = humantime::parse_duration(duration_str)
+        .map_err(|e| PlurcastError::InvalidInput(format!("Invalid duration: {}", e)))?;
+
+    let seconds = duration.as_secs() as i64;
+
+    let new_time = if is_addition {
+        current + seconds
+    } else {
+        current - seconds
+    };
+
+    Ok(new_time)
+}
+
+/// Post immediately
+async fn cmd_now(db: &Database, post_id: &str) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    // Validate post_id format
+    validate_post_id(post_id)?;
+
+    // Check if post exists
+    let post = db.get_post(post_id).await?;
+    let post = post.ok_or_else(|| PlurcastError::InvalidInput("Post not found".to_string()))?;
+
+    // Check if post is scheduled
+    if post.status != libplurcast::PostStatus::Scheduled {
+        return Err(PlurcastError::InvalidInput(
+            "Post is not scheduled".to_string(),
+        ));
+    }
+
+    // Clear scheduled_at and set status to pending
+    db.update_post_schedule(post_id, None).await?;
+    db.update_post_status(post_id, libplurcast::PostStatus::Pending)
+        .await?;
+
+    println!("Posting {} immediately", post_id);
+
+    // TODO: In future, this should trigger posting via PostingService
+    // For now, we just change status to pending, and plur-send daemon will pick it up
+
+    Ok(())
+}
+
+/// Show queue statistics
+async fn cmd_stats(db: &Database, format: &str) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    // Validate format
+    if format != "text" && format != "json" {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Invalid format '{}'. Must be 'text' or 'json'",
+            format
+        )));
+    }
+
+    // Get all scheduled posts
+    let posts = db.get_scheduled_posts().await?;
+
+    // Calculate stats
+    let mut stats = calculate_stats(&posts);
+    stats.deferred = db.list_deferred_posts().await?;
+    stats.paused = db.get_queue_pause().await?;
+    stats.missed = db.get_missed_posts().await?;
+
+    // Output based on format
+    if format == "json" {
+        output_stats_json(&stats);
+    } else {
+        output_stats_text(&stats);
+    }
+
+    Ok(())
+}
+
+/// Export the queue as an iCalendar file
+async fn cmd_export(
+    db: &Database,
+    config: &Config,
+    format: &str,
+    output: Option<&std::path::Path>,
+    platform: Option<&str>,
+) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    // Validate format
+    if format != "ics" && format != "json" {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Invalid format '{}'. Must be 'ics' or 'json'",
+            format
+        )));
+    }
+
+    // Platforms a post goes to, falling back to the configured defaults
+    let platforms_of = |post: &libplurcast::Post| {
+        let platforms = post
+            .metadata
+            .as_deref()
+            .map(extract_platforms)
+            .unwrap_or_default();
+        if platforms.is_empty() {
+            config.defaults.platforms.clone()
+        } else {
+            platforms
+        }
+    };
+
+    let mut posts = db.get_scheduled_posts().await?;
+    if let Some(plat) = platform {
+        posts.retain(|p| platforms_of(p).iter().any(|name| name == plat));
+    }
+
+    let document = if format == "json" {
+        queue_backup(db, &posts).await?
+    } else {
+        ics::calendar(&posts, platforms_of)
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, document).map_err(|e| {
+                PlurcastError::InvalidInput(format!("Failed to write {}: {}", path.display(), e))
+            })?;
+            eprintln!("Exported {} post(s) to {}", posts.len(), path.display());
+        }
+        None => print!("{}", document),
+    }
+
+    Ok(())
+}
+
+/// Everything needed to re-create scheduled posts, as JSON
+///
+/// The layout is what `plur-queue import` reads: posts keep their IDs so
+/// threads and follow-ups still point at the right posts after a restore.
+async fn queue_backup(db: &Database, posts: &[libplurcast::Post]) -> Result<String> {
+    let priorities = db.get_scheduled_priorities().await?;
+
+    let mut entries = Vec::with_capacity(posts.len());
+    for post in posts {
+        let platform_schedules: serde_json::Map<String, serde_json::Value> = db
+            .get_platform_schedules(&post.id)
+            .await?
+            .into_iter()
+            .filter(|s| s.sent_at.is_none())
+            .map(|s| (s.platform, serde_json::json!(s.scheduled_at)))
+            .collect();
+
+        entries.push(serde_json::json!({
+            "id": post.id,
+            "content": post.content,
+            "created_at": post.created_at,
+            "scheduled_at": post.scheduled_at,
+            "metadata": post
+                .metadata
+                .as_deref()
+                .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok()),
+            "priority": priorities.get(&post.id).copied().unwrap_or(0),
+            "labels": db.get_labels(&post.id).await?,
+            "platform_schedules": platform_schedules,
+        }));
+    }
+
+    let backup = serde_json::json!({
+        "version": 1,
+        "exported_at": chrono::Utc::now().timestamp(),
+        "posts": entries,
+    });
+    Ok(format!(
+        "{}\n",
+        serde_json::to_string_pretty(&backup).unwrap()
+    ))
+}
+
+/// Import scheduled posts from a CSV or JSON file
+async fn cmd_import(
+    db: &Database,
+    config: &Config,
+    file: &str,
+    format: Option<&str>,
+    dry_run: bool,
+) -> Result<()> {
+    use libplurcast::PlurcastError;
+    use std::io::Read;
+
+    let text = if file == "-" {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| PlurcastError::InvalidInput(format!("Failed to read stdin: {}", e)))?;
+        text
+    } else {
+        std::fs::read_to_string(file)
+            .map_err(|e| PlurcastError::InvalidInput(format!("Failed to read {}: {}", file, e)))?
+    };
+
+    let is_json = match format {
+        Some(format) => format == "json",
+        None => file.ends_with(".json"),
+    };
+    let rows = if is_json {
+        import::parse_json(&text)?
+    } else {
+        import::parse_csv(&text)?
+    };
+    if rows.is_empty() {
+        println!("Nothing to import");
+        return Ok(());
+    }
+
+    let mut posts = Vec::with_capacity(rows.len());
+    let mut problems = Vec::new();
+    let mut skipped = 0;
+    for row in &rows {
+        // Re-importing an export leaves posts still in the queue alone
+        if let Some(id) = &row.id {
+            if db.get_post(id).await?.is_some() {
+                skipped += 1;
+                continue;
+            }
+        }
+        match prepare_import(config, row) {
+            Ok(post) => posts.push((post, row)),
+            Err(e) => problems.push(format!("  line {}: {}", row.line, e)),
+        }
+    }
+
+    if !problems.is_empty() {
+        eprintln!("{} of {} row(s) are invalid:", problems.len(), rows.len());
+        for problem in &problems {
+            eprintln!("{}", problem);
+        }
+        return Err(PlurcastError::InvalidInput(
+            "Nothing was imported".to_string(),
+        ));
+    }
+
+    if !dry_run {
+        for (post, row) in &posts {
+            db.create_post(post).await?;
+            db.add_labels(&post.id, &row.labels).await?;
+            if let Some(priority) = row.priority.filter(|p| *p != 0) {
+                db.set_post_priority(&post.id, priority).await?;
+            }
+            if !row.platform_schedules.is_empty() {
+                db.set_platform_schedules(&post.id, &row.platform_schedules)
+                    .await?;
+            }
+        }
+    }
+
+    // Summary: how many, when, and where to
+    let mut per_platform: std::collections::BTreeMap<String, usize> = Default::default();
+    for (post, _) in &posts {
+        for platform in post
+            .metadata
+            .as_deref()
+            .map(extract_platforms)
+            .unwrap_or_default()
+        {
+            *per_platform.entry(platform).or_default() += 1;
+        }
+    }
+    let first = posts.iter().filter_map(|(p, _)| p.scheduled_at).min();
+    let last = posts.iter().filter_map(|(p, _)| p.scheduled_at).max();
+
+    println!(
+        "{} {} post(s)",
+        if dry_run { "Would import" } else { "Imported" },
+        posts.len()
+    );
+    if skipped > 0 {
+        println!("  Skipped {} post(s) already in the queue", skipped);
+    }
+    if let (Some(first), Some(last)) = (first, last) {
+        println!(
+            "  From {} to {}",
+            format_schedule_time(first),
+            format_schedule_time(last)
+        );
+    }
+    for (platform, count) in &per_platform {
+        println!("  {}: {}", platform, count);
+    }
+
+    Ok(())
+}
+
+/// Check one imported row and build the scheduled post for it
+fn prepare_import(config: &Config, row: &import::ImportRow) -> Result<libplurcast::Post> {
+    use libplurcast::service::validation::{ValidationRequest, ValidationService};
+    use libplurcast::{PlurcastError, PostStatus, Visibility};
+
+    if row.content.trim().is_empty() {
+        return Err(PlurcastError::InvalidInput("content is empty".to_string()));
+    }
+    if let Some(id) = &row.id {
+        if uuid::Uuid::parse_str(id).is_err() {
+            return Err(PlurcastError::InvalidInput(format!(
+                "invalid post ID '{}'",
+                id
+            )));
+        }
+    }
+    if row.time == import::ImportTime::Text(String::new()) {
+        return Err(PlurcastError::InvalidInput("time is empty".to_string()));
+    }
+    let scheduled_at = row.time.resolve()?;
+    let now = chrono::Utc::now().timestamp();
+    if scheduled_at <= now {
+        return Err(PlurcastError::InvalidInput(format!(
+            "{} is in the past",
+            format_schedule_time(scheduled_at)
+        )));
+    }
+
+    let mut metadata = row.metadata.clone().unwrap_or_default();
+
+    // Platforms from the row, else the metadata, else the configured defaults
+    let mut platforms = row.platforms.clone();
+    if platforms.is_empty() {
+        platforms = extract_platforms(&serde_json::Value::Object(metadata.clone()).to_string());
+    }
+    if platforms.is_empty() {
+        platforms = config.defaults.platforms.clone();
+    }
+    for platform in &platforms {
+        if !["nostr", "mastodon", "ssb"].contains(&platform.as_str()) {
+            return Err(PlurcastError::InvalidInput(format!(
+                "unknown platform '{}' (expected nostr, mastodon or ssb)",
+                platform
+            )));
+        }
+    }
+    let mut unique: Vec<String> = Vec::with_capacity(platforms.len());
+    for platform in platforms {
+        if !unique.contains(&platform) {
+            unique.push(platform);
+        }
+    }
+    let platforms = unique;
+    metadata.insert("platforms".to_string(), serde_json::json!(platforms));
+
+    if !row.hashtags.is_empty() {
+        let tags = row
+            .hashtags
+            .iter()
+            .map(|tag| libplurcast::hashtags::parse(tag))
+            .collect::<Result<Vec<_>>>()?;
+        metadata.insert(
+            libplurcast::hashtags::HASHTAGS_METADATA_KEY.to_string(),
+            serde_json::json!(libplurcast::hashtags::dedup(&tags)),
+        );
+    }
+
+    for label in &row.labels {
+        if label.chars().any(|c| c.is_whitespace() || c == ',') {
+            return Err(PlurcastError::InvalidInput(format!(
+                "invalid label '{}': labels cannot contain spaces or commas",
+                label
+            )));
+        }
+    }
+
+    let metadata = serde_json::Value::Object(metadata).to_string();
+    let validation =
+        ValidationService::new(std::sync::Arc::new(config.clone())).validate(ValidationRequest {
+            content: row.content.clone(),
+            platforms,
+            auto_thread: false,
+            visibility: Visibility::from_metadata(Some(&metadata)),
+        });
+    if !validation.valid {
+        let errors: Vec<String> = validation
+            .results
+            .iter()
+            .flat_map(|r| r.errors.iter().cloned())
+            .collect();
+        return Err(PlurcastError::InvalidInput(errors.join("; ")));
+    }
+
+    Ok(libplurcast::Post {
+        id: row
+            .id
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        content: row.content.clone(),
+        created_at: now,
+        scheduled_at: Some(scheduled_at),
+        status: PostStatus::Scheduled,
+        metadata: Some(metadata),
+    })
+}
+
+/// Pause scheduled posting
+async fn cmd_pause(db: &Database, reason: Option<&str>) -> Result<()> {
+    if db.pause_queue(reason).await? {
+        println!("Queue paused");
+    } else {
+        println!("Queue is already paused");
+    }
+    Ok(())
+}
+
+/// Resume scheduled posting
+async fn cmd_resume(db: &Database) -> Result<()> {
+    if db.resume_queue().await? {
+        println!("Queue resumed");
+    } else {
+        println!("Queue is not paused");
+    }
+    Ok(())
+}
+
+/// Stats structure
+struct QueueStats {
+    total: usize,
+    by_platform: std::collections::HashMap<String, usize>,
+    by_time_bucket: TimeBuckets,
+    upcoming: Vec<libplurcast::Post>,
+    /// Posts plur-send pushed back (caps, blackout windows)
+    deferred: Vec<libplurcast::PostDeferral>,
+    /// Set while the queue is paused
+    paused: Option<libplurcast::QueuePause>,
+    /// Posts held by the catch-up policy until rescheduled
+    missed: Vec<libplurcast::Post>,
+}
+
+/// Time bucket counts
+struct TimeBuckets {
+    next_hour: usize,
+    today: usize,
+    this_week: usize,
+    later: usize,
+}
+
+/// Calculate queue statistics
+fn calculate_stats(posts: &[libplurcast::Post]) -> QueueStats {
+    use std::collections::HashMap;
+
+    let total = posts.len();
+    let mut by_platform: HashMap<String, usize> = HashMap::new();
+    let now = chrono::Utc::now().timestamp();
+
+    // Count by platform and time bucket
+    let mut next_hour = 0;
+    let mut today = 0;
+    let mut this_week = 0;
+    let mut later = 0;
+
+    for post in posts {
+        // Count platforms
+        if let Some(ref metadata) = post.metadata {
+            extract_platforms(metadata).iter().for_each(|p| {
+                *by_platform.entry(p.clone()).or_insert(0) += 1;
+            });
+        }
+
+        // Count time buckets
+        if let Some(scheduled_at) = post.scheduled_at {
+            let diff = scheduled_at - now;
+            if diff < 3600 {
+                // < 1 hour
+                next_hour += 1;
+            } else if diff < 86400 {
+                // < 24 hours
+                today += 1;
+            } else if diff < 604800 {
+                // < 7 days
+                this_week += 1;
+            } else {
+                later += 1;
+            }
+        }
+    }
+
+    // Get next 5 upcoming posts
+    let mut upcoming: Vec<_> = posts.to_vec();
+    upcoming.sort_by_key(|p| p.scheduled_at);
+    upcoming.truncate(5);
+
+    QueueStats {
+        total,
+        by_platform,
+        by_time_bucket: TimeBuckets {
+            next_hour,
+            today,
+            this_week,
+            later,
+        },
+        upcoming,
+        deferred: Vec::new(),
+        paused: None,
+        missed: Vec::new(),
+    }
+}
+
+/// Extract platform names from metadata JSON
+fn extract_platforms(metadata: &str) -> Vec<String> {
+    serde_json::from_str::<serde_json::Value>(metadata)
+        .ok()
+        .and_then(|v| v.get("platforms").cloned())
+        .and_then(|v| serde_json::from_value::<Vec<String>>(v).ok())
+        .unwrap_or_default()
+}
+
+/// Output stats as text
+fn output_stats_text(stats: &QueueStats) {
+    println!("Queue Statistics");
+    println!("================");
+    println!();
+    if let Some(pause) = &stats.paused {
+        let since = chrono::DateTime::from_timestamp(pause.paused_at, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_else(|| pause.paused_at.to_string());
+        match &pause.reason {
+            Some(reason) => println!("PAUSED since {} ({})", since, reason),
+            None => println!("PAUSED since {}", since),
+        }
+        println!();
+    }
+
+    println!("Total: {}", stats.total);
+    println!("Deferred: {}", stats.deferred.len());
+    println!("Missed: {}", stats.missed.len());
+    println!();
+
+    if !stats.by_platform.is_empty() {
+        println!("By Platform:");
+        for (platform, count) in &stats.by_platform {
+            println!("  {}: {}", platform, count);
+        }
+        println!();
+    }
+
+    println!("By Time:");
+    println!("  Next hour: {}", stats.by_time_bucket.next_hour);
+    println!("  Today: {}", stats.by_time_bucket.today);
+    println!("  This week: {}", stats.by_time_bucket.this_week);
+    println!("  Later: {}", stats.by_time_bucket.later);
+    println!();
+
+    if !stats.upcoming.is_empty() {
+        println!("Upcoming Posts:");
+        for post in &stats.upcoming {
+            let preview = truncate_content(&post.content, 40);
+            let time_str = post
+                .scheduled_at
+                .map(|ts| {
+                    let now = chrono::Utc::now().timestamp();
+                    format_time_until(now, ts)
+                })
+                .unwrap_or_else(|| "unknown".to_string());
+            println!("  {} | {}", time_str, preview);
+        }
+    }
+
+    if !stats.deferred.is_empty() {
+        println!();
+        println!("Deferred Posts:");
+        let now = chrono::Utc::now().timestamp();
+        for deferral in &stats.deferred {
+            println!(
+                "  {} | {} | {}",
+                deferral.post_id,
+                format_time_until(now, deferral.deferred_until),
+                deferral.reason
+            );
+        }
+    }
+
+    if !stats.missed.is_empty() {
+        println!();
+        println!("Missed Posts (reschedule to send):");
+        for post in &stats.missed {
+            let due = post
+                .scheduled_at
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+            println!(
+                "  {} | was due {} | {}",
+                post.id,
+                due,
+                truncate_content(&post.content, 40)
+            );
+        }
+    }
+}
+
+/// Output stats as JSON
+fn output_stats_json(stats: &QueueStats) {
+    let upcoming: Vec<serde_json::Value> = stats
+        .upcoming
+        .iter()
+        .map(|p| {
+            serde_json::json!({
+                "id": p.id,
+                "content": p.content,
+                "scheduled_at": p.scheduled_at,
+            })
+        })
+        .collect();
+
+    let missed: Vec<serde_json::Value> = stats
+        .missed
+        .iter()
+        .map(|p| {
+            serde_json::json!({
+                "id": p.id,
+                "content": p.content,
+                "scheduled_at": p.scheduled_at,
+            })
+        })
+        .collect();
+
+    let output = serde_json::json!({
+        "total": stats.total,
+        "by_platform": stats.by_platform,
+        "by_time_bucket": {
+            "next_hour": stats.by_time_bucket.next_hour,
+            "today": stats.by_time_bucket.today,
+            "this_week": stats.by_time_bucket.this_week,
+            "later": stats.by_time_bucket.later,
+        },
+        "upcoming": upcoming,
+        "deferred_count": stats.deferred.len(),
+        "deferred": stats.deferred,
+        "paused": stats.paused,
+        "missed_count": stats.missed.len(),
+        "missed": missed,
+    });
+
+    println!("{}", serde_json::to_string_pretty(&output).unwrap());
+}
+
+/// List failed posts
+async fn cmd_failed_list(db: &Database, format: &str) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    // Validate format
+    if format != "text" && format != "json" {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Invalid format '{}'. Must be 'text' or 'json'",
+            format
+        )));
+    }
+
+    // Get failed posts
+    let failed_posts = db.get_failed_posts().await?;
+
+    if failed_posts.is_empty() {
+        if format == "json" {
+            println!("[]");
+        } else {
+            println!("No failed posts");
+        }
+        return Ok(());
+    }
+
+    // Output based on format
+    if format == "json" {
+        output_failed_posts_json(&failed_posts);
+    } else {
+        output_failed_posts_text(&failed_posts, db).await?;
+    }
+
+    Ok(())
+}
+
+/// Output failed posts as text
+async fn output_failed_posts_text(posts: &[libplurcast::Post], db: &Database) -> Result<()> {
+    println!("Failed Posts ({} total):", posts.len());
+    println!();
+
+    for post in posts {
+        let content_preview = truncate_content(&post.content, 60);
+        let created_at = chrono::DateTime::from_timestamp(post.created_at, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        println!("ID: {}", post.id);
+        println!("Content: {}", content_preview);
+        println!("Created: {}", created_at);
+
+        // Fetch post records to show error information
+        let records = db.get_post_records(&post.id).await?;
+        let failed_records: Vec<_> = records.iter().filter(|r| !r.success).collect();
+
+        if !failed_records.is_empty() {
+            let errors: Vec<String> = failed_records
+                .iter()
+                .filter_map(|r| r.error_message.as_ref())
+                .map(|e| e.to_string())
+                .collect();
+            if !errors.is_empty() {
+                println!("Errors: {}", errors.join(", "));
+            }
+        }
+
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Output failed posts as JSON
+fn output_failed_posts_json(posts: &[libplurcast::Post]) {
+    let json: Vec<serde_json::Value> = posts
+        .iter()
+        .map(|p| {
+            serde_json::json!({
+                "id": p.id,
+                "content": p.content,
+                "created_at": p.created_at,
+                "status": "failed",
+            })
+        })
+        .collect();
+
+    println!("{}", serde_json::to_string_pretty(&json).unwrap());
+}
+
+/// Clear all failed posts
+async fn cmd_failed_clear(db: &Database, force: bool) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    // Get failed posts
+    let failed_posts = db.get_failed_posts().await?;
+
+    if failed_posts.is_empty() {
+        println!("No failed posts to clear");
+        return Ok(());
+    }
+
+    let count = failed_posts.len();
+
+    // Confirm if not forced
+    if !force && !confirm_clear_failed(count)? {
+        return Err(PlurcastError::InvalidInput("Cancelled by user".to_string()));
+    }
+
+    // Delete all failed posts (kept as tombstones until purged)
+    for post in &failed_posts {
+        db.soft_delete_post(&post.id, Some("cleared")).await?;
+    }
+
+    println!("Cleared {} failed post(s)", count);
+    Ok(())
+}
+
+/// Prompt user for confirmation to clear failed posts
+fn confirm_clear_failed(count: usize) -> Result<bool> {
+    use libplurcast::PlurcastError;
+    use std::io::{self, Write};
+
+    eprint!("Clear {} failed post(s)? (y/N): ", count);
+    io::stderr().flush().map_err(|e| {
+        PlurcastError::InvalidInput(format!("Failed to write confirmation prompt: {}", e))
+    })?;
+
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .map_err(|e| PlurcastError::InvalidInput(format!("Failed to read confirmation: {}", e)))?;
+
+    Ok(input.trim().eq_ignore_ascii_case("y"))
+}
+
+/// Delete a specific failed post
+async fn cmd_failed_delete(db: &Database, post_id: &str, force: bool) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    // Validate post_id format
+    validate_post_id(post_id)?;
+
+    // Check if post exists and is failed
+    let post = db.get_post(post_id).await?;
+    let post = post.ok_or_else(|| PlurcastError::InvalidInput("Post not found".to_string()))?;
+
+    if post.status != libplurcast::PostStatus::Failed {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Post is not in failed status (current: {:?})",
+            post.status
+        )));
+    }
+
+    // Confirm if not forced
+    if !force && !confirm_delete_failed(post_id)? {
+        return Err(PlurcastError::InvalidInput("Cancelled by user".to_string()));
+    }
+
+    // Delete the post (kept as a tombstone until purged)
+    db.soft_delete_post(post_id, Some("deleted")).await?;
+
+    println!("Deleted failed post: {}", post_id);
+    Ok(())
+}
+
+/// List recurring post series
+async fn cmd_series_list(db: &Database, format: &str) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    // Validate format
+    if format != "text" && format != "json" {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Invalid format '{}'. Must be 'text' or 'json'",
+            format
+        )));
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let mut entries = Vec::new();
+    for series in db.list_series().await? {
+        let next = libplurcast::recurrence::pending_occurrence(db, &series.id).await?;
+        entries.push((series, next));
+    }
+
+    if format == "json" {
+        let json: Vec<serde_json::Value> = entries
+            .iter()
+            .map(|(series, next)| {
+                serde_json::json!({
+                    "id": series.id,
+                    "cron": series.cron,
+                    "content": series.content,
+                    "labels": series.labels,
+                    "paused": series.paused,
+                    "created_at": series.created_at,
+                    "next_post_id": next.as_ref().map(|p| p.id.clone()),
+                    "next_scheduled_at": next.as_ref().and_then(|p| p.scheduled_at),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        return Ok(());
+    }
+
+    for (series, next) in &entries {
+        let state = if series.paused { "paused" } else { "active" };
+        let next = next
+            .as_ref()
+            .and_then(|p| p.scheduled_at)
+            .map(|ts| format_time_until(now, ts))
+            .unwrap_or_else(|| "-".to_string());
+
+        println!(
+            "{} | {} | {} | {} | {}",
+            series.id,
+            series.cron,
+            state,
+            next,
+            truncate_content(&series.content, 60)
+        );
+    }
+
+    Ok(())
+}
+
+/// Pause a series, cancelling its pending occurrence
+async fn cmd_series_pause(db: &Database, series_id: &str) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    validate_post_id(series_id)?;
+
+    if !db.set_series_paused(series_id, true).await? {
+        return Err(PlurcastError::InvalidInput("Series not found".to_string()));
+    }
+
+    if let Some(post) = libplurcast::recurrence::pending_occurrence(db, series_id).await? {
+        db.soft_delete_post(&post.id, Some("paused")).await?;
+    }
+
+    println!("Paused series {}", series_id);
+    Ok(())
+}
+
+/// Resume a paused series
+async fn cmd_series_resume(db: &Database, series_id: &str) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    validate_post_id(series_id)?;
+
+    if !db.set_series_paused(series_id, false).await? {
+        return Err(PlurcastError::InvalidInput("Series not found".to_string()));
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    libplurcast::recurrence::schedule_next(db, series_id, now).await?;
+
+    match libplurcast::recurrence::pending_occurrence(db, series_id)
+        .await?
+        .and_then(|p| p.scheduled_at)
+    {
+        Some(next) => println!(
+            "Resumed series {} (next in {})",
+            series_id,
+            format_time_until(now, next)
+        ),
+        None => println!("Resumed series {}", series_id),
+    }
+    Ok(())
+}
+
+/// Cancel a series and its pending occurrence
+async fn cmd_series_cancel(db: &Database, series_id: &str, force: bool) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    validate_post_id(series_id)?;
+
+    if db.get_series(series_id).await?.is_none() {
+        return Err(PlurcastError::InvalidInput("Series not found".to_string()));
+    }
+
+    // Confirm if not forced
+    if !force && !confirm_cancel_series()? {
+        return Err(PlurcastError::InvalidInput("Cancelled by user".to_string()));
+    }
+
+    if let Some(post) = libplurcast::recurrence::pending_occurrence(db, series_id).await? {
+        db.soft_delete_post(&post.id, Some("cancelled")).await?;
+    }
+    db.delete_series(series_id).await?;
+
+    println!("Cancelled series {}", series_id);
+    Ok(())
+}
+
+/// Prompt user for confirmation to cancel a series
+fn confirm_cancel_series() -> Result<bool> {
+    use libplurcast::PlurcastError;
+    use std::io::{self, Write};
+
+    eprint!("Cancel this series and its next occurrence? (y/N): ");
+    io::stderr().flush().map_err(|e| {
+        PlurcastError::InvalidInput(format!("Failed to write confirmation prompt: {}", e))
+    })?;
+
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .map_err(|e| PlurcastError::InvalidInput(format!("Failed to read confirmation: {}", e)))?;
+
+    Ok(input.trim().eq_ignore_ascii_case("y"))
+}
+
+/// Permanently remove soft-deleted posts
+async fn cmd_purge(
+    db: &Database,
+    older_than: Option<u32>,
+    dry_run: bool,
+    force: bool,
+) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    let before =
+        older_than.map(|days| chrono::Utc::now().timestamp() - i64::from(days) * 24 * 60 * 60);
+    let tombstones = db.list_tombstones(before).await?;
+
+    if tombstones.is_empty() {
+        println!("No deleted posts to purge");
+        return Ok(());
+    }
+
+    let count = tombstones.len();
+
+    if dry_run {
+        for tombstone in &tombstones {
+            let deleted_at = chrono::DateTime::from_timestamp(tombstone.deleted_at, 0)
+                .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+            println!(
+                "{}  {}  {}",
+                tombstone.post_id,
+                deleted_at,
+                tombstone.reason.as_deref().unwrap_or("-")
+            );
+        }
+        println!("Would purge {} post(s)", count);
+        return Ok(());
+    }
+
+    // Confirm if not forced
+    if !force && !confirm_purge(count)? {
+        return Err(PlurcastError::InvalidInput("Cancelled by user".to_string()));
+    }
+
+    let purged = db.purge_deleted_posts(before).await?;
+
+    println!("Purged {} post(s)", purged.len());
+    Ok(())
+}
+
+/// Prompt user for confirmation to purge deleted posts
+fn confirm_purge(count: usize) -> Result<bool> {
+    use libplurcast::PlurcastError;
+    use std::io::{self, Write};
+
+    eprint!("Permanently remove {} deleted post(s)? (y/N): ", count);
+    io::stderr().flush().map_err(|e| {
+        PlurcastError::InvalidInput(format!("Failed to write confirmation prompt: {}", e))
+    })?;
+
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .map_err(|e| PlurcastError::InvalidInput(format!("Failed to read confirmation: {}", e)))?;
+
+    Ok(input.trim().eq_ignore_ascii_case("y"))
+}
+
+/// Update post metadata
+///
+/// Currently supports updating Nostr PoW difficulty.
+async fn cmd_update(db: &Database, post_id: &str, nostr_pow: Option<u8>) -> Result<()> {
+    use libplurcast::PlurcastError;
+
+    // Validate that at least one update option is provided
+    if nostr_pow.is_none() {
+        return Err(PlurcastError::InvalidInput(
+            "No update options provided. Use --nostr-pow to update Nostr PoW difficulty."
+                .to_string(),
+        ));
+    }
+
+    // Validate PoW difficulty range
+    if let Some(pow) = nostr_pow {
+        if pow > 64 {
+            return Err(PlurcastError::InvalidInput(format!(
+                "Invalid PoW difficulty: {}. Must be 0-64.",
+                pow
+            )));
+        }
+    }
+
+    // Get the existing post
+    let post = db.get_post(post_id).await?;
+    let post =
+        post.ok_or_else(|| PlurcastError::InvalidInput(format!("Post not found: {}", post_id)))?;
+
+    // Check if post is in a state that can be updated
+    match post.status {
+        libplurcast::PostStatus::Draft | libplurcast::PostStatus::Scheduled => {
+            // OK to update
+        }
+        libplurcast::PostStatus::Pending => {
+            // Pending posts can also be updated (they're in the queue)
+        }
+        libplurcast::PostStatus::Posted
+        | libplurcast::PostStatus::Failed
+        | libplurcast::PostStatus::Deleted
+        | libplurcast::PostStatus::Missed => {
+            return Err(PlurcastError::InvalidInput(
+                format!("Cannot update post with status: {:?}. Only draft, scheduled, or pending posts can be updated.", post.status)
+            ));
+        }
+    }
+
+    // Parse existing metadata or create new
+    let mut metadata: serde_json::Value = if let Some(ref metadata_str) = post.metadata {
+        serde_json::from_str(metadata_str).unwrap_or_else(|_| serde_json::json!({}))
+    } else {
+        serde_json::json!({})
+    };
+
+    // Update Nostr PoW difficulty if provided
+    if let Some(pow) = nostr_pow {
+        if !metadata.is_object() {
+            metadata = serde_json::json!({});
+        }
+
+        // Ensure nostr object exists
+        if metadata.get("nostr").is_none() {
+            metadata["nostr"] = serde_json::json!({});
+        }
+
+        // Update pow_difficulty
+        metadata["nostr"]["pow_difficulty"] = serde_json::json!(pow);
+
+        println!("Updated Nostr PoW difficulty to: {}", pow);
+    }
+
+    // Save updated metadata
+    let metadata_str = serde_json::to_string(&metadata)
+        .map_err(|e| PlurcastError::InvalidInput(format!("Failed to serialize metadata: {}", e)))?;
+
+    db.update_post_metadata(post_id, &metadata_str).await?;
+
+    println!("✓ Post {} updated successfully", post_id);
+
+    Ok(())
+}
+
+/// Prompt user for confirmation to delete a failed post
+fn confirm_delete_failed(post_id: &str) -> Result<bool> {
+    use libplurcast::PlurcastError;
+    use std::io::{self, Write};
+
+    eprint!("Delete failed post {}? (y/N): ", post_id);
+    io::stderr().flush().map_err(|e| {
+        PlurcastError::InvalidInput(format!("Failed to write confirmation prompt: {}", e))
+    })?;
+
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .map_err(|e| PlurcastError::InvalidInput(format!("Failed to read confirmation: {}", e)))?;
+
+    Ok(input.trim().eq_ignore_ascii_case("y"))
+}
+
+/// Run database maintenance and report before/after size
+async fn cmd_maintenance(db: &Database, format: &str) -> Result<()> {
+    use libplurcast::error::DbError;
+    use libplurcast::PlurcastError;
+
+    // Validate format
+    if format != "text" && format != "json" {
+        return Err(PlurcastError::InvalidInput(format!(
+            "Invalid format '{}'. Must be 'text' or 'json'",
+            format
+        )));
+    }
+
+    let report = db.maintenance().await?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        output_maintenance_text(&report);
+    }
+
+    if !report.is_healthy() {
+        return Err(DbError::IntegrityCheckFailed(report.integrity.join("; ")).into());
+    }
+
+    Ok(())
+}
+
+/// Output maintenance report as human-readable text
+fn output_maintenance_text(report: &libplurcast::MaintenanceReport) {
+    if report.is_healthy() {
+        println!("Integrity check: ok");
+    } else {
+        println!("Integrity check: FAILED");
+        for problem in &report.integrity {
+            println!("  {}", problem);
+        }
+        println!("Skipped VACUUM and ANALYZE. Restore from a backup (plur-backup restore).");
+        return;
+    }
+
+    println!("Size before: {}", format_bytes(report.size_before));
+    println!("Size after:  {}", format_bytes(report.size_after));
+
+    let reclaimed = report.size_before.saturating_sub(report.size_after);
+    if reclaimed > 0 {
+        println!("Reclaimed:   {}", format_bytes(reclaimed));
+    }
+}
+
+/// Format a byte count for display
+fn format_bytes(bytes: u64) -> String {
+    const KIB: u64 = 1024;
+    const MIB: u64 = 1024 * 1024;
+
+    if bytes >= MIB {
+        format!("{:.1} MiB", bytes as f64 / MIB as f64)
+    } else if bytes >= KIB {
+        format!("{:.1} KiB", bytes as f64 / KIB as f64)
+    } else {
+        format!("{} B", bytes)
+    }
+}
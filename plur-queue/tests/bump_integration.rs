@@ -0,0 +1,138 @@
+//! Integration tests for plur-queue bump command
+
+use assert_cmd::Command;
+use libplurcast::{Database, Post, PostStatus};
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Setup test environment with config and database
+async fn setup_test_env() -> (TempDir, String, String) {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let db_path = temp_dir.path().join("test.db");
+
+    let config_content = format!(
+        r#"
+[database]
+path = "{}"
+
+[defaults]
+platforms = ["nostr"]
+"#,
+        db_path.display().to_string().replace('\\', "/")
+    );
+
+    fs::write(&config_path, config_content).unwrap();
+
+    // Initialize database
+    let _db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+    (
+        temp_dir,
+        config_path.to_str().unwrap().to_string(),
+        db_path.to_str().unwrap().to_string(),
+    )
+}
+
+/// Create a post with the given status
+async fn create_post(db_path: &str, status: PostStatus) -> String {
+    let db = Database::new(db_path).await.unwrap();
+    let now = chrono::Utc::now().timestamp();
+
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content: "Urgent post".to_string(),
+        created_at: now,
+        scheduled_at: Some(now + 3600),
+        status,
+        metadata: None,
+    };
+    db.create_post(&post).await.unwrap();
+
+    post.id
+}
+
+#[tokio::test]
+async fn test_bump_raises_priority() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_post(&db_path, PostStatus::Scheduled).await;
+
+    for expected in ["priority 1", "priority 2"] {
+        Command::cargo_bin("plur-queue")
+            .unwrap()
+            .env("PLURCAST_CONFIG", &config_path)
+            .args(["bump", &post_id])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(expected));
+    }
+
+    let db = Database::new(&db_path).await.unwrap();
+    assert_eq!(db.get_post_priority(&post_id).await.unwrap(), Some(2));
+}
+
+#[tokio::test]
+async fn test_bump_sets_priority() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_post(&db_path, PostStatus::Scheduled).await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["bump", &post_id, "--priority", "-3"])
+        .assert()
+        .success();
+
+    let db = Database::new(&db_path).await.unwrap();
+    assert_eq!(db.get_post_priority(&post_id).await.unwrap(), Some(-3));
+
+    // List shows the priority
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("priority -3"));
+
+    let output = Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["list", "--format", "json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["priority"], -3);
+}
+
+#[tokio::test]
+async fn test_bump_rejects_unscheduled_post() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_post(&db_path, PostStatus::Posted).await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["bump", &post_id])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains(
+            "Only scheduled posts can be bumped",
+        ));
+}
+
+#[tokio::test]
+async fn test_bump_nonexistent_post() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env().await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["bump", &uuid::Uuid::new_v4().to_string()])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("Post not found"));
+}
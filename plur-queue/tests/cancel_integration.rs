@@ -124,10 +124,39 @@ async fn test_cancel_deletes_post() {
         .assert()
         .success();
 
-    // Verify post was deleted (not just status change)
+    // Verify post was soft-deleted and left a tombstone
     let db = libplurcast::Database::new(&db_path).await.unwrap();
-    let post = db.get_post(&post_ids[0]).await.unwrap();
-    assert!(post.is_none(), "Post should be deleted from database");
+    let post = db.get_post(&post_ids[0]).await.unwrap().unwrap();
+    assert_eq!(post.status, libplurcast::PostStatus::Deleted);
+
+    let tombstone = db.get_tombstone(&post_ids[0]).await.unwrap().unwrap();
+    assert_eq!(
+        tombstone.previous_status,
+        libplurcast::PostStatus::Scheduled
+    );
+    assert_eq!(tombstone.reason.as_deref(), Some("cancelled"));
+}
+
+#[tokio::test]
+async fn test_cancel_already_cancelled_post() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_ids = create_scheduled_posts(&db_path, 1).await;
+
+    let db = libplurcast::Database::new(&db_path).await.unwrap();
+    db.soft_delete_post(&post_ids[0], Some("cancelled"))
+        .await
+        .unwrap();
+
+    let mut cmd = Command::cargo_bin("plur-queue").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("cancel")
+        .arg(&post_ids[0])
+        .arg("--force")
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("Post not found"));
 }
 
 #[tokio::test]
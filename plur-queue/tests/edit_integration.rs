@@ -0,0 +1,236 @@
+//! Integration tests for plur-queue edit command
+
+use assert_cmd::Command;
+use libplurcast::{Database, Post, PostStatus};
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Setup test environment with config and database
+async fn setup_test_env() -> (TempDir, String, String) {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let db_path = temp_dir.path().join("test.db");
+
+    let config_content = format!(
+        r#"
+[database]
+path = "{}"
+
+[defaults]
+platforms = ["nostr"]
+"#,
+        db_path.display().to_string().replace('\\', "/")
+    );
+
+    fs::write(&config_path, config_content).unwrap();
+
+    // Initialize database
+    let _db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+    (
+        temp_dir,
+        config_path.to_str().unwrap().to_string(),
+        db_path.to_str().unwrap().to_string(),
+    )
+}
+
+/// Create a post for the given platform
+async fn create_post(db_path: &str, status: PostStatus, platform: &str) -> String {
+    let db = Database::new(db_path).await.unwrap();
+    let now = chrono::Utc::now().timestamp();
+
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content: "Helo world".to_string(),
+        created_at: now,
+        scheduled_at: Some(now + 3600),
+        status,
+        metadata: Some(format!(r#"{{"platforms":["{}"]}}"#, platform)),
+    };
+    db.create_post(&post).await.unwrap();
+
+    post.id
+}
+
+async fn content_of(db_path: &str, post_id: &str) -> String {
+    let db = Database::new(db_path).await.unwrap();
+    db.get_post(post_id).await.unwrap().unwrap().content
+}
+
+#[tokio::test]
+async fn test_edit_with_argument() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_post(&db_path, PostStatus::Scheduled, "nostr").await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["edit", &post_id, "Hello world"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "Updated post {}",
+            post_id
+        )));
+
+    assert_eq!(content_of(&db_path, &post_id).await, "Hello world");
+}
+
+#[tokio::test]
+async fn test_edit_from_stdin() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_post(&db_path, PostStatus::Scheduled, "nostr").await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["edit", &post_id])
+        .write_stdin("Hello from stdin\n")
+        .assert()
+        .success();
+
+    assert_eq!(content_of(&db_path, &post_id).await, "Hello from stdin");
+}
+
+#[tokio::test]
+async fn test_edit_validates_for_platforms() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_post(&db_path, PostStatus::Scheduled, "mastodon").await;
+
+    // Over Mastodon's default 500 character limit
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["edit", &post_id, &"a".repeat(600)])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("Content validation failed"));
+
+    assert_eq!(content_of(&db_path, &post_id).await, "Helo world");
+}
+
+#[tokio::test]
+async fn test_edit_rejects_empty_content() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_post(&db_path, PostStatus::Scheduled, "nostr").await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["edit", &post_id, "   "])
+        .assert()
+        .failure()
+        .code(3);
+
+    assert_eq!(content_of(&db_path, &post_id).await, "Helo world");
+}
+
+#[tokio::test]
+async fn test_edit_only_scheduled_posts() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_post(&db_path, PostStatus::Posted, "nostr").await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["edit", &post_id, "Hello world"])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains(
+            "Only scheduled posts can be edited",
+        ));
+}
+
+async fn metadata_of(db_path: &str, post_id: &str) -> serde_json::Value {
+    let db = Database::new(db_path).await.unwrap();
+    let post = db.get_post(post_id).await.unwrap().unwrap();
+    serde_json::from_str(post.metadata.as_deref().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_edit_metadata_keeps_content() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_post(&db_path, PostStatus::Scheduled, "nostr").await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["edit", &post_id, "--platform", "nostr,mastodon"])
+        .args(["--content-warning", "Spoilers", "--visibility", "unlisted"])
+        .args(["--lang", "DE"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "Updated post {}",
+            post_id
+        )));
+
+    assert_eq!(content_of(&db_path, &post_id).await, "Helo world");
+    let metadata = metadata_of(&db_path, &post_id).await;
+    assert_eq!(
+        metadata["platforms"],
+        serde_json::json!(["nostr", "mastodon"])
+    );
+    assert_eq!(metadata["content_warning"], "Spoilers");
+    assert_eq!(metadata["visibility"], "unlisted");
+    assert_eq!(metadata["language"], "de");
+
+    // Back to the defaults
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args([
+            "edit",
+            &post_id,
+            "--no-content-warning",
+            "--visibility",
+            "public",
+        ])
+        .assert()
+        .success();
+
+    let metadata = metadata_of(&db_path, &post_id).await;
+    assert!(metadata.get("content_warning").is_none());
+    assert!(metadata.get("visibility").is_none());
+}
+
+#[tokio::test]
+async fn test_edit_validates_new_platforms() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_post(&db_path, PostStatus::Scheduled, "nostr").await;
+
+    // Fine for Nostr, too long for Mastodon
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["edit", &post_id, &"a".repeat(600)])
+        .args(["--platform", "mastodon"])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("Content validation failed"));
+
+    assert_eq!(content_of(&db_path, &post_id).await, "Helo world");
+    assert_eq!(
+        metadata_of(&db_path, &post_id).await["platforms"],
+        serde_json::json!(["nostr"])
+    );
+}
+
+#[tokio::test]
+async fn test_edit_rejects_invalid_language() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_post(&db_path, PostStatus::Scheduled, "nostr").await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["edit", &post_id, "--lang", "english"])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("Invalid language"));
+}
@@ -0,0 +1,172 @@
+//! Integration tests for plur-queue export command
+
+use assert_cmd::Command;
+use libplurcast::{Database, Post, PostStatus};
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Setup test environment with config and database
+async fn setup_test_env() -> (TempDir, String, String) {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let db_path = temp_dir.path().join("test.db");
+
+    let config_content = format!(
+        r#"
+[database]
+path = "{}"
+
+[defaults]
+platforms = ["nostr"]
+"#,
+        db_path.display().to_string().replace('\\', "/")
+    );
+
+    fs::write(&config_path, config_content).unwrap();
+
+    // Initialize database
+    let _db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+    (
+        temp_dir,
+        config_path.to_str().unwrap().to_string(),
+        db_path.to_str().unwrap().to_string(),
+    )
+}
+
+/// Create a scheduled post for the given platforms
+async fn create_scheduled_post(db_path: &str, content: &str, platforms: &str) -> String {
+    let db = Database::new(db_path).await.unwrap();
+    let now = chrono::Utc::now().timestamp();
+
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content: content.to_string(),
+        created_at: now,
+        scheduled_at: Some(now + 3600),
+        status: PostStatus::Scheduled,
+        metadata: Some(format!(r#"{{"platforms":{}}}"#, platforms)),
+    };
+    db.create_post(&post).await.unwrap();
+
+    post.id
+}
+
+#[tokio::test]
+async fn test_export_ics_to_stdout() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_scheduled_post(&db_path, "Launch day, finally", r#"["nostr"]"#).await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["export", "--format", "ics"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("BEGIN:VCALENDAR\r\n"))
+        .stdout(predicate::str::contains(format!(
+            "UID:{}@plurcast",
+            post_id
+        )))
+        .stdout(predicate::str::contains("SUMMARY:Launch day\\, finally"))
+        .stdout(predicate::str::contains("END:VCALENDAR"));
+}
+
+#[tokio::test]
+async fn test_export_ics_to_file_with_platform_filter() {
+    let (temp_dir, config_path, db_path) = setup_test_env().await;
+    let nostr = create_scheduled_post(&db_path, "For nostr", r#"["nostr"]"#).await;
+    let mastodon = create_scheduled_post(&db_path, "For mastodon", r#"["mastodon"]"#).await;
+    let output = temp_dir.path().join("queue.ics");
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["export", "--platform", "mastodon", "-o"])
+        .arg(&output)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Exported 1 post(s)"));
+
+    let ics = fs::read_to_string(&output).unwrap();
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+    assert!(ics.contains(&mastodon));
+    assert!(!ics.contains(&nostr));
+}
+
+#[tokio::test]
+async fn test_export_invalid_format() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env().await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["export", "--format", "csv"])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("Invalid format"));
+}
+
+#[tokio::test]
+async fn test_export_json_round_trips_through_import() {
+    let (temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_scheduled_post(&db_path, "Back me up", r#"["mastodon"]"#).await;
+    {
+        let db = Database::new(&db_path).await.unwrap();
+        db.add_labels(&post_id, &["launch".to_string()])
+            .await
+            .unwrap();
+        db.set_post_priority(&post_id, 5).await.unwrap();
+    }
+    let output = temp_dir.path().join("queue.json");
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["export", "--format", "json", "-o"])
+        .arg(&output)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Exported 1 post(s)"));
+
+    let backup: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+    assert_eq!(backup["version"], 1);
+    assert_eq!(backup["posts"][0]["id"], post_id);
+    assert_eq!(backup["posts"][0]["labels"], serde_json::json!(["launch"]));
+    assert_eq!(backup["posts"][0]["priority"], 5);
+
+    // Still in the queue: nothing to restore
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("import")
+        .arg(&output)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Skipped 1 post(s) already in the queue",
+        ));
+
+    // After losing the queue, the backup brings the post back as it was
+    let db = Database::new(&db_path).await.unwrap();
+    db.delete_post(&post_id).await.unwrap();
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("import")
+        .arg(&output)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 1 post(s)"));
+
+    let posts = db.get_scheduled_posts().await.unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].id, post_id);
+    assert_eq!(posts[0].content, "Back me up");
+    assert_eq!(db.get_labels(&post_id).await.unwrap(), vec!["launch"]);
+    assert_eq!(db.get_post_priority(&post_id).await.unwrap(), Some(5));
+}
@@ -347,7 +347,11 @@ async fn test_failed_delete_specific_post() {
     let deleted_post = db.get_post(&post_id).await.unwrap();
     let kept_post = db.get_post(&keep_id).await.unwrap();
 
-    assert!(deleted_post.is_none(), "Specified post should be deleted");
+    assert_eq!(
+        deleted_post.unwrap().status,
+        PostStatus::Deleted,
+        "Specified post should be soft-deleted"
+    );
     assert!(kept_post.is_some(), "Other failed post should remain");
 }
 
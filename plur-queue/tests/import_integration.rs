@@ -0,0 +1,121 @@
+//! Integration tests for plur-queue import
+
+use assert_cmd::Command;
+use libplurcast::{Database, PostStatus};
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Setup test environment with config and database
+async fn setup_test_env() -> (TempDir, String, String) {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let db_path = temp_dir.path().join("test.db");
+
+    let config_content = format!(
+        r#"
+[database]
+path = "{}"
+
+[defaults]
+platforms = ["nostr"]
+"#,
+        db_path.display().to_string().replace('\\', "/")
+    );
+
+    fs::write(&config_path, config_content).unwrap();
+
+    // Initialize database
+    let _db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+    (
+        temp_dir,
+        config_path.to_str().unwrap().to_string(),
+        db_path.to_str().unwrap().to_string(),
+    )
+}
+
+#[tokio::test]
+async fn test_import_csv() {
+    let (temp_dir, config_path, db_path) = setup_test_env().await;
+    let csv_path = temp_dir.path().join("schedule.csv");
+    fs::write(
+        &csv_path,
+        "time,content,platforms,tags,hashtags\n\
+         2099-01-01T09:00:00Z,\"Launch day, finally\",nostr;mastodon,campaign:launch,launch\n\
+         2099-01-02T09:00:00Z,Day two,,,\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["import", csv_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 2 post(s)"))
+        .stdout(predicate::str::contains("nostr: 2"))
+        .stdout(predicate::str::contains("mastodon: 1"));
+
+    let db = Database::new(&db_path).await.unwrap();
+    let posts = db.get_scheduled_posts().await.unwrap();
+    assert_eq!(posts.len(), 2);
+    assert_eq!(posts[0].content, "Launch day, finally");
+    assert_eq!(posts[0].status, PostStatus::Scheduled);
+    let metadata: serde_json::Value =
+        serde_json::from_str(posts[0].metadata.as_deref().unwrap()).unwrap();
+    assert_eq!(
+        metadata["platforms"],
+        serde_json::json!(["nostr", "mastodon"])
+    );
+    assert_eq!(metadata["hashtags"], serde_json::json!(["launch"]));
+    assert_eq!(
+        db.get_labels(&posts[0].id).await.unwrap(),
+        vec!["campaign:launch"]
+    );
+}
+
+#[tokio::test]
+async fn test_import_is_all_or_nothing() {
+    let (temp_dir, config_path, db_path) = setup_test_env().await;
+    let csv_path = temp_dir.path().join("schedule.csv");
+    fs::write(
+        &csv_path,
+        "time,content,platforms\n\
+         2099-01-01T09:00:00Z,Fine,nostr\n\
+         2001-01-01T09:00:00Z,Too late,nostr\n\
+         2099-01-03T09:00:00Z,Wrong place,myspace\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["import", csv_path.to_str().unwrap()])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("2 of 3 row(s) are invalid"))
+        .stderr(predicate::str::contains("line 3:"))
+        .stderr(predicate::str::contains("unknown platform 'myspace'"));
+
+    let db = Database::new(&db_path).await.unwrap();
+    assert!(db.get_scheduled_posts().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_import_json_dry_run() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["import", "-", "--format", "json", "--dry-run"])
+        .write_stdin(r#"[{"time": "2099-01-01T09:00:00Z", "content": "Hi", "labels": ["a"]}]"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would import 1 post(s)"));
+
+    let db = Database::new(&db_path).await.unwrap();
+    assert!(db.get_scheduled_posts().await.unwrap().is_empty());
+}
@@ -0,0 +1,104 @@
+//! Integration tests for plur-queue interactive mode
+
+use assert_cmd::Command;
+use libplurcast::{Database, Post, PostStatus};
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Setup test environment with config and database
+async fn setup_test_env() -> (TempDir, String, String) {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let db_path = temp_dir.path().join("test.db");
+
+    let config_content = format!(
+        r#"
+[database]
+path = "{}"
+
+[defaults]
+platforms = ["nostr"]
+"#,
+        db_path.display().to_string().replace('\\', "/")
+    );
+
+    fs::write(&config_path, config_content).unwrap();
+
+    // Initialize database
+    let _db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+    (
+        temp_dir,
+        config_path.to_str().unwrap().to_string(),
+        db_path.to_str().unwrap().to_string(),
+    )
+}
+
+/// Create scheduled posts an hour apart, returning their IDs in queue order
+async fn create_posts(db_path: &str, count: usize) -> Vec<String> {
+    let db = Database::new(db_path).await.unwrap();
+    let now = chrono::Utc::now().timestamp();
+
+    let mut ids = Vec::new();
+    for i in 0..count {
+        let post = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: format!("Post number {}", i + 1),
+            created_at: now,
+            scheduled_at: Some(now + 3600 * (i as i64 + 1)),
+            status: PostStatus::Scheduled,
+            metadata: None,
+        };
+        db.create_post(&post).await.unwrap();
+        ids.push(post.id);
+    }
+    ids
+}
+
+#[tokio::test]
+async fn test_interactive_cancel_and_reschedule() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let ids = create_posts(&db_path, 2).await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("interactive")
+        .write_stdin("c 1\ny\nr 1 +1d\nq\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Scheduled posts 1-2 of 2"))
+        .stdout(predicate::str::contains("Cancelled post"))
+        .stdout(predicate::str::contains("Rescheduled post"));
+
+    let db = Database::new(&db_path).await.unwrap();
+    let posts = db.get_scheduled_posts().await.unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].id, ids[1]);
+    let now = chrono::Utc::now().timestamp();
+    assert!(posts[0].scheduled_at.unwrap() > now + 86400);
+}
+
+#[tokio::test]
+async fn test_interactive_keeps_going_after_errors() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    create_posts(&db_path, 12).await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("interactive")
+        .write_stdin("x\nc 99\nn\ns 11\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Scheduled posts 11-12 of 12 (page 2/2)",
+        ))
+        .stdout(predicate::str::contains("Posting"))
+        .stderr(predicate::str::contains("Unknown command 'x'"))
+        .stderr(predicate::str::contains("No post 99 in the queue"));
+
+    let db = Database::new(&db_path).await.unwrap();
+    assert_eq!(db.get_scheduled_posts().await.unwrap().len(), 11);
+}
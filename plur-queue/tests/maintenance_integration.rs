@@ -0,0 +1,96 @@
+//! Integration tests for plur-queue maintenance command
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Helper to escape path for TOML on Windows
+fn escape_path_for_toml(path: &str) -> String {
+    path.replace('\\', "\\\\")
+}
+
+/// Helper to create a test environment with config and a populated database
+async fn setup_test_env() -> (TempDir, String) {
+    use libplurcast::{Database, Post, PostStatus};
+
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let db_path = temp_dir.path().join("posts.db");
+
+    let config_content = format!(
+        r#"
+[database]
+path = "{}"
+
+[defaults]
+platforms = ["nostr"]
+"#,
+        escape_path_for_toml(&db_path.to_string_lossy())
+    );
+    fs::write(&config_path, config_content).unwrap();
+
+    // Create and delete posts so there are free pages to reclaim
+    let db = Database::new(&db_path.to_string_lossy()).await.unwrap();
+    let now = chrono::Utc::now().timestamp();
+    for _ in 0..100 {
+        let post = Post {
+            id: uuid::Uuid::new_v4().to_string(),
+            content: "x".repeat(2000),
+            created_at: now,
+            scheduled_at: Some(now + 3600),
+            status: PostStatus::Scheduled,
+            metadata: None,
+        };
+        db.create_post(&post).await.unwrap();
+        db.delete_post(&post.id).await.unwrap();
+    }
+
+    (temp_dir, config_path.to_string_lossy().to_string())
+}
+
+#[tokio::test]
+async fn test_maintenance_text_output() {
+    let (_temp_dir, config_path) = setup_test_env().await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("maintenance")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Integrity check: ok"))
+        .stdout(predicate::str::contains("Size before:"))
+        .stdout(predicate::str::contains("Size after:"));
+}
+
+#[tokio::test]
+async fn test_maintenance_json_output() {
+    let (_temp_dir, config_path) = setup_test_env().await;
+
+    let output = Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["maintenance", "--format", "json"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["integrity"][0], "ok");
+    assert_eq!(report["compacted"], true);
+    assert!(report["size_after"].as_u64().unwrap() <= report["size_before"].as_u64().unwrap());
+}
+
+#[tokio::test]
+async fn test_maintenance_invalid_format() {
+    let (_temp_dir, config_path) = setup_test_env().await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["maintenance", "--format", "xml"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Invalid format"));
+}
@@ -0,0 +1,95 @@
+//! Integration tests for plur-queue pause and resume commands
+
+use assert_cmd::Command;
+use libplurcast::Database;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Setup test environment with config and database
+async fn setup_test_env() -> (TempDir, String, String) {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let db_path = temp_dir.path().join("test.db");
+
+    let config_content = format!(
+        r#"
+[database]
+path = "{}"
+
+[defaults]
+platforms = ["nostr"]
+"#,
+        db_path.display().to_string().replace('\\', "/")
+    );
+
+    fs::write(&config_path, config_content).unwrap();
+
+    // Initialize database
+    let _db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+    (
+        temp_dir,
+        config_path.to_str().unwrap().to_string(),
+        db_path.to_str().unwrap().to_string(),
+    )
+}
+
+#[tokio::test]
+async fn test_pause_and_resume() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["pause", "--reason", "incident"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Queue paused"));
+
+    let db = Database::new(&db_path).await.unwrap();
+    let pause = db.get_queue_pause().await.unwrap().unwrap();
+    assert_eq!(pause.reason.as_deref(), Some("incident"));
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("stats")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PAUSED since"))
+        .stdout(predicate::str::contains("(incident)"));
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("resume")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Queue resumed"));
+
+    assert!(db.get_queue_pause().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_pause_twice_and_resume_unpaused() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env().await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("resume")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Queue is not paused"));
+
+    for expected in ["Queue paused", "Queue is already paused"] {
+        Command::cargo_bin("plur-queue")
+            .unwrap()
+            .env("PLURCAST_CONFIG", &config_path)
+            .arg("pause")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(expected));
+    }
+}
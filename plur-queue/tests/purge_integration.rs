@@ -0,0 +1,115 @@
+//! Integration tests for plur-queue purge command
+
+use assert_cmd::Command;
+use libplurcast::{Database, Post, PostStatus};
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Setup test environment with config and database
+async fn setup_test_env() -> (TempDir, String, String) {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let db_path = temp_dir.path().join("test.db");
+
+    let config_content = format!(
+        r#"
+[database]
+path = "{}"
+
+[defaults]
+platforms = ["nostr"]
+"#,
+        db_path.display().to_string().replace('\\', "/")
+    );
+
+    fs::write(&config_path, config_content).unwrap();
+
+    // Initialize database
+    let _db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+    (
+        temp_dir,
+        config_path.to_str().unwrap().to_string(),
+        db_path.to_str().unwrap().to_string(),
+    )
+}
+
+/// Create a scheduled post and cancel it
+async fn create_cancelled_post(db_path: &str) -> String {
+    let db = Database::new(db_path).await.unwrap();
+    let now = chrono::Utc::now().timestamp();
+
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content: "Cancelled post".to_string(),
+        created_at: now,
+        scheduled_at: Some(now + 3600),
+        status: PostStatus::Scheduled,
+        metadata: None,
+    };
+    db.create_post(&post).await.unwrap();
+    db.soft_delete_post(&post.id, Some("cancelled"))
+        .await
+        .unwrap();
+
+    post.id
+}
+
+#[tokio::test]
+async fn test_purge_removes_deleted_posts() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_cancelled_post(&db_path).await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("purge")
+        .arg("--force")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Purged 1 post(s)"));
+
+    let db = Database::new(&db_path).await.unwrap();
+    assert!(db.get_post(&post_id).await.unwrap().is_none());
+    assert!(db.get_tombstone(&post_id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_purge_dry_run_keeps_posts() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_cancelled_post(&db_path).await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("purge")
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(&post_id))
+        .stdout(predicate::str::contains("Would purge 1 post(s)"));
+
+    let db = Database::new(&db_path).await.unwrap();
+    assert!(db.get_post(&post_id).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_purge_older_than_skips_recent_deletions() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_cancelled_post(&db_path).await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("purge")
+        .arg("--older-than")
+        .arg("30")
+        .arg("--force")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No deleted posts to purge"));
+
+    let db = Database::new(&db_path).await.unwrap();
+    assert!(db.get_post(&post_id).await.unwrap().is_some());
+}
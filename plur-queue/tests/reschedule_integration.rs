@@ -118,6 +118,41 @@ async fn test_reschedule_with_natural_language() {
         .stdout(predicate::str::contains("Rescheduled post"));
 }
 
+#[tokio::test]
+async fn test_reschedule_missed_post_requeues_it() {
+    use libplurcast::PostStatus;
+
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_scheduled_post(&db_path).await;
+
+    let db = libplurcast::Database::new(&db_path).await.unwrap();
+    db.update_post_status(&post_id, PostStatus::Missed)
+        .await
+        .unwrap();
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("stats")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Missed: 1"))
+        .stdout(predicate::str::contains(&post_id));
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("reschedule")
+        .arg(&post_id)
+        .arg("30m")
+        .assert()
+        .success();
+
+    let post = db.get_post(&post_id).await.unwrap().unwrap();
+    assert_eq!(post.status, PostStatus::Scheduled);
+    assert!(db.get_missed_posts().await.unwrap().is_empty());
+}
+
 // RELATIVE ADJUSTMENT TESTS
 
 #[tokio::test]
@@ -0,0 +1,162 @@
+//! Integration tests for plur-queue series commands
+
+use assert_cmd::Command;
+use libplurcast::recurrence;
+use libplurcast::{Database, Post, PostStatus};
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Setup test environment with config and database
+async fn setup_test_env() -> (TempDir, String, String) {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let db_path = temp_dir.path().join("test.db");
+
+    let config_content = format!(
+        r#"
+[database]
+path = "{}"
+
+[defaults]
+platforms = ["nostr"]
+"#,
+        db_path.display().to_string().replace('\\', "/")
+    );
+
+    fs::write(&config_path, config_content).unwrap();
+
+    // Initialize database
+    let _db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+    (
+        temp_dir,
+        config_path.to_str().unwrap().to_string(),
+        db_path.to_str().unwrap().to_string(),
+    )
+}
+
+/// Create a weekly series with a pending occurrence, returning the series ID
+async fn create_series(db_path: &str) -> String {
+    let db = Database::new(db_path).await.unwrap();
+    let now = chrono::Utc::now().timestamp();
+
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content: "Weekly update".to_string(),
+        created_at: now,
+        scheduled_at: Some(recurrence::next_occurrence("0 9 * * MON", now).unwrap()),
+        status: PostStatus::Scheduled,
+        metadata: None,
+    };
+    db.create_post(&post).await.unwrap();
+
+    recurrence::start_series(&db, &post.id, "0 9 * * MON")
+        .await
+        .unwrap()
+        .id
+}
+
+#[tokio::test]
+async fn test_series_list() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let series_id = create_series(&db_path).await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .arg("series")
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(&series_id))
+        .stdout(predicate::str::contains("0 9 * * MON"))
+        .stdout(predicate::str::contains("active"))
+        .stdout(predicate::str::contains("Weekly update"));
+
+    let output = Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["series", "list", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["id"], series_id.as_str());
+    assert_eq!(json[0]["paused"], false);
+    assert!(json[0]["next_scheduled_at"].is_i64());
+}
+
+#[tokio::test]
+async fn test_series_pause_and_resume() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let series_id = create_series(&db_path).await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["series", "pause", &series_id])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Paused series"));
+
+    let db = Database::new(&db_path).await.unwrap();
+    assert!(db.get_series(&series_id).await.unwrap().unwrap().paused);
+    assert!(recurrence::pending_occurrence(&db, &series_id)
+        .await
+        .unwrap()
+        .is_none());
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["series", "resume", &series_id])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Resumed series"));
+
+    assert!(!db.get_series(&series_id).await.unwrap().unwrap().paused);
+    assert!(recurrence::pending_occurrence(&db, &series_id)
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn test_series_cancel() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let series_id = create_series(&db_path).await;
+
+    let db = Database::new(&db_path).await.unwrap();
+    let pending = recurrence::pending_occurrence(&db, &series_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["series", "cancel", &series_id, "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cancelled series"));
+
+    assert!(db.get_series(&series_id).await.unwrap().is_none());
+    let post = db.get_post(&pending.id).await.unwrap().unwrap();
+    assert_eq!(post.status, PostStatus::Deleted);
+}
+
+#[tokio::test]
+async fn test_series_not_found() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env().await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["series", "pause", &uuid::Uuid::new_v4().to_string()])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("Series not found"));
+}
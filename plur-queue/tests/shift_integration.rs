@@ -0,0 +1,161 @@
+//! Integration tests for plur-queue shift and reschedule --filter
+
+use assert_cmd::Command;
+use libplurcast::{Database, Post, PostStatus};
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Setup test environment with config and database
+async fn setup_test_env() -> (TempDir, String, String) {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let db_path = temp_dir.path().join("test.db");
+
+    let config_content = format!(
+        r#"
+[database]
+path = "{}"
+
+[defaults]
+platforms = ["nostr"]
+"#,
+        db_path.display().to_string().replace('\\', "/")
+    );
+
+    fs::write(&config_path, config_content).unwrap();
+
+    // Initialize database
+    let _db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+    (
+        temp_dir,
+        config_path.to_str().unwrap().to_string(),
+        db_path.to_str().unwrap().to_string(),
+    )
+}
+
+/// Create a scheduled post for the given platform, due `in_secs` from now
+async fn create_post(db_path: &str, platform: &str, in_secs: i64) -> (String, i64) {
+    let db = Database::new(db_path).await.unwrap();
+    let now = chrono::Utc::now().timestamp();
+
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content: format!("Post for {}", platform),
+        created_at: now,
+        scheduled_at: Some(now + in_secs),
+        status: PostStatus::Scheduled,
+        metadata: Some(format!(r#"{{"platforms":["{}"]}}"#, platform)),
+    };
+    db.create_post(&post).await.unwrap();
+
+    (post.id, now + in_secs)
+}
+
+async fn scheduled_at(db_path: &str, post_id: &str) -> i64 {
+    let db = Database::new(db_path).await.unwrap();
+    db.get_post(post_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .scheduled_at
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_shift_all() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let (nostr_id, nostr_at) = create_post(&db_path, "nostr", 3600).await;
+    let (mastodon_id, mastodon_at) = create_post(&db_path, "mastodon", 7200).await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["shift", "--all", "+2h"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Shifted 2 post(s)"));
+
+    assert_eq!(scheduled_at(&db_path, &nostr_id).await, nostr_at + 7200);
+    assert_eq!(
+        scheduled_at(&db_path, &mastodon_id).await,
+        mastodon_at + 7200
+    );
+}
+
+#[tokio::test]
+async fn test_shift_dry_run_changes_nothing() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let (post_id, at) = create_post(&db_path, "nostr", 3600).await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["shift", "--all", "-30m", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would shift 1 post(s) by -30m"))
+        .stdout(predicate::str::contains(&post_id));
+
+    assert_eq!(scheduled_at(&db_path, &post_id).await, at);
+}
+
+#[tokio::test]
+async fn test_shift_into_the_past_changes_nothing() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let (soon_id, soon_at) = create_post(&db_path, "nostr", 600).await;
+    let (later_id, later_at) = create_post(&db_path, "nostr", 86400).await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["shift", "--all", "-1h"])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("nothing was changed"));
+
+    assert_eq!(scheduled_at(&db_path, &soon_id).await, soon_at);
+    assert_eq!(scheduled_at(&db_path, &later_id).await, later_at);
+}
+
+#[tokio::test]
+async fn test_reschedule_with_filter() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let (nostr_id, nostr_at) = create_post(&db_path, "nostr", 3600).await;
+    let (mastodon_id, mastodon_at) = create_post(&db_path, "mastodon", 3600).await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["reschedule", "--filter", "platform=nostr", "+1d"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Shifted 1 post(s)"));
+
+    assert_eq!(scheduled_at(&db_path, &nostr_id).await, nostr_at + 86400);
+    assert_eq!(scheduled_at(&db_path, &mastodon_id).await, mastodon_at);
+}
+
+#[tokio::test]
+async fn test_shift_requires_selection() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env().await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["shift", "+2h"])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("--all or --filter"));
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["shift", "--filter", "colour=blue", "+2h"])
+        .assert()
+        .failure()
+        .code(2);
+}
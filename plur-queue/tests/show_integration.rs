@@ -0,0 +1,118 @@
+//! Integration tests for plur-queue show command
+
+use assert_cmd::Command;
+use libplurcast::{Database, Post, PostStatus};
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Setup test environment with config and database
+async fn setup_test_env() -> (TempDir, String, String) {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let db_path = temp_dir.path().join("test.db");
+
+    let config_content = format!(
+        r#"
+[database]
+path = "{}"
+
+[defaults]
+platforms = ["nostr"]
+"#,
+        db_path.display().to_string().replace('\\', "/")
+    );
+
+    fs::write(&config_path, config_content).unwrap();
+
+    // Initialize database
+    let _db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+    (
+        temp_dir,
+        config_path.to_str().unwrap().to_string(),
+        db_path.to_str().unwrap().to_string(),
+    )
+}
+
+/// Create a scheduled post with the given metadata
+async fn create_post(db_path: &str, metadata: Option<serde_json::Value>) -> String {
+    let db = Database::new(db_path).await.unwrap();
+    let now = chrono::Utc::now().timestamp();
+
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content: "Release day".to_string(),
+        created_at: now,
+        scheduled_at: Some(now + 3600),
+        status: PostStatus::Scheduled,
+        metadata: metadata.map(|m| m.to_string()),
+    };
+    db.create_post(&post).await.unwrap();
+
+    post.id
+}
+
+#[tokio::test]
+async fn test_show_renders_each_platform() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_post(
+        &db_path,
+        Some(serde_json::json!({
+            "platforms": ["nostr", "mastodon"],
+            "hashtags": ["rust"],
+            "content_warning": "Spoilers",
+            "account": "work",
+        })),
+    )
+    .await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["show", &post_id])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("=== nostr (account: work) ==="))
+        .stdout(predicate::str::contains("=== mastodon (account: work) ==="))
+        .stdout(predicate::str::contains("Tags: #rust"))
+        .stdout(predicate::str::contains("Release day\n\n#rust"))
+        .stdout(predicate::str::contains("CW: Spoilers"))
+        .stdout(predicate::str::contains("[11/"));
+}
+
+#[tokio::test]
+async fn test_show_json_uses_default_platforms() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_post(&db_path, None).await;
+
+    let output = Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["show", &post_id, "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["id"], post_id);
+    assert_eq!(json["visibility"], "public");
+    assert_eq!(json["platforms"].as_array().unwrap().len(), 1);
+    assert_eq!(json["platforms"][0]["platform"], "nostr");
+    assert_eq!(json["platforms"][0]["content"], "Release day");
+    assert_eq!(json["platforms"][0]["char_count"], 11);
+}
+
+#[tokio::test]
+async fn test_show_unknown_post() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env().await;
+
+    Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["show", &uuid::Uuid::new_v4().to_string()])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("Post not found"));
+}
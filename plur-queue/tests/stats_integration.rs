@@ -281,6 +281,41 @@ async fn test_stats_json_format_empty() {
         .stdout(predicate::str::contains("\"total\": 0"));
 }
 
+#[tokio::test]
+async fn test_stats_shows_deferred_posts() {
+    use libplurcast::Database;
+
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    create_test_posts(&db_path).await;
+
+    let db = Database::new(&db_path).await.unwrap();
+    let post = db.get_scheduled_posts().await.unwrap().remove(0);
+    let until = chrono::Utc::now().timestamp() + 86400;
+    db.defer_post(&post.id, until, "daily cap of 2 reached on nostr")
+        .await
+        .unwrap();
+
+    let mut cmd = Command::cargo_bin("plur-queue").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("stats")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Deferred: 1"))
+        .stdout(predicate::str::contains("daily cap of 2 reached on nostr"));
+
+    let output = Command::cargo_bin("plur-queue")
+        .unwrap()
+        .env("PLURCAST_CONFIG", &config_path)
+        .args(["stats", "--format", "json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["deferred_count"], 1);
+    assert_eq!(json["deferred"][0]["post_id"], post.id.as_str());
+    assert_eq!(json["deferred"][0]["deferred_until"], until);
+}
+
 // ERROR HANDLING TESTS
 
 #[tokio::test]
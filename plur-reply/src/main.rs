@@ -0,0 +1,435 @@
+//! plur-reply - Reply to a post from the command line
+//!
+//! Unix-style tool: takes the post to answer by its Plurcast UUID or any
+//! platform ID, shows it on stderr, and posts the reply on every platform
+//! the post is on. Outputs the reply's platform IDs like plur-post.
+
+use clap::Parser;
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use libplurcast::service::posting::PostRequest;
+use libplurcast::service::reply::{ReplyContext, ReplyTarget};
+use libplurcast::service::PlurcastService;
+use libplurcast::{Config, PlurcastError, Result, Visibility};
+use std::io::{self, IsTerminal, Read};
+
+#[derive(Parser, Debug)]
+#[command(name = "plur-reply")]
+#[command(version)]
+#[command(about = "Reply to a post from the command line")]
+#[command(long_about = "\
+plur-reply - Reply to a post from the command line
+
+DESCRIPTION:
+    plur-reply posts a reply to a post named by its Plurcast UUID or by its
+    ID on one platform, as printed by plur-post, plur-history, plur-timeline
+    or plur-notify.
+
+    A post in your history is answered on every platform it was sent to,
+    whichever of its IDs is given. Any other post is answered on its own
+    platform. Before posting, the post being answered is shown on stderr:
+    from history, or read from its platform.
+
+    The reply text is the second argument, or stdin when it is missing or
+    \"-\". Output is the reply's platform IDs, as with plur-post.
+
+USAGE EXAMPLES:
+    # Reply to one of your posts everywhere it went
+    plur-reply 550e8400-e29b-41d4-a716-446655440000 \"Update: it shipped!\"
+
+    # Reply to a Mastodon status or Nostr note
+    plur-reply 109876543210 \"Thanks!\"
+    plur-reply note1abc... \"Agreed\"
+
+    # Only answer the Mastodon copy, to followers
+    plur-reply 550e8400-e29b-41d4-a716-446655440000 \"More soon\" \\
+        --platform mastodon --visibility private
+
+    # See what you're answering and where, without posting
+    plur-reply 109876543210 --dry-run
+
+    # Reply to the latest mention
+    plur-timeline --mentions -n 1 --format ndjson | jq -r .id | \\
+        xargs -I{} plur-reply {} \"Thank you!\"
+
+EXIT CODES:
+    0 - Success
+    1 - The reply failed on some platform
+    2 - Authentication error
+    3 - Invalid input (unknown post ID, empty reply, etc.)
+
+For more information, visit: https://github.com/plurcast/plurcast
+")]
+struct Cli {
+    /// Post to reply to: a Plurcast UUID or a platform post ID
+    #[arg(value_name = "ID")]
+    id: String,
+
+    /// Reply text (reads from stdin if not provided or "-")
+    #[arg(value_name = "CONTENT")]
+    content: Option<String>,
+
+    /// Only reply on these platforms (comma-separated)
+    #[arg(short, long, value_name = "PLATFORM", value_delimiter = ',')]
+    platform: Option<Vec<String>>,
+
+    /// Account to reply from (default: the active account)
+    #[arg(long, value_name = "ACCOUNT")]
+    account: Option<String>,
+
+    /// Content warning shown before the reply
+    #[arg(long, value_name = "TEXT")]
+    cw: Option<String>,
+
+    /// Who can see the reply: public, unlisted, private or direct
+    #[arg(long, value_name = "VISIBILITY", default_value = "public", value_parser = parse_visibility)]
+    visibility: Visibility,
+
+    /// Show the post and where the reply would go without posting
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Don't show the post being replied to
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// Output format: text or json
+    #[arg(short, long, default_value = "text", value_parser = ["text", "json"])]
+    format: String,
+
+    /// Verbose output
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT"
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL"
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    match run(cli).await {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
+
+/// Post the reply; returns whether it went out on every platform
+async fn run(cli: Cli) -> Result<bool> {
+    let content_warning = match cli.cw.as_deref().map(str::trim) {
+        Some("") => {
+            return Err(PlurcastError::InvalidInput(
+                "--cw cannot be empty".to_string(),
+            ))
+        }
+        cw => cw.map(String::from),
+    };
+
+    let config = Config::load()?;
+    let language = config.defaults.language.clone();
+    let service = PlurcastService::from_config(config).await?;
+
+    let target = service.reply().resolve(&cli.id).await?;
+    let reply_to = select_platforms(&target, cli.platform.as_deref())?;
+    let mut platforms: Vec<String> = reply_to.keys().cloned().collect();
+    platforms.sort();
+
+    if !cli.quiet || cli.dry_run {
+        let context = service
+            .reply()
+            .context(&target, cli.account.as_deref())
+            .await?;
+        eprintln!("{}", describe_context(&target, context.as_ref()));
+    }
+
+    if cli.dry_run {
+        println!("Would reply on {}", platforms.join(", "));
+        return Ok(true);
+    }
+
+    let content = read_content(cli.content.as_deref())?;
+
+    let request = PostRequest {
+        content,
+        platforms,
+        draft: false,
+        account: cli.account.clone(),
+        scheduled_at: None,
+        nostr_pow: None,
+        nostr_21e8: false,
+        reply_to,
+        thread_parent_uuid: None,
+        thread_sequence: None,
+        labels: vec![],
+        content_warning,
+        visibility: cli.visibility,
+        language,
+        hashtags: vec![],
+        expires_in: None,
+        poll: None,
+        fail_fast: false,
+        media: vec![],
+    };
+    let response = service.posting().post(request).await?;
+
+    match cli.format.as_str() {
+        "json" => {
+            let results: Vec<serde_json::Value> = response
+                .results
+                .iter()
+                .map(|r| {
+                    serde_json::json!({
+                        "platform": r.platform,
+                        "success": r.success,
+                        "post_id": r.post_id,
+                        "error": r.error,
+                    })
+                })
+                .collect();
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "post_id": response.post_id,
+                    "in_reply_to": target.post_id.as_deref().unwrap_or(&target.id),
+                    "results": results,
+                }))
+                .unwrap_or_default()
+            );
+        }
+        _ => {
+            for result in &response.results {
+                match (&result.post_id, &result.error) {
+                    (Some(post_id), _) if result.success => {
+                        println!("{}:{}", result.platform, post_id)
+                    }
+                    (_, Some(error)) => eprintln!("Error [{}]: {}", result.platform, error),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Ok(response.results.iter().all(|r| r.success))
+}
+
+/// The target's platform IDs, narrowed to `--platform`
+fn select_platforms(
+    target: &ReplyTarget,
+    platforms: Option<&[String]>,
+) -> Result<std::collections::HashMap<String, String>> {
+    let platforms = match platforms {
+        Some(platforms) => platforms,
+        None => return Ok(target.reply_to.clone()),
+    };
+
+    let missing: Vec<&str> = platforms
+        .iter()
+        .filter(|p| !target.reply_to.contains_key(p.as_str()))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        return Err(PlurcastError::InvalidInput(format!(
+            "'{}' is not on {}; it can be replied to on: {}",
+            target.id,
+            missing.join(", "),
+            target.platforms().join(", ")
+        )));
+    }
+
+    Ok(target
+        .reply_to
+        .iter()
+        .filter(|(platform, _)| platforms.contains(platform))
+        .map(|(platform, id)| (platform.clone(), id.clone()))
+        .collect())
+}
+
+/// The reply text from the argument or stdin
+fn read_content(content: Option<&str>) -> Result<String> {
+    let content = match content {
+        Some(content) if content != "-" => content.to_string(),
+        _ => {
+            if io::stdin().is_terminal() {
+                return Err(PlurcastError::InvalidInput(
+                    "No reply given. Pass it as an argument or on stdin".to_string(),
+                ));
+            }
+            let mut buffer = String::new();
+            io::stdin()
+                .read_to_string(&mut buffer)
+                .map_err(|e| PlurcastError::InvalidInput(format!("Failed to read stdin: {}", e)))?;
+            buffer
+        }
+    };
+
+    if content.trim().is_empty() {
+        return Err(PlurcastError::InvalidInput(
+            "Reply cannot be empty".to_string(),
+        ));
+    }
+    Ok(content)
+}
+
+/// What is being replied to, quoted for stderr
+///
+/// ```text
+/// Replying to bob@example.social on mastodon (2024-03-01 12:00):
+/// > Nice post!
+/// ```
+fn describe_context(target: &ReplyTarget, context: Option<&ReplyContext>) -> String {
+    let (heading, content_warning, content) = match context {
+        Some(ReplyContext::Own(post)) => (
+            format!(
+                "Replying to your post {} ({})",
+                post.id,
+                format_time(post.created_at)
+            ),
+            None,
+            post.content.as_str(),
+        ),
+        Some(ReplyContext::Remote(entry)) => (
+            format!(
+                "Replying to {} on {} ({})",
+                entry.author,
+                entry.platform,
+                format_time(entry.created_at)
+            ),
+            entry.content_warning.as_deref(),
+            entry.content.as_str(),
+        ),
+        None => {
+            return format!(
+                "Replying to {} on {} (the post couldn't be read)",
+                target.id,
+                target.platforms().join(", ")
+            )
+        }
+    };
+
+    let mut lines = vec![format!("{}:", heading)];
+    if let Some(cw) = content_warning {
+        lines.push(format!("> [CW: {}]", cw));
+    }
+    lines.extend(content.trim().lines().map(|line| format!("> {}", line)));
+    lines.join("\n")
+}
+
+fn format_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .unwrap_or_else(chrono::Utc::now)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+/// Parse a `--visibility` value
+fn parse_visibility(value: &str) -> std::result::Result<Visibility, String> {
+    Visibility::parse(value).ok_or_else(|| {
+        format!(
+            "invalid visibility '{}' (expected public, unlisted, private or direct)",
+            value
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libplurcast::platforms::{TimelineEntry, TimelineKind};
+    use std::collections::HashMap;
+
+    fn target() -> ReplyTarget {
+        ReplyTarget {
+            id: "110".to_string(),
+            post_id: None,
+            reply_to: HashMap::from([
+                ("mastodon".to_string(), "110".to_string()),
+                ("nostr".to_string(), "note1abc".to_string()),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_select_platforms() {
+        let target = target();
+        assert_eq!(select_platforms(&target, None).unwrap().len(), 2);
+
+        let only = select_platforms(&target, Some(&["nostr".to_string()])).unwrap();
+        assert_eq!(only.len(), 1);
+        assert_eq!(only["nostr"], "note1abc");
+
+        match select_platforms(&target, Some(&["ssb".to_string()])) {
+            Err(PlurcastError::InvalidInput(msg)) => {
+                assert!(msg.contains("not on ssb"));
+                assert!(msg.contains("mastodon, nostr"));
+            }
+            other => panic!("Expected InvalidInput, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_describe_context() {
+        let target = target();
+        let entry = TimelineEntry {
+            platform: "mastodon".to_string(),
+            kind: TimelineKind::Home,
+            id: "110".to_string(),
+            author: "bob@example.social".to_string(),
+            author_name: None,
+            content: "Nice post!\nAgreed.".to_string(),
+            content_warning: Some("opinions".to_string()),
+            created_at: 1_709_294_400,
+            url: None,
+            reply_to: None,
+        };
+
+        assert_eq!(
+            describe_context(&target, Some(&ReplyContext::Remote(entry))),
+            "Replying to bob@example.social on mastodon (2024-03-01 12:00):\n\
+             > [CW: opinions]\n\
+             > Nice post!\n\
+             > Agreed."
+        );
+        assert_eq!(
+            describe_context(&target, None),
+            "Replying to 110 on mastodon, nostr (the post couldn't be read)"
+        );
+    }
+}
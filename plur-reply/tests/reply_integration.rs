@@ -0,0 +1,130 @@
+//! Integration tests for plur-reply
+
+use assert_cmd::Command;
+use libplurcast::{Database, Post, PostRecord, PostStatus};
+use predicates::prelude::*;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Write a config file pointing at `db_path`, with no platforms enabled
+fn write_config(dir: &Path, db_path: &Path) -> PathBuf {
+    let config_path = dir.join("config.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "[database]\npath = \"{}\"\n\n[defaults]\nplatforms = []\n",
+            db_path.to_string_lossy().replace('\\', "/")
+        ),
+    )
+    .unwrap();
+    config_path
+}
+
+/// Create a posted post with a copy on each platform
+async fn create_sent_post(db: &Database, copies: &[(&str, &str)]) -> String {
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content: "Shipping the new release today".to_string(),
+        created_at: chrono::Utc::now().timestamp(),
+        scheduled_at: None,
+        status: PostStatus::Posted,
+        metadata: None,
+    };
+    db.create_post(&post).await.unwrap();
+    for (platform, platform_post_id) in copies {
+        db.create_post_record(&PostRecord {
+            id: None,
+            post_id: post.id.clone(),
+            platform: platform.to_string(),
+            platform_post_id: Some(platform_post_id.to_string()),
+            posted_at: Some(post.created_at),
+            success: true,
+            error_message: None,
+            account_name: "default".to_string(),
+        })
+        .await
+        .unwrap();
+    }
+    post.id
+}
+
+async fn setup() -> (TempDir, Database, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("posts.db");
+    let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+    let config_path = write_config(temp_dir.path(), &db_path);
+    (temp_dir, db, config_path)
+}
+
+fn plur_reply(config_path: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("plur-reply").unwrap();
+    cmd.env("PLURCAST_CONFIG", config_path)
+        .env_remove("PLURCAST_DB_PATH");
+    cmd
+}
+
+#[tokio::test]
+async fn test_dry_run_shows_post_and_every_platform() {
+    let (_temp_dir, db, config_path) = setup().await;
+    let post_id = create_sent_post(&db, &[("mastodon", "110"), ("ssb", "%abc=.sha256")]).await;
+
+    plur_reply(&config_path)
+        .args(["%abc=.sha256", "Congrats!", "--dry-run"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!(
+            "Replying to your post {}",
+            post_id
+        )))
+        .stderr(predicate::str::contains("> Shipping the new release today"))
+        .stdout(predicate::str::contains("Would reply on mastodon, ssb"));
+
+    plur_reply(&config_path)
+        .args([post_id.as_str(), "--platform", "mastodon", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would reply on mastodon\n"));
+}
+
+#[tokio::test]
+async fn test_post_not_in_history_is_answered_on_its_platform() {
+    let (_temp_dir, _db, config_path) = setup().await;
+
+    plur_reply(&config_path)
+        .args(["109876543210", "--dry-run"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("the post couldn't be read"))
+        .stdout(predicate::str::contains("Would reply on mastodon"));
+
+    plur_reply(&config_path)
+        .args(["109876543210", "Thanks!", "--platform", "nostr"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("is not on nostr"));
+}
+
+#[tokio::test]
+async fn test_invalid_input() {
+    let (_temp_dir, db, config_path) = setup().await;
+    let post_id = create_sent_post(&db, &[("mastodon", "110")]).await;
+
+    plur_reply(&config_path)
+        .args(["550e8400-e29b-41d4-a716-446655440000", "Hello"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("not found"));
+
+    plur_reply(&config_path)
+        .args(["not-a-post-id", "Hello"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Could not detect platform"));
+
+    plur_reply(&config_path)
+        .args([post_id.as_str(), "-", "--quiet"])
+        .write_stdin("  \n")
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Reply cannot be empty"));
+}
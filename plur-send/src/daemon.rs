@@ -0,0 +1,330 @@
+//! Running plur-send in the background
+//!
+//! `plur-send --daemon` starts a copy of itself in a new session, detached
+//! from the terminal and writing its log to a file, and returns once it is
+//! up. Every running instance records its process ID in a PID file, which
+//! `plur-send status` and `plur-send stop` read and which keeps a second
+//! instance from starting on the same queue.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use libplurcast::{Config, PlurcastError, Result};
+use tracing::warn;
+
+/// PID file name, next to the database unless `--pid-file` is given
+pub const PID_FILE_NAME: &str = "plur-send.pid";
+
+/// Log file name for `--daemon`, next to the database unless `--log-file` is given
+pub const LOG_FILE_NAME: &str = "plur-send.log";
+
+/// How long `--daemon` watches the new process before reporting it started
+const STARTUP_CHECK: Duration = Duration::from_secs(1);
+
+/// A file next to the database
+///
+/// # Errors
+///
+/// Returns an error if the database path can't be resolved.
+pub fn default_path(config: &Config, name: &str) -> Result<PathBuf> {
+    let db_path = libplurcast::config::resolve_db_path(Some(&config.database.path))?;
+    Ok(db_path
+        .parent()
+        .map(|dir| dir.join(name))
+        .unwrap_or_else(|| PathBuf::from(name)))
+}
+
+/// The PID file of the running instance, removed when dropped
+pub struct PidFile {
+    path: PathBuf,
+}
+
+impl PidFile {
+    /// Record this process in the PID file
+    ///
+    /// A PID file left behind by a process that no longer exists is replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if another plur-send is already running with
+    /// this PID file, or if the file can't be written.
+    pub fn acquire(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            if !dir.as_os_str().is_empty() {
+                fs::create_dir_all(dir).map_err(|e| write_error(path, e))?;
+            }
+        }
+
+        // Second pass only after removing a stale file
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(path) {
+                Ok(mut file) => {
+                    writeln!(file, "{}", std::process::id()).map_err(|e| write_error(path, e))?;
+                    return Ok(Self {
+                        path: path.to_path_buf(),
+                    });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => match read_pid(path) {
+                    Some(pid) if is_running(pid) => return Err(already_running(pid, path)),
+                    _ => {
+                        warn!("Removing stale PID file {}", path.display());
+                        fs::remove_file(path).map_err(|e| write_error(path, e))?;
+                    }
+                },
+                Err(e) => return Err(write_error(path, e)),
+            }
+        }
+
+        Err(PlurcastError::InvalidInput(format!(
+            "Could not create PID file {}: another plur-send is starting",
+            path.display()
+        )))
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        // Leave the file alone if another instance has since taken it over
+        if read_pid(&self.path) == Some(std::process::id()) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Process ID recorded in a PID file
+pub fn read_pid(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Process ID of the running plur-send, if any
+pub fn running_pid(pid_file: &Path) -> Option<u32> {
+    read_pid(pid_file).filter(|pid| is_running(*pid))
+}
+
+/// Start plur-send in the background with the current arguments
+///
+/// The new process runs in its own session with stdin closed and stdout and
+/// stderr appended to `log_file`. Returns its process ID once it has stayed
+/// up for a moment.
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if plur-send is already running, the log file
+/// can't be opened, or the new process exits straight away.
+pub fn spawn(pid_file: &Path, log_file: &Path) -> Result<u32> {
+    if let Some(pid) = running_pid(pid_file) {
+        return Err(already_running(pid, pid_file));
+    }
+
+    if let Some(dir) = log_file.parent() {
+        if !dir.as_os_str().is_empty() {
+            fs::create_dir_all(dir).map_err(|e| write_error(log_file, e))?;
+        }
+    }
+    let log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_file)
+        .map_err(|e| write_error(log_file, e))?;
+    let log_err = log.try_clone().map_err(|e| write_error(log_file, e))?;
+
+    let exe = std::env::current_exe().map_err(|e| {
+        PlurcastError::InvalidInput(format!("Cannot find the plur-send executable: {}", e))
+    })?;
+    // The same arguments, minus the ones only the launcher understands
+    let mut args = Vec::new();
+    let mut skip_value = false;
+    for arg in std::env::args_os().skip(1) {
+        if skip_value {
+            skip_value = false;
+            continue;
+        }
+        let text = arg.to_string_lossy();
+        if text == "--daemon" || text.starts_with("--log-file=") {
+            continue;
+        }
+        if text == "--log-file" {
+            skip_value = true;
+            continue;
+        }
+        args.push(arg);
+    }
+
+    let mut command = Command::new(exe);
+    command
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::from(log))
+        .stderr(Stdio::from(log_err));
+    detach(&mut command);
+
+    let mut child = command.spawn().map_err(|e| {
+        PlurcastError::InvalidInput(format!(
+            "Failed to start plur-send in the background: {}",
+            e
+        ))
+    })?;
+
+    let started = Instant::now();
+    while started.elapsed() < STARTUP_CHECK {
+        if let Ok(Some(status)) = child.try_wait() {
+            return Err(PlurcastError::InvalidInput(format!(
+                "plur-send exited during startup ({}); see {}",
+                status,
+                log_file.display()
+            )));
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    Ok(child.id())
+}
+
+/// Ask the running plur-send to shut down and wait for it to exit
+///
+/// Returns the stopped process ID, or `None` if nothing was running.
+///
+/// # Errors
+///
+/// Returns `InvalidInput` if the process can't be signalled or is still
+/// running after `timeout`.
+pub fn stop(pid_file: &Path, timeout: Duration) -> Result<Option<u32>> {
+    let pid = match running_pid(pid_file) {
+        Some(pid) => pid,
+        None => return Ok(None),
+    };
+
+    terminate(pid)?;
+
+    let started = Instant::now();
+    while is_running(pid) {
+        if started.elapsed() >= timeout {
+            return Err(PlurcastError::InvalidInput(format!(
+                "plur-send (pid {}) did not stop within {}s",
+                pid,
+                timeout.as_secs()
+            )));
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+
+    Ok(Some(pid))
+}
+
+fn already_running(pid: u32, pid_file: &Path) -> PlurcastError {
+    PlurcastError::InvalidInput(format!(
+        "plur-send is already running (pid {}, PID file {})",
+        pid,
+        pid_file.display()
+    ))
+}
+
+fn write_error(path: &Path, e: std::io::Error) -> PlurcastError {
+    PlurcastError::InvalidInput(format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(unix)]
+fn is_running(pid: u32) -> bool {
+    // Signal 0 checks the process exists without touching it
+    let result = unsafe { libc::kill(pid as libc::pid_t, 0) };
+    result == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn is_running(_pid: u32) -> bool {
+    // No cheap check without POSIX signals; trust the PID file
+    true
+}
+
+#[cfg(unix)]
+fn terminate(pid: u32) -> Result<()> {
+    if unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) } == 0 {
+        Ok(())
+    } else {
+        Err(PlurcastError::InvalidInput(format!(
+            "Failed to signal plur-send (pid {}): {}",
+            pid,
+            std::io::Error::last_os_error()
+        )))
+    }
+}
+
+#[cfg(not(unix))]
+fn terminate(pid: u32) -> Result<()> {
+    Err(PlurcastError::InvalidInput(format!(
+        "plur-send stop is only supported on Unix; end process {} directly",
+        pid
+    )))
+}
+
+#[cfg(unix)]
+fn detach(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+
+    // Keep the name we were started as: run through a plur-send link to the
+    // `plur` multicall binary, that name is what picks the tool
+    if let Some(name) = std::env::args_os().next() {
+        command.arg0(name);
+    }
+
+    // A new session has no controlling terminal, so closing it (SIGHUP)
+    // doesn't take the daemon down
+    unsafe {
+        command.pre_exec(|| {
+            if libc::setsid() == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn detach(_command: &mut Command) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pid_file_is_exclusive_and_removed_on_drop() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("run").join(PID_FILE_NAME);
+
+        let pid_file = PidFile::acquire(&path).unwrap();
+        assert_eq!(read_pid(&path), Some(std::process::id()));
+        assert_eq!(running_pid(&path), Some(std::process::id()));
+        assert!(PidFile::acquire(&path).is_err());
+
+        drop(pid_file);
+        assert!(!path.exists());
+        assert_eq!(running_pid(&path), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stale_pid_file_is_replaced() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(PID_FILE_NAME);
+
+        // A process that has already exited
+        let mut child = Command::new("true").spawn().unwrap();
+        let stale = child.id();
+        child.wait().unwrap();
+        fs::write(&path, format!("{}\n", stale)).unwrap();
+
+        let _pid_file = PidFile::acquire(&path).unwrap();
+        assert_eq!(read_pid(&path), Some(std::process::id()));
+    }
+
+    #[test]
+    fn test_stop_when_not_running() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(PID_FILE_NAME);
+        assert_eq!(stop(&path, Duration::from_secs(1)).unwrap(), None);
+    }
+}
@@ -0,0 +1,107 @@
+//! Single-instance lease on the queue
+//!
+//! The PID file only keeps out instances that use the same file. The lease
+//! lives in the database itself, so two plur-send processes pointed at the
+//! same queue never both send, whatever their PID files. The holder renews
+//! it on every poll; a second instance refuses to start, or with
+//! `--standby` waits and takes over once the holder stops renewing.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use chrono::Utc;
+use libplurcast::{Database, Result};
+use tokio::time::{sleep, Duration};
+use tracing::info;
+
+/// Shortest lease lifetime in seconds, so a slow send (e.g. Nostr proof of
+/// work) doesn't let a standby instance take over mid-poll
+const MIN_TTL: i64 = 300;
+
+/// This instance's claim on the queue
+pub struct QueueLease {
+    holder: String,
+    ttl: i64,
+    standby: bool,
+}
+
+impl QueueLease {
+    /// A lease that lapses after three missed polls (at least five minutes)
+    pub fn new(poll_interval: u64, standby: bool) -> Self {
+        Self {
+            holder: format!(
+                "pid {} started {}",
+                std::process::id(),
+                Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+            ),
+            ttl: (poll_interval as i64 * 3).max(MIN_TTL),
+            standby,
+        }
+    }
+
+    /// Whether to wait for the lease rather than exit when someone else has it
+    pub fn standby(&self) -> bool {
+        self.standby
+    }
+
+    /// Take or renew the lease
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database can't be updated.
+    pub async fn try_acquire(&self, db: &Database) -> Result<bool> {
+        db.acquire_send_lease(&self.holder, Utc::now().timestamp(), self.ttl)
+            .await
+    }
+
+    /// Wait in standby until the lease is free, checking every poll
+    ///
+    /// Returns false if shutdown was requested first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database can't be read or updated.
+    pub async fn wait(
+        &self,
+        db: &Database,
+        poll_interval: u64,
+        shutdown: &Arc<AtomicBool>,
+    ) -> Result<bool> {
+        info!("Standby: {}", self.describe_holder(db).await?);
+        loop {
+            for _ in 0..poll_interval.max(1) {
+                if shutdown.load(Ordering::Relaxed) {
+                    return Ok(false);
+                }
+                sleep(Duration::from_secs(1)).await;
+            }
+            if self.try_acquire(db).await? {
+                info!("Took over the queue from standby");
+                return Ok(true);
+            }
+        }
+    }
+
+    /// Who holds the lease, for log and error messages
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database can't be read.
+    pub async fn describe_holder(&self, db: &Database) -> Result<String> {
+        Ok(match db.get_send_lease().await? {
+            Some(lease) => format!(
+                "another plur-send ({}) is processing this queue, last seen {}s ago",
+                lease.holder,
+                (Utc::now().timestamp() - lease.renewed_at).max(0)
+            ),
+            None => "another plur-send is processing this queue".to_string(),
+        })
+    }
+
+    /// Give up the lease on shutdown so a standby instance takes over at once
+    pub async fn release(&self, db: &Database) {
+        if let Err(e) = db.release_send_lease(&self.holder).await {
+            tracing::warn!("Failed to release the send lease: {}", e);
+        }
+    }
+}
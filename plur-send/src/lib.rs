@@ -0,0 +1,1439 @@
+//! plur-send - Background daemon for scheduled posting
+//!
+//! Monitors the scheduled post queue and automatically posts content
+//! at the scheduled time.
+
+use clap::{Parser, Subcommand};
+use futures::StreamExt;
+use libplurcast::anchor::{self, AnchorState};
+use libplurcast::blackout;
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use libplurcast::rate_limiter::{CapHit, RateLimiter};
+use libplurcast::recurrence;
+use libplurcast::retention;
+use libplurcast::scheduling;
+use libplurcast::service::events::EventBus;
+use libplurcast::service::posting::PostingService;
+use libplurcast::{Config, Database, Post, PostStatus, Result};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::time::{sleep, Duration};
+use tracing::{error, info, warn};
+
+mod daemon;
+mod lease;
+
+#[derive(Parser, Debug)]
+#[command(name = "plur-send")]
+#[command(version)]
+#[command(about = "Background daemon for scheduled posting")]
+#[command(long_about = "\
+plur-send - Background daemon for scheduled posting
+
+DESCRIPTION:
+    plur-send is a long-running daemon that monitors the Plurcast queue
+    and automatically posts scheduled content at the right time.
+
+    It polls the database at regular intervals, checks for posts that are
+    due, handles rate limiting, implements retry logic, and updates post
+    status after successful/failed posting.
+
+USAGE:
+    # Run in foreground (logs to stderr)
+    plur-send
+
+    # Run with custom poll interval
+    plur-send --poll-interval 30
+
+    # Enable verbose logging
+    plur-send --verbose
+
+    # Run in the background (logs to plur-send.log next to the database)
+    plur-send --daemon
+
+    # Check on and stop the background daemon
+    plur-send status
+    plur-send stop
+
+BACKGROUND MODE:
+    --daemon starts plur-send in a new session, detached from the terminal,
+    with its log appended to --log-file, and returns once it is running.
+    Every instance (background or not) writes its process ID to the PID
+    file (plur-send.pid next to the database, or --pid-file) and removes it
+    on exit; a second instance using the same PID file exits with an error.
+    plur-send status reports whether it is running (exit code 0) or not
+    (exit code 1); plur-send stop sends SIGTERM and waits for it to exit.
+
+SINGLE INSTANCE:
+    Besides the PID file, plur-send takes a lease on the queue in the
+    database and renews it on every poll, so two instances pointed at the
+    same database never both send. A second instance exits with an error,
+    or with --standby waits and takes over once the first stops (at once
+    on a clean shutdown, otherwise after three missed polls, at least five
+    minutes).
+
+PAUSING:
+    While the queue is paused with plur-queue pause, plur-send keeps running
+    but sends nothing (no due posts, no retries) until plur-queue resume.
+    The flag is stored in the database and checked on every poll.
+
+BLACKOUT WINDOWS AND CAPS:
+    Due posts that would go out during a [[scheduling.blackouts]] window
+    for any of their platforms are rescheduled to the next time none of
+    them is blacked out. Posts over a [scheduling.caps] daily or weekly cap
+    (local calendar days, weeks from Monday) wait for the next period;
+    retries of failed posts wait for it too, and are held on a platform
+    until its blackout ends. Deferred posts show up in plur-queue stats.
+
+JITTER:
+    With [scheduling] jitter or plur-post --jitter, each post goes out at a
+    fixed, per-post offset within the jitter either side of its scheduled
+    time, so automated posts don't all land on the minute.
+
+MISSED POSTS:
+    [scheduling] catch_up decides what happens on startup to posts that
+    fell due while plur-send wasn't running (more than one poll interval
+    late): "immediate" sends them (the default), "manual" marks them
+    missed, and a duration such as "6h" sends only those less late than
+    that. Missed posts are listed by plur-queue stats and go back into the
+    queue with plur-queue reschedule.
+
+SEND WORKERS:
+    [scheduling] workers (or --workers) lets plur-send send several due
+    posts at once. Posts that share a platform still go one at a time and in
+    order, so rate limits, caps and scheduled threads behave as with a
+    single worker; posts to different platforms go out side by side.
+
+STAGGERED POSTS:
+    Posts scheduled with plur-post --schedule-for PLATFORM=TIME go to each
+    platform at its own time; plur-send sends the platforms that are due and
+    keeps the post queued until the last one has gone out.
+
+FOLLOW-UP POSTS:
+    Posts scheduled with plur-post --schedule \"after:<POST_ID>+30m\" are
+    held until the anchor post is sent, then go out that long after it.
+
+EXPIRING POSTS:
+    Posts sent with plur-post --expires 2h are deleted from Mastodon once
+    they expire (Nostr relays drop them on their own, via NIP-40). This
+    happens on every poll, also while the queue is paused; a deletion that
+    fails is retried on later polls, up to five times.
+
+RECURRING POSTS:
+    Posts scheduled with plur-post --schedule \"cron:...\" belong to a
+    series. After sending an occurrence, plur-send schedules the next one.
+
+SIGNALS:
+    SIGTERM, SIGINT - Graceful shutdown (finishes current post)
+
+CONFIGURATION:
+    Configuration file: ~/.config/plurcast/config.toml
+    Database location: ~/.local/share/plurcast/posts.db
+
+    [scheduling]
+    poll_interval = 60  # seconds between polls
+    max_retries = 3     # retry failed posts
+    retry_delay = 300   # seconds between retries
+    jitter = 600        # send within 10 minutes either side of the schedule
+    catch_up = \"6h\"     # on startup, hold posts more than 6 hours late
+    workers = 4         # send up to 4 due posts at once
+
+    [[scheduling.blackouts]]
+    hours = \"23:00-07:00\"  # local time; days = [\"weekends\"] and
+                            # platforms = [\"mastodon\"] narrow it down
+
+    [scheduling.rate_limits]
+    nostr = { posts_per_hour = 100 }
+    mastodon = { posts_per_hour = 300 }
+
+    [scheduling.caps]
+    mastodon = { posts_per_day = 20, posts_per_week = 100 }
+
+    [retention]
+    max_age_days = 180      # prune published posts older than this
+    prune_interval = 86400  # seconds between pruning runs (unset = never)
+
+EXIT CODES:
+    0 - Clean shutdown
+    1 - Runtime error
+    2 - Configuration error
+
+For more information, visit: https://github.com/plurcast/plurcast
+")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
+    /// Run in the background
+    #[arg(long, conflicts_with = "once")]
+    #[arg(help = "Run in the background, detached from the terminal, logging to --log-file")]
+    daemon: bool,
+
+    /// PID file (default: plur-send.pid next to the database)
+    #[arg(long, value_name = "PATH", global = true)]
+    #[arg(
+        help = "File holding the running instance's process ID (default: plur-send.pid next to the database)"
+    )]
+    pid_file: Option<PathBuf>,
+
+    /// Log file for --daemon (default: plur-send.log next to the database)
+    #[arg(long, value_name = "PATH", requires = "daemon")]
+    #[arg(help = "Where --daemon writes its log (default: plur-send.log next to the database)")]
+    log_file: Option<PathBuf>,
+
+    /// Wait for the queue instead of exiting if another instance has it
+    #[arg(long)]
+    #[arg(
+        help = "If another plur-send is processing the queue, wait and take over when it stops instead of exiting"
+    )]
+    standby: bool,
+
+    /// Poll interval in seconds (overrides config)
+    #[arg(long, value_name = "SECONDS")]
+    #[arg(help = "How often to check for scheduled posts (default: 60)")]
+    poll_interval: Option<u64>,
+
+    /// Enable verbose logging to stderr
+    #[arg(short, long)]
+    #[arg(help = "Enable verbose logging (useful for debugging)")]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT"
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL"
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+
+    /// Run once and exit (for testing)
+    #[arg(long, hide = true)]
+    #[arg(help = "Process due posts once and exit (for testing)")]
+    once: bool,
+
+    /// Due posts to send at once (overrides config)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..=32))]
+    #[arg(
+        help = "How many due posts to send at once (default: 1). Posts that share a platform still go one at a time"
+    )]
+    workers: Option<u64>,
+
+    /// Startup delay in seconds before processing retries
+    #[arg(long, value_name = "SECONDS")]
+    #[arg(help = "Delay before processing retries on startup (prevents burst retries)")]
+    startup_delay: Option<u64>,
+
+    /// Disable retry processing (only process scheduled posts)
+    #[arg(long)]
+    #[arg(help = "Disable automatic retry of failed posts")]
+    no_retry: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Show whether plur-send is running
+    Status,
+
+    /// Stop the running plur-send
+    Stop {
+        /// Seconds to wait for it to finish the current post and exit
+        #[arg(long, default_value = "30", value_name = "SECONDS")]
+        timeout: u64,
+    },
+}
+
+/// Run plur-send with a command line, program name first
+///
+/// The `plur-send` binary and `plur send` both start here.
+#[tokio::main]
+pub async fn main_from(args: Vec<OsString>) -> Result<()> {
+    let cli = Cli::parse_from(args);
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    // Load configuration
+    let config = Config::load()?;
+
+    let pid_file = match cli.pid_file.clone() {
+        Some(path) => path,
+        None => daemon::default_path(&config, daemon::PID_FILE_NAME)?,
+    };
+
+    match cli.command {
+        Some(Commands::Status) => match daemon::running_pid(&pid_file) {
+            Some(pid) => {
+                println!("plur-send is running (pid {})", pid);
+                return Ok(());
+            }
+            None => {
+                println!("plur-send is not running");
+                std::process::exit(1);
+            }
+        },
+        Some(Commands::Stop { timeout }) => {
+            match daemon::stop(&pid_file, Duration::from_secs(timeout))? {
+                Some(pid) => println!("plur-send stopped (pid {})", pid),
+                None => println!("plur-send is not running"),
+            }
+            return Ok(());
+        }
+        None => {}
+    }
+
+    if cli.daemon {
+        let log_file = match cli.log_file.clone() {
+            Some(path) => path,
+            None => daemon::default_path(&config, daemon::LOG_FILE_NAME)?,
+        };
+        let pid = daemon::spawn(&pid_file, &log_file)?;
+        println!("plur-send started (pid {})", pid);
+        println!("Log: {}", log_file.display());
+        return Ok(());
+    }
+
+    // Held until exit; refuses to start if another instance has it
+    let _pid_file = daemon::PidFile::acquire(&pid_file)?;
+
+    let db = Database::from_config(&config).await?;
+
+    info!("plur-send daemon starting");
+
+    // Create posting service
+    let event_bus = EventBus::new(100);
+    let posting = PostingService::new(Arc::new(db.clone()), Arc::new(config.clone()), event_bus);
+
+    // Create rate limiter from config
+    let rate_limits = create_rate_limits(&config);
+    let caps = config
+        .scheduling
+        .as_ref()
+        .map(|s| s.caps.clone())
+        .unwrap_or_default();
+    let rate_limiter = RateLimiter::new(rate_limits).with_caps(caps);
+
+    // Set up graceful shutdown
+    let shutdown = Arc::new(AtomicBool::new(false));
+    setup_signal_handlers(shutdown.clone())?;
+
+    // Determine poll interval (CLI overrides config)
+    let poll_interval = cli
+        .poll_interval
+        .or_else(|| config.scheduling.as_ref().map(|s| s.poll_interval))
+        .unwrap_or(60);
+    info!("Poll interval: {}s", poll_interval);
+
+    // Log scheduling configuration
+    if let Some(ref sched_config) = config.scheduling {
+        info!(
+            "Scheduling config: max_retries={}, retry_delay={}s, rate_limits={} platforms",
+            sched_config.max_retries,
+            sched_config.retry_delay,
+            sched_config.rate_limits.len()
+        );
+    } else {
+        info!("No scheduling configuration found, using defaults");
+    }
+
+    // Determine startup delay
+    let startup_delay = cli
+        .startup_delay
+        .or_else(|| config.scheduling.as_ref().and_then(|s| s.startup_delay))
+        .unwrap_or(0);
+
+    // Only log startup delay if retry processing is enabled
+    if !cli.no_retry && startup_delay > 0 {
+        info!(
+            "Waiting {}s before processing retries (startup delay)",
+            startup_delay
+        );
+    }
+
+    // Only one instance sends from a queue, whatever its PID file
+    let lease = lease::QueueLease::new(poll_interval, cli.standby);
+    if !lease.try_acquire(&db).await? {
+        if !lease.standby() {
+            return Err(libplurcast::PlurcastError::InvalidInput(format!(
+                "{} (use --standby to wait for it)",
+                lease.describe_holder(&db).await?
+            )));
+        }
+        if !lease.wait(&db, poll_interval, &shutdown).await? {
+            info!("plur-send daemon stopped");
+            return Ok(());
+        }
+    }
+
+    let result = run(
+        &cli,
+        &db,
+        &posting,
+        &rate_limiter,
+        &config,
+        &lease,
+        poll_interval,
+        shutdown,
+        startup_delay,
+    )
+    .await;
+
+    lease.release(&db).await;
+    info!("plur-send daemon stopped");
+    result
+}
+
+/// Send posts once or until shutdown, while holding the queue lease
+#[allow(clippy::too_many_arguments)]
+async fn run(
+    cli: &Cli,
+    db: &Database,
+    posting: &PostingService,
+    rate_limiter: &RateLimiter,
+    config: &Config,
+    lease: &lease::QueueLease,
+    poll_interval: u64,
+    shutdown: Arc<AtomicBool>,
+    startup_delay: u64,
+) -> Result<()> {
+    // Send workers (CLI overrides config)
+    let workers = cli
+        .workers
+        .map(|w| w as usize)
+        .or_else(|| config.scheduling.as_ref().and_then(|s| s.workers))
+        .unwrap_or(1);
+    if workers > 1 {
+        info!("Send workers: {}", workers);
+    }
+
+    // Deal with posts that fell due while the daemon was down
+    catch_up_missed_posts(db, config, poll_interval).await?;
+
+    // Main daemon loop
+    if cli.once {
+        // Run once for testing
+        process_due_posts(db, posting, rate_limiter, config, workers).await?;
+        delete_expired_posts(db, config).await;
+        if !cli.no_retry {
+            // Apply startup delay before retry processing in --once mode
+            if startup_delay > 0 {
+                sleep(Duration::from_secs(startup_delay)).await;
+            }
+            process_retry_posts(db, posting, rate_limiter, config).await?;
+        } else {
+            info!("Skipping retry processing (--no-retry flag set)");
+        }
+        info!("plur-send: processed posts once, exiting");
+    } else {
+        // Normal daemon mode
+        run_daemon_loop(
+            db,
+            posting,
+            rate_limiter,
+            config,
+            lease,
+            poll_interval,
+            shutdown,
+            startup_delay,
+            cli.no_retry,
+            workers,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Apply the retention policy if `prune_interval` has elapsed
+///
+/// Errors are logged rather than returned so that a pruning problem never
+/// stops scheduled posts from going out.
+async fn run_retention(
+    db: &Database,
+    config: &Config,
+    last_prune: &mut Option<std::time::Instant>,
+) {
+    let (policy, interval) = match config.retention.as_ref() {
+        Some(policy) => match policy.prune_interval {
+            Some(interval) => (policy, interval),
+            None => return,
+        },
+        None => return,
+    };
+
+    if let Some(last) = last_prune {
+        if last.elapsed() < Duration::from_secs(interval) {
+            return;
+        }
+    }
+    *last_prune = Some(std::time::Instant::now());
+
+    match retention::prune(db, policy, false).await {
+        Ok(report) if report.deleted > 0 => {
+            info!(
+                "Retention: pruned {} post(s) older than {} days",
+                report.deleted, policy.max_age_days
+            );
+        }
+        Ok(_) => {}
+        Err(e) => error!("Error applying retention policy: {}", e),
+    }
+}
+
+/// Delete posts whose `--expires` lifetime has run out
+///
+/// Errors are logged rather than returned, as with retention.
+async fn delete_expired_posts(db: &Database, config: &Config) {
+    let now = chrono::Utc::now().timestamp();
+    match libplurcast::expiry::delete_due(db, config, now).await {
+        Ok(deleted) if deleted > 0 => {
+            info!("Deleted {} expired post(s)", deleted);
+        }
+        Ok(_) => {}
+        Err(e) => error!("Error deleting expired posts: {}", e),
+    }
+}
+
+/// Create rate limits map from config
+fn create_rate_limits(config: &Config) -> HashMap<String, u32> {
+    let mut limits = HashMap::new();
+
+    if let Some(ref sched_config) = config.scheduling {
+        for (platform, rate_config) in &sched_config.rate_limits {
+            limits.insert(platform.clone(), rate_config.posts_per_hour);
+        }
+    }
+
+    limits
+}
+
+/// Set up signal handlers for graceful shutdown (Unix only)
+#[cfg(unix)]
+fn setup_signal_handlers(shutdown: Arc<AtomicBool>) -> Result<()> {
+    use signal_hook::consts::{SIGINT, SIGTERM};
+    use signal_hook::iterator::Signals;
+
+    let mut signals = Signals::new([SIGINT, SIGTERM]).map_err(|e| {
+        libplurcast::PlurcastError::InvalidInput(format!("Signal setup failed: {}", e))
+    })?;
+
+    // Spawn thread to handle signals
+    let shutdown_clone = shutdown.clone();
+    std::thread::spawn(move || {
+        for sig in signals.forever() {
+            match sig {
+                SIGTERM | SIGINT => {
+                    info!("Received shutdown signal, stopping gracefully...");
+                    shutdown_clone.store(true, Ordering::Relaxed);
+                    break;
+                }
+                _ => {}
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// No-op signal handler for Windows
+#[cfg(not(unix))]
+fn setup_signal_handlers(_shutdown: Arc<AtomicBool>) -> Result<()> {
+    // Windows doesn't support POSIX signals
+    // Use Ctrl+C handler from tokio or ctrlc crate if needed
+    Ok(())
+}
+
+/// Main daemon loop
+#[allow(clippy::too_many_arguments)]
+async fn run_daemon_loop(
+    db: &Database,
+    posting: &PostingService,
+    rate_limiter: &RateLimiter,
+    config: &Config,
+    lease: &lease::QueueLease,
+    poll_interval: u64,
+    shutdown: Arc<AtomicBool>,
+    startup_delay: u64,
+    no_retry: bool,
+    workers: usize,
+) -> Result<()> {
+    // Track if this is the first iteration (for startup delay)
+    let mut first_iteration = true;
+
+    // Last time the retention policy ran (None = not yet this session)
+    let mut last_prune: Option<std::time::Instant> = None;
+
+    loop {
+        // Check for shutdown signal
+        if shutdown.load(Ordering::Relaxed) {
+            info!("Shutdown requested, stopping daemon loop");
+            break;
+        }
+
+        // Renew the lease; if another instance took over, stand by or stop
+        match lease.try_acquire(db).await {
+            Ok(true) => {}
+            Ok(false) if lease.standby() => {
+                warn!("Lost the queue to another plur-send");
+                if !lease.wait(db, poll_interval, &shutdown).await? {
+                    break;
+                }
+            }
+            Ok(false) => {
+                return Err(libplurcast::PlurcastError::InvalidInput(format!(
+                    "Lost the queue: {}",
+                    lease.describe_holder(db).await?
+                )));
+            }
+            Err(e) => {
+                // Without a renewed lease, don't send this round
+                error!("Error renewing send lease: {}", e);
+                for _ in 0..poll_interval {
+                    if shutdown.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    sleep(Duration::from_secs(1)).await;
+                }
+                continue;
+            }
+        }
+
+        // Always process due scheduled posts
+        if let Err(e) = process_due_posts(db, posting, rate_limiter, config, workers).await {
+            error!("Error processing posts: {}", e);
+        }
+
+        // Delete expired posts from platforms without native expiry
+        delete_expired_posts(db, config).await;
+
+        // Process retry attempts (with startup delay and no_retry flag)
+        if !no_retry {
+            if first_iteration && startup_delay > 0 {
+                // On first iteration, wait before processing retries
+                info!(
+                    "Applying startup delay of {}s before processing retries",
+                    startup_delay
+                );
+                for _ in 0..startup_delay {
+                    if shutdown.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    sleep(Duration::from_secs(1)).await;
+                }
+                first_iteration = false;
+            }
+
+            // Skip retry processing if shutdown was requested during startup delay
+            if !shutdown.load(Ordering::Relaxed) {
+                if let Err(e) = process_retry_posts(db, posting, rate_limiter, &config).await {
+                    error!("Error processing retries: {}", e);
+                }
+            }
+        } else {
+            first_iteration = false; // Clear flag even if retries disabled
+        }
+
+        if !shutdown.load(Ordering::Relaxed) {
+            run_retention(db, config, &mut last_prune).await;
+        }
+
+        // Sleep until next poll (check shutdown every second)
+        for _ in 0..poll_interval {
+            if shutdown.load(Ordering::Relaxed) {
+                break;
+            }
+            sleep(Duration::from_secs(1)).await;
+        }
+    }
+
+    Ok(())
+}
+
+/// Apply the `[scheduling] catch_up` policy to posts missed while down
+///
+/// Runs once at startup. Posts less than one poll interval late count as on
+/// time, so `--once` runs from cron don't treat the gap between runs as
+/// downtime. Skipped while the queue is paused: those posts were held on
+/// purpose and go out on resume.
+async fn catch_up_missed_posts(db: &Database, config: &Config, poll_interval: u64) -> Result<()> {
+    let policy = match config
+        .scheduling
+        .as_ref()
+        .and_then(|s| s.catch_up.as_deref())
+    {
+        Some(policy) => scheduling::CatchUp::parse(policy)?,
+        None => return Ok(()),
+    };
+    if policy == scheduling::CatchUp::Immediate || db.get_queue_pause().await?.is_some() {
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let grace = poll_interval as i64;
+    let default_jitter = default_jitter(config);
+
+    for post in db.get_scheduled_posts_due_by(now - grace).await? {
+        let late = match scheduling::send_time(&post, default_jitter) {
+            Some(at) => now - at,
+            None => continue,
+        };
+        if late <= grace || policy.sends(late) {
+            continue;
+        }
+
+        db.update_post_status(&post.id, PostStatus::Missed).await?;
+        warn!(
+            "Post {} is {}m late; marked missed (plur-queue reschedule to send it)",
+            post.id,
+            late / 60
+        );
+
+        // A missed occurrence shouldn't end its series
+        if let Some(series_id) = recurrence::series_id(&post) {
+            if let Err(e) = recurrence::schedule_next(db, &series_id, now).await {
+                warn!(
+                    "Failed to schedule next occurrence of series {}: {}",
+                    series_id, e
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// `[scheduling] jitter` in seconds, capped at the maximum
+fn default_jitter(config: &Config) -> i64 {
+    config
+        .scheduling
+        .as_ref()
+        .and_then(|s| s.jitter)
+        .map(|j| j.min(scheduling::MAX_JITTER_SECONDS as u64) as i64)
+        .unwrap_or(0)
+}
+
+/// Process all posts that are due for posting
+async fn process_due_posts(
+    db: &Database,
+    posting: &PostingService,
+    rate_limiter: &RateLimiter,
+    config: &Config,
+    workers: usize,
+) -> Result<()> {
+    // Nothing goes out while the queue is paused (plur-queue pause)
+    if let Some(pause) = db.get_queue_pause().await? {
+        info!(
+            "Queue paused{}; not sending due posts",
+            pause
+                .reason
+                .map(|r| format!(" ({})", r))
+                .unwrap_or_default()
+        );
+        return Ok(());
+    }
+
+    // Get posts that are due, looking ahead for jitter that sends them early
+    let now = chrono::Utc::now().timestamp();
+    let default_jitter = default_jitter(config);
+    let due_posts: Vec<Post> = db
+        .get_scheduled_posts_due_by(now + scheduling::MAX_JITTER_SECONDS)
+        .await?
+        .into_iter()
+        .filter(|post| matches!(scheduling::send_time(post, default_jitter), Some(at) if at <= now))
+        .collect();
+
+    if due_posts.is_empty() {
+        return Ok(());
+    }
+
+    info!("Found {} post(s) due for posting", due_posts.len());
+
+    let blackouts = blackout::parse_all(
+        config
+            .scheduling
+            .as_ref()
+            .map(|s| s.blackouts.as_slice())
+            .unwrap_or_default(),
+    )?;
+
+    // Posts sharing a platform go one at a time and in order, so rate
+    // limits, caps and thread order hold; posts on other platforms overlap
+    let mut lanes: HashMap<String, Arc<tokio::sync::Mutex<()>>> = HashMap::new();
+    for post in &due_posts {
+        for platform in lane_platforms(post, config) {
+            lanes.entry(platform).or_default();
+        }
+    }
+    let lanes = &lanes;
+    let blackouts = &blackouts;
+
+    futures::stream::iter(due_posts)
+        .for_each_concurrent(workers.max(1), |post| async move {
+            let mut guards = Vec::new();
+            for platform in lane_platforms(&post, config) {
+                if let Some(lane) = lanes.get(&platform) {
+                    guards.push(lane.lock().await);
+                }
+            }
+
+            let post_id = post.id.clone();
+            if let Err(e) =
+                process_due_post(db, posting, rate_limiter, config, blackouts, post, now).await
+            {
+                error!("Error processing post {}: {}", post_id, e);
+            }
+        })
+        .await;
+
+    Ok(())
+}
+
+/// Platforms a due post may send to, sorted, for taking their send lanes
+fn lane_platforms(post: &Post, config: &Config) -> Vec<String> {
+    let mut platforms = extract_platforms(post);
+    if platforms.is_empty() {
+        platforms = config.defaults.platforms.clone();
+    }
+    platforms.sort();
+    platforms.dedup();
+    platforms
+}
+
+/// Check one due post against anchors, blackouts, caps and rate limits, and
+/// send it
+async fn process_due_post(
+    db: &Database,
+    posting: &PostingService,
+    rate_limiter: &RateLimiter,
+    config: &Config,
+    blackouts: &[blackout::Blackout],
+    post: Post,
+    now: i64,
+) -> Result<()> {
+    info!("Processing post: {}", post.id);
+
+    // Follow-ups (--schedule "after:<id>+30m") wait for their anchor
+    if let Some(anchor) = anchor::post_anchor(&post) {
+        match anchor::anchor_state(db, &anchor.post_id).await? {
+            AnchorState::Waiting => {
+                info!(
+                    "Post {} is waiting for anchor post {}",
+                    post.id, anchor.post_id
+                );
+                return Ok(());
+            }
+            AnchorState::Sent(sent_at) => {
+                let at = sent_at + anchor.delay;
+                if at > now {
+                    db.update_post_schedule(&post.id, Some(at)).await?;
+                    info!(
+                        "Post {} follows anchor post {}; rescheduled to {}",
+                        post.id, anchor.post_id, at
+                    );
+                    return Ok(());
+                }
+            }
+            AnchorState::Gone => {
+                warn!(
+                    "Anchor post {} of post {} is gone; sending at its scheduled time",
+                    anchor.post_id, post.id
+                );
+            }
+        }
+    }
+
+    // Extract platforms from metadata or use defaults
+    let platforms = extract_platforms(&post);
+
+    // Staggered posts (--schedule-for) go only to the platforms now due
+    let schedules = db.get_platform_schedules(&post.id).await?;
+    let staggered = !schedules.is_empty();
+    let platforms: Vec<String> = if staggered {
+        let slot = post.scheduled_at.unwrap_or(now);
+        schedules
+            .iter()
+            .filter(|s| s.sent_at.is_none() && s.scheduled_at <= slot)
+            .map(|s| s.platform.clone())
+            .collect()
+    } else {
+        platforms
+    };
+    if staggered && platforms.is_empty() {
+        warn!("Post {} has no platform due at its scheduled time", post.id);
+        return Ok(());
+    }
+
+    // Platforms the post will go to, for blackouts and caps
+    let targets = if platforms.is_empty() {
+        &config.defaults.platforms
+    } else {
+        &platforms
+    };
+
+    // Defer posts that would go out during a blackout window
+    if !blackouts.is_empty() {
+        let now = chrono::Local::now();
+        let blocked = blackout::blocked_platforms(&blackouts, targets, &now);
+        if !blocked.is_empty() {
+            match blackout::next_allowed_time(&blackouts, targets, now.timestamp()) {
+                Some(next) => {
+                    let reason = format!("blackout on {}", blocked.join(", "));
+                    defer_post(db, &post.id, next, &reason).await?;
+                    return Ok(());
+                }
+                None => {
+                    warn!(
+                        "Blackout windows cover the whole week for {}; sending post {} anyway",
+                        blocked.join(", "),
+                        post.id
+                    );
+                }
+            }
+        }
+    }
+
+    // Defer posts that would exceed a daily or weekly cap
+    let now = chrono::Utc::now().timestamp();
+    if let Some((platform, hit)) = check_caps(rate_limiter, db, targets, now).await {
+        let reason = format!(
+            "{} cap of {} reached on {}",
+            hit.period, hit.limit, platform
+        );
+        defer_post(db, &post.id, hit.resets_at, &reason).await?;
+        return Ok(());
+    }
+
+    // Check rate limits for all platforms
+    let allowed_platforms = check_rate_limits(rate_limiter, db, &platforms, now).await?;
+
+    if allowed_platforms.is_empty() {
+        warn!(
+            "Post {} blocked by rate limits on all platforms, will retry later",
+            post.id
+        );
+        return Ok(());
+    }
+
+    if allowed_platforms.len() < platforms.len() {
+        let blocked: Vec<_> = platforms
+            .iter()
+            .filter(|p| !allowed_platforms.contains(p))
+            .collect();
+        warn!(
+            "Post {} partially blocked by rate limits on: {:?}",
+            post.id, blocked
+        );
+    }
+
+    // Resolve thread parent if this is part of a scheduled thread
+    // For posts created with --auto-thread --schedule, we need to look up
+    // the parent post's platform-specific IDs and pass them as reply_to
+    let reply_to_override = if let Some(parent_uuid) = extract_thread_parent_uuid(&post) {
+        let resolved = resolve_thread_parent(db, &parent_uuid, &allowed_platforms).await;
+        if !resolved.is_empty() {
+            info!(
+                "Resolved thread parent {} for post {}: {} platform(s)",
+                parent_uuid,
+                post.id,
+                resolved.len()
+            );
+            Some(resolved)
+        } else {
+            warn!(
+                "Post {} references parent {} but no platform IDs found (parent may not be posted yet)",
+                post.id, parent_uuid
+            );
+            None
+        }
+    } else {
+        None
+    };
+
+    // Post scheduled post to platforms using the existing post object
+    // The Post object already contains all metadata (including nostr_pow)
+    // This avoids creating duplicate posts with new UUIDs
+    // If this is a thread part, reply_to_override contains the parent's platform IDs
+    match posting
+        .post_scheduled(
+            post.clone(),
+            allowed_platforms.clone(),
+            None,
+            reply_to_override,
+        )
+        .await
+    {
+        Ok(response) => {
+            if response.overall_success {
+                info!(
+                    "Successfully posted {} to {} platform(s)",
+                    post.id,
+                    response.results.iter().filter(|r| r.success).count()
+                );
+
+                // Record rate limit usage for successful platforms
+                for result in &response.results {
+                    if result.success {
+                        if let Err(e) = rate_limiter.record(db, &result.platform, now).await {
+                            warn!("Failed to record rate limit for {}: {}", result.platform, e);
+                        }
+                    }
+                }
+            } else {
+                warn!("Failed to post {} to all platforms", post.id);
+            }
+
+            // Release follow-ups anchored to this post
+            if let Err(e) = resolve_followers(db, &post.id).await {
+                warn!("Failed to reschedule follow-ups of {}: {}", post.id, e);
+            }
+        }
+        Err(e) => {
+            error!("Error posting {}: {}", post.id, e);
+        }
+    }
+
+    // Move staggered posts on to their next platform
+    if staggered {
+        match db
+            .mark_platforms_sent(&post.id, &allowed_platforms, now)
+            .await
+        {
+            Ok(Some(next)) => {
+                info!(
+                    "Post {} rescheduled to {} for its remaining platform(s)",
+                    post.id, next
+                );
+            }
+            Ok(None) => {}
+            Err(e) => {
+                warn!("Failed to update platform schedules of {}: {}", post.id, e);
+            }
+        }
+    }
+
+    // Keep recurring series going whether or not this occurrence succeeded
+    if let Some(series_id) = recurrence::series_id(&post) {
+        let after = post.scheduled_at.unwrap_or(now);
+        match recurrence::schedule_next(db, &series_id, after).await {
+            Ok(Some(next)) => {
+                info!(
+                    "Scheduled next occurrence of series {}: {}",
+                    series_id, next.id
+                );
+            }
+            Ok(None) => {}
+            Err(e) => {
+                warn!(
+                    "Failed to schedule next occurrence of series {}: {}",
+                    series_id, e
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Move follow-ups of a post to its send time plus their delays
+async fn resolve_followers(db: &Database, post_id: &str) -> Result<()> {
+    if let AnchorState::Sent(sent_at) = anchor::anchor_state(db, post_id).await? {
+        for follow_up in anchor::resolve_followers(db, post_id, sent_at).await? {
+            info!(
+                "Rescheduled follow-up {} of {} to {}",
+                follow_up.id,
+                post_id,
+                follow_up.scheduled_at.unwrap_or(sent_at)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Extract platforms from post metadata, or return empty list
+fn extract_platforms(post: &Post) -> Vec<String> {
+    // For now, return empty list - platforms should be determined by plur-queue
+    // when scheduling the post. In the future, we could store platform info in metadata.
+    post.metadata
+        .as_ref()
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .and_then(|v| v.get("platforms").cloned())
+        .and_then(|p| serde_json::from_value(p).ok())
+        .unwrap_or_default()
+}
+
+/// Extract thread parent UUID from post metadata
+///
+/// For scheduled threads created with --auto-thread, the previous post's UUID
+/// is stored in metadata.thread_parent_uuid. This is used to resolve the parent's
+/// platform-specific post IDs at send time for proper threading.
+fn extract_thread_parent_uuid(post: &Post) -> Option<String> {
+    post.metadata
+        .as_ref()
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .and_then(|v| {
+            v.get("thread_parent_uuid")
+                .and_then(|p| p.as_str())
+                .map(String::from)
+        })
+}
+
+/// Resolve thread parent UUID to platform-specific post IDs
+///
+/// When a scheduled thread part references a parent by UUID, we need to look up
+/// the parent's platform-specific post IDs (e.g., Nostr note1..., Mastodon status ID)
+/// from the post_records table so the new post can reply to the correct posts.
+async fn resolve_thread_parent(
+    db: &Database,
+    parent_uuid: &str,
+    platforms: &[String],
+) -> HashMap<String, String> {
+    // Use the database method to get platform-specific post IDs for the parent
+    match db.get_platform_post_ids(parent_uuid).await {
+        Ok(all_platform_ids) => {
+            // Filter to only include platforms we're posting to
+            all_platform_ids
+                .into_iter()
+                .filter(|(platform, _)| platforms.contains(platform))
+                .collect()
+        }
+        Err(e) => {
+            warn!(
+                "Failed to resolve thread parent {}: {}. Thread part will post without reply_to.",
+                parent_uuid, e
+            );
+            HashMap::new()
+        }
+    }
+}
+
+/// Check rate limits for platforms and return allowed platforms
+async fn check_rate_limits(
+    rate_limiter: &RateLimiter,
+    db: &Database,
+    platforms: &[String],
+    now: i64,
+) -> Result<Vec<String>> {
+    let mut allowed = Vec::new();
+
+    for platform in platforms {
+        match rate_limiter.check(db, platform, now).await {
+            Ok(true) => allowed.push(platform.clone()),
+            Ok(false) => {
+                warn!("Rate limit exceeded for platform: {}", platform);
+            }
+            Err(e) => {
+                warn!("Error checking rate limit for {}: {}", platform, e);
+                // On error, allow the post (fail open)
+                allowed.push(platform.clone());
+            }
+        }
+    }
+
+    Ok(allowed)
+}
+
+/// Find a daily/weekly cap reached by any of the platforms
+///
+/// Returns the platform and the cap that resets last, so a deferred post
+/// isn't deferred again right away.
+async fn check_caps(
+    rate_limiter: &RateLimiter,
+    db: &Database,
+    platforms: &[String],
+    now: i64,
+) -> Option<(String, CapHit)> {
+    let mut latest: Option<(String, CapHit)> = None;
+
+    for platform in platforms {
+        match rate_limiter.check_caps(db, platform, now).await {
+            Ok(Some(hit)) => {
+                let later = match &latest {
+                    Some((_, current)) => hit.resets_at > current.resets_at,
+                    None => true,
+                };
+                if later {
+                    latest = Some((platform.clone(), hit));
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                // On error, allow the post (fail open)
+                warn!("Error checking post caps for {}: {}", platform, e);
+            }
+        }
+    }
+
+    latest
+}
+
+/// Push a due post back and log why
+async fn defer_post(db: &Database, post_id: &str, until: i64, reason: &str) -> Result<()> {
+    db.defer_post(post_id, until, reason).await?;
+
+    let until_str = chrono::DateTime::from_timestamp(until, 0)
+        .map(|dt| {
+            dt.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| until.to_string());
+    info!("Deferred post {} to {} ({})", post_id, until_str, reason);
+
+    Ok(())
+}
+
+/// Process failed posts that are eligible for retry
+async fn process_retry_posts(
+    db: &Database,
+    posting: &PostingService,
+    rate_limiter: &RateLimiter,
+    config: &Config,
+) -> Result<()> {
+    // Retries are frozen along with due posts while the queue is paused
+    if db.get_queue_pause().await?.is_some() {
+        return Ok(());
+    }
+
+    // Get retry configuration
+    let max_retries = config
+        .scheduling
+        .as_ref()
+        .map(|s| s.max_retries)
+        .unwrap_or(3);
+    let retry_delay = config
+        .scheduling
+        .as_ref()
+        .map(|s| s.retry_delay)
+        .unwrap_or(300);
+
+    // Inter-retry delay (seconds to wait between processing different posts)
+    // This prevents burst retries and respects relay rate limits
+    let inter_retry_delay = config
+        .scheduling
+        .as_ref()
+        .and_then(|s| s.inter_retry_delay)
+        .unwrap_or(5); // Default: 5 seconds between retries
+
+    // Max retries per iteration (prevents processing too many at once)
+    let max_retries_per_iteration = config
+        .scheduling
+        .as_ref()
+        .and_then(|s| s.max_retries_per_iteration)
+        .unwrap_or(10); // Default: max 10 retries per poll
+
+    // Get failed posts
+    let failed_posts = db.get_failed_posts().await?;
+
+    if failed_posts.is_empty() {
+        return Ok(());
+    }
+
+    info!("Found {} failed post(s) to retry", failed_posts.len());
+
+    let blackouts = blackout::parse_all(
+        config
+            .scheduling
+            .as_ref()
+            .map(|s| s.blackouts.as_slice())
+            .unwrap_or_default(),
+    )?;
+
+    let now = chrono::Utc::now().timestamp();
+    let mut retries_processed = 0;
+
+    for post in failed_posts {
+        // Stop if we've hit the max retries per iteration
+        if retries_processed >= max_retries_per_iteration {
+            info!(
+                "Reached max retries per iteration ({}), will process remaining posts in next poll",
+                max_retries_per_iteration
+            );
+            break;
+        }
+        // Get post records to check retry attempts
+        let records = db.get_post_records(&post.id).await?;
+
+        // Group records by platform and count failures
+        let mut platforms_to_retry = get_retry_platforms(&records, max_retries, retry_delay, now);
+
+        // Blacked-out platforms are retried once their window ends
+        if !blackouts.is_empty() {
+            let blocked =
+                blackout::blocked_platforms(&blackouts, &platforms_to_retry, &chrono::Local::now());
+            if !blocked.is_empty() {
+                info!(
+                    "Holding retry of post {} on {} until the blackout ends",
+                    post.id,
+                    blocked.join(", ")
+                );
+                platforms_to_retry.retain(|p| !blocked.contains(p));
+            }
+        }
+
+        if platforms_to_retry.is_empty() {
+            continue;
+        }
+
+        // Retries wait out a deferral like due posts do
+        if let Some(deferral) = db.get_deferral(&post.id).await? {
+            if deferral.deferred_until > now {
+                continue;
+            }
+        }
+
+        // Defer retries that would exceed a daily or weekly cap
+        if let Some((platform, hit)) = check_caps(rate_limiter, db, &platforms_to_retry, now).await
+        {
+            let reason = format!(
+                "{} cap of {} reached on {}",
+                hit.period, hit.limit, platform
+            );
+            defer_post(db, &post.id, hit.resets_at, &reason).await?;
+            continue;
+        }
+
+        info!(
+            "Retrying post {} on {} platform(s)",
+            post.id,
+            platforms_to_retry.len()
+        );
+
+        // Check rate limits
+        let allowed_platforms =
+            check_rate_limits(rate_limiter, db, &platforms_to_retry, now).await?;
+
+        if allowed_platforms.is_empty() {
+            warn!("Retry for post {} blocked by rate limits", post.id);
+            continue;
+        }
+
+        // Retry posting
+        match posting
+            .retry_post(&post.id, allowed_platforms.clone(), None)
+            .await
+        {
+            Ok(response) => {
+                if response.overall_success {
+                    info!(
+                        "Successfully retried post {} on {} platform(s)",
+                        post.id,
+                        response.results.iter().filter(|r| r.success).count()
+                    );
+
+                    // Record rate limit usage
+                    for result in &response.results {
+                        if result.success {
+                            if let Err(e) = rate_limiter.record(db, &result.platform, now).await {
+                                warn!("Failed to record rate limit for {}: {}", result.platform, e);
+                            }
+                        }
+                    }
+                } else {
+                    warn!("Retry failed for post {}", post.id);
+                }
+
+                // Increment retry counter
+                retries_processed += 1;
+
+                // Add delay between retries to prevent bursting (skip on last retry)
+                if retries_processed < max_retries_per_iteration && inter_retry_delay > 0 {
+                    info!(
+                        "Waiting {}s before next retry (inter-retry delay)",
+                        inter_retry_delay
+                    );
+                    sleep(Duration::from_secs(inter_retry_delay)).await;
+                }
+            }
+            Err(e) => {
+                error!("Error retrying post {}: {}", post.id, e);
+                retries_processed += 1; // Still count failed attempts
+
+                // Add delay even on error
+                if retries_processed < max_retries_per_iteration && inter_retry_delay > 0 {
+                    sleep(Duration::from_secs(inter_retry_delay)).await;
+                }
+            }
+        }
+    }
+
+    if retries_processed > 0 {
+        info!(
+            "Processed {} retry attempt(s) this iteration",
+            retries_processed
+        );
+    }
+
+    Ok(())
+}
+
+/// Get platforms that should be retried based on retry count and delay
+fn get_retry_platforms(
+    records: &[libplurcast::PostRecord],
+    max_retries: u32,
+    retry_delay: u64,
+    now: i64,
+) -> Vec<String> {
+    use std::collections::HashMap;
+
+    // Group records by platform
+    let mut platform_attempts: HashMap<String, Vec<&libplurcast::PostRecord>> = HashMap::new();
+
+    for record in records {
+        platform_attempts
+            .entry(record.platform.clone())
+            .or_default()
+            .push(record);
+    }
+
+    let mut retry_platforms = Vec::new();
+
+    for (platform, attempts) in platform_attempts {
+        // Count failures
+        let failure_count = attempts.iter().filter(|r| !r.success).count() as u32;
+
+        // Check if we've exceeded max retries
+        if failure_count >= max_retries {
+            continue;
+        }
+
+        // Check if enough time has passed since last attempt
+        if let Some(last_attempt) = attempts.iter().filter_map(|r| r.posted_at).max() {
+            let time_since_last = now - last_attempt;
+            if time_since_last < retry_delay as i64 {
+                continue;
+            }
+        }
+
+        retry_platforms.push(platform);
+    }
+
+    retry_platforms
+}
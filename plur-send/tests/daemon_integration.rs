@@ -204,6 +204,351 @@ async fn test_processes_multiple_due_posts() {
         .stderr(predicate::str::contains("Found 3 post(s) due for posting"));
 }
 
+#[tokio::test]
+async fn test_workers_process_every_due_post() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+
+    let mut post_ids = Vec::new();
+    for _ in 0..4 {
+        post_ids.push(create_due_post(&db_path).await);
+    }
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--once")
+        .arg("--workers")
+        .arg("3")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Send workers: 3"))
+        .stderr(predicate::str::contains("Found 4 post(s) due for posting"));
+
+    // Each post was attempted exactly once and left the queue
+    let db = Database::new(&db_path).await.unwrap();
+    for post_id in &post_ids {
+        let post = db.get_post(post_id).await.unwrap().unwrap();
+        assert_ne!(post.status, PostStatus::Scheduled);
+    }
+}
+
+#[tokio::test]
+async fn test_rejects_invalid_worker_count() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env().await;
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--once")
+        .arg("--workers")
+        .arg("0")
+        .assert()
+        .failure();
+}
+
+/// Create a scheduled post with a one-hour jitter whose offset has the given sign
+///
+/// Scheduled half the offset away from now, so the jitter alone decides
+/// whether it is due.
+async fn create_jittered_post(db_path: &str, early: bool) -> String {
+    use libplurcast::scheduling::{jitter_offset, with_jitter};
+
+    let db = Database::new(db_path).await.unwrap();
+    let now = chrono::Utc::now().timestamp();
+
+    let (id, offset) = std::iter::repeat_with(|| uuid::Uuid::new_v4().to_string())
+        .map(|id| {
+            let offset = jitter_offset(&id, 3600);
+            (id, offset)
+        })
+        .find(|(_, offset)| if early { *offset < -600 } else { *offset > 600 })
+        .unwrap();
+
+    let post = Post {
+        id,
+        content: "Jittered post".to_string(),
+        created_at: now,
+        scheduled_at: Some(now - offset / 2),
+        status: PostStatus::Scheduled,
+        metadata: Some(with_jitter(Some(r#"{"platforms":["nostr"]}"#), 3600)),
+    };
+    db.create_post(&post).await.unwrap();
+    post.id
+}
+
+#[tokio::test]
+async fn test_applies_post_jitter() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+
+    // Scheduled in the future but jittered earlier, and the other way round
+    create_jittered_post(&db_path, true).await;
+    create_jittered_post(&db_path, false).await;
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--once")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Found 1 post(s) due for posting"));
+}
+
+#[tokio::test]
+async fn test_schedules_next_occurrence_of_series() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_due_post(&db_path).await;
+
+    let db = Database::new(&db_path).await.unwrap();
+    let series = libplurcast::recurrence::start_series(&db, &post_id, "0 9 * * *")
+        .await
+        .unwrap();
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--once")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!(
+            "Scheduled next occurrence of series {}",
+            series.id
+        )));
+
+    let next = libplurcast::recurrence::pending_occurrence(&db, &series.id)
+        .await
+        .unwrap()
+        .expect("next occurrence should be scheduled");
+    assert_ne!(next.id, post_id);
+    assert_eq!(next.content, "Test scheduled post");
+    assert!(next.scheduled_at.unwrap() > chrono::Utc::now().timestamp());
+}
+
+#[tokio::test]
+async fn test_defers_posts_during_blackout() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_due_post(&db_path).await;
+
+    // Black out the whole of today (local time)
+    let today = chrono::Local::now().format("%a").to_string().to_lowercase();
+    let config = fs::read_to_string(&config_path).unwrap().replace(
+        "[scheduling.rate_limits.nostr]",
+        &format!(
+            "[[scheduling.blackouts]]\nhours = \"00:00-24:00\"\ndays = [\"{}\"]\n\n[scheduling.rate_limits.nostr]",
+            today
+        ),
+    );
+    fs::write(&config_path, config).unwrap();
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--once")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!(
+            "Deferred post {}",
+            post_id
+        )));
+
+    let db = Database::new(&db_path).await.unwrap();
+    let post = db.get_post(&post_id).await.unwrap().unwrap();
+    assert_eq!(post.status, PostStatus::Scheduled);
+    assert!(post.scheduled_at.unwrap() > chrono::Utc::now().timestamp());
+}
+
+#[tokio::test]
+async fn test_defers_posts_over_daily_cap() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_due_post(&db_path).await;
+
+    // Cap already reached today
+    let config = fs::read_to_string(&config_path).unwrap().replace(
+        "[scheduling.rate_limits.nostr]",
+        "[scheduling.caps.nostr]\nposts_per_day = 1\n\n[scheduling.rate_limits.nostr]",
+    );
+    fs::write(&config_path, config).unwrap();
+
+    let db = Database::new(&db_path).await.unwrap();
+    let limiter = libplurcast::RateLimiter::new(std::collections::HashMap::new());
+    limiter
+        .record(&db, "nostr", chrono::Utc::now().timestamp())
+        .await
+        .unwrap();
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--once")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!(
+            "Deferred post {}",
+            post_id
+        )))
+        .stderr(predicate::str::contains("daily cap of 1 reached on nostr"));
+
+    let deferral = db.get_deferral(&post_id).await.unwrap().unwrap();
+    assert!(deferral.deferred_until > chrono::Utc::now().timestamp());
+    assert_eq!(
+        db.get_post(&post_id).await.unwrap().unwrap().scheduled_at,
+        Some(deferral.deferred_until)
+    );
+}
+
+#[tokio::test]
+async fn test_skips_posts_while_paused() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_due_post(&db_path).await;
+
+    let db = Database::new(&db_path).await.unwrap();
+    db.pause_queue(Some("incident")).await.unwrap();
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--once")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Queue paused (incident)"))
+        .stderr(predicate::str::contains("Found 1 post(s)").not());
+
+    let post = db.get_post(&post_id).await.unwrap().unwrap();
+    assert_eq!(post.status, PostStatus::Scheduled);
+}
+
+#[tokio::test]
+async fn test_staggered_post_sends_due_platforms_only() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let db = Database::new(&db_path).await.unwrap();
+    let now = chrono::Utc::now().timestamp();
+
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content: "Staggered post".to_string(),
+        created_at: now,
+        scheduled_at: Some(now - 10),
+        status: PostStatus::Scheduled,
+        metadata: Some(r#"{"platforms":["nostr","mastodon"]}"#.to_string()),
+    };
+    db.create_post(&post).await.unwrap();
+    db.set_platform_schedules(
+        &post.id,
+        &[
+            ("nostr".to_string(), now - 10),
+            ("mastodon".to_string(), now + 3600),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--once")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("for its remaining platform(s)"));
+
+    // Nostr has had its turn; the post waits for Mastodon's time
+    let stored = db.get_post(&post.id).await.unwrap().unwrap();
+    assert_eq!(stored.status, PostStatus::Scheduled);
+    assert_eq!(stored.scheduled_at, Some(now + 3600));
+
+    let schedules = db.get_platform_schedules(&post.id).await.unwrap();
+    assert!(schedules[0].sent_at.is_some());
+    assert!(schedules[1].sent_at.is_none());
+    assert!(db
+        .get_post_records(&post.id)
+        .await
+        .unwrap()
+        .iter()
+        .all(|r| r.platform == "nostr"));
+}
+
+/// Create a scheduled post that fell due `late` seconds ago
+async fn create_late_post(db_path: &str, late: i64) -> String {
+    let db = Database::new(db_path).await.unwrap();
+    let now = chrono::Utc::now().timestamp();
+
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content: "Late scheduled post".to_string(),
+        created_at: now - late,
+        scheduled_at: Some(now - late),
+        status: PostStatus::Scheduled,
+        metadata: Some(r#"{"platforms":["nostr"]}"#.to_string()),
+    };
+
+    let post_id = post.id.clone();
+    db.create_post(&post).await.unwrap();
+    post_id
+}
+
+fn set_catch_up(config_path: &str, policy: &str) {
+    let config = fs::read_to_string(config_path).unwrap().replace(
+        "retry_delay = 1\n",
+        &format!("retry_delay = 1\ncatch_up = \"{}\"\n", policy),
+    );
+    fs::write(config_path, config).unwrap();
+}
+
+#[tokio::test]
+async fn test_catch_up_within_marks_late_posts_missed() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let stale = create_late_post(&db_path, 2 * 3600).await;
+    let recent = create_late_post(&db_path, 600).await;
+    set_catch_up(&config_path, "1h");
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--once")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!("Post {} is", stale)))
+        .stderr(predicate::str::contains("Found 1 post(s)"));
+
+    let db = Database::new(&db_path).await.unwrap();
+    let post = db.get_post(&stale).await.unwrap().unwrap();
+    assert_eq!(post.status, PostStatus::Missed);
+    let post = db.get_post(&recent).await.unwrap().unwrap();
+    assert_ne!(post.status, PostStatus::Missed);
+}
+
+#[tokio::test]
+async fn test_catch_up_manual_holds_missed_posts() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_late_post(&db_path, 3600).await;
+    set_catch_up(&config_path, "manual");
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--once")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("marked missed"))
+        .stderr(predicate::str::contains("Found 1 post(s)").not());
+
+    let db = Database::new(&db_path).await.unwrap();
+    let missed = db.get_missed_posts().await.unwrap();
+    assert_eq!(missed.len(), 1);
+    assert_eq!(missed[0].id, post_id);
+}
+
+#[tokio::test]
+async fn test_rejects_invalid_catch_up() {
+    let (_temp_dir, config_path, _db_path) = setup_test_env().await;
+    set_catch_up(&config_path, "eventually");
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--once")
+        .assert()
+        .failure();
+}
+
 // CONFIGURATION TESTS
 
 #[tokio::test]
@@ -386,3 +731,113 @@ async fn test_logs_post_processing() {
             post_id
         )));
 }
+
+// BACKGROUND MODE TESTS
+
+#[tokio::test]
+async fn test_status_when_not_running() {
+    let (temp_dir, config_path, _db_path) = setup_test_env().await;
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("status")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("plur-send is not running"));
+
+    assert!(!temp_dir.path().join("plur-send.pid").exists());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_daemon_start_status_stop() {
+    let (temp_dir, config_path, _db_path) = setup_test_env().await;
+    let pid_file = temp_dir.path().join("plur-send.pid");
+    let log_file = temp_dir.path().join("logs").join("plur-send.log");
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--daemon")
+        .arg("--log-file")
+        .arg(&log_file)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("plur-send started (pid"));
+
+    // The PID file goes next to the database by default
+    assert!(pid_file.exists());
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("plur-send is running"));
+
+    // A second instance on the same queue refuses to start
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--once")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already running"));
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("stop")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("plur-send stopped"));
+
+    assert!(!pid_file.exists());
+    let log = fs::read_to_string(&log_file).unwrap();
+    assert!(log.contains("plur-send daemon starting"));
+    assert!(log.contains("plur-send daemon stopped"));
+}
+
+// SINGLE INSTANCE TESTS
+
+#[tokio::test]
+async fn test_refuses_to_start_while_another_instance_holds_the_queue() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_due_post(&db_path).await;
+
+    let db = Database::new(&db_path).await.unwrap();
+    let now = chrono::Utc::now().timestamp();
+    assert!(db.acquire_send_lease("other", now, 300).await.unwrap());
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--once")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "another plur-send (other) is processing this queue",
+        ));
+
+    // Nothing was sent and the other instance keeps the lease
+    let post = db.get_post(&post_id).await.unwrap().unwrap();
+    assert_eq!(post.status, PostStatus::Scheduled);
+    assert_eq!(db.get_send_lease().await.unwrap().unwrap().holder, "other");
+}
+
+#[tokio::test]
+async fn test_takes_over_an_expired_lease_and_releases_it() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+
+    let db = Database::new(&db_path).await.unwrap();
+    let long_ago = chrono::Utc::now().timestamp() - 3600;
+    assert!(db
+        .acquire_send_lease("crashed", long_ago, 300)
+        .await
+        .unwrap());
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--once")
+        .assert()
+        .success();
+
+    assert!(db.get_send_lease().await.unwrap().is_none());
+}
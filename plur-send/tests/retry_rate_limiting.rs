@@ -431,6 +431,80 @@ async fn test_max_retries_allows_all_if_under_limit() {
         .stderr(predicate::str::contains("Reached max retries per iteration").not());
 }
 
+// CAP AND BLACKOUT TESTS
+
+#[tokio::test]
+async fn test_defers_retries_over_daily_cap() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_failed_post(&db_path, "Failed post").await;
+
+    // Cap already reached today
+    let config = fs::read_to_string(&config_path).unwrap().replace(
+        "[scheduling.rate_limits.nostr]",
+        "[scheduling.caps.nostr]\nposts_per_day = 1\n\n[scheduling.rate_limits.nostr]",
+    );
+    fs::write(&config_path, config).unwrap();
+
+    let db = Database::new(&db_path).await.unwrap();
+    let limiter = libplurcast::RateLimiter::new(std::collections::HashMap::new());
+    limiter
+        .record(&db, "nostr", chrono::Utc::now().timestamp())
+        .await
+        .unwrap();
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--once")
+        .arg("--startup-delay")
+        .arg("0")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!(
+            "Deferred post {}",
+            post_id
+        )))
+        .stderr(predicate::str::contains("Retrying post").not());
+
+    let deferral = db.get_deferral(&post_id).await.unwrap().unwrap();
+    assert!(deferral.deferred_until > chrono::Utc::now().timestamp());
+}
+
+#[tokio::test]
+async fn test_holds_retries_during_blackout() {
+    let (_temp_dir, config_path, db_path) = setup_test_env().await;
+    let post_id = create_failed_post(&db_path, "Failed post").await;
+
+    // Black out the whole of today (local time)
+    let today = chrono::Local::now().format("%a").to_string().to_lowercase();
+    let config = fs::read_to_string(&config_path).unwrap().replace(
+        "[scheduling.rate_limits.nostr]",
+        &format!(
+            "[[scheduling.blackouts]]\nhours = \"00:00-24:00\"\ndays = [\"{}\"]\n\n[scheduling.rate_limits.nostr]",
+            today
+        ),
+    );
+    fs::write(&config_path, config).unwrap();
+
+    let mut cmd = Command::cargo_bin("plur-send").unwrap();
+
+    cmd.env("PLURCAST_CONFIG", &config_path)
+        .arg("--once")
+        .arg("--startup-delay")
+        .arg("0")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!(
+            "Holding retry of post {} on nostr",
+            post_id
+        )))
+        .stderr(predicate::str::contains("Retrying post").not());
+
+    let db = Database::new(&db_path).await.unwrap();
+    let post = db.get_post(&post_id).await.unwrap().unwrap();
+    assert_eq!(post.status, PostStatus::Failed);
+}
+
 // COMBINED FEATURES TESTS
 
 #[tokio::test]
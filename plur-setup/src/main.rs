@@ -143,6 +143,7 @@ fn configure_nostr_pow(config: &mut Config, pow_difficulty: u8) -> Result<()> {
                 "wss://relay.nostr.bg".to_string(),
             ],
             default_pow_difficulty: None,
+            media_server: None,
         });
     }
 
@@ -479,6 +480,7 @@ async fn test_nostr_auth(private_key: &str) -> Result<()> {
             "wss://nos.lol".to_string(),
         ],
         default_pow_difficulty: None,
+        media_server: None,
     };
 
     let mut platform = NostrPlatform::new(&config);
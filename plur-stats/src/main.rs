@@ -0,0 +1,417 @@
+//! plur-stats - Engagement analytics for your posts
+//!
+//! Unix-style tool: reads the replies, boosts, likes and zaps your posts
+//! got (refreshing them from the platforms with --refresh) and prints them
+//! per post and in total, as text, JSON or CSV.
+
+use clap::Parser;
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use libplurcast::platforms::Engagement;
+use libplurcast::service::engagement::{EngagementReport, PostEngagement};
+use libplurcast::service::history::{self, HistoryQuery};
+use libplurcast::service::PlurcastService;
+use libplurcast::{Config, Result};
+use tracing::info;
+
+#[derive(Parser, Debug)]
+#[command(name = "plur-stats")]
+#[command(version)]
+#[command(about = "Engagement analytics for your posts")]
+#[command(long_about = "\
+plur-stats - Engagement analytics for your posts
+
+DESCRIPTION:
+    plur-stats prints the replies, boosts, likes and zaps of your posted
+    posts: in total, per platform, and for the top posts.
+
+    Counts are kept in the database. --refresh reads them from the platforms
+    first, as the account each post was sent from:
+
+        Mastodon  replies, boosts and favourites, as the instance counts them
+        Nostr     replies, reposts, reactions and zaps the relays return
+        SSB       not counted
+
+    --since, --until, --platform, --label and --account pick the posts, as
+    in plur-history; both refreshing and the report use them.
+
+USAGE EXAMPLES:
+    # Refresh the counts of last month's posts and show the top ten
+    plur-stats --refresh --since 2025-10-01
+
+    # Most replied-to Mastodon posts
+    plur-stats --platform mastodon --sort replies --limit 5
+
+    # Every post, for a spreadsheet
+    plur-stats --format csv > engagement.csv
+
+    # Total likes as a number
+    plur-stats --format json | jq .total.likes
+
+EXIT CODES:
+    0 - Success
+    1 - Some posts' engagement couldn't be refreshed
+    2 - Authentication error
+    3 - Invalid input (bad date, etc.)
+
+For more information, visit: https://github.com/plurcast/plurcast
+")]
+struct Cli {
+    /// Read the counts from the platforms before reporting
+    #[arg(short, long)]
+    refresh: bool,
+
+    /// Only count this platform
+    #[arg(short, long, value_name = "PLATFORM")]
+    #[arg(value_parser = ["nostr", "mastodon", "ssb"])]
+    platform: Option<String>,
+
+    /// Only posts created since this date (Unix timestamp, YYYY-MM-DD or
+    /// ISO 8601)
+    #[arg(long, value_name = "DATE")]
+    since: Option<String>,
+
+    /// Only posts created until this date (Unix timestamp, YYYY-MM-DD or
+    /// ISO 8601)
+    #[arg(long, value_name = "DATE")]
+    until: Option<String>,
+
+    /// Only posts carrying this label
+    #[arg(long, value_name = "LABEL")]
+    label: Option<String>,
+
+    /// Only posts sent from this account
+    #[arg(long, value_name = "ACCOUNT")]
+    account: Option<String>,
+
+    /// Order of the posts listed
+    #[arg(short, long, default_value = "total")]
+    #[arg(value_parser = ["total", "replies", "boosts", "likes", "zaps", "date"])]
+    sort: String,
+
+    /// Posts to list (default: 10 in text output, all otherwise); totals
+    /// always cover every post
+    #[arg(short = 'n', long, value_name = "N")]
+    limit: Option<usize>,
+
+    /// Output format: text, json or csv
+    #[arg(short, long, default_value = "text")]
+    #[arg(value_parser = ["text", "json", "csv"])]
+    format: String,
+
+    /// Verbose output
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT"
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL"
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+/// Posts listed in text output when no limit is given
+const DEFAULT_TEXT_LIMIT: usize = 10;
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    match run(cli).await {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
+
+/// Print the report; returns whether every refresh succeeded
+async fn run(cli: Cli) -> Result<bool> {
+    let query = HistoryQuery {
+        platform: cli.platform.clone(),
+        since: match cli.since {
+            Some(ref date) => Some(history::parse_date(date)?),
+            None => None,
+        },
+        until: match cli.until {
+            Some(ref date) => Some(history::parse_date(date)?),
+            None => None,
+        },
+        label: cli.label.clone(),
+        account: cli.account.clone(),
+        ..Default::default()
+    };
+
+    let config = Config::load()?;
+    let service = PlurcastService::from_config(config).await?;
+
+    let mut ok = true;
+    if cli.refresh {
+        let refreshed = service.engagement().refresh(&query).await?;
+        info!("Read the engagement of {} post(s)", refreshed.updated);
+        for failure in &refreshed.failures {
+            eprintln!(
+                "Warning: could not read {} {}: {}",
+                failure.platform, failure.platform_post_id, failure.error
+            );
+        }
+        ok = refreshed.failures.is_empty();
+    }
+
+    let mut report = service.engagement().report(&query).await?;
+    if report.posts.iter().all(|post| post.fetched_at.is_none()) && !report.posts.is_empty() {
+        eprintln!("No engagement read yet for these posts; run plur-stats --refresh");
+    }
+
+    sort_posts(&mut report.posts, &cli.sort);
+    let limit = match (cli.limit, cli.format.as_str()) {
+        (Some(limit), _) => Some(limit),
+        (None, "text") => Some(DEFAULT_TEXT_LIMIT),
+        (None, _) => None,
+    };
+    let listed = match limit {
+        Some(limit) => &report.posts[..limit.min(report.posts.len())],
+        None => &report.posts[..],
+    };
+
+    match cli.format.as_str() {
+        "json" => println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "posts": listed,
+                "total": report.total,
+                "platforms": report.platforms,
+            }))
+            .unwrap_or_default()
+        ),
+        "csv" => {
+            println!("{}", CSV_HEADER);
+            for post in listed {
+                println!("{}", csv_row(post));
+            }
+        }
+        _ => print!("{}", format_text(&report, listed, &cli.sort)),
+    }
+
+    Ok(ok)
+}
+
+/// Order posts by the chosen count, highest first; newest first on ties
+/// and for `date`
+fn sort_posts(posts: &mut [PostEngagement], sort: &str) {
+    let key = |post: &PostEngagement| -> u64 {
+        let engagement = &post.total;
+        match sort {
+            "replies" => engagement.replies,
+            "boosts" => engagement.boosts,
+            "likes" => engagement.likes,
+            "zaps" => engagement.zaps,
+            "date" => 0,
+            _ => engagement.total(),
+        }
+    };
+    posts.sort_by(|a, b| {
+        key(b)
+            .cmp(&key(a))
+            .then_with(|| b.created_at.cmp(&a.created_at))
+    });
+}
+
+const CSV_HEADER: &str = "post_id,created_at,replies,boosts,likes,zaps,zap_sats,fetched_at,content";
+
+fn csv_row(post: &PostEngagement) -> String {
+    let e = &post.total;
+    [
+        post.post_id.clone(),
+        post.created_at.to_string(),
+        e.replies.to_string(),
+        e.boosts.to_string(),
+        e.likes.to_string(),
+        e.zaps.to_string(),
+        e.zap_sats.to_string(),
+        post.fetched_at.map(|t| t.to_string()).unwrap_or_default(),
+        post.content.clone(),
+    ]
+    .iter()
+    .map(|value| csv_field(value))
+    .collect::<Vec<_>>()
+    .join(",")
+}
+
+/// Quote a CSV field when it needs it
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Totals, per-platform counts and the listed posts as aligned columns
+fn format_text(report: &EngagementReport, listed: &[PostEngagement], sort: &str) -> String {
+    let mut out = String::new();
+    let fetched_at = report.posts.iter().filter_map(|post| post.fetched_at).max();
+    out.push_str(&format!(
+        "Engagement of {} post(s){}\n\n",
+        report.posts.len(),
+        fetched_at
+            .map(|t| format!(" (counts read up to {})", format_time(t)))
+            .unwrap_or_default()
+    ));
+
+    out.push_str(&format!(
+        "{:<16} {:>7} {:>7} {:>7} {:>7} {:>9}\n",
+        "", "REPLIES", "BOOSTS", "LIKES", "ZAPS", "SATS"
+    ));
+    out.push_str(&format!("{}\n", count_line("Total", &report.total)));
+    for (platform, engagement) in &report.platforms {
+        out.push_str(&format!("{}\n", count_line(platform, engagement)));
+    }
+
+    if !listed.is_empty() {
+        let order = if sort == "date" { "newest" } else { sort };
+        out.push_str(&format!("\nPosts by {}:\n", order));
+        for post in listed {
+            out.push_str(&format!(
+                "{}  {}  {}\n",
+                count_line(&format_time(post.created_at), &post.total),
+                short_id(&post.post_id),
+                preview(&post.content)
+            ));
+        }
+    }
+
+    out
+}
+
+fn count_line(label: &str, e: &Engagement) -> String {
+    format!(
+        "{:<16} {:>7} {:>7} {:>7} {:>7} {:>9}",
+        label, e.replies, e.boosts, e.likes, e.zaps, e.zap_sats
+    )
+}
+
+fn short_id(post_id: &str) -> &str {
+    post_id.get(..8).unwrap_or(post_id)
+}
+
+/// The first 60 characters of the content, on one line
+fn preview(content: &str) -> String {
+    let content = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    if content.chars().count() > 60 {
+        format!("{}...", content.chars().take(57).collect::<String>())
+    } else {
+        content
+    }
+}
+
+fn format_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .unwrap_or_else(chrono::Utc::now)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn post(id: &str, created_at: i64, replies: u64, likes: u64) -> PostEngagement {
+        let total = Engagement {
+            replies,
+            likes,
+            ..Default::default()
+        };
+        PostEngagement {
+            post_id: id.to_string(),
+            content: format!("Post {}", id),
+            created_at,
+            platforms: BTreeMap::from([("mastodon".to_string(), total)]),
+            total,
+            fetched_at: Some(created_at + 60),
+        }
+    }
+
+    #[test]
+    fn test_sort_posts() {
+        let mut posts = vec![
+            post("a", 100, 5, 0),
+            post("b", 200, 1, 9),
+            post("c", 300, 1, 0),
+        ];
+
+        sort_posts(&mut posts, "total");
+        let ids: Vec<&str> = posts.iter().map(|p| p.post_id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "a", "c"]);
+
+        sort_posts(&mut posts, "replies");
+        let ids: Vec<&str> = posts.iter().map(|p| p.post_id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "c", "b"]);
+
+        sort_posts(&mut posts, "date");
+        let ids: Vec<&str> = posts.iter().map(|p| p.post_id.as_str()).collect();
+        assert_eq!(ids, vec!["c", "b", "a"]);
+    }
+
+    #[test]
+    fn test_csv_row_quotes_content() {
+        let mut post = post("550e8400-e29b-41d4-a716-446655440000", 100, 2, 3);
+        post.content = "Hello, \"world\"".to_string();
+        assert_eq!(
+            csv_row(&post),
+            "550e8400-e29b-41d4-a716-446655440000,100,2,0,3,0,0,160,\"Hello, \"\"world\"\"\""
+        );
+    }
+
+    #[test]
+    fn test_format_text() {
+        let posts = vec![post("550e8400-e29b", 1_709_294_400, 2, 3)];
+        let report = EngagementReport {
+            total: posts[0].total,
+            platforms: posts[0].platforms.clone(),
+            posts: posts.clone(),
+        };
+
+        let text = format_text(&report, &posts, "total");
+        assert!(text.starts_with("Engagement of 1 post(s) (counts read up to 2024-03-01 12:01)\n"));
+        assert!(text.contains("\nTotal                  2       0       3       0         0\n"));
+        assert!(text.contains("\nmastodon               2       0       3       0         0\n"));
+        assert!(text.ends_with(
+            "Posts by total:\n\
+             2024-03-01 12:00       2       0       3       0         0  550e8400  Post 550e8400-e29b\n"
+        ));
+    }
+}
@@ -0,0 +1,136 @@
+//! Integration tests for plur-stats
+
+use assert_cmd::Command;
+use libplurcast::platforms::Engagement;
+use libplurcast::{Database, EngagementRecord, Post, PostRecord, PostStatus};
+use predicates::prelude::*;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Write a config file pointing at `db_path`, with no platforms enabled
+fn write_config(dir: &Path, db_path: &Path) -> PathBuf {
+    let config_path = dir.join("config.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "[database]\npath = \"{}\"\n\n[defaults]\nplatforms = []\n",
+            db_path.to_string_lossy().replace('\\', "/")
+        ),
+    )
+    .unwrap();
+    config_path
+}
+
+/// Create a posted Mastodon post with the given engagement
+async fn create_post(db: &Database, content: &str, created_at: i64, likes: u64) -> String {
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content: content.to_string(),
+        created_at,
+        scheduled_at: None,
+        status: PostStatus::Posted,
+        metadata: None,
+    };
+    db.create_post(&post).await.unwrap();
+    let platform_post_id = created_at.to_string();
+    db.create_post_record(&PostRecord {
+        id: None,
+        post_id: post.id.clone(),
+        platform: "mastodon".to_string(),
+        platform_post_id: Some(platform_post_id.clone()),
+        posted_at: Some(created_at),
+        success: true,
+        error_message: None,
+        account_name: "default".to_string(),
+    })
+    .await
+    .unwrap();
+    db.save_engagement(&EngagementRecord {
+        post_id: post.id.clone(),
+        platform: "mastodon".to_string(),
+        platform_post_id,
+        engagement: Engagement {
+            replies: 1,
+            likes,
+            ..Default::default()
+        },
+        fetched_at: created_at + 3600,
+    })
+    .await
+    .unwrap();
+    post.id
+}
+
+async fn setup() -> (TempDir, Database, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("posts.db");
+    let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+    let config_path = write_config(temp_dir.path(), &db_path);
+    (temp_dir, db, config_path)
+}
+
+fn plur_stats(config_path: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("plur-stats").unwrap();
+    cmd.env("PLURCAST_CONFIG", config_path)
+        .env_remove("PLURCAST_DB_PATH");
+    cmd
+}
+
+#[tokio::test]
+async fn test_json_totals_and_top_posts() {
+    let (_temp_dir, db, config_path) = setup().await;
+    create_post(&db, "Quiet post", 1_700_000_000, 1).await;
+    let popular = create_post(&db, "Popular post", 1_700_100_000, 10).await;
+
+    let output = plur_stats(&config_path)
+        .args(["--format", "json", "--limit", "1"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["total"]["replies"], 2);
+    assert_eq!(json["total"]["likes"], 11);
+    assert_eq!(json["platforms"]["mastodon"]["likes"], 11);
+    let posts = json["posts"].as_array().unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0]["post_id"], popular.as_str());
+}
+
+#[tokio::test]
+async fn test_time_window_and_csv() {
+    let (_temp_dir, db, config_path) = setup().await;
+    create_post(&db, "Old post", 1_600_000_000, 4).await;
+    let recent = create_post(&db, "Recent, with a comma", 1_700_000_000, 2).await;
+
+    plur_stats(&config_path)
+        .args(["--format", "csv", "--since", "2023-01-01"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "post_id,created_at,replies,boosts,likes,zaps,zap_sats,fetched_at,content\n",
+        ))
+        .stdout(predicate::str::contains(format!(
+            "{},1700000000,1,0,2,0,0,1700003600,\"Recent, with a comma\"",
+            recent
+        )))
+        .stdout(predicate::str::contains("Old post").not());
+
+    plur_stats(&config_path)
+        .args(["--until", "2023-01-01"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Engagement of 1 post(s)"))
+        .stdout(predicate::str::contains("Old post"));
+}
+
+#[tokio::test]
+async fn test_invalid_date() {
+    let (_temp_dir, _db, config_path) = setup().await;
+
+    plur_stats(&config_path)
+        .args(["--since", "last tuesday"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Invalid date format"));
+}
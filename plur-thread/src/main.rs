@@ -0,0 +1,396 @@
+//! plur-thread - Compose and post threads
+//!
+//! Unix-style tool: reads a thread from a file or stdin with its parts
+//! divided by separator lines, numbers the parts, and posts or schedules
+//! the whole thread, each part replying to the one before it.
+
+use clap::Parser;
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use libplurcast::service::thread::{ThreadRequest, ThreadResponse};
+use libplurcast::service::PlurcastService;
+use libplurcast::thread::{self, NumberPosition};
+use libplurcast::{scheduling, Config, PlurcastError, Result};
+use std::io::{self, IsTerminal, Read};
+use std::path::PathBuf;
+use tracing::info;
+
+#[derive(Parser, Debug)]
+#[command(name = "plur-thread")]
+#[command(version)]
+#[command(about = "Compose and post threads")]
+#[command(long_about = "\
+plur-thread - Compose and post threads
+
+DESCRIPTION:
+    plur-thread reads a thread from FILE (or stdin) and posts it, each part
+    replying to the one before it. Parts are divided by lines holding only
+    the separator (--- by default):
+
+        Why I moved my posting to plain text files.
+        ---
+        First, they outlive every app.
+        ---
+        Second, grep.
+
+    Parts are numbered \"1/3\", \"2/3\", ... after their text. Change the
+    format with --numbering (\"{n}\" is the part, \"{total}\" the number of
+    parts), put it first with --number-position prefix, or turn it off
+    with --no-numbering.
+
+    Every part is checked against each platform's limits before anything
+    is sent. A scheduled thread is queued whole, a minute between parts,
+    for plur-send. When a part fails on a platform, the rest of the thread
+    isn't sent there and the parts already posted there are deleted again,
+    unless --keep-partial is given.
+
+USAGE EXAMPLES:
+    # Preview the numbered parts and their lengths
+    plur-thread thread.md --dry-run
+
+    # Post to the default platforms
+    plur-thread thread.md
+
+    # Compose in a pipeline, post to Mastodon
+    cat notes.txt | plur-thread -p mastodon
+
+    # Schedule the thread for tomorrow morning
+    plur-thread thread.md --schedule \"tomorrow 9am\"
+
+    # Number as \"(1)\" in front, with === as separator
+    plur-thread thread.md --separator === --numbering \"({n})\" --number-position prefix
+
+EXIT CODES:
+    0 - Success
+    1 - The thread couldn't be completed on a platform
+    2 - Authentication error
+    3 - Invalid input (empty thread, part too long, etc.)
+
+For more information, visit: https://github.com/plurcast/plurcast
+")]
+struct Cli {
+    /// File with the thread (default: stdin; - reads stdin)
+    #[arg(value_name = "FILE")]
+    file: Option<PathBuf>,
+
+    /// Platforms to post to (comma-separated; default: config defaults)
+    #[arg(short, long, value_name = "PLATFORM", value_delimiter = ',')]
+    #[arg(value_parser = ["nostr", "mastodon", "ssb"])]
+    platform: Vec<String>,
+
+    /// Account to post from (default: each platform's active account)
+    #[arg(long, value_name = "ACCOUNT")]
+    account: Option<String>,
+
+    /// Schedule the thread (same formats as plur-post --schedule)
+    #[arg(short, long, value_name = "WHEN")]
+    schedule: Option<String>,
+
+    /// Line dividing the parts
+    #[arg(long, default_value = thread::DEFAULT_SEPARATOR, value_name = "LINE")]
+    separator: String,
+
+    /// Numbering format: {n} is the part, {total} the number of parts
+    #[arg(long, default_value = thread::DEFAULT_NUMBERING, value_name = "FORMAT")]
+    numbering: String,
+
+    /// Where the number goes (suffix, prefix)
+    #[arg(long, default_value = "suffix", value_name = "POSITION")]
+    #[arg(value_parser = ["suffix", "prefix"])]
+    number_position: String,
+
+    /// Post the parts without numbers
+    #[arg(long, conflicts_with_all = ["numbering", "number_position"])]
+    no_numbering: bool,
+
+    /// Content warning shown before every part
+    #[arg(long, value_name = "TEXT")]
+    cw: Option<String>,
+
+    /// Label stored with every part (can be repeated)
+    #[arg(long, value_name = "LABEL")]
+    label: Vec<String>,
+
+    /// Leave the parts already posted on a platform where a later part failed
+    #[arg(long)]
+    keep_partial: bool,
+
+    /// Show the numbered parts without posting
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Output format (text, json)
+    #[arg(short = 'f', long, default_value = "text", value_name = "FORMAT")]
+    #[arg(value_parser = ["text", "json"])]
+    format: String,
+
+    /// Verbose output
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT"
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL"
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    match run(cli).await {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
+
+/// Read, number and post or schedule the thread
+///
+/// Returns whether the thread reached every platform.
+async fn run(cli: Cli) -> Result<bool> {
+    let text = read_input(&cli)?;
+    let parts = compose(&cli, &text)?;
+
+    let config = Config::load()?;
+    let platforms = if cli.platform.is_empty() {
+        config.defaults.platforms.clone()
+    } else {
+        cli.platform.clone()
+    };
+    if platforms.is_empty() {
+        return Err(PlurcastError::InvalidInput(
+            "No platforms: use --platform, or set [defaults] platforms in the config".to_string(),
+        ));
+    }
+
+    let service = PlurcastService::from_config(config.clone()).await?;
+    let scheduled_at = match cli.schedule {
+        Some(ref schedule) => {
+            let last_scheduled = service.database().get_last_scheduled_timestamp().await?;
+            Some(scheduling::parse_schedule(schedule, last_scheduled)?.timestamp())
+        }
+        None => None,
+    };
+
+    let request = ThreadRequest {
+        parts,
+        platforms,
+        account: cli.account.clone(),
+        scheduled_at,
+        labels: cli.label.clone(),
+        content_warning: cli.cw.clone(),
+        language: config.defaults.language.clone(),
+        keep_partial: cli.keep_partial,
+    };
+
+    if cli.dry_run {
+        service.thread().validate(&request)?;
+        output_preview(&cli.format, &request);
+        return Ok(true);
+    }
+
+    info!(
+        "Posting a thread of {} parts to {}",
+        request.parts.len(),
+        request.platforms.join(", ")
+    );
+    let response = service.thread().post(request).await?;
+    output_response(&cli.format, &response, scheduled_at);
+    Ok(response.success())
+}
+
+/// The thread's text, from FILE or stdin
+fn read_input(cli: &Cli) -> Result<String> {
+    match cli.file {
+        Some(ref file) if file.as_os_str() != "-" => std::fs::read_to_string(file).map_err(|e| {
+            PlurcastError::InvalidInput(format!("Failed to read {}: {}", file.display(), e))
+        }),
+        _ => {
+            let stdin = io::stdin();
+            if stdin.is_terminal() {
+                return Err(PlurcastError::InvalidInput(
+                    "No thread given: pass a FILE or pipe the thread to stdin".to_string(),
+                ));
+            }
+            let mut text = String::new();
+            stdin.lock().read_to_string(&mut text).map_err(|e| {
+                PlurcastError::InvalidInput(format!("Failed to read from stdin: {}", e))
+            })?;
+            Ok(text)
+        }
+    }
+}
+
+/// Split the text into parts and number them
+fn compose(cli: &Cli, text: &str) -> Result<Vec<String>> {
+    let parts = thread::split(text, &cli.separator);
+    if parts.is_empty() {
+        return Err(PlurcastError::InvalidInput(
+            "The thread is empty".to_string(),
+        ));
+    }
+    if cli.no_numbering {
+        return Ok(parts);
+    }
+
+    let position = match cli.number_position.as_str() {
+        "prefix" => NumberPosition::Prefix,
+        _ => NumberPosition::Suffix,
+    };
+    thread::number(&parts, &cli.numbering, position)
+}
+
+/// Print the parts a dry run would post
+fn output_preview(format: &str, request: &ThreadRequest) {
+    let total = request.parts.len();
+    if format == "json" {
+        let parts: Vec<serde_json::Value> = request
+            .parts
+            .iter()
+            .enumerate()
+            .map(|(i, part)| {
+                serde_json::json!({
+                    "part": i + 1,
+                    "content": part,
+                    "chars": part.chars().count(),
+                    "scheduled_at": request
+                        .scheduled_at
+                        .map(|at| at + i as i64 * thread::SCHEDULE_GAP_SECS),
+                })
+            })
+            .collect();
+        let output = serde_json::json!({
+            "dry_run": true,
+            "platforms": request.platforms,
+            "parts": parts,
+        });
+        println!("{}", output);
+        return;
+    }
+
+    for (i, part) in request.parts.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("[{}/{}] {} chars", i + 1, total, part.chars().count());
+        println!("{}", part);
+    }
+}
+
+/// Print the posted or scheduled parts and any platform that failed
+fn output_response(format: &str, response: &ThreadResponse, scheduled_at: Option<i64>) {
+    let part_time = |i: usize| scheduled_at.map(|at| at + i as i64 * thread::SCHEDULE_GAP_SECS);
+
+    if format == "json" {
+        let parts: Vec<serde_json::Value> = response
+            .parts
+            .iter()
+            .enumerate()
+            .map(|(i, part)| {
+                serde_json::json!({
+                    "part": i + 1,
+                    "post_id": part.post_id,
+                    "scheduled_at": part_time(i),
+                    "results": part
+                        .results
+                        .iter()
+                        .map(|r| {
+                            serde_json::json!({
+                                "platform": r.platform,
+                                "success": r.success,
+                                "post_id": r.post_id,
+                                "error": r.error,
+                            })
+                        })
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        let failures: Vec<serde_json::Value> = response
+            .failures
+            .iter()
+            .map(|f| {
+                serde_json::json!({
+                    "platform": f.platform,
+                    "part": f.part,
+                    "error": f.error,
+                    "rolled_back": f.rolled_back,
+                })
+            })
+            .collect();
+        let output = serde_json::json!({
+            "success": response.success(),
+            "parts": parts,
+            "failures": failures,
+        });
+        println!("{}", output);
+        return;
+    }
+
+    for (i, part) in response.parts.iter().enumerate() {
+        if let Some(at) = part_time(i) {
+            println!("scheduled:{}:for:{}", part.post_id, format_time(at));
+            continue;
+        }
+        for result in part.results.iter().filter(|r| r.success) {
+            if let Some(ref post_id) = result.post_id {
+                println!("{}:{}", result.platform, post_id);
+            }
+        }
+    }
+    for failure in &response.failures {
+        let note = if failure.part == 1 {
+            ""
+        } else if failure.rolled_back {
+            " (earlier parts deleted)"
+        } else {
+            " (earlier parts left posted)"
+        };
+        eprintln!(
+            "Error [{}] part {}: {}{}",
+            failure.platform, failure.part, failure.error, note
+        );
+    }
+}
+
+/// A Unix timestamp as a UTC date and time
+fn format_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
@@ -0,0 +1,149 @@
+//! Integration tests for plur-thread
+
+use assert_cmd::Command;
+use libplurcast::Database;
+use predicates::prelude::*;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Write a config file pointing at `db_path`, with no platforms enabled
+fn write_config(dir: &Path, db_path: &Path) -> PathBuf {
+    let config_path = dir.join("config.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "[database]\npath = \"{}\"\n\n[defaults]\nplatforms = []\n",
+            db_path.to_string_lossy().replace('\\', "/")
+        ),
+    )
+    .unwrap();
+    config_path
+}
+
+async fn setup() -> (TempDir, Database, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("posts.db");
+    let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+    let config_path = write_config(temp_dir.path(), &db_path);
+    (temp_dir, db, config_path)
+}
+
+fn plur_thread(config_path: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("plur-thread").unwrap();
+    cmd.env("PLURCAST_CONFIG", config_path)
+        .env_remove("PLURCAST_DB_PATH");
+    cmd
+}
+
+const THREAD: &str = "Why plain text?\n---\nIt outlives apps.\n---\nAnd grep.\n";
+
+#[tokio::test]
+async fn test_scheduled_thread_is_queued_whole() {
+    let (_temp_dir, db, config_path) = setup().await;
+
+    plur_thread(&config_path)
+        .args(["-p", "mastodon", "--schedule", "2h", "--label", "essay"])
+        .write_stdin(THREAD)
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("scheduled:"));
+
+    let mut scheduled = db.get_scheduled_posts().await.unwrap();
+    scheduled.sort_by_key(|p| p.scheduled_at);
+    let contents: Vec<&str> = scheduled.iter().map(|p| p.content.as_str()).collect();
+    assert_eq!(
+        contents,
+        vec![
+            "Why plain text? 1/3",
+            "It outlives apps. 2/3",
+            "And grep. 3/3"
+        ]
+    );
+
+    for pair in scheduled.windows(2) {
+        assert_eq!(
+            pair[1].scheduled_at.unwrap() - pair[0].scheduled_at.unwrap(),
+            60
+        );
+        let metadata: serde_json::Value =
+            serde_json::from_str(pair[1].metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata["thread_parent_uuid"], pair[0].id.as_str());
+    }
+    assert_eq!(
+        db.get_labels(&scheduled[0].id).await.unwrap(),
+        vec!["essay"]
+    );
+}
+
+#[tokio::test]
+async fn test_dry_run_previews_numbered_parts() {
+    let (temp_dir, db, config_path) = setup().await;
+    let file = temp_dir.path().join("thread.md");
+    std::fs::write(&file, THREAD.replace("---", "===")).unwrap();
+
+    let output = plur_thread(&config_path)
+        .arg(&file)
+        .args(["-p", "nostr", "--dry-run", "--format", "json"])
+        .args(["--separator", "===", "--numbering", "({n})"])
+        .args(["--number-position", "prefix"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let preview: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(preview["dry_run"], true);
+    assert_eq!(preview["parts"][0]["content"], "(1) Why plain text?");
+    assert_eq!(preview["parts"][2]["content"], "(3) And grep.");
+    assert_eq!(preview["parts"][2]["chars"], 13);
+    assert!(db.get_scheduled_posts().await.unwrap().is_empty());
+
+    plur_thread(&config_path)
+        .arg(&file)
+        .args(["-p", "nostr", "--dry-run", "--no-numbering"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[1/1]"));
+}
+
+#[tokio::test]
+async fn test_invalid_threads_exit_with_invalid_input() {
+    let (temp_dir, _db, config_path) = setup().await;
+
+    // Nothing but separators
+    plur_thread(&config_path)
+        .args(["-p", "nostr"])
+        .write_stdin("---\n---\n")
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("empty"));
+
+    // A part over Mastodon's limit stops the whole thread
+    let long = format!("Short\n---\n{}\n", "x".repeat(600));
+    plur_thread(&config_path)
+        .args(["-p", "mastodon", "--schedule", "1h"])
+        .write_stdin(long)
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("part 2 on mastodon"));
+
+    // No platforms given or configured
+    plur_thread(&config_path)
+        .write_stdin(THREAD)
+        .assert()
+        .code(3);
+
+    // Numbering without {n}
+    plur_thread(&config_path)
+        .args(["-p", "nostr", "--numbering", "{total}"])
+        .write_stdin(THREAD)
+        .assert()
+        .code(3);
+
+    // A file that isn't there
+    plur_thread(&config_path)
+        .arg(temp_dir.path().join("missing.md"))
+        .args(["-p", "nostr"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Failed to read"));
+}
@@ -0,0 +1,233 @@
+//! plur-timeline - Read your home timeline and mentions
+//!
+//! Unix-style tool: prints recent posts from the accounts you follow, or
+//! posts mentioning you, from Nostr and Mastodon, one per line, as JSON,
+//! or as NDJSON for further processing.
+
+use clap::Parser;
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use libplurcast::platforms::{TimelineEntry, TimelineKind};
+use libplurcast::service::timeline::{TimelineRequest, DEFAULT_LIMIT};
+use libplurcast::service::PlurcastService;
+use libplurcast::{Config, PlurcastError, Result};
+
+#[derive(Parser, Debug)]
+#[command(name = "plur-timeline")]
+#[command(version)]
+#[command(about = "Read your home timeline and mentions")]
+#[command(long_about = "\
+plur-timeline - Read your home timeline and mentions
+
+DESCRIPTION:
+    plur-timeline prints recent posts from the accounts you follow on every
+    enabled platform, newest first. With --mentions it prints posts that
+    mention you instead, replies to your posts included.
+
+    Mastodon reads the home timeline and mention notifications of the
+    account. Nostr reads notes by the keys in your contact list (NIP-02),
+    or notes tagging your key, from the configured relays. SSB has no
+    timeline to read.
+
+    A read-only Mastodon token is used when the account has one
+    (plur-creds login mastodon --scope read).
+
+USAGE EXAMPLES:
+    # Latest posts from people you follow
+    plur-timeline
+
+    # Who mentioned you on Mastodon?
+    plur-timeline --mentions --platform mastodon
+
+    # Mentions of a project, as NDJSON for jq
+    plur-timeline --mentions --format ndjson | jq -r 'select(.content | test(\"plurcast\")) | .url'
+
+    # Or plain text for grep
+    plur-timeline --mentions | grep -i plurcast
+
+EXIT CODES:
+    0 - Success
+    1 - A platform's timeline couldn't be read
+    2 - Authentication error
+    3 - Invalid input (no platform with a timeline, etc.)
+
+For more information, visit: https://github.com/plurcast/plurcast
+")]
+struct Cli {
+    /// Show posts mentioning you instead of the home timeline
+    #[arg(short, long)]
+    mentions: bool,
+
+    /// Only read these platforms (comma-separated)
+    #[arg(short, long, value_name = "PLATFORM", value_delimiter = ',')]
+    platform: Option<Vec<String>>,
+
+    /// Account to read as (default: the active account)
+    #[arg(long, value_name = "ACCOUNT")]
+    account: Option<String>,
+
+    /// Most posts to show
+    #[arg(short = 'n', long, default_value_t = DEFAULT_LIMIT as u64, value_name = "N")]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    limit: u64,
+
+    /// Output format: text (one post per line), json, or ndjson
+    #[arg(short, long, default_value = "text")]
+    #[arg(value_parser = ["text", "json", "ndjson"])]
+    format: String,
+
+    /// Verbose output
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT"
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL"
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    match run(cli).await {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
+
+/// Print the timeline; returns whether every platform could be read
+async fn run(cli: Cli) -> Result<bool> {
+    let config = Config::load()?;
+    let service = PlurcastService::from_config(config).await?;
+
+    let request = TimelineRequest {
+        kind: if cli.mentions {
+            TimelineKind::Mentions
+        } else {
+            TimelineKind::Home
+        },
+        platforms: cli.platform.clone(),
+        account: cli.account.clone(),
+        limit: cli.limit as usize,
+    };
+    let timeline = service.timeline().fetch(&request).await?;
+
+    if timeline.platforms.is_empty() && timeline.failures.is_empty() {
+        return Err(PlurcastError::InvalidInput(
+            "No enabled platform has a timeline to read. Enable Nostr or Mastodon in the config"
+                .to_string(),
+        ));
+    }
+    for failure in &timeline.failures {
+        eprintln!(
+            "Warning: could not read {}: {}",
+            failure.platform, failure.error
+        );
+    }
+
+    match cli.format.as_str() {
+        "json" => println!(
+            "{}",
+            serde_json::to_string_pretty(&timeline.entries).unwrap_or_default()
+        ),
+        "ndjson" => {
+            for entry in &timeline.entries {
+                println!("{}", serde_json::to_string(entry).unwrap_or_default());
+            }
+        }
+        _ => {
+            for entry in &timeline.entries {
+                println!("{}", format_line(entry));
+            }
+        }
+    }
+
+    Ok(timeline.failures.is_empty())
+}
+
+/// One entry on one line, so it can be grepped
+///
+/// `2024-03-01 12:00:00 | mastodon | bob@example.social | [CW: ...] text`
+fn format_line(entry: &TimelineEntry) -> String {
+    let timestamp = chrono::DateTime::from_timestamp(entry.created_at, 0)
+        .unwrap_or_else(chrono::Utc::now)
+        .format("%Y-%m-%d %H:%M:%S");
+    let content = entry
+        .content
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let content = match entry.content_warning {
+        Some(ref cw) => format!("[CW: {}] {}", cw, content),
+        None => content,
+    };
+
+    format!(
+        "{} | {} | {} | {}",
+        timestamp, entry.platform, entry.author, content
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_line() {
+        let mut entry = TimelineEntry {
+            platform: "mastodon".to_string(),
+            kind: TimelineKind::Mentions,
+            id: "110".to_string(),
+            author: "bob@example.social".to_string(),
+            author_name: Some("Bob".to_string()),
+            content: "@alice Nice post!\n\nAgreed.".to_string(),
+            content_warning: None,
+            created_at: 1_709_294_400,
+            url: None,
+            reply_to: Some("109".to_string()),
+        };
+        assert_eq!(
+            format_line(&entry),
+            "2024-03-01 12:00:00 | mastodon | bob@example.social | @alice Nice post! Agreed."
+        );
+
+        entry.content_warning = Some("spoilers".to_string());
+        assert!(format_line(&entry).ends_with("| [CW: spoilers] @alice Nice post! Agreed."));
+    }
+}
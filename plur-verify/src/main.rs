@@ -0,0 +1,323 @@
+//! plur-verify - Check your posts are still there
+//!
+//! Unix-style tool: looks the published copies of your posts up on their
+//! platforms (the note on the relays, the status on the instance), records
+//! which can no longer be found, and reports what changed since the last
+//! check.
+
+use clap::Parser;
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use libplurcast::service::history::{self, HistoryQuery};
+use libplurcast::service::verify::{CopyCheck, VerifyReport};
+use libplurcast::service::PlurcastService;
+use libplurcast::{Config, PostCheck, Result};
+use tracing::info;
+
+#[derive(Parser, Debug)]
+#[command(name = "plur-verify")]
+#[command(version)]
+#[command(about = "Check your posts are still there")]
+#[command(long_about = "\
+plur-verify - Check your posts are still there
+
+DESCRIPTION:
+    plur-verify looks up the published copies of your posted posts, as the
+    account each was sent from, and records whether they were found:
+
+        Mastodon  the status on the instance (gone if it answers 404 or 410)
+        Nostr     the note on your relays (gone if no relay returns it, or
+                  you asked for it to be deleted)
+        SSB       not checked; feeds are append-only
+
+    Copies that went missing or turned up again since their previous check
+    are reported as drift. A post none of whose copies was found at two
+    checks in a row is marked deleted in history (reason \"vanished\").
+    Relays that don't answer are a lookup failure, not a missing note.
+
+    --since, --until, --platform, --label and --account pick the posts, as
+    in plur-history. --sample looks up only some copies, picked at random,
+    for regular spot checks of a large history. --missing lists the copies
+    missing at their latest check without looking anything up.
+
+USAGE EXAMPLES:
+    # Check everything posted this year
+    plur-verify --since 2026-01-01
+
+    # Spot-check 20 copies from cron
+    plur-verify --sample 20
+
+    # What is known to be gone
+    plur-verify --missing
+
+    # Drift as JSON
+    plur-verify --platform nostr --format json | jq '.checks[] | select(.drift)'
+
+EXIT CODES:
+    0 - Success: every copy checked was found
+    1 - Some copies are missing or couldn't be looked up
+    2 - Authentication error
+    3 - Invalid input (bad date, etc.)
+
+For more information, visit: https://github.com/plurcast/plurcast
+")]
+struct Cli {
+    /// Only check this platform
+    #[arg(short, long, value_name = "PLATFORM")]
+    #[arg(value_parser = ["nostr", "mastodon", "ssb"])]
+    platform: Option<String>,
+
+    /// Only posts created since this date (Unix timestamp, YYYY-MM-DD or
+    /// ISO 8601)
+    #[arg(long, value_name = "DATE")]
+    since: Option<String>,
+
+    /// Only posts created until this date (Unix timestamp, YYYY-MM-DD or
+    /// ISO 8601)
+    #[arg(long, value_name = "DATE")]
+    until: Option<String>,
+
+    /// Only posts carrying this label
+    #[arg(long, value_name = "LABEL")]
+    label: Option<String>,
+
+    /// Only posts sent from this account
+    #[arg(long, value_name = "ACCOUNT")]
+    account: Option<String>,
+
+    /// Only the N newest posts
+    #[arg(short = 'n', long, value_name = "N")]
+    limit: Option<usize>,
+
+    /// Look up only N copies, picked at random
+    #[arg(long, value_name = "N")]
+    sample: Option<usize>,
+
+    /// List the copies missing at their latest check, without checking
+    #[arg(long, conflicts_with_all = ["since", "until", "label", "account", "limit", "sample"])]
+    missing: bool,
+
+    /// Output format (text, json)
+    #[arg(short, long, default_value = "text", value_name = "FORMAT")]
+    #[arg(value_parser = ["text", "json"])]
+    format: String,
+
+    /// Verbose output
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT"
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL"
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    match run(cli).await {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
+
+/// Check the posts, or list what is missing; returns whether nothing is
+async fn run(cli: Cli) -> Result<bool> {
+    let query = HistoryQuery {
+        platform: cli.platform.clone(),
+        since: match cli.since {
+            Some(ref date) => Some(history::parse_date(date)?),
+            None => None,
+        },
+        until: match cli.until {
+            Some(ref date) => Some(history::parse_date(date)?),
+            None => None,
+        },
+        label: cli.label.clone(),
+        account: cli.account.clone(),
+        limit: cli.limit,
+        ..Default::default()
+    };
+
+    let config = Config::load()?;
+    let service = PlurcastService::from_config(config).await?;
+
+    if cli.missing {
+        let missing = service.verify().missing(cli.platform.as_deref()).await?;
+        output_missing(&cli.format, &missing);
+        return Ok(missing.is_empty());
+    }
+
+    let report = service.verify().verify(&query, cli.sample).await?;
+    info!(
+        "Looked up {} copies, {} missing",
+        report.checks.len(),
+        report.missing().count()
+    );
+    output_report(&cli.format, &report);
+
+    Ok(report.missing().next().is_none() && report.failures.is_empty())
+}
+
+/// What changed for a copy since its previous check, if anything
+fn drift(check: &CopyCheck) -> Option<&'static str> {
+    if check.vanished() {
+        Some("vanished")
+    } else if check.reappeared() {
+        Some("reappeared")
+    } else {
+        None
+    }
+}
+
+/// Print the outcome of a check
+fn output_report(format: &str, report: &VerifyReport) {
+    if format == "json" {
+        let checks: Vec<serde_json::Value> = report
+            .checks
+            .iter()
+            .map(|c| {
+                serde_json::json!({
+                    "post_id": c.check.post_id,
+                    "platform": c.check.platform,
+                    "platform_post_id": c.check.platform_post_id,
+                    "present": c.check.present,
+                    "missing_since": c.check.missing_since,
+                    "drift": drift(c),
+                })
+            })
+            .collect();
+        let failures: Vec<serde_json::Value> = report
+            .failures
+            .iter()
+            .map(|f| {
+                serde_json::json!({
+                    "platform": f.platform,
+                    "platform_post_id": f.platform_post_id,
+                    "error": f.error,
+                })
+            })
+            .collect();
+        let output = serde_json::json!({
+            "checked": report.checks.len(),
+            "missing": report.missing().count(),
+            "drift": report.drift().count(),
+            "checks": checks,
+            "failures": failures,
+            "marked_deleted": report.marked_deleted,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output).unwrap_or_default()
+        );
+        return;
+    }
+
+    println!(
+        "Checked {} copies: {} found, {} missing, {} changed since the last check",
+        report.checks.len(),
+        report.checks.len() - report.missing().count(),
+        report.missing().count(),
+        report.drift().count()
+    );
+    for check in report
+        .checks
+        .iter()
+        .filter(|c| !c.check.present || c.reappeared())
+    {
+        println!(
+            "{}",
+            check_line(&check.check, drift(check).unwrap_or("missing"))
+        );
+    }
+    for post_id in &report.marked_deleted {
+        println!("Marked deleted (gone everywhere): {}", post_id);
+    }
+    for failure in &report.failures {
+        eprintln!(
+            "Warning: could not look up {} {}: {}",
+            failure.platform, failure.platform_post_id, failure.error
+        );
+    }
+}
+
+/// Print the copies missing at their latest check
+fn output_missing(format: &str, missing: &[PostCheck]) {
+    if format == "json" {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&missing).unwrap_or_default()
+        );
+        return;
+    }
+
+    if missing.is_empty() {
+        println!("No copies are known to be missing");
+    }
+    for check in missing {
+        println!("{}", check_line(check, "missing"));
+    }
+}
+
+/// One line about a copy: state, platform, ID, post and timing
+fn check_line(check: &PostCheck, state: &str) -> String {
+    let since = match check.missing_since {
+        Some(since) => format!(", missing since {}", format_time(since)),
+        None => String::new(),
+    };
+    format!(
+        "{:<10} {:<8} {} (post {}{})",
+        state,
+        check.platform,
+        check.platform_post_id,
+        short_id(&check.post_id),
+        since
+    )
+}
+
+fn short_id(post_id: &str) -> &str {
+    post_id.get(..8).unwrap_or(post_id)
+}
+
+fn format_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .unwrap_or_else(chrono::Utc::now)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
@@ -0,0 +1,141 @@
+//! Integration tests for plur-verify
+
+use assert_cmd::Command;
+use libplurcast::{Database, Post, PostCheck, PostRecord, PostStatus};
+use predicates::prelude::*;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Write a config file pointing at `db_path`, with no platforms enabled
+fn write_config(dir: &Path, db_path: &Path) -> PathBuf {
+    let config_path = dir.join("config.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "[database]\npath = \"{}\"\n\n[defaults]\nplatforms = []\n",
+            db_path.to_string_lossy().replace('\\', "/")
+        ),
+    )
+    .unwrap();
+    config_path
+}
+
+/// Create a posted Mastodon post
+async fn create_post(db: &Database, platform_post_id: &str) -> String {
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content: "Out there".to_string(),
+        created_at: 1_700_000_000,
+        scheduled_at: None,
+        status: PostStatus::Posted,
+        metadata: None,
+    };
+    db.create_post(&post).await.unwrap();
+    db.create_post_record(&PostRecord {
+        id: None,
+        post_id: post.id.clone(),
+        platform: "mastodon".to_string(),
+        platform_post_id: Some(platform_post_id.to_string()),
+        posted_at: Some(post.created_at),
+        success: true,
+        error_message: None,
+        account_name: "default".to_string(),
+    })
+    .await
+    .unwrap();
+    post.id
+}
+
+async fn setup() -> (TempDir, Database, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("posts.db");
+    let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+    let config_path = write_config(temp_dir.path(), &db_path);
+    (temp_dir, db, config_path)
+}
+
+fn plur_verify(config_path: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("plur-verify").unwrap();
+    cmd.env("PLURCAST_CONFIG", config_path)
+        .env_remove("PLURCAST_DB_PATH");
+    cmd
+}
+
+#[tokio::test]
+async fn test_missing_lists_recorded_checks() {
+    let (_temp_dir, db, config_path) = setup().await;
+
+    plur_verify(&config_path)
+        .arg("--missing")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "No copies are known to be missing",
+        ));
+
+    let kept = create_post(&db, "110").await;
+    let gone = create_post(&db, "120").await;
+    for (post_id, platform_post_id, present) in [(&kept, "110", true), (&gone, "120", false)] {
+        db.save_post_check(&PostCheck {
+            post_id: post_id.clone(),
+            platform: "mastodon".to_string(),
+            platform_post_id: platform_post_id.to_string(),
+            present,
+            checked_at: 1_700_100_000,
+            missing_since: (!present).then_some(1_700_100_000),
+        })
+        .await
+        .unwrap();
+    }
+
+    let output = plur_verify(&config_path)
+        .args(["--missing", "--format", "json"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let missing: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let missing = missing.as_array().unwrap();
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0]["post_id"], gone.as_str());
+    assert_eq!(missing[0]["platform_post_id"], "120");
+
+    // Other platforms have nothing missing
+    plur_verify(&config_path)
+        .args(["--missing", "--platform", "nostr"])
+        .assert()
+        .success();
+}
+
+#[tokio::test]
+async fn test_platforms_not_enabled_are_skipped() {
+    let (_temp_dir, db, config_path) = setup().await;
+    let post = create_post(&db, "110").await;
+
+    let output = plur_verify(&config_path)
+        .args(["--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["checked"], 0);
+    assert_eq!(report["missing"], 0);
+    assert!(report["marked_deleted"].as_array().unwrap().is_empty());
+    assert!(db.get_post_checks(&post).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_invalid_input() {
+    let (_temp_dir, _db, config_path) = setup().await;
+
+    plur_verify(&config_path)
+        .args(["--since", "last tuesday"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Invalid date format"));
+
+    // --missing doesn't look anything up, so it takes no post filters
+    plur_verify(&config_path)
+        .args(["--missing", "--sample", "5"])
+        .assert()
+        .failure();
+}
@@ -0,0 +1,441 @@
+//! plur-watch - Post files dropped into a directory
+//!
+//! Unix-style tool: watches a directory and posts every markdown or text
+//! file that appears in it, with optional frontmatter choosing platforms,
+//! schedule, labels, content warning and account, then moves the file to
+//! `sent/` (or `failed/`). Runs until interrupted, or once with --once.
+
+use clap::Parser;
+use libplurcast::logging::{LogFormat, LoggingConfig};
+use libplurcast::service::posting::{PostRequest, PostResponse};
+use libplurcast::service::validation::ValidationRequest;
+use libplurcast::service::PlurcastService;
+use libplurcast::{frontmatter, scheduling, Config, PlurcastError, Result, Visibility};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::info;
+
+mod outbox;
+
+#[derive(Parser, Debug)]
+#[command(name = "plur-watch")]
+#[command(version)]
+#[command(about = "Post files dropped into a directory")]
+#[command(long_about = "\
+plur-watch - Post files dropped into a directory
+
+DESCRIPTION:
+    plur-watch watches DIR and posts every markdown or text file (.md,
+    .markdown, .txt) that appears in it, in file name order. A posted or
+    scheduled file is moved to DIR/sent/; a file that couldn't be posted is
+    moved to DIR/failed/ so it isn't tried again. Move it back to retry.
+
+    Files are picked up once they have gone unchanged for --settle seconds,
+    so a file that is still being written isn't posted half-finished.
+    Hidden files and subdirectories are ignored.
+
+FRONTMATTER:
+    As with plur-post --from-file, a file may start with YAML (---) or TOML
+    (+++) frontmatter setting the post's options:
+
+        ---
+        platforms: [nostr, mastodon]
+        schedule: \"tomorrow 9am\"
+        tags: [campaign:launch]
+        cw: Spoilers
+        account: work
+        ---
+        The post body.
+
+    A file with a schedule is added to the queue for plur-send. Files
+    without platforms go to --platform, or the config's default platforms.
+
+USAGE EXAMPLES:
+    # Post whatever lands in ~/outbox
+    plur-watch ~/outbox
+
+    # From a script or note app: write the file, plur-watch does the rest
+    echo \"Hello from cron\" > ~/outbox/$(date +%s).txt
+
+    # Post what's there now and exit (e.g. from cron)
+    plur-watch ~/outbox --once
+
+    # One JSON line per file, for logs
+    plur-watch ~/outbox --format json
+
+EXIT CODES:
+    0 - Success
+    1 - A file couldn't be posted (it is moved to failed/)
+    2 - Authentication error
+    3 - Invalid input (DIR doesn't exist, etc.)
+
+For more information, visit: https://github.com/plurcast/plurcast
+")]
+struct Cli {
+    /// Directory to watch
+    #[arg(value_name = "DIR")]
+    dir: PathBuf,
+
+    /// Post the files in DIR now, then exit
+    #[arg(long)]
+    once: bool,
+
+    /// Seconds between checks of DIR
+    #[arg(long, default_value_t = 5, value_name = "SECONDS")]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    interval: u64,
+
+    /// Seconds a file must go unchanged before it is posted
+    #[arg(long, default_value_t = 2, value_name = "SECONDS")]
+    settle: u64,
+
+    /// Platforms for files whose frontmatter names none (comma-separated)
+    #[arg(short, long, value_name = "PLATFORM", value_delimiter = ',')]
+    #[arg(value_parser = ["nostr", "mastodon", "ssb"])]
+    platform: Vec<String>,
+
+    /// Account for files whose frontmatter names none
+    #[arg(long, value_name = "ACCOUNT")]
+    account: Option<String>,
+
+    /// Output format (text, json)
+    #[arg(short = 'f', long, default_value = "text", value_name = "FORMAT")]
+    #[arg(value_parser = ["text", "json"])]
+    format: String,
+
+    /// Verbose output
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Log format (text, json, pretty)
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        env = "PLURCAST_LOG_FORMAT"
+    )]
+    #[arg(
+        help = "Log output format: 'text' (default), 'json' (machine-parseable), or 'pretty' (colored for development)"
+    )]
+    log_format: String,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(
+        long,
+        default_value = "info",
+        value_name = "LEVEL",
+        env = "PLURCAST_LOG_LEVEL"
+    )]
+    #[arg(help = "Minimum log level to display (error, warn, info, debug, trace)")]
+    log_level: String,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    // Initialize logging with centralized configuration
+    let log_format = cli.log_format.parse::<LogFormat>().unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(3);
+    });
+
+    let log_level = if cli.verbose {
+        "debug".to_string()
+    } else {
+        cli.log_level.clone()
+    };
+
+    let logging_config = LoggingConfig::new(log_format, log_level, cli.verbose);
+    logging_config.init();
+
+    match run(cli).await {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
+
+/// Post the files in the directory once, or until interrupted
+///
+/// Returns whether every file was posted. A file that can't be moved out
+/// of the directory after posting is fatal, since it would otherwise be
+/// posted again on the next check.
+async fn run(cli: Cli) -> Result<bool> {
+    if !cli.dir.is_dir() {
+        return Err(PlurcastError::InvalidInput(format!(
+            "{} is not a directory",
+            cli.dir.display()
+        )));
+    }
+
+    let config = Config::load()?;
+    let service = PlurcastService::from_config(config.clone()).await?;
+
+    let mut ok = check(&cli, &config, &service).await?;
+    if cli.once {
+        return Ok(ok);
+    }
+
+    info!("Watching {} every {}s", cli.dir.display(), cli.interval);
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                info!("Interrupted, stopping");
+                return Ok(ok);
+            }
+            _ = tokio::time::sleep(Duration::from_secs(cli.interval)) => {}
+        }
+
+        ok &= check(&cli, &config, &service).await?;
+    }
+}
+
+/// Post every settled file in the directory and move it out of the way
+async fn check(cli: &Cli, config: &Config, service: &PlurcastService) -> Result<bool> {
+    let files = outbox::pending(&cli.dir, Duration::from_secs(cli.settle), SystemTime::now())
+        .map_err(|e| {
+            PlurcastError::InvalidInput(format!("Failed to read {}: {}", cli.dir.display(), e))
+        })?;
+
+    let mut ok = true;
+    for file in files {
+        let name = file
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+        let (outcome, dir) = match post_file(cli, config, service, &file).await {
+            Ok(response) if response.overall_success => {
+                (Ok(response), cli.dir.join(outbox::SENT_DIR))
+            }
+            Ok(response) => (Ok(response), cli.dir.join(outbox::FAILED_DIR)),
+            Err(e) => (Err(e), cli.dir.join(outbox::FAILED_DIR)),
+        };
+
+        let moved_to = outbox::move_into(&file, &dir).map_err(|e| {
+            PlurcastError::InvalidInput(format!(
+                "Failed to move {} to {}: {}",
+                file.display(),
+                dir.display(),
+                e
+            ))
+        })?;
+
+        ok &= outcome.as_ref().is_ok_and(|r| r.overall_success);
+        report(&cli.format, &name, &outcome, &moved_to);
+    }
+
+    Ok(ok)
+}
+
+/// Post or schedule one file
+async fn post_file(
+    cli: &Cli,
+    config: &Config,
+    service: &PlurcastService,
+    file: &Path,
+) -> Result<PostResponse> {
+    let text = std::fs::read_to_string(file).map_err(|e| {
+        PlurcastError::InvalidInput(format!("Failed to read {}: {}", file.display(), e))
+    })?;
+    let (fm, body) = frontmatter::parse(&text)?;
+
+    let content = body.trim().to_string();
+    if content.is_empty() {
+        return Err(PlurcastError::InvalidInput(
+            "The file has no post text".to_string(),
+        ));
+    }
+
+    let platforms = platforms_for(&fm.platforms, &cli.platform, &config.defaults.platforms)?;
+
+    let scheduled_at = match fm.schedule {
+        Some(ref schedule) => {
+            let last_scheduled = service.database().get_last_scheduled_timestamp().await?;
+            Some(scheduling::parse_schedule(schedule, last_scheduled)?.timestamp())
+        }
+        None => None,
+    };
+
+    let validation = service.validation().validate(ValidationRequest {
+        content: content.clone(),
+        platforms: platforms.clone(),
+        auto_thread: false,
+        visibility: Visibility::Public,
+    });
+    if !validation.valid {
+        let errors: Vec<String> = validation
+            .results
+            .iter()
+            .flat_map(|r| r.errors.iter().cloned())
+            .collect();
+        return Err(PlurcastError::InvalidInput(format!(
+            "Content validation failed: {}",
+            errors.join("; ")
+        )));
+    }
+
+    info!("Posting {} to {}", file.display(), platforms.join(", "));
+    service
+        .posting()
+        .post(PostRequest {
+            content,
+            platforms,
+            draft: false,
+            account: fm.account.or_else(|| cli.account.clone()),
+            scheduled_at,
+            nostr_pow: None,
+            nostr_21e8: false,
+            reply_to: Default::default(),
+            thread_parent_uuid: None,
+            thread_sequence: None,
+            labels: fm.tags,
+            content_warning: fm.cw,
+            visibility: Visibility::Public,
+            language: config.defaults.language.clone(),
+            hashtags: vec![],
+            expires_in: None,
+            poll: None,
+            fail_fast: false,
+            media: vec![],
+        })
+        .await
+}
+
+/// Platforms for a file: its frontmatter, else `--platform`, else the
+/// config's defaults
+fn platforms_for(
+    frontmatter: &[String],
+    flags: &[String],
+    defaults: &[String],
+) -> Result<Vec<String>> {
+    let mut platforms = Vec::new();
+    for platform in frontmatter {
+        let platform = platform.to_lowercase();
+        if !["nostr", "mastodon", "ssb"].contains(&platform.as_str()) {
+            return Err(PlurcastError::InvalidInput(format!(
+                "Invalid platform '{}' in frontmatter (expected nostr, mastodon or ssb)",
+                platform
+            )));
+        }
+        if !platforms.contains(&platform) {
+            platforms.push(platform);
+        }
+    }
+
+    if platforms.is_empty() {
+        platforms = if flags.is_empty() {
+            defaults.to_vec()
+        } else {
+            flags.to_vec()
+        };
+    }
+    if platforms.is_empty() {
+        return Err(PlurcastError::InvalidInput(
+            "No platforms: set them in the frontmatter, with --platform, or as [defaults] platforms in the config".to_string(),
+        ));
+    }
+
+    Ok(platforms)
+}
+
+/// Print what became of a file
+fn report(format: &str, name: &str, outcome: &Result<PostResponse>, moved_to: &Path) {
+    if format == "json" {
+        let mut json = serde_json::json!({
+            "file": name,
+            "moved_to": moved_to.display().to_string(),
+        });
+        match outcome {
+            Ok(response) => {
+                json["post_id"] = serde_json::json!(response.post_id);
+                let status = if response.results.is_empty() {
+                    "scheduled"
+                } else if response.overall_success {
+                    "posted"
+                } else {
+                    "failed"
+                };
+                json["status"] = serde_json::json!(status);
+                json["results"] = response
+                    .results
+                    .iter()
+                    .map(|r| {
+                        serde_json::json!({
+                            "platform": r.platform,
+                            "success": r.success,
+                            "post_id": r.post_id,
+                            "error": r.error,
+                        })
+                    })
+                    .collect();
+            }
+            Err(e) => {
+                json["status"] = serde_json::json!("failed");
+                json["error"] = serde_json::json!(e.to_string());
+            }
+        }
+        println!("{}", json);
+        return;
+    }
+
+    match outcome {
+        Ok(response) if response.results.is_empty() => {
+            println!("{}: scheduled {}", name, response.post_id)
+        }
+        Ok(response) => {
+            for result in &response.results {
+                match (&result.post_id, &result.error) {
+                    (Some(post_id), _) if result.success => {
+                        println!("{}: {}:{}", name, result.platform, post_id)
+                    }
+                    (_, Some(error)) => {
+                        eprintln!("Error [{}] {}: {}", result.platform, name, error)
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Err(e) => eprintln!("Error: {}: {}", name, e),
+    }
+    if !outcome.as_ref().is_ok_and(|r| r.overall_success) {
+        eprintln!("Moved {} to {}", name, moved_to.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_platforms_for() {
+        let defaults = strings(&["nostr"]);
+
+        assert_eq!(
+            platforms_for(&strings(&["Mastodon", "ssb", "mastodon"]), &[], &defaults).unwrap(),
+            vec!["mastodon", "ssb"]
+        );
+        assert_eq!(
+            platforms_for(&[], &strings(&["ssb"]), &defaults).unwrap(),
+            vec!["ssb"]
+        );
+        assert_eq!(platforms_for(&[], &[], &defaults).unwrap(), vec!["nostr"]);
+
+        assert!(matches!(
+            platforms_for(&[], &[], &[]),
+            Err(PlurcastError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            platforms_for(&strings(&["twitter"]), &[], &defaults),
+            Err(PlurcastError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,147 @@
+//! The watched directory
+//!
+//! Post files are markdown or text files directly in the directory; hidden
+//! files (editor swap files and the like) and anything in subdirectories,
+//! including `sent/` and `failed/`, are left alone. A file is only picked
+//! up once it has gone unchanged for a moment, so one that is still being
+//! written isn't posted half-finished.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Subdirectory posted and scheduled files are moved to
+pub const SENT_DIR: &str = "sent";
+
+/// Subdirectory files that couldn't be posted are moved to
+pub const FAILED_DIR: &str = "failed";
+
+/// Extensions of post files, compared case-insensitively
+const POST_EXTENSIONS: &[&str] = &["md", "markdown", "txt"];
+
+/// Post files in `dir` unchanged for at least `settle`, in file name order
+pub fn pending(dir: &Path, settle: Duration, now: SystemTime) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if !is_post_file(&path) {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+        let settled = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_none_or(|age| age >= settle);
+        if settled {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Whether the path names a post file
+fn is_post_file(path: &Path) -> bool {
+    let hidden = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_none_or(|name| name.starts_with('.'));
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_lowercase);
+    !hidden && extension.is_some_and(|ext| POST_EXTENSIONS.contains(&ext.as_str()))
+}
+
+/// Move `file` into `dir`, creating it if needed
+///
+/// A file already there under the same name is kept; the moved file gets
+/// a numbered name instead (`post-1.md`, `post-2.md`, ...). Returns the
+/// new path.
+pub fn move_into(file: &Path, dir: &Path) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+
+    let name = file.file_name().unwrap_or_default();
+    let mut target = dir.join(name);
+    let stem = file
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    let extension = file
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    let mut n = 1;
+    while target.exists() {
+        target = dir.join(format!("{}-{}{}", stem, n, extension));
+        n += 1;
+    }
+
+    std::fs::rename(file, &target)?;
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pending_picks_settled_post_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        for name in [
+            "b.md",
+            "a.TXT",
+            "c.markdown",
+            "notes.pdf",
+            ".a.md.swp",
+            ".hidden.md",
+        ] {
+            std::fs::write(dir.join(name), "Post").unwrap();
+        }
+        std::fs::create_dir(dir.join("sent")).unwrap();
+        std::fs::write(dir.join("sent").join("old.md"), "Old").unwrap();
+        std::fs::create_dir(dir.join("folder.md")).unwrap();
+
+        let now = SystemTime::now() + Duration::from_secs(10);
+        let files = pending(dir, Duration::from_secs(2), now).unwrap();
+        let names: Vec<_> = files
+            .iter()
+            .map(|f| f.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["a.TXT", "b.md", "c.markdown"]);
+
+        // Just written: still settling
+        let files = pending(dir, Duration::from_secs(60), SystemTime::now()).unwrap();
+        assert!(files.is_empty());
+    }
+
+    #[test]
+    fn test_move_into_keeps_existing_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let sent = temp_dir.path().join(SENT_DIR);
+
+        let mut moved = Vec::new();
+        for _ in 0..3 {
+            let file = temp_dir.path().join("post.md");
+            std::fs::write(&file, "Post").unwrap();
+            moved.push(move_into(&file, &sent).unwrap());
+            assert!(!file.exists());
+        }
+
+        assert_eq!(
+            moved,
+            vec![
+                sent.join("post.md"),
+                sent.join("post-1.md"),
+                sent.join("post-2.md")
+            ]
+        );
+    }
+}
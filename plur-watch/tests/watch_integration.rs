@@ -0,0 +1,120 @@
+//! Integration tests for plur-watch
+
+use assert_cmd::Command;
+use libplurcast::{Database, PostStatus};
+use predicates::prelude::*;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Write a config file pointing at `db_path`, with no platforms enabled
+fn write_config(dir: &Path, db_path: &Path) -> PathBuf {
+    let config_path = dir.join("config.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "[database]\npath = \"{}\"\n\n[defaults]\nplatforms = []\n",
+            db_path.to_string_lossy().replace('\\', "/")
+        ),
+    )
+    .unwrap();
+    config_path
+}
+
+/// A config, its database and an empty outbox directory
+async fn setup() -> (TempDir, Database, PathBuf, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("posts.db");
+    let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+    let config_path = write_config(temp_dir.path(), &db_path);
+    let outbox = temp_dir.path().join("outbox");
+    std::fs::create_dir(&outbox).unwrap();
+    (temp_dir, db, config_path, outbox)
+}
+
+fn plur_watch(config_path: &Path, outbox: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("plur-watch").unwrap();
+    cmd.env("PLURCAST_CONFIG", config_path)
+        .env_remove("PLURCAST_DB_PATH")
+        .arg(outbox)
+        .args(["--once", "--settle", "0"]);
+    cmd
+}
+
+#[tokio::test]
+async fn test_scheduled_file_is_queued_and_moved_to_sent() {
+    let (_temp_dir, db, config_path, outbox) = setup().await;
+    std::fs::write(
+        outbox.join("launch.md"),
+        "---\nplatforms: [mastodon]\nschedule: 2h\ntags: [campaign:launch]\ncw: Spoilers\n---\nLaunch day!\n",
+    )
+    .unwrap();
+    std::fs::write(outbox.join("notes.pdf"), "Not a post").unwrap();
+
+    plur_watch(&config_path, &outbox)
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("launch.md: scheduled "));
+
+    assert!(!outbox.join("launch.md").exists());
+    assert!(outbox.join("sent").join("launch.md").exists());
+    assert!(outbox.join("notes.pdf").exists());
+
+    let scheduled = db.get_scheduled_posts().await.unwrap();
+    assert_eq!(scheduled.len(), 1);
+    let post = &scheduled[0];
+    assert_eq!(post.content, "Launch day!");
+    assert_eq!(post.status, PostStatus::Scheduled);
+    assert!(post.metadata.as_deref().unwrap().contains("Spoilers"));
+    assert_eq!(
+        db.get_labels(&post.id).await.unwrap(),
+        vec!["campaign:launch"]
+    );
+}
+
+#[tokio::test]
+async fn test_unpostable_files_are_moved_to_failed() {
+    let (_temp_dir, db, config_path, outbox) = setup().await;
+    // No platforms anywhere: not in the frontmatter, flags or config
+    std::fs::write(outbox.join("a.txt"), "Nowhere to go").unwrap();
+    std::fs::write(outbox.join("b.md"), "---\nplatforms: [nostr]\nBody").unwrap();
+
+    let output = plur_watch(&config_path, &outbox)
+        .args(["--format", "json"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+
+    let lines: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["file"], "a.txt");
+    assert_eq!(lines[0]["status"], "failed");
+    assert!(lines[0]["error"].as_str().unwrap().contains("No platforms"));
+    assert_eq!(lines[1]["file"], "b.md");
+    assert!(lines[1]["error"]
+        .as_str()
+        .unwrap()
+        .contains("Frontmatter is not closed"));
+
+    assert!(outbox.join("failed").join("a.txt").exists());
+    assert!(outbox.join("failed").join("b.md").exists());
+    assert!(db.get_scheduled_posts().await.unwrap().is_empty());
+
+    // Nothing left to post on the next run
+    plur_watch(&config_path, &outbox)
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[tokio::test]
+async fn test_missing_directory() {
+    let (temp_dir, _db, config_path, _outbox) = setup().await;
+
+    plur_watch(&config_path, &temp_dir.path().join("nope"))
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("is not a directory"));
+}
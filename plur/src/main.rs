@@ -0,0 +1,195 @@
+//! plur - All the plurcast tools in one binary
+//!
+//! Runs the standalone tools as subcommands: `plur post` is plur-post,
+//! `plur queue` is plur-queue, and so on. Started under a tool's name (a
+//! `plur-post` link to it), it is that tool, so one binary can stand in
+//! for all of them.
+
+use clap::{Args, Parser, Subcommand};
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
+
+#[derive(Parser, Debug)]
+#[command(name = "plur")]
+#[command(version)]
+#[command(about = "All the plurcast tools in one binary")]
+#[command(long_about = "\
+plur - All the plurcast tools in one binary
+
+DESCRIPTION:
+    plur runs the plurcast tools as subcommands. Everything after the
+    subcommand goes to the tool, so `plur post --help` is plur-post's help
+    and `plur queue list` does what `plur-queue list` does.
+
+    Installed under a tool's name instead, plur is that tool: link it as
+    plur-post and `plur-post \"Hello\"` works as before. One binary linked
+    under every name replaces the separate tools:
+
+        for tool in post queue history creds send import export; do
+            ln -s plur plur-$tool
+        done
+
+USAGE EXAMPLES:
+    # Post to your default platforms
+    plur post \"Hello from plur\"
+
+    # What's scheduled
+    plur queue list
+
+    # Search your history
+    plur history --search rust --format json
+
+    # Run the scheduler in the background
+    plur send --daemon
+
+EXIT CODES:
+    The tool's own exit code; see `plur <TOOL> --help`.
+
+For more information, visit: https://github.com/plurcast/plurcast
+")]
+struct Cli {
+    #[command(subcommand)]
+    tool: Tool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Tool {
+    /// Post to all your platforms at once (plur-post)
+    #[command(disable_help_flag = true)]
+    Post(ToolArgs),
+
+    /// Manage scheduled posts (plur-queue)
+    #[command(disable_help_flag = true)]
+    Queue(ToolArgs),
+
+    /// Query your posting history (plur-history)
+    #[command(disable_help_flag = true)]
+    History(ToolArgs),
+
+    /// Manage platform credentials (plur-creds)
+    #[command(disable_help_flag = true)]
+    Creds(ToolArgs),
+
+    /// Send scheduled posts when they're due (plur-send)
+    #[command(disable_help_flag = true)]
+    Send(ToolArgs),
+
+    /// Import posts from platform exports and feeds (plur-import)
+    #[command(disable_help_flag = true)]
+    Import(ToolArgs),
+
+    /// Export your posts (plur-export)
+    #[command(disable_help_flag = true)]
+    Export(ToolArgs),
+}
+
+/// Everything after the subcommand, passed to the tool untouched
+#[derive(Args, Debug)]
+struct ToolArgs {
+    /// Arguments for the tool (see `plur <TOOL> --help`)
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<OsString>,
+}
+
+impl Tool {
+    /// The subcommand name and the arguments for the tool
+    fn into_parts(self) -> (&'static str, Vec<OsString>) {
+        match self {
+            Self::Post(a) => ("post", a.args),
+            Self::Queue(a) => ("queue", a.args),
+            Self::History(a) => ("history", a.args),
+            Self::Creds(a) => ("creds", a.args),
+            Self::Send(a) => ("send", a.args),
+            Self::Import(a) => ("import", a.args),
+            Self::Export(a) => ("export", a.args),
+        }
+    }
+}
+
+/// The subcommands, in the order `plur --help` lists them
+const TOOLS: &[&str] = &[
+    "post", "queue", "history", "creds", "send", "import", "export",
+];
+
+fn main() {
+    let args: Vec<OsString> = std::env::args_os().collect();
+
+    // Started as plur-post (or another tool's name): be that tool
+    if let Some(tool) = args.first().and_then(|arg0| tool_name(arg0)) {
+        run(tool, args);
+        return;
+    }
+
+    let (tool, tool_args) = Cli::parse().tool.into_parts();
+    // The tool names itself after its first argument in usage and errors
+    let mut args = vec![OsString::from(format!("plur {}", tool))];
+    args.extend(tool_args);
+    run(tool, args);
+}
+
+/// The tool a program name stands for: `plur-post` (or `plur-post.exe`)
+/// is `post`
+fn tool_name(program: &OsStr) -> Option<&'static str> {
+    let stem = Path::new(program).file_stem()?.to_str()?;
+    let name = stem.strip_prefix("plur-")?;
+    TOOLS.iter().copied().find(|tool| *tool == name)
+}
+
+/// Run a tool with its command line, program name first
+fn run(tool: &str, args: Vec<OsString>) {
+    match tool {
+        "post" => plur_post::main_from(args),
+        "queue" => plur_queue::main_from(args),
+        "history" => exit_on_error(plur_history::main_from(args)),
+        "creds" => exit_on_error(plur_creds::main_from(args)),
+        "send" => exit_on_error(plur_send::main_from(args)),
+        "import" => exit_on_error(plur_import::main_from(args)),
+        "export" => exit_on_error(plur_export::main_from(args)),
+        _ => unreachable!("unknown tool {}", tool),
+    }
+}
+
+/// Report a tool's error the way its own `main` would, and exit with 1
+fn exit_on_error<E: std::fmt::Debug>(result: Result<(), E>) {
+    if let Err(e) = result {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_tool_name() {
+        assert_eq!(tool_name(OsStr::new("plur-post")), Some("post"));
+        assert_eq!(tool_name(OsStr::new("/usr/bin/plur-send")), Some("send"));
+        assert_eq!(tool_name(OsStr::new("plur-queue.exe")), Some("queue"));
+        assert_eq!(tool_name(OsStr::new("plur")), None);
+        assert_eq!(tool_name(OsStr::new("plur-nope")), None);
+        // Tools plur doesn't carry still need their own binary
+        assert_eq!(tool_name(OsStr::new("plur-setup")), None);
+    }
+
+    #[test]
+    fn test_every_subcommand_is_a_tool() {
+        let names: Vec<String> = Cli::command()
+            .get_subcommands()
+            .map(|c| c.get_name().to_string())
+            .filter(|name| name != "help")
+            .collect();
+        assert_eq!(names, TOOLS);
+    }
+
+    #[test]
+    fn test_arguments_pass_through() {
+        let cli = Cli::try_parse_from(["plur", "post", "--help", "-p", "nostr", "hi"]).unwrap();
+        let (tool, args) = cli.tool.into_parts();
+        assert_eq!(tool, "post");
+        assert_eq!(args, ["--help", "-p", "nostr", "hi"]);
+
+        assert!(Cli::try_parse_from(["plur", "nope"]).is_err());
+    }
+}
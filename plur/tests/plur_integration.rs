@@ -0,0 +1,102 @@
+//! Integration tests for the plur multicall binary
+
+use assert_cmd::Command;
+use libplurcast::{Database, Post, PostStatus};
+use predicates::prelude::*;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Write a config file pointing at `db_path`, with no platforms enabled
+fn write_config(dir: &Path, db_path: &Path) -> PathBuf {
+    let config_path = dir.join("config.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "[database]\npath = \"{}\"\n\n[defaults]\nplatforms = []\n",
+            db_path.to_string_lossy().replace('\\', "/")
+        ),
+    )
+    .unwrap();
+    config_path
+}
+
+/// A database with one scheduled post, and a config pointing at it
+async fn setup() -> (TempDir, PathBuf, String) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("posts.db");
+    let db = Database::new(db_path.to_str().unwrap()).await.unwrap();
+    let now = chrono::Utc::now().timestamp();
+    let post = Post {
+        id: uuid::Uuid::new_v4().to_string(),
+        content: "Queued through plur".to_string(),
+        created_at: now,
+        scheduled_at: Some(now + 3600),
+        status: PostStatus::Scheduled,
+        metadata: None,
+    };
+    db.create_post(&post).await.unwrap();
+    let config_path = write_config(temp_dir.path(), &db_path);
+    (temp_dir, config_path, post.id)
+}
+
+fn with_config(mut cmd: Command, config_path: &Path) -> Command {
+    cmd.env("PLURCAST_CONFIG", config_path)
+        .env_remove("PLURCAST_DB_PATH");
+    cmd
+}
+
+#[tokio::test]
+async fn test_subcommand_runs_the_tool() {
+    let (_temp_dir, config_path, post_id) = setup().await;
+
+    let output = with_config(Command::cargo_bin("plur").unwrap(), &config_path)
+        .args(["queue", "list", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let queue: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(queue[0]["id"], post_id.as_str());
+}
+
+#[tokio::test]
+async fn test_tool_name_runs_the_tool() {
+    let (temp_dir, config_path, _post_id) = setup().await;
+
+    // The same binary under a tool's name is that tool
+    let linked = temp_dir
+        .path()
+        .join(format!("plur-queue{}", std::env::consts::EXE_SUFFIX));
+    std::fs::copy(assert_cmd::cargo::cargo_bin("plur"), &linked).unwrap();
+
+    with_config(Command::new(&linked), &config_path)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Queued through plur"));
+}
+
+#[test]
+fn test_help_lists_tools_and_passes_through() {
+    Command::cargo_bin("plur")
+        .unwrap()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("queue"))
+        .stdout(predicate::str::contains("export"));
+
+    // A tool's own help, under its plur name
+    Command::cargo_bin("plur")
+        .unwrap()
+        .args(["history", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("plur history"))
+        .stdout(predicate::str::contains("--search"));
+
+    Command::cargo_bin("plur")
+        .unwrap()
+        .arg("frobnicate")
+        .assert()
+        .failure();
+}